@@ -0,0 +1,55 @@
+use axum::{extract::State, http::HeaderMap, response::Json};
+use cross_chain::{
+    CrossChainTransferRequest, CrossChainTransferResponse, IdempotencyKeyConflict, TransferOutcomeUnknown,
+};
+
+use crate::{ApiError, ApiResponse, ApiState};
+
+/// Cross-chain token transfer endpoint
+/// POST /cross-chain/transfer
+/// Retries must reuse the request's `idempotency_key`; a replay returns the
+/// original message id rather than initiating a second transfer. Keys are scoped
+/// to the caller's `x-api-key`, so clients cannot collide on or replay each other's keys.
+
+/// POST /cross-chain/transfer
+pub async fn initiate_cross_chain_transfer(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Json(request): Json<CrossChainTransferRequest>,
+) -> Result<Json<ApiResponse<CrossChainTransferResponse>>, ApiError> {
+    let caller = headers.get("x-api-key")
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .ok_or(ApiError::MissingApiKey)?;
+    validate_transfer(&request)?;
+    let cross_chain = state.cross_chain.as_ref()
+        .ok_or_else(|| anyhow::anyhow!("Cross-chain transfers are not configured"))?;
+
+    let response = cross_chain.initiate_transfer(caller, request).await.map_err(|e| {
+        if let Some(conflict) = e.downcast_ref::<IdempotencyKeyConflict>() {
+            return ApiError::IdempotencyKeyConflict(conflict.idempotency_key.clone());
+        }
+        if let Some(unknown) = e.downcast_ref::<TransferOutcomeUnknown>() {
+            return ApiError::TransferOutcomeUnknown(unknown.idempotency_key.clone());
+        }
+        ApiError::Internal(e)
+    })?;
+    Ok(Json(ApiResponse::success(response)))
+}
+
+fn validate_transfer(request: &CrossChainTransferRequest) -> Result<(), ApiError> {
+    if request.idempotency_key.trim().is_empty() {
+        return Err(ApiError::invalid_field("idempotency_key", "must not be empty"));
+    }
+    if request.amount == 0 {
+        return Err(ApiError::invalid_field("amount", "must be greater than zero"));
+    }
+    if request.receiver.trim().is_empty() {
+        return Err(ApiError::invalid_field("receiver", "must not be empty"));
+    }
+    if request.source_chain == request.destination_chain {
+        return Err(ApiError::invalid_field("destination_chain", "must differ from source_chain"));
+    }
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use axum::{
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use tracing::error;
+
+use crate::{ApiResponse, RateLimitExceeded};
+
+/// Errors returned by API handlers, rendered as an `ApiResponse` error envelope
+#[derive(Debug, thiserror::Error)]
+pub enum ApiError {
+    #[error("Invalid field `{field}`: {reason}")]
+    InvalidField { field: &'static str, reason: String },
+    #[error("Vault {0} not found")]
+    VaultNotFound(String),
+    #[error("Fund {0} not found")]
+    FundNotFound(String),
+    #[error("Unknown tranche index {tranche_index} for vault {vault_id}")]
+    UnknownTranche { vault_id: String, tranche_index: u8 },
+    #[error("Deposit not permitted: {reason}")]
+    NotEligible { reason: String },
+    #[error("Amount {amount} is outside the tranche limits [{min}, {max}]")]
+    OutsideTrancheLimits { amount: u64, min: u64, max: u64 },
+    #[error("Idempotency key {0} was already used for a different transfer")]
+    IdempotencyKeyConflict(String),
+    #[error("Transfer for idempotency key {0} has an unknown outcome and is awaiting reconciliation")]
+    TransferOutcomeUnknown(String),
+    #[error("Missing `x-api-key` header")]
+    MissingApiKey,
+    #[error("No aggregated price for {symbol}: {reason}")]
+    PriceUnavailable { symbol: String, reason: String },
+    #[error(transparent)]
+    RateLimited(#[from] RateLimitExceeded),
+    #[error(transparent)]
+    Internal(#[from] anyhow::Error),
+}
+
+impl ApiError {
+    pub fn invalid_field(field: &'static str, reason: impl Into<String>) -> Self {
+        Self::InvalidField { field, reason: reason.into() }
+    }
+
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            Self::InvalidField { .. } => StatusCode::BAD_REQUEST,
+            Self::NotEligible { .. } => StatusCode::FORBIDDEN,
+            Self::VaultNotFound(_) | Self::FundNotFound(_) => StatusCode::NOT_FOUND,
+            Self::UnknownTranche { .. } | Self::OutsideTrancheLimits { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::MissingApiKey => StatusCode::UNAUTHORIZED,
+            Self::IdempotencyKeyConflict(_) | Self::TransferOutcomeUnknown(_) => StatusCode::CONFLICT,
+            Self::PriceUnavailable { .. } => StatusCode::SERVICE_UNAVAILABLE,
+            Self::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+            Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = self.status_code();
+        let message = match &self {
+            Self::Internal(e) => {
+                error!("Internal API error: {:?}", e);
+                "Internal server error".to_string()
+            }
+            other => other.to_string(),
+        };
+        let mut response = (status, Json(ApiResponse::<()>::error(message))).into_response();
+        if let Self::RateLimited(exceeded) = &self {
+            response.headers_mut().insert(header::RETRY_AFTER, HeaderValue::from(exceeded.retry_after_secs()));
+        }
+        response
+    }
+}
@@ -0,0 +1,62 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+};
+use exposure_detector::{ExposureAnalysisResult, RecursiveExposureFlattening};
+use serde::{Deserialize, Serialize};
+
+use crate::{ApiError, ApiResponse, ApiState};
+
+/// Fund exposure analytics endpoint
+/// GET /analytics/exposure/:fund_id?depth=3&threshold=100&dot=true
+/// `depth` caps the recursive flattening (defaults to the service's maximum exposure depth),
+/// `threshold` drops exposures below that many basis points, and `dot` adds a Graphviz
+/// rendering of the flattened exposure graph.
+
+/// Default `threshold`: every exposure of at least 1 bps is flattened
+pub const DEFAULT_EXPOSURE_THRESHOLD_BPS: u16 = 1;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ExposureQuery {
+    pub depth: Option<u32>,
+    pub threshold: Option<u16>,
+    #[serde(default)]
+    pub dot: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FundExposureReport {
+    pub analysis: ExposureAnalysisResult,
+    pub flattening: RecursiveExposureFlattening,
+    /// Graphviz DOT of `flattening`, when requested
+    pub dot: Option<String>,
+}
+
+/// GET /analytics/exposure/:fund_id
+pub async fn get_fund_exposure(
+    State(state): State<ApiState>,
+    Path(fund_id): Path<String>,
+    Query(query): Query<ExposureQuery>,
+) -> Result<Json<ApiResponse<FundExposureReport>>, ApiError> {
+    let exposure = state.exposure.as_ref()
+        .ok_or_else(|| anyhow::anyhow!("Exposure analytics are not configured"))?;
+
+    let max_depth = exposure.max_exposure_depth() as u32;
+    let depth = query.depth.unwrap_or(max_depth);
+    if depth == 0 || depth > max_depth {
+        return Err(ApiError::invalid_field("depth", format!("must be between 1 and {}", max_depth)));
+    }
+    let threshold = query.threshold.unwrap_or(DEFAULT_EXPOSURE_THRESHOLD_BPS);
+    if !(1..=10_000).contains(&threshold) {
+        return Err(ApiError::invalid_field("threshold", "must be between 1 and 10000 bps"));
+    }
+    if !exposure.is_registered(&fund_id).await {
+        return Err(ApiError::FundNotFound(fund_id));
+    }
+
+    let analysis = exposure.analyze_fund_exposure(&fund_id).await?;
+    let flattening = exposure.recursive_zknav_flattening(fund_id, threshold, depth).await?;
+    let dot = query.dot.then(|| flattening.to_dot());
+
+    Ok(Json(ApiResponse::success(FundExposureReport { analysis, flattening, dot })))
+}
@@ -1,6 +1,28 @@
+pub mod cross_chain_transfer;
+pub mod error;
+pub mod exposure_analytics;
+pub mod oracle_prices;
+pub mod rate_limiter;
+pub mod reports;
+pub mod vault_read;
+pub mod vault_stream;
+pub mod vault_transactions;
+
+pub use cross_chain_transfer::*;
+pub use error::*;
+pub use exposure_analytics::*;
+pub use oracle_prices::*;
+pub use rate_limiter::*;
+pub use reports::*;
+pub use vault_read::*;
+pub use vault_stream::*;
+pub use vault_transactions::*;
+
 use anyhow::Result;
+use axum::{middleware, routing::{get, post}, Router};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{info, warn, error};
 
@@ -14,6 +36,9 @@ pub struct ApiConfig {
     pub cors_enabled: bool,
     pub rate_limiting_enabled: bool,
     pub max_requests_per_minute: u32,
+    /// Reverse proxies whose `X-Forwarded-For` / `X-Real-IP` headers identify the client
+    #[serde(default)]
+    pub trusted_proxies: Vec<std::net::IpAddr>,
 }
 
 impl Default for ApiConfig {
@@ -24,6 +49,7 @@ impl Default for ApiConfig {
             cors_enabled: true,
             rate_limiting_enabled: true,
             max_requests_per_minute: 1000,
+            trusted_proxies: Vec::new(),
         }
     }
 }
@@ -56,6 +82,94 @@ impl<T> ApiResponse<T> {
     }
 }
 
+/// Shared state for the library router
+#[derive(Clone)]
+pub struct ApiState {
+    pub vault_reader: Arc<dyn VaultReadService>,
+    pub rate_limiter: Option<Arc<RateLimiterService>>,
+    pub eligibility: Option<Arc<compliance::EligibilityEngine>>,
+    pub cross_chain: Option<Arc<cross_chain::CrossChainService>>,
+    pub exposure: Option<Arc<exposure_detector::FundExposureService>>,
+    pub oracle: Option<Arc<rtf_oracle::OracleService>>,
+    pub reports: Option<Arc<ReportGenerator>>,
+    pub vault_events: Option<Arc<VaultEventBus>>,
+}
+
+impl ApiState {
+    pub fn new(vault_reader: Arc<dyn VaultReadService>) -> Self {
+        Self { vault_reader, rate_limiter: None, eligibility: None, cross_chain: None, exposure: None, oracle: None, reports: None, vault_events: None }
+    }
+
+    /// Require deposits to pass the jurisdiction and sanctions eligibility check
+    pub fn with_eligibility_engine(mut self, eligibility: Arc<compliance::EligibilityEngine>) -> Self {
+        self.eligibility = Some(eligibility);
+        self
+    }
+
+    /// Serve cross-chain transfers through this service
+    pub fn with_cross_chain(mut self, cross_chain: Arc<cross_chain::CrossChainService>) -> Self {
+        self.cross_chain = Some(cross_chain);
+        self
+    }
+
+    /// Serve exposure analytics from this service
+    pub fn with_exposure_service(mut self, exposure: Arc<exposure_detector::FundExposureService>) -> Self {
+        self.exposure = Some(exposure);
+        self
+    }
+
+    /// Serve aggregated oracle prices from this service
+    pub fn with_oracle_service(mut self, oracle: Arc<rtf_oracle::OracleService>) -> Self {
+        self.oracle = Some(oracle);
+        self
+    }
+
+    /// Serve analytics reports through this generator
+    pub fn with_report_generator(mut self, reports: Arc<ReportGenerator>) -> Self {
+        self.reports = Some(reports);
+        self
+    }
+
+    /// Stream vault events published on this bus
+    pub fn with_vault_events(mut self, vault_events: Arc<VaultEventBus>) -> Self {
+        self.vault_events = Some(vault_events);
+        self
+    }
+
+    /// Publish to the vault event bus, if one is configured
+    pub fn publish_vault_event(&self, event: VaultEvent) {
+        if let Some(vault_events) = &self.vault_events {
+            vault_events.publish(event);
+        }
+    }
+
+    /// Limit each client to the limiter's request rate
+    pub fn with_rate_limiter(mut self, rate_limiter: Arc<RateLimiterService>) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+}
+
+/// Build the `/api/v1` router for the endpoints implemented in this crate
+pub fn build_api_router(state: ApiState) -> Router {
+    let api_v1 = Router::new()
+        .route("/vaults/:vault_id", get(get_vault))
+        .route("/vaults/:vault_id/deposit", post(deposit_to_vault))
+        .route("/vaults/:vault_id/redeem", post(request_redemption))
+        .route("/vaults/:vault_id/nav", get(get_vault_nav))
+        .route("/vaults/:vault_id/performance", get(get_performance_metrics))
+        .route("/vaults/:vault_id/stream", get(stream_vault_events))
+        .route("/cross-chain/transfer", post(initiate_cross_chain_transfer))
+        .route("/oracles/prices", get(get_current_prices))
+        .route("/analytics/exposure/:fund_id", get(get_fund_exposure))
+        .route("/analytics/reports", get(get_report).post(generate_report));
+
+    Router::new()
+        .nest("/api/v1", api_v1)
+        .layer(middleware::from_fn_with_state(state.clone(), rate_limiting_middleware))
+        .with_state(state)
+}
+
 /// Initialize API service
 pub async fn init_api_service(config: ApiConfig) -> Result<()> {
     info!("🌐 Initializing RTF API Service");
@@ -106,7 +106,8 @@ async fn main() -> Result<()> {
     let listener = tokio::net::TcpListener::bind(addr).await?;
     
     // Graceful shutdown
-    axum::serve(listener, app)
+    // Rate limiting identifies clients by their peer address
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
         .with_graceful_shutdown(shutdown_signal())
         .await?;
     
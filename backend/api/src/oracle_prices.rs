@@ -0,0 +1,37 @@
+use axum::{
+    extract::{Query, State},
+    response::Json,
+};
+use rtf_oracle::AggregatedPrice;
+use serde::Deserialize;
+
+use crate::{ApiError, ApiResponse, ApiState};
+
+/// Aggregated oracle prices endpoint
+/// GET /oracles/prices?symbol=SOL/USD
+/// Without `symbol`, every symbol with a quorum of agreeing feeds is returned; with it,
+/// only that symbol, failing when its feeds cannot currently be aggregated.
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PriceQuery {
+    pub symbol: Option<String>,
+}
+
+/// GET /oracles/prices
+pub async fn get_current_prices(
+    State(state): State<ApiState>,
+    Query(query): Query<PriceQuery>,
+) -> Result<Json<ApiResponse<Vec<AggregatedPrice>>>, ApiError> {
+    let oracle = state.oracle.as_ref()
+        .ok_or_else(|| anyhow::anyhow!("Oracle prices are not configured"))?;
+
+    let prices = match query.symbol {
+        Some(symbol) => {
+            let price = oracle.aggregated_price(&symbol).await
+                .map_err(|e| ApiError::PriceUnavailable { symbol, reason: format!("{:#}", e) })?;
+            vec![price]
+        }
+        None => oracle.aggregated_prices().await,
+    };
+    Ok(Json(ApiResponse::success(prices)))
+}
@@ -0,0 +1,280 @@
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::HeaderMap,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::{ApiConfig, ApiError, ApiState};
+
+/// Per-client token-bucket rate limiter
+/// Each client IP gets a bucket holding up to `max_requests_per_minute` tokens, refilled
+/// continuously at the same rate per minute. Clients are identified by the TCP peer
+/// address (`ConnectInfo`, so the server must be run with
+/// `into_make_service_with_connect_info::<SocketAddr>`). `X-Forwarded-For` and
+/// `X-Real-IP` are only believed when the peer is a configured trusted proxy, and API
+/// keys are not authenticated here, so neither can be varied to get a fresh bucket.
+/// Buckets live in a fixed number of independently locked shards so concurrent
+/// clients rarely contend on the same lock. A bucket left idle long enough to refill
+/// completely is indistinguishable from a new one, so shards drop such buckets as they
+/// go and the map only holds recently active clients.
+
+const SHARD_COUNT: usize = 16;
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("Rate limit exceeded, retry after {} seconds", retry_after_secs(.retry_after))]
+pub struct RateLimitExceeded {
+    pub retry_after: Duration,
+}
+
+impl RateLimitExceeded {
+    /// Value for the `Retry-After` header: whole seconds, rounded up
+    pub fn retry_after_secs(&self) -> u64 {
+        retry_after_secs(&self.retry_after)
+    }
+}
+
+fn retry_after_secs(retry_after: &Duration) -> u64 {
+    let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+    secs.max(1)
+}
+
+#[derive(Debug, Clone)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+#[derive(Debug)]
+struct Shard {
+    buckets: HashMap<String, TokenBucket>,
+    last_eviction: Instant,
+}
+
+pub struct RateLimiterService {
+    shards: Vec<Mutex<Shard>>,
+    capacity: f64,
+    refill_per_sec: f64,
+    trusted_proxies: HashSet<IpAddr>,
+}
+
+impl RateLimiterService {
+    pub fn new(max_requests_per_minute: u32) -> Self {
+        let capacity = f64::from(max_requests_per_minute.max(1));
+        let now = Instant::now();
+        Self {
+            shards: (0..SHARD_COUNT)
+                .map(|_| Mutex::new(Shard { buckets: HashMap::new(), last_eviction: now }))
+                .collect(),
+            capacity,
+            refill_per_sec: capacity / 60.0,
+            trusted_proxies: HashSet::new(),
+        }
+    }
+
+    /// Believe forwarding headers on requests arriving from these proxy addresses
+    pub fn with_trusted_proxies(mut self, proxies: impl IntoIterator<Item = IpAddr>) -> Self {
+        self.trusted_proxies = proxies.into_iter().collect();
+        self
+    }
+
+    /// Limiter for the configured rate, or `None` when rate limiting is disabled
+    pub fn from_config(config: &ApiConfig) -> Option<Self> {
+        config.rate_limiting_enabled.then(|| {
+            Self::new(config.max_requests_per_minute)
+                .with_trusted_proxies(config.trusted_proxies.iter().copied())
+        })
+    }
+
+    /// Take one token from the client's bucket
+    pub fn check(&self, key: &str) -> Result<(), RateLimitExceeded> {
+        self.check_at(key, Instant::now())
+    }
+
+    pub fn check_at(&self, key: &str, now: Instant) -> Result<(), RateLimitExceeded> {
+        let mut shard = self.shard(key).lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let refill_time = self.refill_time();
+        if now.saturating_duration_since(shard.last_eviction) >= refill_time {
+            shard.buckets.retain(|_, bucket| now.saturating_duration_since(bucket.last_refill) < refill_time);
+            shard.last_eviction = now;
+        }
+
+        let bucket = shard.buckets.entry(key.to_string()).or_insert_with(|| TokenBucket {
+            tokens: self.capacity,
+            last_refill: now,
+        });
+
+        let elapsed = now.saturating_duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        bucket.last_refill = bucket.last_refill.max(now);
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            let wait = (1.0 - bucket.tokens) / self.refill_per_sec;
+            Err(RateLimitExceeded { retry_after: Duration::from_secs_f64(wait) })
+        }
+    }
+
+    /// Number of buckets currently held across all shards
+    pub fn tracked_clients(&self) -> usize {
+        self.shards.iter()
+            .map(|shard| shard.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).buckets.len())
+            .sum()
+    }
+
+    /// Client identity for a request from `peer`, see `client_key`
+    pub fn client_key(&self, peer: Option<IpAddr>, headers: &HeaderMap) -> String {
+        client_key(peer, headers, &self.trusted_proxies)
+    }
+
+    /// Time for an empty bucket to refill completely
+    fn refill_time(&self) -> Duration {
+        Duration::from_secs_f64(self.capacity / self.refill_per_sec)
+    }
+
+    fn shard(&self, key: &str) -> &Mutex<Shard> {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        &self.shards[hasher.finish() as usize % self.shards.len()]
+    }
+}
+
+/// Client identity for rate limiting: the peer IP, or the client a trusted proxy forwarded
+///
+/// Behind a trusted proxy the client is the right-most `X-Forwarded-For` hop that is not
+/// itself a trusted proxy, since everything to its left was written by the client. A
+/// forwarding header that cannot be parsed falls back to the peer address.
+pub fn client_key(peer: Option<IpAddr>, headers: &HeaderMap, trusted_proxies: &HashSet<IpAddr>) -> String {
+    let header = |name: &str| headers.get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+
+    let forwarded = || {
+        let from_forwarded_for = header("x-forwarded-for").and_then(|forwarded| {
+            forwarded.rsplit(',')
+                .map(|hop| hop.trim().parse::<IpAddr>())
+                .find(|hop| !matches!(hop, Ok(ip) if trusted_proxies.contains(ip)))
+                .and_then(Result::ok)
+        });
+        from_forwarded_for.or_else(|| header("x-real-ip").and_then(|ip| ip.parse().ok()))
+    };
+
+    let client = match peer {
+        Some(ip) if trusted_proxies.contains(&ip) => forwarded().or(Some(ip)),
+        peer => peer,
+    };
+    client
+        .map(|ip| format!("ip:{}", ip))
+        .unwrap_or_else(|| "ip:unknown".to_string())
+}
+
+pub async fn rate_limiting_middleware(
+    State(state): State<ApiState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if let Some(limiter) = &state.rate_limiter {
+        let peer = request.extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip());
+        if let Err(exceeded) = limiter.check(&limiter.client_key(peer, request.headers())) {
+            return ApiError::RateLimited(exceeded).into_response();
+        }
+    }
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_over_capacity_is_limited() {
+        let limiter = RateLimiterService::new(3);
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert!(limiter.check_at("key:a", now).is_ok());
+        }
+        let exceeded = limiter.check_at("key:a", now).unwrap_err();
+        assert_eq!(exceeded.retry_after_secs(), 20);
+
+        // Other clients have their own bucket
+        assert!(limiter.check_at("key:b", now).is_ok());
+    }
+
+    #[test]
+    fn test_tokens_refill_over_time() {
+        let limiter = RateLimiterService::new(60);
+        let start = Instant::now();
+
+        for _ in 0..60 {
+            limiter.check_at("ip:10.0.0.1", start).unwrap();
+        }
+        assert!(limiter.check_at("ip:10.0.0.1", start).is_err());
+        assert!(limiter.check_at("ip:10.0.0.1", start + Duration::from_millis(500)).is_err());
+
+        // One token per second
+        assert!(limiter.check_at("ip:10.0.0.1", start + Duration::from_secs(1)).is_ok());
+        assert!(limiter.check_at("ip:10.0.0.1", start + Duration::from_secs(1)).is_err());
+
+        // Refill never exceeds capacity
+        let later = start + Duration::from_secs(3_600);
+        for _ in 0..60 {
+            limiter.check_at("ip:10.0.0.1", later).unwrap();
+        }
+        assert!(limiter.check_at("ip:10.0.0.1", later).is_err());
+    }
+
+    #[test]
+    fn test_idle_buckets_are_evicted() {
+        let limiter = RateLimiterService::new(60);
+        let start = Instant::now();
+
+        for client in 0..100 {
+            limiter.check_at(&format!("ip:10.0.0.{}", client), start).unwrap();
+        }
+        assert_eq!(limiter.tracked_clients(), 100);
+
+        // A minute later every bucket is full again; each shard sweeps on its next check
+        let later = start + Duration::from_secs(61);
+        for client in 0..SHARD_COUNT * 16 {
+            limiter.check_at(&format!("ip:192.0.2.{}", client), later).unwrap();
+        }
+        assert_eq!(limiter.tracked_clients(), SHARD_COUNT * 16);
+    }
+
+    #[test]
+    fn test_forwarding_headers_only_trusted_from_proxies() {
+        let proxy: IpAddr = "10.0.0.1".parse().unwrap();
+        let client: IpAddr = "198.51.100.4".parse().unwrap();
+        let trusted = HashSet::from([proxy]);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", "203.0.113.7, 198.51.100.9".parse().unwrap());
+        headers.insert("x-api-key", "abc".parse().unwrap());
+
+        // A direct client cannot choose its identity with headers or keys
+        assert_eq!(client_key(Some(client), &headers, &trusted), "ip:198.51.100.4");
+        assert_eq!(client_key(Some(client), &headers, &HashSet::new()), "ip:198.51.100.4");
+
+        // Behind the proxy, the hop it appended is the client; earlier hops are spoofable
+        assert_eq!(client_key(Some(proxy), &headers, &trusted), "ip:198.51.100.9");
+
+        let mut real_ip = HeaderMap::new();
+        real_ip.insert("x-real-ip", "203.0.113.7".parse().unwrap());
+        assert_eq!(client_key(Some(proxy), &real_ip, &trusted), "ip:203.0.113.7");
+        assert_eq!(client_key(Some(proxy), &HeaderMap::new(), &trusted), "ip:10.0.0.1");
+
+        assert_eq!(client_key(None, &headers, &trusted), "ip:unknown");
+    }
+}
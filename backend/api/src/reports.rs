@@ -0,0 +1,458 @@
+use anyhow::Result;
+use axum::{
+    body::Body,
+    extract::{Query, State},
+    http::header,
+    response::{IntoResponse, Json, Response},
+};
+use chrono::{DateTime, TimeZone, Utc};
+use exposure_detector::FundExposureService;
+use futures::Stream;
+use rtf_esg_compliance::{ComplianceStatus, ESGComplianceSystem};
+use rtf_governance::{GovernanceSystem, Pagination, ProposalFilter, MAX_PROPOSAL_PAGE_SIZE};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+use crate::{ApiError, ApiState, VaultReadService};
+
+/// Analytics reports
+/// GET /analytics/reports?sections=...&period_start=...&period_end=...&format=json|csv
+/// POST /analytics/reports?format=json|csv
+/// A `ReportSpec` selects the vaults, sections and reporting period. The period bounds ESG
+/// record updates and governance proposal creation; performance and exposure reflect
+/// current state. Both formats are streamed one section at a time, so only a single
+/// section is held in memory however many vaults or proposals the report covers.
+
+const REPORT_CHANNEL_CAPACITY: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportSection {
+    VaultPerformance,
+    EsgScores,
+    ExposureSummary,
+    GovernanceActivity,
+}
+
+impl ReportSection {
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Self::VaultPerformance, Self::EsgScores, Self::ExposureSummary, Self::GovernanceActivity]
+            .into_iter()
+            .find(|section| section.name() == name)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::VaultPerformance => "vault_performance",
+            Self::EsgScores => "esg_scores",
+            Self::ExposureSummary => "exposure_summary",
+            Self::GovernanceActivity => "governance_activity",
+        }
+    }
+
+    pub fn columns(&self) -> &'static [&'static str] {
+        match self {
+            Self::VaultPerformance => &[
+                "vault_id", "period_start", "period_end", "total_return_bps", "annualized_return_bps",
+                "volatility_bps", "max_drawdown_bps", "sharpe_ratio",
+            ],
+            Self::EsgScores => &["entity_id", "overall_score", "compliance_status", "last_updated"],
+            Self::ExposureSummary => &[
+                "fund_id", "direct_exposures", "total_exposure_amount", "total_exposure_percentage", "risk_level",
+            ],
+            Self::GovernanceActivity => &[
+                "proposal_id", "dao_type", "title", "status", "created_at", "votes_for", "votes_against", "votes_abstain",
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportSpec {
+    pub vault_ids: Vec<String>,
+    pub sections: Vec<ReportSection>,
+    /// Inclusive start of the reporting period (unix seconds)
+    pub period_start: i64,
+    /// Exclusive end of the reporting period (unix seconds)
+    pub period_end: i64,
+}
+
+/// One report section as a table; `rows` follow `columns` order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportTable {
+    pub section: ReportSection,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+}
+
+impl ReportTable {
+    fn new(section: ReportSection) -> Self {
+        Self {
+            section,
+            columns: section.columns().iter().map(|c| c.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Header then one line per row, each prefixed with the section name
+    pub fn csv_lines(&self) -> impl Iterator<Item = String> + '_ {
+        let header = std::iter::once("section").chain(self.columns.iter().map(String::as_str))
+            .map(csv_field)
+            .collect::<Vec<_>>()
+            .join(",");
+        let rows = self.rows.iter().map(|row| {
+            std::iter::once(csv_field(self.section.name()))
+                .chain(row.iter().map(csv_value))
+                .collect::<Vec<_>>()
+                .join(",")
+        });
+        std::iter::once(header).chain(rows).map(|line| line + "\n")
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Report {
+    pub report_id: String,
+    pub generated_at: i64,
+    pub period_start: i64,
+    pub period_end: i64,
+    pub sections: Vec<ReportTable>,
+}
+
+impl Report {
+    /// Sections separated by a blank line
+    pub fn to_csv(&self) -> String {
+        self.sections.iter()
+            .map(|table| table.csv_lines().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Assembles reports from the vault read model and whichever services are configured
+pub struct ReportGenerator {
+    vault_reader: Arc<dyn VaultReadService>,
+    esg: Option<Arc<ESGComplianceSystem>>,
+    exposure: Option<Arc<FundExposureService>>,
+    governance: Option<Arc<GovernanceSystem>>,
+}
+
+impl ReportGenerator {
+    pub fn new(vault_reader: Arc<dyn VaultReadService>) -> Self {
+        Self { vault_reader, esg: None, exposure: None, governance: None }
+    }
+
+    pub fn with_esg(mut self, esg: Arc<ESGComplianceSystem>) -> Self {
+        self.esg = Some(esg);
+        self
+    }
+
+    pub fn with_exposure(mut self, exposure: Arc<FundExposureService>) -> Self {
+        self.exposure = Some(exposure);
+        self
+    }
+
+    pub fn with_governance(mut self, governance: Arc<GovernanceSystem>) -> Self {
+        self.governance = Some(governance);
+        self
+    }
+
+    /// Reject specs this generator cannot produce before any output is written
+    pub async fn validate(&self, spec: &ReportSpec) -> Result<(), ApiError> {
+        if spec.sections.is_empty() {
+            return Err(ApiError::invalid_field("sections", "must select at least one section"));
+        }
+        if spec.period_start >= spec.period_end {
+            return Err(ApiError::invalid_field("period_end", "must be after period_start"));
+        }
+        for section in &spec.sections {
+            let configured = match section {
+                ReportSection::VaultPerformance => true,
+                ReportSection::EsgScores => self.esg.is_some(),
+                ReportSection::ExposureSummary => self.exposure.is_some(),
+                ReportSection::GovernanceActivity => self.governance.is_some(),
+            };
+            if !configured {
+                return Err(ApiError::invalid_field("sections", format!("{} is not available", section.name())));
+            }
+        }
+        for vault_id in &spec.vault_ids {
+            if self.vault_reader.get_vault(vault_id).await?.is_none() {
+                return Err(ApiError::VaultNotFound(vault_id.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Build every section of the report in memory
+    pub async fn generate(&self, spec: &ReportSpec) -> Result<Report> {
+        let mut sections = Vec::with_capacity(spec.sections.len());
+        for section in &spec.sections {
+            sections.push(self.section(*section, spec).await?);
+        }
+        Ok(Report {
+            report_id: uuid::Uuid::new_v4().to_string(),
+            generated_at: Utc::now().timestamp(),
+            period_start: spec.period_start,
+            period_end: spec.period_end,
+            sections,
+        })
+    }
+
+    /// Report chunks produced by a background task one section at a time
+    ///
+    /// JSON output has the same shape as `ApiResponse<Report>`. The task stops as soon as
+    /// the consumer drops the stream.
+    pub fn stream(self: Arc<Self>, spec: ReportSpec, format: ReportFormat) -> impl Stream<Item = Result<String>> {
+        let (sender, receiver) = mpsc::channel(REPORT_CHANNEL_CAPACITY);
+        tokio::spawn(async move {
+            if let Err(e) = self.write_chunks(&spec, format, &sender).await {
+                let _ = sender.send(Err(e)).await;
+            }
+        });
+        futures::stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|chunk| (chunk, receiver))
+        })
+    }
+
+    /// Send the report down `sender`, returning early once the consumer has gone
+    async fn write_chunks(&self, spec: &ReportSpec, format: ReportFormat, sender: &mpsc::Sender<Result<String>>) -> Result<()> {
+        let send = |chunk: String| async move { sender.send(Ok(chunk)).await.is_ok() };
+
+        if matches!(format, ReportFormat::Json) && !send(json_report_prefix(spec)?).await {
+            return Ok(());
+        }
+        for (index, section) in spec.sections.iter().enumerate() {
+            let table = self.section(*section, spec).await?;
+            let chunks: Vec<String> = match format {
+                ReportFormat::Json => {
+                    let separator = if index > 0 { "," } else { "" };
+                    vec![format!("{}{}", separator, serde_json::to_string(&table)?)]
+                }
+                ReportFormat::Csv => (index > 0).then(|| "\n".to_string())
+                    .into_iter()
+                    .chain(table.csv_lines())
+                    .collect(),
+            };
+            for chunk in chunks {
+                if !send(chunk).await {
+                    return Ok(());
+                }
+            }
+        }
+        if matches!(format, ReportFormat::Json) {
+            send(format!("]}},\"error\":null,\"timestamp\":{}}}", Utc::now().timestamp())).await;
+        }
+        Ok(())
+    }
+
+    async fn section(&self, section: ReportSection, spec: &ReportSpec) -> Result<ReportTable> {
+        let mut table = ReportTable::new(section);
+        match section {
+            ReportSection::VaultPerformance => self.vault_performance(spec, &mut table).await?,
+            ReportSection::EsgScores => self.esg_scores(spec, &mut table).await?,
+            ReportSection::ExposureSummary => self.exposure_summary(spec, &mut table).await?,
+            ReportSection::GovernanceActivity => self.governance_activity(spec, &mut table).await?,
+        }
+        Ok(table)
+    }
+
+    async fn vault_performance(&self, spec: &ReportSpec, table: &mut ReportTable) -> Result<()> {
+        for vault_id in &spec.vault_ids {
+            let Some(performance) = self.vault_reader.get_performance(vault_id).await? else { continue };
+            table.rows.push(vec![
+                json!(performance.vault_id),
+                json!(performance.period_start),
+                json!(performance.period_end),
+                json!(performance.total_return_bps),
+                json!(performance.annualized_return_bps),
+                json!(performance.volatility_bps),
+                json!(performance.max_drawdown_bps),
+                json!(performance.sharpe_ratio),
+            ]);
+        }
+        Ok(())
+    }
+
+    async fn esg_scores(&self, spec: &ReportSpec, table: &mut ReportTable) -> Result<()> {
+        let Some(esg) = &self.esg else { return Ok(()) };
+        for vault_id in &spec.vault_ids {
+            let Some(record) = esg.get_compliance_record(vault_id).await else { continue };
+            let updated_at = record.last_updated.timestamp();
+            if updated_at < spec.period_start || updated_at >= spec.period_end {
+                continue;
+            }
+            let status = match &record.compliance_status {
+                ComplianceStatus::Compliant => "Compliant",
+                ComplianceStatus::NonCompliant { .. } => "NonCompliant",
+                ComplianceStatus::UnderReview => "UnderReview",
+                ComplianceStatus::Exempt => "Exempt",
+            };
+            table.rows.push(vec![
+                json!(record.entity_id),
+                json!(record.overall_score),
+                json!(status),
+                json!(updated_at),
+            ]);
+        }
+        Ok(())
+    }
+
+    async fn exposure_summary(&self, spec: &ReportSpec, table: &mut ReportTable) -> Result<()> {
+        let Some(exposure) = &self.exposure else { return Ok(()) };
+        for vault_id in &spec.vault_ids {
+            if !exposure.is_registered(vault_id).await {
+                continue;
+            }
+            let analysis = exposure.analyze_fund_exposure(vault_id).await?;
+            table.rows.push(vec![
+                json!(analysis.fund_id),
+                json!(analysis.direct_exposures.len()),
+                json!(analysis.total_exposure_amount),
+                json!(analysis.total_exposure_percentage),
+                json!(format!("{:?}", analysis.risk_assessment.overall_risk_level)),
+            ]);
+        }
+        Ok(())
+    }
+
+    async fn governance_activity(&self, spec: &ReportSpec, table: &mut ReportTable) -> Result<()> {
+        let Some(governance) = &self.governance else { return Ok(()) };
+        let filter = ProposalFilter {
+            created_from: Some(timestamp(spec.period_start)?),
+            created_until: Some(timestamp(spec.period_end)?),
+            ..ProposalFilter::default()
+        };
+
+        let mut offset = 0;
+        loop {
+            let page = governance.list_proposals(filter.clone(), Pagination { offset, limit: MAX_PROPOSAL_PAGE_SIZE }).await;
+            for proposal in &page.proposals {
+                table.rows.push(vec![
+                    json!(proposal.id),
+                    json!(format!("{:?}", proposal.dao_type)),
+                    json!(proposal.title),
+                    json!(format!("{:?}", proposal.status)),
+                    json!(proposal.created_at.timestamp()),
+                    json!(proposal.votes_for),
+                    json!(proposal.votes_against),
+                    json!(proposal.votes_abstain),
+                ]);
+            }
+            offset += page.proposals.len();
+            if page.proposals.is_empty() || offset >= page.total {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Opening of a streamed `ApiResponse<Report>`, up to the first section
+fn json_report_prefix(spec: &ReportSpec) -> Result<String> {
+    Ok(format!(
+        "{{\"success\":true,\"data\":{{\"report_id\":{},\"generated_at\":{},\"period_start\":{},\"period_end\":{},\"sections\":[",
+        serde_json::to_string(&uuid::Uuid::new_v4().to_string())?,
+        Utc::now().timestamp(),
+        spec.period_start,
+        spec.period_end,
+    ))
+}
+
+fn timestamp(seconds: i64) -> Result<DateTime<Utc>> {
+    Utc.timestamp_opt(seconds, 0).single()
+        .ok_or_else(|| anyhow::anyhow!("Timestamp {} is out of range", seconds))
+}
+
+fn csv_value(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => csv_field(s),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        other => csv_field(&other.to_string()),
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ReportQuery {
+    #[serde(default)]
+    pub format: ReportFormat,
+}
+
+/// Query string form of a `ReportSpec`, with comma-separated vault ids and sections
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReportParams {
+    #[serde(default)]
+    pub vault_ids: String,
+    pub sections: String,
+    pub period_start: i64,
+    pub period_end: i64,
+    #[serde(default)]
+    pub format: ReportFormat,
+}
+
+impl ReportParams {
+    pub fn to_spec(&self) -> Result<ReportSpec, ApiError> {
+        let sections = split_list(&self.sections)
+            .map(|name| ReportSection::from_name(name)
+                .ok_or_else(|| ApiError::invalid_field("sections", format!("unknown section {}", name))))
+            .collect::<Result<_, _>>()?;
+        Ok(ReportSpec {
+            vault_ids: split_list(&self.vault_ids).map(str::to_string).collect(),
+            sections,
+            period_start: self.period_start,
+            period_end: self.period_end,
+        })
+    }
+}
+
+fn split_list(list: &str) -> impl Iterator<Item = &str> {
+    list.split(',').map(str::trim).filter(|item| !item.is_empty())
+}
+
+/// GET /analytics/reports
+pub async fn get_report(
+    State(state): State<ApiState>,
+    Query(params): Query<ReportParams>,
+) -> Result<Response, ApiError> {
+    stream_report(&state, params.to_spec()?, params.format).await
+}
+
+/// POST /analytics/reports
+pub async fn generate_report(
+    State(state): State<ApiState>,
+    Query(query): Query<ReportQuery>,
+    Json(spec): Json<ReportSpec>,
+) -> Result<Response, ApiError> {
+    stream_report(&state, spec, query.format).await
+}
+
+async fn stream_report(state: &ApiState, spec: ReportSpec, format: ReportFormat) -> Result<Response, ApiError> {
+    let reports = state.reports.clone()
+        .ok_or_else(|| anyhow::anyhow!("Reports are not configured"))?;
+    reports.validate(&spec).await?;
+
+    let content_type = match format {
+        ReportFormat::Json => "application/json",
+        ReportFormat::Csv => "text/csv; charset=utf-8",
+    };
+    Ok(([(header::CONTENT_TYPE, content_type)], Body::from_stream(reports.stream(spec, format))).into_response())
+}
@@ -0,0 +1,193 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::{ApiError, ApiResponse, ApiState, VaultEvent, VaultEventBus};
+
+/// Vault read endpoints
+/// GET /vaults/:vault_id, GET /vaults/:vault_id/nav, GET /vaults/:vault_id/performance
+/// Amounts and per-share values are fixed point with the vault's `nav_decimals`
+/// (6 unless the vault says otherwise, so 1_000_000 = 1.0).
+
+/// `nav_decimals` of vaults that predate the field
+pub const DEFAULT_NAV_DECIMALS: u8 = 6;
+
+fn default_nav_decimals() -> u8 {
+    DEFAULT_NAV_DECIMALS
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VaultSummary {
+    pub vault_id: String,
+    pub name: String,
+    pub status: VaultStatus,
+    pub total_assets: u64,
+    pub total_liabilities: u64,
+    pub nav_per_share: u64,
+    #[serde(default = "default_nav_decimals")]
+    pub nav_decimals: u8,
+    pub tranches: Vec<TrancheSummary>,
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VaultStatus {
+    Active,
+    Paused,
+    Emergency,
+    Closed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrancheSummary {
+    pub index: u8,
+    pub tranche_type: String,
+    pub total_supply: u64,
+    pub nav_per_share: u64,
+    pub min_deposit: u64,
+    pub max_deposit: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VaultNav {
+    pub vault_id: String,
+    pub nav_per_share: u64,
+    pub total_assets: u64,
+    pub total_liabilities: u64,
+    pub net_asset_value: u64,
+    pub computed_at: i64,
+    pub proof_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VaultPerformance {
+    pub vault_id: String,
+    pub total_return_bps: i64,
+    pub annualized_return_bps: i64,
+    pub volatility_bps: u64,
+    pub max_drawdown_bps: u64,
+    pub sharpe_ratio: f64,
+    pub period_start: i64,
+    pub period_end: i64,
+}
+
+/// Read-side view of vault state; `None` means the vault does not exist
+#[async_trait]
+pub trait VaultReadService: Send + Sync {
+    async fn get_vault(&self, vault_id: &str) -> Result<Option<VaultSummary>>;
+    async fn get_nav(&self, vault_id: &str) -> Result<Option<VaultNav>>;
+    async fn get_performance(&self, vault_id: &str) -> Result<Option<VaultPerformance>>;
+}
+
+#[derive(Debug, Clone)]
+pub struct VaultRecord {
+    pub summary: VaultSummary,
+    pub nav: VaultNav,
+    pub performance: VaultPerformance,
+}
+
+/// Vault read service backed by process memory
+#[derive(Default)]
+pub struct InMemoryVaultReadService {
+    vaults: RwLock<HashMap<String, VaultRecord>>,
+    vault_events: Option<Arc<VaultEventBus>>,
+}
+
+impl InMemoryVaultReadService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Publish a `NavUpdate` on this bus whenever a vault's NAV changes
+    pub fn with_vault_events(mut self, vault_events: Arc<VaultEventBus>) -> Self {
+        self.vault_events = Some(vault_events);
+        self
+    }
+
+    pub async fn upsert_vault(&self, record: VaultRecord) {
+        let nav = record.nav.clone();
+        let previous = self.vaults.write().await.insert(record.summary.vault_id.clone(), record);
+        if previous.is_none_or(|previous| previous.nav != nav) {
+            self.publish_nav(nav);
+        }
+    }
+
+    /// Replace the NAV of a known vault, returning false if the vault does not exist
+    pub async fn update_nav(&self, nav: VaultNav) -> bool {
+        {
+            let mut vaults = self.vaults.write().await;
+            let Some(record) = vaults.get_mut(&nav.vault_id) else {
+                return false;
+            };
+            record.summary.nav_per_share = nav.nav_per_share;
+            record.summary.total_assets = nav.total_assets;
+            record.summary.total_liabilities = nav.total_liabilities;
+            record.summary.updated_at = nav.computed_at;
+            record.nav = nav.clone();
+        }
+        self.publish_nav(nav);
+        true
+    }
+
+    fn publish_nav(&self, nav: VaultNav) {
+        if let Some(vault_events) = &self.vault_events {
+            vault_events.publish(VaultEvent::NavUpdate { nav });
+        }
+    }
+}
+
+#[async_trait]
+impl VaultReadService for InMemoryVaultReadService {
+    async fn get_vault(&self, vault_id: &str) -> Result<Option<VaultSummary>> {
+        Ok(self.vaults.read().await.get(vault_id).map(|record| record.summary.clone()))
+    }
+
+    async fn get_nav(&self, vault_id: &str) -> Result<Option<VaultNav>> {
+        Ok(self.vaults.read().await.get(vault_id).map(|record| record.nav.clone()))
+    }
+
+    async fn get_performance(&self, vault_id: &str) -> Result<Option<VaultPerformance>> {
+        Ok(self.vaults.read().await.get(vault_id).map(|record| record.performance.clone()))
+    }
+}
+
+type ReadResponse<T> = Result<Json<ApiResponse<T>>, ApiError>;
+
+fn respond<T>(vault_id: String, result: Result<Option<T>>) -> ReadResponse<T> {
+    let data = result?.ok_or(ApiError::VaultNotFound(vault_id))?;
+    Ok(Json(ApiResponse::success(data)))
+}
+
+/// GET /vaults/:vault_id
+pub async fn get_vault(
+    State(state): State<ApiState>,
+    Path(vault_id): Path<String>,
+) -> ReadResponse<VaultSummary> {
+    let result = state.vault_reader.get_vault(&vault_id).await;
+    respond(vault_id, result)
+}
+
+/// GET /vaults/:vault_id/nav
+pub async fn get_vault_nav(
+    State(state): State<ApiState>,
+    Path(vault_id): Path<String>,
+) -> ReadResponse<VaultNav> {
+    let result = state.vault_reader.get_nav(&vault_id).await;
+    respond(vault_id, result)
+}
+
+/// GET /vaults/:vault_id/performance
+pub async fn get_performance_metrics(
+    State(state): State<ApiState>,
+    Path(vault_id): Path<String>,
+) -> ReadResponse<VaultPerformance> {
+    let result = state.vault_reader.get_performance(&vault_id).await;
+    respond(vault_id, result)
+}
@@ -0,0 +1,152 @@
+use axum::{
+    extract::{Path, State},
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::warn;
+
+use crate::{ApiError, ApiState, VaultNav};
+
+/// Vault event stream
+/// GET /vaults/:vault_id/stream
+/// Server-sent events for one vault, replacing polling of `/vaults/:vault_id/nav`.
+/// Producers publish to a `VaultEventBus` without ever waiting on clients; a client that
+/// falls more than the bus capacity behind is disconnected and should refetch the NAV
+/// before reconnecting.
+
+pub const DEFAULT_VAULT_EVENT_CAPACITY: usize = 1_024;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum VaultEvent {
+    NavUpdate {
+        nav: VaultNav,
+    },
+    /// A drift-ledger entry exceeded the vault's tolerance
+    DriftAlert {
+        vault_id: String,
+        epoch: u64,
+        drift_bps: u64,
+        tolerance_bps: u64,
+    },
+    RedemptionProcessed {
+        vault_id: String,
+        redemption_id: String,
+        tranche_index: u8,
+        shares: u64,
+        amount: u64,
+    },
+    /// A deposit was accepted and quoted by `POST /vaults/:vault_id/deposit`
+    DepositRequested {
+        vault_id: String,
+        user_address: String,
+        tranche_index: u8,
+        amount: u64,
+        expected_shares: u64,
+    },
+    /// A redemption was accepted and quoted by `POST /vaults/:vault_id/redeem`
+    RedemptionRequested {
+        vault_id: String,
+        user_address: String,
+        tranche_index: u8,
+        shares: u64,
+        expected_amount: u64,
+    },
+}
+
+impl VaultEvent {
+    pub fn vault_id(&self) -> &str {
+        match self {
+            Self::NavUpdate { nav } => &nav.vault_id,
+            Self::DriftAlert { vault_id, .. }
+            | Self::RedemptionProcessed { vault_id, .. }
+            | Self::DepositRequested { vault_id, .. }
+            | Self::RedemptionRequested { vault_id, .. } => vault_id,
+        }
+    }
+
+    /// SSE `event:` name
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            Self::NavUpdate { .. } => "nav_update",
+            Self::DriftAlert { .. } => "drift_alert",
+            Self::RedemptionProcessed { .. } => "redemption_processed",
+            Self::DepositRequested { .. } => "deposit_requested",
+            Self::RedemptionRequested { .. } => "redemption_requested",
+        }
+    }
+}
+
+/// Fan-out of vault events to stream subscribers
+pub struct VaultEventBus {
+    sender: broadcast::Sender<VaultEvent>,
+}
+
+impl Default for VaultEventBus {
+    fn default() -> Self {
+        Self::new(DEFAULT_VAULT_EVENT_CAPACITY)
+    }
+}
+
+impl VaultEventBus {
+    /// `capacity` events may be buffered per subscriber before it is dropped as too slow
+    pub fn new(capacity: usize) -> Self {
+        Self { sender: broadcast::channel(capacity.max(1)).0 }
+    }
+
+    /// Publish without blocking, returning how many subscribers will receive the event
+    pub fn publish(&self, event: VaultEvent) -> usize {
+        self.sender.send(event).unwrap_or(0)
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<VaultEvent> {
+        self.sender.subscribe()
+    }
+}
+
+/// Events for `vault_id`, ending if the subscriber lags behind the bus
+pub fn vault_event_stream(
+    receiver: broadcast::Receiver<VaultEvent>,
+    vault_id: String,
+) -> impl Stream<Item = Result<Event, Infallible>> {
+    futures::stream::unfold(receiver, move |mut receiver| {
+        let vault_id = vault_id.clone();
+        async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) if event.vault_id() == vault_id => {
+                        let sse = Event::default()
+                            .event(event.event_name())
+                            .json_data(&event)
+                            .unwrap_or_else(|_| Event::default().event(event.event_name()));
+                        return Some((Ok(sse), receiver));
+                    }
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(missed)) => {
+                        warn!("⚠️ Dropping vault {} stream subscriber {} events behind", vault_id, missed);
+                        return None;
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        }
+    })
+}
+
+/// GET /vaults/:vault_id/stream
+pub async fn stream_vault_events(
+    State(state): State<ApiState>,
+    Path(vault_id): Path<String>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let events = state.vault_events.as_ref()
+        .ok_or_else(|| anyhow::anyhow!("Vault event streaming is not configured"))?;
+    if state.vault_reader.get_vault(&vault_id).await?.is_none() {
+        return Err(ApiError::VaultNotFound(vault_id));
+    }
+
+    let receiver = events.subscribe();
+    Ok(Sse::new(vault_event_stream(receiver, vault_id)).keep_alive(KeepAlive::default()))
+}
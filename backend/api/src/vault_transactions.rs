@@ -0,0 +1,249 @@
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use compliance::{Eligibility, KiltCredential};
+use serde::{Deserialize, Serialize};
+
+use crate::{ApiError, ApiResponse, ApiState, TrancheSummary, VaultEvent};
+
+/// Vault deposit and redemption endpoints
+/// POST /vaults/:vault_id/deposit, POST /vaults/:vault_id/redeem
+/// Requests are validated and quoted against the tranche NAV; the returned minimum
+/// output is the slippage bound the on-chain instruction enforces. Accepted requests
+/// are published on the vault event bus when one is configured.
+
+/// Largest slippage tolerance a client may request (10%)
+pub const MAX_SLIPPAGE_BPS: u16 = 1_000;
+
+const BPS_DENOMINATOR: u128 = 10_000;
+/// Same bound the vault program enforces on `VaultConfig::nav_decimals`
+const MAX_NAV_DECIMALS: u8 = 18;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepositRequest {
+    pub user_address: String,
+    pub tranche_index: u8,
+    pub amount: u64,
+    pub max_slippage_bps: u16,
+    /// Jurisdiction the depositor proves residency in; required when eligibility is enforced
+    #[serde(default)]
+    pub jurisdiction: Option<String>,
+    #[serde(default)]
+    pub kyc_credential: Option<KiltCredential>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedemptionRequest {
+    pub user_address: String,
+    pub tranche_index: u8,
+    pub shares: u64,
+    pub max_slippage_bps: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DepositResponse {
+    pub vault_id: String,
+    pub tranche_index: u8,
+    pub amount: u64,
+    pub nav_per_share: u64,
+    pub expected_shares: u64,
+    pub min_shares_out: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RedemptionResponse {
+    pub vault_id: String,
+    pub tranche_index: u8,
+    pub shares: u64,
+    pub nav_per_share: u64,
+    pub expected_amount: u64,
+    pub min_amount_out: u64,
+}
+
+impl DepositRequest {
+    /// Field-level checks that do not depend on vault state
+    pub fn validate(&self) -> Result<(), ApiError> {
+        validate_user_address(&self.user_address)?;
+        if self.amount == 0 {
+            return Err(ApiError::invalid_field("amount", "must be greater than zero"));
+        }
+        validate_slippage(self.max_slippage_bps)
+    }
+}
+
+impl RedemptionRequest {
+    /// Field-level checks that do not depend on vault state
+    pub fn validate(&self) -> Result<(), ApiError> {
+        validate_user_address(&self.user_address)?;
+        if self.shares == 0 {
+            return Err(ApiError::invalid_field("shares", "must be greater than zero"));
+        }
+        validate_slippage(self.max_slippage_bps)
+    }
+}
+
+fn validate_user_address(user_address: &str) -> Result<(), ApiError> {
+    if user_address.trim().is_empty() {
+        return Err(ApiError::invalid_field("user_address", "must not be empty"));
+    }
+    Ok(())
+}
+
+fn validate_slippage(max_slippage_bps: u16) -> Result<(), ApiError> {
+    if max_slippage_bps > MAX_SLIPPAGE_BPS {
+        return Err(ApiError::invalid_field(
+            "max_slippage_bps",
+            format!("must not exceed {} bps", MAX_SLIPPAGE_BPS),
+        ));
+    }
+    Ok(())
+}
+
+/// Minimum acceptable output after applying the slippage tolerance
+fn apply_slippage(expected: u64, max_slippage_bps: u16) -> u64 {
+    (expected as u128 * (BPS_DENOMINATOR - max_slippage_bps as u128) / BPS_DENOMINATOR) as u64
+}
+
+/// Quoted output, rejected rather than truncated when it does not fit a u64
+fn to_u64(quoted: u128) -> Result<u64, ApiError> {
+    u64::try_from(quoted).map_err(|_| anyhow::anyhow!("Quoted amount {} exceeds u64", quoted).into())
+}
+
+/// The tranche and the raw value of 1.0 at the vault's NAV decimals
+async fn load_tranche(state: &ApiState, vault_id: &str, tranche_index: u8) -> Result<(TrancheSummary, u128), ApiError> {
+    let vault = state.vault_reader.get_vault(vault_id).await?
+        .ok_or_else(|| ApiError::VaultNotFound(vault_id.to_string()))?;
+
+    if vault.nav_decimals > MAX_NAV_DECIMALS {
+        return Err(anyhow::anyhow!("Vault {} has unsupported NAV decimals {}", vault_id, vault.nav_decimals).into());
+    }
+    let nav_scale = 10u128.pow(vault.nav_decimals as u32);
+
+    let tranche = vault.tranches.into_iter()
+        .find(|tranche| tranche.index == tranche_index)
+        .ok_or_else(|| ApiError::UnknownTranche { vault_id: vault_id.to_string(), tranche_index })?;
+
+    if tranche.nav_per_share == 0 {
+        return Err(anyhow::anyhow!("Tranche {} of vault {} has zero NAV", tranche_index, vault_id).into());
+    }
+    Ok((tranche, nav_scale))
+}
+
+/// POST /vaults/:vault_id/deposit
+pub async fn deposit_to_vault(
+    State(state): State<ApiState>,
+    Path(vault_id): Path<String>,
+    Json(request): Json<DepositRequest>,
+) -> Result<Json<ApiResponse<DepositResponse>>, ApiError> {
+    request.validate()?;
+    let (tranche, nav_scale) = load_tranche(&state, &vault_id, request.tranche_index).await?;
+
+    if request.amount < tranche.min_deposit || request.amount > tranche.max_deposit {
+        return Err(ApiError::OutsideTrancheLimits {
+            amount: request.amount,
+            min: tranche.min_deposit,
+            max: tranche.max_deposit,
+        });
+    }
+
+    if let Some(eligibility) = &state.eligibility {
+        let (jurisdiction, credential) = match (&request.jurisdiction, &request.kyc_credential) {
+            (Some(jurisdiction), Some(credential)) => (jurisdiction, credential),
+            (None, _) => return Err(ApiError::invalid_field("jurisdiction", "is required for this vault")),
+            (_, None) => return Err(ApiError::invalid_field("kyc_credential", "is required for this vault")),
+        };
+        let decision = eligibility
+            .check_deposit_eligibility(&request.user_address, &vault_id, jurisdiction, credential)
+            .await?;
+        if let Eligibility::Denied { reason } = decision {
+            return Err(ApiError::NotEligible { reason });
+        }
+    }
+
+    let expected_shares = to_u64(request.amount as u128 * nav_scale / tranche.nav_per_share as u128)?;
+    state.publish_vault_event(VaultEvent::DepositRequested {
+        vault_id: vault_id.clone(),
+        user_address: request.user_address.clone(),
+        tranche_index: request.tranche_index,
+        amount: request.amount,
+        expected_shares,
+    });
+    Ok(Json(ApiResponse::success(DepositResponse {
+        vault_id,
+        tranche_index: request.tranche_index,
+        amount: request.amount,
+        nav_per_share: tranche.nav_per_share,
+        expected_shares,
+        min_shares_out: apply_slippage(expected_shares, request.max_slippage_bps),
+    })))
+}
+
+/// POST /vaults/:vault_id/redeem
+pub async fn request_redemption(
+    State(state): State<ApiState>,
+    Path(vault_id): Path<String>,
+    Json(request): Json<RedemptionRequest>,
+) -> Result<Json<ApiResponse<RedemptionResponse>>, ApiError> {
+    request.validate()?;
+    let (tranche, nav_scale) = load_tranche(&state, &vault_id, request.tranche_index).await?;
+
+    if request.shares > tranche.total_supply {
+        return Err(ApiError::invalid_field("shares", "exceeds the tranche supply"));
+    }
+
+    let expected_amount = to_u64(request.shares as u128 * tranche.nav_per_share as u128 / nav_scale)?;
+    state.publish_vault_event(VaultEvent::RedemptionRequested {
+        vault_id: vault_id.clone(),
+        user_address: request.user_address.clone(),
+        tranche_index: request.tranche_index,
+        shares: request.shares,
+        expected_amount,
+    });
+    Ok(Json(ApiResponse::success(RedemptionResponse {
+        vault_id,
+        tranche_index: request.tranche_index,
+        shares: request.shares,
+        nav_per_share: tranche.nav_per_share,
+        expected_amount,
+        min_amount_out: apply_slippage(expected_amount, request.max_slippage_bps),
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deposit(amount: u64, max_slippage_bps: u16) -> DepositRequest {
+        DepositRequest {
+            user_address: "investor".to_string(),
+            tranche_index: 0,
+            amount,
+            max_slippage_bps,
+            jurisdiction: None,
+            kyc_credential: None,
+        }
+    }
+
+    #[test]
+    fn test_deposit_validation_reports_field() {
+        assert!(deposit(1_000_000, 50).validate().is_ok());
+        assert!(deposit(1_000_000, MAX_SLIPPAGE_BPS).validate().is_ok());
+
+        match deposit(0, 50).validate() {
+            Err(ApiError::InvalidField { field, .. }) => assert_eq!(field, "amount"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        match deposit(1_000_000, MAX_SLIPPAGE_BPS + 1).validate() {
+            Err(ApiError::InvalidField { field, .. }) => assert_eq!(field, "max_slippage_bps"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_apply_slippage() {
+        assert_eq!(apply_slippage(1_000_000, 0), 1_000_000);
+        assert_eq!(apply_slippage(1_000_000, 50), 995_000);
+        assert_eq!(apply_slippage(u64::MAX, MAX_SLIPPAGE_BPS), (u64::MAX as u128 * 9 / 10) as u64);
+    }
+}
@@ -0,0 +1,104 @@
+#![allow(dead_code)]
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    Router,
+};
+use rtf_api::*;
+use serde::de::DeserializeOwned;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+pub const VAULT_ID: &str = "vault-1";
+
+pub fn vault_record() -> VaultRecord {
+    VaultRecord {
+        summary: VaultSummary {
+            vault_id: VAULT_ID.to_string(),
+            name: "RTF Treasury Fund".to_string(),
+            status: VaultStatus::Active,
+            total_assets: 10_000_000_000,
+            total_liabilities: 500_000_000,
+            nav_per_share: 1_050_000,
+            nav_decimals: 6,
+            tranches: vec![
+                TrancheSummary {
+                    index: 0,
+                    tranche_type: "Senior".to_string(),
+                    total_supply: 6_000_000_000,
+                    nav_per_share: 1_020_000,
+                    min_deposit: 1_000_000,
+                    max_deposit: 1_000_000_000_000,
+                },
+                TrancheSummary {
+                    index: 1,
+                    tranche_type: "Junior".to_string(),
+                    total_supply: 3_000_000_000,
+                    nav_per_share: 1_110_000,
+                    min_deposit: 1_000_000,
+                    max_deposit: 1_000_000_000_000,
+                },
+            ],
+            updated_at: 1_700_000_000,
+        },
+        nav: VaultNav {
+            vault_id: VAULT_ID.to_string(),
+            nav_per_share: 1_050_000,
+            total_assets: 10_000_000_000,
+            total_liabilities: 500_000_000,
+            net_asset_value: 9_500_000_000,
+            computed_at: 1_700_000_000,
+            proof_hash: Some("ab".repeat(32)),
+        },
+        performance: VaultPerformance {
+            vault_id: VAULT_ID.to_string(),
+            total_return_bps: 500,
+            annualized_return_bps: 480,
+            volatility_bps: 320,
+            max_drawdown_bps: 150,
+            sharpe_ratio: 1.2,
+            period_start: 1_668_464_000,
+            period_end: 1_700_000_000,
+        },
+    }
+}
+
+pub async fn test_router() -> Router {
+    let reader = Arc::new(InMemoryVaultReadService::new());
+    reader.upsert_vault(vault_record()).await;
+    build_api_router(ApiState::new(reader))
+}
+
+pub async fn get<T: DeserializeOwned>(router: Router, uri: &str) -> (StatusCode, ApiResponse<T>) {
+    send(router, Request::builder().uri(uri).body(Body::empty()).unwrap()).await
+}
+
+pub async fn post_json<T: DeserializeOwned>(router: Router, uri: &str, body: serde_json::Value) -> (StatusCode, ApiResponse<T>) {
+    send(router, json_request(uri).body(Body::from(body.to_string())).unwrap()).await
+}
+
+/// POST as the client identified by `api_key`
+pub async fn post_json_as<T: DeserializeOwned>(
+    router: Router,
+    uri: &str,
+    api_key: &str,
+    body: serde_json::Value,
+) -> (StatusCode, ApiResponse<T>) {
+    let request = json_request(uri).header("x-api-key", api_key).body(Body::from(body.to_string())).unwrap();
+    send(router, request).await
+}
+
+fn json_request(uri: &str) -> axum::http::request::Builder {
+    Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", "application/json")
+}
+
+async fn send<T: DeserializeOwned>(router: Router, request: Request<Body>) -> (StatusCode, ApiResponse<T>) {
+    let response = router.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
@@ -0,0 +1,138 @@
+use async_trait::async_trait;
+use axum::{http::StatusCode, Router};
+use cross_chain::ccip_service::{CrossChainVaultSync, NavData};
+use cross_chain::icp_service::ICPVerification;
+use cross_chain::{
+    BitcoinAnchor, BitcoinAnchorLeg, CrossChainService, CrossChainSyncConfig, CrossChainTransferRequest,
+    CrossChainTransferResponse, MessagingLeg, VerificationLeg,
+};
+use rtf_api::*;
+use serde_json::json;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+mod common;
+use common::{post_json, post_json_as, vault_record};
+
+const TRANSFER_URI: &str = "/api/v1/cross-chain/transfer";
+
+/// Only token transfers are exercised; the sync legs are never reached
+struct UnusedLeg;
+
+#[async_trait]
+impl BitcoinAnchorLeg for UnusedLeg {
+    async fn anchor_nav(&self, _vault_id: String, _nav_data: &NavData) -> anyhow::Result<BitcoinAnchor> {
+        Err(anyhow::anyhow!("not used"))
+    }
+}
+
+#[async_trait]
+impl VerificationLeg for UnusedLeg {
+    async fn verify_state(&self, _vault_id: String, _nav_data: &NavData) -> anyhow::Result<ICPVerification> {
+        Err(anyhow::anyhow!("not used"))
+    }
+}
+
+#[derive(Default)]
+struct CountingMessaging {
+    sent: AtomicU32,
+}
+
+#[async_trait]
+impl MessagingLeg for CountingMessaging {
+    async fn sync_vault(&self, _vault_sync: CrossChainVaultSync) -> anyhow::Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    async fn latest_nav_root(&self, _vault_id: &str, _chain_id: u64) -> anyhow::Result<Option<String>> {
+        Ok(None)
+    }
+
+    async fn send_token_transfer(&self, _transfer: &CrossChainTransferRequest) -> anyhow::Result<String> {
+        let sent = self.sent.fetch_add(1, Ordering::SeqCst) + 1;
+        Ok(format!("ccip_msg_{}", sent))
+    }
+}
+
+async fn transfer_router(messaging: Arc<CountingMessaging>) -> Router {
+    let cross_chain = CrossChainService::with_sync_legs(
+        Arc::new(UnusedLeg),
+        messaging,
+        Arc::new(UnusedLeg),
+        Vec::new(),
+        CrossChainSyncConfig::default(),
+    ).await.unwrap();
+
+    let reader = Arc::new(InMemoryVaultReadService::new());
+    reader.upsert_vault(vault_record()).await;
+    build_api_router(ApiState::new(reader).with_cross_chain(Arc::new(cross_chain)))
+}
+
+fn transfer_body(idempotency_key: &str, amount: u64) -> serde_json::Value {
+    json!({
+        "idempotency_key": idempotency_key,
+        "source_chain": 1,
+        "destination_chain": 43114,
+        "token_address": "0x742d35Cc6634C0532925a3b8D4C9db96C4b4Db44",
+        "amount": amount,
+        "receiver": "0x742d35Cc6634C0532925a3b8D4C9db96C4b4Db44",
+    })
+}
+
+#[tokio::test]
+async fn test_retried_transfer_returns_original_response() {
+    let messaging = Arc::new(CountingMessaging::default());
+    let router = transfer_router(messaging.clone()).await;
+
+    let (first_status, first) = post_json_as::<CrossChainTransferResponse>(
+        router.clone(), TRANSFER_URI, "client-a", transfer_body("client-retry-1", 5_000_000),
+    ).await;
+    let (retry_status, retry) = post_json_as::<CrossChainTransferResponse>(
+        router, TRANSFER_URI, "client-a", transfer_body("client-retry-1", 5_000_000),
+    ).await;
+
+    assert_eq!(first_status, StatusCode::OK);
+    assert_eq!(retry_status, StatusCode::OK);
+    assert_eq!(retry.data, first.data);
+    assert_eq!(first.data.unwrap().message_id, "ccip_msg_1");
+    assert_eq!(messaging.sent.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_reused_key_with_different_transfer_is_conflict() {
+    let messaging = Arc::new(CountingMessaging::default());
+    let router = transfer_router(messaging.clone()).await;
+
+    post_json_as::<CrossChainTransferResponse>(
+        router.clone(), TRANSFER_URI, "client-a", transfer_body("client-key", 5_000_000),
+    ).await;
+    let (status, body) = post_json_as::<serde_json::Value>(
+        router.clone(), TRANSFER_URI, "client-a", transfer_body("client-key", 6_000_000),
+    ).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert!(!body.success);
+
+    let (status, _) = post_json_as::<serde_json::Value>(router, TRANSFER_URI, "client-a", transfer_body("", 5_000_000)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(messaging.sent.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_transfer_keys_are_scoped_to_the_caller() {
+    let messaging = Arc::new(CountingMessaging::default());
+    let router = transfer_router(messaging.clone()).await;
+
+    let (status, _) = post_json::<serde_json::Value>(router.clone(), TRANSFER_URI, transfer_body("shared-key", 5_000_000)).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    post_json_as::<CrossChainTransferResponse>(
+        router.clone(), TRANSFER_URI, "client-a", transfer_body("shared-key", 5_000_000),
+    ).await;
+    // Another client's identical key is neither a conflict nor a replay of client-a's transfer
+    let (status, other) = post_json_as::<CrossChainTransferResponse>(
+        router, TRANSFER_URI, "client-b", transfer_body("shared-key", 6_000_000),
+    ).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(other.data.unwrap().message_id, "ccip_msg_2");
+    assert_eq!(messaging.sent.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,157 @@
+use axum::{http::StatusCode, Router};
+use exposure_detector::*;
+use rtf_api::*;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+mod common;
+use common::{get, vault_record};
+
+fn origin_proof(fund_id: &str) -> FundOriginProof {
+    let legal_ancestry = LegalAncestry {
+        legal_entity_id: format!("{}-lp", fund_id),
+        incorporation_documents: Vec::new(),
+        regulatory_approvals: Vec::new(),
+        compliance_certifications: Vec::new(),
+        legal_opinion_hash: "legal_opinion".to_string(),
+        jurisdiction_chain: vec!["US-DE".to_string()],
+    };
+    let dao_ancestry = DaoAncestry {
+        governance_contract_address: "gov".to_string(),
+        dao_proposal_history: Vec::new(),
+        voting_power_distribution: HashMap::new(),
+        governance_token_address: "token".to_string(),
+        multisig_signers: Vec::new(),
+        governance_parameters: HashMap::new(),
+    };
+    let circuit_ancestry = CircuitAncestry {
+        zknav_circuit_hash: "circuit".to_string(),
+        verification_key_hash: "vk".to_string(),
+        circuit_parameters: HashMap::new(),
+        trusted_setup_ceremony: "ceremony".to_string(),
+        circuit_audit_reports: Vec::new(),
+        upgrade_history: Vec::new(),
+    };
+
+    let snapshot_data = format!(r#"{{"fund_id":"{}"}}"#, fund_id);
+    FundOriginProof {
+        vault_origin_hash: format!("origin_{}", fund_id),
+        signed_snapshot: SignedSnapshot {
+            snapshot_hash: snapshot_data_hash(&snapshot_data),
+            snapshot_data,
+            signatures: Vec::new(),
+            merkle_root: ancestry_merkle_root(&legal_ancestry, &dao_ancestry, &circuit_ancestry),
+            block_height: 0,
+            timestamp: 0,
+        },
+        legal_ancestry,
+        dao_ancestry,
+        circuit_ancestry,
+        whitelist_status: WhitelistStatus::ExplicitlyWhitelisted,
+        fork_derivation_proof: None,
+        created_at: 0,
+        version: 1,
+    }
+}
+
+async fn register(service: &FundExposureService, fund_id: &str) {
+    let metadata = FundMetadata {
+        fund_id: fund_id.to_string(),
+        name: fund_id.to_string(),
+        manager: "manager".to_string(),
+        inception_date: 0,
+        fund_origin_proof: origin_proof(fund_id),
+        legal_structure: "LP".to_string(),
+        domicile: "US-DE".to_string(),
+        base_currency: "USD".to_string(),
+        investment_strategy: "fund of funds".to_string(),
+        target_assets: Vec::new(),
+    };
+    service.register_fund(metadata).await.unwrap();
+    service.update_fund_assets(fund_id, 1_000).await.unwrap();
+}
+
+/// fund-a holds 40% in fund-b and 20% in fund-c; fund-b holds 50% in fund-d
+async fn exposure_router() -> Router {
+    // Snapshots are unsigned, so no signer roles are required
+    let policy = OriginSignaturePolicy { signers: HashMap::new(), required_roles: BTreeSet::new() };
+    let service = FundExposureService::new(5, 0.25).await.unwrap().with_origin_policy(policy);
+    for fund_id in ["fund-a", "fund-b", "fund-c", "fund-d"] {
+        register(&service, fund_id).await;
+    }
+    for (from, to, amount) in [("fund-a", "fund-b", 400), ("fund-a", "fund-c", 200), ("fund-b", "fund-d", 500)] {
+        service.add_fund_exposure(
+            from.to_string(),
+            to.to_string(),
+            amount,
+            ExposureType::DirectInvestment,
+            "proof".to_string(),
+        ).await.unwrap();
+    }
+
+    let reader = Arc::new(InMemoryVaultReadService::new());
+    reader.upsert_vault(vault_record()).await;
+    build_api_router(ApiState::new(reader).with_exposure_service(Arc::new(service)))
+}
+
+#[tokio::test]
+async fn test_exposure_endpoint_returns_analysis() {
+    let (status, body) = get::<FundExposureReport>(exposure_router().await, "/api/v1/analytics/exposure/fund-a").await;
+
+    assert_eq!(status, StatusCode::OK);
+    let report = body.data.unwrap();
+    assert_eq!(report.analysis.fund_id, "fund-a");
+    assert_eq!(report.analysis.direct_exposures.len(), 2);
+    assert_eq!(report.flattening.root_fund_id, "fund-a");
+    assert_eq!(report.flattening.total_recursion_depth, 2);
+    let nested = &report.flattening.flattened_exposures["fund-b_fund-d"];
+    assert!((nested.cumulative_weight - 0.2).abs() < 1e-9);
+    assert!(report.dot.is_none());
+}
+
+#[tokio::test]
+async fn test_exposure_query_params_limit_flattening() {
+    let uri = "/api/v1/analytics/exposure/fund-a?depth=1&threshold=3000";
+    let (status, body) = get::<FundExposureReport>(exposure_router().await, uri).await;
+
+    assert_eq!(status, StatusCode::OK);
+    let flattening = body.data.unwrap().flattening;
+    assert_eq!(flattening.weight_threshold_bps, 3000);
+    assert_eq!(flattening.total_recursion_depth, 1);
+    let mut keys: Vec<&String> = flattening.flattened_exposures.keys().collect();
+    keys.sort();
+    assert_eq!(keys, vec!["fund-a_fund-b"]);
+}
+
+#[tokio::test]
+async fn test_exposure_dot_export_contains_nodes_and_edges() {
+    let (status, body) = get::<FundExposureReport>(exposure_router().await, "/api/v1/analytics/exposure/fund-a?dot=true").await;
+
+    assert_eq!(status, StatusCode::OK);
+    let dot = body.data.unwrap().dot.unwrap();
+    assert!(dot.starts_with("digraph exposure {"));
+    assert!(dot.contains(r#""fund-a" [style=bold];"#));
+    for fund in ["fund-b", "fund-c", "fund-d"] {
+        assert!(dot.contains(&format!("\"{}\";", fund)));
+    }
+    assert!(dot.contains(r#""fund-a" -> "fund-b" [label="40.00% (40.00% cumulative)"];"#));
+    assert!(dot.contains(r#""fund-a" -> "fund-c" [label="20.00% (20.00% cumulative)"];"#));
+    assert!(dot.contains(r#""fund-b" -> "fund-d" [label="50.00% (20.00% cumulative)"];"#));
+}
+
+#[tokio::test]
+async fn test_exposure_rejects_invalid_params_and_unknown_fund() {
+    for (uri, field) in [
+        ("/api/v1/analytics/exposure/fund-a?depth=6", "depth"),
+        ("/api/v1/analytics/exposure/fund-a?depth=0", "depth"),
+        ("/api/v1/analytics/exposure/fund-a?threshold=0", "threshold"),
+        ("/api/v1/analytics/exposure/fund-a?threshold=10001", "threshold"),
+    ] {
+        let (status, body) = get::<FundExposureReport>(exposure_router().await, uri).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", uri);
+        assert!(body.error.unwrap().contains(field));
+    }
+
+    let (status, _) = get::<FundExposureReport>(exposure_router().await, "/api/v1/analytics/exposure/missing").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
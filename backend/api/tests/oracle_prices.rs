@@ -0,0 +1,55 @@
+use axum::{http::StatusCode, Router};
+use rtf_api::*;
+use rtf_oracle::{AggregatedPrice, AggregationPolicy, OracleReading, OracleService};
+use std::sync::Arc;
+
+mod common;
+use common::{get, vault_record};
+
+const PRICES_URI: &str = "/api/v1/oracles/prices";
+
+/// SOL/USD has four feeds, one of them manipulated; BTC/USD has a single feed and no quorum
+async fn oracle_router() -> (Router, i64) {
+    let service = OracleService::new(AggregationPolicy::default()).await.unwrap();
+    let now = chrono::Utc::now().timestamp_millis();
+    for (feed, price) in [("pyth", 101.0), ("chainlink", 100.0), ("switchboard", 99.0), ("manipulated", 150.0)] {
+        let reading = OracleReading { oracle_id: feed.to_string(), price, timestamp: now };
+        service.record_reading("SOL/USD", reading).await;
+    }
+    let reading = OracleReading { oracle_id: "pyth".to_string(), price: 60_000.0, timestamp: now };
+    service.record_reading("BTC/USD", reading).await;
+
+    let reader = Arc::new(InMemoryVaultReadService::new());
+    reader.upsert_vault(vault_record()).await;
+    let router = build_api_router(ApiState::new(reader).with_oracle_service(Arc::new(service)));
+    (router, now)
+}
+
+#[tokio::test]
+async fn test_prices_include_contributing_feeds_and_dispersion() {
+    let (router, now) = oracle_router().await;
+    let (status, response) = get::<Vec<AggregatedPrice>>(router, PRICES_URI).await;
+
+    assert_eq!(status, StatusCode::OK);
+    let prices = response.data.unwrap();
+    // BTC/USD lacks a quorum and is left out rather than priced from one feed
+    assert_eq!(prices.len(), 1);
+    let price = &prices[0];
+    assert_eq!(price.symbol, "SOL/USD");
+    assert_eq!(price.feeds_used, vec!["chainlink", "pyth", "switchboard"]);
+    assert!((price.price - 100.0).abs() < 1e-9);
+    assert_eq!(price.dispersion_bps, 100);
+    assert_eq!(price.as_of, now);
+}
+
+#[tokio::test]
+async fn test_single_symbol_query() {
+    let (router, _) = oracle_router().await;
+    let (status, response) = get::<Vec<AggregatedPrice>>(router.clone(), &format!("{}?symbol=SOL/USD", PRICES_URI)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response.data.unwrap()[0].feeds_used.len(), 3);
+
+    let (status, response) = get::<Vec<AggregatedPrice>>(router, &format!("{}?symbol=BTC/USD", PRICES_URI)).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert!(response.error.unwrap().contains("BTC/USD"));
+}
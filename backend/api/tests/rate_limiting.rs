@@ -0,0 +1,59 @@
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{header, Request, StatusCode},
+};
+use rtf_api::*;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+fn limited_router(max_requests_per_minute: u32) -> axum::Router {
+    let state = ApiState::new(Arc::new(InMemoryVaultReadService::new()))
+        .with_rate_limiter(Arc::new(RateLimiterService::new(max_requests_per_minute)));
+    build_api_router(state)
+}
+
+fn request(peer: &str) -> Request<Body> {
+    let mut request = Request::builder()
+        .uri("/api/v1/vaults/missing")
+        .body(Body::empty())
+        .unwrap();
+    let peer: SocketAddr = format!("{}:40000", peer).parse().unwrap();
+    request.extensions_mut().insert(ConnectInfo(peer));
+    request
+}
+
+#[tokio::test]
+async fn test_request_over_limit_returns_429_with_retry_after() {
+    let router = limited_router(2);
+
+    for _ in 0..2 {
+        let response = router.clone().oneshot(request("198.51.100.1")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    let response = router.clone().oneshot(request("198.51.100.1")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers()[header::RETRY_AFTER], "30");
+
+    let response = router.oneshot(request("198.51.100.2")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_spoofed_forwarding_headers_share_the_peer_bucket() {
+    let router = limited_router(2);
+
+    for forwarded in ["203.0.113.1", "203.0.113.2"] {
+        let mut spoofed = request("198.51.100.1");
+        spoofed.headers_mut().insert("x-forwarded-for", forwarded.parse().unwrap());
+        let response = router.clone().oneshot(spoofed).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    let mut spoofed = request("198.51.100.1");
+    spoofed.headers_mut().insert("x-forwarded-for", "203.0.113.3".parse().unwrap());
+    let response = router.oneshot(spoofed).await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+}
@@ -0,0 +1,162 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use futures::StreamExt;
+use rtf_api::*;
+use rtf_governance::{DAOType, GovernanceConfig, GovernanceSystem, ProposalType};
+use serde_json::json;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+mod common;
+use common::{get, post_json, vault_record, VAULT_ID};
+
+async fn vault_reader() -> Arc<InMemoryVaultReadService> {
+    let reader = Arc::new(InMemoryVaultReadService::new());
+    reader.upsert_vault(vault_record()).await;
+    reader
+}
+
+async fn report_generator(reader: Arc<InMemoryVaultReadService>) -> ReportGenerator {
+    let governance = GovernanceSystem::new(GovernanceConfig::default()).await.unwrap();
+    governance.submit_proposal(
+        DAOType::LP,
+        ProposalType::ParameterChange {
+            parameter: "management_fee".to_string(),
+            old_value: "2%".to_string(),
+            new_value: "1.5%".to_string(),
+        },
+        "Lower fee, effective Q3".to_string(),
+        "Reduce the management fee".to_string(),
+        "lp_committee".to_string(),
+    ).await.unwrap();
+
+    ReportGenerator::new(reader).with_governance(Arc::new(governance))
+}
+
+async fn report_router() -> Router {
+    let reader = vault_reader().await;
+    let reports = report_generator(reader.clone()).await;
+    build_api_router(ApiState::new(reader).with_report_generator(Arc::new(reports)))
+}
+
+fn two_section_spec() -> serde_json::Value {
+    let now = chrono::Utc::now().timestamp();
+    json!({
+        "vault_ids": [VAULT_ID],
+        "sections": ["vault_performance", "governance_activity"],
+        "period_start": now - 3_600,
+        "period_end": now + 3_600,
+    })
+}
+
+#[tokio::test]
+async fn test_json_report_contains_selected_sections() {
+    let (status, body) = post_json::<Report>(report_router().await, "/api/v1/analytics/reports", two_section_spec()).await;
+
+    assert_eq!(status, StatusCode::OK);
+    let report = body.data.unwrap();
+    let sections: Vec<ReportSection> = report.sections.iter().map(|table| table.section).collect();
+    assert_eq!(sections, vec![ReportSection::VaultPerformance, ReportSection::GovernanceActivity]);
+
+    let performance = &report.sections[0];
+    assert_eq!(performance.columns[0], "vault_id");
+    assert_eq!(performance.rows.len(), 1);
+    assert_eq!(performance.rows[0][0], json!(VAULT_ID));
+    assert_eq!(performance.rows[0][3], json!(500));
+
+    let governance = &report.sections[1];
+    assert_eq!(governance.rows.len(), 1);
+    assert_eq!(governance.rows[0].len(), governance.columns.len());
+    assert_eq!(governance.rows[0][2], json!("Lower fee, effective Q3"));
+}
+
+#[tokio::test]
+async fn test_csv_report_streams_headers_per_section() {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/v1/analytics/reports?format=csv")
+        .header("content-type", "application/json")
+        .body(Body::from(two_section_spec().to_string()))
+        .unwrap();
+    let response = report_router().await.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "text/csv; charset=utf-8");
+    let csv = String::from_utf8(to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+
+    assert_eq!(
+        lines[0],
+        "section,vault_id,period_start,period_end,total_return_bps,annualized_return_bps,volatility_bps,max_drawdown_bps,sharpe_ratio"
+    );
+    assert_eq!(lines[1], "vault_performance,vault-1,1668464000,1700000000,500,480,320,150,1.2");
+    assert_eq!(lines[2], "");
+    assert_eq!(
+        lines[3],
+        "section,proposal_id,dao_type,title,status,created_at,votes_for,votes_against,votes_abstain"
+    );
+    // Titles with commas are quoted
+    assert!(lines[4].starts_with("governance_activity,"));
+    assert!(lines[4].contains(",LP,\"Lower fee, effective Q3\",Active,"));
+    assert_eq!(lines.len(), 5);
+}
+
+#[tokio::test]
+async fn test_report_rejects_unavailable_sections_and_empty_periods() {
+    let mut spec = two_section_spec();
+    spec["sections"] = json!(["esg_scores"]);
+    let (status, body) = post_json::<Report>(report_router().await, "/api/v1/analytics/reports", spec).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body.error.unwrap().contains("esg_scores is not available"));
+
+    let mut spec = two_section_spec();
+    spec["period_end"] = spec["period_start"].clone();
+    let (status, _) = post_json::<Report>(report_router().await, "/api/v1/analytics/reports", spec).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_json_report_streams_one_chunk_per_section() {
+    let spec: ReportSpec = serde_json::from_value(two_section_spec()).unwrap();
+    let chunks: Vec<String> = Arc::new(report_generator(vault_reader().await).await)
+        .stream(spec, ReportFormat::Json)
+        .map(|chunk| chunk.unwrap())
+        .collect()
+        .await;
+
+    // Envelope opening, two sections, envelope closing
+    assert_eq!(chunks.len(), 4);
+    let response: ApiResponse<Report> = serde_json::from_str(&chunks.concat()).unwrap();
+    assert!(response.success);
+    assert_eq!(response.data.unwrap().sections.len(), 2);
+}
+
+#[tokio::test]
+async fn test_get_report_reads_spec_from_query() {
+    let now = chrono::Utc::now().timestamp();
+    let uri = format!(
+        "/api/v1/analytics/reports?vault_ids={}&sections=vault_performance,governance_activity&period_start={}&period_end={}",
+        VAULT_ID, now - 3_600, now + 3_600
+    );
+    let (status, body) = get::<Report>(report_router().await, &uri).await;
+
+    assert_eq!(status, StatusCode::OK);
+    let report = body.data.unwrap();
+    let sections: Vec<ReportSection> = report.sections.iter().map(|table| table.section).collect();
+    assert_eq!(sections, vec![ReportSection::VaultPerformance, ReportSection::GovernanceActivity]);
+    assert_eq!(report.sections[0].rows[0][0], json!(VAULT_ID));
+
+    let request = Request::builder().uri(format!("{}&format=csv", uri)).body(Body::empty()).unwrap();
+    let response = report_router().await.oneshot(request).await.unwrap();
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "text/csv; charset=utf-8");
+    let csv = String::from_utf8(to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()).unwrap();
+    assert_eq!(csv.lines().count(), 5);
+
+    let uri = format!("/api/v1/analytics/reports?sections=fund_flows&period_start={}&period_end={}", now, now + 1);
+    let (status, body) = get::<Report>(report_router().await, &uri).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body.error.unwrap().contains("unknown section fund_flows"));
+}
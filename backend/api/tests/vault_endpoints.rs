@@ -0,0 +1,46 @@
+use axum::http::StatusCode;
+use rtf_api::*;
+
+mod common;
+use common::{get, test_router, vault_record};
+
+#[tokio::test]
+async fn test_get_vault_returns_summary() {
+    let (status, body) = get::<VaultSummary>(test_router().await, "/api/v1/vaults/vault-1").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.success);
+    assert_eq!(body.data, Some(vault_record().summary));
+}
+
+#[tokio::test]
+async fn test_get_vault_nav_returns_nav() {
+    let (status, body) = get::<VaultNav>(test_router().await, "/api/v1/vaults/vault-1/nav").await;
+
+    assert_eq!(status, StatusCode::OK);
+    let nav = body.data.unwrap();
+    assert_eq!(nav.net_asset_value, nav.total_assets - nav.total_liabilities);
+    assert_eq!(nav, vault_record().nav);
+}
+
+#[tokio::test]
+async fn test_get_vault_performance_returns_metrics() {
+    let (status, body) = get::<VaultPerformance>(test_router().await, "/api/v1/vaults/vault-1/performance").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body.data, Some(vault_record().performance));
+}
+
+#[tokio::test]
+async fn test_unknown_vault_returns_404() {
+    for uri in [
+        "/api/v1/vaults/missing",
+        "/api/v1/vaults/missing/nav",
+        "/api/v1/vaults/missing/performance",
+    ] {
+        let (status, body) = get::<serde_json::Value>(test_router().await, uri).await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{}", uri);
+        assert!(!body.success);
+        assert_eq!(body.error, Some("Vault missing not found".to_string()));
+    }
+}
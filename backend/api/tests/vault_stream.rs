@@ -0,0 +1,129 @@
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use futures::StreamExt;
+use rtf_api::*;
+use std::sync::Arc;
+use std::time::Duration;
+use tower::ServiceExt;
+
+mod common;
+use common::{post_json, vault_record, VAULT_ID};
+
+#[tokio::test]
+async fn test_subscriber_receives_published_nav_update() {
+    let reader = Arc::new(InMemoryVaultReadService::new());
+    reader.upsert_vault(vault_record()).await;
+    let events = Arc::new(VaultEventBus::default());
+    let router = build_api_router(ApiState::new(reader).with_vault_events(events.clone()));
+
+    let request = Request::builder().uri("/api/v1/vaults/vault-1/stream").body(Body::empty()).unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "text/event-stream");
+
+    let mut other_vault_nav = vault_record().nav;
+    other_vault_nav.vault_id = "vault-2".to_string();
+    events.publish(VaultEvent::NavUpdate { nav: other_vault_nav });
+    let mut nav = vault_record().nav;
+    nav.nav_per_share = 1_060_000;
+    assert_eq!(events.publish(VaultEvent::NavUpdate { nav: nav.clone() }), 1);
+
+    let mut body = response.into_body().into_data_stream();
+    let frame = tokio::time::timeout(Duration::from_secs(5), body.next()).await.unwrap().unwrap().unwrap();
+    let frame = String::from_utf8(frame.to_vec()).unwrap();
+
+    assert!(frame.starts_with("event: nav_update\n"));
+    let data = frame.lines().find_map(|line| line.strip_prefix("data: ")).unwrap();
+    let event: VaultEvent = serde_json::from_str(data).unwrap();
+    assert_eq!(event, VaultEvent::NavUpdate { nav });
+}
+
+#[tokio::test]
+async fn test_lagging_subscriber_is_dropped_without_blocking_publisher() {
+    let events = VaultEventBus::new(2);
+    let stream = vault_event_stream(events.subscribe(), VAULT_ID.to_string());
+
+    for epoch in 0..5 {
+        events.publish(VaultEvent::DriftAlert {
+            vault_id: VAULT_ID.to_string(),
+            epoch,
+            drift_bps: 150,
+            tolerance_bps: 100,
+        });
+    }
+
+    let received: Vec<_> = tokio::time::timeout(Duration::from_secs(5), stream.collect::<Vec<_>>()).await.unwrap();
+    assert!(received.is_empty());
+}
+
+#[tokio::test]
+async fn test_deposit_and_redemption_handlers_publish_events() {
+    let reader = Arc::new(InMemoryVaultReadService::new());
+    reader.upsert_vault(vault_record()).await;
+    let events = Arc::new(VaultEventBus::default());
+    let mut subscriber = events.subscribe();
+    let router = build_api_router(ApiState::new(reader).with_vault_events(events));
+
+    let deposit = serde_json::json!({
+        "user_address": "investor",
+        "tranche_index": 0,
+        "amount": 10_200_000u64,
+        "max_slippage_bps": 50,
+    });
+    let (status, _) = post_json::<DepositResponse>(router.clone(), "/api/v1/vaults/vault-1/deposit", deposit).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        subscriber.try_recv().unwrap(),
+        VaultEvent::DepositRequested {
+            vault_id: VAULT_ID.to_string(),
+            user_address: "investor".to_string(),
+            tranche_index: 0,
+            amount: 10_200_000,
+            expected_shares: 10_000_000,
+        }
+    );
+
+    let redemption = serde_json::json!({
+        "user_address": "investor",
+        "tranche_index": 1,
+        "shares": 1_000_000u64,
+        "max_slippage_bps": 50,
+    });
+    let (status, _) = post_json::<RedemptionResponse>(router, "/api/v1/vaults/vault-1/redeem", redemption).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        subscriber.try_recv().unwrap(),
+        VaultEvent::RedemptionRequested {
+            vault_id: VAULT_ID.to_string(),
+            user_address: "investor".to_string(),
+            tranche_index: 1,
+            shares: 1_000_000,
+            expected_amount: 1_110_000,
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_nav_update_is_published_to_subscribers() {
+    let events = Arc::new(VaultEventBus::default());
+    let reader = InMemoryVaultReadService::new().with_vault_events(events.clone());
+    reader.upsert_vault(vault_record()).await;
+    let mut subscriber = events.subscribe();
+
+    let mut nav = vault_record().nav;
+    nav.nav_per_share = 1_060_000;
+    nav.computed_at += 3_600;
+    assert!(reader.update_nav(nav.clone()).await);
+    assert_eq!(subscriber.try_recv().unwrap(), VaultEvent::NavUpdate { nav: nav.clone() });
+    assert_eq!(reader.get_vault(VAULT_ID).await.unwrap().unwrap().nav_per_share, 1_060_000);
+
+    // Re-upserting an unchanged record publishes nothing; unknown vaults are not created
+    let mut record = vault_record();
+    record.nav = nav.clone();
+    reader.upsert_vault(record).await;
+    nav.vault_id = "vault-2".to_string();
+    assert!(!reader.update_nav(nav).await);
+    assert!(subscriber.try_recv().is_err());
+}
@@ -0,0 +1,156 @@
+use axum::http::StatusCode;
+use rtf_api::*;
+use serde_json::json;
+
+mod common;
+use common::{post_json, test_router};
+
+#[tokio::test]
+async fn test_valid_deposit_is_quoted_at_tranche_nav() {
+    let (status, body) = post_json::<DepositResponse>(
+        test_router().await,
+        "/api/v1/vaults/vault-1/deposit",
+        json!({ "user_address": "investor", "tranche_index": 0, "amount": 10_200_000, "max_slippage_bps": 50 }),
+    ).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.success);
+    let deposit = body.data.unwrap();
+    assert_eq!(deposit.nav_per_share, 1_020_000);
+    assert_eq!(deposit.expected_shares, 10_000_000);
+    assert_eq!(deposit.min_shares_out, 9_950_000);
+}
+
+#[tokio::test]
+async fn test_zero_amount_deposit_is_bad_request() {
+    let (status, body) = post_json::<serde_json::Value>(
+        test_router().await,
+        "/api/v1/vaults/vault-1/deposit",
+        json!({ "user_address": "investor", "tranche_index": 0, "amount": 0, "max_slippage_bps": 50 }),
+    ).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(!body.success);
+    assert_eq!(body.error, Some("Invalid field `amount`: must be greater than zero".to_string()));
+}
+
+#[tokio::test]
+async fn test_unknown_tranche_is_unprocessable() {
+    let (status, body) = post_json::<serde_json::Value>(
+        test_router().await,
+        "/api/v1/vaults/vault-1/deposit",
+        json!({ "user_address": "investor", "tranche_index": 7, "amount": 10_000_000, "max_slippage_bps": 50 }),
+    ).await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body.error, Some("Unknown tranche index 7 for vault vault-1".to_string()));
+}
+
+#[tokio::test]
+async fn test_excessive_slippage_is_bad_request() {
+    let (status, body) = post_json::<serde_json::Value>(
+        test_router().await,
+        "/api/v1/vaults/vault-1/redeem",
+        json!({ "user_address": "investor", "tranche_index": 1, "shares": 1_000_000, "max_slippage_bps": 5_000 }),
+    ).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body.error.unwrap().starts_with("Invalid field `max_slippage_bps`"));
+}
+
+#[tokio::test]
+async fn test_valid_redemption_is_quoted_at_tranche_nav() {
+    let (status, body) = post_json::<RedemptionResponse>(
+        test_router().await,
+        "/api/v1/vaults/vault-1/redeem",
+        json!({ "user_address": "investor", "tranche_index": 1, "shares": 1_000_000, "max_slippage_bps": 100 }),
+    ).await;
+
+    assert_eq!(status, StatusCode::OK);
+    let redemption = body.data.unwrap();
+    assert_eq!(redemption.expected_amount, 1_110_000);
+    assert_eq!(redemption.min_amount_out, 1_098_900);
+}
+
+#[tokio::test]
+async fn test_nine_decimal_vault_is_quoted_at_its_own_scale() {
+    let mut record = common::vault_record();
+    record.summary.nav_decimals = 9;
+    for tranche in &mut record.summary.tranches {
+        tranche.nav_per_share *= 1_000;
+        tranche.min_deposit *= 1_000;
+        tranche.max_deposit *= 1_000;
+        tranche.total_supply *= 1_000;
+    }
+    let reader = std::sync::Arc::new(InMemoryVaultReadService::new());
+    reader.upsert_vault(record).await;
+    let router = build_api_router(ApiState::new(reader));
+
+    // Same positions as the 6-decimal tests, with three more decimals on every value
+    let (status, body) = post_json::<DepositResponse>(
+        router.clone(),
+        "/api/v1/vaults/vault-1/deposit",
+        json!({ "user_address": "investor", "tranche_index": 0, "amount": 10_200_000_000u64, "max_slippage_bps": 50 }),
+    ).await;
+    assert_eq!(status, StatusCode::OK);
+    let deposit = body.data.unwrap();
+    assert_eq!(deposit.expected_shares, 10_000_000_000);
+    assert_eq!(deposit.min_shares_out, 9_950_000_000);
+
+    let (status, body) = post_json::<RedemptionResponse>(
+        router,
+        "/api/v1/vaults/vault-1/redeem",
+        json!({ "user_address": "investor", "tranche_index": 1, "shares": deposit.expected_shares, "max_slippage_bps": 100 }),
+    ).await;
+    assert_eq!(status, StatusCode::OK);
+    let redemption = body.data.unwrap();
+    assert_eq!(redemption.expected_amount, 11_100_000_000);
+    assert_eq!(redemption.min_amount_out, 10_989_000_000);
+}
+
+#[tokio::test]
+async fn test_deposit_from_disallowed_jurisdiction_is_forbidden() {
+    use ed25519_dalek::Signer;
+
+    let attester = ed25519_dalek::SigningKey::from_bytes(&[42; 32]);
+    let kyc_service = compliance::ZkKycService::new_with_providers(
+        "https://api.kilt.io".to_string(),
+        "https://api.fractal.id".to_string(),
+        "app_rtf_worldid".to_string(),
+        "rtf_verified_users".to_string(),
+    ).await.unwrap()
+        .with_trusted_attester("did:kilt:issuer", attester.verifying_key().to_bytes());
+    let eligibility = std::sync::Arc::new(compliance::EligibilityEngine::new(kyc_service));
+    eligibility.set_vault_jurisdictions("vault-1", vec!["EU".to_string()]).await;
+
+    let reader = std::sync::Arc::new(InMemoryVaultReadService::new());
+    reader.upsert_vault(common::vault_record()).await;
+    let router = build_api_router(ApiState::new(reader).with_eligibility_engine(eligibility));
+
+    let mut credential = compliance::KiltCredential {
+        credential_id: "cred_us".to_string(),
+        issuer_did: "did:kilt:issuer".to_string(),
+        subject_did: "did:kilt:investor".to_string(),
+        credential_type: "ResidencyProof".to_string(),
+        claims: json!({ "jurisdiction": "US" }),
+        proof: String::new(),
+        expiry: chrono::Utc::now().timestamp() + 86_400,
+    };
+    credential.proof = hex::encode(attester.sign(&compliance::kilt_credential_digest(&credential)).to_bytes());
+
+    let (status, body) = post_json::<serde_json::Value>(
+        router,
+        "/api/v1/vaults/vault-1/deposit",
+        json!({
+            "user_address": "investor",
+            "tranche_index": 0,
+            "amount": 10_000_000,
+            "max_slippage_bps": 50,
+            "jurisdiction": "US",
+            "kyc_credential": credential,
+        }),
+    ).await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body.error, Some("Deposit not permitted: Jurisdiction US is not permitted for vault vault-1".to_string()));
+}
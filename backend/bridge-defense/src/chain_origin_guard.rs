@@ -1,6 +1,9 @@
 use anyhow::Result;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use domain_tags::DomainTag;
+use std::collections::{HashMap, HashSet};
 use tokio::sync::RwLock;
 use tracing::{info, warn, error};
 
@@ -15,9 +18,102 @@ pub struct ChainOriginGuard {
     cross_chain_validator: CrossChainValidator,
     attestation_cache: RwLock<HashMap<String, CachedAttestation>>,
     fraud_detection: FraudDetectionSystem,
+    validator_sets: ValidatorSetRegistry,
     audit_trail: RwLock<Vec<OriginAuditEvent>>,
 }
 
+/// Committee of ed25519 validators attesting to messages from one chain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainValidatorSet {
+    pub validators: Vec<[u8; 32]>,
+    /// Distinct validator signatures required to accept a message
+    pub threshold: usize,
+}
+
+/// Inbound message body with validator signatures over its origin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OriginSignedMessage {
+    pub source_chain: u64,
+    pub body: Vec<u8>,
+    pub signatures: Vec<OriginSignature>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OriginSignature {
+    pub validator: [u8; 32],
+    pub signature: Vec<u8>,
+}
+
+impl OriginSignedMessage {
+    pub fn decode(message: &[u8]) -> Result<Self> {
+        Ok(serde_json::from_slice(message)?)
+    }
+
+    /// Digest validators sign for a message body from `source_chain`
+    pub fn signing_digest(source_chain: u64, body: &[u8]) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(DomainTag::ChainOrigin.as_bytes());
+        hasher.update(source_chain.to_be_bytes());
+        hasher.update(body);
+        hasher.finalize().into()
+    }
+}
+
+/// Registered validator sets per source chain
+#[derive(Debug, Default)]
+pub struct ValidatorSetRegistry {
+    sets: RwLock<HashMap<u64, ChainValidatorSet>>,
+}
+
+impl ValidatorSetRegistry {
+    /// Register or rotate the validator set for a chain
+    pub async fn update_validator_set(&self, chain_id: u64, set: ChainValidatorSet) -> Result<()> {
+        if set.threshold == 0 || set.threshold > set.validators.len() {
+            return Err(anyhow::anyhow!(
+                "Invalid threshold {} for {} validators on chain {}",
+                set.threshold, set.validators.len(), chain_id
+            ));
+        }
+        info!("🔑 Validator set for chain {} updated ({} validators, threshold {})",
+              chain_id, set.validators.len(), set.threshold);
+        self.sets.write().await.insert(chain_id, set);
+        Ok(())
+    }
+
+    /// Whether the message carries enough valid signatures from `source_chain`'s current set
+    pub async fn verify(&self, source_chain: u64, message: &OriginSignedMessage) -> bool {
+        if message.source_chain != source_chain {
+            warn!("Message claims chain {} but arrived from chain {}", message.source_chain, source_chain);
+            return false;
+        }
+
+        let sets = self.sets.read().await;
+        let Some(set) = sets.get(&source_chain) else {
+            warn!("Rejecting message from unregistered chain {}", source_chain);
+            return false;
+        };
+
+        let digest = OriginSignedMessage::signing_digest(source_chain, &message.body);
+        let mut signers = HashSet::new();
+        for sig in &message.signatures {
+            if !set.validators.contains(&sig.validator) || signers.contains(&sig.validator) {
+                continue;
+            }
+            let Ok(key) = VerifyingKey::from_bytes(&sig.validator) else { continue };
+            let Ok(signature) = Signature::from_slice(&sig.signature) else { continue };
+            if key.verify(&digest, &signature).is_ok() {
+                signers.insert(sig.validator);
+            }
+        }
+
+        if signers.len() < set.threshold {
+            warn!("Only {} of {} required validator signatures for chain {}", signers.len(), set.threshold, source_chain);
+            return false;
+        }
+        true
+    }
+}
+
 /// PRD: "chain-id proof + vault attestation"
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChainOriginProof {
@@ -187,10 +283,21 @@ impl ChainOriginGuard {
             cross_chain_validator: CrossChainValidator::new(config.validator_config).await?,
             attestation_cache: RwLock::new(HashMap::new()),
             fraud_detection: FraudDetectionSystem::new(config.fraud_config).await?,
+            validator_sets: ValidatorSetRegistry::default(),
             audit_trail: RwLock::new(Vec::new()),
         })
     }
 
+    /// Verify a message is signed by the registered validator set for `source_chain`
+    pub async fn verify_chain_origin(&self, source_chain: u64, message: &OriginSignedMessage) -> Result<bool> {
+        Ok(self.validator_sets.verify(source_chain, message).await)
+    }
+
+    /// Register or rotate the validator set trusted for a chain
+    pub async fn update_validator_set(&self, chain_id: u64, set: ChainValidatorSet) -> Result<()> {
+        self.validator_sets.update_validator_set(chain_id, set).await
+    }
+
     /// PRD: "All messages and redemption requests must include chain-id proof + vault attestation"
     /// Comprehensive origin verification for all cross-chain operations
     pub async fn verify_chain_origin_and_vault_attestation(
@@ -460,3 +567,70 @@ impl ChainOriginGuard {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn validator_key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn signed_message(source_chain: u64, signers: &[&SigningKey]) -> OriginSignedMessage {
+        let body = b"nav update".to_vec();
+        let digest = OriginSignedMessage::signing_digest(source_chain, &body);
+        OriginSignedMessage {
+            source_chain,
+            body,
+            signatures: signers.iter().map(|key| OriginSignature {
+                validator: key.verifying_key().to_bytes(),
+                signature: key.sign(&digest).to_bytes().to_vec(),
+            }).collect(),
+        }
+    }
+
+    async fn registry_for(chain_id: u64, keys: &[&SigningKey], threshold: usize) -> ValidatorSetRegistry {
+        let registry = ValidatorSetRegistry::default();
+        registry.update_validator_set(chain_id, ChainValidatorSet {
+            validators: keys.iter().map(|key| key.verifying_key().to_bytes()).collect(),
+            threshold,
+        }).await.unwrap();
+        registry
+    }
+
+    #[tokio::test]
+    async fn test_message_from_known_chain_accepted() {
+        let (a, b, c) = (validator_key(1), validator_key(2), validator_key(3));
+        let registry = registry_for(1, &[&a, &b, &c], 2).await;
+
+        assert!(registry.verify(1, &signed_message(1, &[&a, &b])).await);
+        // Duplicate signatures from one validator don't reach the threshold
+        assert!(!registry.verify(1, &signed_message(1, &[&a, &a])).await);
+    }
+
+    #[tokio::test]
+    async fn test_message_from_unknown_chain_rejected() {
+        let a = validator_key(1);
+        let registry = registry_for(1, &[&a], 1).await;
+
+        assert!(!registry.verify(43114, &signed_message(43114, &[&a])).await);
+        // Claimed origin must match the receiving channel
+        assert!(!registry.verify(43114, &signed_message(1, &[&a])).await);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_after_signer_rotated_out() {
+        let (a, b) = (validator_key(1), validator_key(2));
+        let registry = registry_for(1, &[&a, &b], 1).await;
+        let message = signed_message(1, &[&a]);
+        assert!(registry.verify(1, &message).await);
+
+        registry.update_validator_set(1, ChainValidatorSet {
+            validators: vec![b.verifying_key().to_bytes()],
+            threshold: 1,
+        }).await.unwrap();
+
+        assert!(!registry.verify(1, &message).await);
+    }
+}
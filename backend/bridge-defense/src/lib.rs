@@ -4,12 +4,15 @@
 //! against oracle manipulation, bridge attacks, and cross-chain vulnerabilities.
 
 pub mod meta_oracle_selector;
+pub mod oracle_blacklist_store;
 pub mod zk_message_filter;
 pub mod chain_origin_guard;
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{info, warn, error};
 
@@ -27,6 +30,9 @@ pub struct BridgeDefenseSystem {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DefenseConfig {
     pub oracle_timeout_ms: u64,
+    pub oracle_blacklist_cooldown_ms: u64,
+    /// JSON file the oracle blacklist is persisted to; kept in memory only when unset
+    pub oracle_blacklist_path: Option<PathBuf>,
     pub max_oracle_deviation: f64,
     pub min_oracle_quorum: usize,
     pub message_encryption_enabled: bool,
@@ -38,6 +44,8 @@ impl Default for DefenseConfig {
     fn default() -> Self {
         Self {
             oracle_timeout_ms: 5000,
+            oracle_blacklist_cooldown_ms: 3_600_000, // 1 hour
+            oracle_blacklist_path: None,
             max_oracle_deviation: 0.05, // 5%
             min_oracle_quorum: 3,
             message_encryption_enabled: true,
@@ -66,6 +74,11 @@ pub enum DefenseAlert {
         deviation: f64,
         timestamp: chrono::DateTime<chrono::Utc>,
     },
+    OracleStale {
+        oracle_id: String,
+        staleness_ms: u64,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
     BridgeAttack {
         chain_id: u64,
         attack_type: String,
@@ -81,6 +94,12 @@ pub enum DefenseAlert {
         confidence_score: f64,
         details: String,
     },
+    DataUnavailable {
+        commitment: String,
+        layer: String,
+        reason: String,
+        severity: AlertSeverity,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -96,7 +115,11 @@ impl BridgeDefenseSystem {
     pub async fn new(config: DefenseConfig) -> Result<Self> {
         info!("Initializing RTF Bridge Defense System");
         
-        let meta_oracle = meta_oracle_selector::MetaOracleSelector::new(&config).await?;
+        let mut meta_oracle = meta_oracle_selector::MetaOracleSelector::new((&config).into()).await?;
+        if let Some(path) = &config.oracle_blacklist_path {
+            let store = oracle_blacklist_store::FileOracleBlacklistStore::new(path.clone());
+            meta_oracle = meta_oracle.with_blacklist_store(Arc::new(store)).await?;
+        }
         let message_filter = zk_message_filter::ZkMessageFilter::new(&config).await?;
         let origin_guard = chain_origin_guard::ChainOriginGuard::new(&config).await?;
         
@@ -158,14 +181,21 @@ impl BridgeDefenseSystem {
         }
 
         // 1. Verify chain of origin
-        if !self.origin_guard.verify_chain_origin(source_chain, message).await? {
+        let signed = match chain_origin_guard::OriginSignedMessage::decode(message) {
+            Ok(signed) => signed,
+            Err(e) => {
+                warn!("Malformed origin envelope from chain {}: {}", source_chain, e);
+                return Ok(false);
+            }
+        };
+        if !self.origin_guard.verify_chain_origin(source_chain, &signed).await? {
             warn!("Chain origin verification failed for message from chain {}", source_chain);
             return Ok(false);
         }
 
         // 2. Filter message through zkMessage filter
-        if !self.message_filter.validate_message(message, source_chain).await? {
-            warn!("Message validation failed for chain {}", source_chain);
+        if let Err(e) = self.message_filter.validate_message(&signed.body, source_chain).await {
+            warn!("Message validation failed for chain {}: {}", source_chain, e);
             return Ok(false);
         }
 
@@ -179,12 +209,40 @@ impl BridgeDefenseSystem {
         Ok(true)
     }
 
+    /// Aggregate oracle readings, raising alerts for stale and manipulated feeds
+    pub async fn aggregate_oracle_prices(
+        &self,
+        readings: &[meta_oracle_selector::OracleReading],
+    ) -> Result<meta_oracle_selector::AggregatedPrice> {
+        {
+            let mut metrics = self.metrics.write().await;
+            metrics.oracle_queries_total += 1;
+        }
+
+        let aggregated = match self.meta_oracle.aggregate(readings).await {
+            Ok(aggregated) => aggregated,
+            Err(e) => {
+                self.metrics.write().await.oracle_failures += 1;
+                return Err(e);
+            }
+        };
+
+        for alert in &aggregated.alerts {
+            self.handle_alert(alert.clone()).await?;
+        }
+
+        Ok(aggregated)
+    }
+
     /// Handle defense alerts
     pub async fn handle_alert(&self, alert: DefenseAlert) -> Result<()> {
         match &alert {
             DefenseAlert::OracleManipulation { oracle_id, deviation, .. } => {
                 error!("Oracle manipulation detected: {} with deviation {}", oracle_id, deviation);
-                // Implement oracle blacklisting logic
+                self.meta_oracle.blacklist_oracle(oracle_id, self.meta_oracle.blacklist_cooldown()).await?;
+            }
+            DefenseAlert::OracleStale { oracle_id, staleness_ms, .. } => {
+                warn!("Stale oracle feed excluded: {} ({}ms since last update)", oracle_id, staleness_ms);
             }
             DefenseAlert::BridgeAttack { chain_id, attack_type, severity } => {
                 error!("Bridge attack detected on chain {}: {} (severity: {:?})", 
@@ -201,6 +259,10 @@ impl BridgeDefenseSystem {
                        transaction_hash, details, confidence_score);
                 // Implement fraud response protocols
             }
+            DefenseAlert::DataUnavailable { commitment, layer, reason, severity } => {
+                error!("Committed data unavailable on {}: {} - {} (severity: {:?})",
+                       layer, commitment, reason, severity);
+            }
         }
 
         // Update metrics
@@ -234,6 +296,30 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_manipulated_oracle_blacklisted_after_aggregation() {
+        let config = DefenseConfig::default();
+        let defense_system = BridgeDefenseSystem::new(config).await.unwrap();
+        let now = chrono::Utc::now().timestamp_millis();
+        let readings: Vec<meta_oracle_selector::OracleReading> = [
+            ("chainlink", 100.0),
+            ("pyth", 100.2),
+            ("switchboard", 99.9),
+            ("manipulated", 180.0),
+        ].iter().map(|(oracle_id, price)| meta_oracle_selector::OracleReading {
+            oracle_id: oracle_id.to_string(),
+            price: *price,
+            timestamp: now,
+        }).collect();
+
+        let aggregated = defense_system.aggregate_oracle_prices(&readings).await.unwrap();
+        assert_eq!(aggregated.rejected_oracles, vec!["manipulated".to_string()]);
+        assert!(defense_system.meta_oracle.is_oracle_blacklisted("manipulated").await);
+
+        let aggregated = defense_system.aggregate_oracle_prices(&readings).await.unwrap();
+        assert_eq!(aggregated.blacklisted_oracles, vec!["manipulated".to_string()]);
+    }
+
     #[tokio::test]
     async fn test_metrics_collection() {
         let config = DefenseConfig::default();
@@ -1,9 +1,14 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use tracing::{info, warn, error};
 
+use crate::oracle_blacklist_store::OracleBlacklistStore;
+use crate::DefenseAlert;
+
 /// PRD Section 6: Bridge & Oracle Defense
 /// PRD: "Meta-Oracle Selector (MTR): Latency, fault, and quorum-based relay rotation"
 /// Advanced oracle selection with sophisticated fault tolerance and performance optimization
@@ -16,7 +21,92 @@ pub struct MetaOracleSelector {
     quorum_manager: QuorumManager,
     relay_rotator: RelayRotator,
     performance_metrics: RwLock<HashMap<String, PerformanceMetrics>>,
-    blacklist: RwLock<Vec<String>>,
+    /// Blacklisted oracle ids and when their cooldown lapses
+    blacklist: RwLock<HashMap<String, Instant>>,
+    /// Where cooldowns are persisted so they outlive the process
+    blacklist_store: Option<Arc<dyn OracleBlacklistStore>>,
+    aggregation_policy: AggregationPolicy,
+    last_updates: RwLock<HashMap<String, i64>>,
+}
+
+/// Meta-Oracle Selector configuration
+#[derive(Debug, Clone, Default)]
+pub struct MtrConfig {
+    pub selection_algorithm: SelectionAlgorithm,
+    pub fault_detector: FaultDetector,
+    pub latency_monitor: LatencyMonitor,
+    pub quorum_manager: QuorumManager,
+    pub relay_rotator: RelayRotator,
+    pub aggregation_policy: AggregationPolicy,
+}
+
+impl From<&crate::DefenseConfig> for MtrConfig {
+    fn from(config: &crate::DefenseConfig) -> Self {
+        Self {
+            aggregation_policy: AggregationPolicy {
+                min_oracle_quorum: config.min_oracle_quorum,
+                max_oracle_deviation: config.max_oracle_deviation,
+                oracle_timeout_ms: config.oracle_timeout_ms,
+                ..AggregationPolicy::default()
+            },
+            fault_detector: FaultDetector {
+                blacklist_cooldown_ms: config.oracle_blacklist_cooldown_ms,
+                ..FaultDetector::default()
+            },
+            ..Self::default()
+        }
+    }
+}
+
+/// Robust price aggregation policy
+/// Readings further than `mad_multiplier` median absolute deviations from the median are rejected
+#[derive(Debug, Clone)]
+pub struct AggregationPolicy {
+    pub min_oracle_quorum: usize,
+    pub mad_multiplier: f64,
+    /// Relative tolerance around the median used when the MAD is zero
+    pub max_oracle_deviation: f64,
+    /// Readings older than this are treated as frozen feeds and excluded
+    pub oracle_timeout_ms: u64,
+}
+
+impl Default for AggregationPolicy {
+    fn default() -> Self {
+        Self {
+            min_oracle_quorum: 3,
+            mad_multiplier: 3.0,
+            max_oracle_deviation: 0.05, // 5%
+            oracle_timeout_ms: 5000,
+        }
+    }
+}
+
+/// Single price observation from an oracle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OracleReading {
+    pub oracle_id: String,
+    pub price: f64,
+    pub timestamp: i64,
+}
+
+/// Outcome of robust aggregation across oracle readings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregatedPrice {
+    pub price: f64,
+    pub median_absolute_deviation: f64,
+    pub accepted_oracles: Vec<String>,
+    pub rejected_oracles: Vec<String>,
+    pub stale_oracles: Vec<String>,
+    pub blacklisted_oracles: Vec<String>,
+    pub alerts: Vec<DefenseAlert>,
+}
+
+/// Liveness of an oracle feed based on its most recent reading
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OracleLiveness {
+    pub last_update: Option<i64>,
+    pub staleness_ms: Option<u64>,
+    pub alive: bool,
 }
 
 /// Advanced Oracle Node with comprehensive metrics
@@ -102,6 +192,7 @@ pub struct FaultDetector {
     pub consensus_deviation_threshold: f64,
     pub automatic_blacklisting: bool,
     pub recovery_monitoring: bool,
+    pub blacklist_cooldown_ms: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -141,6 +232,68 @@ pub enum RotationStrategy {
     Hybrid,
 }
 
+impl Default for SelectionAlgorithm {
+    fn default() -> Self {
+        Self {
+            latency_weight: 0.2,
+            fault_weight: 0.2,
+            quorum_weight: 0.1,
+            reputation_weight: 0.2,
+            stake_weight: 0.1,
+            geographic_diversity_weight: 0.1,
+        }
+    }
+}
+
+impl Default for FaultDetector {
+    fn default() -> Self {
+        Self {
+            fault_threshold: 0.1,
+            detection_window_ms: 60_000,
+            consensus_deviation_threshold: 0.05,
+            automatic_blacklisting: true,
+            recovery_monitoring: true,
+            blacklist_cooldown_ms: 3_600_000, // 1 hour
+        }
+    }
+}
+
+impl Default for LatencyMonitor {
+    fn default() -> Self {
+        Self {
+            monitoring_interval_ms: 10_000,
+            latency_threshold_ms: 2_000,
+            jitter_threshold: 0.2,
+            timeout_threshold_ms: 5_000,
+            performance_window_size: 100,
+        }
+    }
+}
+
+impl Default for QuorumManager {
+    fn default() -> Self {
+        Self {
+            minimum_quorum_size: 3,
+            optimal_quorum_size: 5,
+            consensus_threshold: 0.67,
+            byzantine_fault_tolerance: 1,
+            quorum_rotation_interval: 3_600_000,
+        }
+    }
+}
+
+impl Default for RelayRotator {
+    fn default() -> Self {
+        Self {
+            rotation_strategy: RotationStrategy::Hybrid,
+            rotation_interval_ms: 3_600_000,
+            performance_based_rotation: true,
+            geographic_rotation: true,
+            load_balancing: true,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PerformanceMetrics {
     pub total_requests: u64,
@@ -165,10 +318,113 @@ impl MetaOracleSelector {
             quorum_manager: config.quorum_manager,
             relay_rotator: config.relay_rotator,
             performance_metrics: RwLock::new(HashMap::new()),
-            blacklist: RwLock::new(Vec::new()),
+            blacklist: RwLock::new(HashMap::new()),
+            blacklist_store: None,
+            aggregation_policy: config.aggregation_policy,
+            last_updates: RwLock::new(HashMap::new()),
         })
     }
 
+    /// Persist blacklist cooldowns through `store`, restoring the ones that have not lapsed
+    pub async fn with_blacklist_store(mut self, store: Arc<dyn OracleBlacklistStore>) -> Result<Self> {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let now = Instant::now();
+        {
+            let blacklist = self.blacklist.get_mut();
+            for (oracle_id, until_ms) in store.load_entries().await? {
+                if until_ms > now_ms {
+                    let until = now + Duration::from_millis((until_ms - now_ms) as u64);
+                    let entry = blacklist.entry(oracle_id).or_insert(until);
+                    *entry = (*entry).max(until);
+                }
+            }
+            if !blacklist.is_empty() {
+                info!("Restored {} oracle blacklist cooldowns", blacklist.len());
+            }
+        }
+        self.blacklist_store = Some(store);
+        Ok(self)
+    }
+
+    /// Aggregate oracle readings into a manipulation-resistant price
+    ///
+    /// Readings older than `oracle_timeout_ms` are excluded with a staleness alert,
+    /// readings beyond `mad_multiplier * MAD` from the median are dropped, and the
+    /// median of the survivors is returned if at least `min_oracle_quorum` remain.
+    pub async fn aggregate(&self, prices: &[OracleReading]) -> Result<AggregatedPrice> {
+        {
+            let mut last_updates = self.last_updates.write().await;
+            for reading in prices {
+                let last = last_updates.entry(reading.oracle_id.clone()).or_insert(reading.timestamp);
+                *last = (*last).max(reading.timestamp);
+            }
+        }
+
+        // Blacklisted oracles are excluded before consensus and never count toward quorum
+        let (eligible, blacklisted_oracles) = {
+            let mut blacklist = self.blacklist.write().await;
+            let now = Instant::now();
+            blacklist.retain(|_, until| *until > now);
+
+            let (eligible, excluded): (Vec<OracleReading>, Vec<OracleReading>) = prices.iter()
+                .cloned()
+                .partition(|reading| !blacklist.contains_key(&reading.oracle_id));
+            (eligible, excluded.into_iter().map(|reading| reading.oracle_id).collect::<Vec<_>>())
+        };
+
+        let mut aggregated = aggregate_readings(&eligible, &self.aggregation_policy, chrono::Utc::now().timestamp_millis())
+            .map_err(|e| {
+                error!("❌ Oracle aggregation failed with {} blacklisted oracles excluded: {}", blacklisted_oracles.len(), e);
+                e.context(format!("{} blacklisted oracles excluded", blacklisted_oracles.len()))
+            })?;
+        aggregated.blacklisted_oracles = blacklisted_oracles;
+        Ok(aggregated)
+    }
+
+    /// Exclude an oracle from selection and aggregation until `duration` elapses
+    pub async fn blacklist_oracle(&self, oracle_id: &str, duration: Duration) -> Result<()> {
+        warn!("🚫 Blacklisting oracle {} for {:?}", oracle_id, duration);
+        {
+            let mut blacklist = self.blacklist.write().await;
+            let until = Instant::now() + duration;
+            let entry = blacklist.entry(oracle_id.to_string()).or_insert(until);
+            *entry = (*entry).max(until);
+        }
+
+        if let Some(store) = &self.blacklist_store {
+            let until_ms = chrono::Utc::now().timestamp_millis()
+                .saturating_add(duration.as_millis().min(i64::MAX as u128) as i64);
+            store.save_entry(oracle_id, until_ms).await?;
+        }
+        Ok(())
+    }
+
+    /// Whether an oracle is currently serving a blacklist cooldown
+    pub async fn is_oracle_blacklisted(&self, oracle_id: &str) -> bool {
+        is_blacklisted(&*self.blacklist.read().await, oracle_id)
+    }
+
+    /// Cooldown applied to oracles flagged by alerts
+    pub fn blacklist_cooldown(&self) -> Duration {
+        Duration::from_millis(self.fault_detector.blacklist_cooldown_ms)
+    }
+
+    /// Liveness of every registered or previously reporting oracle
+    pub async fn oracle_health(&self) -> HashMap<String, OracleLiveness> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let last_updates = self.last_updates.read().await;
+        let registry = self.oracle_registry.read().await;
+
+        registry.keys().chain(last_updates.keys())
+            .map(|oracle_id| {
+                let last_update = last_updates.get(oracle_id).copied();
+                let staleness_ms = last_update.map(|ts| now.saturating_sub(ts).max(0) as u64);
+                let alive = staleness_ms.map(|ms| ms <= self.aggregation_policy.oracle_timeout_ms).unwrap_or(false);
+                (oracle_id.clone(), OracleLiveness { last_update, staleness_ms, alive })
+            })
+            .collect()
+    }
+
     /// PRD: "Latency, fault, and quorum-based relay rotation"
     /// Advanced oracle selection using multi-criteria optimization
     pub async fn select_optimal_oracles(
@@ -187,7 +443,7 @@ impl MetaOracleSelector {
             .values()
             .filter(|oracle| {
                 oracle.supported_feeds.contains(&feed_type) &&
-                !blacklist.contains(&oracle.node_id) &&
+                !is_blacklisted(&blacklist, &oracle.node_id) &&
                 self.is_oracle_healthy(oracle)
             })
             .collect();
@@ -360,8 +616,10 @@ impl MetaOracleSelector {
         
         // Update blacklist
         if !blacklisted_oracles.is_empty() {
-            let mut blacklist = self.blacklist.write().await;
-            blacklist.extend(blacklisted_oracles.clone());
+            let cooldown = Duration::from_millis(self.fault_detector.blacklist_cooldown_ms);
+            for oracle_id in &blacklisted_oracles {
+                self.blacklist_oracle(oracle_id, cooldown).await?;
+            }
         }
         
         let result = FaultDetectionResult {
@@ -389,3 +647,244 @@ impl MetaOracleSelector {
         oracle.fault_history.len() < 10
     }
 }
+
+fn is_blacklisted(blacklist: &HashMap<String, Instant>, oracle_id: &str) -> bool {
+    blacklist.get(oracle_id).map(|until| *until > Instant::now()).unwrap_or(false)
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// Staleness filtering and median/MAD outlier rejection over a set of readings
+pub fn aggregate_readings(readings: &[OracleReading], policy: &AggregationPolicy, now_ms: i64) -> Result<AggregatedPrice> {
+    let mut stale_oracles = Vec::new();
+    let mut alerts = Vec::new();
+    let (fresh, stale): (Vec<&OracleReading>, Vec<&OracleReading>) = readings.iter()
+        .partition(|reading| now_ms.saturating_sub(reading.timestamp) <= policy.oracle_timeout_ms as i64);
+    for reading in stale {
+        let staleness_ms = now_ms.saturating_sub(reading.timestamp).max(0) as u64;
+        warn!("⏰ Excluding stale oracle {} ({}ms since last update)", reading.oracle_id, staleness_ms);
+        stale_oracles.push(reading.oracle_id.clone());
+        alerts.push(DefenseAlert::OracleStale {
+            oracle_id: reading.oracle_id.clone(),
+            staleness_ms,
+            timestamp: chrono::Utc::now(),
+        });
+    }
+
+    let (valid, invalid): (Vec<&OracleReading>, Vec<&OracleReading>) =
+        fresh.into_iter().partition(|reading| reading.price.is_finite());
+    let mut rejected_oracles: Vec<String> = invalid.iter().map(|r| r.oracle_id.clone()).collect();
+
+    if valid.is_empty() {
+        return Err(anyhow::anyhow!("No fresh, valid oracle readings to aggregate"));
+    }
+
+    let center = median(&mut valid.iter().map(|r| r.price).collect::<Vec<_>>());
+    let mad = median(&mut valid.iter().map(|r| (r.price - center).abs()).collect::<Vec<_>>());
+
+    // A zero MAD means most feeds agree exactly; fall back to a relative band
+    let tolerance = if mad > 0.0 {
+        policy.mad_multiplier * mad
+    } else {
+        policy.max_oracle_deviation * center.abs()
+    };
+
+    let mut accepted = Vec::new();
+    for reading in valid {
+        if (reading.price - center).abs() <= tolerance {
+            accepted.push(reading);
+        } else {
+            warn!("🚫 Rejecting oracle {} price {} (median {}, tolerance {})",
+                  reading.oracle_id, reading.price, center, tolerance);
+            rejected_oracles.push(reading.oracle_id.clone());
+            alerts.push(DefenseAlert::OracleManipulation {
+                oracle_id: reading.oracle_id.clone(),
+                deviation: if center != 0.0 { (reading.price - center).abs() / center.abs() } else { f64::INFINITY },
+                timestamp: chrono::Utc::now(),
+            });
+        }
+    }
+
+    if accepted.len() < policy.min_oracle_quorum {
+        return Err(anyhow::anyhow!(
+            "Oracle quorum not met: {} of {} readings survived outlier rejection, {} required",
+            accepted.len(), readings.len(), policy.min_oracle_quorum
+        ));
+    }
+
+    Ok(AggregatedPrice {
+        price: median(&mut accepted.iter().map(|r| r.price).collect::<Vec<_>>()),
+        median_absolute_deviation: mad,
+        accepted_oracles: accepted.iter().map(|r| r.oracle_id.clone()).collect(),
+        rejected_oracles,
+        stale_oracles,
+        blacklisted_oracles: Vec::new(),
+        alerts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oracle_blacklist_store::FileOracleBlacklistStore;
+
+    fn reading(oracle_id: &str, price: f64) -> OracleReading {
+        OracleReading {
+            oracle_id: oracle_id.to_string(),
+            price,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_extreme_outlier_dropped() {
+        let selector = MetaOracleSelector::new(MtrConfig::default()).await.unwrap();
+        let readings = vec![
+            reading("chainlink", 100.0),
+            reading("pyth", 100.5),
+            reading("switchboard", 99.8),
+            reading("band", 100.2),
+            reading("manipulated", 150.0),
+        ];
+
+        let aggregated = selector.aggregate(&readings).await.unwrap();
+
+        assert_eq!(aggregated.rejected_oracles, vec!["manipulated".to_string()]);
+        assert_eq!(aggregated.accepted_oracles.len(), 4);
+        assert!((aggregated.price - 100.1).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_quorum_failure_when_too_many_rejected() {
+        let config = MtrConfig {
+            aggregation_policy: AggregationPolicy { min_oracle_quorum: 4, ..AggregationPolicy::default() },
+            ..MtrConfig::default()
+        };
+        let selector = MetaOracleSelector::new(config).await.unwrap();
+        let readings = vec![
+            reading("chainlink", 100.0),
+            reading("pyth", 100.0),
+            reading("switchboard", 100.0),
+            reading("manipulated_a", 140.0),
+            reading("manipulated_b", 60.0),
+        ];
+
+        assert!(selector.aggregate(&readings).await.is_err());
+    }
+
+    #[test]
+    fn test_non_finite_readings_rejected() {
+        let readings = vec![
+            reading("chainlink", 100.0),
+            reading("pyth", 100.1),
+            reading("switchboard", 99.9),
+            reading("broken", f64::NAN),
+        ];
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let aggregated = aggregate_readings(&readings, &AggregationPolicy::default(), now).unwrap();
+        assert_eq!(aggregated.rejected_oracles, vec!["broken".to_string()]);
+        assert!((aggregated.price - 100.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_stale_oracle_excluded_from_consensus() {
+        let selector = MetaOracleSelector::new(MtrConfig::default()).await.unwrap();
+        let mut frozen = reading("frozen", 250.0);
+        frozen.timestamp -= 60_000;
+        let readings = vec![
+            reading("chainlink", 100.0),
+            reading("pyth", 100.2),
+            reading("switchboard", 99.9),
+            frozen,
+        ];
+
+        let aggregated = selector.aggregate(&readings).await.unwrap();
+
+        assert_eq!(aggregated.stale_oracles, vec!["frozen".to_string()]);
+        assert!(!aggregated.accepted_oracles.contains(&"frozen".to_string()));
+        assert!((aggregated.price - 100.0).abs() < 1e-9);
+        assert!(matches!(
+            aggregated.alerts.as_slice(),
+            [DefenseAlert::OracleStale { oracle_id, .. }] if oracle_id == "frozen"
+        ));
+
+        let health = selector.oracle_health().await;
+        assert!(!health["frozen"].alive);
+        assert!(health["frozen"].staleness_ms.unwrap() >= 60_000);
+        assert!(health["chainlink"].alive);
+    }
+
+    #[tokio::test]
+    async fn test_blacklisted_oracle_ignored_until_cooldown_lapses() {
+        let selector = MetaOracleSelector::new(MtrConfig::default()).await.unwrap();
+        let readings = vec![
+            reading("chainlink", 100.0),
+            reading("pyth", 100.2),
+            reading("switchboard", 99.9),
+            reading("band", 100.1),
+        ];
+
+        selector.blacklist_oracle("pyth", Duration::from_millis(50)).await.unwrap();
+        let aggregated = selector.aggregate(&readings).await.unwrap();
+        assert_eq!(aggregated.blacklisted_oracles, vec!["pyth".to_string()]);
+        assert!(!aggregated.accepted_oracles.contains(&"pyth".to_string()));
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(!selector.is_oracle_blacklisted("pyth").await);
+        let aggregated = selector.aggregate(&readings).await.unwrap();
+        assert!(aggregated.blacklisted_oracles.is_empty());
+        assert!(aggregated.accepted_oracles.contains(&"pyth".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_quorum_enforced_after_blacklist_exclusions() {
+        let selector = MetaOracleSelector::new(MtrConfig::default()).await.unwrap();
+        let readings = vec![
+            reading("chainlink", 100.0),
+            reading("pyth", 100.2),
+            reading("switchboard", 99.9),
+            reading("band", 100.1),
+        ];
+
+        selector.blacklist_oracle("pyth", Duration::from_secs(60)).await.unwrap();
+        selector.blacklist_oracle("band", Duration::from_secs(60)).await.unwrap();
+
+        assert!(selector.aggregate(&readings).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_blacklist_survives_restart_through_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("oracle_blacklist.json");
+
+        let selector = MetaOracleSelector::new(MtrConfig::default()).await.unwrap()
+            .with_blacklist_store(Arc::new(FileOracleBlacklistStore::new(&path))).await.unwrap();
+        selector.blacklist_oracle("pyth", Duration::from_secs(60)).await.unwrap();
+        selector.blacklist_oracle("band", Duration::from_millis(1)).await.unwrap();
+        drop(selector);
+
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        let restarted = MetaOracleSelector::new(MtrConfig::default()).await.unwrap()
+            .with_blacklist_store(Arc::new(FileOracleBlacklistStore::new(&path))).await.unwrap();
+        assert!(restarted.is_oracle_blacklisted("pyth").await);
+        assert!(!restarted.is_oracle_blacklisted("band").await);
+
+        let readings = vec![
+            reading("chainlink", 100.0),
+            reading("pyth", 100.2),
+            reading("switchboard", 99.9),
+            reading("band", 100.1),
+        ];
+        let aggregated = restarted.aggregate(&readings).await.unwrap();
+        assert_eq!(aggregated.blacklisted_oracles, vec!["pyth".to_string()]);
+    }
+}
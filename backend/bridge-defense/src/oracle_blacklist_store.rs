@@ -0,0 +1,118 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::sync::{Mutex, RwLock};
+
+/// Persistence for the Meta-Oracle Selector blacklist
+/// Cooldowns are stored as wall-clock expiry times (Unix milliseconds) so an oracle
+/// flagged before a restart stays excluded until its cooldown actually lapses.
+#[async_trait]
+pub trait OracleBlacklistStore: Send + Sync {
+    /// Record a cooldown, keeping the later expiry if the oracle is already listed
+    async fn save_entry(&self, oracle_id: &str, blacklisted_until_ms: i64) -> Result<()>;
+
+    /// Every stored cooldown by oracle id, including ones that may have lapsed
+    async fn load_entries(&self) -> Result<HashMap<String, i64>>;
+}
+
+/// Blacklist store backed by process memory
+#[derive(Default)]
+pub struct InMemoryOracleBlacklistStore {
+    entries: RwLock<HashMap<String, i64>>,
+}
+
+impl InMemoryOracleBlacklistStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl OracleBlacklistStore for InMemoryOracleBlacklistStore {
+    async fn save_entry(&self, oracle_id: &str, blacklisted_until_ms: i64) -> Result<()> {
+        let mut entries = self.entries.write().await;
+        let entry = entries.entry(oracle_id.to_string()).or_insert(blacklisted_until_ms);
+        *entry = (*entry).max(blacklisted_until_ms);
+        Ok(())
+    }
+
+    async fn load_entries(&self) -> Result<HashMap<String, i64>> {
+        Ok(self.entries.read().await.clone())
+    }
+}
+
+/// Blacklist store kept in a JSON file on disk
+/// The whole blacklist is rewritten on every save, with lapsed entries pruned, and
+/// swapped into place with a rename so a crash never leaves a truncated file.
+pub struct FileOracleBlacklistStore {
+    path: PathBuf,
+    write_lock: Mutex<()>,
+}
+
+impl FileOracleBlacklistStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    async fn read_entries(&self) -> Result<HashMap<String, i64>> {
+        match tokio::fs::read(&self.path).await {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .with_context(|| format!("Corrupt oracle blacklist at {}", self.path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(HashMap::new()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read oracle blacklist at {}", self.path.display())),
+        }
+    }
+}
+
+#[async_trait]
+impl OracleBlacklistStore for FileOracleBlacklistStore {
+    async fn save_entry(&self, oracle_id: &str, blacklisted_until_ms: i64) -> Result<()> {
+        let _guard = self.write_lock.lock().await;
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut entries = self.read_entries().await?;
+        entries.retain(|_, until| *until > now);
+        let entry = entries.entry(oracle_id.to_string()).or_insert(blacklisted_until_ms);
+        *entry = (*entry).max(blacklisted_until_ms);
+
+        let tmp_path = self.path.with_extension("tmp");
+        tokio::fs::write(&tmp_path, serde_json::to_vec_pretty(&entries)?).await
+            .with_context(|| format!("Failed to write oracle blacklist to {}", tmp_path.display()))?;
+        tokio::fs::rename(&tmp_path, &self.path).await
+            .with_context(|| format!("Failed to replace oracle blacklist at {}", self.path.display()))?;
+        Ok(())
+    }
+
+    async fn load_entries(&self) -> Result<HashMap<String, i64>> {
+        let _guard = self.write_lock.lock().await;
+        self.read_entries().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_file_store_keeps_later_expiry_and_prunes_lapsed_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileOracleBlacklistStore::new(dir.path().join("oracle_blacklist.json"));
+        assert!(store.load_entries().await.unwrap().is_empty());
+
+        let now = chrono::Utc::now().timestamp_millis();
+        store.save_entry("lapsed", now - 1_000).await.unwrap();
+        store.save_entry("pyth", now + 60_000).await.unwrap();
+        store.save_entry("pyth", now + 30_000).await.unwrap();
+
+        let entries = store.load_entries().await.unwrap();
+        assert_eq!(entries.get("pyth"), Some(&(now + 60_000)));
+        assert!(!entries.contains_key("lapsed"));
+    }
+}
@@ -1,8 +1,11 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use domain_tags::DomainTag;
 use tokio::sync::RwLock;
 use tracing::{info, warn, error};
+use zk_proofs::{zksnark, ZKProofManager};
 
 /// PRD Section 6: Bridge & Oracle Defense
 /// PRD: "zkMessage Filter: Bridge relayers cannot inspect message content"
@@ -10,19 +13,134 @@ use tracing::{info, warn, error};
 /// Advanced zero-knowledge message filtering with privacy preservation
 
 pub struct ZkMessageFilter {
-    message_registry: RwLock<HashMap<String, FilteredMessage>>,
+    message_registry: RwLock<HashMap<String, RelayFilteredMessage>>,
     zk_circuit_verifier: ZkCircuitVerifier,
     privacy_engine: PrivacyEngine,
     message_type_classifier: MessageTypeClassifier,
     sender_anonymizer: SenderAnonymizer,
     content_validator: ContentValidator,
     relay_protection: RelayProtection,
+    envelope_validator: MessageEnvelopeValidator,
     audit_trail: RwLock<Vec<AuditEvent>>,
 }
 
-/// PRD: "Bridge relayers cannot inspect message content"
+/// Default cap on inbound message payloads
+pub const DEFAULT_MAX_PAYLOAD_BYTES: usize = 64 * 1024;
+
+/// Wire format of inbound cross-chain messages
+/// The proof's public inputs must commit to the payload, source chain and nonce
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FilteredMessage {
+    pub payload: Vec<u8>,
+    pub proof: EnvelopeProof,
+    pub source_chain: u64,
+    pub nonce: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvelopeProof {
+    pub circuit_id: String,
+    pub proof: zksnark::Proof,
+}
+
+/// Required structure of a decoded payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessagePayload {
+    pub message_type: MessageType,
+    pub data: serde_json::Value,
+}
+
+/// Reasons an inbound envelope is rejected
+#[derive(Debug, thiserror::Error)]
+pub enum MessageFilterError {
+    #[error("Malformed message envelope: {0}")]
+    MalformedEnvelope(String),
+    #[error("Envelope claims source chain {claimed}, received from chain {actual}")]
+    SourceChainMismatch { claimed: u64, actual: u64 },
+    #[error("Payload of {size} bytes exceeds the {max} byte limit")]
+    PayloadTooLarge { size: usize, max: usize },
+    #[error("Payload does not match message schema: {0}")]
+    InvalidSchema(String),
+    #[error("Proof circuit {0} is not registered")]
+    UnknownCircuit(String),
+    #[error("Proof public inputs do not commit to this envelope")]
+    ProofBindingMismatch,
+    #[error("Proof failed verification against circuit {0}")]
+    InvalidProof(String),
+}
+
+/// Public inputs an envelope proof must expose
+pub fn envelope_public_inputs(payload: &[u8], source_chain: u64, nonce: u64) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(DomainTag::BridgeMessage.as_bytes());
+    hasher.update(source_chain.to_be_bytes());
+    hasher.update(nonce.to_be_bytes());
+    hasher.update(payload);
+    hasher.finalize().to_vec()
+}
+
+/// Structural, size and proof checks for inbound envelopes
+#[derive(Debug)]
+pub struct MessageEnvelopeValidator {
+    proof_manager: RwLock<ZKProofManager>,
+    max_payload_bytes: usize,
+}
+
+impl MessageEnvelopeValidator {
+    pub fn new(max_payload_bytes: usize) -> Self {
+        Self {
+            proof_manager: RwLock::new(ZKProofManager::new()),
+            max_payload_bytes,
+        }
+    }
+
+    /// Register a circuit that envelope proofs may be verified against
+    pub async fn register_circuit(&self, circuit: zksnark::Circuit) -> Result<()> {
+        self.proof_manager.write().await.register_circuit(circuit)
+    }
+
+    /// Decode and validate an envelope received from `source_chain`
+    pub async fn validate(&self, message: &[u8], source_chain: u64) -> Result<FilteredMessage, MessageFilterError> {
+        let envelope: FilteredMessage = serde_json::from_slice(message)
+            .map_err(|e| MessageFilterError::MalformedEnvelope(e.to_string()))?;
+
+        if envelope.source_chain != source_chain {
+            return Err(MessageFilterError::SourceChainMismatch {
+                claimed: envelope.source_chain,
+                actual: source_chain,
+            });
+        }
+
+        if envelope.payload.len() > self.max_payload_bytes {
+            return Err(MessageFilterError::PayloadTooLarge {
+                size: envelope.payload.len(),
+                max: self.max_payload_bytes,
+            });
+        }
+
+        serde_json::from_slice::<MessagePayload>(&envelope.payload)
+            .map_err(|e| MessageFilterError::InvalidSchema(e.to_string()))?;
+
+        let expected_inputs = envelope_public_inputs(&envelope.payload, envelope.source_chain, envelope.nonce);
+        if envelope.proof.proof.public_inputs != expected_inputs {
+            return Err(MessageFilterError::ProofBindingMismatch);
+        }
+
+        let circuit_id = &envelope.proof.circuit_id;
+        let verified = self.proof_manager.read().await
+            .verify_proof(circuit_id, &envelope.proof.proof)
+            .map_err(|_| MessageFilterError::UnknownCircuit(circuit_id.clone()))?;
+        if !verified {
+            return Err(MessageFilterError::InvalidProof(circuit_id.clone()));
+        }
+
+        Ok(envelope)
+    }
+}
+
+/// PRD: "Bridge relayers cannot inspect message content"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelayFilteredMessage {
     pub message_id: String,
     pub encrypted_content: Vec<u8>,
     pub message_type_proof: ZkProof,
@@ -182,6 +300,7 @@ impl ZkMessageFilter {
             sender_anonymizer: SenderAnonymizer::new(config.anonymizer_config).await?,
             content_validator: ContentValidator::new(config.validator_config).await?,
             relay_protection: RelayProtection::new(config.relay_config).await?,
+            envelope_validator: MessageEnvelopeValidator::new(DEFAULT_MAX_PAYLOAD_BYTES),
             audit_trail: RwLock::new(Vec::new()),
         })
     }
@@ -192,7 +311,7 @@ impl ZkMessageFilter {
         &self,
         raw_message: RawMessage,
         relay_id: String,
-    ) -> Result<FilteredMessage> {
+    ) -> Result<RelayFilteredMessage> {
         info!("🔍 Filtering message for relay: {} (preserving privacy)", relay_id);
         
         // Step 1: Classify message type using ZK proof
@@ -219,7 +338,7 @@ impl ZkMessageFilter {
             priority_level: self.calculate_priority_level(&raw_message).await?,
         };
         
-        let filtered_message = FilteredMessage {
+        let filtered_message = RelayFilteredMessage {
             message_id: self.generate_message_id(&raw_message).await?,
             encrypted_content,
             message_type_proof,
@@ -348,7 +467,7 @@ impl ZkMessageFilter {
     /// Verify ZK proof without exposing sensitive information
     pub async fn verify_message_proof(
         &self,
-        filtered_message: &FilteredMessage,
+        filtered_message: &RelayFilteredMessage,
     ) -> Result<ProofVerificationResult> {
         info!("✅ Verifying ZK proof for message: {}", filtered_message.message_id);
         
@@ -396,6 +515,22 @@ impl ZkMessageFilter {
         Ok(result)
     }
 
+    /// Validate an inbound envelope's structure, size, schema and zk proof
+    pub async fn validate_message(
+        &self,
+        message: &[u8],
+        source_chain: u64,
+    ) -> Result<FilteredMessage, MessageFilterError> {
+        let envelope = self.envelope_validator.validate(message, source_chain).await?;
+        info!("✅ Envelope from chain {} (nonce {}) passed zk message filter", envelope.source_chain, envelope.nonce);
+        Ok(envelope)
+    }
+
+    /// Register a circuit for inbound envelope proofs
+    pub async fn register_envelope_circuit(&self, circuit: zksnark::Circuit) -> Result<()> {
+        self.envelope_validator.register_circuit(circuit).await
+    }
+
     /// Log audit event for compliance and monitoring
     async fn log_audit_event(&self, event: AuditEvent) -> Result<()> {
         let mut audit_trail = self.audit_trail.write().await;
@@ -448,3 +583,87 @@ pub enum AuditEventType {
     RelayAccessDenied,
     AnonymitySetUpdated,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CIRCUIT_ID: &str = "bridge_message_v1";
+
+    async fn validator_with_circuit(max_payload_bytes: usize) -> MessageEnvelopeValidator {
+        let validator = MessageEnvelopeValidator::new(max_payload_bytes);
+        validator.register_circuit(zksnark::Circuit::new(CIRCUIT_ID.to_string())).await.unwrap();
+        validator
+    }
+
+    fn nav_update_payload() -> Vec<u8> {
+        serde_json::to_vec(&MessagePayload {
+            message_type: MessageType::NavUpdate,
+            data: serde_json::json!({ "vault_id": "vault_1", "nav_per_share": 1_000_000 }),
+        }).unwrap()
+    }
+
+    fn signed_envelope(payload: Vec<u8>, source_chain: u64, nonce: u64) -> FilteredMessage {
+        let mut prover = ZKProofManager::new();
+        prover.register_circuit(zksnark::Circuit::new(CIRCUIT_ID.to_string())).unwrap();
+        let public_inputs = envelope_public_inputs(&payload, source_chain, nonce);
+        let proof = prover.generate_proof(CIRCUIT_ID, &[], &public_inputs).unwrap();
+
+        FilteredMessage {
+            payload,
+            proof: EnvelopeProof { circuit_id: CIRCUIT_ID.to_string(), proof },
+            source_chain,
+            nonce,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_valid_message_passes() {
+        let validator = validator_with_circuit(DEFAULT_MAX_PAYLOAD_BYTES).await;
+        let envelope = signed_envelope(nav_update_payload(), 1, 7);
+
+        let validated = validator.validate(&serde_json::to_vec(&envelope).unwrap(), 1).await.unwrap();
+        assert_eq!(validated.nonce, 7);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_payload_rejected() {
+        let validator = validator_with_circuit(32).await;
+        let envelope = signed_envelope(nav_update_payload(), 1, 7);
+
+        let result = validator.validate(&serde_json::to_vec(&envelope).unwrap(), 1).await;
+        assert!(matches!(result, Err(MessageFilterError::PayloadTooLarge { max: 32, .. })));
+    }
+
+    #[tokio::test]
+    async fn test_bad_proof_rejected() {
+        let validator = validator_with_circuit(DEFAULT_MAX_PAYLOAD_BYTES).await;
+        let mut envelope = signed_envelope(nav_update_payload(), 1, 7);
+        envelope.proof.proof.proof_data[0] ^= 0xff;
+
+        let result = validator.validate(&serde_json::to_vec(&envelope).unwrap(), 1).await;
+        assert!(matches!(result, Err(MessageFilterError::InvalidProof(_))));
+    }
+
+    #[tokio::test]
+    async fn test_malformed_envelopes_rejected() {
+        let validator = validator_with_circuit(DEFAULT_MAX_PAYLOAD_BYTES).await;
+
+        let result = validator.validate(b"not an envelope", 1).await;
+        assert!(matches!(result, Err(MessageFilterError::MalformedEnvelope(_))));
+
+        let envelope = signed_envelope(b"{\"unexpected\": true}".to_vec(), 1, 7);
+        let result = validator.validate(&serde_json::to_vec(&envelope).unwrap(), 1).await;
+        assert!(matches!(result, Err(MessageFilterError::InvalidSchema(_))));
+
+        // Proof bound to a different nonce
+        let mut envelope = signed_envelope(nav_update_payload(), 1, 7);
+        envelope.nonce = 8;
+        let result = validator.validate(&serde_json::to_vec(&envelope).unwrap(), 1).await;
+        assert!(matches!(result, Err(MessageFilterError::ProofBindingMismatch)));
+
+        let envelope = signed_envelope(nav_update_payload(), 1, 7);
+        let result = validator.validate(&serde_json::to_vec(&envelope).unwrap(), 2).await;
+        assert!(matches!(result, Err(MessageFilterError::SourceChainMismatch { claimed: 1, actual: 2 })));
+    }
+}
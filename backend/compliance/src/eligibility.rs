@@ -0,0 +1,190 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::zk_kyc_service::{KiltCredential, KycResult, ZkKycService};
+
+/// Deposit eligibility: zk-KYC jurisdiction proof + vault jurisdiction policy + sanctions
+/// PRD: "Jurisdictional zk constraints at mint/redemption"
+/// Called by the API deposit handler before the on-chain deposit instruction is submitted.
+pub struct EligibilityEngine {
+    kyc_service: RwLock<ZkKycService>,
+    vault_jurisdictions: RwLock<HashMap<String, HashSet<String>>>,
+    sanctioned_parties: RwLock<HashSet<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Eligibility {
+    Eligible,
+    Denied { reason: String },
+}
+
+impl Eligibility {
+    pub fn is_eligible(&self) -> bool {
+        matches!(self, Eligibility::Eligible)
+    }
+
+    fn denied(reason: impl Into<String>) -> Self {
+        Eligibility::Denied { reason: reason.into() }
+    }
+}
+
+impl EligibilityEngine {
+    pub fn new(kyc_service: ZkKycService) -> Self {
+        Self {
+            kyc_service: RwLock::new(kyc_service),
+            vault_jurisdictions: RwLock::new(HashMap::new()),
+            sanctioned_parties: RwLock::new(HashSet::new()),
+        }
+    }
+
+    /// Replace the set of jurisdictions a vault accepts deposits from
+    pub async fn set_vault_jurisdictions(&self, vault_id: &str, jurisdictions: Vec<String>) {
+        info!("⚖️ Vault {} accepts deposits from {:?}", vault_id, jurisdictions);
+        let mut vaults = self.vault_jurisdictions.write().await;
+        vaults.insert(vault_id.to_string(), jurisdictions.into_iter().collect());
+    }
+
+    /// Mark a wallet address or DID as sanctioned
+    pub async fn add_sanctioned(&self, party: &str) {
+        self.sanctioned_parties.write().await.insert(party.to_string());
+    }
+
+    pub async fn remove_sanctioned(&self, party: &str) {
+        self.sanctioned_parties.write().await.remove(party);
+    }
+
+    /// Decide whether `user_wallet` may deposit into `vault_id` as a resident of `jurisdiction`
+    pub async fn check_deposit_eligibility(
+        &self,
+        user_wallet: &str,
+        vault_id: &str,
+        jurisdiction: &str,
+        credential: &KiltCredential,
+    ) -> Result<Eligibility> {
+        let eligibility = self.evaluate(user_wallet, vault_id, jurisdiction, credential).await?;
+        match &eligibility {
+            Eligibility::Eligible => info!("✅ {} eligible to deposit into {}", user_wallet, vault_id),
+            Eligibility::Denied { reason } => warn!("❌ {} denied deposit into {}: {}", user_wallet, vault_id, reason),
+        }
+        Ok(eligibility)
+    }
+
+    async fn evaluate(
+        &self,
+        user_wallet: &str,
+        vault_id: &str,
+        jurisdiction: &str,
+        credential: &KiltCredential,
+    ) -> Result<Eligibility> {
+        {
+            let sanctioned = self.sanctioned_parties.read().await;
+            if sanctioned.contains(user_wallet) || sanctioned.contains(&credential.subject_did) {
+                return Ok(Eligibility::denied("User is subject to sanctions"));
+            }
+        }
+
+        {
+            let vaults = self.vault_jurisdictions.read().await;
+            match vaults.get(vault_id) {
+                None => return Ok(Eligibility::denied(format!("Vault {} has no jurisdiction policy", vault_id))),
+                Some(allowed) if !allowed.contains(jurisdiction) => {
+                    return Ok(Eligibility::denied(format!(
+                        "Jurisdiction {} is not permitted for vault {}",
+                        jurisdiction, vault_id
+                    )));
+                }
+                Some(_) => {}
+            }
+        }
+
+        let kyc_result = self.kyc_service.write().await
+            .verify_credential(credential, jurisdiction)
+            .await?;
+        Ok(match kyc_result {
+            KycResult::Valid { .. } => Eligibility::Eligible,
+            KycResult::Expired { .. } => Eligibility::denied("KYC credential has expired"),
+            KycResult::Revoked { reason, .. } => Eligibility::denied(format!("KYC credential revoked: {}", reason)),
+            KycResult::Invalid { reason } => Eligibility::denied(format!("KYC credential invalid: {}", reason)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::zk_kyc_service::kilt_credential_digest;
+    use ed25519_dalek::Signer;
+
+    fn attester_key() -> ed25519_dalek::SigningKey {
+        ed25519_dalek::SigningKey::from_bytes(&[42; 32])
+    }
+
+    async fn test_engine() -> EligibilityEngine {
+        let kyc_service = ZkKycService::new_with_providers(
+            "https://api.kilt.io".to_string(),
+            "https://api.fractal.id".to_string(),
+            "app_rtf_worldid".to_string(),
+            "rtf_verified_users".to_string(),
+        ).await.unwrap()
+            .with_trusted_attester("did:kilt:issuer", attester_key().verifying_key().to_bytes());
+        let engine = EligibilityEngine::new(kyc_service);
+        engine.set_vault_jurisdictions("vault_eu", vec!["EU".to_string()]).await;
+        engine
+    }
+
+    fn credential(jurisdiction: &str) -> KiltCredential {
+        let mut credential = KiltCredential {
+            credential_id: format!("cred_{}", jurisdiction),
+            issuer_did: "did:kilt:issuer".to_string(),
+            subject_did: "did:kilt:investor".to_string(),
+            credential_type: "ResidencyProof".to_string(),
+            claims: serde_json::json!({ "jurisdiction": jurisdiction }),
+            proof: String::new(),
+            expiry: chrono::Utc::now().timestamp() + 86_400,
+        };
+        credential.proof = hex::encode(attester_key().sign(&kilt_credential_digest(&credential)).to_bytes());
+        credential
+    }
+
+    #[tokio::test]
+    async fn test_allowed_jurisdiction_is_eligible() {
+        let engine = test_engine().await;
+        let result = engine.check_deposit_eligibility("wallet_1", "vault_eu", "EU", &credential("EU")).await.unwrap();
+        assert_eq!(result, Eligibility::Eligible);
+    }
+
+    #[tokio::test]
+    async fn test_disallowed_jurisdiction_is_denied() {
+        let engine = test_engine().await;
+        let result = engine.check_deposit_eligibility("wallet_1", "vault_eu", "US", &credential("US")).await.unwrap();
+        assert_eq!(result, Eligibility::Denied { reason: "Jurisdiction US is not permitted for vault vault_eu".to_string() });
+
+        // Claiming an allowed jurisdiction with a credential for another one fails KYC
+        let result = engine.check_deposit_eligibility("wallet_1", "vault_eu", "EU", &credential("US")).await.unwrap();
+        assert!(!result.is_eligible());
+
+        let result = engine.check_deposit_eligibility("wallet_1", "vault_unknown", "EU", &credential("EU")).await.unwrap();
+        assert!(!result.is_eligible());
+    }
+
+    #[tokio::test]
+    async fn test_sanctioned_user_is_denied() {
+        let engine = test_engine().await;
+        engine.add_sanctioned("wallet_2").await;
+
+        let result = engine.check_deposit_eligibility("wallet_2", "vault_eu", "EU", &credential("EU")).await.unwrap();
+        assert_eq!(result, Eligibility::Denied { reason: "User is subject to sanctions".to_string() });
+
+        // Sanctions also follow the credential subject to other wallets
+        engine.add_sanctioned("did:kilt:investor").await;
+        let result = engine.check_deposit_eligibility("wallet_3", "vault_eu", "EU", &credential("EU")).await.unwrap();
+        assert!(!result.is_eligible());
+
+        engine.remove_sanctioned("did:kilt:investor").await;
+        let result = engine.check_deposit_eligibility("wallet_3", "vault_eu", "EU", &credential("EU")).await.unwrap();
+        assert!(result.is_eligible());
+    }
+}
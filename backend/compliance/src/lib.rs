@@ -1,5 +1,7 @@
+pub mod eligibility;
 pub mod zk_kyc_service;
 
+pub use eligibility::*;
 pub use zk_kyc_service::*;
 
 use anyhow::Result;
@@ -19,6 +21,8 @@ pub struct ComplianceConfig {
     pub fractal_endpoint: String,
     pub worldid_app_id: String,
     pub sismo_group_id: String,
+    /// KILT issuer DID -> Ed25519 attestation key; credentials from other issuers are rejected
+    pub trusted_kilt_attesters: HashMap<String, [u8; 32]>,
 }
 
 impl Default for ComplianceConfig {
@@ -30,6 +34,7 @@ impl Default for ComplianceConfig {
             fractal_endpoint: "https://api.fractal.id".to_string(),
             worldid_app_id: "app_rtf_worldid".to_string(),
             sismo_group_id: "rtf_verified_users".to_string(),
+            trusted_kilt_attesters: HashMap::new(),
         }
     }
 }
@@ -38,12 +43,15 @@ impl Default for ComplianceConfig {
 pub async fn init_compliance_service(config: ComplianceConfig) -> Result<ZkKycService> {
     info!("🔐 Initializing RTF Compliance Service");
 
-    let service = ZkKycService::new_with_providers(
+    let mut service = ZkKycService::new_with_providers(
         config.kilt_endpoint,
         config.fractal_endpoint,
         config.worldid_app_id,
         config.sismo_group_id,
     ).await?;
+    for (issuer_did, attestation_key) in &config.trusted_kilt_attesters {
+        service = service.with_trusted_attester(issuer_did, *attestation_key);
+    }
 
     info!("✅ RTF Compliance Service initialized successfully");
     Ok(service)
@@ -7,6 +7,7 @@ use reqwest::Client;
 use serde_json::Value;
 use sha2::{Sha256, Digest};
 use base64::{Engine as _, engine::general_purpose};
+use domain_tags::DomainTag;
 
 /// Advanced Zero-Knowledge KYC & Legal Anchoring Service for RTF Infrastructure
 /// PRD Section 3.4: "Compliance & Legal Anchoring"
@@ -27,6 +28,11 @@ pub struct ZkKycService {
     filecoin_endpoint: String,
     supported_jurisdictions: HashMap<String, JurisdictionConfig>,
     verification_cache: HashMap<String, VerificationResult>,
+    credential_cache: HashMap<String, CachedKycResult>,
+    credential_cache_ttl_seconds: i64,
+    revoked_credentials: HashMap<String, CredentialRevocation>,
+    /// Ed25519 attestation keys of the KILT issuers whose credentials are accepted
+    trusted_kilt_attesters: HashMap<String, [u8; 32]>,
     legal_document_cache: HashMap<String, LegalDocumentAnchor>,
     http_client: Client,
 }
@@ -87,6 +93,76 @@ pub struct KiltCredential {
     pub expiry: i64,
 }
 
+/// Outcome of verifying a single credential against a required jurisdiction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum KycResult {
+    Valid {
+        credential_id: String,
+        subject_did: String,
+        jurisdiction: String,
+        expires_at: i64,
+    },
+    Expired {
+        credential_id: String,
+        expired_at: i64,
+    },
+    Revoked {
+        credential_id: String,
+        reason: String,
+        revoked_at: i64,
+    },
+    Invalid {
+        reason: String,
+    },
+}
+
+impl KycResult {
+    pub fn is_valid(&self) -> bool {
+        matches!(self, KycResult::Valid { .. })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CredentialRevocation {
+    pub credential_id: String,
+    pub reason: String,
+    pub revoked_at: i64,
+}
+
+/// Cache key binding a result to the credential's signed contents, not just its id
+fn credential_cache_key(credential: &KiltCredential, jurisdiction: &str) -> String {
+    format!("{}:{}:{}", credential.credential_id, hex::encode(kilt_credential_digest(credential)), jurisdiction)
+}
+
+#[derive(Debug, Clone)]
+struct CachedKycResult {
+    result: KycResult,
+    cached_until: i64,
+}
+
+pub const DEFAULT_CREDENTIAL_CACHE_TTL_SECONDS: i64 = 300;
+
+/// Digest the issuer signs; a credential's `proof` is the hex-encoded Ed25519
+/// signature over it by the issuer's attestation key
+pub fn kilt_credential_digest(credential: &KiltCredential) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(DomainTag::KiltCredential.as_bytes());
+    for field in [
+        &credential.credential_id,
+        &credential.issuer_did,
+        &credential.subject_did,
+        &credential.credential_type,
+    ] {
+        hasher.update((field.len() as u64).to_be_bytes());
+        hasher.update(field.as_bytes());
+    }
+    let claims = credential.claims.to_string();
+    hasher.update((claims.len() as u64).to_be_bytes());
+    hasher.update(claims.as_bytes());
+    hasher.update(credential.expiry.to_be_bytes());
+    hasher.finalize().into()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FractalProof {
     pub user_id: String,
@@ -287,8 +363,19 @@ impl ZkKycService {
             fractal_endpoint,
             worldid_app_id,
             sismo_group_id,
+            openlaw_endpoint: "https://api.openlaw.io".to_string(),
+            accord_endpoint: "https://api.accordproject.org".to_string(),
+            celestia_da_endpoint: "https://rpc.celestia.org".to_string(),
+            btc_anchor_endpoint: "https://blockstream.info/api".to_string(),
+            filecoin_endpoint: "https://api.node.glif.io".to_string(),
             supported_jurisdictions: HashMap::new(),
             verification_cache: HashMap::new(),
+            credential_cache: HashMap::new(),
+            credential_cache_ttl_seconds: DEFAULT_CREDENTIAL_CACHE_TTL_SECONDS,
+            revoked_credentials: HashMap::new(),
+            trusted_kilt_attesters: HashMap::new(),
+            legal_document_cache: HashMap::new(),
+            http_client: Client::new(),
         };
 
         // Initialize supported jurisdictions
@@ -385,6 +472,132 @@ impl ZkKycService {
         Ok(verification_result)
     }
 
+    /// How long a positive credential verification is served from cache
+    pub fn with_credential_cache_ttl(mut self, ttl_seconds: i64) -> Self {
+        self.credential_cache_ttl_seconds = ttl_seconds;
+        self
+    }
+
+    /// Accept credentials signed by `issuer_did` under this Ed25519 attestation key
+    pub fn with_trusted_attester(mut self, issuer_did: &str, attestation_key: [u8; 32]) -> Self {
+        self.trusted_kilt_attesters.insert(issuer_did.to_string(), attestation_key);
+        self
+    }
+
+    /// Verify a credential for the required jurisdiction
+    /// Expired and revoked credentials are reported distinctly from invalid ones;
+    /// only `Valid` results are cached, never beyond the credential's own expiry.
+    /// The issuer signature is checked before the cache is consulted, and cache
+    /// entries are keyed by the signed digest, so a cached result is only ever
+    /// served for the exact credential that earned it.
+    pub async fn verify_credential(
+        &mut self,
+        credential: &KiltCredential,
+        required_jurisdiction: &str,
+    ) -> Result<KycResult> {
+        if let Err(reason) = self.authenticate_credential(credential) {
+            warn!("❌ Credential {} rejected: {}", credential.credential_id, reason);
+            return Ok(KycResult::Invalid { reason });
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        let cache_key = credential_cache_key(credential, required_jurisdiction);
+
+        if let Some(cached) = self.credential_cache.get(&cache_key) {
+            if cached.cached_until > now {
+                return Ok(cached.result.clone());
+            }
+            self.credential_cache.remove(&cache_key);
+        }
+
+        let result = self.evaluate_credential(credential, required_jurisdiction, now);
+        match &result {
+            KycResult::Valid { expires_at, .. } => {
+                let cached_until = (now + self.credential_cache_ttl_seconds).min(*expires_at);
+                self.credential_cache.insert(cache_key, CachedKycResult { result: result.clone(), cached_until });
+                info!("✅ Credential {} valid for {}", credential.credential_id, required_jurisdiction);
+            }
+            other => warn!("❌ Credential {} rejected: {:?}", credential.credential_id, other),
+        }
+        Ok(result)
+    }
+
+    /// Add a credential to the revocation list and drop any cached verification of it
+    pub fn revoke_credential(&mut self, credential_id: &str, reason: &str) {
+        warn!("🚫 Revoking credential {}: {}", credential_id, reason);
+        self.revoked_credentials.insert(credential_id.to_string(), CredentialRevocation {
+            credential_id: credential_id.to_string(),
+            reason: reason.to_string(),
+            revoked_at: chrono::Utc::now().timestamp(),
+        });
+        let prefix = format!("{}:", credential_id);
+        self.credential_cache.retain(|key, _| !key.starts_with(&prefix));
+    }
+
+    pub fn is_credential_revoked(&self, credential_id: &str) -> bool {
+        self.revoked_credentials.contains_key(credential_id)
+    }
+
+    /// Check the credential carries a valid signature from a trusted attester
+    fn authenticate_credential(&self, credential: &KiltCredential) -> std::result::Result<(), String> {
+        use ed25519_dalek::{Signature, VerifyingKey};
+
+        let attester = self.trusted_kilt_attesters.get(&credential.issuer_did)
+            .ok_or_else(|| format!("Untrusted credential issuer: {}", credential.issuer_did))?;
+        let key = VerifyingKey::from_bytes(attester)
+            .map_err(|e| format!("Invalid attestation key for {}: {}", credential.issuer_did, e))?;
+        let signature = hex::decode(&credential.proof)
+            .ok()
+            .and_then(|bytes| Signature::from_slice(&bytes).ok())
+            .ok_or_else(|| "Malformed credential proof".to_string())?;
+        key.verify_strict(&kilt_credential_digest(credential), &signature)
+            .map_err(|_| "Credential proof is not a valid issuer signature".to_string())
+    }
+
+    /// Checks that apply once the credential is known to come from its issuer
+    fn evaluate_credential(&self, credential: &KiltCredential, required_jurisdiction: &str, now: i64) -> KycResult {
+        if let Some(revocation) = self.revoked_credentials.get(&credential.credential_id) {
+            return KycResult::Revoked {
+                credential_id: credential.credential_id.clone(),
+                reason: revocation.reason.clone(),
+                revoked_at: revocation.revoked_at,
+            };
+        }
+
+        if credential.expiry <= now {
+            return KycResult::Expired {
+                credential_id: credential.credential_id.clone(),
+                expired_at: credential.expiry,
+            };
+        }
+
+        match self.supported_jurisdictions.get(required_jurisdiction) {
+            None => return KycResult::Invalid { reason: format!("Unsupported jurisdiction: {}", required_jurisdiction) },
+            Some(config) if config.restricted => {
+                return KycResult::Invalid { reason: format!("Restricted jurisdiction: {}", required_jurisdiction) };
+            }
+            Some(_) => {}
+        }
+
+        let credential_jurisdiction = credential.claims.get("jurisdiction").and_then(Value::as_str);
+        if credential_jurisdiction != Some(required_jurisdiction) {
+            return KycResult::Invalid {
+                reason: format!(
+                    "Credential jurisdiction {} does not match required {}",
+                    credential_jurisdiction.unwrap_or("<none>"),
+                    required_jurisdiction
+                ),
+            };
+        }
+
+        KycResult::Valid {
+            credential_id: credential.credential_id.clone(),
+            subject_did: credential.subject_did.clone(),
+            jurisdiction: required_jurisdiction.to_string(),
+            expires_at: credential.expiry,
+        }
+    }
+
     /// PRD: Check jurisdictional zk constraints
     /// PRD: "Jurisdictional zk constraints at mint/redemption"
     pub async fn check_transaction_compliance(
@@ -512,9 +725,11 @@ impl ZkKycService {
             return Ok(false);
         }
 
-        // TODO: Actual KILT credential verification
-        // This would involve verifying the DID, checking the issuer, and validating the proof
-        
+        if let Err(reason) = self.authenticate_credential(credential) {
+            warn!("❌ KILT credential rejected: {}", reason);
+            return Ok(false);
+        }
+
         info!("✅ KILT credential verified");
         Ok(true)
     }
@@ -644,3 +859,155 @@ impl ZkKycService {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ISSUER: &str = "did:kilt:issuer";
+
+    fn attester_key() -> ed25519_dalek::SigningKey {
+        ed25519_dalek::SigningKey::from_bytes(&[42; 32])
+    }
+
+    async fn test_service() -> ZkKycService {
+        ZkKycService::new_with_providers(
+            "https://api.kilt.io".to_string(),
+            "https://api.fractal.id".to_string(),
+            "app_rtf_worldid".to_string(),
+            "rtf_verified_users".to_string(),
+        ).await.unwrap()
+            .with_trusted_attester(ISSUER, attester_key().verifying_key().to_bytes())
+    }
+
+    fn sign(credential: &mut KiltCredential, key: &ed25519_dalek::SigningKey) {
+        use ed25519_dalek::Signer;
+        credential.proof = hex::encode(key.sign(&kilt_credential_digest(credential)).to_bytes());
+    }
+
+    fn credential(id: &str, jurisdiction: &str, expiry: i64) -> KiltCredential {
+        let mut credential = KiltCredential {
+            credential_id: id.to_string(),
+            issuer_did: ISSUER.to_string(),
+            subject_did: "did:kilt:investor".to_string(),
+            credential_type: "AccreditedInvestor".to_string(),
+            claims: serde_json::json!({ "jurisdiction": jurisdiction }),
+            proof: String::new(),
+            expiry,
+        };
+        sign(&mut credential, &attester_key());
+        credential
+    }
+
+    fn in_days(days: i64) -> i64 {
+        chrono::Utc::now().timestamp() + days * 86_400
+    }
+
+    #[tokio::test]
+    async fn test_valid_credential_for_matching_jurisdiction() {
+        let mut service = test_service().await;
+        let credential = credential("cred_1", "EU", in_days(30));
+
+        let result = service.verify_credential(&credential, "EU").await.unwrap();
+        assert_eq!(result, KycResult::Valid {
+            credential_id: "cred_1".to_string(),
+            subject_did: "did:kilt:investor".to_string(),
+            jurisdiction: "EU".to_string(),
+            expires_at: credential.expiry,
+        });
+
+        // Wrong jurisdiction is invalid, not expired or revoked
+        let result = service.verify_credential(&credential, "US").await.unwrap();
+        assert!(matches!(result, KycResult::Invalid { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_expired_credential_is_reported_as_expired() {
+        let mut service = test_service().await;
+        let credential = credential("cred_old", "EU", in_days(-1));
+
+        let result = service.verify_credential(&credential, "EU").await.unwrap();
+        assert_eq!(result, KycResult::Expired { credential_id: "cred_old".to_string(), expired_at: credential.expiry });
+    }
+
+    #[tokio::test]
+    async fn test_revoked_credential_is_reported_as_revoked_even_when_cached() {
+        let mut service = test_service().await;
+        let credential = credential("cred_2", "US", in_days(30));
+        assert!(service.verify_credential(&credential, "US").await.unwrap().is_valid());
+
+        service.revoke_credential("cred_2", "Issuer reported compromise");
+        let result = service.verify_credential(&credential, "US").await.unwrap();
+        match result {
+            KycResult::Revoked { credential_id, reason, .. } => {
+                assert_eq!(credential_id, "cred_2");
+                assert_eq!(reason, "Issuer reported compromise");
+            }
+            other => panic!("expected revoked, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tampered_credential_is_invalid() {
+        let mut service = test_service().await;
+        let mut credential = credential("cred_3", "EU", in_days(30));
+        credential.expiry = in_days(3650);
+
+        let result = service.verify_credential(&credential, "EU").await.unwrap();
+        assert!(matches!(result, KycResult::Invalid { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_only_positive_results_are_cached() {
+        let mut service = test_service().await;
+        let valid = credential("cred_4", "EU", in_days(30));
+        assert!(service.verify_credential(&valid, "EU").await.unwrap().is_valid());
+        let cached = &service.credential_cache[&credential_cache_key(&valid, "EU")];
+        assert!(cached.cached_until > chrono::Utc::now().timestamp());
+        assert!(cached.cached_until <= chrono::Utc::now().timestamp() + DEFAULT_CREDENTIAL_CACHE_TTL_SECONDS);
+
+        // A credential expiring before the TTL is never cached past its expiry
+        let expiring = credential("cred_5", "EU", chrono::Utc::now().timestamp() + 60);
+        service.verify_credential(&expiring, "EU").await.unwrap();
+        assert_eq!(service.credential_cache[&credential_cache_key(&expiring, "EU")].cached_until, expiring.expiry);
+
+        let expired = credential("cred_6", "EU", in_days(-1));
+        service.verify_credential(&expired, "EU").await.unwrap();
+        assert!(!service.credential_cache.contains_key(&credential_cache_key(&expired, "EU")));
+    }
+
+    #[tokio::test]
+    async fn test_self_computed_or_foreign_proofs_are_invalid() {
+        let mut service = test_service().await;
+
+        // Anyone can hash the contents; that is not an issuer signature
+        let mut digest_only = credential("cred_7", "EU", in_days(30));
+        digest_only.proof = hex::encode(kilt_credential_digest(&digest_only));
+        let result = service.verify_credential(&digest_only, "EU").await.unwrap();
+        assert!(matches!(result, KycResult::Invalid { .. }));
+
+        // Signed, but not by the issuer's registered attestation key
+        let mut forged = credential("cred_8", "EU", in_days(30));
+        sign(&mut forged, &ed25519_dalek::SigningKey::from_bytes(&[7; 32]));
+        let result = service.verify_credential(&forged, "EU").await.unwrap();
+        assert!(matches!(result, KycResult::Invalid { .. }));
+
+        let mut unknown_issuer = credential("cred_9", "EU", in_days(30));
+        unknown_issuer.issuer_did = "did:kilt:someone-else".to_string();
+        sign(&mut unknown_issuer, &attester_key());
+        let result = service.verify_credential(&unknown_issuer, "EU").await.unwrap();
+        assert!(matches!(result, KycResult::Invalid { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_cached_result_is_not_served_for_a_different_credential_with_the_same_id() {
+        let mut service = test_service().await;
+        let genuine = credential("cred_10", "EU", in_days(30));
+        assert!(service.verify_credential(&genuine, "EU").await.unwrap().is_valid());
+
+        let mut impostor = genuine.clone();
+        impostor.subject_did = "did:kilt:impostor".to_string();
+        let result = service.verify_credential(&impostor, "EU").await.unwrap();
+        assert!(matches!(result, KycResult::Invalid { .. }));
+    }
+}
@@ -1,9 +1,14 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
 use tokio::time::{Duration, sleep};
 use tracing::{info, warn, error};
 
+use crate::btc_headers::{merkle_branch, BitcoinBlockSource, BlockHeader, HeaderChain};
+
 /// Babylon Protocol Bitcoin Staking Service
 /// July 2025 - Phase-2 Mainnet with 56,853+ BTC staked ($5.64B TVL)
 pub struct BabylonService {
@@ -13,6 +18,9 @@ pub struct BabylonService {
     staked_btc_amount: u64,
     total_value_locked: u64,
     phase: BabylonPhase,
+    /// Headers validated from the configured checkpoint; anchor inclusion is checked against these
+    header_chain: RwLock<Option<HeaderChain>>,
+    block_source: Option<Arc<dyn BitcoinBlockSource>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +40,22 @@ pub struct BitcoinAnchor {
     pub op_return_data: Vec<u8>,
     pub finality_signature: String,
     pub timestamp: i64,
+    /// SPV proof that `bitcoin_tx_hash` is included in a block, once confirmed
+    #[serde(default)]
+    pub inclusion_proof: Option<SpvProof>,
+}
+
+/// Confirmations an anchor needs before it is treated as included
+pub const REQUIRED_ANCHOR_CONFIRMATIONS: u32 = 6;
+
+/// SPV Merkle proof of a transaction, mirroring zkReplay's `BtcAnchorRoot`
+/// Hashes are hex in Bitcoin RPC display order (byte-reversed). The proof only names
+/// the block; its merkle root and depth come from the validated header chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpvProof {
+    pub block_hash: String,
+    pub tx_index: u32,
+    pub merkle_branch: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -87,6 +111,8 @@ impl BabylonService {
             staked_btc_amount: 56853, // As of July 2025
             total_value_locked: 5_640_000_000, // $5.64B USD
             phase: BabylonPhase::Phase2,
+            header_chain: RwLock::new(None),
+            block_source: None,
         };
 
         // Verify Babylon connectivity
@@ -102,6 +128,28 @@ impl BabylonService {
         Ok(service)
     }
 
+    /// Validate Bitcoin headers from this trusted checkpoint onwards
+    pub fn with_header_checkpoint(mut self, height: u64, header_hex: &str) -> Result<Self> {
+        let checkpoint = HeaderChain::from_checkpoint(height, BlockHeader::from_hex(header_hex)?);
+        self.header_chain = RwLock::new(Some(checkpoint));
+        Ok(self)
+    }
+
+    /// Node headers and SPV proofs are fetched from
+    pub fn with_block_source(mut self, block_source: Arc<dyn BitcoinBlockSource>) -> Self {
+        self.block_source = Some(block_source);
+        self
+    }
+
+    /// Extend the validated header chain to the block source's best tip
+    pub async fn sync_headers(&self) -> Result<u64> {
+        let source = self.block_source.as_ref()
+            .ok_or_else(|| anyhow!("No Bitcoin block source configured"))?;
+        let mut guard = self.header_chain.write().await;
+        let chain = guard.as_mut().ok_or_else(|| anyhow!("No Bitcoin header checkpoint configured"))?;
+        chain.sync(source.as_ref()).await
+    }
+
     /// Anchor vault NAV to Bitcoin blockchain with finality guarantee
     pub async fn anchor_vault_nav_to_bitcoin(
         &self,
@@ -131,7 +179,7 @@ impl BabylonService {
             bitcoin_height,
         ).await?;
         
-        let anchor = BitcoinAnchor {
+        let mut anchor = BitcoinAnchor {
             vault_id,
             nav_hash,
             epoch,
@@ -140,10 +188,12 @@ impl BabylonService {
             op_return_data,
             finality_signature,
             timestamp: chrono::Utc::now().timestamp(),
+            inclusion_proof: None,
         };
 
         // Wait for Bitcoin confirmation
-        self.wait_for_bitcoin_confirmation(&anchor.bitcoin_tx_hash, 6).await?;
+        self.wait_for_bitcoin_confirmation(&anchor.bitcoin_tx_hash, REQUIRED_ANCHOR_CONFIRMATIONS as u64).await?;
+        anchor.inclusion_proof = self.fetch_spv_proof(&anchor.bitcoin_tx_hash).await?;
         
         // Submit finality signature to Babylon
         self.submit_finality_signature(&anchor).await?;
@@ -185,6 +235,41 @@ impl BabylonService {
         Ok(true)
     }
 
+    /// Check the anchor transaction is in a block with enough confirmations
+    /// The block must be on the validated header chain; the tx hash and its SPV branch
+    /// must fold to that header's merkle root, and confirmations are counted from the
+    /// chain tip.
+    pub async fn verify_anchor_inclusion(&self, anchor: &BitcoinAnchor) -> Result<bool> {
+        let Some(proof) = &anchor.inclusion_proof else {
+            warn!("⏳ No inclusion proof yet for anchor tx {}", anchor.bitcoin_tx_hash);
+            return Ok(false);
+        };
+
+        let guard = self.header_chain.read().await;
+        let chain = guard.as_ref().ok_or_else(|| anyhow!("No Bitcoin header checkpoint configured"))?;
+        let Some(height) = chain.height_of(&proof.block_hash) else {
+            warn!("⏳ Block {} is not on the validated header chain", proof.block_hash);
+            return Ok(false);
+        };
+
+        let confirmations = chain.tip_height() - height + 1;
+        if confirmations < REQUIRED_ANCHOR_CONFIRMATIONS as u64 {
+            warn!("⏳ Insufficient Bitcoin confirmations: {} < {}", confirmations, REQUIRED_ANCHOR_CONFIRMATIONS);
+            return Ok(false);
+        }
+
+        let header = chain.header_at(height).expect("height is on the chain");
+        let computed_root = compute_merkle_root(&anchor.bitcoin_tx_hash, proof.tx_index, &proof.merkle_branch)?;
+        if !computed_root.eq_ignore_ascii_case(&header.merkle_root_hex()) {
+            error!("❌ Merkle branch for tx {} does not match block {} root", anchor.bitcoin_tx_hash, proof.block_hash);
+            return Ok(false);
+        }
+
+        info!("✅ Anchor tx {} included in block {} with {} confirmations",
+              anchor.bitcoin_tx_hash, proof.block_hash, confirmations);
+        Ok(true)
+    }
+
     /// Get current Bitcoin staking information
     pub async fn get_staking_info(&self) -> Result<BitcoinStakingInfo> {
         info!("📊 Fetching Bitcoin staking information");
@@ -300,6 +385,24 @@ impl BabylonService {
         Ok(())
     }
 
+    /// Build the tx's SPV branch from its block's txids, once it is mined
+    async fn fetch_spv_proof(&self, tx_hash: &str) -> Result<Option<SpvProof>> {
+        info!("🌳 Fetching SPV proof for tx {}", tx_hash);
+        let source = self.block_source.as_ref()
+            .ok_or_else(|| anyhow!("No Bitcoin block source configured"))?;
+        let Some((block_hash, txids)) = source.containing_block(tx_hash).await? else {
+            return Ok(None);
+        };
+        let tx_index = txids.iter()
+            .position(|txid| txid.eq_ignore_ascii_case(tx_hash))
+            .ok_or_else(|| anyhow!("Block {} does not list tx {}", block_hash, tx_hash))?;
+        Ok(Some(SpvProof {
+            block_hash,
+            tx_index: tx_index as u32,
+            merkle_branch: merkle_branch(&txids, tx_index)?,
+        }))
+    }
+
     async fn verify_finality_signature(&self, anchor: &BitcoinAnchor) -> Result<bool> {
         // TODO: Implement actual finality signature verification
         Ok(true)
@@ -351,9 +454,200 @@ impl BabylonService {
     }
 }
 
+/// Fold an SPV branch into a merkle root (double SHA-256, display-order hex in and out)
+pub fn compute_merkle_root(tx_hash: &str, tx_index: u32, merkle_branch: &[String]) -> Result<String> {
+    let mut node = display_hex_to_internal(tx_hash)?;
+    let mut index = tx_index;
+    for sibling in merkle_branch {
+        let sibling = display_hex_to_internal(sibling)?;
+        let mut concatenated = Vec::with_capacity(64);
+        if index & 1 == 0 {
+            concatenated.extend_from_slice(&node);
+            concatenated.extend_from_slice(&sibling);
+        } else {
+            concatenated.extend_from_slice(&sibling);
+            concatenated.extend_from_slice(&node);
+        }
+        node = Sha256::digest(Sha256::digest(&concatenated)).into();
+        index >>= 1;
+    }
+    if index != 0 {
+        return Err(anyhow!("Transaction index {} exceeds merkle branch depth {}", tx_index, merkle_branch.len()));
+    }
+    node.reverse();
+    Ok(hex::encode(node))
+}
+
+fn display_hex_to_internal(hash: &str) -> Result<[u8; 32]> {
+    let mut bytes: [u8; 32] = hex::decode(hash)?
+        .try_into()
+        .map_err(|_| anyhow!("Bitcoin hash must be 32 bytes: {}", hash))?;
+    bytes.reverse();
+    Ok(bytes)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::btc_headers::mine_header;
+    use async_trait::async_trait;
+
+    // Bitcoin block 100000: four transactions
+    const BLOCK_100000_MERKLE_ROOT: &str = "f3e94742aca4b5ef85488dc37c06c3282295ffec960994b2c0d5ac2a25a95766";
+    const BLOCK_100000_TX2: &str = "6359f0868171b1d194cbee1af2f16ea598ae8fad666d9b012c8ed2b79a236ec4";
+    const EASY_BITS: u32 = 0x207f_ffff;
+
+    /// Regtest-difficulty chain whose block 1 mines the anchor tx among `txids`
+    struct MockBlockSource {
+        headers: std::sync::Mutex<Vec<BlockHeader>>,
+        txids: Vec<String>,
+    }
+
+    impl MockBlockSource {
+        fn new(txids: Vec<String>) -> Self {
+            let genesis = mine_header([0; 32], [0; 32], EASY_BITS);
+            let mut root = hex::decode(compute_merkle_root(&txids[0], 0, &merkle_branch(&txids, 0).unwrap()).unwrap()).unwrap();
+            root.reverse();
+            let block_1 = mine_header(genesis.hash(), root.try_into().unwrap(), EASY_BITS);
+            Self { headers: std::sync::Mutex::new(vec![genesis, block_1]), txids }
+        }
+
+        fn mine(&self, blocks: usize) {
+            let mut headers = self.headers.lock().unwrap();
+            for _ in 0..blocks {
+                let tip = headers.last().unwrap().hash();
+                headers.push(mine_header(tip, [7; 32], EASY_BITS));
+            }
+        }
+
+        fn header_hex_at(&self, height: usize) -> String {
+            hex::encode(self.headers.lock().unwrap()[height].to_bytes())
+        }
+    }
+
+    #[async_trait]
+    impl BitcoinBlockSource for MockBlockSource {
+        async fn tip_height(&self) -> Result<u64> {
+            Ok(self.headers.lock().unwrap().len() as u64 - 1)
+        }
+
+        async fn header_hex(&self, height: u64) -> Result<String> {
+            Ok(self.header_hex_at(height as usize))
+        }
+
+        async fn containing_block(&self, txid: &str) -> Result<Option<(String, Vec<String>)>> {
+            let block_1 = self.headers.lock().unwrap()[1].block_hash();
+            Ok(self.txids.iter().any(|t| t == txid).then(|| (block_1, self.txids.clone())))
+        }
+    }
+
+    fn test_service() -> BabylonService {
+        BabylonService {
+            babylon_rpc_url: "test".to_string(),
+            bitcoin_rpc_url: "test".to_string(),
+            finality_provider_address: "test".to_string(),
+            staked_btc_amount: 56853,
+            total_value_locked: 5_640_000_000,
+            phase: BabylonPhase::Phase2,
+            header_chain: RwLock::new(None),
+            block_source: None,
+        }
+    }
+
+    fn block_txids() -> Vec<String> {
+        (1u8..=4).map(|i| hex::encode([i; 32])).collect()
+    }
+
+    /// Service checkpointed at the mock's genesis, with the anchor tx mined in block 1
+    async fn synced_service(extra_blocks: usize) -> (BabylonService, Arc<MockBlockSource>, BitcoinAnchor) {
+        let source = Arc::new(MockBlockSource::new(block_txids()));
+        source.mine(extra_blocks);
+        let service = test_service()
+            .with_header_checkpoint(0, &source.header_hex_at(0)).unwrap()
+            .with_block_source(source.clone());
+        service.sync_headers().await.unwrap();
+
+        let mut anchor = test_anchor(&block_txids()[2]);
+        anchor.inclusion_proof = service.fetch_spv_proof(&anchor.bitcoin_tx_hash).await.unwrap();
+        (service, source, anchor)
+    }
+
+    fn test_anchor(tx_hash: &str) -> BitcoinAnchor {
+        BitcoinAnchor {
+            vault_id: "vault123".to_string(),
+            nav_hash: "abcdef".to_string(),
+            epoch: 100,
+            bitcoin_block_height: 1,
+            bitcoin_tx_hash: tx_hash.to_string(),
+            op_return_data: b"RTF".to_vec(),
+            finality_signature: "sig".to_string(),
+            timestamp: 0,
+            inclusion_proof: None,
+        }
+    }
+
+    #[test]
+    fn test_merkle_root_of_a_mainnet_block() {
+        let branch = vec![
+            "e9a66845e05d5abc0ad04ec80f774a7e585c6e8db975962d069a522137b80c1d".to_string(),
+            "ccdafb73d8dcd0173d5d5c3c9a0770d0b3953db889dab99ef05b1907518cb815".to_string(),
+        ];
+        assert_eq!(compute_merkle_root(BLOCK_100000_TX2, 2, &branch).unwrap(), BLOCK_100000_MERKLE_ROOT);
+        assert!(compute_merkle_root(BLOCK_100000_TX2, 2, &["zz".to_string()]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_anchor_on_the_validated_chain_is_accepted() {
+        let (service, _, anchor) = synced_service(5).await;
+        assert_eq!(anchor.inclusion_proof.as_ref().unwrap().tx_index, 2);
+        assert!(service.verify_anchor_inclusion(&anchor).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_confirmations_come_from_the_chain_tip() {
+        let (service, source, anchor) = synced_service(4).await;
+        assert!(!service.verify_anchor_inclusion(&anchor).await.unwrap());
+
+        source.mine(1);
+        service.sync_headers().await.unwrap();
+        assert!(service.verify_anchor_inclusion(&anchor).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_tampered_branch_or_unknown_block_is_rejected() {
+        let (service, _, anchor) = synced_service(5).await;
+
+        let mut tampered = anchor.clone();
+        tampered.inclusion_proof.as_mut().unwrap().merkle_branch[0].replace_range(0..2, "ea");
+        assert!(!service.verify_anchor_inclusion(&tampered).await.unwrap());
+
+        // Right siblings at the wrong position
+        let mut misplaced = anchor.clone();
+        misplaced.inclusion_proof.as_mut().unwrap().tx_index = 1;
+        assert!(!service.verify_anchor_inclusion(&misplaced).await.unwrap());
+
+        // A self-consistent proof for a block the header chain has never seen
+        let mut foreign = test_anchor(BLOCK_100000_TX2);
+        foreign.inclusion_proof = Some(SpvProof {
+            block_hash: "000000000003ba27aa200b1cecaad478d2b00432346c3f1f3986da1afd33e506".to_string(),
+            tx_index: 2,
+            merkle_branch: vec![
+                "e9a66845e05d5abc0ad04ec80f774a7e585c6e8db975962d069a522137b80c1d".to_string(),
+                "ccdafb73d8dcd0173d5d5c3c9a0770d0b3953db889dab99ef05b1907518cb815".to_string(),
+            ],
+        });
+        assert!(!service.verify_anchor_inclusion(&foreign).await.unwrap());
+
+        let mut unproven = anchor;
+        unproven.inclusion_proof = None;
+        assert!(!service.verify_anchor_inclusion(&unproven).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_inclusion_needs_a_header_checkpoint() {
+        let (_, _, anchor) = synced_service(5).await;
+        assert!(test_service().verify_anchor_inclusion(&anchor).await.is_err());
+    }
 
     #[tokio::test]
     async fn test_babylon_service_initialization() {
@@ -371,14 +665,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_op_return_data_creation() {
-        let service = BabylonService {
-            babylon_rpc_url: "test".to_string(),
-            bitcoin_rpc_url: "test".to_string(),
-            finality_provider_address: "test".to_string(),
-            staked_btc_amount: 56853,
-            total_value_locked: 5_640_000_000,
-            phase: BabylonPhase::Phase2,
-        };
+        let service = test_service();
 
         let op_return_data = service.create_op_return_data(
             "vault123",
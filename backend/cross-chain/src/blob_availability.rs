@@ -0,0 +1,355 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use rtf_bridge_defense::{AlertSeverity, DefenseAlert};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use tokio::sync::broadcast;
+use tracing::{error, info};
+
+use crate::celestia_service::CelestiaService;
+use crate::filecoin_service::FilecoinService;
+
+/// Data availability checks for NAV and legal-document blobs
+/// PRD: "Store in Celestia DA"
+/// A blob is committed to as the SHA-256 of its chunk hashes, so large blobs can be
+/// checked by sampling chunks instead of downloading them whole. Blobs that cannot be
+/// fetched or no longer match their commitment raise a `DefenseAlert::DataUnavailable`.
+
+pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+const ALERT_CHANNEL_CAPACITY: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DataLayer {
+    Celestia,
+    Filecoin,
+}
+
+/// Commitment recorded when a blob is stored
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobCommitment {
+    /// Hex SHA-256 over the concatenated chunk hashes
+    pub commitment: String,
+    pub layer: DataLayer,
+    pub height: u64,
+    pub size: usize,
+    pub chunk_size: usize,
+    pub chunk_hashes: Vec<String>,
+}
+
+impl BlobCommitment {
+    pub fn from_data(data: &[u8], layer: DataLayer, height: u64, chunk_size: usize) -> Self {
+        let chunk_size = chunk_size.max(1);
+        let chunk_hashes: Vec<String> = if data.is_empty() {
+            vec![hex::encode(Sha256::digest(data))]
+        } else {
+            data.chunks(chunk_size).map(|chunk| hex::encode(Sha256::digest(chunk))).collect()
+        };
+        Self {
+            commitment: chunk_root(&chunk_hashes),
+            layer,
+            height,
+            size: data.len(),
+            chunk_size,
+            chunk_hashes,
+        }
+    }
+
+    pub fn chunk_count(&self) -> usize {
+        self.chunk_hashes.len()
+    }
+
+    fn chunk_range(&self, index: usize) -> (usize, usize) {
+        let offset = index * self.chunk_size;
+        (offset, self.chunk_size.min(self.size - offset))
+    }
+}
+
+/// How much of a blob is fetched to establish availability
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SamplingStrategy {
+    /// Fetch and hash the whole blob
+    Full,
+    /// Fetch whole blobs up to `full_threshold` bytes, otherwise `samples` random chunks
+    Sampled { full_threshold: usize, samples: usize },
+}
+
+impl Default for SamplingStrategy {
+    fn default() -> Self {
+        Self::Sampled { full_threshold: 1024 * 1024, samples: 16 }
+    }
+}
+
+/// Evidence that a committed blob was retrievable and matched its commitment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AvailabilityProof {
+    pub commitment: String,
+    pub layer: DataLayer,
+    pub height: u64,
+    /// Chunk indices fetched and verified; every chunk for a full check
+    pub verified_chunks: Vec<usize>,
+    pub bytes_fetched: usize,
+    pub full_check: bool,
+    pub verified_at: i64,
+}
+
+/// Storage layer a blob can be fetched back from
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    /// Whole blob, or `None` if the layer no longer serves it
+    async fn fetch_blob(&self, commitment: &BlobCommitment) -> Result<Option<Vec<u8>>>;
+
+    /// Byte range of a blob; stores with range queries should override this
+    async fn fetch_range(&self, commitment: &BlobCommitment, offset: usize, len: usize) -> Result<Option<Vec<u8>>> {
+        Ok(self.fetch_blob(commitment).await?
+            .and_then(|blob| blob.get(offset..offset + len).map(<[u8]>::to_vec)))
+    }
+}
+
+#[async_trait]
+impl BlobStore for CelestiaService {
+    async fn fetch_blob(&self, commitment: &BlobCommitment) -> Result<Option<Vec<u8>>> {
+        match self.get_blob_by_commitment(&commitment.commitment, commitment.height).await {
+            Ok(blob) => Ok(Some(blob)),
+            Err(e) => {
+                error!("❌ Celestia blob {} not retrievable: {}", commitment.commitment, e);
+                Ok(None)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl BlobStore for FilecoinService {
+    async fn fetch_blob(&self, commitment: &BlobCommitment) -> Result<Option<Vec<u8>>> {
+        match self.retrieve_payload(&commitment.commitment).await {
+            Ok(blob) => Ok(Some(blob)),
+            Err(e) => {
+                error!("❌ Filecoin blob {} not retrievable: {}", commitment.commitment, e);
+                Ok(None)
+            }
+        }
+    }
+}
+
+pub struct BlobAvailabilityVerifier {
+    store: Box<dyn BlobStore>,
+    strategy: SamplingStrategy,
+    alert_sender: broadcast::Sender<DefenseAlert>,
+}
+
+impl BlobAvailabilityVerifier {
+    pub fn new(store: Box<dyn BlobStore>, strategy: SamplingStrategy) -> Self {
+        let (alert_sender, _) = broadcast::channel(ALERT_CHANNEL_CAPACITY);
+        Self { store, strategy, alert_sender }
+    }
+
+    /// Receive every availability alert raised after this call
+    pub fn subscribe(&self) -> broadcast::Receiver<DefenseAlert> {
+        self.alert_sender.subscribe()
+    }
+
+    /// Fetch the blob (or a sample of its chunks) and check it against the commitment
+    pub async fn verify_blob_availability(&self, commitment: &BlobCommitment) -> Result<AvailabilityProof> {
+        match self.check(commitment).await {
+            Ok(proof) => {
+                info!("✅ Blob {} available on {:?} ({} chunks verified)",
+                      commitment.commitment, commitment.layer, proof.verified_chunks.len());
+                Ok(proof)
+            }
+            Err(reason) => {
+                error!("🚨 Blob {} unavailable on {:?}: {}", commitment.commitment, commitment.layer, reason);
+                // No subscribers is not an error
+                let _ = self.alert_sender.send(DefenseAlert::DataUnavailable {
+                    commitment: commitment.commitment.clone(),
+                    layer: format!("{:?}", commitment.layer),
+                    reason: reason.clone(),
+                    severity: AlertSeverity::High,
+                });
+                Err(anyhow!("Blob {} unavailable: {}", commitment.commitment, reason))
+            }
+        }
+    }
+
+    async fn check(&self, commitment: &BlobCommitment) -> std::result::Result<AvailabilityProof, String> {
+        if chunk_root(&commitment.chunk_hashes) != commitment.commitment
+            || commitment.chunk_count() != expected_chunk_count(commitment.size, commitment.chunk_size)
+        {
+            return Err("commitment record is inconsistent with its chunk hashes".to_string());
+        }
+
+        let full_check = match self.strategy {
+            SamplingStrategy::Full => true,
+            SamplingStrategy::Sampled { full_threshold, samples } => {
+                commitment.size <= full_threshold || samples >= commitment.chunk_count()
+            }
+        };
+
+        let (verified_chunks, bytes_fetched) = if full_check {
+            let blob = self.store.fetch_blob(commitment).await
+                .map_err(|e| format!("fetch failed: {}", e))?
+                .ok_or_else(|| "blob not found".to_string())?;
+            if blob.len() != commitment.size {
+                return Err(format!("blob is {} bytes, committed {}", blob.len(), commitment.size));
+            }
+            let fetched = BlobCommitment::from_data(&blob, commitment.layer, commitment.height, commitment.chunk_size);
+            if fetched.commitment != commitment.commitment {
+                return Err("blob does not hash to its commitment".to_string());
+            }
+            ((0..commitment.chunk_count()).collect(), blob.len())
+        } else {
+            let indices = self.sample_indices(commitment);
+            let mut bytes_fetched = 0;
+            for &index in &indices {
+                let (offset, len) = commitment.chunk_range(index);
+                let chunk = self.store.fetch_range(commitment, offset, len).await
+                    .map_err(|e| format!("fetch of chunk {} failed: {}", index, e))?
+                    .ok_or_else(|| format!("chunk {} not found", index))?;
+                if chunk.len() != len || hex::encode(Sha256::digest(&chunk)) != commitment.chunk_hashes[index] {
+                    return Err(format!("chunk {} does not match its commitment", index));
+                }
+                bytes_fetched += chunk.len();
+            }
+            (indices, bytes_fetched)
+        };
+
+        Ok(AvailabilityProof {
+            commitment: commitment.commitment.clone(),
+            layer: commitment.layer,
+            height: commitment.height,
+            verified_chunks,
+            bytes_fetched,
+            full_check,
+            verified_at: chrono::Utc::now().timestamp(),
+        })
+    }
+
+    /// Distinct chunk indices, unpredictable to the store ahead of the check
+    fn sample_indices(&self, commitment: &BlobCommitment) -> Vec<usize> {
+        let samples = match self.strategy {
+            SamplingStrategy::Sampled { samples, .. } => samples.clamp(1, commitment.chunk_count()),
+            SamplingStrategy::Full => commitment.chunk_count(),
+        };
+        let seed = chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default();
+        let mut indices = BTreeSet::new();
+        let mut counter = 0u64;
+        while indices.len() < samples {
+            let digest = Sha256::new()
+                .chain_update(seed.to_le_bytes())
+                .chain_update(commitment.commitment.as_bytes())
+                .chain_update(counter.to_le_bytes())
+                .finalize();
+            let value = u64::from_le_bytes(digest[..8].try_into().expect("digest is 32 bytes"));
+            indices.insert((value % commitment.chunk_count() as u64) as usize);
+            counter += 1;
+        }
+        indices.into_iter().collect()
+    }
+}
+
+fn chunk_root(chunk_hashes: &[String]) -> String {
+    let mut hasher = Sha256::new();
+    for chunk_hash in chunk_hashes {
+        hasher.update(chunk_hash.as_bytes());
+    }
+    hex::encode(hasher.finalize())
+}
+
+fn expected_chunk_count(size: usize, chunk_size: usize) -> usize {
+    if size == 0 { 1 } else { size.div_ceil(chunk_size.max(1)) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    /// In-memory store keyed by commitment
+    #[derive(Default, Clone)]
+    struct MemoryStore {
+        blobs: Arc<RwLock<HashMap<String, Vec<u8>>>>,
+    }
+
+    #[async_trait]
+    impl BlobStore for MemoryStore {
+        async fn fetch_blob(&self, commitment: &BlobCommitment) -> Result<Option<Vec<u8>>> {
+            Ok(self.blobs.read().await.get(&commitment.commitment).cloned())
+        }
+    }
+
+    async fn stored(store: &MemoryStore, data: Vec<u8>, chunk_size: usize) -> BlobCommitment {
+        let commitment = BlobCommitment::from_data(&data, DataLayer::Celestia, 1_000_001, chunk_size);
+        store.blobs.write().await.insert(commitment.commitment.clone(), data);
+        commitment
+    }
+
+    fn nav_blob(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[tokio::test]
+    async fn test_matching_blob_is_available() {
+        let store = MemoryStore::default();
+        let small = stored(&store, nav_blob(1_000), DEFAULT_CHUNK_SIZE).await;
+        let large = stored(&store, nav_blob(10_000), 100).await;
+        let verifier = BlobAvailabilityVerifier::new(
+            Box::new(store),
+            SamplingStrategy::Sampled { full_threshold: 4_096, samples: 8 },
+        );
+
+        let proof = verifier.verify_blob_availability(&small).await.unwrap();
+        assert!(proof.full_check);
+        assert_eq!(proof.bytes_fetched, 1_000);
+
+        // Large blobs are sampled instead of downloaded
+        let proof = verifier.verify_blob_availability(&large).await.unwrap();
+        assert!(!proof.full_check);
+        assert_eq!(proof.verified_chunks.len(), 8);
+        assert_eq!(proof.bytes_fetched, 800);
+    }
+
+    #[tokio::test]
+    async fn test_corrupted_blob_raises_alert() {
+        let store = MemoryStore::default();
+        let commitment = stored(&store, nav_blob(1_000), DEFAULT_CHUNK_SIZE).await;
+        store.blobs.write().await.get_mut(&commitment.commitment).unwrap()[500] ^= 0xff;
+
+        let verifier = BlobAvailabilityVerifier::new(Box::new(store.clone()), SamplingStrategy::Full);
+        let mut alerts = verifier.subscribe();
+        assert!(verifier.verify_blob_availability(&commitment).await.is_err());
+        assert!(matches!(
+            alerts.try_recv().unwrap(),
+            DefenseAlert::DataUnavailable { commitment: ref c, .. } if *c == commitment.commitment
+        ));
+
+        // Every chunk sampled from a fully corrupted blob fails
+        let large = stored(&store, nav_blob(10_000), 100).await;
+        for byte in store.blobs.write().await.get_mut(&large.commitment).unwrap().iter_mut() {
+            *byte = byte.wrapping_add(1);
+        }
+        let sampled = BlobAvailabilityVerifier::new(
+            Box::new(store),
+            SamplingStrategy::Sampled { full_threshold: 0, samples: 4 },
+        );
+        assert!(sampled.verify_blob_availability(&large).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_missing_blob_raises_alert() {
+        let store = MemoryStore::default();
+        let commitment = BlobCommitment::from_data(&nav_blob(1_000), DataLayer::Filecoin, 42, DEFAULT_CHUNK_SIZE);
+        let verifier = BlobAvailabilityVerifier::new(Box::new(store), SamplingStrategy::default());
+        let mut alerts = verifier.subscribe();
+
+        assert!(verifier.verify_blob_availability(&commitment).await.is_err());
+        match alerts.try_recv().unwrap() {
+            DefenseAlert::DataUnavailable { layer, reason, .. } => {
+                assert_eq!(layer, "Filecoin");
+                assert_eq!(reason, "blob not found");
+            }
+            other => panic!("unexpected alert {:?}", other),
+        }
+    }
+}
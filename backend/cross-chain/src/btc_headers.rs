@@ -0,0 +1,362 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+
+/// Bitcoin header chain used to check anchor inclusion without trusting the prover
+/// Headers are accepted from a trusted checkpoint onwards, each one linking to its
+/// parent, meeting its own proof-of-work target and keeping its parent's difficulty
+/// except at retarget boundaries. Merkle roots and confirmation depth are read from
+/// this chain, never from a submitted proof.
+
+/// Blocks between difficulty adjustments
+pub const RETARGET_INTERVAL: u64 = 2016;
+
+/// Deepest reorganization `HeaderChain::sync` follows before refusing the source
+pub const MAX_REORG_DEPTH: u64 = 12;
+
+/// Parsed 80-byte block header; hashes are kept in internal (little-endian) byte order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockHeader {
+    pub version: i32,
+    pub prev_block_hash: [u8; 32],
+    pub merkle_root: [u8; 32],
+    pub time: u32,
+    pub bits: u32,
+    pub nonce: u32,
+}
+
+impl BlockHeader {
+    /// Parse a raw header as returned by `getblockheader <hash> false`
+    pub fn from_hex(header_hex: &str) -> Result<Self> {
+        let bytes: [u8; 80] = hex::decode(header_hex)?
+            .try_into()
+            .map_err(|_| anyhow!("Bitcoin block header must be 80 bytes"))?;
+        let word = |at: usize| u32::from_le_bytes(bytes[at..at + 4].try_into().expect("4 bytes"));
+        Ok(Self {
+            version: word(0) as i32,
+            prev_block_hash: bytes[4..36].try_into().expect("32 bytes"),
+            merkle_root: bytes[36..68].try_into().expect("32 bytes"),
+            time: word(68),
+            bits: word(72),
+            nonce: word(76),
+        })
+    }
+
+    pub fn to_bytes(&self) -> [u8; 80] {
+        let mut bytes = [0u8; 80];
+        bytes[0..4].copy_from_slice(&self.version.to_le_bytes());
+        bytes[4..36].copy_from_slice(&self.prev_block_hash);
+        bytes[36..68].copy_from_slice(&self.merkle_root);
+        bytes[68..72].copy_from_slice(&self.time.to_le_bytes());
+        bytes[72..76].copy_from_slice(&self.bits.to_le_bytes());
+        bytes[76..80].copy_from_slice(&self.nonce.to_le_bytes());
+        bytes
+    }
+
+    /// Double SHA-256 of the header, internal byte order
+    pub fn hash(&self) -> [u8; 32] {
+        Sha256::digest(Sha256::digest(self.to_bytes())).into()
+    }
+
+    /// Block hash as hex in RPC display order
+    pub fn block_hash(&self) -> String {
+        display_hex(self.hash())
+    }
+
+    /// Merkle root as hex in RPC display order
+    pub fn merkle_root_hex(&self) -> String {
+        display_hex(self.merkle_root)
+    }
+
+    /// Whether the header hash is at or below the target its `bits` encode
+    pub fn meets_target(&self) -> Result<bool> {
+        let mut hash = self.hash();
+        hash.reverse();
+        Ok(hash <= bits_to_target(self.bits)?)
+    }
+}
+
+/// Expand compact `bits` into a 256-bit big-endian target
+pub fn bits_to_target(bits: u32) -> Result<[u8; 32]> {
+    let exponent = (bits >> 24) as i64;
+    let mantissa = bits & 0x007f_ffff;
+    if bits & 0x0080_0000 != 0 || mantissa == 0 {
+        return Err(anyhow!("Invalid compact target {:#010x}", bits));
+    }
+
+    let mut target = [0u8; 32];
+    for (i, byte) in mantissa.to_be_bytes()[1..].iter().enumerate() {
+        // Byte `i` of the mantissa has weight 256^(exponent - 1 - i)
+        let power = exponent - 1 - i as i64;
+        if power < 0 {
+            continue;
+        }
+        if power >= 32 {
+            if *byte != 0 {
+                return Err(anyhow!("Compact target {:#010x} overflows 256 bits", bits));
+            }
+            continue;
+        }
+        target[31 - power as usize] = *byte;
+    }
+    Ok(target)
+}
+
+/// Source of Bitcoin blocks on its best chain, e.g. a Bitcoin Core node's RPC
+#[async_trait]
+pub trait BitcoinBlockSource: Send + Sync {
+    /// Height of the source's best block
+    async fn tip_height(&self) -> Result<u64>;
+
+    /// Hex raw header at `height` on the source's best chain
+    async fn header_hex(&self, height: u64) -> Result<String>;
+
+    /// Hash of the block that mined `txid` and that block's txids in order, both
+    /// display-order hex, or `None` while the transaction is unconfirmed
+    async fn containing_block(&self, txid: &str) -> Result<Option<(String, Vec<String>)>>;
+}
+
+/// Headers validated from a trusted checkpoint to the best known tip
+#[derive(Debug, Clone)]
+pub struct HeaderChain {
+    base_height: u64,
+    headers: Vec<BlockHeader>,
+}
+
+impl HeaderChain {
+    /// Start a chain at a header trusted out of band
+    pub fn from_checkpoint(height: u64, header: BlockHeader) -> Self {
+        Self { base_height: height, headers: vec![header] }
+    }
+
+    pub fn tip_height(&self) -> u64 {
+        self.base_height + self.headers.len() as u64 - 1
+    }
+
+    pub fn header_at(&self, height: u64) -> Option<&BlockHeader> {
+        height.checked_sub(self.base_height).and_then(|offset| self.headers.get(offset as usize))
+    }
+
+    /// Height of a block on this chain, by display-order hash
+    pub fn height_of(&self, block_hash: &str) -> Option<u64> {
+        self.headers.iter()
+            .position(|header| header.block_hash().eq_ignore_ascii_case(block_hash))
+            .map(|offset| self.base_height + offset as u64)
+    }
+
+    /// Confirmations of a block on this chain, counting the block itself
+    pub fn confirmations(&self, block_hash: &str) -> Option<u64> {
+        self.height_of(block_hash).map(|height| self.tip_height() - height + 1)
+    }
+
+    /// Append a header that extends the tip
+    pub fn push(&mut self, header: BlockHeader) -> Result<()> {
+        let height = self.tip_height() + 1;
+        let parent = self.headers.last().expect("chain holds its checkpoint");
+
+        if header.prev_block_hash != parent.hash() {
+            return Err(anyhow!("Header at height {} does not link to {}", height, parent.block_hash()));
+        }
+        if height % RETARGET_INTERVAL == 0 {
+            let (parent_target, target) = (bits_to_target(parent.bits)?, bits_to_target(header.bits)?);
+            if target > shift_left_2(parent_target) || target < shift_right_2(parent_target) {
+                return Err(anyhow!("Retarget at height {} moves difficulty more than 4x", height));
+            }
+        } else if header.bits != parent.bits {
+            return Err(anyhow!("Header at height {} changes difficulty outside a retarget", height));
+        }
+        if !header.meets_target()? {
+            return Err(anyhow!("Header at height {} does not meet its proof-of-work target", height));
+        }
+
+        self.headers.push(header);
+        Ok(())
+    }
+
+    /// Follow the source's best chain, adopting a reorganization only when it is
+    /// no deeper than `MAX_REORG_DEPTH` and ends on a longer chain than ours
+    ///
+    /// Returns the resulting tip height.
+    pub async fn sync(&mut self, source: &dyn BitcoinBlockSource) -> Result<u64> {
+        let source_tip = source.tip_height().await?;
+        if source_tip < self.base_height {
+            return Err(anyhow!("Block source tip {} is below the checkpoint {}", source_tip, self.base_height));
+        }
+
+        let mut fork = self.tip_height().min(source_tip);
+        loop {
+            let header = BlockHeader::from_hex(&source.header_hex(fork).await?)?;
+            if self.header_at(fork) == Some(&header) {
+                break;
+            }
+            if fork == self.base_height {
+                return Err(anyhow!("Block source is not on the checkpointed chain"));
+            }
+            if self.tip_height() - fork >= MAX_REORG_DEPTH {
+                return Err(anyhow!("Block source diverges more than {} blocks deep", MAX_REORG_DEPTH));
+            }
+            fork -= 1;
+        }
+
+        let mut candidate = Self {
+            base_height: self.base_height,
+            headers: self.headers[..=(fork - self.base_height) as usize].to_vec(),
+        };
+        for height in fork + 1..=source_tip {
+            candidate.push(BlockHeader::from_hex(&source.header_hex(height).await?)?)?;
+        }
+
+        if fork == self.tip_height() || candidate.tip_height() > self.tip_height() {
+            *self = candidate;
+        }
+        Ok(self.tip_height())
+    }
+}
+
+/// SPV branch for the transaction at `index` among a block's txids (display-order hex)
+pub fn merkle_branch(txids: &[String], index: usize) -> Result<Vec<String>> {
+    if index >= txids.len() {
+        return Err(anyhow!("Transaction index {} is outside a block of {}", index, txids.len()));
+    }
+
+    let mut level = txids.iter().map(|txid| internal_bytes(txid)).collect::<Result<Vec<_>>>()?;
+    let mut index = index;
+    let mut branch = Vec::new();
+    while level.len() > 1 {
+        if level.len() % 2 == 1 {
+            level.push(*level.last().expect("level is not empty"));
+        }
+        branch.push(display_hex(level[index ^ 1]));
+        level = level.chunks(2)
+            .map(|pair| Sha256::digest(Sha256::digest([pair[0], pair[1]].concat())).into())
+            .collect();
+        index >>= 1;
+    }
+    Ok(branch)
+}
+
+fn internal_bytes(display: &str) -> Result<[u8; 32]> {
+    let mut bytes: [u8; 32] = hex::decode(display)?
+        .try_into()
+        .map_err(|_| anyhow!("Bitcoin hash must be 32 bytes: {}", display))?;
+    bytes.reverse();
+    Ok(bytes)
+}
+
+fn display_hex(mut internal: [u8; 32]) -> String {
+    internal.reverse();
+    hex::encode(internal)
+}
+
+/// `target * 4`, saturating at the largest 256-bit value
+fn shift_left_2(target: [u8; 32]) -> [u8; 32] {
+    if target[0] & 0xc0 != 0 {
+        return [0xff; 32];
+    }
+    let mut shifted = [0u8; 32];
+    for (i, byte) in shifted.iter_mut().enumerate() {
+        *byte = target[i] << 2 | target.get(i + 1).map_or(0, |next| next >> 6);
+    }
+    shifted
+}
+
+/// `target / 4`
+fn shift_right_2(target: [u8; 32]) -> [u8; 32] {
+    let mut shifted = [0u8; 32];
+    for (i, byte) in shifted.iter_mut().enumerate() {
+        *byte = target[i] >> 2 | i.checked_sub(1).map_or(0, |previous| target[previous] << 6);
+    }
+    shifted
+}
+
+/// Mine a header on an easy target; a 0x207fffff target needs about two attempts
+#[cfg(test)]
+pub(crate) fn mine_header(prev_block_hash: [u8; 32], merkle_root: [u8; 32], bits: u32) -> BlockHeader {
+    let mut header = BlockHeader { version: 1, prev_block_hash, merkle_root, time: 1_700_000_000, bits, nonce: 0 };
+    while !header.meets_target().unwrap() {
+        header.nonce += 1;
+    }
+    header
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Bitcoin block 100000
+    const BLOCK_100000_HEADER: &str = "0100000050120119172a610421a6c3011dd330d9df07b63616c2cc1f1cd00200000000006657a9252aacd5c0b2940996ecff952228c3067cc38d4885efb5a4ac4247e9f337221b4d4c86041b0f2b5710";
+    const BLOCK_100000_HASH: &str = "000000000003ba27aa200b1cecaad478d2b00432346c3f1f3986da1afd33e506";
+    const BLOCK_100000_MERKLE_ROOT: &str = "f3e94742aca4b5ef85488dc37c06c3282295ffec960994b2c0d5ac2a25a95766";
+    const EASY_BITS: u32 = 0x207f_ffff;
+
+    fn easy_chain(blocks: u64) -> HeaderChain {
+        let mut chain = HeaderChain::from_checkpoint(0, mine_header([0; 32], [1; 32], EASY_BITS));
+        for _ in 0..blocks {
+            let tip = chain.header_at(chain.tip_height()).unwrap().hash();
+            chain.push(mine_header(tip, [2; 32], EASY_BITS)).unwrap();
+        }
+        chain
+    }
+
+    #[test]
+    fn test_mainnet_header_parses_and_meets_its_target() {
+        let header = BlockHeader::from_hex(BLOCK_100000_HEADER).unwrap();
+        assert_eq!(header.block_hash(), BLOCK_100000_HASH);
+        assert_eq!(header.merkle_root_hex(), BLOCK_100000_MERKLE_ROOT);
+        assert_eq!(hex::encode(header.to_bytes()), BLOCK_100000_HEADER);
+        assert!(header.meets_target().unwrap());
+
+        // The same header claiming a 16x harder target fails proof-of-work
+        let harder = BlockHeader { bits: 0x1a48_64c0, ..header };
+        assert!(!harder.meets_target().unwrap());
+    }
+
+    #[test]
+    fn test_chain_rejects_unlinked_underworked_or_retargeted_headers() {
+        let mut chain = easy_chain(2);
+        let tip = chain.header_at(2).unwrap().hash();
+
+        assert!(chain.push(mine_header([9; 32], [2; 32], EASY_BITS)).is_err());
+
+        let mut underworked = mine_header(tip, [2; 32], EASY_BITS);
+        while underworked.meets_target().unwrap() {
+            underworked.nonce += 1;
+        }
+        assert!(chain.push(underworked).is_err());
+
+        // Difficulty only moves at retarget boundaries
+        assert!(chain.push(mine_header(tip, [2; 32], 0x2000_ffff)).is_err());
+        assert_eq!(chain.tip_height(), 2);
+    }
+
+    #[test]
+    fn test_confirmations_are_counted_from_the_tip() {
+        let chain = easy_chain(5);
+        let block_1 = chain.header_at(1).unwrap().block_hash();
+        assert_eq!(chain.confirmations(&block_1), Some(5));
+        assert_eq!(chain.confirmations(BLOCK_100000_HASH), None);
+    }
+
+    #[test]
+    fn test_merkle_branch_rebuilds_the_block_root() {
+        let txids: Vec<String> = (1u8..=5).map(|i| hex::encode([i; 32])).collect();
+        let root = {
+            let branch = merkle_branch(&txids, 0).unwrap();
+            crate::babylon_service::compute_merkle_root(&txids[0], 0, &branch).unwrap()
+        };
+        for (index, txid) in txids.iter().enumerate() {
+            let branch = merkle_branch(&txids, index).unwrap();
+            assert_eq!(branch.len(), 3);
+            assert_eq!(crate::babylon_service::compute_merkle_root(txid, index as u32, &branch).unwrap(), root);
+        }
+        assert!(merkle_branch(&txids, 5).is_err());
+    }
+
+    #[test]
+    fn test_retarget_is_bounded_to_four_times() {
+        let target = bits_to_target(0x1b04_864c).unwrap();
+        assert!(shift_left_2(target) > target);
+        assert_eq!(shift_right_2(shift_left_2(target)), target);
+        assert_eq!(shift_left_2([0xff; 32]), [0xff; 32]);
+    }
+}
@@ -4,6 +4,8 @@ use std::collections::HashMap;
 use tokio::time::{Duration, sleep};
 use tracing::{info, warn, error};
 
+use crate::TransferNotSent;
+
 /// Chainlink CCIP v1.6.0 Service with SVM Support and Programmable Tokens
 /// July 2025 - Production-ready cross-chain infrastructure
 pub struct CCIPService {
@@ -52,6 +54,8 @@ pub struct CrossChainVaultSync {
     pub nav_data: NavData,
     pub proof_hash: String,
     pub timestamp: i64,
+    /// Replay-protection nonce for each destination chain
+    pub destination_nonces: HashMap<u64, u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -130,11 +134,13 @@ impl CCIPService {
     ) -> Result<String> {
         info!("💰 Sending programmable token transfer: {} tokens to chain {}", amount, dest_chain);
 
+        // Nothing has reached the router if the message cannot be built, so the transfer can be retried
+        let not_sent = |e: anyhow::Error| TransferNotSent { reason: e.to_string() };
         let message = CCIPMessage {
             message_id: String::new(), // Will be set by router
-            source_chain_selector: self.get_chain_selector(source_chain)?,
-            destination_chain_selector: self.get_chain_selector(dest_chain)?,
-            sender: self.get_vault_address(source_chain)?,
+            source_chain_selector: self.get_chain_selector(source_chain).map_err(not_sent)?,
+            destination_chain_selector: self.get_chain_selector(dest_chain).map_err(not_sent)?,
+            sender: self.get_vault_address(source_chain).map_err(not_sent)?,
             receiver,
             data: custom_logic,
             token_amounts: vec![TokenAmount {
@@ -142,7 +148,7 @@ impl CCIPService {
                 amount,
             }],
             fee_token: "LINK".to_string(),
-            extra_args: self.encode_gas_limit(500_000)?,
+            extra_args: self.encode_gas_limit(500_000).map_err(not_sent)?,
         };
 
         let message_id = self.send_ccip_message(message).await?;
@@ -185,6 +191,16 @@ impl CCIPService {
         }
     }
 
+    /// Read the latest NAV root accepted by a vault on a destination chain
+    ///
+    /// Receiver contracts expose no read path yet, so this fails with
+    /// `NavRootReadUnsupported` rather than reporting that no root was applied.
+    pub async fn read_vault_nav_root(&self, vault_id: &str, chain_id: u64) -> Result<Option<String>> {
+        self.get_chain_selector(chain_id)?;
+        warn!("⚠️ Cannot read NAV root for vault {} on chain {}: receiver reads are unsupported", vault_id, chain_id);
+        Err(NavRootReadUnsupported { chain_id }.into())
+    }
+
     // Private helper methods
     async fn verify_router_connectivity(&self) -> Result<()> {
         // Implement CCIP router connectivity verification
@@ -280,6 +296,13 @@ impl CCIPService {
     }
 }
 
+/// Reading a vault's applied NAV root back from a destination chain is not supported
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Reading vault NAV roots from chain {chain_id} is not supported")]
+pub struct NavRootReadUnsupported {
+    pub chain_id: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MessageStatus {
     Pending,
@@ -313,4 +336,27 @@ mod tests {
 
         assert!(service.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_nav_root_read_reports_unsupported() {
+        let mut chain_configs = HashMap::new();
+        chain_configs.insert(1, ChainConfig {
+            chain_id: 1,
+            chain_selector: 5009297550715157269,
+            rpc_url: "https://eth-mainnet.g.alchemy.com/v2/test".to_string(),
+            router_address: "0x80226fc0Ee2b096224EeAc085Bb9a8cba1146f7D".to_string(),
+            token_pool: "0x742d35Cc6634C0532925a3b8D4C9db96C4b4Db44".to_string(),
+            gas_limit: 500_000,
+            confirmation_blocks: 12,
+        });
+        let service = CCIPService::new_with_svm_support(
+            "0x80226fc0Ee2b096224EeAc085Bb9a8cba1146f7D".to_string(),
+            "0x742d35Cc6634C0532925a3b8D4C9db96C4b4Db44".to_string(),
+            chain_configs,
+        ).await.unwrap();
+
+        let err = service.read_vault_nav_root("vault-1", 1).await.unwrap_err();
+        assert_eq!(err.downcast_ref::<NavRootReadUnsupported>(), Some(&NavRootReadUnsupported { chain_id: 1 }));
+        assert!(service.read_vault_nav_root("vault-1", 999).await.unwrap_err().downcast_ref::<NavRootReadUnsupported>().is_none());
+    }
 }
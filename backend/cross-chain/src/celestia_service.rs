@@ -1,9 +1,12 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use tokio::sync::RwLock;
 use tokio::time::{Duration, sleep};
 use tracing::{info, warn, error};
 
+use crate::blob_availability::{BlobCommitment, DataLayer, DEFAULT_CHUNK_SIZE};
+
 /// Celestia Data Availability Service for RTF Infrastructure
 /// PRD: "Store in Celestia DA" - Data availability layer for NAV anchoring
 /// July 2025: 128MB blocks with 21.33MB/s throughput on mamo-1 testnet
@@ -14,6 +17,8 @@ pub struct CelestiaService {
     max_blob_size: usize,
     gas_price: u64,
     timeout_seconds: u64,
+    /// Submitted blobs by commitment, standing in for the Celestia network
+    blobs: RwLock<HashMap<String, Vec<u8>>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +34,8 @@ pub struct CelestiaBlob {
 pub struct BlobSubmissionResult {
     pub height: u64,
     pub commitment: String,
+    /// Record to keep for `BlobAvailabilityVerifier::verify_blob_availability`
+    pub blob_commitment: BlobCommitment,
     pub share_commitments: Vec<String>,
     pub namespace_id: String,
     pub blob_size: usize,
@@ -85,6 +92,7 @@ impl CelestiaService {
             max_blob_size: 128 * 1024 * 1024, // 128MB max blob size
             gas_price: 1000, // Adjusted for July 2025
             timeout_seconds: 60,
+            blobs: RwLock::new(HashMap::new()),
         };
 
         // Verify Celestia connectivity
@@ -114,7 +122,7 @@ impl CelestiaService {
             namespace_id: self.namespace_id.clone(),
             data: nav_json,
             share_version: 0,
-            commitment: String::new(), // Will be computed by Celestia
+            commitment: String::new(), // Computed on submission
             proof: Vec::new(),         // Will be generated by Celestia
         };
 
@@ -258,11 +266,15 @@ impl CelestiaService {
         // In production, this would use the actual Celestia API
         
         let current_height = self.get_current_height().await?;
-        let commitment = self.calculate_commitment(&blob.data)?;
-        
+        let height = current_height + 1;
+        // Same chunked commitment the availability verifier checks the blob against
+        let blob_commitment = BlobCommitment::from_data(&blob.data, DataLayer::Celestia, height, DEFAULT_CHUNK_SIZE);
+        self.blobs.write().await.insert(blob_commitment.commitment.clone(), blob.data.clone());
+
         let result = BlobSubmissionResult {
-            height: current_height + 1,
-            commitment,
+            height,
+            commitment: blob_commitment.commitment.clone(),
+            blob_commitment,
             share_commitments: vec![format!("share_commit_{}", chrono::Utc::now().timestamp())],
             namespace_id: blob.namespace_id,
             blob_size: blob.data.len(),
@@ -276,7 +288,7 @@ impl CelestiaService {
         Ok(result)
     }
 
-    async fn get_blob_by_commitment(&self, commitment: &str, height: u64) -> Result<Vec<u8>> {
+    pub(crate) async fn get_blob_by_commitment(&self, commitment: &str, height: u64) -> Result<Vec<u8>> {
         // Simulate blob retrieval from Celestia
         // In production, this would query the actual Celestia network
         
@@ -284,9 +296,9 @@ impl CelestiaService {
         
         // Simulate network delay
         sleep(Duration::from_millis(200)).await;
-        
-        // Return mock data for now
-        Ok(b"mock_blob_data".to_vec())
+
+        self.blobs.read().await.get(commitment).cloned()
+            .ok_or_else(|| anyhow!("No blob with commitment {} at height {}", commitment, height))
     }
 
     async fn get_blobs_at_height(&self, height: u64) -> Result<Vec<Vec<u8>>> {
@@ -307,19 +319,12 @@ impl CelestiaService {
         // In production, this would query the actual Celestia network
         Ok(1000000) // Mock height for July 2025
     }
-
-    fn calculate_commitment(&self, data: &[u8]) -> Result<String> {
-        // Simulate commitment calculation
-        // In production, this would use the actual Celestia commitment scheme
-        use sha2::{Sha256, Digest};
-        let hash = Sha256::digest(data);
-        Ok(format!("celestia_commit_{}", hex::encode(&hash[..16])))
-    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::blob_availability::{BlobAvailabilityVerifier, SamplingStrategy};
 
     #[tokio::test]
     async fn test_celestia_service_initialization() {
@@ -334,18 +339,20 @@ mod tests {
         assert_eq!(service.max_blob_size, 128 * 1024 * 1024);
     }
 
-    #[tokio::test]
-    async fn test_nav_data_storage() {
-        let service = CelestiaService {
+    fn test_service() -> CelestiaService {
+        CelestiaService {
             node_url: "http://localhost:26658".to_string(),
             auth_token: "test".to_string(),
             namespace_id: "test_namespace".to_string(),
             max_blob_size: 128 * 1024 * 1024,
             gas_price: 1000,
             timeout_seconds: 60,
-        };
+            blobs: RwLock::new(HashMap::new()),
+        }
+    }
 
-        let nav_data = NAVDataBlob {
+    fn nav_data() -> NAVDataBlob {
+        NAVDataBlob {
             vault_id: "test_vault".to_string(),
             epoch: 100,
             nav_per_share: 1_100_000,
@@ -360,9 +367,29 @@ mod tests {
                 bitcoin_tx_hash: "test_bitcoin".to_string(),
                 icp_canister_id: "test_icp".to_string(),
             },
-        };
+        }
+    }
 
-        let result = service.store_nav_data(nav_data).await;
+    #[tokio::test]
+    async fn test_nav_data_storage() {
+        let service = test_service();
+        let result = service.store_nav_data(nav_data()).await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_stored_nav_data_passes_availability_verification() {
+        let service = test_service();
+        let result = service.store_nav_data(nav_data()).await.unwrap();
+        assert_eq!(result.commitment, result.blob_commitment.commitment);
+        assert_eq!(result.blob_commitment.layer, DataLayer::Celestia);
+
+        let retrieved = service.retrieve_nav_data(&result.commitment, result.height).await.unwrap();
+        assert_eq!(retrieved.vault_id, "test_vault");
+
+        let verifier = BlobAvailabilityVerifier::new(Box::new(service), SamplingStrategy::Full);
+        let proof = verifier.verify_blob_availability(&result.blob_commitment).await.unwrap();
+        assert!(proof.full_check);
+        assert_eq!(proof.bytes_fetched, result.blob_size);
+    }
 }
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Circuit breaker guarding a single sync leg or destination chain
+/// Opens after consecutive failed syncs and short-circuits attempts until the cooldown elapses
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BreakerState {
+    Closed,
+    /// Attempts are short-circuited for the remaining cooldown
+    Open { retry_after_ms: u64 },
+    /// Cooldown elapsed; the next attempt decides whether the breaker closes or reopens
+    HalfOpen,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CircuitBreaker {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    pub fn state(&self, cooldown: Duration) -> BreakerState {
+        match self.opened_at {
+            None => BreakerState::Closed,
+            Some(opened_at) => {
+                let elapsed = opened_at.elapsed();
+                if elapsed >= cooldown {
+                    BreakerState::HalfOpen
+                } else {
+                    BreakerState::Open {
+                        retry_after_ms: (cooldown - elapsed).as_millis() as u64,
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn allows_attempt(&self, cooldown: Duration) -> bool {
+        !matches!(self.state(cooldown), BreakerState::Open { .. })
+    }
+
+    pub fn record_success(&mut self) {
+        self.consecutive_failures = 0;
+        self.opened_at = None;
+    }
+
+    /// Record a failed sync, opening (or reopening) the breaker at `threshold`
+    pub fn record_failure(&mut self, threshold: u32) {
+        self.consecutive_failures += 1;
+        if self.consecutive_failures >= threshold {
+            self.opened_at = Some(Instant::now());
+        }
+    }
+
+    pub fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breaker_opens_at_threshold_and_half_opens_after_cooldown() {
+        let mut breaker = CircuitBreaker::default();
+        let cooldown = Duration::from_millis(20);
+
+        breaker.record_failure(2);
+        assert_eq!(breaker.state(cooldown), BreakerState::Closed);
+        breaker.record_failure(2);
+        assert!(!breaker.allows_attempt(cooldown));
+
+        std::thread::sleep(cooldown);
+        assert_eq!(breaker.state(cooldown), BreakerState::HalfOpen);
+
+        breaker.record_success();
+        assert_eq!(breaker.state(cooldown), BreakerState::Closed);
+        assert_eq!(breaker.consecutive_failures(), 0);
+    }
+}
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ccip_service::NavData;
+
+/// Compensation for partially applied vault syncs
+/// When the Bitcoin anchor lands but CCIP delivery fails, the anchored NAV is not what
+/// the destination chains hold. A `CompensationPlan` records which legs succeeded and
+/// how to reconcile: resend the anchored NAV, or failing that, supersede the anchor so
+/// the next sync replaces it. A plan only applies while its sync is the vault's latest;
+/// once a newer sync starts, resending the older NAV would roll the chains back.
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompensationAction {
+    /// Send a correcting vault sync carrying the anchored NAV to chains that missed it
+    ResendNavSync { chain_ids: Vec<u64> },
+    /// Mark the anchor superseded so the next successful sync replaces it
+    SupersedeBitcoinAnchor { bitcoin_tx_hash: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompensationPlan {
+    pub plan_id: String,
+    pub vault_id: String,
+    pub nav_data: NavData,
+    /// Sync version of the vault the plan was created for
+    pub nav_version: u64,
+    pub succeeded_legs: Vec<String>,
+    pub failed_legs: Vec<String>,
+    /// Applied in order; `SupersedeBitcoinAnchor` only runs if the resend fails
+    pub actions: Vec<CompensationAction>,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompensationOutcome {
+    pub plan_id: String,
+    pub resent_chains: Vec<u64>,
+    pub message_ids: Vec<String>,
+    pub superseded_anchor: Option<String>,
+    /// A newer sync of the vault started first, so no action was taken
+    pub obsolete: bool,
+    pub completed_at: i64,
+}
+
+/// Progress of a plan, recorded before any action runs so concurrent calls can't both apply it
+#[derive(Debug, Clone)]
+pub(crate) enum CompensationState {
+    InProgress,
+    Completed(CompensationOutcome),
+}
+
+impl CompensationPlan {
+    pub(crate) fn for_partial_sync(
+        vault_id: &str,
+        nav_data: &NavData,
+        nav_version: u64,
+        bitcoin_tx_hash: &str,
+        succeeded_legs: Vec<String>,
+        failed_legs: Vec<String>,
+        failed_chain_ids: Vec<u64>,
+    ) -> Self {
+        Self {
+            plan_id: uuid::Uuid::new_v4().to_string(),
+            vault_id: vault_id.to_string(),
+            nav_data: nav_data.clone(),
+            nav_version,
+            succeeded_legs,
+            failed_legs,
+            actions: vec![
+                CompensationAction::ResendNavSync { chain_ids: failed_chain_ids },
+                CompensationAction::SupersedeBitcoinAnchor { bitcoin_tx_hash: bitcoin_tx_hash.to_string() },
+            ],
+            created_at: chrono::Utc::now().timestamp(),
+        }
+    }
+}
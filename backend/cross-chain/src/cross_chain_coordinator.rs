@@ -12,6 +12,18 @@ pub struct CrossChainCoordinator {
     chain_health: RwLock<HashMap<u64, ChainHealth>>,
     operation_timeout: Duration,
     max_retries: u32,
+    outbound_nonces: RwLock<HashMap<(String, u64), u64>>,
+    processed_nonces: RwLock<HashMap<(String, u64), u64>>,
+    nonce_gap_policy: NonceGapPolicy,
+}
+
+/// How inbound sync nonces that skip ahead of the last processed nonce are treated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NonceGapPolicy {
+    /// Only `last + 1` is accepted; later nonces wait for the gap to fill
+    RejectGaps,
+    /// Any nonce above the last processed one is accepted, skipping the gap
+    AllowGaps,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -86,6 +98,9 @@ impl CrossChainCoordinator {
             chain_health: RwLock::new(HashMap::new()),
             operation_timeout: Duration::from_secs(300), // 5 minutes
             max_retries: 3,
+            outbound_nonces: RwLock::new(HashMap::new()),
+            processed_nonces: RwLock::new(HashMap::new()),
+            nonce_gap_policy: NonceGapPolicy::RejectGaps,
         };
 
         // Initialize chain health monitoring
@@ -95,6 +110,57 @@ impl CrossChainCoordinator {
         Ok(coordinator)
     }
 
+    /// Set how inbound nonce gaps are handled
+    pub fn with_nonce_gap_policy(mut self, policy: NonceGapPolicy) -> Self {
+        self.nonce_gap_policy = policy;
+        self
+    }
+
+    /// Allocate the next outbound sync nonce for a vault on a destination chain (starting at 1)
+    pub async fn next_sync_nonce(&self, vault_id: &str, chain_id: u64) -> u64 {
+        let mut nonces = self.outbound_nonces.write().await;
+        let nonce = nonces.entry((vault_id.to_string(), chain_id)).or_insert(0);
+        *nonce += 1;
+        *nonce
+    }
+
+    /// Accept an inbound sync nonce, rejecting replays and out-of-order delivery
+    pub async fn accept_sync_nonce(&self, vault_id: &str, chain_id: u64, nonce: u64) -> Result<()> {
+        let mut processed = self.processed_nonces.write().await;
+        let key = (vault_id.to_string(), chain_id);
+        let last = processed.get(&key).copied().unwrap_or(0);
+
+        if nonce <= last {
+            return Err(anyhow::anyhow!(
+                "Replayed sync nonce {} for vault {} on chain {} (last processed {})",
+                nonce, vault_id, chain_id, last
+            ));
+        }
+        if nonce > last + 1 {
+            match self.nonce_gap_policy {
+                NonceGapPolicy::RejectGaps => {
+                    return Err(anyhow::anyhow!(
+                        "Sync nonce gap for vault {} on chain {}: expected {}, got {}",
+                        vault_id, chain_id, last + 1, nonce
+                    ));
+                }
+                NonceGapPolicy::AllowGaps => {
+                    warn!("⚠️ Skipping sync nonces {}..{} for vault {} on chain {}",
+                          last + 1, nonce, vault_id, chain_id);
+                }
+            }
+        }
+
+        processed.insert(key, nonce);
+        Ok(())
+    }
+
+    /// Last sync nonce processed for a vault on a chain
+    pub async fn last_processed_nonce(&self, vault_id: &str, chain_id: u64) -> Option<u64> {
+        let processed = self.processed_nonces.read().await;
+        processed.get(&(vault_id.to_string(), chain_id)).copied()
+    }
+
     /// Coordinate cross-chain operation with fault tolerance
     pub async fn coordinate_operation(
         &self,
@@ -424,3 +490,46 @@ struct BatchResult {
     successful_chains: Vec<u64>,
     failed_chains: Vec<u64>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_outbound_nonces_increase_per_destination() {
+        let coordinator = CrossChainCoordinator::new().await.unwrap();
+
+        assert_eq!(coordinator.next_sync_nonce("vault_1", 1).await, 1);
+        assert_eq!(coordinator.next_sync_nonce("vault_1", 1).await, 2);
+        assert_eq!(coordinator.next_sync_nonce("vault_1", 43114).await, 1);
+        assert_eq!(coordinator.next_sync_nonce("vault_2", 1).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_replayed_nonce_rejected() {
+        let coordinator = CrossChainCoordinator::new().await.unwrap();
+
+        assert!(coordinator.accept_sync_nonce("vault_1", 1, 1).await.is_ok());
+        assert!(coordinator.accept_sync_nonce("vault_1", 1, 2).await.is_ok());
+        assert!(coordinator.accept_sync_nonce("vault_1", 1, 2).await.is_err());
+        assert!(coordinator.accept_sync_nonce("vault_1", 1, 1).await.is_err());
+        assert_eq!(coordinator.last_processed_nonce("vault_1", 1).await, Some(2));
+        assert_eq!(coordinator.last_processed_nonce("vault_1", 43114).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_nonce_gap_handled_per_policy() {
+        let strict = CrossChainCoordinator::new().await.unwrap();
+        strict.accept_sync_nonce("vault_1", 1, 1).await.unwrap();
+        assert!(strict.accept_sync_nonce("vault_1", 1, 3).await.is_err());
+        assert_eq!(strict.last_processed_nonce("vault_1", 1).await, Some(1));
+
+        let lenient = CrossChainCoordinator::new().await.unwrap()
+            .with_nonce_gap_policy(NonceGapPolicy::AllowGaps);
+        lenient.accept_sync_nonce("vault_1", 1, 1).await.unwrap();
+        assert!(lenient.accept_sync_nonce("vault_1", 1, 3).await.is_ok());
+        assert_eq!(lenient.last_processed_nonce("vault_1", 1).await, Some(3));
+        // The skipped nonce arrives late and is treated as a replay
+        assert!(lenient.accept_sync_nonce("vault_1", 1, 2).await.is_err());
+    }
+}
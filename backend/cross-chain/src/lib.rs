@@ -4,6 +4,12 @@ pub mod icp_service;
 pub mod cross_chain_coordinator;
 pub mod celestia_service;
 pub mod zknav_cross_chain;
+pub mod sync_legs;
+pub mod circuit_breaker;
+pub mod blob_availability;
+pub mod transfer_idempotency;
+pub mod compensation;
+pub mod btc_headers;
 
 pub use ccip_service::*;
 pub use babylon_service::*;
@@ -11,6 +17,12 @@ pub use icp_service::*;
 pub use cross_chain_coordinator::*;
 pub use celestia_service::*;
 pub use zknav_cross_chain::*;
+pub use sync_legs::*;
+pub use circuit_breaker::*;
+pub use blob_availability::*;
+pub use transfer_idempotency::*;
+pub use compensation::*;
+pub use btc_headers::*;
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
@@ -75,29 +87,77 @@ pub use filecoin_service::*;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tokio::sync::RwLock;
-use tracing::{info, error};
+use std::sync::Arc;
+use tokio::sync::{RwLock, Semaphore};
+use tokio::time::Duration;
+use tracing::{info, warn, error};
 
 /// Comprehensive Cross-Chain Service for RTF Infrastructure
 /// July 2025 - Multi-chain coordination with latest technologies
 pub struct CrossChainService {
-    ccip_service: CCIPService,
-    babylon_service: BabylonService,
-    icp_service: ICPService,
+    bitcoin_anchor: Arc<dyn BitcoinAnchorLeg>,
+    messaging: Arc<dyn MessagingLeg>,
+    verification: Arc<dyn VerificationLeg>,
     coordinator: CrossChainCoordinator,
     supported_chains: RwLock<HashMap<u64, ChainInfo>>,
+    sync_config: CrossChainSyncConfig,
+    breakers: RwLock<HashMap<String, CircuitBreaker>>,
+    transfers: Arc<TransferIdempotency>,
+    compensations: RwLock<HashMap<String, CompensationState>>,
+    superseded_anchors: RwLock<HashMap<String, Vec<String>>>,
+    /// Number of syncs started per vault; a compensation plan is current only while this
+    /// still equals its `nav_version`
+    nav_versions: RwLock<HashMap<String, u64>>,
+}
+
+/// Execution limits for comprehensive vault syncs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrossChainSyncConfig {
+    /// Maximum number of sync legs (Bitcoin, CCIP, ICP) in flight at once
+    pub max_concurrent_legs: usize,
+    /// Per-leg timeout; a timed out leg is recorded as failed without cancelling the others
+    pub verification_timeout_seconds: u64,
+    /// Retries per leg after the first attempt, with exponential backoff
+    pub max_leg_retries: u32,
+    pub retry_base_delay_ms: u64,
+    /// Consecutive failed syncs before a leg or chain's circuit breaker opens
+    pub breaker_failure_threshold: u32,
+    pub breaker_cooldown_seconds: u64,
+    /// How long a transfer idempotency key is remembered
+    pub idempotency_window_seconds: u64,
+}
+
+impl Default for CrossChainSyncConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_legs: 3,
+            verification_timeout_seconds: 300, // 5 minutes
+            max_leg_retries: 3,
+            retry_base_delay_ms: 500,
+            breaker_failure_threshold: 5,
+            breaker_cooldown_seconds: 300,
+            idempotency_window_seconds: DEFAULT_IDEMPOTENCY_WINDOW_SECONDS,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChainInfo {
     pub chain_id: u64,
     pub name: String,
+    pub chain_type: ChainType,
     pub rpc_url: String,
     pub vault_address: String,
     pub status: ChainStatus,
     pub last_sync: i64,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChainType {
+    Evm,
+    Solana,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ChainStatus {
     Active,
@@ -112,6 +172,7 @@ impl CrossChainService {
         ccip_config: CCIPConfig,
         babylon_config: BabylonConfig,
         icp_config: ICPConfig,
+        chains: Vec<ChainInfo>,
     ) -> Result<Self> {
         info!("🌐 Initializing Comprehensive Cross-Chain Service");
 
@@ -134,24 +195,54 @@ impl CrossChainService {
             icp_config.identity_path,
         ).await?;
 
+        let service = Self::with_sync_legs(
+            Arc::new(babylon_service),
+            Arc::new(ccip_service),
+            Arc::new(icp_service),
+            chains,
+            CrossChainSyncConfig::default(),
+        ).await?;
+
+        info!("✅ Cross-Chain Service initialized with all protocols");
+        Ok(service)
+    }
+
+    /// Build a service over explicit sync legs, registering `chains` for sync fan-out
+    pub async fn with_sync_legs(
+        bitcoin_anchor: Arc<dyn BitcoinAnchorLeg>,
+        messaging: Arc<dyn MessagingLeg>,
+        verification: Arc<dyn VerificationLeg>,
+        chains: Vec<ChainInfo>,
+        sync_config: CrossChainSyncConfig,
+    ) -> Result<Self> {
         let coordinator = CrossChainCoordinator::new().await?;
+        let transfers = Arc::new(TransferIdempotency::new(Duration::from_secs(sync_config.idempotency_window_seconds)));
 
         let service = Self {
-            ccip_service,
-            babylon_service,
-            icp_service,
+            bitcoin_anchor,
+            messaging,
+            verification,
             coordinator,
             supported_chains: RwLock::new(HashMap::new()),
+            sync_config,
+            breakers: RwLock::new(HashMap::new()),
+            transfers,
+            compensations: RwLock::new(HashMap::new()),
+            superseded_anchors: RwLock::new(HashMap::new()),
+            nav_versions: RwLock::new(HashMap::new()),
         };
 
-        // Initialize supported chains
-        service.initialize_supported_chains().await?;
+        for chain in chains {
+            service.register_chain(chain).await?;
+        }
 
-        info!("✅ Cross-Chain Service initialized with all protocols");
         Ok(service)
     }
 
     /// Synchronize vault state across all supported chains
+    ///
+    /// The Bitcoin anchor, CCIP fan-out and ICP verification run concurrently,
+    /// bounded by `max_concurrent_legs`, so total latency tracks the slowest leg.
     pub async fn sync_vault_state_comprehensive(
         &self,
         vault_id: String,
@@ -159,6 +250,13 @@ impl CrossChainService {
     ) -> Result<CrossChainSyncResult> {
         info!("🔄 Starting comprehensive vault synchronization for {}", vault_id);
 
+        let nav_version = {
+            let mut versions = self.nav_versions.write().await;
+            let version = versions.entry(vault_id.clone()).or_insert(0);
+            *version += 1;
+            *version
+        };
+
         let mut sync_result = CrossChainSyncResult {
             vault_id: vault_id.clone(),
             successful_chains: Vec::new(),
@@ -166,73 +264,148 @@ impl CrossChainService {
             bitcoin_anchor: None,
             icp_verification: None,
             total_time_ms: 0,
+            breaker_states: HashMap::new(),
+            compensation_plan: None,
+            superseded_anchors: Vec::new(),
+        };
+
+        let start_time = tokio::time::Instant::now();
+
+        let chains = self.supported_chains.read().await;
+        let mut chain_ids: Vec<u64> = chains.keys().cloned().collect();
+        drop(chains);
+
+        // Short-circuit legs and chains whose breakers are open
+        let mut blocked_chains = Vec::new();
+        for chain_id in std::mem::take(&mut chain_ids) {
+            if self.leg_allowed(&chain_label(chain_id)).await {
+                chain_ids.push(chain_id);
+            } else {
+                blocked_chains.push(chain_id);
+            }
+        }
+        let anchor_allowed = self.leg_allowed(BITCOIN_LEG).await;
+        let icp_allowed = self.leg_allowed(ICP_LEG).await;
+
+        let vault_sync = if chain_ids.is_empty() {
+            None
+        } else {
+            Some(self.build_vault_sync(&vault_id, &nav_data, &chain_ids).await)
         };
+        let mut failed_chain_ids = blocked_chains.clone();
 
-        let start_time = std::time::Instant::now();
+        let permits = Semaphore::new(self.sync_config.max_concurrent_legs.max(1));
+        let config = &self.sync_config;
 
-        // 1. Anchor to Bitcoin via Babylon
-        match self.babylon_service.anchor_vault_nav_to_bitcoin(
-            vault_id.clone(),
-            nav_data.computation_hash.clone(),
-            nav_data.epoch,
-        ).await {
-            Ok(anchor) => {
+        // 1. Anchor to Bitcoin via Babylon, 2. sync via CCIP, 3. verify via ICP Chain Fusion
+        let (anchor_result, ccip_result, icp_result) = tokio::join!(
+            async {
+                if !anchor_allowed {
+                    return None;
+                }
+                Some(run_leg(&permits, config, || self.bitcoin_anchor.anchor_nav(vault_id.clone(), &nav_data)).await)
+            },
+            async {
+                let vault_sync = vault_sync?;
+                Some(run_leg(&permits, config, || self.messaging.sync_vault(vault_sync.clone())).await)
+            },
+            async {
+                if !icp_allowed {
+                    return None;
+                }
+                Some(run_leg(&permits, config, || self.verification.verify_state(vault_id.clone(), &nav_data)).await)
+            },
+        );
+
+        match anchor_result {
+            Some(Ok(anchor)) => {
                 sync_result.bitcoin_anchor = Some(anchor);
+                self.record_leg_outcome(BITCOIN_LEG, true).await;
                 info!("✅ Bitcoin anchor successful");
             },
-            Err(e) => {
+            Some(Err(e)) => {
                 error!("❌ Bitcoin anchor failed: {}", e);
-                sync_result.failed_chains.push("Bitcoin".to_string());
+                self.record_leg_outcome(BITCOIN_LEG, false).await;
+                sync_result.failed_chains.push(BITCOIN_LEG.to_string());
+            },
+            None => {
+                warn!("⛔ Bitcoin anchor skipped: circuit breaker open");
+                sync_result.failed_chains.push(BITCOIN_LEG.to_string());
             }
         }
 
-        // 2. Sync via CCIP to supported chains
-        let chains = self.supported_chains.read().await;
-        let chain_ids: Vec<u64> = chains.keys().cloned().collect();
-        drop(chains);
-
-        if !chain_ids.is_empty() {
-            let vault_sync = CrossChainVaultSync {
-                vault_id: vault_id.clone(),
-                source_chain: 1, // Ethereum as source
-                destination_chains: chain_ids.clone(),
-                nav_data: nav_data.clone(),
-                proof_hash: nav_data.computation_hash.clone(),
-                timestamp: chrono::Utc::now().timestamp(),
-            };
-
-            match self.ccip_service.sync_vault_cross_chain(vault_sync).await {
-                Ok(message_ids) => {
-                    for (i, chain_id) in chain_ids.iter().enumerate() {
-                        sync_result.successful_chains.push(format!("Chain-{}", chain_id));
-                        info!("✅ CCIP sync successful for chain {} with message {}",
-                              chain_id, message_ids.get(i).unwrap_or(&"unknown".to_string()));
-                    }
-                },
-                Err(e) => {
-                    error!("❌ CCIP sync failed: {}", e);
-                    for chain_id in &chain_ids {
-                        sync_result.failed_chains.push(format!("Chain-{}", chain_id));
-                    }
+        match ccip_result {
+            Some(Ok(message_ids)) => {
+                for (i, chain_id) in chain_ids.iter().enumerate() {
+                    self.record_chain_outcome(*chain_id, true).await;
+                    sync_result.successful_chains.push(chain_label(*chain_id));
+                    info!("✅ CCIP sync successful for chain {} with message {}",
+                          chain_id, message_ids.get(i).unwrap_or(&"unknown".to_string()));
                 }
-            }
+            },
+            Some(Err(e)) => {
+                error!("❌ CCIP sync failed: {}", e);
+                for chain_id in &chain_ids {
+                    self.record_chain_outcome(*chain_id, false).await;
+                    sync_result.failed_chains.push(chain_label(*chain_id));
+                }
+                failed_chain_ids.extend(chain_ids.iter().copied());
+            },
+            None => {}
         }
 
-        // 3. Verify via ICP Chain Fusion
-        match self.icp_service.verify_cross_chain_state(
-            vault_id.clone(),
-            nav_data.clone(),
-        ).await {
-            Ok(verification) => {
+        for chain_id in blocked_chains {
+            warn!("⛔ CCIP sync to chain {} skipped: circuit breaker open", chain_id);
+            sync_result.failed_chains.push(chain_label(chain_id));
+        }
+
+        match icp_result {
+            Some(Ok(verification)) => {
                 sync_result.icp_verification = Some(verification);
+                self.record_leg_outcome(ICP_LEG, true).await;
                 info!("✅ ICP verification successful");
             },
-            Err(e) => {
+            Some(Err(e)) => {
                 error!("❌ ICP verification failed: {}", e);
-                sync_result.failed_chains.push("ICP".to_string());
+                self.record_leg_outcome(ICP_LEG, false).await;
+                sync_result.failed_chains.push(ICP_LEG.to_string());
+            },
+            None => {
+                warn!("⛔ ICP verification skipped: circuit breaker open");
+                sync_result.failed_chains.push(ICP_LEG.to_string());
+            }
+        }
+
+        if let Some(anchor) = &sync_result.bitcoin_anchor {
+            if failed_chain_ids.is_empty() {
+                // This anchor replaces any the vault's earlier partial syncs left superseded
+                sync_result.superseded_anchors = self.superseded_anchors.write().await
+                    .remove(&vault_id)
+                    .unwrap_or_default();
+            } else {
+                let mut succeeded_legs = vec![BITCOIN_LEG.to_string()];
+                succeeded_legs.extend(sync_result.successful_chains.iter().cloned());
+                if sync_result.icp_verification.is_some() {
+                    succeeded_legs.push(ICP_LEG.to_string());
+                }
+                failed_chain_ids.sort_unstable();
+                let plan = CompensationPlan::for_partial_sync(
+                    &vault_id,
+                    &nav_data,
+                    nav_version,
+                    &anchor.bitcoin_tx_hash,
+                    succeeded_legs,
+                    sync_result.failed_chains.clone(),
+                    failed_chain_ids,
+                );
+                warn!("🩹 Partial sync for {}: Bitcoin anchored but {} chains missed it, compensation plan {}",
+                      vault_id, plan.failed_legs.len(), plan.plan_id);
+                sync_result.compensation_plan = Some(plan);
             }
         }
 
+        sync_result.breaker_states = self.breaker_states().await;
+
         sync_result.total_time_ms = start_time.elapsed().as_millis() as u64;
 
         info!("🏁 Cross-chain sync completed in {}ms - Success: {}, Failed: {}",
@@ -243,41 +416,414 @@ impl CrossChainService {
         Ok(sync_result)
     }
 
-    async fn initialize_supported_chains(&self) -> Result<()> {
-        let mut chains = self.supported_chains.write().await;
+    /// Send a CCIP token transfer at most once per caller and idempotency key
+    ///
+    /// A replayed key returns the original response; a replay that arrives while the
+    /// first request is still in flight waits for it instead of sending again. The send
+    /// runs in its own task, so a caller that goes away mid-send cannot leave the key
+    /// without a recorded outcome.
+    pub async fn initiate_transfer(&self, caller: &str, request: CrossChainTransferRequest) -> Result<CrossChainTransferResponse> {
+        let reservation = match self.transfers.reserve(caller, &request).await? {
+            TransferReservation::Replay(response) => {
+                info!("🔁 Replayed transfer {} for idempotency key {}", response.message_id, request.idempotency_key);
+                return Ok(response);
+            },
+            TransferReservation::Pending(outcome) => {
+                info!("⏳ Transfer for idempotency key {} in flight, awaiting its result", request.idempotency_key);
+                return TransferIdempotency::await_outcome(outcome).await;
+            },
+            TransferReservation::New(reservation) => reservation,
+        };
 
-        // Ethereum
-        chains.insert(1, ChainInfo {
-            chain_id: 1,
-            name: "Ethereum".to_string(),
-            rpc_url: "https://eth-mainnet.g.alchemy.com/v2/key".to_string(),
-            vault_address: "0x742d35Cc6634C0532925a3b8D4C9db96C4b4Db44".to_string(),
-            status: ChainStatus::Active,
-            last_sync: 0,
+        let outcome = reservation.subscribe();
+        let messaging = self.messaging.clone();
+        let transfers = self.transfers.clone();
+        let caller = caller.to_string();
+        tokio::spawn(async move {
+            let result = messaging.send_token_transfer(&request).await
+                .map(|message_id| CrossChainTransferResponse {
+                    idempotency_key: request.idempotency_key.clone(),
+                    message_id,
+                    source_chain: request.source_chain,
+                    destination_chain: request.destination_chain,
+                    amount: request.amount,
+                    initiated_at: chrono::Utc::now().timestamp(),
+                });
+            match &result {
+                Ok(response) => info!("✅ Transfer {} initiated for idempotency key {}", response.message_id, request.idempotency_key),
+                Err(e) => error!("❌ Transfer for idempotency key {} failed: {}", request.idempotency_key, e),
+            }
+            transfers.complete(&caller, &request, reservation, &result).await;
         });
 
-        // Avalanche
-        chains.insert(43114, ChainInfo {
-            chain_id: 43114,
-            name: "Avalanche".to_string(),
-            rpc_url: "https://api.avax.network/ext/bc/C/rpc".to_string(),
-            vault_address: "0x742d35Cc6634C0532925a3b8D4C9db96C4b4Db44".to_string(),
-            status: ChainStatus::Active,
-            last_sync: 0,
-        });
+        TransferIdempotency::await_outcome(outcome).await
+    }
 
-        // Solana (represented as chain for CCIP)
-        chains.insert(999999, ChainInfo {
-            chain_id: 999999,
-            name: "Solana".to_string(),
-            rpc_url: "https://api.mainnet-beta.solana.com".to_string(),
-            vault_address: "RTFVau1tAdvancedSPLTokenVau1tProgram11111111".to_string(),
-            status: ChainStatus::Active,
-            last_sync: 0,
-        });
+    /// Resolve a transfer whose send failed ambiguously; see `TransferIdempotency::reconcile`
+    pub async fn reconcile_transfer(
+        &self,
+        caller: &str,
+        idempotency_key: &str,
+        message_id: Option<String>,
+    ) -> Result<Option<CrossChainTransferResponse>> {
+        self.transfers.reconcile(caller, idempotency_key, message_id).await
+    }
+
+    /// Reconcile a partial sync described by `plan`
+    ///
+    /// Resends the anchored NAV to the chains that missed it; if that fails too, the
+    /// Bitcoin anchor is marked superseded so the next full sync replaces it. Running a
+    /// completed plan again returns the recorded outcome without repeating any action, and
+    /// a call made while the plan is being applied fails instead of applying it twice. A
+    /// plan whose vault has synced again since completes as obsolete without any action.
+    pub async fn compensate(&self, plan: &CompensationPlan) -> Result<CompensationOutcome> {
+        let mut outcome = CompensationOutcome {
+            plan_id: plan.plan_id.clone(),
+            resent_chains: Vec::new(),
+            message_ids: Vec::new(),
+            superseded_anchor: None,
+            obsolete: false,
+            completed_at: 0,
+        };
+
+        {
+            let mut compensations = self.compensations.write().await;
+            match compensations.get(&plan.plan_id) {
+                Some(CompensationState::Completed(outcome)) => {
+                    info!("🔁 Compensation plan {} already applied", plan.plan_id);
+                    return Ok(outcome.clone());
+                },
+                Some(CompensationState::InProgress) => {
+                    return Err(anyhow::anyhow!("Compensation plan {} is already being applied", plan.plan_id));
+                },
+                None => {}
+            }
+            if !self.nav_version_current(plan).await {
+                return Ok(self.complete_obsolete(&mut compensations, outcome));
+            }
+            compensations.insert(plan.plan_id.clone(), CompensationState::InProgress);
+        }
+
+        for action in &plan.actions {
+            match action {
+                CompensationAction::ResendNavSync { chain_ids } => {
+                    if chain_ids.is_empty() {
+                        continue;
+                    }
+                    let vault_sync = self.build_vault_sync(&plan.vault_id, &plan.nav_data, chain_ids).await;
+                    // A sync that started while this one was being built carries a newer NAV
+                    if !self.nav_version_current(plan).await {
+                        let mut compensations = self.compensations.write().await;
+                        return Ok(self.complete_obsolete(&mut compensations, outcome));
+                    }
+                    match self.messaging.sync_vault(vault_sync).await {
+                        Ok(message_ids) => {
+                            for &chain_id in chain_ids {
+                                self.record_chain_outcome(chain_id, true).await;
+                            }
+                            outcome.resent_chains = chain_ids.clone();
+                            outcome.message_ids = message_ids;
+                            info!("✅ Correcting NAV sync for {} delivered to {} chains", plan.vault_id, chain_ids.len());
+                            break;
+                        },
+                        Err(e) => {
+                            for &chain_id in chain_ids {
+                                self.record_chain_outcome(chain_id, false).await;
+                            }
+                            warn!("❌ Correcting NAV sync for {} failed: {}", plan.vault_id, e);
+                        }
+                    }
+                },
+                CompensationAction::SupersedeBitcoinAnchor { bitcoin_tx_hash } => {
+                    let mut superseded = self.superseded_anchors.write().await;
+                    let anchors = superseded.entry(plan.vault_id.clone()).or_default();
+                    if !anchors.contains(bitcoin_tx_hash) {
+                        anchors.push(bitcoin_tx_hash.clone());
+                    }
+                    outcome.superseded_anchor = Some(bitcoin_tx_hash.clone());
+                    warn!("🪦 Bitcoin anchor {} for {} superseded until the next full sync", bitcoin_tx_hash, plan.vault_id);
+                },
+            }
+        }
+
+        outcome.completed_at = chrono::Utc::now().timestamp();
+        self.compensations.write().await
+            .insert(plan.plan_id.clone(), CompensationState::Completed(outcome.clone()));
+        Ok(outcome)
+    }
+
+    /// Whether no sync of the plan's vault has started since the plan was created
+    async fn nav_version_current(&self, plan: &CompensationPlan) -> bool {
+        self.nav_versions.read().await.get(&plan.vault_id).copied().unwrap_or(0) == plan.nav_version
+    }
+
+    fn complete_obsolete(
+        &self,
+        compensations: &mut HashMap<String, CompensationState>,
+        mut outcome: CompensationOutcome,
+    ) -> CompensationOutcome {
+        warn!("⏭️ Compensation plan {} is obsolete: its vault has synced a newer NAV", outcome.plan_id);
+        outcome.obsolete = true;
+        outcome.completed_at = chrono::Utc::now().timestamp();
+        compensations.insert(outcome.plan_id.clone(), CompensationState::Completed(outcome.clone()));
+        outcome
+    }
+
+    /// Bitcoin anchors of a vault superseded by compensation and not yet replaced
+    pub async fn superseded_anchors(&self, vault_id: &str) -> Vec<String> {
+        self.superseded_anchors.read().await.get(vault_id).cloned().unwrap_or_default()
+    }
+
+    async fn build_vault_sync(&self, vault_id: &str, nav_data: &NavData, chain_ids: &[u64]) -> CrossChainVaultSync {
+        let mut destination_nonces = HashMap::new();
+        for &chain_id in chain_ids {
+            destination_nonces.insert(chain_id, self.coordinator.next_sync_nonce(vault_id, chain_id).await);
+        }
+
+        CrossChainVaultSync {
+            vault_id: vault_id.to_string(),
+            source_chain: 1, // Ethereum as source
+            destination_chains: chain_ids.to_vec(),
+            nav_data: nav_data.clone(),
+            proof_hash: nav_data.computation_hash.clone(),
+            timestamp: chrono::Utc::now().timestamp(),
+            destination_nonces,
+        }
+    }
+
+    /// Current circuit breaker state for every leg and chain that has been attempted
+    pub async fn breaker_states(&self) -> HashMap<String, BreakerState> {
+        let cooldown = Duration::from_secs(self.sync_config.breaker_cooldown_seconds);
+        let breakers = self.breakers.read().await;
+        breakers.iter().map(|(label, breaker)| (label.clone(), breaker.state(cooldown))).collect()
+    }
+
+    /// Compare the latest NAV root on every registered chain against the consensus root
+    ///
+    /// The consensus is the root reported by the most chains; chains reporting a different
+    /// root diverge, and chains that can't be read are unreachable. Per-chain staleness
+    /// comes from `last_sync`, so the report can be recorded against the drift ledger.
+    pub async fn reconcile_vault(&self, vault_id: &str) -> Result<ReconciliationReport> {
+        info!("🧮 Reconciling vault {} across chains", vault_id);
+
+        let chains: Vec<ChainInfo> = {
+            let chains = self.supported_chains.read().await;
+            let mut chains: Vec<ChainInfo> = chains.values().cloned().collect();
+            chains.sort_by_key(|chain| chain.chain_id);
+            chains
+        };
+
+        let now = chrono::Utc::now().timestamp();
+        let mut chain_reports = Vec::with_capacity(chains.len());
+        for chain in &chains {
+            let nav_root = match self.messaging.latest_nav_root(vault_id, chain.chain_id).await {
+                Ok(root) => root,
+                Err(e) => {
+                    warn!("⚠️ Failed to read NAV root for vault {} on chain {}: {}", vault_id, chain.chain_id, e);
+                    None
+                }
+            };
+            chain_reports.push(ChainReconciliation {
+                chain_id: chain.chain_id,
+                nav_root,
+                last_sync: chain.last_sync,
+                staleness_seconds: now - chain.last_sync,
+                agrees_with_consensus: false,
+            });
+        }
+
+        let consensus_root = consensus_root(chain_reports.iter().filter_map(|report| report.nav_root.as_deref()));
+
+        let mut diverging_chains = Vec::new();
+        let mut unreachable_chains = Vec::new();
+        for report in &mut chain_reports {
+            match &report.nav_root {
+                Some(root) if Some(root) == consensus_root.as_ref() => report.agrees_with_consensus = true,
+                Some(_) => diverging_chains.push(report.chain_id),
+                None => unreachable_chains.push(report.chain_id),
+            }
+        }
+
+        if !diverging_chains.is_empty() {
+            warn!("⚠️ Vault {} diverges from consensus on chains {:?}", vault_id, diverging_chains);
+        }
+
+        Ok(ReconciliationReport {
+            vault_id: vault_id.to_string(),
+            in_sync: diverging_chains.is_empty() && unreachable_chains.is_empty(),
+            consensus_root,
+            chains: chain_reports,
+            diverging_chains,
+            unreachable_chains,
+            generated_at: now,
+        })
+    }
+
+    /// Registry entry for a chain
+    pub async fn chain_info(&self, chain_id: u64) -> Option<ChainInfo> {
+        self.supported_chains.read().await.get(&chain_id).cloned()
+    }
+
+    async fn leg_allowed(&self, label: &str) -> bool {
+        let cooldown = Duration::from_secs(self.sync_config.breaker_cooldown_seconds);
+        let breakers = self.breakers.read().await;
+        breakers.get(label).map(|breaker| breaker.allows_attempt(cooldown)).unwrap_or(true)
+    }
+
+    /// Update a leg's breaker, returning its consecutive failure count
+    async fn record_leg_outcome(&self, label: &str, success: bool) -> u32 {
+        let mut breakers = self.breakers.write().await;
+        let breaker = breakers.entry(label.to_string()).or_default();
+        if success {
+            breaker.record_success();
+        } else {
+            breaker.record_failure(self.sync_config.breaker_failure_threshold);
+        }
+        breaker.consecutive_failures()
+    }
+
+    /// Update a chain's breaker and reflect it in the chain's registry status
+    async fn record_chain_outcome(&self, chain_id: u64, success: bool) {
+        let failures = self.record_leg_outcome(&chain_label(chain_id), success).await;
+
+        let mut chains = self.supported_chains.write().await;
+        if let Some(chain) = chains.get_mut(&chain_id) {
+            chain.status = if failures == 0 {
+                ChainStatus::Active
+            } else if failures >= self.sync_config.breaker_failure_threshold {
+                ChainStatus::Offline
+            } else {
+                ChainStatus::Degraded
+            };
+            if success {
+                chain.last_sync = chrono::Utc::now().timestamp();
+            }
+        }
+    }
+
+    /// Apply an inbound vault sync on `chain_id`, rejecting replayed or out-of-order nonces
+    pub async fn process_vault_sync(&self, vault_sync: &CrossChainVaultSync, chain_id: u64) -> Result<()> {
+        let nonce = vault_sync.destination_nonces.get(&chain_id).copied().ok_or_else(|| {
+            anyhow::anyhow!("Vault sync for {} carries no nonce for chain {}", vault_sync.vault_id, chain_id)
+        })?;
 
+        self.coordinator.accept_sync_nonce(&vault_sync.vault_id, chain_id, nonce).await?;
+
+        let mut chains = self.supported_chains.write().await;
+        if let Some(chain) = chains.get_mut(&chain_id) {
+            chain.last_sync = vault_sync.timestamp;
+        }
+
+        info!("✅ Applied vault sync for {} on chain {} (nonce {})", vault_sync.vault_id, chain_id, nonce);
         Ok(())
     }
+
+    /// Last sync nonce processed for a vault on a chain
+    pub async fn last_processed_nonce(&self, vault_id: &str, chain_id: u64) -> Option<u64> {
+        self.coordinator.last_processed_nonce(vault_id, chain_id).await
+    }
+
+    /// Add a chain to the sync registry
+    pub async fn register_chain(&self, chain: ChainInfo) -> Result<()> {
+        validate_vault_address(&chain.chain_type, &chain.vault_address).map_err(|e| {
+            anyhow::anyhow!("Invalid vault address for chain {} ({}): {}", chain.chain_id, chain.name, e)
+        })?;
+
+        let mut chains = self.supported_chains.write().await;
+        if chains.contains_key(&chain.chain_id) {
+            return Err(anyhow::anyhow!("Chain {} is already registered", chain.chain_id));
+        }
+
+        info!("🔗 Registered chain {} ({})", chain.chain_id, chain.name);
+        chains.insert(chain.chain_id, chain);
+        Ok(())
+    }
+
+    /// Remove a chain from the sync registry, dropping its breaker state
+    pub async fn deregister_chain(&self, chain_id: u64) -> Result<ChainInfo> {
+        let chain = self.supported_chains.write().await.remove(&chain_id)
+            .ok_or_else(|| anyhow::anyhow!("Chain {} is not registered", chain_id))?;
+        self.breakers.write().await.remove(&chain_label(chain_id));
+
+        info!("🔌 Deregistered chain {} ({})", chain_id, chain.name);
+        Ok(chain)
+    }
+}
+
+/// Check a vault address is well-formed for its chain type
+fn validate_vault_address(chain_type: &ChainType, address: &str) -> Result<()> {
+    match chain_type {
+        ChainType::Evm => {
+            let hex_part = address.strip_prefix("0x")
+                .ok_or_else(|| anyhow::anyhow!("EVM address must start with 0x"))?;
+            let bytes = hex::decode(hex_part)?;
+            if bytes.len() != 20 {
+                return Err(anyhow::anyhow!("EVM address must be 20 bytes, got {}", bytes.len()));
+            }
+        },
+        ChainType::Solana => {
+            const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+            if !(32..=44).contains(&address.len()) {
+                return Err(anyhow::anyhow!("Solana address must be 32-44 base58 characters"));
+            }
+            if let Some(c) = address.chars().find(|c| !BASE58_ALPHABET.contains(*c)) {
+                return Err(anyhow::anyhow!("Invalid base58 character '{}' in Solana address", c));
+            }
+        },
+    }
+    Ok(())
+}
+
+/// Most commonly reported root, ties broken by the lexicographically smallest root
+fn consensus_root<'a>(roots: impl Iterator<Item = &'a str>) -> Option<String> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for root in roots {
+        *counts.entry(root).or_insert(0) += 1;
+    }
+    counts
+        .into_iter()
+        .max_by(|(a_root, a_count), (b_root, b_count)| a_count.cmp(b_count).then(b_root.cmp(a_root)))
+        .map(|(root, _)| root.to_string())
+}
+
+const BITCOIN_LEG: &str = "Bitcoin";
+const ICP_LEG: &str = "ICP";
+
+fn chain_label(chain_id: u64) -> String {
+    format!("Chain-{}", chain_id)
+}
+
+/// Run one sync leg under the shared concurrency cap, retrying with exponential backoff
+///
+/// Each attempt gets its own timeout, and the permit is released while backing off.
+async fn run_leg<T, F, Fut>(permits: &Semaphore, config: &CrossChainSyncConfig, leg: F) -> Result<T>
+where
+    F: Fn() -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    let leg_timeout = Duration::from_secs(config.verification_timeout_seconds);
+    let mut attempt = 0;
+
+    loop {
+        let outcome = {
+            let _permit = permits.acquire().await?;
+            tokio::time::timeout(leg_timeout, leg())
+                .await
+                .map_err(|_| anyhow::anyhow!("Sync leg timed out after {:?}", leg_timeout))
+                .and_then(|result| result)
+        };
+
+        match outcome {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < config.max_leg_retries => {
+                let backoff = Duration::from_millis(config.retry_base_delay_ms.saturating_mul(1 << attempt.min(16)));
+                warn!("⏳ Sync leg attempt {} failed ({}), retrying in {:?}", attempt + 1, e, backoff);
+                tokio::time::sleep(backoff).await;
+                attempt += 1;
+            },
+            Err(e) => return Err(e),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -288,6 +834,32 @@ pub struct CrossChainSyncResult {
     pub bitcoin_anchor: Option<BitcoinAnchor>,
     pub icp_verification: Option<ICPVerification>,
     pub total_time_ms: u64,
+    pub breaker_states: HashMap<String, BreakerState>,
+    /// Set when the Bitcoin anchor succeeded but some destination chains did not receive the NAV
+    pub compensation_plan: Option<CompensationPlan>,
+    /// Earlier anchors this sync replaced
+    pub superseded_anchors: Vec<String>,
+}
+
+/// Cross-chain agreement on a vault's latest NAV root
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationReport {
+    pub vault_id: String,
+    pub consensus_root: Option<String>,
+    pub in_sync: bool,
+    pub chains: Vec<ChainReconciliation>,
+    pub diverging_chains: Vec<u64>,
+    pub unreachable_chains: Vec<u64>,
+    pub generated_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainReconciliation {
+    pub chain_id: u64,
+    pub nav_root: Option<String>,
+    pub last_sync: i64,
+    pub staleness_seconds: i64,
+    pub agrees_with_consensus: bool,
 }
 
 // Configuration structures
@@ -311,3 +883,634 @@ pub struct ICPConfig {
     pub canister_id: String,
     pub identity_path: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    struct MockAnchor {
+        delay: Duration,
+        failures_remaining: AtomicU32,
+        calls: AtomicU32,
+    }
+
+    impl MockAnchor {
+        fn new(delay_ms: u64, failures: u32) -> Self {
+            Self {
+                delay: Duration::from_millis(delay_ms),
+                failures_remaining: AtomicU32::new(failures),
+                calls: AtomicU32::new(0),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl BitcoinAnchorLeg for MockAnchor {
+        async fn anchor_nav(&self, vault_id: String, nav_data: &NavData) -> Result<BitcoinAnchor> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            if self.failures_remaining.load(Ordering::SeqCst) > 0 {
+                self.failures_remaining.fetch_sub(1, Ordering::SeqCst);
+                return Err(anyhow::anyhow!("Simulated Babylon RPC failure"));
+            }
+            Ok(BitcoinAnchor {
+                vault_id,
+                nav_hash: nav_data.computation_hash.clone(),
+                epoch: nav_data.epoch,
+                bitcoin_block_height: 850_000,
+                bitcoin_tx_hash: "mock_tx".to_string(),
+                op_return_data: b"RTF".to_vec(),
+                finality_signature: "mock_sig".to_string(),
+                timestamp: chrono::Utc::now().timestamp(),
+                inclusion_proof: None,
+            })
+        }
+    }
+
+    struct MockMessaging {
+        delay: Duration,
+        fail: bool,
+        roots: HashMap<u64, String>,
+    }
+
+    #[async_trait]
+    impl MessagingLeg for MockMessaging {
+        async fn sync_vault(&self, vault_sync: CrossChainVaultSync) -> Result<Vec<String>> {
+            tokio::time::sleep(self.delay).await;
+            if self.fail {
+                return Err(anyhow::anyhow!("Simulated CCIP router failure"));
+            }
+            Ok(vault_sync.destination_chains.iter().map(|chain| format!("msg_{}", chain)).collect())
+        }
+
+        async fn latest_nav_root(&self, _vault_id: &str, chain_id: u64) -> Result<Option<String>> {
+            Ok(self.roots.get(&chain_id).cloned())
+        }
+
+        async fn send_token_transfer(&self, transfer: &CrossChainTransferRequest) -> Result<String> {
+            Ok(format!("transfer_{}", transfer.idempotency_key))
+        }
+    }
+
+    /// How a `CountingTransfers` send fails
+    #[derive(Clone, Copy)]
+    enum TransferFailure {
+        /// Rejected before anything reached the router
+        NotSent,
+        /// Router call errored with the transfer possibly submitted
+        Ambiguous,
+    }
+
+    /// Messaging leg that counts token transfers actually sent
+    struct CountingTransfers {
+        delay: Duration,
+        failure: Option<TransferFailure>,
+        sent: AtomicU32,
+    }
+
+    impl CountingTransfers {
+        fn new(delay: Duration, failure: Option<TransferFailure>) -> Arc<Self> {
+            Arc::new(Self { delay, failure, sent: AtomicU32::new(0) })
+        }
+    }
+
+    #[async_trait]
+    impl MessagingLeg for CountingTransfers {
+        async fn sync_vault(&self, vault_sync: CrossChainVaultSync) -> Result<Vec<String>> {
+            Ok(vault_sync.destination_chains.iter().map(|chain| format!("msg_{}", chain)).collect())
+        }
+
+        async fn latest_nav_root(&self, _vault_id: &str, _chain_id: u64) -> Result<Option<String>> {
+            Ok(None)
+        }
+
+        async fn send_token_transfer(&self, _transfer: &CrossChainTransferRequest) -> Result<String> {
+            tokio::time::sleep(self.delay).await;
+            match self.failure {
+                Some(TransferFailure::NotSent) => {
+                    return Err(TransferNotSent { reason: "Simulated insufficient allowance".to_string() }.into());
+                }
+                Some(TransferFailure::Ambiguous) => {
+                    return Err(anyhow::anyhow!("Simulated CCIP router timeout"));
+                }
+                None => {}
+            }
+            let sent = self.sent.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(format!("ccip_msg_{}", sent))
+        }
+    }
+
+    struct MockVerification {
+        delay: Duration,
+    }
+
+    #[async_trait]
+    impl VerificationLeg for MockVerification {
+        async fn verify_state(&self, vault_id: String, _nav_data: &NavData) -> Result<ICPVerification> {
+            tokio::time::sleep(self.delay).await;
+            Ok(ICPVerification {
+                vault_id,
+                verification_result: true,
+                cross_chain_consistency: true,
+                bitcoin_state_verified: true,
+                ethereum_state_verified: true,
+                threshold_signature: "mock_threshold_sig".to_string(),
+                verification_timestamp: chrono::Utc::now().timestamp(),
+                canister_response: CanisterResponse {
+                    status: "success".to_string(),
+                    cycles_used: 0,
+                    response_time_ms: 0,
+                    https_outcalls_made: 0,
+                    verification_proofs: Vec::new(),
+                },
+            })
+        }
+    }
+
+    fn test_nav_data() -> NavData {
+        NavData {
+            nav_per_share: 1_000_000,
+            total_assets: 100_000_000,
+            total_liabilities: 0,
+            epoch: 42,
+            computation_hash: "nav_hash_42".to_string(),
+        }
+    }
+
+    fn test_chain(chain_id: u64, name: &str, chain_type: ChainType, vault_address: &str) -> ChainInfo {
+        ChainInfo {
+            chain_id,
+            name: name.to_string(),
+            chain_type,
+            rpc_url: format!("https://rpc.{}.test", name.to_lowercase()),
+            vault_address: vault_address.to_string(),
+            status: ChainStatus::Active,
+            last_sync: 0,
+        }
+    }
+
+    fn test_chains() -> Vec<ChainInfo> {
+        vec![
+            test_chain(1, "Ethereum", ChainType::Evm, "0x742d35Cc6634C0532925a3b8D4C9db96C4b4Db44"),
+            test_chain(43114, "Avalanche", ChainType::Evm, "0x742d35Cc6634C0532925a3b8D4C9db96C4b4Db44"),
+            test_chain(999999, "Solana", ChainType::Solana, "RTFVau1tAdvancedSPLTokenVau1tProgram11111111"),
+        ]
+    }
+
+    async fn mock_service(anchor_delay_ms: u64, sync_config: CrossChainSyncConfig) -> CrossChainService {
+        CrossChainService::with_sync_legs(
+            Arc::new(MockAnchor::new(anchor_delay_ms, 0)),
+            Arc::new(MockMessaging { delay: Duration::from_millis(200), fail: false, roots: HashMap::new() }),
+            Arc::new(MockVerification { delay: Duration::from_millis(200) }),
+            test_chains(),
+            sync_config,
+        ).await.unwrap()
+    }
+
+    fn fast_retry_config(max_leg_retries: u32, breaker_failure_threshold: u32) -> CrossChainSyncConfig {
+        CrossChainSyncConfig {
+            max_leg_retries,
+            retry_base_delay_ms: 10,
+            breaker_failure_threshold,
+            ..CrossChainSyncConfig::default()
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_slow_bitcoin_leg_does_not_block_other_legs() {
+        let service = mock_service(400, CrossChainSyncConfig::default()).await;
+
+        let result = service.sync_vault_state_comprehensive("vault_1".to_string(), test_nav_data()).await.unwrap();
+
+        assert!(result.bitcoin_anchor.is_some());
+        assert!(result.icp_verification.is_some());
+        assert_eq!(result.successful_chains.len(), 3);
+        assert!(result.failed_chains.is_empty());
+        // Sequential execution would take 800ms
+        assert_eq!(result.total_time_ms, 400);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_timed_out_leg_does_not_cancel_others() {
+        let sync_config = CrossChainSyncConfig {
+            max_concurrent_legs: 3,
+            verification_timeout_seconds: 1,
+            max_leg_retries: 0,
+            ..CrossChainSyncConfig::default()
+        };
+        let service = mock_service(5_000, sync_config).await;
+
+        let result = service.sync_vault_state_comprehensive("vault_1".to_string(), test_nav_data()).await.unwrap();
+
+        assert!(result.bitcoin_anchor.is_none());
+        assert_eq!(result.failed_chains, vec!["Bitcoin".to_string()]);
+        assert!(result.icp_verification.is_some());
+        assert_eq!(result.successful_chains.len(), 3);
+        assert_eq!(result.total_time_ms, 1_000);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_concurrency_cap_serializes_legs() {
+        let sync_config = CrossChainSyncConfig {
+            max_concurrent_legs: 1,
+            ..CrossChainSyncConfig::default()
+        };
+        let service = mock_service(200, sync_config).await;
+
+        let result = service.sync_vault_state_comprehensive("vault_1".to_string(), test_nav_data()).await.unwrap();
+
+        assert!(result.failed_chains.is_empty());
+        assert_eq!(result.total_time_ms, 600);
+    }
+
+    #[tokio::test]
+    async fn test_transient_failures_retried_until_success() {
+        let anchor = Arc::new(MockAnchor::new(0, 2));
+        let service = CrossChainService::with_sync_legs(
+            anchor.clone(),
+            Arc::new(MockMessaging { delay: Duration::ZERO, fail: false, roots: HashMap::new() }),
+            Arc::new(MockVerification { delay: Duration::ZERO }),
+            test_chains(),
+            fast_retry_config(3, 5),
+        ).await.unwrap();
+
+        let result = service.sync_vault_state_comprehensive("vault_1".to_string(), test_nav_data()).await.unwrap();
+
+        assert_eq!(anchor.calls.load(Ordering::SeqCst), 3);
+        assert!(result.bitcoin_anchor.is_some());
+        assert!(result.failed_chains.is_empty());
+        assert_eq!(result.breaker_states.get("Bitcoin"), Some(&BreakerState::Closed));
+    }
+
+    #[tokio::test]
+    async fn test_breaker_trips_after_threshold_and_short_circuits() {
+        let anchor = Arc::new(MockAnchor::new(0, u32::MAX));
+        let service = CrossChainService::with_sync_legs(
+            anchor.clone(),
+            Arc::new(MockMessaging { delay: Duration::ZERO, fail: true, roots: HashMap::new() }),
+            Arc::new(MockVerification { delay: Duration::ZERO }),
+            test_chains(),
+            fast_retry_config(0, 2),
+        ).await.unwrap();
+
+        let first = service.sync_vault_state_comprehensive("vault_1".to_string(), test_nav_data()).await.unwrap();
+        assert_eq!(first.breaker_states.get("Bitcoin"), Some(&BreakerState::Closed));
+        assert!(matches!(service.chain_info(1).await.unwrap().status, ChainStatus::Degraded));
+
+        let second = service.sync_vault_state_comprehensive("vault_1".to_string(), test_nav_data()).await.unwrap();
+        assert!(matches!(second.breaker_states.get("Bitcoin"), Some(BreakerState::Open { .. })));
+        assert!(matches!(second.breaker_states.get("Chain-1"), Some(BreakerState::Open { .. })));
+        assert!(matches!(service.chain_info(1).await.unwrap().status, ChainStatus::Offline));
+
+        // Open breakers skip the legs entirely until the cooldown elapses
+        let third = service.sync_vault_state_comprehensive("vault_1".to_string(), test_nav_data()).await.unwrap();
+        assert_eq!(anchor.calls.load(Ordering::SeqCst), 2);
+        assert!(third.failed_chains.contains(&"Bitcoin".to_string()));
+        assert!(third.failed_chains.contains(&"Chain-1".to_string()));
+        assert!(third.icp_verification.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_chain_registration_rejected() {
+        let mut chains = test_chains();
+        chains.push(test_chain(1, "Ethereum", ChainType::Evm, "0x742d35Cc6634C0532925a3b8D4C9db96C4b4Db44"));
+        let duplicate_config = CrossChainService::with_sync_legs(
+            Arc::new(MockAnchor::new(0, 0)),
+            Arc::new(MockMessaging { delay: Duration::ZERO, fail: false, roots: HashMap::new() }),
+            Arc::new(MockVerification { delay: Duration::ZERO }),
+            chains,
+            CrossChainSyncConfig::default(),
+        ).await;
+        assert!(duplicate_config.is_err());
+
+        let service = mock_service(0, CrossChainSyncConfig::default()).await;
+        let duplicate = test_chain(43114, "Avalanche", ChainType::Evm, "0x742d35Cc6634C0532925a3b8D4C9db96C4b4Db44");
+        assert!(service.register_chain(duplicate).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_vault_addresses_validated_for_chain_type() {
+        let service = mock_service(0, CrossChainSyncConfig::default()).await;
+
+        let solana_address_on_evm = test_chain(10, "Optimism", ChainType::Evm, "RTFVau1tAdvancedSPLTokenVau1tProgram11111111");
+        assert!(service.register_chain(solana_address_on_evm).await.is_err());
+
+        let evm_address_on_solana = test_chain(20, "Eclipse", ChainType::Solana, "0x742d35Cc6634C0532925a3b8D4C9db96C4b4Db44");
+        assert!(service.register_chain(evm_address_on_solana).await.is_err());
+
+        let valid = test_chain(10, "Optimism", ChainType::Evm, "0x742d35Cc6634C0532925a3b8D4C9db96C4b4Db44");
+        assert!(service.register_chain(valid).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_deregistered_chain_removed_from_fan_out() {
+        let service = mock_service(0, CrossChainSyncConfig::default()).await;
+
+        let removed = service.deregister_chain(43114).await.unwrap();
+        assert_eq!(removed.name, "Avalanche");
+        assert!(service.deregister_chain(43114).await.is_err());
+
+        let result = service.sync_vault_state_comprehensive("vault_1".to_string(), test_nav_data()).await.unwrap();
+        assert_eq!(result.successful_chains.len(), 2);
+        assert!(!result.successful_chains.contains(&"Chain-43114".to_string()));
+    }
+
+    async fn service_with_roots(roots: HashMap<u64, String>) -> CrossChainService {
+        CrossChainService::with_sync_legs(
+            Arc::new(MockAnchor::new(0, 0)),
+            Arc::new(MockMessaging { delay: Duration::ZERO, fail: false, roots }),
+            Arc::new(MockVerification { delay: Duration::ZERO }),
+            test_chains(),
+            CrossChainSyncConfig::default(),
+        ).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_flags_diverging_chain() {
+        let roots = HashMap::from([
+            (1, "root_a".to_string()),
+            (43114, "root_b".to_string()),
+            (999999, "root_a".to_string()),
+        ]);
+        let service = service_with_roots(roots).await;
+
+        let report = service.reconcile_vault("vault_1").await.unwrap();
+
+        assert_eq!(report.consensus_root.as_deref(), Some("root_a"));
+        assert_eq!(report.diverging_chains, vec![43114]);
+        assert!(report.unreachable_chains.is_empty());
+        assert!(!report.in_sync);
+        let avalanche = report.chains.iter().find(|chain| chain.chain_id == 43114).unwrap();
+        assert!(!avalanche.agrees_with_consensus);
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_in_sync_reports_staleness() {
+        let roots = HashMap::from([
+            (1, "root_a".to_string()),
+            (43114, "root_a".to_string()),
+            (999999, "root_a".to_string()),
+        ]);
+        let service = service_with_roots(roots).await;
+        service.sync_vault_state_comprehensive("vault_1".to_string(), test_nav_data()).await.unwrap();
+
+        let report = service.reconcile_vault("vault_1").await.unwrap();
+
+        assert!(report.in_sync);
+        assert!(report.chains.iter().all(|chain| chain.agrees_with_consensus));
+        assert!(report.chains.iter().all(|chain| chain.staleness_seconds <= 1));
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_reports_unreachable_chain() {
+        let roots = HashMap::from([(1, "root_a".to_string()), (43114, "root_a".to_string())]);
+        let service = service_with_roots(roots).await;
+
+        let report = service.reconcile_vault("vault_1").await.unwrap();
+
+        assert_eq!(report.unreachable_chains, vec![999999]);
+        assert!(report.diverging_chains.is_empty());
+        assert!(!report.in_sync);
+    }
+
+    async fn transfer_service(messaging: Arc<CountingTransfers>, idempotency_window_seconds: u64) -> CrossChainService {
+        CrossChainService::with_sync_legs(
+            Arc::new(MockAnchor::new(0, 0)),
+            messaging,
+            Arc::new(MockVerification { delay: Duration::ZERO }),
+            test_chains(),
+            CrossChainSyncConfig { idempotency_window_seconds, ..CrossChainSyncConfig::default() },
+        ).await.unwrap()
+    }
+
+    fn transfer_request(idempotency_key: &str, amount: u64) -> CrossChainTransferRequest {
+        CrossChainTransferRequest {
+            idempotency_key: idempotency_key.to_string(),
+            source_chain: 1,
+            destination_chain: 43114,
+            token_address: "0x742d35Cc6634C0532925a3b8D4C9db96C4b4Db44".to_string(),
+            amount,
+            receiver: "0x742d35Cc6634C0532925a3b8D4C9db96C4b4Db44".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replayed_transfer_key_sends_once() {
+        let messaging = CountingTransfers::new(Duration::from_millis(100), None);
+        let service = transfer_service(messaging.clone(), 3_600).await;
+
+        // A retry racing the original request and a retry after it completed
+        let (first, concurrent) = tokio::join!(
+            service.initiate_transfer("client-a", transfer_request("key-1", 1_000)),
+            service.initiate_transfer("client-a", transfer_request("key-1", 1_000)),
+        );
+        let later = service.initiate_transfer("client-a", transfer_request("key-1", 1_000)).await.unwrap();
+
+        let first = first.unwrap();
+        assert_eq!(concurrent.unwrap(), first);
+        assert_eq!(later, first);
+        assert_eq!(messaging.sent.load(Ordering::SeqCst), 1);
+
+        // A new key is a new transfer; reusing a key for a different transfer is rejected
+        let second = service.initiate_transfer("client-a", transfer_request("key-2", 1_000)).await.unwrap();
+        assert_ne!(second.message_id, first.message_id);
+        assert!(service.initiate_transfer("client-a", transfer_request("key-1", 2_000)).await.is_err());
+        assert_eq!(messaging.sent.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_same_key_from_another_caller_is_a_separate_transfer() {
+        let messaging = CountingTransfers::new(Duration::ZERO, None);
+        let service = transfer_service(messaging.clone(), 3_600).await;
+
+        let first = service.initiate_transfer("client-a", transfer_request("key-1", 1_000)).await.unwrap();
+        // Another caller can neither replay nor block client-a's key
+        let other = service.initiate_transfer("client-b", transfer_request("key-1", 2_000)).await.unwrap();
+
+        assert_ne!(other.message_id, first.message_id);
+        assert_eq!(messaging.sent.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_abandoned_request_still_records_its_transfer() {
+        let messaging = CountingTransfers::new(Duration::from_millis(500), None);
+        let service = transfer_service(messaging.clone(), 3_600).await;
+
+        // The client gives up mid-send; the send carries on without it
+        let abandoned = tokio::time::timeout(
+            Duration::from_millis(100),
+            service.initiate_transfer("client-a", transfer_request("key-1", 1_000)),
+        ).await;
+        assert!(abandoned.is_err());
+
+        let retry = service.initiate_transfer("client-a", transfer_request("key-1", 1_000)).await.unwrap();
+        assert_eq!(retry.message_id, "ccip_msg_1");
+        assert_eq!(messaging.sent.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_only_definite_failures_release_the_key() {
+        let rejected = CountingTransfers::new(Duration::ZERO, Some(TransferFailure::NotSent));
+        let service = transfer_service(rejected, 3_600).await;
+        assert!(service.initiate_transfer("client-a", transfer_request("key-1", 1_000)).await.is_err());
+        assert_eq!(service.transfers.tracked_keys().await, 0);
+
+        let ambiguous = CountingTransfers::new(Duration::ZERO, Some(TransferFailure::Ambiguous));
+        let service = transfer_service(ambiguous, 3_600).await;
+        assert!(service.initiate_transfer("client-a", transfer_request("key-1", 1_000)).await.is_err());
+        assert_eq!(service.transfers.unreconciled_keys().await, 1);
+
+        // The transfer may have gone out, so a retry is refused until reconciled
+        let retry = service.initiate_transfer("client-a", transfer_request("key-1", 1_000)).await.unwrap_err();
+        assert!(retry.is::<TransferOutcomeUnknown>());
+
+        let reconciled = service.reconcile_transfer("client-a", "key-1", Some("ccip_msg_found".to_string()))
+            .await.unwrap().unwrap();
+        let replay = service.initiate_transfer("client-a", transfer_request("key-1", 1_000)).await.unwrap();
+        assert_eq!(replay, reconciled);
+        assert_eq!(service.transfers.unreconciled_keys().await, 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_completed_keys_expire_after_the_window() {
+        let messaging = CountingTransfers::new(Duration::ZERO, None);
+        let service = transfer_service(messaging.clone(), 1).await;
+        service.initiate_transfer("client-a", transfer_request("key-1", 1_000)).await.unwrap();
+
+        tokio::time::advance(Duration::from_millis(500)).await;
+        service.initiate_transfer("client-a", transfer_request("key-1", 1_000)).await.unwrap();
+        assert_eq!(messaging.sent.load(Ordering::SeqCst), 1);
+
+        tokio::time::advance(Duration::from_millis(600)).await;
+        // The key expired, so the same request is a new transfer
+        service.initiate_transfer("client-a", transfer_request("key-1", 1_000)).await.unwrap();
+        assert_eq!(messaging.sent.load(Ordering::SeqCst), 2);
+    }
+
+    /// Messaging leg whose first `failures` vault syncs fail
+    struct FlakyMessaging {
+        failures_remaining: AtomicU32,
+        syncs: AtomicU32,
+    }
+
+    impl FlakyMessaging {
+        fn new(failures: u32) -> Self {
+            Self { failures_remaining: AtomicU32::new(failures), syncs: AtomicU32::new(0) }
+        }
+    }
+
+    #[async_trait]
+    impl MessagingLeg for FlakyMessaging {
+        async fn sync_vault(&self, vault_sync: CrossChainVaultSync) -> Result<Vec<String>> {
+            self.syncs.fetch_add(1, Ordering::SeqCst);
+            if self.failures_remaining.load(Ordering::SeqCst) > 0 {
+                self.failures_remaining.fetch_sub(1, Ordering::SeqCst);
+                return Err(anyhow::anyhow!("Simulated CCIP router failure"));
+            }
+            Ok(vault_sync.destination_chains.iter().map(|chain| format!("msg_{}", chain)).collect())
+        }
+
+        async fn latest_nav_root(&self, _vault_id: &str, _chain_id: u64) -> Result<Option<String>> {
+            Ok(None)
+        }
+
+        async fn send_token_transfer(&self, transfer: &CrossChainTransferRequest) -> Result<String> {
+            Ok(format!("transfer_{}", transfer.idempotency_key))
+        }
+    }
+
+    async fn flaky_service(messaging: Arc<FlakyMessaging>) -> CrossChainService {
+        CrossChainService::with_sync_legs(
+            Arc::new(MockAnchor::new(0, 0)),
+            messaging,
+            Arc::new(MockVerification { delay: Duration::ZERO }),
+            test_chains(),
+            fast_retry_config(0, 5),
+        ).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_partial_sync_produces_plan_and_resend_reconciles() {
+        let messaging = Arc::new(FlakyMessaging::new(1));
+        let service = flaky_service(messaging.clone()).await;
+
+        let result = service.sync_vault_state_comprehensive("vault_1".to_string(), test_nav_data()).await.unwrap();
+        let plan = result.compensation_plan.expect("anchored but undelivered sync needs compensation");
+        assert_eq!(plan.succeeded_legs, vec!["Bitcoin".to_string(), "ICP".to_string()]);
+        assert_eq!(plan.failed_legs.len(), 3);
+        assert_eq!(plan.actions[0], CompensationAction::ResendNavSync { chain_ids: vec![1, 43114, 999999] });
+        assert!(matches!(service.chain_info(1).await.unwrap().status, ChainStatus::Degraded));
+
+        let outcome = service.compensate(&plan).await.unwrap();
+        assert_eq!(outcome.resent_chains, vec![1, 43114, 999999]);
+        assert!(outcome.superseded_anchor.is_none());
+        assert!(matches!(service.chain_info(1).await.unwrap().status, ChainStatus::Active));
+        // The correcting message gets fresh nonces
+        assert_eq!(service.coordinator.next_sync_nonce("vault_1", 1).await, 3);
+
+        // Compensating again changes nothing
+        assert_eq!(service.compensate(&plan).await.unwrap(), outcome);
+        assert_eq!(messaging.syncs.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failed_resend_supersedes_anchor_until_next_full_sync() {
+        let messaging = Arc::new(FlakyMessaging::new(2));
+        let service = flaky_service(messaging.clone()).await;
+
+        let result = service.sync_vault_state_comprehensive("vault_1".to_string(), test_nav_data()).await.unwrap();
+        let plan = result.compensation_plan.unwrap();
+
+        let outcome = service.compensate(&plan).await.unwrap();
+        assert!(outcome.resent_chains.is_empty());
+        assert_eq!(outcome.superseded_anchor.as_deref(), Some("mock_tx"));
+        assert_eq!(service.compensate(&plan).await.unwrap(), outcome);
+        assert_eq!(service.superseded_anchors("vault_1").await, vec!["mock_tx".to_string()]);
+
+        let next = service.sync_vault_state_comprehensive("vault_1".to_string(), test_nav_data()).await.unwrap();
+        assert!(next.compensation_plan.is_none());
+        assert_eq!(next.superseded_anchors, vec!["mock_tx".to_string()]);
+        assert!(service.superseded_anchors("vault_1").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_plan_for_an_older_sync_is_not_applied() {
+        let messaging = Arc::new(FlakyMessaging::new(2));
+        let service = flaky_service(messaging.clone()).await;
+
+        let first = service.sync_vault_state_comprehensive("vault_1".to_string(), test_nav_data()).await.unwrap();
+        let stale_plan = first.compensation_plan.unwrap();
+        // A newer NAV sync starts before the first plan is compensated
+        let second = service.sync_vault_state_comprehensive("vault_1".to_string(), test_nav_data()).await.unwrap();
+        let current_plan = second.compensation_plan.unwrap();
+        assert_eq!(current_plan.nav_version, stale_plan.nav_version + 1);
+
+        let outcome = service.compensate(&stale_plan).await.unwrap();
+        assert!(outcome.obsolete);
+        assert!(outcome.resent_chains.is_empty());
+        assert!(outcome.superseded_anchor.is_none());
+        assert_eq!(messaging.syncs.load(Ordering::SeqCst), 2);
+        assert_eq!(service.compensate(&stale_plan).await.unwrap(), outcome);
+
+        let outcome = service.compensate(&current_plan).await.unwrap();
+        assert!(!outcome.obsolete);
+        assert_eq!(outcome.resent_chains, vec![1, 43114, 999999]);
+    }
+
+    #[tokio::test]
+    async fn test_plan_in_progress_is_not_applied_twice() {
+        let messaging = Arc::new(FlakyMessaging::new(1));
+        let service = flaky_service(messaging.clone()).await;
+        let plan = service.sync_vault_state_comprehensive("vault_1".to_string(), test_nav_data()).await
+            .unwrap()
+            .compensation_plan
+            .unwrap();
+
+        service.compensations.write().await.insert(plan.plan_id.clone(), CompensationState::InProgress);
+        assert!(service.compensate(&plan).await.is_err());
+        assert_eq!(messaging.syncs.load(Ordering::SeqCst), 1);
+    }
+}
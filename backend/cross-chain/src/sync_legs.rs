@@ -0,0 +1,77 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::babylon_service::{BabylonService, BitcoinAnchor};
+use crate::ccip_service::{CCIPService, CrossChainVaultSync, NavData};
+use crate::icp_service::{self, ICPService, ICPVerification};
+use crate::transfer_idempotency::CrossChainTransferRequest;
+
+/// Independent legs of a comprehensive vault sync
+/// Each leg is driven concurrently by `CrossChainService` and can be swapped for testing
+
+/// Anchors a vault NAV commitment to Bitcoin
+#[async_trait]
+pub trait BitcoinAnchorLeg: Send + Sync {
+    async fn anchor_nav(&self, vault_id: String, nav_data: &NavData) -> Result<BitcoinAnchor>;
+}
+
+/// Delivers vault sync messages to destination chains
+#[async_trait]
+pub trait MessagingLeg: Send + Sync {
+    async fn sync_vault(&self, vault_sync: CrossChainVaultSync) -> Result<Vec<String>>;
+
+    /// Latest NAV root the vault has applied on `chain_id`, if any
+    async fn latest_nav_root(&self, vault_id: &str, chain_id: u64) -> Result<Option<String>>;
+
+    /// Send a token transfer, returning its message id
+    async fn send_token_transfer(&self, transfer: &CrossChainTransferRequest) -> Result<String>;
+}
+
+/// Verifies vault state consistency across chains
+#[async_trait]
+pub trait VerificationLeg: Send + Sync {
+    async fn verify_state(&self, vault_id: String, nav_data: &NavData) -> Result<ICPVerification>;
+}
+
+#[async_trait]
+impl BitcoinAnchorLeg for BabylonService {
+    async fn anchor_nav(&self, vault_id: String, nav_data: &NavData) -> Result<BitcoinAnchor> {
+        self.anchor_vault_nav_to_bitcoin(vault_id, nav_data.computation_hash.clone(), nav_data.epoch).await
+    }
+}
+
+#[async_trait]
+impl MessagingLeg for CCIPService {
+    async fn sync_vault(&self, vault_sync: CrossChainVaultSync) -> Result<Vec<String>> {
+        self.sync_vault_cross_chain(vault_sync).await
+    }
+
+    async fn latest_nav_root(&self, vault_id: &str, chain_id: u64) -> Result<Option<String>> {
+        self.read_vault_nav_root(vault_id, chain_id).await
+    }
+
+    async fn send_token_transfer(&self, transfer: &CrossChainTransferRequest) -> Result<String> {
+        self.send_programmable_token_transfer(
+            transfer.source_chain,
+            transfer.destination_chain,
+            transfer.token_address.clone(),
+            transfer.amount,
+            transfer.receiver.clone(),
+            Vec::new(),
+        ).await
+    }
+}
+
+#[async_trait]
+impl VerificationLeg for ICPService {
+    async fn verify_state(&self, vault_id: String, nav_data: &NavData) -> Result<ICPVerification> {
+        let nav_data = icp_service::NavData {
+            nav_per_share: nav_data.nav_per_share,
+            total_assets: nav_data.total_assets,
+            total_liabilities: nav_data.total_liabilities,
+            epoch: nav_data.epoch,
+            computation_hash: nav_data.computation_hash.clone(),
+        };
+        self.verify_cross_chain_state(vault_id, nav_data).await
+    }
+}
@@ -0,0 +1,270 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use tokio::sync::{watch, RwLock};
+use tokio::time::{Duration, Instant};
+use tracing::info;
+
+/// Idempotent cross-chain token transfers
+/// Clients that retry `POST /cross-chain/transfer` after a timeout reuse their
+/// `idempotency_key`; a replay returns the original message id instead of sending
+/// a second CCIP transfer. Keys are scoped to the caller that sent them, and a completed
+/// key is forgotten once the window elapses. A send that fails without proving the
+/// transfer was not submitted keeps its key until an operator reconciles it.
+
+pub const DEFAULT_IDEMPOTENCY_WINDOW_SECONDS: u64 = 24 * 60 * 60;
+
+/// A transfer leg failure that proves the transfer was never submitted, so its key
+/// can be released for a retry. Any other failure leaves the outcome unknown.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Transfer was not sent: {reason}")]
+pub struct TransferNotSent {
+    pub reason: String,
+}
+
+/// A transfer whose send failed ambiguously and awaits reconciliation
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Transfer for idempotency key {idempotency_key} has an unknown outcome and must be reconciled")]
+pub struct TransferOutcomeUnknown {
+    pub idempotency_key: String,
+}
+
+/// An idempotency key replayed with a different transfer payload
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Idempotency key {idempotency_key} was already used for a different transfer")]
+pub struct IdempotencyKeyConflict {
+    pub idempotency_key: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrossChainTransferRequest {
+    pub idempotency_key: String,
+    pub source_chain: u64,
+    pub destination_chain: u64,
+    pub token_address: String,
+    pub amount: u64,
+    pub receiver: String,
+}
+
+impl CrossChainTransferRequest {
+    /// Hash of everything but the key, so a key cannot be reused for a different transfer
+    fn fingerprint(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.source_chain.to_le_bytes());
+        hasher.update(self.destination_chain.to_le_bytes());
+        hasher.update((self.token_address.len() as u64).to_le_bytes());
+        hasher.update(self.token_address.as_bytes());
+        hasher.update(self.amount.to_le_bytes());
+        hasher.update(self.receiver.as_bytes());
+        hasher.finalize().into()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrossChainTransferResponse {
+    pub idempotency_key: String,
+    pub message_id: String,
+    pub source_chain: u64,
+    pub destination_chain: u64,
+    pub amount: u64,
+    pub initiated_at: i64,
+}
+
+/// Result shared with replays that arrive while the transfer is in flight
+type TransferOutcome = Option<std::result::Result<CrossChainTransferResponse, String>>;
+
+/// Keys are tracked per caller so one client cannot replay or block another's key
+type EntryKey = (String, String);
+
+enum IdempotencyEntry {
+    InFlight {
+        fingerprint: [u8; 32],
+        outcome: watch::Receiver<TransferOutcome>,
+    },
+    Completed {
+        fingerprint: [u8; 32],
+        completed_at: Instant,
+        response: CrossChainTransferResponse,
+    },
+    /// The send failed in a way that may still have submitted the transfer
+    Ambiguous {
+        request: CrossChainTransferRequest,
+        error: String,
+    },
+}
+
+impl IdempotencyEntry {
+    fn fingerprint(&self) -> [u8; 32] {
+        match self {
+            Self::InFlight { fingerprint, .. } | Self::Completed { fingerprint, .. } => *fingerprint,
+            Self::Ambiguous { request, .. } => request.fingerprint(),
+        }
+    }
+
+    /// Only completed keys expire; in-flight and ambiguous keys are kept until resolved
+    fn expired(&self, now: Instant, window: Duration) -> bool {
+        match self {
+            Self::Completed { completed_at, .. } => now.duration_since(*completed_at) >= window,
+            Self::InFlight { .. } | Self::Ambiguous { .. } => false,
+        }
+    }
+}
+
+/// What the caller should do with a transfer request
+pub enum TransferReservation {
+    /// First use of the key: send the transfer, then `complete` the reservation
+    New(watch::Sender<TransferOutcome>),
+    /// The key already completed; return this response
+    Replay(CrossChainTransferResponse),
+    /// The key is in flight; await its outcome
+    Pending(watch::Receiver<TransferOutcome>),
+}
+
+/// In-flight, completed and unreconciled transfer keys
+pub struct TransferIdempotency {
+    window: Duration,
+    entries: RwLock<HashMap<EntryKey, IdempotencyEntry>>,
+}
+
+impl TransferIdempotency {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Claim `request.idempotency_key` for `caller`, or find the transfer already using it
+    pub async fn reserve(&self, caller: &str, request: &CrossChainTransferRequest) -> Result<TransferReservation> {
+        if request.idempotency_key.trim().is_empty() {
+            return Err(anyhow!("Transfer idempotency key must not be empty"));
+        }
+        let fingerprint = request.fingerprint();
+        let now = Instant::now();
+        let key = (caller.to_string(), request.idempotency_key.clone());
+
+        let mut entries = self.entries.write().await;
+        entries.retain(|_, entry| !entry.expired(now, self.window));
+
+        if let Some(entry) = entries.get(&key) {
+            if entry.fingerprint() != fingerprint {
+                return Err(IdempotencyKeyConflict {
+                    idempotency_key: request.idempotency_key.clone(),
+                }.into());
+            }
+            return match entry {
+                IdempotencyEntry::Completed { response, .. } => Ok(TransferReservation::Replay(response.clone())),
+                IdempotencyEntry::InFlight { outcome, .. } => Ok(TransferReservation::Pending(outcome.clone())),
+                IdempotencyEntry::Ambiguous { .. } => Err(TransferOutcomeUnknown {
+                    idempotency_key: request.idempotency_key.clone(),
+                }.into()),
+            };
+        }
+
+        let (sender, outcome) = watch::channel(None);
+        entries.insert(key, IdempotencyEntry::InFlight { fingerprint, outcome });
+        Ok(TransferReservation::New(sender))
+    }
+
+    /// Record a reserved transfer's outcome
+    ///
+    /// Only a `TransferNotSent` failure releases the key for a retry; any other failure
+    /// may have submitted the transfer, so the key is held until `reconcile`d.
+    pub async fn complete(
+        &self,
+        caller: &str,
+        request: &CrossChainTransferRequest,
+        reservation: watch::Sender<TransferOutcome>,
+        result: &Result<CrossChainTransferResponse>,
+    ) {
+        let key = (caller.to_string(), request.idempotency_key.clone());
+        let mut entries = self.entries.write().await;
+        match result {
+            Ok(response) => {
+                entries.insert(key, IdempotencyEntry::Completed {
+                    fingerprint: request.fingerprint(),
+                    completed_at: Instant::now(),
+                    response: response.clone(),
+                });
+                let _ = reservation.send(Some(Ok(response.clone())));
+            }
+            Err(e) if e.is::<TransferNotSent>() => {
+                entries.remove(&key);
+                let _ = reservation.send(Some(Err(e.to_string())));
+            }
+            Err(e) => {
+                entries.insert(key, IdempotencyEntry::Ambiguous {
+                    request: request.clone(),
+                    error: e.to_string(),
+                });
+                let _ = reservation.send(Some(Err(e.to_string())));
+            }
+        }
+    }
+
+    /// Resolve an ambiguous transfer once its fate is known from the CCIP router
+    ///
+    /// `message_id` records the transfer as sent so replays return it; `None` records that
+    /// it was never sent and releases the key.
+    pub async fn reconcile(
+        &self,
+        caller: &str,
+        idempotency_key: &str,
+        message_id: Option<String>,
+    ) -> Result<Option<CrossChainTransferResponse>> {
+        let key = (caller.to_string(), idempotency_key.to_string());
+        let mut entries = self.entries.write().await;
+        let Some(IdempotencyEntry::Ambiguous { request, error }) = entries.get(&key) else {
+            return Err(anyhow!("No ambiguous transfer for idempotency key {}", idempotency_key));
+        };
+        info!("Reconciling transfer for idempotency key {} after: {}", idempotency_key, error);
+
+        let Some(message_id) = message_id else {
+            entries.remove(&key);
+            return Ok(None);
+        };
+        let response = CrossChainTransferResponse {
+            idempotency_key: request.idempotency_key.clone(),
+            message_id,
+            source_chain: request.source_chain,
+            destination_chain: request.destination_chain,
+            amount: request.amount,
+            initiated_at: chrono::Utc::now().timestamp(),
+        };
+        entries.insert(key, IdempotencyEntry::Completed {
+            fingerprint: request.fingerprint(),
+            completed_at: Instant::now(),
+            response: response.clone(),
+        });
+        Ok(Some(response))
+    }
+
+    /// Wait for the in-flight transfer holding the key
+    pub async fn await_outcome(mut outcome: watch::Receiver<TransferOutcome>) -> Result<CrossChainTransferResponse> {
+        loop {
+            if let Some(result) = outcome.borrow_and_update().clone() {
+                return result.map_err(|e| anyhow!("Transfer failed: {}", e));
+            }
+            outcome.changed().await
+                .map_err(|_| anyhow!("Transfer task ended without recording an outcome"))?;
+        }
+    }
+
+    pub async fn tracked_keys(&self) -> usize {
+        self.entries.read().await.len()
+    }
+
+    /// Keys whose send failed ambiguously and still await `reconcile`
+    pub async fn unreconciled_keys(&self) -> usize {
+        self.entries.read().await.values()
+            .filter(|entry| matches!(entry, IdempotencyEntry::Ambiguous { .. }))
+            .count()
+    }
+}
+
+impl Default for TransferIdempotency {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(DEFAULT_IDEMPOTENCY_WINDOW_SECONDS))
+    }
+}
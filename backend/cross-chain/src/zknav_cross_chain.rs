@@ -1,6 +1,8 @@
 use anyhow::Result;
+use epoch_clock::{EpochClock, SystemEpochClock};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{info, warn, error};
 use reqwest::Client;
@@ -10,6 +12,11 @@ use reqwest::Client;
 /// PRD: "NAV is computed daily using a verifiable zk circuit"
 /// PRD: "zkNAV drift ledger over 100 epochs"
 /// PRD: "Dilithium512 post-quantum signatures"
+/// Anchors are keyed by the epoch `epoch_clock` is in when they are made, one drift
+/// ledger entry per epoch.
+
+/// Default NAV epoch: NAV is computed daily
+pub const DEFAULT_NAV_EPOCH_SECONDS: u64 = 86_400;
 
 pub struct CrossChainZkNavService {
     solana_client: SolanaClient,
@@ -20,6 +27,7 @@ pub struct CrossChainZkNavService {
     nav_anchors: RwLock<HashMap<u64, NavAnchorSet>>,
     drift_ledger: RwLock<Vec<NavDriftEpoch>>,
     http_client: Client,
+    epoch_clock: Arc<dyn EpochClock>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -188,19 +196,29 @@ impl CrossChainZkNavService {
             nav_anchors: RwLock::new(HashMap::new()),
             drift_ledger: RwLock::new(Vec::new()),
             http_client,
+            epoch_clock: Arc::new(SystemEpochClock::new(DEFAULT_NAV_EPOCH_SECONDS)),
         })
     }
 
+    /// Clock picking the epoch NAV anchors and drift entries are recorded for
+    pub fn with_epoch_clock(mut self, epoch_clock: Arc<dyn EpochClock>) -> Self {
+        self.epoch_clock = epoch_clock;
+        self
+    }
+
     /// PRD: "NAV is computed daily using a verifiable zk circuit"
     /// PRD: "Posted to Solana, Anchored to BTC via Babylon, Pushed to Ethereum via CCIP, Stored in Celestia"
+    /// Anchors for the epoch the clock is currently in; anchoring again within the same
+    /// epoch replaces that epoch's anchors and drift entry.
     pub async fn anchor_nav_cross_chain(
         &self,
-        epoch: u64,
         nav_per_share: u64,
         zk_proof: Vec<u8>,
         dilithium_signature: String,
         sha256_signature: String,
     ) -> Result<NavAnchorSet> {
+        let now = self.epoch_clock.now();
+        let epoch = self.epoch_clock.epoch_for(now);
         info!("⚓ Anchoring NAV across all chains for epoch: {}", epoch);
         
         let zk_proof_hash = self.compute_proof_hash(&zk_proof);
@@ -245,7 +263,7 @@ impl CrossChainZkNavService {
             icp_anchor,
             dilithium_signature,
             sha256_signature,
-            timestamp: chrono::Utc::now().timestamp(),
+            timestamp: now,
             verification_status,
         };
         
@@ -350,7 +368,12 @@ impl CrossChainZkNavService {
 
     async fn update_drift_ledger(&self, epoch: u64, nav_per_share: u64, _anchor_set: &NavAnchorSet) -> Result<()> {
         let mut ledger = self.drift_ledger.write().await;
-        
+
+        // A later anchor in the same epoch supersedes the earlier one
+        if ledger.last().is_some_and(|last| last.epoch == epoch) {
+            ledger.pop();
+        }
+
         let drift_from_previous = if let Some(previous) = ledger.last() {
             let prev_nav = previous.nav_per_share as f64;
             let current_nav = nav_per_share as f64;
@@ -373,7 +396,7 @@ impl CrossChainZkNavService {
             ]),
             dilithium_verified: true,
             sha256_verified: true,
-            timestamp: chrono::Utc::now().timestamp(),
+            timestamp: self.epoch_clock.now(),
         };
         
         ledger.push(drift_epoch);
@@ -406,3 +429,46 @@ impl CrossChainZkNavService {
         anchors.get(&epoch).cloned()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use epoch_clock::MockEpochClock;
+
+    async fn service(clock: Arc<MockEpochClock>) -> CrossChainZkNavService {
+        CrossChainZkNavService::new(
+            "http://solana".to_string(),
+            "http://babylon".to_string(),
+            "http://ethereum".to_string(),
+            "http://celestia".to_string(),
+            "http://icp".to_string(),
+        ).await.unwrap().with_epoch_clock(clock)
+    }
+
+    async fn anchor(service: &CrossChainZkNavService, nav_per_share: u64) -> NavAnchorSet {
+        service.anchor_nav_cross_chain(nav_per_share, vec![1, 2, 3], String::new(), String::new())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_drift_ledger_has_one_entry_per_clock_epoch() {
+        let clock = Arc::new(MockEpochClock::new(DEFAULT_NAV_EPOCH_SECONDS, 1_700_000_000));
+        let service = service(clock.clone()).await;
+        let first_epoch = clock.current_epoch();
+
+        assert_eq!(anchor(&service, 1_000_000).await.epoch, first_epoch);
+        // Re-anchored later the same day: replaces that epoch's entry
+        clock.advance(3_600);
+        assert_eq!(anchor(&service, 1_010_000).await.epoch, first_epoch);
+
+        clock.advance_epochs(1);
+        assert_eq!(anchor(&service, 1_030_000).await.epoch, first_epoch + 1);
+
+        let ledger = service.get_drift_ledger().await;
+        let epochs: Vec<u64> = ledger.iter().map(|entry| entry.epoch).collect();
+        assert_eq!(epochs, vec![first_epoch, first_epoch + 1]);
+        assert_eq!(ledger[0].nav_per_share, 1_010_000);
+        assert!((ledger[1].drift_from_previous - 1.98).abs() < 0.01);
+    }
+}
@@ -0,0 +1,320 @@
+use anyhow::{anyhow, Result};
+use audit_log::{AuditEvent, AuditLog};
+use rtf_governance::{EmergencyAction, EmergencyActivation, EmergencyAuthorization, GovernanceSystem};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+/// Signal-driven circuit breaker
+/// PRD: "Circuit breaker mechanisms"
+/// Call `evaluate` on a timer with the latest NAV drift, redemption and oracle signals.
+/// A breach sets `circuit_breaker_active` on the shared governance `EmergencyState`,
+/// and the breaker stays tripped until the emergency signers clear
+/// `EmergencyAction::ActivateCircuitBreaker` through `reset`.
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BreakerSignals {
+    /// Latest zkReplay NAV drift
+    pub nav_drift_bps: u64,
+    /// Redemptions over the evaluation window as a share of AUM
+    pub redemption_rate_bps: u64,
+    /// Consecutive oracle update failures
+    pub oracle_failures: u32,
+}
+
+/// Limits above which a single signal trips the breaker; a limit of 0 disables that signal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BreakerThresholds {
+    pub max_nav_drift_bps: u64,
+    pub max_redemption_rate_bps: u64,
+    pub max_oracle_failures: u32,
+    /// Two or more signals at this fraction of their limits also trip the breaker
+    pub combined_fraction: f64,
+}
+
+impl Default for BreakerThresholds {
+    fn default() -> Self {
+        Self {
+            max_nav_drift_bps: 100,        // 1%
+            max_redemption_rate_bps: 1000, // 10% of AUM per window
+            max_oracle_failures: 3,
+            combined_fraction: 0.75,
+        }
+    }
+}
+
+impl BreakerThresholds {
+    /// Reasons `signals` should trip the breaker, empty if they are within limits
+    pub fn breaches(&self, signals: &BreakerSignals) -> Vec<String> {
+        let readings = [
+            ("NAV drift", signals.nav_drift_bps, self.max_nav_drift_bps, "bps"),
+            ("Redemption rate", signals.redemption_rate_bps, self.max_redemption_rate_bps, "bps"),
+            ("Oracle failures", signals.oracle_failures as u64, self.max_oracle_failures as u64, ""),
+        ];
+        let enabled = readings.iter().filter(|(_, _, limit, _)| *limit > 0);
+
+        let breaches: Vec<String> = enabled.clone()
+            .filter(|(_, value, limit, _)| value > limit)
+            .map(|(name, value, limit, unit)| format!("{} {}{} exceeds {}{}", name, value, unit, limit, unit))
+            .collect();
+        if !breaches.is_empty() {
+            return breaches;
+        }
+
+        let stressed: Vec<&str> = enabled
+            .filter(|(_, value, limit, _)| *value as f64 >= self.combined_fraction * *limit as f64)
+            .map(|(name, ..)| *name)
+            .collect();
+        if stressed.len() >= 2 {
+            return vec![format!(
+                "Combined stress: {} at {:.0}% of limits",
+                stressed.join(", "), self.combined_fraction * 100.0
+            )];
+        }
+
+        Vec::new()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BreakerTrip {
+    pub reasons: Vec<String>,
+    pub signals: BreakerSignals,
+    pub tripped_at: i64,
+}
+
+/// Outcome of submitting approvals to reset a tripped breaker
+#[derive(Debug, Clone, PartialEq)]
+pub enum BreakerReset {
+    /// More emergency signer approvals are needed
+    Pending { approvals: usize, required: usize },
+    /// The breaker was reset; carries the trip that was cleared
+    Cleared(BreakerTrip),
+}
+
+pub struct CircuitBreaker {
+    thresholds: BreakerThresholds,
+    enabled: bool,
+    governance: Arc<GovernanceSystem>,
+    trip: RwLock<Option<BreakerTrip>>,
+    audit_log: Option<Arc<AuditLog>>,
+}
+
+impl CircuitBreaker {
+    pub fn new(thresholds: BreakerThresholds, governance: Arc<GovernanceSystem>) -> Self {
+        Self {
+            thresholds,
+            enabled: true,
+            governance,
+            trip: RwLock::new(None),
+            audit_log: None,
+        }
+    }
+
+    /// Breaker configured from `EmergencyConfig`; disabled breakers never trip
+    pub fn from_config(config: &crate::EmergencyConfig, governance: Arc<GovernanceSystem>) -> Self {
+        Self {
+            enabled: config.circuit_breaker_enabled,
+            ..Self::new(config.breaker_thresholds.clone(), governance)
+        }
+    }
+
+    pub fn with_audit_log(mut self, audit_log: Arc<AuditLog>) -> Self {
+        self.audit_log = Some(audit_log);
+        self
+    }
+
+    /// Check the latest signals, tripping the breaker on a breach
+    ///
+    /// Returns the active trip, if any. Signals returning to normal do not clear a trip.
+    pub async fn evaluate(&self, signals: &BreakerSignals) -> Option<BreakerTrip> {
+        let mut trip = self.trip.write().await;
+        if trip.is_some() || !self.enabled {
+            return trip.clone();
+        }
+
+        let reasons = self.thresholds.breaches(signals);
+        if reasons.is_empty() {
+            return None;
+        }
+
+        {
+            let emergency_state = self.governance.shared_emergency_state();
+            let mut state = emergency_state.write().await;
+            state.circuit_breaker_active = true;
+            state.last_updated = Some(chrono::Utc::now());
+        }
+
+        let tripped = BreakerTrip {
+            reasons,
+            signals: signals.clone(),
+            tripped_at: chrono::Utc::now().timestamp(),
+        };
+        *trip = Some(tripped.clone());
+        drop(trip);
+
+        warn!("🔌 Circuit breaker tripped: {}", tripped.reasons.join("; "));
+        if let Some(audit_log) = &self.audit_log {
+            audit_log.append(AuditEvent::new("emergency", "circuit_breaker_tripped", serde_json::json!({
+                "reasons": tripped.reasons,
+                "signals": tripped.signals,
+            }))).await;
+        }
+
+        Some(tripped)
+    }
+
+    /// Submit emergency signer approvals to clear a trip and restore normal operation
+    ///
+    /// Approvals are collected by governance's `clear_emergency` for
+    /// `EmergencyAction::ActivateCircuitBreaker`, so a reset needs the same quorum as
+    /// clearing any other emergency. The trip stays in place until that quorum is reached.
+    pub async fn reset(&self, justification: String, authorization: EmergencyAuthorization) -> Result<BreakerReset> {
+        let mut trip = self.trip.write().await;
+        if trip.is_none() {
+            return Err(anyhow!("Circuit breaker is not tripped"));
+        }
+
+        let outcome = self.governance
+            .clear_emergency(EmergencyAction::ActivateCircuitBreaker, justification.clone(), authorization)
+            .await?;
+        if let EmergencyActivation::Pending { approvals, required } = outcome {
+            return Ok(BreakerReset::Pending { approvals, required });
+        }
+        let cleared = trip.take().expect("trip checked above");
+        drop(trip);
+
+        info!("🔌 Circuit breaker reset ({})", justification);
+        if let Some(audit_log) = &self.audit_log {
+            audit_log.append(AuditEvent::new("emergency", "circuit_breaker_reset", serde_json::json!({
+                "reasons": cleared.reasons,
+                "justification": justification,
+            }))).await;
+        }
+
+        Ok(BreakerReset::Cleared(cleared))
+    }
+
+    pub async fn is_tripped(&self) -> bool {
+        self.trip.read().await.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+    use rtf_governance::{
+        emergency_clearance_message, EmergencyError, GovernanceConfig, SignerApproval, DEFAULT_EMERGENCY_DOMAIN,
+    };
+
+    const JUSTIFICATION: &str = "Signals back within limits";
+
+    fn signing_keys() -> Vec<SigningKey> {
+        (1u8..=3).map(|seed| SigningKey::from_bytes(&[seed; 32])).collect()
+    }
+
+    async fn governance() -> Arc<GovernanceSystem> {
+        let config = GovernanceConfig {
+            emergency_threshold: 0.66, // 2-of-3
+            emergency_signers: signing_keys().iter().map(|k| k.verifying_key().to_bytes()).collect(),
+            ..GovernanceConfig::default()
+        };
+        Arc::new(GovernanceSystem::new(config).await.unwrap())
+    }
+
+    /// `key`'s approval to clear the circuit breaker under the current emergency nonce
+    async fn approve_reset(governance: &GovernanceSystem, key: &SigningKey) -> EmergencyAuthorization {
+        let nonce = governance.emergency_nonce().await;
+        let message = emergency_clearance_message(
+            DEFAULT_EMERGENCY_DOMAIN,
+            nonce,
+            &EmergencyAction::ActivateCircuitBreaker,
+            JUSTIFICATION,
+        );
+        EmergencyAuthorization {
+            approvals: vec![SignerApproval {
+                signer: key.verifying_key().to_bytes(),
+                signature: key.sign(&message).to_bytes().to_vec(),
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_combined_signals_trip_breaker_until_reset() {
+        let governance = governance().await;
+        let keys = signing_keys();
+        let breaker = CircuitBreaker::new(BreakerThresholds::default(), governance.clone());
+
+        let calm = BreakerSignals { nav_drift_bps: 80, redemption_rate_bps: 200, oracle_failures: 0 };
+        assert!(breaker.evaluate(&calm).await.is_none());
+        assert!(governance.ensure_deposits_allowed().await.is_ok());
+
+        // Neither drift nor redemptions exceed their limits alone, but both are elevated
+        let stressed = BreakerSignals { nav_drift_bps: 80, redemption_rate_bps: 900, oracle_failures: 1 };
+        let trip = breaker.evaluate(&stressed).await.unwrap();
+        assert!(trip.reasons[0].starts_with("Combined stress: NAV drift, Redemption rate"));
+        assert!(governance.circuit_breaker_active().await);
+        let blocked = governance.ensure_deposits_allowed().await.unwrap_err();
+        assert_eq!(blocked.downcast_ref::<EmergencyError>(), Some(&EmergencyError::CircuitBreakerActive));
+
+        // Recovered signals leave the breaker tripped
+        assert_eq!(breaker.evaluate(&calm).await, Some(trip.clone()));
+        assert!(governance.circuit_breaker_active().await);
+
+        // One signer is not enough to reset
+        let first = approve_reset(&governance, &keys[0]).await;
+        assert_eq!(
+            breaker.reset(JUSTIFICATION.to_string(), first).await.unwrap(),
+            BreakerReset::Pending { approvals: 1, required: 2 }
+        );
+        assert!(breaker.is_tripped().await);
+        assert!(governance.circuit_breaker_active().await);
+
+        let second = approve_reset(&governance, &keys[1]).await;
+        assert_eq!(breaker.reset(JUSTIFICATION.to_string(), second).await.unwrap(), BreakerReset::Cleared(trip));
+        assert!(!governance.circuit_breaker_active().await);
+        assert!(governance.ensure_deposits_allowed().await.is_ok());
+        assert!(breaker.evaluate(&calm).await.is_none());
+        let again = approve_reset(&governance, &keys[2]).await;
+        assert!(breaker.reset(JUSTIFICATION.to_string(), again).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reset_without_signer_approval_is_rejected() {
+        let governance = governance().await;
+        let breaker = CircuitBreaker::new(BreakerThresholds::default(), governance.clone());
+        let breach = BreakerSignals { nav_drift_bps: 150, ..BreakerSignals::default() };
+        breaker.evaluate(&breach).await.unwrap();
+
+        // Unsigned, and signed by a key outside the emergency signer set
+        let unsigned = EmergencyAuthorization { approvals: Vec::new() };
+        assert_eq!(
+            breaker.reset(JUSTIFICATION.to_string(), unsigned).await.unwrap(),
+            BreakerReset::Pending { approvals: 0, required: 2 }
+        );
+        let outsider = approve_reset(&governance, &SigningKey::from_bytes(&[9; 32])).await;
+        assert!(breaker.reset(JUSTIFICATION.to_string(), outsider).await.is_err());
+
+        assert!(breaker.is_tripped().await);
+        assert!(governance.circuit_breaker_active().await);
+    }
+
+    #[test]
+    fn test_single_signal_breaches_are_reported() {
+        let thresholds = BreakerThresholds::default();
+        let breaches = thresholds.breaches(&BreakerSignals {
+            nav_drift_bps: 150,
+            redemption_rate_bps: 0,
+            oracle_failures: 4,
+        });
+        assert_eq!(breaches, vec![
+            "NAV drift 150bps exceeds 100bps".to_string(),
+            "Oracle failures 4 exceeds 3".to_string(),
+        ]);
+
+        let no_oracle_limit = BreakerThresholds { max_oracle_failures: 0, ..BreakerThresholds::default() };
+        assert!(no_oracle_limit.breaches(&BreakerSignals { oracle_failures: 50, ..BreakerSignals::default() }).is_empty());
+    }
+}
@@ -1,10 +1,16 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use audit_log::{AuditEvent, AuditLog};
+use rtf_governance::EmergencyAuthorization;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 use tokio::sync::RwLock;
 use tokio::time::{Duration, sleep, Instant};
 use tracing::{info, warn, error, debug};
 
+use crate::notifier::{DeliveryRecord, LogNotifier, NotificationDispatcher, Notifier, HIGH_PRIORITY_CONTACT};
+use crate::suicide_lock::{suicide_lock_cancellation_message, suicide_lock_message, SuicideLock, SuicideLockSigners};
+
 /// Emergency Handler Service for RTF Infrastructure
 /// PRD Section 4.3: Emergency Handler
 /// PRD: "Circuit breaker mechanisms"
@@ -19,6 +25,12 @@ pub struct EmergencyService {
     auto_response_enabled: bool,
     emergency_multisig: String,
     suicide_lock_delay: Duration,
+    suicide_lock_signers: SuicideLockSigners,
+    suicide_lock: RwLock<Option<SuicideLock>>,
+    /// Unix time at `Instant`; suicide lock timestamps advance with tokio's clock from here
+    clock_origin: (Instant, i64),
+    notifications: NotificationDispatcher,
+    audit_log: Option<Arc<AuditLog>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -208,6 +220,11 @@ impl EmergencyService {
             auto_response_enabled: true,
             emergency_multisig,
             suicide_lock_delay: Duration::from_secs(7 * 24 * 3600), // 7 days
+            suicide_lock_signers: SuicideLockSigners::default(),
+            suicide_lock: RwLock::new(None),
+            clock_origin: (Instant::now(), chrono::Utc::now().timestamp()),
+            notifications: NotificationDispatcher::new(Arc::new(LogNotifier)),
+            audit_log: None,
         };
 
         // Initialize default circuit breakers
@@ -224,6 +241,50 @@ impl EmergencyService {
         Ok(service)
     }
 
+    /// Record emergency responses, breaker trips and suicide locks in `audit_log`
+    pub fn with_audit_log(mut self, audit_log: Arc<AuditLog>) -> Self {
+        self.audit_log = Some(audit_log);
+        self
+    }
+
+    /// Override the 7-day notice period before an armed suicide lock may execute
+    pub fn with_suicide_lock_delay(mut self, delay: Duration) -> Self {
+        self.suicide_lock_delay = delay;
+        self
+    }
+
+    /// Signers whose approvals are required to execute the suicide lock
+    pub fn with_suicide_lock_signers(mut self, signers: SuicideLockSigners) -> Self {
+        self.suicide_lock_signers = signers;
+        self
+    }
+
+    /// Deliver contact notifications through `notifier` instead of logging them
+    pub fn with_notifier(mut self, notifier: Arc<dyn Notifier>) -> Self {
+        self.notifications = NotificationDispatcher::new(notifier);
+        self
+    }
+
+    /// Current unix time as seen by the suicide lock
+    ///
+    /// Measured on tokio's monotonic clock from service start, so a wall clock step
+    /// cannot shorten the notice period.
+    fn now(&self) -> i64 {
+        let (instant, timestamp) = self.clock_origin;
+        timestamp + instant.elapsed().as_secs() as i64
+    }
+
+    /// Per-contact delivery outcomes for an emergency event
+    pub async fn notification_deliveries(&self, event_id: &str) -> Vec<DeliveryRecord> {
+        self.notifications.deliveries_for(event_id).await
+    }
+
+    async fn audit(&self, event: AuditEvent) {
+        if let Some(audit_log) = &self.audit_log {
+            audit_log.append(event).await;
+        }
+    }
+
     /// PRD: Monitor system health and trigger circuit breakers
     /// PRD: "Circuit breaker mechanisms"
     pub async fn monitor_system_health(&self) -> Result<()> {
@@ -323,6 +384,14 @@ impl EmergencyService {
         // Update event status
         emergency_event.status = EmergencyStatus::Mitigating;
 
+        self.audit(AuditEvent::new("emergency", "emergency_response_executed", serde_json::json!({
+            "event_id": emergency_event.event_id,
+            "event_type": emergency_event.event_type,
+            "severity": emergency_event.severity,
+            "component": emergency_event.component,
+            "actions_taken": emergency_event.actions_taken,
+        })).with_actor(emergency_event.triggered_by.clone())).await;
+
         info!("✅ Emergency response executed for event: {}", event_id);
         Ok(emergency_event)
     }
@@ -346,6 +415,10 @@ impl EmergencyService {
 
             // Execute emergency response
             drop(breakers); // Release lock before async call
+            self.audit(AuditEvent::new("emergency", "circuit_breaker_triggered", serde_json::json!({
+                "component": component,
+                "reason": reason,
+            }))).await;
             self.execute_emergency_response(
                 EmergencyEventType::CircuitBreakerTriggered,
                 component.to_string(),
@@ -368,18 +441,18 @@ impl EmergencyService {
     ) -> Result<()> {
         info!("🔒 Activating suicide lock with 7-day delay");
 
-        let unlock_timestamp = chrono::Utc::now().timestamp() + self.suicide_lock_delay.as_secs() as i64;
+        let lock = self.arm_suicide_lock(reason, initiated_by).await?;
 
         // Create emergency event
         let emergency_event = EmergencyEvent {
-            event_id: format!("suicide_lock_{}", chrono::Utc::now().timestamp()),
+            event_id: format!("suicide_lock_{}", lock.lock_id),
             event_type: EmergencyEventType::SystemFailure,
             severity: EmergencyPriority::Critical,
             component: "entire_system".to_string(),
-            description: format!("Suicide lock activated: {}", reason),
-            triggered_by: initiated_by,
-            timestamp: chrono::Utc::now().timestamp(),
-            actions_taken: vec![format!("Suicide lock scheduled for: {}", unlock_timestamp)],
+            description: format!("Suicide lock activated: {}", lock.reason),
+            triggered_by: lock.initiated_by.clone(),
+            timestamp: lock.armed_at,
+            actions_taken: vec![format!("Suicide lock eligible at: {}", lock.unlock_eligible_at)],
             status: EmergencyStatus::Active,
             resolution_time: None,
         };
@@ -387,13 +460,145 @@ impl EmergencyService {
         // Send critical notifications
         self.send_critical_notifications(&emergency_event).await?;
 
-        // Schedule the actual lock execution
-        self.schedule_suicide_lock_execution(unlock_timestamp, reason).await?;
-
-        info!("✅ Suicide lock activated, will execute at timestamp: {}", unlock_timestamp);
+        info!("✅ Suicide lock activated, executable with approvals from timestamp: {}", lock.unlock_eligible_at);
         Ok(())
     }
 
+    /// Arm the suicide lock, starting its notice period
+    pub async fn arm_suicide_lock(&self, reason: String, initiated_by: String) -> Result<SuicideLock> {
+        let mut current = self.suicide_lock.write().await;
+        if let Some(lock) = current.as_ref() {
+            return Err(match lock.executed_at {
+                Some(_) => anyhow!("Suicide lock {} has already executed", lock.lock_id),
+                None => anyhow!("Suicide lock {} is already armed", lock.lock_id),
+            });
+        }
+
+        let armed_at = self.now();
+        let lock = SuicideLock {
+            lock_id: uuid::Uuid::new_v4().to_string(),
+            reason,
+            initiated_by,
+            armed_at,
+            unlock_eligible_at: armed_at + self.suicide_lock_delay.as_secs() as i64,
+            executed_at: None,
+        };
+        *current = Some(lock.clone());
+        drop(current);
+
+        self.audit(AuditEvent::new("emergency", "suicide_lock_armed", serde_json::json!({
+            "lock_id": lock.lock_id,
+            "reason": lock.reason,
+            "unlock_eligible_at": lock.unlock_eligible_at,
+        })).with_actor(lock.initiated_by.clone())).await;
+
+        warn!("🔒 Suicide lock {} armed, eligible at {}", lock.lock_id, lock.unlock_eligible_at);
+        Ok(lock)
+    }
+
+    /// Execute the armed suicide lock once its notice period has elapsed and enough
+    /// signers have approved `suicide_lock_message` for it
+    ///
+    /// Execution halts the system by opening every circuit breaker; the breakers stay
+    /// open because an executed lock cannot be cancelled or re-armed.
+    pub async fn execute_suicide_lock(&self, authorization: EmergencyAuthorization) -> Result<SuicideLock> {
+        let mut current = self.suicide_lock.write().await;
+        let lock = current.as_mut().ok_or_else(|| anyhow!("Suicide lock is not armed"))?;
+        if lock.executed_at.is_some() {
+            return Err(anyhow!("Suicide lock {} has already executed", lock.lock_id));
+        }
+
+        let now = self.now();
+        if now < lock.unlock_eligible_at {
+            return Err(anyhow!(
+                "Suicide lock {} cannot execute for another {} seconds",
+                lock.lock_id, lock.unlock_eligible_at - now
+            ));
+        }
+
+        let approvals = self.suicide_lock_quorum(lock, &suicide_lock_message(lock), &authorization)?;
+
+        lock.executed_at = Some(now);
+        let lock = lock.clone();
+        drop(current);
+
+        error!("🔒 SUICIDE LOCK EXECUTED: {}", lock.reason);
+        let halted = self.halt_all_components(now).await;
+
+        self.audit(AuditEvent::new("emergency", "suicide_lock_executed", serde_json::json!({
+            "lock_id": lock.lock_id,
+            "reason": lock.reason,
+            "approvals": approvals,
+            "halted_components": halted,
+        }))).await;
+
+        Ok(lock)
+    }
+
+    /// Disarm a suicide lock that has not yet executed, once enough signers have approved
+    /// `suicide_lock_cancellation_message` for it
+    pub async fn cancel_suicide_lock(&self, authorization: EmergencyAuthorization) -> Result<SuicideLock> {
+        let mut current = self.suicide_lock.write().await;
+        let lock = current.as_ref().ok_or_else(|| anyhow!("Suicide lock is not armed"))?;
+        if lock.executed_at.is_some() {
+            return Err(anyhow!("Suicide lock {} has already executed", lock.lock_id));
+        }
+        let approvals = self.suicide_lock_quorum(lock, &suicide_lock_cancellation_message(lock), &authorization)?;
+        let lock = current.take().expect("suicide lock checked above");
+        drop(current);
+
+        self.audit(AuditEvent::new("emergency", "suicide_lock_cancelled", serde_json::json!({
+            "lock_id": lock.lock_id,
+            "approvals": approvals,
+        }))).await;
+
+        info!("🔓 Suicide lock {} cancelled with {} approvals", lock.lock_id, approvals);
+        Ok(lock)
+    }
+
+    /// Check `authorization` carries enough signer approvals of `message`, returning the count
+    fn suicide_lock_quorum(
+        &self,
+        lock: &SuicideLock,
+        message: &[u8; 32],
+        authorization: &EmergencyAuthorization,
+    ) -> Result<usize> {
+        let required = self.suicide_lock_signers.required_approvals()?;
+        let approvals = self.suicide_lock_signers.count_approvals(message, authorization)?;
+        if approvals < required {
+            return Err(anyhow!(
+                "Suicide lock {} approved by {}/{} signers", lock.lock_id, approvals, required
+            ));
+        }
+        Ok(approvals)
+    }
+
+    /// Open every circuit breaker, returning the halted components
+    async fn halt_all_components(&self, now: i64) -> Vec<String> {
+        let mut breakers = self.circuit_breakers.write().await;
+        let mut halted: Vec<String> = breakers.values_mut()
+            .map(|breaker| {
+                breaker.state = CircuitBreakerState::Open;
+                breaker.last_failure = Some(now);
+                breaker.component.clone()
+            })
+            .collect();
+        halted.sort();
+        halted
+    }
+
+    /// State of each component's circuit breaker
+    pub async fn circuit_breaker_states(&self) -> HashMap<String, CircuitBreakerState> {
+        self.circuit_breakers.read().await.iter()
+            .map(|(component, breaker)| (component.clone(), breaker.state.clone()))
+            .collect()
+    }
+
+    /// Currently armed or executed suicide lock
+    pub async fn suicide_lock(&self) -> Option<SuicideLock> {
+        self.suicide_lock.read().await.clone()
+    }
+
     // Private helper methods
     async fn initialize_circuit_breakers(&self) -> Result<()> {
         let mut breakers = self.circuit_breakers.write().await;
@@ -749,11 +954,11 @@ impl EmergencyService {
     async fn send_emergency_notifications(&self, event: &EmergencyEvent, protocol: &EmergencyProtocol) -> Result<()> {
         info!("📢 Sending emergency notifications for event: {}", event.event_id);
 
-        for contact in &self.emergency_contacts {
-            if contact.priority <= 3 { // High priority contacts
-                self.send_notification_to_contact(contact, event).await?;
-            }
-        }
+        let contacts: Vec<EmergencyContact> = self.emergency_contacts.iter()
+            .filter(|contact| contact.priority <= HIGH_PRIORITY_CONTACT)
+            .cloned()
+            .collect();
+        self.notifications.dispatch(&contacts, event).await;
 
         Ok(())
     }
@@ -761,9 +966,7 @@ impl EmergencyService {
     async fn send_critical_notifications(&self, event: &EmergencyEvent) -> Result<()> {
         info!("🚨 Sending critical notifications for event: {}", event.event_id);
 
-        for contact in &self.emergency_contacts {
-            self.send_notification_to_contact(contact, event).await?;
-        }
+        self.notifications.dispatch(&self.emergency_contacts, event).await;
 
         Ok(())
     }
@@ -774,33 +977,9 @@ impl EmergencyService {
         Ok(())
     }
 
-    async fn send_notification_to_contact(&self, contact: &EmergencyContact, event: &EmergencyEvent) -> Result<()> {
-        info!("📞 Notifying contact {}: {}", contact.name, event.event_id);
-        // TODO: Implement actual contact notification
-        Ok(())
-    }
-
     async fn send_emergency_alert(&self, parameters: &serde_json::Value) -> Result<()> {
         info!("🚨 Sending emergency alert: {:?}", parameters);
         // TODO: Implement actual alert sending
         Ok(())
     }
-
-    async fn schedule_suicide_lock_execution(&self, unlock_timestamp: i64, reason: String) -> Result<()> {
-        info!("⏰ Scheduling suicide lock execution for timestamp: {}", unlock_timestamp);
-        
-        // In production, this would schedule the actual system shutdown
-        // For now, we just log the scheduled action
-        
-        tokio::spawn(async move {
-            let delay = unlock_timestamp - chrono::Utc::now().timestamp();
-            if delay > 0 {
-                sleep(Duration::from_secs(delay as u64)).await;
-                error!("🔒 SUICIDE LOCK EXECUTED: {}", reason);
-                // TODO: Implement actual system shutdown
-            }
-        });
-
-        Ok(())
-    }
 }
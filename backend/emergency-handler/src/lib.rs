@@ -1,6 +1,13 @@
+pub mod circuit_breaker;
 pub mod emergency_service;
+pub mod notifier;
+pub mod suicide_lock;
 
 pub use emergency_service::*;
+pub use notifier::*;
+pub use suicide_lock::*;
+// `emergency_service` already exports a per-component `CircuitBreaker`
+pub use circuit_breaker::{BreakerReset, BreakerSignals, BreakerThresholds, BreakerTrip};
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
@@ -14,18 +21,25 @@ use tracing::{info, warn, error};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmergencyConfig {
     pub circuit_breaker_enabled: bool,
+    pub breaker_thresholds: BreakerThresholds,
     pub suicide_lock_delay_hours: u64,
     pub auto_response_enabled: bool,
     pub emergency_contacts: Vec<String>,
+    /// Ed25519 keys allowed to approve suicide lock execution
+    pub suicide_lock_signers: Vec<[u8; 32]>,
+    pub suicide_lock_threshold: f64,
 }
 
 impl Default for EmergencyConfig {
     fn default() -> Self {
         Self {
             circuit_breaker_enabled: true,
+            breaker_thresholds: BreakerThresholds::default(),
             suicide_lock_delay_hours: 168, // 7 days
             auto_response_enabled: true,
             emergency_contacts: vec!["emergency@rtf.finance".to_string()],
+            suicide_lock_signers: Vec::new(),
+            suicide_lock_threshold: 0.66,
         }
     }
 }
@@ -47,7 +61,12 @@ pub async fn init_emergency_service(config: EmergencyConfig) -> Result<Emergency
     let service = EmergencyService::new(
         "emergency_multisig".to_string(),
         emergency_contacts,
-    ).await?;
+    ).await?
+    .with_suicide_lock_delay(tokio::time::Duration::from_secs(config.suicide_lock_delay_hours * 3600))
+    .with_suicide_lock_signers(SuicideLockSigners {
+        signers: config.suicide_lock_signers,
+        threshold: config.suicide_lock_threshold,
+    });
 
     info!("✅ RTF Emergency Handler Service initialized successfully");
     Ok(service)
@@ -63,4 +82,103 @@ mod tests {
         let result = init_emergency_service(config).await;
         assert!(result.is_ok());
     }
+
+    fn signing_keys() -> Vec<ed25519_dalek::SigningKey> {
+        (1u8..=3).map(|seed| ed25519_dalek::SigningKey::from_bytes(&[seed; 32])).collect()
+    }
+
+    async fn service_with_delay(keys: &[ed25519_dalek::SigningKey], delay_seconds: u64) -> EmergencyService {
+        let config = EmergencyConfig {
+            suicide_lock_signers: keys.iter().map(|k| k.verifying_key().to_bytes()).collect(),
+            ..EmergencyConfig::default()
+        };
+        init_emergency_service(config).await.unwrap()
+            .with_suicide_lock_delay(tokio::time::Duration::from_secs(delay_seconds))
+    }
+
+    fn approve(keys: &[ed25519_dalek::SigningKey], lock: &SuicideLock) -> rtf_governance::EmergencyAuthorization {
+        sign(keys, suicide_lock_message(lock))
+    }
+
+    fn approve_cancellation(keys: &[ed25519_dalek::SigningKey], lock: &SuicideLock) -> rtf_governance::EmergencyAuthorization {
+        sign(keys, suicide_lock_cancellation_message(lock))
+    }
+
+    fn sign(keys: &[ed25519_dalek::SigningKey], message: [u8; 32]) -> rtf_governance::EmergencyAuthorization {
+        use ed25519_dalek::Signer;
+        rtf_governance::EmergencyAuthorization {
+            approvals: keys.iter().map(|key| rtf_governance::SignerApproval {
+                signer: key.verifying_key().to_bytes(),
+                signature: key.sign(&message).to_bytes().to_vec(),
+            }).collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_suicide_lock_cannot_execute_before_delay() {
+        let keys = signing_keys();
+        let service = service_with_delay(&keys, 168 * 3600).await;
+        let lock = service.arm_suicide_lock("Custodian insolvency".to_string(), "ops".to_string()).await.unwrap();
+        assert_eq!(lock.unlock_eligible_at - lock.armed_at, 168 * 3600);
+
+        let result = service.execute_suicide_lock(approve(&keys, &lock)).await;
+        assert!(result.unwrap_err().to_string().contains("cannot execute"));
+        assert_eq!(service.suicide_lock().await.unwrap().executed_at, None);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_suicide_lock_cannot_execute() {
+        let keys = signing_keys();
+        let service = service_with_delay(&keys, 168 * 3600).await;
+        let lock = service.arm_suicide_lock("Custodian insolvency".to_string(), "ops".to_string()).await.unwrap();
+        assert!(service.arm_suicide_lock("Again".to_string(), "ops".to_string()).await.is_err());
+
+        let cancelled = service.cancel_suicide_lock(approve_cancellation(&keys[..2], &lock)).await.unwrap();
+        assert_eq!(cancelled.lock_id, lock.lock_id);
+        assert!(service.suicide_lock().await.is_none());
+        assert!(service.execute_suicide_lock(approve(&keys, &lock)).await.is_err());
+        assert!(service.cancel_suicide_lock(approve_cancellation(&keys, &lock)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_suicide_lock_cancellation_requires_quorum() {
+        let keys = signing_keys();
+        let service = service_with_delay(&keys, 168 * 3600).await;
+        let lock = service.arm_suicide_lock("Custodian insolvency".to_string(), "ops".to_string()).await.unwrap();
+
+        let single = service.cancel_suicide_lock(approve_cancellation(&keys[..1], &lock)).await;
+        assert!(single.unwrap_err().to_string().contains("approved by 1/2"));
+
+        // Execution approvals do not double as cancellation approvals
+        assert!(service.cancel_suicide_lock(approve(&keys, &lock)).await.is_err());
+
+        let outsider = ed25519_dalek::SigningKey::from_bytes(&[9; 32]);
+        assert!(service.cancel_suicide_lock(approve_cancellation(&[outsider], &lock)).await.is_err());
+        assert_eq!(service.suicide_lock().await, Some(lock));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_suicide_lock_executes_after_delay_with_approvals() {
+        let keys = signing_keys();
+        let service = service_with_delay(&keys, 3_600).await;
+        let lock = service.arm_suicide_lock("Custodian insolvency".to_string(), "ops".to_string()).await.unwrap();
+
+        tokio::time::advance(tokio::time::Duration::from_secs(3_599)).await;
+        let early = service.execute_suicide_lock(approve(&keys, &lock)).await;
+        assert!(early.unwrap_err().to_string().contains("cannot execute"));
+        tokio::time::advance(tokio::time::Duration::from_secs(1)).await;
+
+        // 0.66 of three signers rounds up to two approvals
+        let single = service.execute_suicide_lock(approve(&keys[..1], &lock)).await;
+        assert!(single.unwrap_err().to_string().contains("approved by 1/2"));
+
+        let executed = service.execute_suicide_lock(approve(&keys[..2], &lock)).await.unwrap();
+        assert!(executed.executed_at.is_some_and(|at| at >= lock.unlock_eligible_at));
+        let breakers = service.circuit_breaker_states().await;
+        assert!(!breakers.is_empty());
+        assert!(breakers.values().all(|state| *state == CircuitBreakerState::Open));
+
+        assert!(service.execute_suicide_lock(approve(&keys, &lock)).await.is_err());
+        assert!(service.cancel_suicide_lock(approve_cancellation(&keys, &lock)).await.is_err());
+    }
 }
@@ -0,0 +1,227 @@
+//! Emergency contact notification
+//!
+//! Alerts fan out to contacts in `priority` order (1 first). Contacts with priority
+//! `1..=HIGH_PRIORITY_CONTACT` are retried on failure, and every contact's outcome is
+//! recorded per event so responders can see who was actually reached.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tokio::time::{sleep, Duration};
+use tracing::{info, warn};
+
+use crate::emergency_service::{EmergencyContact, EmergencyEvent};
+
+/// Contacts with priority up to this value are retried on failure
+pub const HIGH_PRIORITY_CONTACT: u8 = 3;
+
+/// Delivery channel for emergency alerts (email, SMS, Telegram, ...)
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    async fn notify(&self, contact: &EmergencyContact, alert: &EmergencyEvent) -> Result<()>;
+}
+
+/// Notifier that only logs the alert
+#[derive(Debug, Clone, Default)]
+pub struct LogNotifier;
+
+#[async_trait]
+impl Notifier for LogNotifier {
+    async fn notify(&self, contact: &EmergencyContact, alert: &EmergencyEvent) -> Result<()> {
+        info!("📞 Notifying contact {}: {}", contact.name, alert.event_id);
+        Ok(())
+    }
+}
+
+/// Notifier that accepts every alert without sending anything
+#[derive(Debug, Clone, Default)]
+pub struct NoopNotifier;
+
+#[async_trait]
+impl Notifier for NoopNotifier {
+    async fn notify(&self, _contact: &EmergencyContact, _alert: &EmergencyEvent) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeliveryStatus {
+    Delivered,
+    Failed { error: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeliveryRecord {
+    pub event_id: String,
+    pub contact_name: String,
+    pub priority: u8,
+    pub status: DeliveryStatus,
+    pub attempts: u32,
+    pub completed_at: i64,
+}
+
+pub struct NotificationDispatcher {
+    notifier: Arc<dyn Notifier>,
+    max_retries: u32,
+    retry_delay: Duration,
+    deliveries: RwLock<HashMap<String, Vec<DeliveryRecord>>>,
+}
+
+impl NotificationDispatcher {
+    pub fn new(notifier: Arc<dyn Notifier>) -> Self {
+        Self {
+            notifier,
+            max_retries: 3,
+            retry_delay: Duration::from_millis(500),
+            deliveries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_retry_policy(mut self, max_retries: u32, retry_delay: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Notify `contacts` one priority tier at a time, highest priority first
+    pub async fn dispatch(&self, contacts: &[EmergencyContact], alert: &EmergencyEvent) -> Vec<DeliveryRecord> {
+        let mut tiers: BTreeMap<u8, Vec<&EmergencyContact>> = BTreeMap::new();
+        for contact in contacts {
+            tiers.entry(contact.priority).or_default().push(contact);
+        }
+
+        let mut records = Vec::with_capacity(contacts.len());
+        for (priority, tier) in tiers {
+            let max_attempts = if priority <= HIGH_PRIORITY_CONTACT { 1 + self.max_retries } else { 1 };
+            for contact in tier {
+                records.push(self.deliver(contact, alert, max_attempts).await);
+            }
+        }
+
+        self.deliveries.write().await
+            .entry(alert.event_id.clone())
+            .or_default()
+            .extend(records.iter().cloned());
+        records
+    }
+
+    async fn deliver(&self, contact: &EmergencyContact, alert: &EmergencyEvent, max_attempts: u32) -> DeliveryRecord {
+        let mut attempts = 0;
+        let status = loop {
+            attempts += 1;
+            match self.notifier.notify(contact, alert).await {
+                Ok(()) => break DeliveryStatus::Delivered,
+                Err(e) if attempts < max_attempts => {
+                    warn!("⚠️ Notifying {} failed (attempt {}): {}", contact.name, attempts, e);
+                    sleep(self.retry_delay).await;
+                }
+                Err(e) => {
+                    warn!("❌ Could not notify {} about {}: {}", contact.name, alert.event_id, e);
+                    break DeliveryStatus::Failed { error: e.to_string() };
+                }
+            }
+        };
+
+        DeliveryRecord {
+            event_id: alert.event_id.clone(),
+            contact_name: contact.name.clone(),
+            priority: contact.priority,
+            status,
+            attempts,
+            completed_at: chrono::Utc::now().timestamp(),
+        }
+    }
+
+    /// Delivery outcomes recorded for an event
+    pub async fn deliveries_for(&self, event_id: &str) -> Vec<DeliveryRecord> {
+        self.deliveries.read().await.get(event_id).cloned().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::emergency_service::{EmergencyEventType, EmergencyPriority, EmergencyStatus};
+    use std::sync::Mutex;
+
+    /// Records notification order and fails each contact a configured number of times
+    #[derive(Default)]
+    struct MockNotifier {
+        calls: Mutex<Vec<String>>,
+        failures: Mutex<HashMap<String, u32>>,
+    }
+
+    #[async_trait]
+    impl Notifier for MockNotifier {
+        async fn notify(&self, contact: &EmergencyContact, _alert: &EmergencyEvent) -> Result<()> {
+            self.calls.lock().unwrap().push(contact.name.clone());
+            let mut failures = self.failures.lock().unwrap();
+            match failures.get_mut(&contact.name) {
+                Some(remaining) if *remaining > 0 => {
+                    *remaining -= 1;
+                    Err(anyhow::anyhow!("{} unreachable", contact.name))
+                }
+                _ => Ok(()),
+            }
+        }
+    }
+
+    fn contact(name: &str, priority: u8) -> EmergencyContact {
+        EmergencyContact {
+            name: name.to_string(),
+            role: "Emergency Response".to_string(),
+            email: format!("{}@rtf.finance", name),
+            phone: "+1-555-EMERGENCY".to_string(),
+            telegram: None,
+            priority,
+            available_24_7: true,
+        }
+    }
+
+    fn critical_alert() -> EmergencyEvent {
+        EmergencyEvent {
+            event_id: "emergency_test".to_string(),
+            event_type: EmergencyEventType::SecurityBreach,
+            severity: EmergencyPriority::Critical,
+            component: "vault".to_string(),
+            description: "Bridge exploit".to_string(),
+            triggered_by: "system_monitor".to_string(),
+            timestamp: 0,
+            actions_taken: Vec::new(),
+            status: EmergencyStatus::Active,
+            resolution_time: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_critical_alert_notifies_priority_one_first_and_records_outcomes() {
+        let notifier = Arc::new(MockNotifier::default());
+        notifier.failures.lock().unwrap().extend([
+            ("cto".to_string(), 1),           // recovers on retry
+            ("auditor".to_string(), u32::MAX), // low priority, not retried
+        ]);
+        let dispatcher = NotificationDispatcher::new(notifier.clone())
+            .with_retry_policy(2, Duration::from_millis(1));
+
+        let contacts = vec![
+            contact("auditor", 5),
+            contact("ops_lead", 2),
+            contact("ciso", 1),
+            contact("cto", 1),
+        ];
+        let records = dispatcher.dispatch(&contacts, &critical_alert()).await;
+
+        assert_eq!(*notifier.calls.lock().unwrap(), vec!["ciso", "cto", "cto", "ops_lead", "auditor"]);
+
+        let outcome = |name: &str| records.iter().find(|r| r.contact_name == name).unwrap().clone();
+        assert_eq!(outcome("ciso").status, DeliveryStatus::Delivered);
+        assert_eq!((outcome("cto").status, outcome("cto").attempts), (DeliveryStatus::Delivered, 2));
+        assert_eq!(outcome("auditor").attempts, 1);
+        assert!(matches!(outcome("auditor").status, DeliveryStatus::Failed { .. }));
+
+        assert_eq!(dispatcher.deliveries_for("emergency_test").await, records);
+    }
+}
@@ -0,0 +1,86 @@
+use anyhow::{anyhow, Result};
+use domain_tags::DomainTag;
+use rtf_governance::{EmergencyAuthorization, SignerApproval};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Timelocked suicide lock
+/// PRD: "Emergency pause with 7-day notice (suicide lock)"
+/// Arming records when the lock becomes eligible to execute; execution additionally
+/// needs approvals from the emergency signer set, so a single rushed caller cannot
+/// trigger the irreversible shutdown. Cancelling needs the same quorum over a separate
+/// message, so nobody can quietly disarm a lock the signers intend to execute.
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SuicideLock {
+    pub lock_id: String,
+    pub reason: String,
+    pub initiated_by: String,
+    pub armed_at: i64,
+    /// Earliest timestamp `execute_suicide_lock` will proceed
+    pub unlock_eligible_at: i64,
+    pub executed_at: Option<i64>,
+}
+
+/// Signers allowed to approve suicide lock execution and cancellation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SuicideLockSigners {
+    pub signers: Vec<[u8; 32]>,
+    /// Fraction of `signers` whose approval is required
+    pub threshold: f64,
+}
+
+impl SuicideLockSigners {
+    pub fn required_approvals(&self) -> Result<usize> {
+        if self.signers.is_empty() {
+            return Err(anyhow!("No suicide lock signers configured"));
+        }
+        let required = (self.threshold * self.signers.len() as f64).ceil() as usize;
+        Ok(required.clamp(1, self.signers.len()))
+    }
+
+    /// Count distinct configured signers that approved `message`; any invalid approval is rejected
+    pub fn count_approvals(&self, message: &[u8; 32], authorization: &EmergencyAuthorization) -> Result<usize> {
+        let mut approved = HashSet::new();
+        for approval in &authorization.approvals {
+            self.verify(message, approval)?;
+            approved.insert(approval.signer);
+        }
+        Ok(approved.len())
+    }
+
+    fn verify(&self, message: &[u8; 32], approval: &SignerApproval) -> Result<()> {
+        use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+        if !self.signers.contains(&approval.signer) {
+            return Err(anyhow!("Approval from unknown suicide lock signer"));
+        }
+
+        let key = VerifyingKey::from_bytes(&approval.signer)
+            .map_err(|e| anyhow!("Invalid suicide lock signer key: {}", e))?;
+        let signature = Signature::from_slice(&approval.signature)
+            .map_err(|e| anyhow!("Malformed suicide lock signature: {}", e))?;
+        key.verify(message, &signature)
+            .map_err(|_| anyhow!("Suicide lock signature verification failed"))
+    }
+}
+
+/// Message signers approve to execute a specific armed lock
+pub fn suicide_lock_message(lock: &SuicideLock) -> [u8; 32] {
+    suicide_lock_digest(DomainTag::SuicideLockExecution, lock)
+}
+
+/// Message signers approve to cancel a specific armed lock
+pub fn suicide_lock_cancellation_message(lock: &SuicideLock) -> [u8; 32] {
+    suicide_lock_digest(DomainTag::SuicideLockCancellation, lock)
+}
+
+fn suicide_lock_digest(tag: DomainTag, lock: &SuicideLock) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    hasher.update(tag.as_bytes());
+    hasher.update(lock.lock_id.as_bytes());
+    hasher.update(lock.unlock_eligible_at.to_le_bytes());
+    hasher.update(lock.reason.as_bytes());
+    hasher.finalize().into()
+}
@@ -0,0 +1,63 @@
+//! Score commitments backing ESG zk attestations
+//!
+//! The commitment binds `(entity_id, score, timestamp, nonce)`. The nonce stays
+//! with the issuing system, so verifiers only learn whether the committed score
+//! clears the compliance threshold, not the score itself.
+
+use chrono::{DateTime, Utc};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use domain_tags::DomainTag;
+
+/// Public part of an attestation, attached to compliance records
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZkAttestation {
+    pub commitment: String,
+    pub meets_threshold: bool,
+    pub threshold: f64,
+    pub issued_at: DateTime<Utc>,
+}
+
+/// Private opening of a commitment, retained by the issuer
+#[derive(Debug, Clone)]
+pub(crate) struct AttestationOpening {
+    pub entity_id: String,
+    pub score: f64,
+    pub timestamp: i64,
+    pub nonce: [u8; 32],
+}
+
+impl AttestationOpening {
+    pub fn new(entity_id: &str, score: f64, issued_at: DateTime<Utc>) -> Self {
+        let mut nonce = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        Self {
+            entity_id: entity_id.to_string(),
+            score,
+            timestamp: issued_at.timestamp_millis(),
+            nonce,
+        }
+    }
+
+    pub fn commitment(&self) -> String {
+        commit_score(&self.entity_id, self.score, self.timestamp, &self.nonce)
+    }
+
+    /// Whether `entity_id` and `score` re-derive the commitment under this opening
+    pub fn opens_to(&self, entity_id: &str, score: f64, commitment: &str) -> bool {
+        commit_score(entity_id, score, self.timestamp, &self.nonce) == commitment
+    }
+}
+
+/// Hex sha256 commitment over the exact score bits and a length-prefixed entity id
+pub(crate) fn commit_score(entity_id: &str, score: f64, timestamp: i64, nonce: &[u8; 32]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(DomainTag::EsgAttestation.as_bytes());
+    hasher.update((entity_id.len() as u64).to_be_bytes());
+    hasher.update(entity_id.as_bytes());
+    hasher.update(score.to_bits().to_be_bytes());
+    hasher.update(timestamp.to_be_bytes());
+    hasher.update(nonce);
+    hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect()
+}
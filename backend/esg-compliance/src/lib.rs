@@ -4,10 +4,21 @@
 //! zero-knowledge attestations, and multi-jurisdictional support.
 
 pub mod zk_esg_system;
+pub mod data_provider;
+pub mod sanctions;
+pub mod attestation;
+
+pub use data_provider::{EsgDataProvider, StaticEsgProvider};
+pub use sanctions::{SanctionsScreener, ScreenResult, StaticSanctionsList};
+pub use attestation::ZkAttestation;
+
+use attestation::AttestationOpening;
 
 use anyhow::Result;
+use audit_log::{AuditEvent, AuditLog};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{info, warn, error};
 use chrono::{DateTime, Utc};
@@ -15,10 +26,14 @@ use chrono::{DateTime, Utc};
 /// ESG Compliance System coordinator
 #[derive(Debug)]
 pub struct ESGComplianceSystem {
-    esg_tracker: zk_esg_system::ZkESGSystem,
     config: ESGConfig,
     metrics: RwLock<ESGMetrics>,
     compliance_cache: RwLock<HashMap<String, ComplianceRecord>>,
+    data_provider: Arc<dyn EsgDataProvider>,
+    sanctions_screener: Arc<dyn SanctionsScreener>,
+    sanctions_cache: RwLock<HashMap<(String, String), ScreenResult>>,
+    attestation_openings: RwLock<HashMap<String, AttestationOpening>>,
+    audit_log: Option<Arc<AuditLog>>,
 }
 
 /// Configuration for ESG compliance
@@ -30,6 +45,44 @@ pub struct ESGConfig {
     pub zk_attestations_enabled: bool,
     pub compliance_check_interval_hours: u64,
     pub carbon_offset_threshold: f64,
+    pub category_weights: ESGCategoryWeights,
+    pub sanctions_cache_ttl_minutes: i64,
+    pub attestation_validity_hours: i64,
+}
+
+/// Relative weight of each category in the overall compliance score (must sum to 1.0)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ESGCategoryWeights {
+    pub environmental: f64,
+    pub social: f64,
+    pub governance: f64,
+    pub jurisdictional: f64,
+}
+
+impl Default for ESGCategoryWeights {
+    fn default() -> Self {
+        Self {
+            environmental: 0.4,
+            social: 0.3,
+            governance: 0.2,
+            jurisdictional: 0.1,
+        }
+    }
+}
+
+impl ESGCategoryWeights {
+    /// Ensure weights are non-negative and sum to 1.0
+    pub fn validate(&self) -> Result<()> {
+        let weights = [self.environmental, self.social, self.governance, self.jurisdictional];
+        if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+            return Err(anyhow::anyhow!("ESG category weights must be non-negative"));
+        }
+        let total: f64 = weights.iter().sum();
+        if (total - 1.0).abs() > 1e-6 {
+            return Err(anyhow::anyhow!("ESG category weights must sum to 1.0, got {:.4}", total));
+        }
+        Ok(())
+    }
 }
 
 impl Default for ESGConfig {
@@ -41,6 +94,9 @@ impl Default for ESGConfig {
             zk_attestations_enabled: true,
             compliance_check_interval_hours: 24,
             carbon_offset_threshold: 0.95, // 95% offset requirement
+            category_weights: ESGCategoryWeights::default(),
+            sanctions_cache_ttl_minutes: 60,
+            attestation_validity_hours: 24 * 7,
         }
     }
 }
@@ -148,6 +204,18 @@ pub struct JurisdictionalCompliance {
     pub sanctions_screening_passed: bool,
 }
 
+impl JurisdictionalCompliance {
+    /// Whether the scheduled review date has passed
+    pub fn review_overdue(&self, now: DateTime<Utc>) -> bool {
+        now > self.next_review_date
+    }
+
+    /// Whole days until the next review (negative when overdue)
+    pub fn days_until_review(&self, now: DateTime<Utc>) -> i64 {
+        (self.next_review_date - now).num_days()
+    }
+}
+
 /// Compliance status
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ComplianceStatus {
@@ -166,24 +234,48 @@ pub struct ComplianceRecord {
     pub overall_score: f64,
     pub compliance_status: ComplianceStatus,
     pub last_updated: DateTime<Utc>,
-    pub zk_attestation_hash: Option<String>,
+    pub zk_attestation: Option<ZkAttestation>,
 }
 
 impl ESGComplianceSystem {
-    /// Create a new ESG compliance system
+    /// Create a new ESG compliance system backed by static ESG data
     pub async fn new(config: ESGConfig) -> Result<Self> {
+        Self::new_with_provider(config, Arc::new(StaticEsgProvider::default())).await
+    }
+
+    /// Create a new ESG compliance system collecting data from `data_provider`
+    pub async fn new_with_provider(
+        config: ESGConfig,
+        data_provider: Arc<dyn EsgDataProvider>,
+    ) -> Result<Self> {
         info!("Initializing RTF ESG Compliance System");
-        
-        let esg_tracker = zk_esg_system::ZkESGSystem::new(&config).await?;
-        
+
+        config.category_weights.validate()?;
+
         Ok(Self {
-            esg_tracker,
             config,
             metrics: RwLock::new(ESGMetrics::default()),
             compliance_cache: RwLock::new(HashMap::new()),
+            data_provider,
+            sanctions_screener: Arc::new(StaticSanctionsList::default()),
+            sanctions_cache: RwLock::new(HashMap::new()),
+            attestation_openings: RwLock::new(HashMap::new()),
+            audit_log: None,
         })
     }
 
+    /// Screen entities with `screener` instead of the empty default list
+    pub fn with_sanctions_screener(mut self, screener: Arc<dyn SanctionsScreener>) -> Self {
+        self.sanctions_screener = screener;
+        self
+    }
+
+    /// Record compliance check outcomes in `audit_log`
+    pub fn with_audit_log(mut self, audit_log: Arc<AuditLog>) -> Self {
+        self.audit_log = Some(audit_log);
+        self
+    }
+
     /// Perform comprehensive ESG compliance check
     pub async fn perform_compliance_check(&self, entity_id: &str) -> Result<ComplianceRecord> {
         info!("Performing ESG compliance check for entity: {}", entity_id);
@@ -195,10 +287,27 @@ impl ESGComplianceSystem {
         }
 
         // Gather ESG data
-        let environmental_data = self.collect_environmental_data(entity_id).await?;
+        let mut environmental_data = self.collect_environmental_data(entity_id).await?;
         let social_data = self.collect_social_data(entity_id).await?;
         let governance_data = self.collect_governance_data(entity_id).await?;
 
+        // Recompute net emissions from scopes and enforce the offset threshold
+        let mut violations = Vec::new();
+        if let ESGCategory::Environmental { carbon_tracking, .. } = &mut environmental_data {
+            carbon_tracking.net_emissions = gross_emissions(carbon_tracking) - carbon_tracking.carbon_offsets;
+
+            if self.config.carbon_tracking_enabled {
+                if let Some(violation) = check_carbon_offsets(carbon_tracking, self.config.carbon_offset_threshold) {
+                    warn!("Carbon offset violation for entity {}: {}", entity_id, violation);
+                    violations.push(violation);
+                }
+
+                let mut metrics = self.metrics.write().await;
+                metrics.carbon_emissions_tracked += gross_emissions(carbon_tracking);
+                metrics.carbon_offsets_verified += carbon_tracking.carbon_offsets;
+            }
+        }
+
         // Perform jurisdictional compliance checks
         let jurisdictional_compliance = self.check_jurisdictional_compliance(entity_id).await?;
 
@@ -211,26 +320,22 @@ impl ESGComplianceSystem {
         ).await?;
 
         // Generate zero-knowledge attestation if enabled
-        let zk_attestation_hash = if self.config.zk_attestations_enabled {
+        let zk_attestation = if self.config.zk_attestations_enabled {
             Some(self.generate_zk_attestation(entity_id, overall_score).await?)
         } else {
             None
         };
 
+        let compliance_status = determine_compliance_status(overall_score, violations, &jurisdictional_compliance);
+
         let compliance_record = ComplianceRecord {
             entity_id: entity_id.to_string(),
             esg_categories: vec![environmental_data, social_data, governance_data],
             jurisdictional_compliance,
             overall_score,
-            compliance_status: if overall_score >= 0.7 {
-                ComplianceStatus::Compliant
-            } else {
-                ComplianceStatus::NonCompliant {
-                    violations: vec!["ESG score below threshold".to_string()],
-                }
-            },
+            compliance_status,
             last_updated: Utc::now(),
-            zk_attestation_hash,
+            zk_attestation,
         };
 
         // Cache the result
@@ -248,6 +353,14 @@ impl ESGComplianceSystem {
             }
         }
 
+        if let Some(audit_log) = &self.audit_log {
+            audit_log.append(AuditEvent::new("esg", "compliance_check", serde_json::json!({
+                "entity_id": entity_id,
+                "overall_score": overall_score,
+                "compliance_status": format!("{:?}", compliance_record.compliance_status),
+            }))).await;
+        }
+
         info!("ESG compliance check completed for entity: {} (score: {:.2})", 
               entity_id, overall_score);
         
@@ -260,13 +373,22 @@ impl ESGComplianceSystem {
         cache.get(entity_id).cloned()
     }
 
-    /// Verify zero-knowledge attestation
+    /// Verify that an attestation commits to exactly `entity_id` and `score`
+    ///
+    /// Returns false for unknown commitments or any field mismatch.
     pub async fn verify_zk_attestation(
         &self,
         entity_id: &str,
+        score: f64,
         attestation_hash: &str,
     ) -> Result<bool> {
-        self.esg_tracker.verify_attestation(entity_id, attestation_hash).await
+        let cutoff = self.attestation_cutoff(Utc::now());
+        let openings = self.attestation_openings.read().await;
+        Ok(openings
+            .get(attestation_hash)
+            .filter(|opening| opening.timestamp >= cutoff)
+            .map(|opening| opening.opens_to(entity_id, score, attestation_hash))
+            .unwrap_or(false))
     }
 
     /// Get ESG metrics
@@ -276,126 +398,275 @@ impl ESGComplianceSystem {
 
     /// Collect environmental data
     async fn collect_environmental_data(&self, entity_id: &str) -> Result<ESGCategory> {
-        // Simulate environmental data collection
-        let carbon_tracking = CarbonTracking {
-            scope_1_emissions: 1000.0,
-            scope_2_emissions: 500.0,
-            scope_3_emissions: 2000.0,
-            carbon_offsets: 3300.0,
-            net_emissions: 200.0,
-            verification_timestamp: Utc::now(),
-        };
-
-        let sustainability_metrics = SustainabilityMetrics {
-            water_usage: 10000.0,
-            waste_management_score: 0.85,
-            renewable_energy_percentage: 0.75,
-            biodiversity_impact_score: 0.9,
-        };
+        let data = self.data_provider.environmental_data(entity_id).await?;
 
         Ok(ESGCategory::Environmental {
-            carbon_tracking,
-            sustainability_metrics,
+            carbon_tracking: data.carbon_tracking,
+            sustainability_metrics: data.sustainability_metrics,
         })
     }
 
     /// Collect social data
-    async fn collect_social_data(&self, _entity_id: &str) -> Result<ESGCategory> {
-        let labor_practices = LaborPractices {
-            fair_wages_compliance: true,
-            working_conditions_score: 0.9,
-            diversity_index: 0.8,
-            safety_record_score: 0.95,
-        };
-
-        let community_impact = CommunityImpact {
-            local_investment: 1000000.0,
-            community_programs: 15,
-            stakeholder_engagement_score: 0.85,
-        };
-
-        let human_rights = HumanRights {
-            compliance_score: 0.95,
-            violations_reported: 0,
-            remediation_actions: 0,
-        };
+    async fn collect_social_data(&self, entity_id: &str) -> Result<ESGCategory> {
+        let data = self.data_provider.social_data(entity_id).await?;
 
         Ok(ESGCategory::Social {
-            labor_practices,
-            community_impact,
-            human_rights,
+            labor_practices: data.labor_practices,
+            community_impact: data.community_impact,
+            human_rights: data.human_rights,
         })
     }
 
     /// Collect governance data
-    async fn collect_governance_data(&self, _entity_id: &str) -> Result<ESGCategory> {
-        let board_composition = BoardComposition {
-            independence_ratio: 0.8,
-            diversity_score: 0.7,
-            expertise_coverage: 0.9,
-        };
-
-        let ethics_compliance = EthicsCompliance {
-            code_of_conduct_score: 0.95,
-            whistleblower_protections: true,
-            conflict_of_interest_management: 0.9,
-        };
+    async fn collect_governance_data(&self, entity_id: &str) -> Result<ESGCategory> {
+        let data = self.data_provider.governance_data(entity_id).await?;
 
         Ok(ESGCategory::Governance {
-            board_composition,
-            transparency_score: 0.85,
-            ethics_compliance,
+            board_composition: data.board_composition,
+            transparency_score: data.transparency_score,
+            ethics_compliance: data.ethics_compliance,
         })
     }
 
-    /// Check jurisdictional compliance
-    async fn check_jurisdictional_compliance(&self, _entity_id: &str) -> Result<Vec<JurisdictionalCompliance>> {
-        Ok(vec![
-            JurisdictionalCompliance {
-                jurisdiction: "US".to_string(),
-                regulatory_framework: "SEC".to_string(),
-                compliance_status: ComplianceStatus::Compliant,
-                last_audit_date: Utc::now() - chrono::Duration::days(30),
-                next_review_date: Utc::now() + chrono::Duration::days(335),
-                sanctions_screening_passed: true,
-            },
-            JurisdictionalCompliance {
-                jurisdiction: "EU".to_string(),
-                regulatory_framework: "MiCA".to_string(),
-                compliance_status: ComplianceStatus::Compliant,
-                last_audit_date: Utc::now() - chrono::Duration::days(45),
-                next_review_date: Utc::now() + chrono::Duration::days(320),
-                sanctions_screening_passed: true,
-            },
-        ])
+    /// Check jurisdictional compliance, placing jurisdictions with past-due reviews under review
+    async fn check_jurisdictional_compliance(&self, entity_id: &str) -> Result<Vec<JurisdictionalCompliance>> {
+        let mut jurisdictions = self.data_provider.jurisdictional_data(entity_id).await?;
+        let now = Utc::now();
+
+        for jurisdiction in &mut jurisdictions {
+            let screening = self.screen_entity(entity_id, &jurisdiction.jurisdiction).await?;
+            jurisdiction.sanctions_screening_passed = screening.passed;
+            if !screening.passed {
+                warn!(
+                    "Entity {} failed sanctions screening in {} ({:?})",
+                    entity_id, jurisdiction.jurisdiction, screening.matched_list
+                );
+                jurisdiction.compliance_status = ComplianceStatus::NonCompliant {
+                    violations: vec!["Sanctions screening failed".to_string()],
+                };
+                continue;
+            }
+
+            if jurisdiction.review_overdue(now)
+                && matches!(jurisdiction.compliance_status, ComplianceStatus::Compliant | ComplianceStatus::Exempt)
+            {
+                warn!(
+                    "Audit for entity {} in {} is stale (review due {})",
+                    entity_id, jurisdiction.jurisdiction, jurisdiction.next_review_date
+                );
+                jurisdiction.compliance_status = ComplianceStatus::UnderReview;
+            }
+        }
+
+        {
+            let mut metrics = self.metrics.write().await;
+            metrics.jurisdictions_monitored = jurisdictions.len() as u64;
+        }
+
+        Ok(jurisdictions)
+    }
+
+    /// Screen an entity, reusing results younger than the configured TTL
+    async fn screen_entity(&self, entity_id: &str, jurisdiction: &str) -> Result<ScreenResult> {
+        let key = (entity_id.to_string(), jurisdiction.to_string());
+        let ttl = chrono::Duration::minutes(self.config.sanctions_cache_ttl_minutes);
+
+        if let Some(cached) = self.sanctions_cache.read().await.get(&key) {
+            if Utc::now() - cached.screened_at < ttl {
+                return Ok(cached.clone());
+            }
+        }
+
+        let result = self.sanctions_screener.screen(entity_id, jurisdiction).await?;
+        self.sanctions_cache.write().await.insert(key, result.clone());
+        Ok(result)
+    }
+
+    /// Days until an entity's next review in a jurisdiction (negative when overdue)
+    pub async fn days_until_review(&self, entity_id: &str, jurisdiction: &str) -> Option<i64> {
+        let cache = self.compliance_cache.read().await;
+        cache.get(entity_id)?
+            .jurisdictional_compliance.iter()
+            .find(|j| j.jurisdiction == jurisdiction)
+            .map(|j| j.days_until_review(Utc::now()))
     }
 
     /// Calculate overall compliance score
     async fn calculate_compliance_score(
         &self,
-        _environmental: &ESGCategory,
-        _social: &ESGCategory,
-        _governance: &ESGCategory,
+        environmental: &ESGCategory,
+        social: &ESGCategory,
+        governance: &ESGCategory,
         jurisdictional: &[JurisdictionalCompliance],
     ) -> Result<f64> {
-        // Simplified scoring algorithm
-        let jurisdictional_score = jurisdictional.iter()
-            .map(|j| match j.compliance_status {
-                ComplianceStatus::Compliant => 1.0,
-                _ => 0.0,
-            })
-            .sum::<f64>() / jurisdictional.len() as f64;
-
-        // Weighted average: 40% environmental, 30% social, 20% governance, 10% jurisdictional
-        let overall_score = 0.4 * 0.85 + 0.3 * 0.9 + 0.2 * 0.88 + 0.1 * jurisdictional_score;
-        
-        Ok(overall_score)
+        let weights = &self.config.category_weights;
+
+        let overall_score = weights.environmental * environmental_score(environmental)
+            + weights.social * social_score(social)
+            + weights.governance * governance_score(governance)
+            + weights.jurisdictional * jurisdictional_score(jurisdictional);
+
+        Ok(overall_score.clamp(0.0, 1.0))
+    }
+
+    /// Oldest issue time, in Unix milliseconds, at which an attestation is still valid
+    fn attestation_cutoff(&self, now: DateTime<Utc>) -> i64 {
+        (now - chrono::Duration::hours(self.config.attestation_validity_hours)).timestamp_millis()
+    }
+
+    /// Commit to the score, publishing only whether it clears the compliance threshold
+    async fn generate_zk_attestation(&self, entity_id: &str, score: f64) -> Result<ZkAttestation> {
+        let issued_at = Utc::now();
+        let opening = AttestationOpening::new(entity_id, score, issued_at);
+        let commitment = opening.commitment();
+
+        // A new attestation supersedes the entity's previous one; expired openings go with it
+        let cutoff = self.attestation_cutoff(issued_at);
+        {
+            let mut openings = self.attestation_openings.write().await;
+            openings.retain(|_, existing| existing.entity_id != entity_id && existing.timestamp >= cutoff);
+            openings.insert(commitment.clone(), opening);
+        }
+
+        Ok(ZkAttestation {
+            commitment,
+            meets_threshold: score >= MIN_COMPLIANCE_SCORE,
+            threshold: MIN_COMPLIANCE_SCORE,
+            issued_at,
+        })
+    }
+}
+
+/// Total gross emissions across all scopes
+fn gross_emissions(carbon: &CarbonTracking) -> f64 {
+    carbon.scope_1_emissions + carbon.scope_2_emissions + carbon.scope_3_emissions
+}
+
+/// Share of gross emissions covered by offsets, capped at 1.0
+fn carbon_offset_ratio(carbon: &CarbonTracking) -> f64 {
+    let gross = gross_emissions(carbon);
+    if gross <= 0.0 {
+        return 1.0;
+    }
+    (carbon.carbon_offsets / gross).clamp(0.0, 1.0)
+}
+
+/// Violation message when offsets cover less than `threshold` of gross emissions
+fn check_carbon_offsets(carbon: &CarbonTracking, threshold: f64) -> Option<String> {
+    let ratio = carbon_offset_ratio(carbon);
+    if ratio < threshold {
+        Some(format!(
+            "Carbon offset ratio {:.2}% below required {:.2}%",
+            ratio * 100.0,
+            threshold * 100.0
+        ))
+    } else {
+        None
+    }
+}
+
+/// Minimum overall ESG score required for compliance
+const MIN_COMPLIANCE_SCORE: f64 = 0.7;
+
+/// Overall status from the score and any hard violations (carbon offsets, sanctions),
+/// which fail compliance regardless of score
+///
+/// An otherwise compliant entity is downgraded to `UnderReview` while any
+/// jurisdiction's audit is stale.
+fn determine_compliance_status(
+    overall_score: f64,
+    mut violations: Vec<String>,
+    jurisdictional: &[JurisdictionalCompliance],
+) -> ComplianceStatus {
+    if overall_score < MIN_COMPLIANCE_SCORE {
+        violations.push("ESG score below threshold".to_string());
+    }
+    for jurisdiction in jurisdictional.iter().filter(|j| !j.sanctions_screening_passed) {
+        violations.push(format!("Sanctions screening failed in {}", jurisdiction.jurisdiction));
+    }
+    if !violations.is_empty() {
+        return ComplianceStatus::NonCompliant { violations };
     }
+    if jurisdictional.iter().any(|j| matches!(j.compliance_status, ComplianceStatus::UnderReview)) {
+        return ComplianceStatus::UnderReview;
+    }
+    ComplianceStatus::Compliant
+}
 
-    /// Generate zero-knowledge attestation
-    async fn generate_zk_attestation(&self, entity_id: &str, score: f64) -> Result<String> {
-        self.esg_tracker.generate_attestation(entity_id, score).await
+fn average(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
     }
+    values.iter().map(|v| v.clamp(0.0, 1.0)).sum::<f64>() / values.len() as f64
+}
+
+fn bool_score(value: bool) -> f64 {
+    if value { 1.0 } else { 0.0 }
+}
+
+/// Environmental sub-score: carbon offset coverage and sustainability practices
+fn environmental_score(category: &ESGCategory) -> f64 {
+    match category {
+        ESGCategory::Environmental { carbon_tracking, sustainability_metrics } => {
+            let sustainability = average(&[
+                sustainability_metrics.waste_management_score,
+                sustainability_metrics.renewable_energy_percentage,
+                sustainability_metrics.biodiversity_impact_score,
+            ]);
+            0.5 * carbon_offset_ratio(carbon_tracking) + 0.5 * sustainability
+        }
+        _ => 0.0,
+    }
+}
+
+/// Social sub-score: labor practices, community impact and human rights record
+fn social_score(category: &ESGCategory) -> f64 {
+    match category {
+        ESGCategory::Social { labor_practices, community_impact, human_rights } => {
+            let labor = average(&[
+                bool_score(labor_practices.fair_wages_compliance),
+                labor_practices.working_conditions_score,
+                labor_practices.diversity_index,
+                labor_practices.safety_record_score,
+            ]);
+            // Each unremediated violation costs 5% of the human rights score
+            let unremediated = human_rights.violations_reported
+                .saturating_sub(human_rights.remediation_actions);
+            let rights = (human_rights.compliance_score - 0.05 * unremediated as f64).clamp(0.0, 1.0);
+            average(&[labor, community_impact.stakeholder_engagement_score, rights])
+        }
+        _ => 0.0,
+    }
+}
+
+/// Governance sub-score: board composition, transparency and ethics
+fn governance_score(category: &ESGCategory) -> f64 {
+    match category {
+        ESGCategory::Governance { board_composition, transparency_score, ethics_compliance } => {
+            let board = average(&[
+                board_composition.independence_ratio,
+                board_composition.diversity_score,
+                board_composition.expertise_coverage,
+            ]);
+            let ethics = average(&[
+                ethics_compliance.code_of_conduct_score,
+                bool_score(ethics_compliance.whistleblower_protections),
+                ethics_compliance.conflict_of_interest_management,
+            ]);
+            average(&[board, *transparency_score, ethics])
+        }
+        _ => 0.0,
+    }
+}
+
+/// Share of jurisdictions in which the entity is compliant
+fn jurisdictional_score(jurisdictional: &[JurisdictionalCompliance]) -> f64 {
+    if jurisdictional.is_empty() {
+        return 1.0;
+    }
+    jurisdictional.iter()
+        .filter(|j| matches!(j.compliance_status, ComplianceStatus::Compliant | ComplianceStatus::Exempt))
+        .count() as f64 / jurisdictional.len() as f64
 }
 
 #[cfg(test)]
@@ -421,4 +692,292 @@ mod tests {
         assert_eq!(record.entity_id, "test_entity");
         assert!(record.overall_score > 0.0);
     }
+
+    fn provider_with_offset_ratio(ratio: f64) -> StaticEsgProvider {
+        let mut provider = fully_offset_provider(90);
+        let carbon_tracking = &mut provider.environmental.carbon_tracking;
+        carbon_tracking.carbon_offsets = 3500.0 * ratio;
+        carbon_tracking.net_emissions = 0.0; // Deliberately wrong; recomputed from scopes
+        provider
+    }
+
+    fn net_emissions(record: &ComplianceRecord) -> f64 {
+        record.esg_categories.iter().find_map(|category| match category {
+            ESGCategory::Environmental { carbon_tracking, .. } => Some(carbon_tracking.net_emissions),
+            _ => None,
+        }).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_offset_ratio_below_threshold_is_non_compliant() {
+        let esg_system = ESGComplianceSystem::new_with_provider(
+            ESGConfig::default(),
+            Arc::new(provider_with_offset_ratio(0.90)),
+        ).await.unwrap();
+
+        let record = esg_system.perform_compliance_check("under_offset_fund").await.unwrap();
+        assert!((net_emissions(&record) - 350.0).abs() < 1e-9);
+        // The score clears the threshold, so the offset shortfall is the only violation
+        assert!(record.overall_score >= MIN_COMPLIANCE_SCORE);
+        match &record.compliance_status {
+            ComplianceStatus::NonCompliant { violations } => {
+                assert_eq!(violations.len(), 1);
+                assert!(violations[0].starts_with("Carbon offset ratio"));
+            }
+            status => panic!("expected non-compliant, got {:?}", status),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_offset_ratio_above_threshold_is_compliant() {
+        let esg_system = ESGComplianceSystem::new_with_provider(
+            ESGConfig::default(),
+            Arc::new(provider_with_offset_ratio(0.97)),
+        ).await.unwrap();
+
+        let record = esg_system.perform_compliance_check("offset_fund").await.unwrap();
+        assert!((net_emissions(&record) - 105.0).abs() < 1e-9);
+        assert!(matches!(record.compliance_status, ComplianceStatus::Compliant));
+    }
+
+    #[tokio::test]
+    async fn test_offset_threshold_skipped_when_carbon_tracking_disabled() {
+        let config = ESGConfig { carbon_tracking_enabled: false, ..ESGConfig::default() };
+        let esg_system = ESGComplianceSystem::new_with_provider(config, Arc::new(provider_with_offset_ratio(0.90)))
+            .await.unwrap();
+
+        let record = esg_system.perform_compliance_check("untracked_fund").await.unwrap();
+        assert!(matches!(record.compliance_status, ComplianceStatus::Compliant));
+    }
+
+    #[tokio::test]
+    async fn test_compliance_record_reflects_provider_values() {
+        let mut provider = StaticEsgProvider::default();
+        provider.environmental.carbon_tracking.carbon_offsets = 4000.0;
+        provider.social.labor_practices.diversity_index = 0.42;
+        provider.governance.transparency_score = 0.61;
+
+        let esg_system = ESGComplianceSystem::new_with_provider(ESGConfig::default(), Arc::new(provider))
+            .await.unwrap();
+        let record = esg_system.perform_compliance_check("provider_entity").await.unwrap();
+
+        let mut seen = 0;
+        for category in &record.esg_categories {
+            match category {
+                ESGCategory::Environmental { carbon_tracking, .. } => {
+                    assert_eq!(carbon_tracking.carbon_offsets, 4000.0);
+                    assert_eq!(carbon_tracking.net_emissions, -500.0); // 3500 gross - 4000 offsets
+                    seen += 1;
+                }
+                ESGCategory::Social { labor_practices, .. } => {
+                    assert_eq!(labor_practices.diversity_index, 0.42);
+                    seen += 1;
+                }
+                ESGCategory::Governance { transparency_score, .. } => {
+                    assert_eq!(*transparency_score, 0.61);
+                    seen += 1;
+                }
+            }
+        }
+        assert_eq!(seen, 3);
+    }
+
+    fn fully_offset_provider(next_review_in_days: i64) -> StaticEsgProvider {
+        let mut provider = StaticEsgProvider::default();
+        provider.environmental.carbon_tracking.carbon_offsets = 3500.0;
+        for jurisdiction in &mut provider.jurisdictions {
+            jurisdiction.next_review_date = Utc::now() + chrono::Duration::days(next_review_in_days);
+        }
+        provider
+    }
+
+    #[tokio::test]
+    async fn test_past_due_review_forces_under_review() {
+        let esg_system = ESGComplianceSystem::new_with_provider(
+            ESGConfig::default(),
+            Arc::new(fully_offset_provider(-10)),
+        ).await.unwrap();
+
+        let record = esg_system.perform_compliance_check("stale_entity").await.unwrap();
+        assert!(record.jurisdictional_compliance.iter()
+            .all(|j| matches!(j.compliance_status, ComplianceStatus::UnderReview)));
+        assert!(matches!(record.compliance_status, ComplianceStatus::UnderReview));
+
+        let days = esg_system.days_until_review("stale_entity", "US").await.unwrap();
+        assert!(days < 0);
+    }
+
+    #[tokio::test]
+    async fn test_future_review_remains_compliant() {
+        let esg_system = ESGComplianceSystem::new_with_provider(
+            ESGConfig::default(),
+            Arc::new(fully_offset_provider(90)),
+        ).await.unwrap();
+
+        let record = esg_system.perform_compliance_check("fresh_entity").await.unwrap();
+        assert!(record.jurisdictional_compliance.iter()
+            .all(|j| matches!(j.compliance_status, ComplianceStatus::Compliant)));
+        assert!(matches!(record.compliance_status, ComplianceStatus::Compliant));
+
+        let days = esg_system.days_until_review("fresh_entity", "EU").await.unwrap();
+        assert!((89..=90).contains(&days));
+        assert!(esg_system.days_until_review("fresh_entity", "SG").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_zk_attestation_binds_exact_score() {
+        let esg_system = ESGComplianceSystem::new(ESGConfig::default()).await.unwrap();
+        let record = esg_system.perform_compliance_check("attested_fund").await.unwrap();
+        let attestation = record.zk_attestation.clone().unwrap();
+
+        assert_eq!(attestation.meets_threshold, record.overall_score >= attestation.threshold);
+        assert!(esg_system
+            .verify_zk_attestation("attested_fund", record.overall_score, &attestation.commitment)
+            .await.unwrap());
+
+        // Any altered field breaks the commitment
+        assert!(!esg_system
+            .verify_zk_attestation("attested_fund", record.overall_score + 0.01, &attestation.commitment)
+            .await.unwrap());
+        assert!(!esg_system
+            .verify_zk_attestation("other_fund", record.overall_score, &attestation.commitment)
+            .await.unwrap());
+        assert!(!esg_system
+            .verify_zk_attestation("attested_fund", record.overall_score, "00")
+            .await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_zk_attestations_use_fresh_nonces() {
+        let esg_system = ESGComplianceSystem::new(ESGConfig::default()).await.unwrap();
+        let first = esg_system.perform_compliance_check("attested_fund").await.unwrap();
+        let second = esg_system.perform_compliance_check("attested_fund").await.unwrap();
+
+        assert_eq!(first.overall_score, second.overall_score);
+        assert_ne!(
+            first.zk_attestation.unwrap().commitment,
+            second.zk_attestation.unwrap().commitment
+        );
+    }
+
+    #[tokio::test]
+    async fn test_superseded_attestations_are_pruned() {
+        let esg_system = ESGComplianceSystem::new(ESGConfig::default()).await.unwrap();
+        let first = esg_system.perform_compliance_check("attested_fund").await.unwrap();
+        let second = esg_system.perform_compliance_check("attested_fund").await.unwrap();
+        let score = second.overall_score;
+
+        assert!(!esg_system
+            .verify_zk_attestation("attested_fund", score, &first.zk_attestation.unwrap().commitment)
+            .await.unwrap());
+        assert!(esg_system
+            .verify_zk_attestation("attested_fund", score, &second.zk_attestation.unwrap().commitment)
+            .await.unwrap());
+        assert_eq!(esg_system.attestation_openings.read().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_expired_attestations_are_pruned() {
+        let esg_system = ESGComplianceSystem::new(ESGConfig::default()).await.unwrap();
+        let stale = esg_system.perform_compliance_check("stale_fund").await.unwrap();
+        let stale_commitment = stale.zk_attestation.unwrap().commitment;
+
+        // Age the opening past the validity window
+        let validity_ms = esg_system.config.attestation_validity_hours * 60 * 60 * 1000;
+        esg_system.attestation_openings.write().await
+            .get_mut(&stale_commitment).unwrap()
+            .timestamp -= validity_ms + 1;
+
+        esg_system.perform_compliance_check("fresh_fund").await.unwrap();
+        let openings = esg_system.attestation_openings.read().await;
+        assert!(!openings.contains_key(&stale_commitment));
+        assert_eq!(openings.len(), 1);
+    }
+
+    #[derive(Debug, Default)]
+    struct CountingScreener {
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl SanctionsScreener for CountingScreener {
+        async fn screen(&self, _entity_id: &str, _jurisdiction: &str) -> Result<ScreenResult> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(ScreenResult::clear())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sanctioned_entity_non_compliant_despite_perfect_score() {
+        let mut provider = fully_offset_provider(90);
+        provider.environmental.sustainability_metrics.waste_management_score = 1.0;
+        provider.environmental.sustainability_metrics.renewable_energy_percentage = 1.0;
+        provider.environmental.sustainability_metrics.biodiversity_impact_score = 1.0;
+
+        let screener = StaticSanctionsList::new("OFAC-SDN", vec!["sanctioned_fund".to_string()]);
+        let esg_system = ESGComplianceSystem::new_with_provider(ESGConfig::default(), Arc::new(provider))
+            .await.unwrap()
+            .with_sanctions_screener(Arc::new(screener));
+
+        let record = esg_system.perform_compliance_check("sanctioned_fund").await.unwrap();
+        assert!(record.jurisdictional_compliance.iter().all(|j| !j.sanctions_screening_passed));
+        assert!(matches!(record.compliance_status, ComplianceStatus::NonCompliant { .. }));
+
+        let clean = esg_system.perform_compliance_check("clean_fund").await.unwrap();
+        assert!(matches!(clean.compliance_status, ComplianceStatus::Compliant));
+    }
+
+    #[tokio::test]
+    async fn test_sanctions_results_cached_within_ttl() {
+        let screener = Arc::new(CountingScreener::default());
+        let esg_system = ESGComplianceSystem::new_with_provider(
+            ESGConfig::default(),
+            Arc::new(fully_offset_provider(90)),
+        ).await.unwrap()
+            .with_sanctions_screener(screener.clone());
+
+        esg_system.perform_compliance_check("cached_entity").await.unwrap();
+        esg_system.perform_compliance_check("cached_entity").await.unwrap();
+
+        // One lookup per jurisdiction, reused on the second check
+        assert_eq!(screener.calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_category_weights_rejected() {
+        let mut config = ESGConfig::default();
+        config.category_weights.environmental = 0.6;
+        assert!(ESGComplianceSystem::new(config).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_score_reflects_carbon_offsets_and_diversity() {
+        let esg_system = ESGComplianceSystem::new(ESGConfig::default()).await.unwrap();
+        let environmental = esg_system.collect_environmental_data("entity").await.unwrap();
+        let social = esg_system.collect_social_data("entity").await.unwrap();
+        let governance = esg_system.collect_governance_data("entity").await.unwrap();
+        let jurisdictions = esg_system.check_jurisdictional_compliance("entity").await.unwrap();
+
+        let baseline = esg_system
+            .calculate_compliance_score(&environmental, &social, &governance, &jurisdictions)
+            .await.unwrap();
+
+        let mut fewer_offsets = environmental.clone();
+        if let ESGCategory::Environmental { carbon_tracking, .. } = &mut fewer_offsets {
+            carbon_tracking.carbon_offsets /= 2.0;
+        }
+        let offset_score = esg_system
+            .calculate_compliance_score(&fewer_offsets, &social, &governance, &jurisdictions)
+            .await.unwrap();
+        assert!(offset_score < baseline);
+
+        let mut more_diverse = social.clone();
+        if let ESGCategory::Social { labor_practices, .. } = &mut more_diverse {
+            labor_practices.diversity_index = 1.0;
+        }
+        let diversity_score = esg_system
+            .calculate_compliance_score(&environmental, &more_diverse, &governance, &jurisdictions)
+            .await.unwrap();
+        assert!(diversity_score > baseline);
+    }
 }
@@ -0,0 +1,68 @@
+//! Sanctions screening for jurisdictional compliance
+//!
+//! A failed screening fails compliance outright, independent of ESG scores.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Outcome of screening an entity against sanctions lists
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenResult {
+    pub passed: bool,
+    pub matched_list: Option<String>,
+    pub screened_at: DateTime<Utc>,
+}
+
+impl ScreenResult {
+    pub fn clear() -> Self {
+        Self {
+            passed: true,
+            matched_list: None,
+            screened_at: Utc::now(),
+        }
+    }
+
+    pub fn matched(list: &str) -> Self {
+        Self {
+            passed: false,
+            matched_list: Some(list.to_string()),
+            screened_at: Utc::now(),
+        }
+    }
+}
+
+/// Screens entities against sanctions lists for a jurisdiction
+#[async_trait]
+pub trait SanctionsScreener: std::fmt::Debug + Send + Sync {
+    async fn screen(&self, entity_id: &str, jurisdiction: &str) -> Result<ScreenResult>;
+}
+
+/// Screener backed by a fixed set of sanctioned entity ids
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StaticSanctionsList {
+    pub list_name: String,
+    pub sanctioned_entities: HashSet<String>,
+}
+
+impl StaticSanctionsList {
+    pub fn new(list_name: &str, sanctioned_entities: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            list_name: list_name.to_string(),
+            sanctioned_entities: sanctioned_entities.into_iter().collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl SanctionsScreener for StaticSanctionsList {
+    async fn screen(&self, entity_id: &str, _jurisdiction: &str) -> Result<ScreenResult> {
+        if self.sanctioned_entities.contains(entity_id) {
+            Ok(ScreenResult::matched(&self.list_name))
+        } else {
+            Ok(ScreenResult::clear())
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::fund_exposure_service::ExposureGraph;
+
+/// Point-in-time copies of the exposure graph and the changes between two of them
+/// Risk teams snapshot the graph each epoch and alert on the diff rather than re-reading
+/// the whole graph. Parallel edges between the same pair of funds are summed, so an edge
+/// is identified by its `(from_fund, to_fund)` pair.
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExposureSnapshot {
+    pub taken_at: i64,
+    /// fund -> total assets
+    pub funds: BTreeMap<String, u64>,
+    /// from fund -> to fund -> exposure amount
+    pub edges: BTreeMap<String, BTreeMap<String, u64>>,
+    /// HHI (0-10000) of exposure received per fund
+    pub concentration_hhi: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExposureEdgeSnapshot {
+    pub from_fund: String,
+    pub to_fund: String,
+    pub exposure_amount: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangedExposureEdge {
+    pub from_fund: String,
+    pub to_fund: String,
+    pub old_amount: u64,
+    pub new_amount: u64,
+}
+
+/// Changes from one snapshot to a later one, each list sorted by fund id
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExposureDiff {
+    pub added_funds: Vec<String>,
+    pub removed_funds: Vec<String>,
+    pub added_edges: Vec<ExposureEdgeSnapshot>,
+    pub removed_edges: Vec<ExposureEdgeSnapshot>,
+    pub changed_edges: Vec<ChangedExposureEdge>,
+    /// New minus old `concentration_hhi`
+    pub concentration_delta: f64,
+}
+
+impl ExposureGraph {
+    /// Copy the graph's funds and aggregated edges
+    pub fn snapshot(&self) -> ExposureSnapshot {
+        let funds = self.nodes.values()
+            .map(|node| (node.fund_id.clone(), node.total_assets))
+            .collect();
+
+        let mut edges: BTreeMap<String, BTreeMap<String, u64>> = BTreeMap::new();
+        for edge in self.edges.values().flatten() {
+            let amount = edges.entry(edge.from_fund.clone()).or_default()
+                .entry(edge.to_fund.clone()).or_default();
+            *amount = amount.saturating_add(edge.exposure_amount);
+        }
+
+        let concentration_hhi = received_exposure_hhi(&edges);
+        ExposureSnapshot { taken_at: self.last_updated, funds, edges, concentration_hhi }
+    }
+}
+
+impl ExposureSnapshot {
+    /// Every exposure edge as `(from_fund, to_fund, amount)`, in order
+    pub fn edge_list(&self) -> impl Iterator<Item = (&str, &str, u64)> {
+        self.edges.iter().flat_map(|(from, targets)| {
+            targets.iter().map(move |(to, amount)| (from.as_str(), to.as_str(), *amount))
+        })
+    }
+
+    fn edge_amount(&self, from_fund: &str, to_fund: &str) -> Option<u64> {
+        self.edges.get(from_fund).and_then(|targets| targets.get(to_fund)).copied()
+    }
+
+    /// What changed between `old` and `new`
+    pub fn diff(old: &ExposureSnapshot, new: &ExposureSnapshot) -> ExposureDiff {
+        let old_funds: BTreeSet<&String> = old.funds.keys().collect();
+        let new_funds: BTreeSet<&String> = new.funds.keys().collect();
+
+        let mut added_edges = Vec::new();
+        let mut changed_edges = Vec::new();
+        for (from, to, new_amount) in new.edge_list() {
+            match old.edge_amount(from, to) {
+                None => added_edges.push(ExposureEdgeSnapshot {
+                    from_fund: from.to_string(),
+                    to_fund: to.to_string(),
+                    exposure_amount: new_amount,
+                }),
+                Some(old_amount) if old_amount != new_amount => changed_edges.push(ChangedExposureEdge {
+                    from_fund: from.to_string(),
+                    to_fund: to.to_string(),
+                    old_amount,
+                    new_amount,
+                }),
+                Some(_) => {}
+            }
+        }
+        let removed_edges = old.edge_list()
+            .filter(|(from, to, _)| new.edge_amount(from, to).is_none())
+            .map(|(from, to, amount)| ExposureEdgeSnapshot {
+                from_fund: from.to_string(),
+                to_fund: to.to_string(),
+                exposure_amount: amount,
+            })
+            .collect();
+
+        ExposureDiff {
+            added_funds: new_funds.difference(&old_funds).map(|fund| fund.to_string()).collect(),
+            removed_funds: old_funds.difference(&new_funds).map(|fund| fund.to_string()).collect(),
+            added_edges,
+            removed_edges,
+            changed_edges,
+            concentration_delta: new.concentration_hhi - old.concentration_hhi,
+        }
+    }
+}
+
+impl ExposureDiff {
+    /// Whether nothing alert-worthy changed
+    pub fn is_empty(&self) -> bool {
+        self.added_funds.is_empty()
+            && self.removed_funds.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.changed_edges.is_empty()
+            && self.concentration_delta == 0.0
+    }
+}
+
+/// HHI in basis points of the exposure each fund receives
+fn received_exposure_hhi(edges: &BTreeMap<String, BTreeMap<String, u64>>) -> f64 {
+    let mut received: BTreeMap<&str, f64> = BTreeMap::new();
+    for targets in edges.values() {
+        for (to, amount) in targets {
+            *received.entry(to.as_str()).or_default() += *amount as f64;
+        }
+    }
+    let total: f64 = received.values().sum();
+    if total == 0.0 {
+        return 0.0;
+    }
+    received.values().map(|amount| (amount / total).powi(2) * 10_000.0).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fund_exposure_service::{ExposureEdge, ExposureType, FundNode, FundStatus, FundType};
+    use std::collections::HashMap;
+
+    fn fund(fund_id: &str) -> FundNode {
+        FundNode {
+            fund_id: fund_id.to_string(),
+            fund_origin_hash: format!("origin_{}", fund_id),
+            total_assets: 1_000,
+            nav_per_share: 1_000_000,
+            fund_type: FundType::Primary,
+            jurisdiction: "US".to_string(),
+            creation_timestamp: 0,
+            last_nav_update: 0,
+            status: FundStatus::Active,
+        }
+    }
+
+    fn edge(from: &str, to: &str, exposure_amount: u64) -> ExposureEdge {
+        ExposureEdge {
+            from_fund: from.to_string(),
+            to_fund: to.to_string(),
+            exposure_amount,
+            exposure_percentage: exposure_amount as f64 / 10.0,
+            exposure_type: ExposureType::DirectInvestment,
+            timestamp: 0,
+            proof_hash: "proof".to_string(),
+        }
+    }
+
+    fn graph(funds: &[&str], edges: &[(&str, &str, u64)]) -> ExposureGraph {
+        let mut edge_map: HashMap<String, Vec<ExposureEdge>> =
+            funds.iter().map(|fund_id| (fund_id.to_string(), Vec::new())).collect();
+        for (from, to, amount) in edges {
+            edge_map.get_mut(*from).unwrap().push(edge(from, to, *amount));
+        }
+        ExposureGraph {
+            nodes: funds.iter().map(|fund_id| (fund_id.to_string(), fund(fund_id))).collect(),
+            edges: edge_map,
+            total_funds: funds.len(),
+            total_exposures: edges.len(),
+            last_updated: 0,
+        }
+    }
+
+    #[test]
+    fn test_diff_captures_exactly_the_changed_edge() {
+        let old = graph(&["fund-a", "fund-b", "fund-c"], &[("fund-a", "fund-b", 300), ("fund-a", "fund-c", 100)]).snapshot();
+        let new = graph(&["fund-a", "fund-b", "fund-c"], &[("fund-a", "fund-b", 300), ("fund-a", "fund-c", 300)]).snapshot();
+
+        let diff = ExposureSnapshot::diff(&old, &new);
+        assert_eq!(diff.changed_edges, vec![ChangedExposureEdge {
+            from_fund: "fund-a".to_string(),
+            to_fund: "fund-c".to_string(),
+            old_amount: 100,
+            new_amount: 300,
+        }]);
+        assert!(diff.added_funds.is_empty() && diff.removed_funds.is_empty());
+        assert!(diff.added_edges.is_empty() && diff.removed_edges.is_empty());
+        // Received exposure went from 75/25 to 50/50
+        assert!((old.concentration_hhi - 6_250.0).abs() < 1e-9);
+        assert!((diff.concentration_delta + 1_250.0).abs() < 1e-9);
+
+        assert!(ExposureSnapshot::diff(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn test_diff_reports_added_and_removed_funds_and_edges() {
+        // Parallel edges between one pair count as a single edge
+        let old = graph(&["fund-a", "fund-b", "fund-c"], &[("fund-a", "fund-b", 100), ("fund-a", "fund-b", 50), ("fund-b", "fund-c", 10)]).snapshot();
+        let new = graph(&["fund-a", "fund-b", "fund-d"], &[("fund-a", "fund-b", 150), ("fund-b", "fund-d", 20)]).snapshot();
+
+        let diff = ExposureSnapshot::diff(&old, &new);
+        assert_eq!(diff.added_funds, vec!["fund-d".to_string()]);
+        assert_eq!(diff.removed_funds, vec!["fund-c".to_string()]);
+        assert_eq!(diff.added_edges, vec![ExposureEdgeSnapshot {
+            from_fund: "fund-b".to_string(),
+            to_fund: "fund-d".to_string(),
+            exposure_amount: 20,
+        }]);
+        assert_eq!(diff.removed_edges, vec![ExposureEdgeSnapshot {
+            from_fund: "fund-b".to_string(),
+            to_fund: "fund-c".to_string(),
+            exposure_amount: 10,
+        }]);
+        assert!(diff.changed_edges.is_empty());
+    }
+}
@@ -1,11 +1,16 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet, VecDeque};
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 use tracing::{info, warn, error};
 use sha2::{Sha256, Digest};
+use domain_tags::DomainTag;
 use petgraph::{Graph, Directed, graph::NodeIndex};
-use petgraph::algo::{is_cyclic_directed, toposort};
+use petgraph::algo::{is_cyclic_directed, tarjan_scc, toposort};
+use petgraph::visit::Dfs;
+
+use crate::exposure_snapshot::ExposureSnapshot;
+use crate::origin_verification::{verify_ed25519, verify_snapshot_consistency, OriginSignaturePolicy};
 
 /// Fund Exposure Detection and Isolation Service
 /// PRD Section 4.1: Fund Exposure & Isolation
@@ -16,9 +21,38 @@ pub struct FundExposureService {
     exposure_graph: RwLock<ExposureGraph>,
     fund_registry: RwLock<HashMap<String, FundMetadata>>,
     circular_dependency_cache: RwLock<HashMap<String, bool>>,
+    fund_whitelist: RwLock<HashMap<String, WhitelistEntry>>,
     max_exposure_depth: usize,
     max_circular_exposure: f64, // Percentage
+    max_single_exposure_percentage: f64,
+    max_flatten_visits: usize,
+    cr4_concentration_threshold: f64, // Percentage
     monitoring_enabled: bool,
+    origin_policy: OriginSignaturePolicy,
+    /// Hex Ed25519 public keys of the addresses allowed to authorize whitelist changes
+    whitelist_authorizers: HashMap<String, String>,
+    /// Signatures already spent on a whitelist change, so none can be replayed
+    used_whitelist_authorizations: RwLock<HashSet<String>>,
+    risk_alert_sender: broadcast::Sender<SystemicRiskAlert>,
+    /// Critical risks already broadcast, keyed by `SystemicRiskAlert::risk_key`, with the root fund that raised them
+    alerted_risks: RwLock<HashMap<String, String>>,
+}
+
+/// Default cap on fund expansions during a single recursive flattening
+pub const DEFAULT_MAX_FLATTEN_VISITS: usize = 10_000;
+
+/// Default cap on a fund's exposure to any one counterparty, percent of its assets
+pub const DEFAULT_MAX_SINGLE_EXPOSURE_PERCENTAGE: f64 = 50.0;
+
+/// Default CR4 (top-4 share, percent) above which a market counts as highly concentrated
+pub const DEFAULT_CR4_CONCENTRATION_THRESHOLD: f64 = 60.0;
+
+const RISK_ALERT_CHANNEL_CAPACITY: usize = 256;
+
+/// Flattened exposures beneath one fund, weighted relative to that fund's own weight
+struct SubtreeFlattening {
+    relative_weights: Vec<(String, f64)>,
+    contains_loop: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -165,7 +199,7 @@ pub struct OriginSignature {
     pub timestamp: i64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum SignerRole {
     LegalEntity,
     DaoMultisig,
@@ -174,7 +208,7 @@ pub enum SignerRole {
     TechnicalValidator,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SignatureType {
     ECDSA,
     EdDSA,
@@ -197,10 +231,25 @@ pub struct ForkDerivationProof {
     pub fork_reason: String,
     pub derivation_proof: String,
     pub dao_approval_tx: String,
+    /// JSON-encoded `LegalAncestry` of the child fund
     pub legal_continuity_proof: String,
+    /// JSON-encoded `AssetMigrationProof`
     pub asset_migration_proof: String,
 }
 
+/// Conservation proof for assets moved from the parent fund into a fork
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetMigrationProof {
+    pub declared_amount: u64,
+    pub migrated_assets: Vec<MigratedAsset>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigratedAsset {
+    pub asset_id: String,
+    pub amount: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CircularDependencyResult {
     pub circular_detected: bool,
@@ -226,6 +275,53 @@ pub struct RecursiveExposureFlattening {
     pub timestamp: i64,
 }
 
+impl RecursiveExposureFlattening {
+    /// Graphviz DOT rendering of the flattened exposures
+    /// Edges are labelled with direct and cumulative weight; edges on an exposure loop are red.
+    pub fn to_dot(&self) -> String {
+        let loop_edges: HashSet<(&str, &str)> = self.exposure_loops.iter()
+            .flat_map(|l| l.loop_path.windows(2).map(|pair| (pair[0].as_str(), pair[1].as_str())))
+            .collect();
+
+        let mut exposures: Vec<&FlattenedExposure> = self.flattened_exposures.values().collect();
+        exposures.sort_by(|a, b| (&a.from_fund, &a.to_fund).cmp(&(&b.from_fund, &b.to_fund)));
+
+        let mut funds: Vec<&str> = exposures.iter()
+            .flat_map(|e| [e.from_fund.as_str(), e.to_fund.as_str()])
+            .filter(|fund| *fund != self.root_fund_id)
+            .collect();
+        funds.sort();
+        funds.dedup();
+
+        let mut dot = String::from("digraph exposure {\n");
+        dot.push_str(&format!("    {} [style=bold];\n", dot_id(&self.root_fund_id)));
+        for fund in funds {
+            dot.push_str(&format!("    {};\n", dot_id(fund)));
+        }
+        for exposure in exposures {
+            let color = if loop_edges.contains(&(exposure.from_fund.as_str(), exposure.to_fund.as_str())) {
+                ", color=red"
+            } else {
+                ""
+            };
+            dot.push_str(&format!(
+                "    {} -> {} [label=\"{:.2}% ({:.2}% cumulative)\"{}];\n",
+                dot_id(&exposure.from_fund),
+                dot_id(&exposure.to_fund),
+                exposure.direct_weight * 100.0,
+                exposure.cumulative_weight * 100.0,
+                color,
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn dot_id(fund_id: &str) -> String {
+    format!("\"{}\"", fund_id.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FlattenedExposure {
     pub from_fund: String,
@@ -303,6 +399,18 @@ pub struct SystemicRisk {
     pub mitigation_recommendations: Vec<String>,
 }
 
+/// Critical systemic risk raised by exposure analysis
+/// Broadcast once when first detected; it can be raised again only after an analysis
+/// of the same root fund no longer reports it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemicRiskAlert {
+    /// Stable identity of the risk: its type and the funds involved
+    pub risk_key: String,
+    pub root_fund_id: String,
+    pub risk: SystemicRisk,
+    pub raised_at: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SystemicRiskType {
     HighConcentration,
@@ -474,6 +582,37 @@ pub struct RiskAssessment {
     pub recommendations: Vec<String>,
 }
 
+/// Message a whitelist authorizer signs for one change to one fund
+pub fn whitelist_authorization_message(
+    fund_id: &str,
+    action: &WhitelistAction,
+    authorization: &WhitelistAuthorization,
+) -> [u8; 32] {
+    #[derive(Serialize)]
+    struct AuthorizedChange<'a> {
+        fund_id: &'a str,
+        action: &'a WhitelistAction,
+        authorizer_address: &'a str,
+        expiry_timestamp: i64,
+        conditions: &'a [String],
+        authorization_level: &'a AuthorizationLevel,
+    }
+
+    let change = canonical_json::to_canonical_json(&AuthorizedChange {
+        fund_id,
+        action,
+        authorizer_address: &authorization.authorizer_address,
+        expiry_timestamp: authorization.expiry_timestamp,
+        conditions: &authorization.conditions,
+        authorization_level: &authorization.authorization_level,
+    }).expect("whitelist change serializes to JSON");
+
+    let mut hasher = Sha256::new();
+    hasher.update(DomainTag::WhitelistAuthorization.as_bytes());
+    hasher.update(change.as_bytes());
+    hasher.finalize().into()
+}
+
 impl FundExposureService {
     /// Initialize Fund Exposure Service
     pub async fn new(
@@ -492,9 +631,18 @@ impl FundExposureService {
             }),
             fund_registry: RwLock::new(HashMap::new()),
             circular_dependency_cache: RwLock::new(HashMap::new()),
+            fund_whitelist: RwLock::new(HashMap::new()),
             max_exposure_depth,
             max_circular_exposure,
+            max_single_exposure_percentage: DEFAULT_MAX_SINGLE_EXPOSURE_PERCENTAGE,
+            max_flatten_visits: DEFAULT_MAX_FLATTEN_VISITS,
+            cr4_concentration_threshold: DEFAULT_CR4_CONCENTRATION_THRESHOLD,
             monitoring_enabled: true,
+            origin_policy: OriginSignaturePolicy::default(),
+            whitelist_authorizers: HashMap::new(),
+            used_whitelist_authorizations: RwLock::new(HashSet::new()),
+            risk_alert_sender: broadcast::channel(RISK_ALERT_CHANNEL_CAPACITY).0,
+            alerted_risks: RwLock::new(HashMap::new()),
         };
 
         info!("✅ Fund Exposure Service initialized with max depth: {}, max circular: {}%", 
@@ -502,22 +650,63 @@ impl FundExposureService {
         Ok(service)
     }
 
+    /// Cap the number of fund expansions a single recursive flattening may perform
+    pub fn with_flatten_visit_budget(mut self, max_flatten_visits: usize) -> Self {
+        self.max_flatten_visits = max_flatten_visits;
+        self
+    }
+
+    /// Signers and role quorum required on fund-origin snapshots
+    pub fn with_origin_policy(mut self, origin_policy: OriginSignaturePolicy) -> Self {
+        self.origin_policy = origin_policy;
+        self
+    }
+
+    /// Allow `authorizer_address` to sign whitelist changes with this hex Ed25519 public key
+    pub fn with_whitelist_authorizer(mut self, authorizer_address: impl Into<String>, public_key: impl Into<String>) -> Self {
+        self.whitelist_authorizers.insert(authorizer_address.into(), public_key.into());
+        self
+    }
+
+    /// Largest share of a fund's assets, in percent, it may have exposed to one counterparty
+    pub fn with_max_single_exposure_percentage(mut self, max_single_exposure_percentage: f64) -> Self {
+        self.max_single_exposure_percentage = max_single_exposure_percentage;
+        self
+    }
+
+    /// CR4 percentage above which HHI analysis escalates to highly concentrated
+    pub fn with_cr4_threshold(mut self, cr4_concentration_threshold: f64) -> Self {
+        self.cr4_concentration_threshold = cr4_concentration_threshold;
+        self
+    }
+
+    /// Receive critical systemic risks discovered by analyses run after this call
+    pub fn subscribe_risk_alerts(&self) -> broadcast::Receiver<SystemicRiskAlert> {
+        self.risk_alert_sender.subscribe()
+    }
+
+    /// Deepest recursion `recursive_zknav_flattening` accepts
+    pub fn max_exposure_depth(&self) -> usize {
+        self.max_exposure_depth
+    }
+
+    pub async fn is_registered(&self, fund_id: &str) -> bool {
+        self.exposure_graph.read().await.nodes.contains_key(fund_id)
+    }
+
     /// PRD: Register new fund with origin proof
     /// PRD: "Fund-Origin Proof with comprehensive ancestry tracking"
-    pub async fn register_fund(
-        &self,
-        fund_metadata: FundMetadata,
-        fund_origin_proof: String,
-    ) -> Result<()> {
+    /// The fund's origin hash is the snapshot hash its origin signers signed for this fund.
+    pub async fn register_fund(&self, fund_metadata: FundMetadata) -> Result<()> {
         info!("📝 Registering fund: {}", fund_metadata.fund_id);
 
         // Verify fund origin proof
-        self.verify_fund_origin_proof(&fund_origin_proof).await?;
+        self.verify_fund_origin_proof(&fund_metadata.fund_id, &fund_metadata.fund_origin_proof).await?;
 
         // Create fund node
         let fund_node = FundNode {
             fund_id: fund_metadata.fund_id.clone(),
-            fund_origin_hash: fund_origin_proof.clone(),
+            fund_origin_hash: fund_metadata.fund_origin_proof.signed_snapshot.snapshot_hash.clone(),
             total_assets: 0,
             nav_per_share: 1_000_000, // 1.0 with 6 decimals
             fund_type: FundType::Primary, // Will be updated based on exposures
@@ -536,12 +725,13 @@ impl FundExposureService {
             graph.last_updated = chrono::Utc::now().timestamp();
         }
 
+        let fund_id = fund_metadata.fund_id.clone();
         {
             let mut registry = self.fund_registry.write().await;
-            registry.insert(fund_metadata.fund_id.clone(), fund_metadata);
+            registry.insert(fund_id.clone(), fund_metadata);
         }
 
-        info!("✅ Fund registered successfully: {}", fund_metadata.fund_id);
+        info!("✅ Fund registered successfully: {}", fund_id);
         Ok(())
     }
 
@@ -561,18 +751,41 @@ impl FundExposureService {
         self.verify_exposure_proof(&zk_proof, &from_fund, &to_fund, exposure_amount).await?;
 
         // Calculate exposure percentage
-        let from_fund_assets = {
+        let (from_fund_assets, existing_target_exposure) = {
             let graph = self.exposure_graph.read().await;
-            graph.nodes.get(&from_fund)
+            let assets = graph.nodes.get(&from_fund)
                 .map(|node| node.total_assets)
-                .unwrap_or(0)
+                .ok_or_else(|| anyhow::anyhow!("Fund not registered: {}", from_fund))?;
+            let existing = graph.edges.get(&from_fund)
+                .map(|edges| edges.iter()
+                    .filter(|edge| edge.to_fund == to_fund)
+                    .fold(0u64, |total, edge| total.saturating_add(edge.exposure_amount)))
+                .unwrap_or(0);
+            (assets, existing)
         };
 
-        let exposure_percentage = if from_fund_assets > 0 {
-            (exposure_amount as f64 / from_fund_assets as f64) * 100.0
-        } else {
-            0.0
-        };
+        // Without recorded assets the exposure percentage is unbounded, so nothing can pass the limit
+        if from_fund_assets == 0 {
+            error!("❌ Exposure {} -> {} rejected: {} has no recorded assets", from_fund, to_fund, from_fund);
+            return Err(anyhow::anyhow!(
+                "Fund {} has no recorded assets; set them with update_fund_assets before adding exposure",
+                from_fund
+            ));
+        }
+
+        let exposure_percentage = (exposure_amount as f64 / from_fund_assets as f64) * 100.0;
+
+        // Single-counterparty limit on the fund's total exposure to `to_fund`, not just this edge
+        let target_percentage = existing_target_exposure.saturating_add(exposure_amount) as f64
+            / from_fund_assets as f64 * 100.0;
+        if target_percentage > self.max_single_exposure_percentage {
+            error!("❌ Exposure {} -> {} would reach {:.2}% of assets, limit {}%",
+                   from_fund, to_fund, target_percentage, self.max_single_exposure_percentage);
+            return Err(anyhow::anyhow!(
+                "Single counterparty exposure limit exceeded: {} -> {} would reach {:.2}% (limit {}%)",
+                from_fund, to_fund, target_percentage, self.max_single_exposure_percentage
+            ));
+        }
 
         // Create exposure edge
         let exposure_edge = ExposureEdge {
@@ -593,6 +806,7 @@ impl FundExposureService {
                 graph.total_exposures += 1;
                 graph.last_updated = chrono::Utc::now().timestamp();
             }
+            self.invalidate_cycle_cache(&graph, &from_fund).await;
         }
 
         // PRD: Check for circular dependencies
@@ -613,6 +827,131 @@ impl FundExposureService {
         Ok(())
     }
 
+    /// Remove every exposure edge from `from_fund` to `to_fund`
+    pub async fn remove_fund_exposure(&self, from_fund: &str, to_fund: &str) -> Result<()> {
+        {
+            let mut graph = self.exposure_graph.write().await;
+            let edges = graph.edges.get_mut(from_fund)
+                .ok_or_else(|| anyhow::anyhow!("Fund not registered: {}", from_fund))?;
+
+            let before = edges.len();
+            edges.retain(|edge| edge.to_fund != to_fund);
+            let removed = before - edges.len();
+            if removed == 0 {
+                return Err(anyhow::anyhow!("No exposure from {} to {}", from_fund, to_fund));
+            }
+
+            graph.total_exposures = graph.total_exposures.saturating_sub(removed);
+            graph.last_updated = chrono::Utc::now().timestamp();
+            self.invalidate_cycle_cache(&graph, from_fund).await;
+        }
+
+        self.update_fund_types().await?;
+
+        info!("✂️ Exposure removed: {} -> {}", from_fund, to_fund);
+        Ok(())
+    }
+
+    /// Remove a fund, its outgoing exposures and every inbound exposure to it
+    /// Funds still referenced by other funds are only removed when `force` is set
+    pub async fn deregister_fund(&self, fund_id: &str, force: bool) -> Result<()> {
+        {
+            let mut graph = self.exposure_graph.write().await;
+            if !graph.nodes.contains_key(fund_id) {
+                return Err(anyhow::anyhow!("Fund not registered: {}", fund_id));
+            }
+
+            let mut referrers: Vec<&String> = graph.edges.iter()
+                .filter(|(from, edges)| from.as_str() != fund_id && edges.iter().any(|e| e.to_fund == fund_id))
+                .map(|(from, _)| from)
+                .collect();
+            if !referrers.is_empty() && !force {
+                referrers.sort();
+                return Err(anyhow::anyhow!(
+                    "Fund {} is still referenced by {:?}; pass force to remove inbound exposures", fund_id, referrers
+                ));
+            }
+
+            // Every fund that can reach this one may have a stale cycle result
+            self.invalidate_cycle_cache(&graph, fund_id).await;
+
+            let mut removed = graph.edges.remove(fund_id).map(|edges| edges.len()).unwrap_or(0);
+            for edges in graph.edges.values_mut() {
+                let before = edges.len();
+                edges.retain(|edge| edge.to_fund != fund_id);
+                removed += before - edges.len();
+            }
+
+            graph.nodes.remove(fund_id);
+            graph.total_funds = graph.total_funds.saturating_sub(1);
+            graph.total_exposures = graph.total_exposures.saturating_sub(removed);
+            graph.last_updated = chrono::Utc::now().timestamp();
+        }
+
+        self.fund_registry.write().await.remove(fund_id);
+        self.update_fund_types().await?;
+
+        info!("🗑️ Fund deregistered: {}", fund_id);
+        Ok(())
+    }
+
+    /// Record a fund's new total assets and rescale its outgoing exposure percentages
+    pub async fn update_fund_assets(&self, fund_id: &str, new_total_assets: u64) -> Result<()> {
+        let mut graph = self.exposure_graph.write().await;
+
+        if !graph.nodes.contains_key(fund_id) {
+            return Err(anyhow::anyhow!("Fund not registered: {}", fund_id));
+        }
+
+        // Shrinking assets raises every edge's share, so re-check the single-counterparty limit
+        // against the new total before committing it
+        if let Some(edges) = graph.edges.get(fund_id) {
+            let mut per_counterparty: HashMap<&str, u64> = HashMap::new();
+            for edge in edges {
+                let total = per_counterparty.entry(edge.to_fund.as_str()).or_insert(0);
+                *total = total.saturating_add(edge.exposure_amount);
+            }
+            for (to_fund, amount) in per_counterparty {
+                if amount == 0 {
+                    continue;
+                }
+                let percentage = if new_total_assets > 0 {
+                    amount as f64 / new_total_assets as f64 * 100.0
+                } else {
+                    f64::INFINITY
+                };
+                if percentage > self.max_single_exposure_percentage {
+                    error!("❌ Assets update for {} would put {} -> {} at {:.2}%, limit {}%",
+                           fund_id, fund_id, to_fund, percentage, self.max_single_exposure_percentage);
+                    return Err(anyhow::anyhow!(
+                        "Single counterparty exposure limit exceeded: {} -> {} would reach {:.2}% (limit {}%)",
+                        fund_id, to_fund, percentage, self.max_single_exposure_percentage
+                    ));
+                }
+            }
+        }
+
+        if let Some(node) = graph.nodes.get_mut(fund_id) {
+            node.total_assets = new_total_assets;
+        }
+
+        if let Some(edges) = graph.edges.get_mut(fund_id) {
+            for edge in edges.iter_mut() {
+                edge.exposure_percentage = if new_total_assets > 0 {
+                    (edge.exposure_amount as f64 / new_total_assets as f64) * 100.0
+                } else {
+                    0.0
+                };
+            }
+        }
+        graph.last_updated = chrono::Utc::now().timestamp();
+
+        self.invalidate_cycle_cache(&graph, fund_id).await;
+
+        info!("📈 Fund {} assets updated to {}", fund_id, new_total_assets);
+        Ok(())
+    }
+
     /// PRD: Detect circular dependencies
     /// PRD: "Cross-fund Ring Detector preventing circular dependencies"
     pub async fn detect_circular_dependency(
@@ -639,32 +978,12 @@ impl FundExposureService {
         }
 
         let graph = self.exposure_graph.read().await;
-        
-        // Use DFS to detect cycles
-        let mut visited = HashSet::new();
-        let mut rec_stack = HashSet::new();
-        let mut path = Vec::new();
-        
-        if self.dfs_cycle_detection(&graph, fund_id, &mut visited, &mut rec_stack, &mut path) {
-            // Calculate cycle metrics
-            let cycle_start_index = path.iter().position(|f| f == fund_id).unwrap_or(0);
-            let cycle_path = path[cycle_start_index..].to_vec();
-            
-            let (total_exposure, max_percentage) = self.calculate_cycle_exposure(&graph, &cycle_path);
-            
-            let risk_level = match max_percentage {
-                p if p > 50.0 => RiskLevel::Critical,
-                p if p > 25.0 => RiskLevel::High,
-                p if p > 10.0 => RiskLevel::Medium,
-                _ => RiskLevel::Low,
-            };
+        let fund_graph = Self::build_fund_graph(&graph);
 
-            let recommended_action = match risk_level {
-                RiskLevel::Critical => "Immediate reduction of circular exposure required".to_string(),
-                RiskLevel::High => "Reduce circular exposure within 30 days".to_string(),
-                RiskLevel::Medium => "Monitor and consider reducing exposure".to_string(),
-                RiskLevel::Low => "Continue monitoring".to_string(),
-            };
+        if let Some(cycle_path) = Self::find_cycle_from(&fund_graph, fund_id) {
+            // Calculate cycle metrics
+            let (total_exposure, max_percentage) =
+                self.calculate_cycle_exposure(&graph, &cycle_path[..cycle_path.len() - 1]);
 
             // Cache result
             {
@@ -672,14 +991,7 @@ impl FundExposureService {
                 cache.insert(fund_id.to_string(), true);
             }
 
-            Ok(CircularDependencyResult {
-                circular_detected: true,
-                cycle_path,
-                total_exposure_in_cycle: total_exposure,
-                max_exposure_percentage: max_percentage,
-                risk_level,
-                recommended_action,
-            })
+            Ok(Self::cycle_result(cycle_path, total_exposure, max_percentage))
         } else {
             // Cache negative result
             {
@@ -698,6 +1010,72 @@ impl FundExposureService {
         }
     }
 
+    /// Point-in-time copy of the exposure graph, for diffing against later snapshots
+    pub async fn exposure_snapshot(&self) -> ExposureSnapshot {
+        self.exposure_graph.read().await.snapshot()
+    }
+
+    /// Materialize the exposure graph as a petgraph graph weighted by exposure percentage
+    pub async fn to_petgraph(&self) -> Graph<String, f64, Directed> {
+        let graph = self.exposure_graph.read().await;
+        Self::build_fund_graph(&graph)
+    }
+
+    /// Whether any circular exposure exists anywhere in the graph
+    pub async fn has_circular_exposure(&self) -> bool {
+        is_cyclic_directed(&self.to_petgraph().await)
+    }
+
+    /// Funds ordered so every fund precedes the funds it is exposed to
+    /// Fails with the offending cycle when the exposure graph is not a DAG
+    pub async fn topological_fund_order(&self) -> Result<Vec<String>> {
+        let fund_graph = self.to_petgraph().await;
+
+        match toposort(&fund_graph, None) {
+            Ok(order) => Ok(order.into_iter().map(|n| fund_graph[n].clone()).collect()),
+            Err(cycle) => {
+                let fund_id = &fund_graph[cycle.node_id()];
+                let cycle_path = Self::find_cycle_from(&fund_graph, fund_id)
+                    .unwrap_or_else(|| vec![fund_id.clone()]);
+                Err(anyhow::anyhow!("Exposure graph contains a cycle: {}", cycle_path.join(" -> ")))
+            }
+        }
+    }
+
+    /// Enumerate every elementary cycle in the exposure graph
+    /// Cycles are found per strongly connected component, each reported once starting
+    /// from its lexicographically smallest fund
+    pub async fn detect_all_cycles(&self) -> Vec<CircularDependencyResult> {
+        let graph = self.exposure_graph.read().await;
+        let fund_graph = Self::build_fund_graph(&graph);
+
+        let mut cycles = Vec::new();
+        for mut component in tarjan_scc(&fund_graph) {
+            component.sort_by(|a, b| fund_graph[*a].cmp(&fund_graph[*b]));
+            for (i, &start) in component.iter().enumerate() {
+                let allowed: HashSet<NodeIndex> = component[i..].iter().copied().collect();
+                let mut path = vec![start];
+                Self::collect_elementary_cycles(&fund_graph, start, start, &allowed, &mut path, &mut cycles);
+            }
+        }
+
+        let mut results: Vec<CircularDependencyResult> = cycles.into_iter()
+            .map(|cycle| {
+                let open_path: Vec<String> = cycle.iter().map(|&n| fund_graph[n].clone()).collect();
+                let (total_exposure, max_percentage) = self.calculate_cycle_exposure(&graph, &open_path);
+                let mut cycle_path = open_path;
+                cycle_path.push(cycle_path[0].clone());
+                Self::cycle_result(cycle_path, total_exposure, max_percentage)
+            })
+            .collect();
+        results.sort_by(|a, b| a.cycle_path.cmp(&b.cycle_path));
+
+        if !results.is_empty() {
+            warn!("🚨 {} circular dependencies detected across the exposure graph", results.len());
+        }
+        results
+    }
+
     /// PRD: Comprehensive exposure analysis
     /// PRD: "Recursive zkNAV Flattening for exposure graph detection"
     pub async fn analyze_fund_exposure(
@@ -754,9 +1132,10 @@ impl FundExposureService {
     }
 
     // Private helper methods
-    async fn verify_fund_origin_proof(&self, proof: &str) -> Result<()> {
-        // TODO: Implement actual zkProof verification of fund origin
-        info!("🔍 Verifying fund origin proof");
+    async fn verify_fund_origin_proof(&self, fund_id: &str, proof: &FundOriginProof) -> Result<()> {
+        info!("🔍 Verifying fund origin proof for {}", fund_id);
+        verify_snapshot_consistency(proof)?;
+        self.origin_policy.verify(fund_id, &proof.signed_snapshot)?;
         Ok(())
     }
 
@@ -766,6 +1145,66 @@ impl FundExposureService {
         Ok(())
     }
 
+    /// Drop cached cycle results that a change to `fund_id`'s outgoing edges can affect:
+    /// the fund itself and every fund whose DFS can reach it
+    async fn invalidate_cycle_cache(&self, graph: &ExposureGraph, fund_id: &str) {
+        let affected = Self::funds_reaching(graph, fund_id);
+        let mut cache = self.circular_dependency_cache.write().await;
+        for fund in &affected {
+            cache.remove(fund);
+        }
+    }
+
+    fn funds_reaching(graph: &ExposureGraph, fund_id: &str) -> HashSet<String> {
+        let mut reaching = HashSet::from([fund_id.to_string()]);
+        let mut queue = VecDeque::from([fund_id.to_string()]);
+
+        while let Some(target) = queue.pop_front() {
+            for (source, edges) in &graph.edges {
+                if !reaching.contains(source) && edges.iter().any(|e| e.to_fund == target) {
+                    reaching.insert(source.clone());
+                    queue.push_back(source.clone());
+                }
+            }
+        }
+
+        reaching
+    }
+
+    /// First cycle reachable from `fund_id`, as a closed path
+    /// Prefers a cycle through the fund itself over one further downstream
+    fn find_cycle_from(fund_graph: &Graph<String, f64, Directed>, fund_id: &str) -> Option<Vec<String>> {
+        let start = fund_graph.node_indices().find(|&n| fund_graph[n] == fund_id)?;
+
+        let mut reachable = HashSet::new();
+        let mut dfs = Dfs::new(fund_graph, start);
+        while let Some(node) = dfs.next(fund_graph) {
+            reachable.insert(node);
+        }
+
+        let mut components: Vec<Vec<NodeIndex>> = tarjan_scc(fund_graph).into_iter()
+            .filter(|c| reachable.contains(&c[0]))
+            .filter(|c| c.len() > 1 || fund_graph.contains_edge(c[0], c[0]))
+            .collect();
+        components.sort_by_key(|c| !c.contains(&start));
+        let component = components.first()?;
+
+        let entry = if component.contains(&start) {
+            start
+        } else {
+            *component.iter().min_by(|a, b| fund_graph[**a].cmp(&fund_graph[**b]))?
+        };
+        let allowed: HashSet<NodeIndex> = component.iter().copied().collect();
+        let mut cycles = Vec::new();
+        Self::collect_elementary_cycles(fund_graph, entry, entry, &allowed, &mut vec![entry], &mut cycles);
+
+        let mut cycle_path: Vec<String> = cycles.first()?.iter().map(|&n| fund_graph[n].clone()).collect();
+        cycle_path.push(cycle_path[0].clone());
+        Some(cycle_path)
+    }
+
+    /// Hand-rolled DFS kept as a reference for the petgraph-based detection
+    #[cfg(test)]
     fn dfs_cycle_detection(
         &self,
         graph: &ExposureGraph,
@@ -796,6 +1235,76 @@ impl FundExposureService {
         false
     }
 
+    fn build_fund_graph(graph: &ExposureGraph) -> Graph<String, f64, Directed> {
+        let mut fund_ids: Vec<&String> = graph.nodes.keys()
+            .chain(graph.edges.keys())
+            .chain(graph.edges.values().flatten().map(|edge| &edge.to_fund))
+            .collect();
+        fund_ids.sort();
+        fund_ids.dedup();
+
+        let mut fund_graph = Graph::new();
+        let indices: HashMap<&str, NodeIndex> = fund_ids.into_iter()
+            .map(|fund_id| (fund_id.as_str(), fund_graph.add_node(fund_id.clone())))
+            .collect();
+
+        for (from_fund, edges) in &graph.edges {
+            for edge in edges {
+                fund_graph.add_edge(indices[from_fund.as_str()], indices[edge.to_fund.as_str()], edge.exposure_percentage);
+            }
+        }
+
+        fund_graph
+    }
+
+    fn collect_elementary_cycles(
+        fund_graph: &Graph<String, f64, Directed>,
+        start: NodeIndex,
+        current: NodeIndex,
+        allowed: &HashSet<NodeIndex>,
+        path: &mut Vec<NodeIndex>,
+        cycles: &mut Vec<Vec<NodeIndex>>,
+    ) {
+        let mut successors: Vec<NodeIndex> = fund_graph.neighbors(current).collect();
+        successors.sort();
+        successors.dedup();
+
+        for next in successors {
+            if next == start {
+                cycles.push(path.clone());
+            } else if allowed.contains(&next) && !path.contains(&next) {
+                path.push(next);
+                Self::collect_elementary_cycles(fund_graph, start, next, allowed, path, cycles);
+                path.pop();
+            }
+        }
+    }
+
+    fn cycle_result(cycle_path: Vec<String>, total_exposure: u64, max_percentage: f64) -> CircularDependencyResult {
+        let risk_level = match max_percentage {
+            p if p > 50.0 => RiskLevel::Critical,
+            p if p > 25.0 => RiskLevel::High,
+            p if p > 10.0 => RiskLevel::Medium,
+            _ => RiskLevel::Low,
+        };
+
+        let recommended_action = match risk_level {
+            RiskLevel::Critical => "Immediate reduction of circular exposure required".to_string(),
+            RiskLevel::High => "Reduce circular exposure within 30 days".to_string(),
+            RiskLevel::Medium => "Monitor and consider reducing exposure".to_string(),
+            RiskLevel::Low => "Continue monitoring".to_string(),
+        };
+
+        CircularDependencyResult {
+            circular_detected: true,
+            cycle_path,
+            total_exposure_in_cycle: total_exposure,
+            max_exposure_percentage: max_percentage,
+            risk_level,
+            recommended_action,
+        }
+    }
+
     fn calculate_cycle_exposure(&self, graph: &ExposureGraph, cycle_path: &[String]) -> (u64, f64) {
         let mut total_exposure = 0u64;
         let mut max_percentage = 0.0f64;
@@ -929,7 +1438,8 @@ impl FundExposureService {
 
     async fn update_fund_types(&self) -> Result<()> {
         // Update fund types based on their exposure patterns
-        let mut graph = self.exposure_graph.write().await;
+        let mut guard = self.exposure_graph.write().await;
+        let graph = &mut *guard;
 
         for (fund_id, node) in graph.nodes.iter_mut() {
             let has_exposures = graph.edges.get(fund_id)
@@ -954,6 +1464,17 @@ impl FundExposureService {
         weight_threshold_bps: u16, // basis points (e.g., 1000 = 10%)
         max_recursion_depth: u32,
     ) -> Result<RecursiveExposureFlattening> {
+        if max_recursion_depth as usize > self.max_exposure_depth {
+            return Err(anyhow::anyhow!(
+                "Recursion depth {} exceeds maximum exposure depth {}", max_recursion_depth, self.max_exposure_depth
+            ));
+        }
+        if !(1..=10_000).contains(&weight_threshold_bps) {
+            return Err(anyhow::anyhow!(
+                "Weight threshold must be between 1 and 10000 bps, got {}", weight_threshold_bps
+            ));
+        }
+
         info!("🔄 Starting recursive zkNAV flattening for fund: {}", root_fund_id);
 
         let mut flattened_exposures = HashMap::new();
@@ -962,6 +1483,8 @@ impl FundExposureService {
         let mut multi_fund_shareholdings = Vec::new();
         let mut visited_funds = HashSet::new();
         let mut recursion_stack = Vec::new();
+        let mut subtree_memo = HashMap::new();
+        let mut visits = 0usize;
 
         // Start recursive flattening
         self.flatten_fund_exposures_recursive(
@@ -976,13 +1499,23 @@ impl FundExposureService {
             &mut multi_fund_shareholdings,
             &mut visited_funds,
             &mut recursion_stack,
+            &mut subtree_memo,
+            &mut visits,
         ).await?;
 
+        // Deepest level actually expanded, rather than the requested limit
+        let total_recursion_depth = flattened_exposures.values()
+            .map(|exposure| exposure.recursion_depth + 1)
+            .max()
+            .unwrap_or(0)
+            .min(max_recursion_depth);
+
         // Analyze exposure concentration
         let concentration_analysis = self.analyze_exposure_concentration(&flattened_exposures).await?;
 
         // Detect systemic risks
         let systemic_risks = self.detect_systemic_risks(&flattened_exposures, &exposure_loops).await?;
+        self.broadcast_critical_risks(&root_fund_id, &systemic_risks).await;
 
         let result = RecursiveExposureFlattening {
             root_fund_id,
@@ -992,7 +1525,7 @@ impl FundExposureService {
             multi_fund_shareholdings,
             concentration_analysis,
             systemic_risks,
-            total_recursion_depth: recursion_stack.len() as u32,
+            total_recursion_depth,
             weight_threshold_bps,
             timestamp: chrono::Utc::now().timestamp(),
         };
@@ -1004,6 +1537,8 @@ impl FundExposureService {
     }
 
     /// Recursive function to flatten fund exposures
+    /// Loop-free subtrees are memoized by (fund, depth) and replayed at the new weight
+    /// when reached again through another path, so diamonds are expanded only once
     async fn flatten_fund_exposures_recursive(
         &self,
         fund_id: &str,
@@ -1017,10 +1552,12 @@ impl FundExposureService {
         multi_fund_shareholdings: &mut Vec<MultiFundShareholding>,
         visited_funds: &mut HashSet<String>,
         recursion_stack: &mut Vec<String>,
-    ) -> Result<()> {
+        subtree_memo: &mut HashMap<(String, u32), Vec<(String, f64)>>,
+        visits: &mut usize,
+    ) -> Result<SubtreeFlattening> {
         // Check recursion limits
         if current_depth >= max_depth {
-            return Ok(());
+            return Ok(SubtreeFlattening { relative_weights: Vec::new(), contains_loop: false });
         }
 
         // Check for exposure loops
@@ -1036,14 +1573,36 @@ impl FundExposureService {
                 loop_depth: current_depth,
                 risk_score: self.calculate_loop_risk_score(cumulative_weight, current_depth),
             });
-            return Ok(());
+            return Ok(SubtreeFlattening { relative_weights: Vec::new(), contains_loop: true });
+        }
+
+        // Replay an already-flattened subtree at this path's weight
+        if let Some(relative_weights) = subtree_memo.get(&(fund_id.to_string(), current_depth)) {
+            for (key, relative_weight) in relative_weights {
+                if let Some(exposure) = flattened_exposures.get_mut(key) {
+                    exposure.cumulative_weight += cumulative_weight * relative_weight;
+                    exposure.risk_contribution = self.calculate_risk_contribution(exposure.cumulative_weight, exposure.recursion_depth);
+                }
+            }
+            return Ok(SubtreeFlattening { relative_weights: relative_weights.clone(), contains_loop: false });
+        }
+
+        *visits += 1;
+        if *visits > self.max_flatten_visits {
+            return Err(anyhow::anyhow!(
+                "Exposure flattening exceeded visit budget of {} funds", self.max_flatten_visits
+            ));
         }
 
         recursion_stack.push(fund_id.to_string());
+        let mut subtree = SubtreeFlattening { relative_weights: Vec::new(), contains_loop: false };
 
         // Get fund exposures
-        let graph = self.exposure_graph.read().await;
-        if let Some(fund_exposures) = graph.edges.get(fund_id) {
+        let fund_exposures = {
+            let graph = self.exposure_graph.read().await;
+            graph.edges.get(fund_id).cloned()
+        };
+        if let Some(fund_exposures) = fund_exposures {
             let mut current_graph = NestedExposureGraph {
                 fund_id: fund_id.to_string(),
                 depth: current_depth,
@@ -1052,24 +1611,28 @@ impl FundExposureService {
                 exposure_concentration: 0.0,
             };
 
-            for exposure in fund_exposures {
+            for exposure in &fund_exposures {
                 let exposure_weight_bps = (exposure.exposure_percentage * 100.0) as u16;
 
                 // Only process exposures above threshold
                 if exposure_weight_bps >= weight_threshold_bps {
-                    let nested_weight = cumulative_weight * (exposure.exposure_percentage / 100.0);
+                    let direct_weight = exposure.exposure_percentage / 100.0;
+                    let nested_weight = cumulative_weight * direct_weight;
 
-                    // Add to flattened exposures
+                    // Add to flattened exposures, accumulating weight reached via other paths
                     let flattened_key = format!("{}_{}", fund_id, exposure.to_fund);
-                    flattened_exposures.insert(flattened_key.clone(), FlattenedExposure {
+                    let flattened = flattened_exposures.entry(flattened_key.clone()).or_insert(FlattenedExposure {
                         from_fund: fund_id.to_string(),
                         to_fund: exposure.to_fund.clone(),
-                        direct_weight: exposure.exposure_percentage / 100.0,
-                        cumulative_weight: nested_weight,
+                        direct_weight,
+                        cumulative_weight: 0.0,
                         recursion_depth: current_depth,
                         exposure_type: exposure.exposure_type.clone(),
-                        risk_contribution: self.calculate_risk_contribution(nested_weight, current_depth),
+                        risk_contribution: 0.0,
                     });
+                    flattened.cumulative_weight += nested_weight;
+                    flattened.risk_contribution = self.calculate_risk_contribution(flattened.cumulative_weight, flattened.recursion_depth);
+                    subtree.relative_weights.push((flattened_key, direct_weight));
 
                     // Add to current graph
                     current_graph.direct_exposures.push(DirectExposureInfo {
@@ -1078,7 +1641,7 @@ impl FundExposureService {
                         exposure_type: exposure.exposure_type.clone(),
                         risk_metrics: self.calculate_exposure_risk_metrics(exposure).await?,
                     });
-                    current_graph.total_exposure_weight += exposure.exposure_percentage / 100.0;
+                    current_graph.total_exposure_weight += direct_weight;
 
                     // Check for multi-fund shareholding
                     if visited_funds.contains(&exposure.to_fund) {
@@ -1093,7 +1656,7 @@ impl FundExposureService {
                     visited_funds.insert(exposure.to_fund.clone());
 
                     // Recurse into nested fund
-                    self.flatten_fund_exposures_recursive(
+                    let nested = self.flatten_fund_exposures_recursive(
                         &exposure.to_fund,
                         nested_weight,
                         current_depth + 1,
@@ -1105,7 +1668,14 @@ impl FundExposureService {
                         multi_fund_shareholdings,
                         visited_funds,
                         recursion_stack,
+                        subtree_memo,
+                        visits,
                     ).await?;
+
+                    subtree.contains_loop |= nested.contains_loop;
+                    subtree.relative_weights.extend(
+                        nested.relative_weights.into_iter().map(|(key, weight)| (key, weight * direct_weight))
+                    );
                 }
             }
 
@@ -1115,7 +1685,12 @@ impl FundExposureService {
         }
 
         recursion_stack.pop();
-        Ok(())
+
+        // Subtrees that closed a loop depend on the current ancestry and can't be replayed
+        if !subtree.contains_loop {
+            subtree_memo.insert((fund_id.to_string(), current_depth), subtree.relative_weights.clone());
+        }
+        Ok(subtree)
     }
 
     // Helper methods for recursive flattening
@@ -1220,6 +1795,41 @@ impl FundExposureService {
         Ok(systemic_risks)
     }
 
+    /// Broadcast critical risks not already alerted, and forget this root's alerts that have cleared
+    async fn broadcast_critical_risks(&self, root_fund_id: &str, systemic_risks: &[SystemicRisk]) {
+        let critical: HashMap<String, &SystemicRisk> = systemic_risks.iter()
+            .filter(|risk| matches!(risk.severity, RiskSeverity::Critical))
+            .map(|risk| (Self::risk_key(risk), risk))
+            .collect();
+
+        let mut alerted = self.alerted_risks.write().await;
+        alerted.retain(|key, raised_by| raised_by != root_fund_id || critical.contains_key(key));
+
+        for (risk_key, risk) in critical {
+            if alerted.contains_key(&risk_key) {
+                continue;
+            }
+            alerted.insert(risk_key.clone(), root_fund_id.to_string());
+            error!("🚨 Critical systemic risk: {}", risk.description);
+            // No subscribers is not an error
+            let _ = self.risk_alert_sender.send(SystemicRiskAlert {
+                risk_key,
+                root_fund_id: root_fund_id.to_string(),
+                risk: risk.clone(),
+                raised_at: chrono::Utc::now().timestamp(),
+            });
+        }
+    }
+
+    /// The same loop is reported starting from whichever fund the analysis entered it at,
+    /// so funds are sorted rather than kept in path order
+    fn risk_key(risk: &SystemicRisk) -> String {
+        let mut funds = risk.affected_funds.clone();
+        funds.sort();
+        funds.dedup();
+        format!("{:?}:{}", risk.risk_type, funds.join(","))
+    }
+
     /// PRD: "fund fork must derive" - Advanced Fund Fork Derivation System
     pub async fn verify_fund_fork_derivation(
         &self,
@@ -1247,6 +1857,7 @@ impl FundExposureService {
 
         // Verify asset migration integrity
         let asset_migration_valid = self.verify_asset_migration_proof(
+            &parent_metadata,
             &derivation_proof.asset_migration_proof,
         ).await?;
 
@@ -1256,16 +1867,21 @@ impl FundExposureService {
             &derivation_proof,
         ).await?;
 
+        let derivation_valid = legal_continuity_valid && dao_approval_valid && asset_migration_valid;
+        if derivation_valid {
+            self.inherit_parent_whitelist(&child_fund_id, &parent_fund_id, &child_origin_hash).await;
+        }
+
         let result = ForkDerivationResult {
             child_fund_id,
             parent_fund_id,
-            derivation_valid: legal_continuity_valid && dao_approval_valid && asset_migration_valid,
+            derivation_valid,
             child_origin_hash,
             legal_continuity_verified: legal_continuity_valid,
             dao_approval_verified: dao_approval_valid,
             asset_migration_verified: asset_migration_valid,
             derivation_timestamp: chrono::Utc::now().timestamp(),
-            compliance_status: if legal_continuity_valid && dao_approval_valid && asset_migration_valid {
+            compliance_status: if derivation_valid {
                 ComplianceStatus::Compliant
             } else {
                 ComplianceStatus::NonCompliant
@@ -1276,6 +1892,75 @@ impl FundExposureService {
         Ok(result)
     }
 
+    /// SHA-256 over the parent's origin hash and the canonical JSON of the fork proof
+    /// No timestamps are hashed, so auditors can recompute the child's origin from the same inputs.
+    async fn generate_derived_origin_hash(&self, parent_origin_hash: &str, derivation_proof: &ForkDerivationProof) -> Result<String> {
+        let mut hasher = Sha256::new();
+        hasher.update(DomainTag::DerivedFundOrigin.as_bytes());
+        // Length prefix keeps the parent hash and proof encoding from running together
+        hasher.update((parent_origin_hash.len() as u64).to_le_bytes());
+        hasher.update(parent_origin_hash.as_bytes());
+        hasher.update(canonical_json::to_canonical_json(derivation_proof)?.as_bytes());
+        Ok(hex::encode(hasher.finalize()))
+    }
+
+    async fn get_fund_metadata(&self, fund_id: &str) -> Result<FundMetadata> {
+        self.fund_registry.read().await.get(fund_id).cloned()
+            .ok_or_else(|| anyhow::anyhow!("Fund not registered: {}", fund_id))
+    }
+
+    /// The child's jurisdiction chain must extend the parent's, so the fork stays within
+    /// the parent's legal lineage
+    async fn verify_legal_continuity(&self, parent_legal: &LegalAncestry, legal_continuity_proof: &str) -> Result<bool> {
+        let Ok(child_legal) = serde_json::from_str::<LegalAncestry>(legal_continuity_proof) else {
+            warn!("⚠️ Malformed legal continuity proof");
+            return Ok(false);
+        };
+        let parent_chain = &parent_legal.jurisdiction_chain;
+        Ok(!parent_chain.is_empty() && child_legal.jurisdiction_chain.starts_with(parent_chain))
+    }
+
+    /// The fork's governance transaction must be one the parent DAO recorded
+    async fn verify_dao_fork_approval(&self, parent_dao: &DaoAncestry, dao_approval_tx: &str) -> Result<bool> {
+        let dao_approval_tx = dao_approval_tx.trim();
+        Ok(!dao_approval_tx.is_empty()
+            && parent_dao.dao_proposal_history.iter().any(|tx| tx.trim().eq_ignore_ascii_case(dao_approval_tx)))
+    }
+
+    /// Migrated assets must sum exactly to the declared amount, be assets the parent holds,
+    /// and fit within the parent's total assets as recorded in the exposure graph rather than
+    /// as reported by the fork
+    async fn verify_asset_migration_proof(&self, parent: &FundMetadata, asset_migration_proof: &str) -> Result<bool> {
+        let Ok(proof) = serde_json::from_str::<AssetMigrationProof>(asset_migration_proof) else {
+            warn!("⚠️ Malformed asset migration proof");
+            return Ok(false);
+        };
+        let migrated = proof.migrated_assets.iter()
+            .try_fold(0u64, |total, asset| total.checked_add(asset.amount));
+        if proof.declared_amount == 0 || migrated != Some(proof.declared_amount) {
+            return Ok(false);
+        }
+
+        if let Some(asset) = proof.migrated_assets.iter().find(|asset| !parent.target_assets.contains(&asset.asset_id)) {
+            warn!("⚠️ Fork migrates {} which parent {} does not hold", asset.asset_id, parent.fund_id);
+            return Ok(false);
+        }
+
+        let graph = self.exposure_graph.read().await;
+        let Some(parent_node) = graph.nodes.get(&parent.fund_id) else {
+            warn!("⚠️ Parent {} has no recorded assets to migrate from", parent.fund_id);
+            return Ok(false);
+        };
+        if proof.declared_amount > parent_node.total_assets {
+            warn!(
+                "⚠️ Fork migrates {} but parent {} holds {}",
+                proof.declared_amount, parent.fund_id, parent_node.total_assets
+            );
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
     /// PRD: "explicitly whitelisted" - Advanced Fund Whitelisting System
     pub async fn manage_fund_whitelist(
         &self,
@@ -1286,8 +1971,10 @@ impl FundExposureService {
         info!("📋 Managing fund whitelist: {} - {:?}", fund_id, whitelist_action);
 
         // Verify authorization
-        let auth_valid = self.verify_whitelist_authorization(&authorization).await?;
-        require!(auth_valid, "Invalid whitelist authorization");
+        let auth_valid = self.verify_whitelist_authorization(&fund_id, &whitelist_action, &authorization).await?;
+        if !auth_valid {
+            return Err(anyhow::anyhow!("Invalid whitelist authorization"));
+        }
 
         let mut whitelist = self.fund_whitelist.write().await;
 
@@ -1356,6 +2043,81 @@ impl FundExposureService {
         Ok(result)
     }
 
+    /// Current whitelist entry for a fund, if any
+    pub async fn get_whitelist_entry(&self, fund_id: &str) -> Option<WhitelistEntry> {
+        self.fund_whitelist.read().await.get(fund_id).cloned()
+    }
+
+    /// Whether the fund holds an unexpired explicit or derived whitelisting
+    pub async fn is_whitelisted(&self, fund_id: &str) -> bool {
+        self.fund_whitelist.read().await.get(fund_id)
+            .is_some_and(|entry| Self::whitelist_entry_active(entry, chrono::Utc::now().timestamp()))
+    }
+
+    fn whitelist_entry_active(entry: &WhitelistEntry, now: i64) -> bool {
+        matches!(entry.whitelist_status, WhitelistStatus::ExplicitlyWhitelisted | WhitelistStatus::DerivedFromWhitelisted)
+            && entry.expiry_timestamp > now
+    }
+
+    /// Grant a validly derived fork `DerivedFromWhitelisted` status when its parent is
+    /// explicitly whitelisted; the child inherits the parent's expiry and conditions.
+    /// A child that already has an entry keeps it, so a decision made about the child
+    /// itself (a rejection in particular) is never replaced by one inferred from its parent.
+    async fn inherit_parent_whitelist(&self, child_fund_id: &str, parent_fund_id: &str, child_origin_hash: &str) {
+        let now = chrono::Utc::now().timestamp();
+        let mut whitelist = self.fund_whitelist.write().await;
+        if let Some(existing) = whitelist.get(child_fund_id) {
+            info!("📋 Fund {} keeps its {:?} whitelist entry instead of deriving from {}",
+                  child_fund_id, existing.whitelist_status, parent_fund_id);
+            return;
+        }
+        let Some(parent_entry) = whitelist.get(parent_fund_id)
+            .filter(|entry| matches!(entry.whitelist_status, WhitelistStatus::ExplicitlyWhitelisted))
+            .filter(|entry| Self::whitelist_entry_active(entry, now))
+            .cloned()
+        else {
+            return;
+        };
+
+        info!("📋 Fund {} derives whitelist status from {}", child_fund_id, parent_fund_id);
+        whitelist.insert(child_fund_id.to_string(), WhitelistEntry {
+            fund_id: child_fund_id.to_string(),
+            whitelist_status: WhitelistStatus::DerivedFromWhitelisted,
+            authorized_by: parent_fund_id.to_string(),
+            authorization_proof: child_origin_hash.to_string(),
+            whitelist_timestamp: now,
+            expiry_timestamp: parent_entry.expiry_timestamp,
+            conditions: parent_entry.conditions,
+        });
+    }
+
+    /// Check the authorization is an unexpired, unused signature by a registered
+    /// authorizer over this exact change, and spend it
+    async fn verify_whitelist_authorization(
+        &self,
+        fund_id: &str,
+        action: &WhitelistAction,
+        authorization: &WhitelistAuthorization,
+    ) -> Result<bool> {
+        let Some(public_key) = self.whitelist_authorizers.get(&authorization.authorizer_address) else {
+            warn!("⚠️ Whitelist authorization from unregistered authorizer {}", authorization.authorizer_address);
+            return Ok(false);
+        };
+        if authorization.expiry_timestamp <= chrono::Utc::now().timestamp() {
+            return Ok(false);
+        }
+
+        let (Ok(public_key), Ok(signature)) = (hex::decode(public_key), hex::decode(&authorization.signature)) else {
+            return Ok(false);
+        };
+        let message = whitelist_authorization_message(fund_id, action, authorization);
+        if verify_ed25519(&public_key, &message, &signature).is_err() {
+            return Ok(false);
+        }
+
+        Ok(self.used_whitelist_authorizations.write().await.insert(authorization.signature.to_lowercase()))
+    }
+
     /// PRD: "legal ancestry" - Advanced Legal Ancestry Verification
     pub async fn verify_comprehensive_legal_ancestry(
         &self,
@@ -1544,7 +2306,8 @@ impl FundExposureService {
             .collect();
 
         // Classify market concentration
-        let market_concentration = self.classify_market_concentration(hhi_score, cr4);
+        let funds_with_exposure = fund_exposures.values().filter(|&&exposure| exposure > 0.0).count();
+        let market_concentration = self.classify_market_concentration(hhi_score, cr4, funds_with_exposure);
 
         let result = HerfindahlIndexResult {
             hhi_score,
@@ -1557,30 +2320,29 @@ impl FundExposureService {
             analysis_timestamp: chrono::Utc::now().timestamp(),
         };
 
-        info!("✅ HHI calculation completed - Score: {:.2}, Concentration: {:?}", hhi_score, market_concentration);
+        info!("✅ HHI calculation completed - Score: {:.2}, Concentration: {:?}", hhi_score, result.market_concentration);
         Ok(result)
     }
 
     /// Calculate Gini coefficient for inequality measurement
+    /// Uses the sorted-cumulative form G = 2·Σ i·x_i / (n·Σ x_i) - (n+1)/n (1-indexed, ascending)
     async fn calculate_gini_coefficient(&self, exposures: &HashMap<String, f64>) -> Result<f64> {
         let mut values: Vec<f64> = exposures.values().cloned().collect();
-        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        values.sort_by(f64::total_cmp);
 
         let n = values.len() as f64;
-        let mean = values.iter().sum::<f64>() / n;
+        let total: f64 = values.iter().sum();
 
-        if mean == 0.0 {
+        if values.is_empty() || total == 0.0 {
             return Ok(0.0);
         }
 
-        let mut sum_diff = 0.0;
-        for i in 0..values.len() {
-            for j in 0..values.len() {
-                sum_diff += (values[i] - values[j]).abs();
-            }
-        }
+        let weighted_sum: f64 = values.iter()
+            .enumerate()
+            .map(|(i, &x)| (i + 1) as f64 * x)
+            .sum();
 
-        let gini = sum_diff / (2.0 * n * n * mean);
+        let gini = (2.0 * weighted_sum) / (n * total) - (n + 1.0) / n;
         Ok(gini)
     }
 
@@ -1591,11 +2353,808 @@ impl FundExposureService {
     }
 
     /// Classify market concentration based on HHI and CR4
-    fn classify_market_concentration(&self, hhi: f64, cr4: f64) -> MarketConcentration {
-        match hhi {
-            h if h < 1500.0 => MarketConcentration::Unconcentrated,
-            h if h < 2500.0 => MarketConcentration::ModeratelyConcentrated,
-            _ => MarketConcentration::HighlyConcentrated,
+    /// - no funds with exposure: NoConcentration
+    /// - a single fund holding everything: HighlyConcentrated
+    /// - HHI >= 2500 or CR4 above the configured threshold: HighlyConcentrated
+    /// - HHI >= 1500: ModeratelyConcentrated
+    /// - otherwise: Unconcentrated
+    fn classify_market_concentration(&self, hhi: f64, cr4: f64, funds_with_exposure: usize) -> MarketConcentration {
+        match funds_with_exposure {
+            0 => return MarketConcentration::NoConcentration,
+            1 => return MarketConcentration::HighlyConcentrated,
+            _ => {}
+        }
+
+        if hhi >= 2500.0 || cr4 > self.cr4_concentration_threshold {
+            MarketConcentration::HighlyConcentrated
+        } else if hhi >= 1500.0 {
+            MarketConcentration::ModeratelyConcentrated
+        } else {
+            MarketConcentration::Unconcentrated
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn test_service() -> FundExposureService {
+        FundExposureService::new(5, 0.25).await.unwrap()
+            .with_whitelist_authorizer("governance", hex::encode(test_authorizer().verifying_key().to_bytes()))
+    }
+
+    async fn add_test_fund(service: &FundExposureService, fund_id: &str) {
+        let mut graph = service.exposure_graph.write().await;
+        graph.nodes.insert(fund_id.to_string(), FundNode {
+            fund_id: fund_id.to_string(),
+            fund_origin_hash: format!("origin_{}", fund_id),
+            total_assets: 1_000,
+            nav_per_share: 1_000_000,
+            fund_type: FundType::Primary,
+            jurisdiction: "US".to_string(),
+            creation_timestamp: 0,
+            last_nav_update: 0,
+            status: FundStatus::Active,
+        });
+        graph.edges.insert(fund_id.to_string(), Vec::new());
+        graph.total_funds += 1;
+    }
+
+    fn test_origin_proof(fund_id: &str) -> FundOriginProof {
+        FundOriginProof {
+            vault_origin_hash: format!("origin_{}", fund_id),
+            legal_ancestry: LegalAncestry {
+                legal_entity_id: format!("{}_lp", fund_id),
+                incorporation_documents: Vec::new(),
+                regulatory_approvals: Vec::new(),
+                compliance_certifications: Vec::new(),
+                legal_opinion_hash: "legal_opinion".to_string(),
+                jurisdiction_chain: vec!["US".to_string(), "US-DE".to_string()],
+            },
+            dao_ancestry: DaoAncestry {
+                governance_contract_address: "gov".to_string(),
+                dao_proposal_history: vec!["0xabc123".to_string(), "0xfork42".to_string()],
+                voting_power_distribution: HashMap::new(),
+                governance_token_address: "token".to_string(),
+                multisig_signers: Vec::new(),
+                governance_parameters: HashMap::new(),
+            },
+            circuit_ancestry: CircuitAncestry {
+                zknav_circuit_hash: "circuit".to_string(),
+                verification_key_hash: "vk".to_string(),
+                circuit_parameters: HashMap::new(),
+                trusted_setup_ceremony: "ceremony".to_string(),
+                circuit_audit_reports: Vec::new(),
+                upgrade_history: Vec::new(),
+            },
+            signed_snapshot: SignedSnapshot {
+                snapshot_hash: String::new(),
+                snapshot_data: String::new(),
+                signatures: Vec::new(),
+                merkle_root: String::new(),
+                block_height: 0,
+                timestamp: 0,
+            },
+            whitelist_status: WhitelistStatus::PendingApproval,
+            fork_derivation_proof: None,
+            created_at: 0,
+            version: 1,
+        }
+    }
+
+    async fn add_registered_fund(service: &FundExposureService, fund_id: &str) {
+        add_test_fund(service, fund_id).await;
+        service.fund_registry.write().await.insert(fund_id.to_string(), FundMetadata {
+            fund_id: fund_id.to_string(),
+            name: fund_id.to_string(),
+            manager: "manager".to_string(),
+            inception_date: 0,
+            fund_origin_proof: test_origin_proof(fund_id),
+            legal_structure: "LP".to_string(),
+            domicile: "US-DE".to_string(),
+            base_currency: "USD".to_string(),
+            investment_strategy: "fund of funds".to_string(),
+            target_assets: vec!["t-bill".to_string(), "usdc".to_string()],
+        });
+    }
+
+    /// Fork of "parent" that passes every derivation sub-check
+    fn test_fork_proof() -> ForkDerivationProof {
+        let mut child_legal = test_origin_proof("child").legal_ancestry;
+        child_legal.jurisdiction_chain.push("US-DE-SPV".to_string());
+        ForkDerivationProof {
+            parent_fund_id: "parent".to_string(),
+            fork_reason: "strategy split".to_string(),
+            derivation_proof: "derivation".to_string(),
+            dao_approval_tx: "0xFORK42".to_string(),
+            legal_continuity_proof: serde_json::to_string(&child_legal).unwrap(),
+            asset_migration_proof: serde_json::to_string(&AssetMigrationProof {
+                declared_amount: 750,
+                migrated_assets: vec![
+                    MigratedAsset { asset_id: "t-bill".to_string(), amount: 500 },
+                    MigratedAsset { asset_id: "usdc".to_string(), amount: 250 },
+                ],
+            }).unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fork_derivation_sub_checks_fail_independently() {
+        let service = test_service().await;
+        add_registered_fund(&service, "parent").await;
+        let derive = |proof: ForkDerivationProof| {
+            service.verify_fund_fork_derivation("child".to_string(), "parent".to_string(), proof)
+        };
+
+        let valid = derive(test_fork_proof()).await.unwrap();
+        assert!(valid.derivation_valid);
+        assert!(matches!(valid.compliance_status, ComplianceStatus::Compliant));
+
+        // Child moved to a jurisdiction outside the parent's chain
+        let mut proof = test_fork_proof();
+        let mut child_legal = test_origin_proof("child").legal_ancestry;
+        child_legal.jurisdiction_chain = vec!["KY".to_string()];
+        proof.legal_continuity_proof = serde_json::to_string(&child_legal).unwrap();
+        let result = derive(proof).await.unwrap();
+        assert_eq!(
+            (result.legal_continuity_verified, result.dao_approval_verified, result.asset_migration_verified),
+            (false, true, true)
+        );
+        assert!(!result.derivation_valid);
+
+        // Governance tx the parent DAO never recorded
+        let mut proof = test_fork_proof();
+        proof.dao_approval_tx = "0xunknown".to_string();
+        let result = derive(proof).await.unwrap();
+        assert_eq!(
+            (result.legal_continuity_verified, result.dao_approval_verified, result.asset_migration_verified),
+            (true, false, true)
+        );
+        assert!(matches!(result.compliance_status, ComplianceStatus::NonCompliant));
+
+        // Migrated assets fall short of the declared amount
+        let mut proof = test_fork_proof();
+        proof.asset_migration_proof = serde_json::to_string(&AssetMigrationProof {
+            declared_amount: 750,
+            migrated_assets: vec![MigratedAsset { asset_id: "t-bill".to_string(), amount: 500 }],
+        }).unwrap();
+        let result = derive(proof).await.unwrap();
+        assert_eq!(
+            (result.legal_continuity_verified, result.dao_approval_verified, result.asset_migration_verified),
+            (true, true, false)
+        );
+        assert!(!result.derivation_valid);
+
+        // Self-consistent proof claiming more than the parent's recorded 1_000 in assets
+        let mut proof = test_fork_proof();
+        proof.asset_migration_proof = serde_json::to_string(&AssetMigrationProof {
+            declared_amount: 1_500,
+            migrated_assets: vec![
+                MigratedAsset { asset_id: "t-bill".to_string(), amount: 1_000 },
+                MigratedAsset { asset_id: "usdc".to_string(), amount: 500 },
+            ],
+        }).unwrap();
+        assert!(!derive(proof).await.unwrap().asset_migration_verified);
+
+        // Asset the parent never held
+        let mut proof = test_fork_proof();
+        proof.asset_migration_proof = serde_json::to_string(&AssetMigrationProof {
+            declared_amount: 750,
+            migrated_assets: vec![MigratedAsset { asset_id: "gold".to_string(), amount: 750 }],
+        }).unwrap();
+        assert!(!derive(proof).await.unwrap().asset_migration_verified);
+
+        assert!(service.verify_fund_fork_derivation("child".to_string(), "missing".to_string(), test_fork_proof()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_derived_origin_hash_is_deterministic_and_input_bound() {
+        let service = test_service().await;
+        let proof = test_fork_proof();
+        let hash = service.generate_derived_origin_hash("origin_parent", &proof).await.unwrap();
+
+        assert_eq!(hash.len(), 64);
+        assert_eq!(service.generate_derived_origin_hash("origin_parent", &proof).await.unwrap(), hash);
+        assert_ne!(service.generate_derived_origin_hash("origin_other", &proof).await.unwrap(), hash);
+
+        let variants: Vec<fn(&mut ForkDerivationProof)> = vec![
+            |p| p.parent_fund_id.push('x'),
+            |p| p.fork_reason.push('x'),
+            |p| p.derivation_proof.push('x'),
+            |p| p.dao_approval_tx.push('x'),
+            |p| p.legal_continuity_proof.push('x'),
+            |p| p.asset_migration_proof.push('x'),
+        ];
+        for alter in variants {
+            let mut altered = proof.clone();
+            alter(&mut altered);
+            assert_ne!(service.generate_derived_origin_hash("origin_parent", &altered).await.unwrap(), hash);
+        }
+
+        // Reported through fork verification unchanged
+        add_registered_fund(&service, "parent").await;
+        let result = service.verify_fund_fork_derivation("child".to_string(), "parent".to_string(), proof).await.unwrap();
+        assert_eq!(result.child_origin_hash, hash);
+    }
+
+    #[tokio::test]
+    async fn test_expired_whitelist_entry_is_not_whitelisted() {
+        let service = test_service().await;
+        assert!(!service.is_whitelisted("fund_a").await);
+
+        service.manage_fund_whitelist("fund_a".to_string(), WhitelistAction::Add, test_authorization("fund_a", WhitelistAction::Add)).await.unwrap();
+        assert!(service.is_whitelisted("fund_a").await);
+
+        service.fund_whitelist.write().await.get_mut("fund_a").unwrap().expiry_timestamp = chrono::Utc::now().timestamp() - 1;
+        assert!(!service.is_whitelisted("fund_a").await);
+        assert!(service.get_whitelist_entry("fund_a").await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_valid_fork_of_whitelisted_parent_inherits_whitelist() {
+        let service = test_service().await;
+        add_registered_fund(&service, "parent").await;
+
+        // No inheritance before the parent is whitelisted
+        service.verify_fund_fork_derivation("child".to_string(), "parent".to_string(), test_fork_proof()).await.unwrap();
+        assert!(!service.is_whitelisted("child").await);
+
+        service.manage_fund_whitelist("parent".to_string(), WhitelistAction::Add, test_authorization("parent", WhitelistAction::Add)).await.unwrap();
+        let parent = service.get_whitelist_entry("parent").await.unwrap();
+
+        let mut invalid = test_fork_proof();
+        invalid.dao_approval_tx = "0xunknown".to_string();
+        service.verify_fund_fork_derivation("rogue".to_string(), "parent".to_string(), invalid).await.unwrap();
+        assert!(!service.is_whitelisted("rogue").await);
+
+        let result = service.verify_fund_fork_derivation("child".to_string(), "parent".to_string(), test_fork_proof()).await.unwrap();
+        assert!(service.is_whitelisted("child").await);
+        let child = service.get_whitelist_entry("child").await.unwrap();
+        assert!(matches!(child.whitelist_status, WhitelistStatus::DerivedFromWhitelisted));
+        assert_eq!(child.expiry_timestamp, parent.expiry_timestamp);
+        assert_eq!(child.authorization_proof, result.child_origin_hash);
+
+        // Derived status does not chain to grandchildren
+        let mut grandchild_proof = test_fork_proof();
+        grandchild_proof.parent_fund_id = "child".to_string();
+        add_registered_fund(&service, "child").await;
+        service.verify_fund_fork_derivation("grandchild".to_string(), "child".to_string(), grandchild_proof).await.unwrap();
+        assert!(!service.is_whitelisted("grandchild").await);
+    }
+
+    #[tokio::test]
+    async fn test_fork_does_not_override_existing_child_entry() {
+        let service = test_service().await;
+        add_registered_fund(&service, "parent").await;
+        service.manage_fund_whitelist("parent".to_string(), WhitelistAction::Add, test_authorization("parent", WhitelistAction::Add)).await.unwrap();
+
+        for (child, status) in [("rejected", WhitelistStatus::Rejected), ("under_review", WhitelistStatus::UnderReview)] {
+            service.fund_whitelist.write().await.insert(child.to_string(), WhitelistEntry {
+                fund_id: child.to_string(),
+                whitelist_status: status,
+                authorized_by: "governance".to_string(),
+                authorization_proof: String::new(),
+                whitelist_timestamp: 0,
+                expiry_timestamp: i64::MAX,
+                conditions: Vec::new(),
+            });
+
+            service.verify_fund_fork_derivation(child.to_string(), "parent".to_string(), test_fork_proof()).await.unwrap();
+            assert!(!service.is_whitelisted(child).await);
+            assert_eq!(service.get_whitelist_entry(child).await.unwrap().authorized_by, "governance");
+        }
+        let rejected = service.get_whitelist_entry("rejected").await.unwrap();
+        assert!(matches!(rejected.whitelist_status, WhitelistStatus::Rejected));
+    }
+
+    async fn add_edge(service: &FundExposureService, from: &str, to: &str) {
+        service.add_fund_exposure(
+            from.to_string(),
+            to.to_string(),
+            1,
+            ExposureType::DirectInvestment,
+            "proof".to_string(),
+        ).await.unwrap();
+    }
+
+    fn test_authorizer() -> ed25519_dalek::SigningKey {
+        ed25519_dalek::SigningKey::from_bytes(&[13u8; 32])
+    }
+
+    fn signed_authorization(fund_id: &str, action: WhitelistAction, expiry_timestamp: i64) -> WhitelistAuthorization {
+        let mut authorization = WhitelistAuthorization {
+            authorizer_address: "governance".to_string(),
+            signature: String::new(),
+            expiry_timestamp,
+            conditions: vec!["kyc_verified".to_string()],
+            authorization_level: AuthorizationLevel::Governance,
+        };
+        let message = whitelist_authorization_message(fund_id, &action, &authorization);
+        authorization.signature = hex::encode(ed25519_dalek::Signer::sign(&test_authorizer(), &message).to_bytes());
+        authorization
+    }
+
+    fn test_authorization(fund_id: &str, action: WhitelistAction) -> WhitelistAuthorization {
+        signed_authorization(fund_id, action, chrono::Utc::now().timestamp() + 3600)
+    }
+
+    #[tokio::test]
+    async fn test_whitelist_add_remove_round_trip() {
+        let service = test_service().await;
+
+        let added = service.manage_fund_whitelist("fund_a".to_string(), WhitelistAction::Add, test_authorization("fund_a", WhitelistAction::Add)).await.unwrap();
+        assert!(added.success);
+        let entry = service.get_whitelist_entry("fund_a").await.unwrap();
+        assert!(matches!(entry.whitelist_status, WhitelistStatus::ExplicitlyWhitelisted));
+        assert_eq!(entry.authorized_by, "governance");
+        assert_eq!(entry.conditions, vec!["kyc_verified".to_string()]);
+
+        let removed = service.manage_fund_whitelist("fund_a".to_string(), WhitelistAction::Remove, test_authorization("fund_a", WhitelistAction::Remove)).await.unwrap();
+        assert!(removed.success);
+        assert!(service.get_whitelist_entry("fund_a").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_whitelist_rejects_expired_authorization() {
+        let service = test_service().await;
+        let authorization = signed_authorization("fund_a", WhitelistAction::Add, chrono::Utc::now().timestamp() - 1);
+
+        assert!(service.manage_fund_whitelist("fund_a".to_string(), WhitelistAction::Add, authorization).await.is_err());
+        assert!(service.get_whitelist_entry("fund_a").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_whitelist_rejects_unsigned_forged_or_misdirected_authorizations() {
+        let service = test_service().await;
+
+        let mut unsigned = test_authorization("fund_a", WhitelistAction::Add);
+        unsigned.signature = "sig".to_string();
+        assert!(service.manage_fund_whitelist("fund_a".to_string(), WhitelistAction::Add, unsigned).await.is_err());
+
+        let mut unregistered = test_authorization("fund_a", WhitelistAction::Add);
+        unregistered.authorizer_address = "someone".to_string();
+        assert!(service.manage_fund_whitelist("fund_a".to_string(), WhitelistAction::Add, unregistered).await.is_err());
+
+        // A signature over one fund or action does not authorize another
+        let for_fund_b = test_authorization("fund_b", WhitelistAction::Add);
+        assert!(service.manage_fund_whitelist("fund_a".to_string(), WhitelistAction::Add, for_fund_b).await.is_err());
+        let for_removal = test_authorization("fund_a", WhitelistAction::Remove);
+        assert!(service.manage_fund_whitelist("fund_a".to_string(), WhitelistAction::Add, for_removal).await.is_err());
+
+        assert!(service.get_whitelist_entry("fund_a").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_whitelist_authorization_cannot_be_replayed() {
+        let service = test_service().await;
+        let add = test_authorization("fund_a", WhitelistAction::Add);
+        service.manage_fund_whitelist("fund_a".to_string(), WhitelistAction::Add, add.clone()).await.unwrap();
+        let remove = test_authorization("fund_a", WhitelistAction::Remove);
+        service.manage_fund_whitelist("fund_a".to_string(), WhitelistAction::Remove, remove).await.unwrap();
+
+        assert!(service.manage_fund_whitelist("fund_a".to_string(), WhitelistAction::Add, add).await.is_err());
+        assert!(!service.is_whitelisted("fund_a").await);
+    }
+
+    #[tokio::test]
+    async fn test_new_edge_invalidates_cached_no_cycle() {
+        let service = test_service().await;
+        for fund in ["fund_a", "fund_b"] {
+            add_test_fund(&service, fund).await;
+        }
+
+        add_edge(&service, "fund_a", "fund_b").await;
+        assert!(!service.detect_circular_dependency("fund_a").await.unwrap().circular_detected);
+        assert_eq!(service.circular_dependency_cache.read().await.get("fund_a"), Some(&false));
+
+        add_edge(&service, "fund_b", "fund_a").await;
+        let result = service.detect_circular_dependency("fund_a").await.unwrap();
+        assert!(result.circular_detected);
+        assert_eq!(result.cycle_path, vec!["fund_a", "fund_b", "fund_a"]);
+    }
+
+    #[tokio::test]
+    async fn test_detect_all_cycles_reports_disjoint_rings() {
+        let service = test_service().await;
+        for fund in ["fund_a", "fund_b", "fund_c", "fund_d", "fund_e", "fund_f"] {
+            add_test_fund(&service, fund).await;
+        }
+
+        add_edge(&service, "fund_a", "fund_b").await;
+        add_edge(&service, "fund_b", "fund_a").await;
+        add_edge(&service, "fund_c", "fund_d").await;
+        add_edge(&service, "fund_d", "fund_e").await;
+        add_edge(&service, "fund_e", "fund_c").await;
+        add_edge(&service, "fund_e", "fund_f").await;
+
+        let cycles = service.detect_all_cycles().await;
+        let paths: Vec<Vec<String>> = cycles.iter().map(|c| c.cycle_path.clone()).collect();
+        assert_eq!(paths, vec![
+            vec!["fund_a", "fund_b", "fund_a"],
+            vec!["fund_c", "fund_d", "fund_e", "fund_c"],
+        ]);
+        assert!(cycles.iter().all(|c| c.circular_detected));
+        assert_eq!(cycles[0].total_exposure_in_cycle, 2);
+        assert_eq!(cycles[1].total_exposure_in_cycle, 3);
+    }
+
+    async fn build_graph(edges: &[(&str, &str)]) -> FundExposureService {
+        let weighted: Vec<(&str, &str, f64)> = edges.iter().map(|&(from, to)| (from, to, 0.1)).collect();
+        build_weighted_graph(&weighted).await
+    }
+
+    async fn build_weighted_graph(edges: &[(&str, &str, f64)]) -> FundExposureService {
+        let service = test_service().await;
+        let mut funds: Vec<&str> = edges.iter().flat_map(|&(from, to, _)| [from, to]).collect();
+        funds.sort();
+        funds.dedup();
+        for fund in funds {
+            add_test_fund(&service, fund).await;
+        }
+        let mut graph = service.exposure_graph.write().await;
+        for &(from, to, percentage) in edges {
+            graph.edges.get_mut(from).unwrap().push(ExposureEdge {
+                from_fund: from.to_string(),
+                to_fund: to.to_string(),
+                exposure_amount: 1,
+                exposure_percentage: percentage,
+                exposure_type: ExposureType::DirectInvestment,
+                timestamp: 0,
+                proof_hash: "proof".to_string(),
+            });
+        }
+        drop(graph);
+        service
+    }
+
+    #[tokio::test]
+    async fn test_petgraph_detection_matches_dfs() {
+        let graphs: Vec<Vec<(&str, &str)>> = vec![
+            vec![("a", "b"), ("b", "c")],
+            vec![("a", "b"), ("b", "a")],
+            vec![("a", "b"), ("a", "c"), ("b", "d"), ("c", "d")],
+            vec![("a", "b"), ("b", "c"), ("c", "b"), ("d", "a")],
+            vec![("a", "a"), ("b", "c")],
+            vec![("a", "b"), ("b", "c"), ("c", "d"), ("d", "b"), ("e", "f"), ("f", "e")],
+        ];
+
+        for edges in graphs {
+            let service = build_graph(&edges).await;
+            let graph = service.exposure_graph.read().await.clone();
+            let fund_graph = service.to_petgraph().await;
+
+            let mut any_cycle = false;
+            for fund_id in graph.nodes.keys() {
+                let mut path = Vec::new();
+                let dfs = service.dfs_cycle_detection(&graph, fund_id, &mut HashSet::new(), &mut HashSet::new(), &mut path);
+                let petgraph_cycle = FundExposureService::find_cycle_from(&fund_graph, fund_id);
+                assert_eq!(dfs, petgraph_cycle.is_some(), "{:?} from {}", edges, fund_id);
+                if let Some(cycle) = petgraph_cycle {
+                    assert_eq!(cycle.first(), cycle.last());
+                    assert!(cycle.contains(path.last().unwrap()));
+                }
+                any_cycle |= dfs;
+            }
+            assert_eq!(service.has_circular_exposure().await, any_cycle, "{:?}", edges);
+            assert_eq!(service.topological_fund_order().await.is_ok(), !any_cycle, "{:?}", edges);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_topological_order_of_fund_dag() {
+        let edges = [("a", "b"), ("a", "c"), ("b", "d"), ("c", "d")];
+        let service = build_graph(&edges).await;
+
+        let order = service.topological_fund_order().await.unwrap();
+        let position = |fund: &str| order.iter().position(|f| f == fund).unwrap();
+        for (from, to) in edges {
+            assert!(position(from) < position(to));
+        }
+
+        let cyclic = build_graph(&[("a", "b"), ("b", "c"), ("c", "a")]).await;
+        let err = cyclic.topological_fund_order().await.unwrap_err().to_string();
+        assert!(err.contains("a -> b -> c -> a") || err.contains("b -> c -> a -> b") || err.contains("c -> a -> b -> c"));
+    }
+
+    #[tokio::test]
+    async fn test_flattening_diamond_counts_each_path_once() {
+        let service = build_weighted_graph(&[
+            ("a", "b", 50.0),
+            ("a", "c", 50.0),
+            ("b", "d", 40.0),
+            ("c", "d", 20.0),
+            ("d", "e", 50.0),
+        ]).await;
+
+        let result = service.recursive_zknav_flattening("a".to_string(), 1, 5).await.unwrap();
+        let weight = |key: &str| result.flattened_exposures[key].cumulative_weight;
+
+        assert!((weight("b_d") - 0.2).abs() < 1e-12);
+        assert!((weight("c_d") - 0.1).abs() < 1e-12);
+        // d's own exposure carries the weight of both paths into it
+        assert!((weight("d_e") - 0.15).abs() < 1e-12);
+        assert_eq!(result.nested_exposure_graphs.iter().filter(|g| g.fund_id == "d").count(), 1);
+        assert!(result.exposure_loops.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_flattening_loop_detection_and_visit_budget() {
+        let service = build_weighted_graph(&[
+            ("a", "b", 50.0),
+            ("b", "c", 50.0),
+            ("c", "a", 50.0),
+        ]).await;
+
+        let result = service.recursive_zknav_flattening("a".to_string(), 1, 5).await.unwrap();
+        assert_eq!(result.exposure_loops.len(), 1);
+        assert_eq!(result.exposure_loops[0].loop_path, vec!["a", "b", "c", "a"]);
+        assert!(result.to_dot().contains(r#""c" -> "a" [label="50.00% (12.50% cumulative)", color=red];"#));
+
+        let service = service.with_flatten_visit_budget(2);
+        assert!(service.recursive_zknav_flattening("a".to_string(), 1, 5).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_critical_concentration_is_broadcast_once() {
+        let service = build_weighted_graph(&[
+            ("a", "b", 60.0),
+            ("a", "c", 30.0),
+        ]).await;
+        let mut alerts = service.subscribe_risk_alerts();
+
+        service.recursive_zknav_flattening("a".to_string(), 1, 5).await.unwrap();
+        let alert = alerts.try_recv().unwrap();
+        assert_eq!(alert.root_fund_id, "a");
+        assert_eq!(alert.risk.affected_funds, vec!["a", "b"]);
+        assert!(matches!(alert.risk.risk_type, SystemicRiskType::HighConcentration));
+        // a -> c is only High severity
+        assert!(alerts.try_recv().is_err());
+
+        service.recursive_zknav_flattening("a".to_string(), 1, 5).await.unwrap();
+        assert!(alerts.try_recv().is_err());
+
+        // Once the concentration clears, a recurrence is alerted again
+        service.exposure_graph.write().await.edges.get_mut("a").unwrap()[0].exposure_percentage = 40.0;
+        service.recursive_zknav_flattening("a".to_string(), 1, 5).await.unwrap();
+        assert!(alerts.try_recv().is_err());
+        service.exposure_graph.write().await.edges.get_mut("a").unwrap()[0].exposure_percentage = 60.0;
+        service.recursive_zknav_flattening("a".to_string(), 1, 5).await.unwrap();
+        assert_eq!(alerts.try_recv().unwrap().risk_key, alert.risk_key);
+    }
+
+    #[tokio::test]
+    async fn test_flattening_validates_depth_and_threshold() {
+        let service = build_weighted_graph(&[
+            ("a", "b", 50.0),
+            ("b", "c", 50.0),
+        ]).await;
+
+        let too_deep = service.recursive_zknav_flattening("a".to_string(), 1, 6).await.unwrap_err();
+        assert!(too_deep.to_string().contains("exceeds maximum exposure depth 5"));
+        let zero_threshold = service.recursive_zknav_flattening("a".to_string(), 0, 5).await.unwrap_err();
+        assert!(zero_threshold.to_string().contains("between 1 and 10000 bps"));
+        assert!(service.recursive_zknav_flattening("a".to_string(), 10_001, 5).await.is_err());
+
+        // Reported depth is how far the graph went, not the requested limit
+        let result = service.recursive_zknav_flattening("a".to_string(), 1, 5).await.unwrap();
+        assert_eq!(result.total_recursion_depth, 2);
+        let result = service.recursive_zknav_flattening("a".to_string(), 1, 1).await.unwrap();
+        assert_eq!(result.total_recursion_depth, 1);
+    }
+
+    fn naive_gini(values: &[f64]) -> f64 {
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let sum_diff: f64 = values.iter()
+            .flat_map(|a| values.iter().map(move |b| (a - b).abs()))
+            .sum();
+        sum_diff / (2.0 * n * n * mean)
+    }
+
+    fn pseudo_random_exposures(count: usize, mut seed: u64) -> HashMap<String, f64> {
+        (0..count).map(|i| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (format!("fund_{}", i), (seed >> 11) as f64 / (1u64 << 53) as f64 * 1_000_000.0)
+        }).collect()
+    }
+
+    #[tokio::test]
+    async fn test_gini_matches_naive_pairwise() {
+        let service = test_service().await;
+
+        for (count, seed) in [(1, 7), (2, 11), (10, 42), (250, 1234)] {
+            let exposures = pseudo_random_exposures(count, seed);
+            let values: Vec<f64> = exposures.values().cloned().collect();
+            let fast = service.calculate_gini_coefficient(&exposures).await.unwrap();
+            assert!((fast - naive_gini(&values)).abs() < 1e-9, "{} funds", count);
+        }
+
+        let equal: HashMap<String, f64> = (0..4).map(|i| (format!("fund_{}", i), 5.0)).collect();
+        assert!(service.calculate_gini_coefficient(&equal).await.unwrap().abs() < 1e-12);
+    }
+
+    #[tokio::test]
+    async fn test_gini_on_5000_funds() {
+        let service = test_service().await;
+        let exposures = pseudo_random_exposures(5_000, 99);
+
+        let values: Vec<f64> = exposures.values().cloned().collect();
+        let gini = service.calculate_gini_coefficient(&exposures).await.unwrap();
+        assert!((gini - naive_gini(&values)).abs() < 1e-9);
+        // Uniformly distributed exposures have a Gini coefficient near 1/3
+        assert!((gini - 1.0 / 3.0).abs() < 0.02);
+    }
+
+    #[tokio::test]
+    async fn test_high_cr4_escalates_low_hhi_market() {
+        let service = test_service().await;
+        // Four funds at 16% and 36 at 1%: HHI 1060, CR4 64%
+        let mut exposures: HashMap<String, f64> = (0..4).map(|i| (format!("large_{}", i), 16.0)).collect();
+        exposures.extend((0..36).map(|i| (format!("small_{}", i), 1.0)));
+
+        let result = service.calculate_herfindahl_index(exposures.clone()).await.unwrap();
+        assert!((result.hhi_score - 1060.0).abs() < 1e-6);
+        assert!((result.concentration_ratio_cr4 - 64.0).abs() < 1e-9);
+        assert!(matches!(result.market_concentration, MarketConcentration::HighlyConcentrated));
+
+        let relaxed = test_service().await.with_cr4_threshold(70.0);
+        let result = relaxed.calculate_herfindahl_index(exposures).await.unwrap();
+        assert!(matches!(result.market_concentration, MarketConcentration::Unconcentrated));
+    }
+
+    #[tokio::test]
+    async fn test_single_fund_and_empty_market_concentration() {
+        let service = test_service().await;
+
+        let single = HashMap::from([("fund_a".to_string(), 100.0)]);
+        let result = service.calculate_herfindahl_index(single).await.unwrap();
+        assert!(matches!(result.market_concentration, MarketConcentration::HighlyConcentrated));
+
+        let result = service.calculate_herfindahl_index(HashMap::new()).await.unwrap();
+        assert!(matches!(result.market_concentration, MarketConcentration::NoConcentration));
+    }
+
+    #[tokio::test]
+    async fn test_single_counterparty_exposure_limit() {
+        let service = test_service().await.with_max_single_exposure_percentage(25.0);
+        for fund in ["fund_a", "fund_b", "fund_c"] {
+            add_test_fund(&service, fund).await;
+        }
+        let add = |to: &str, amount: u64| service.add_fund_exposure(
+            "fund_a".to_string(), to.to_string(), amount, ExposureType::DirectInvestment, "proof".to_string(),
+        );
+
+        // 24.9% and exactly 25% of fund_a's 1_000 assets are within the limit
+        add("fund_b", 249).await.unwrap();
+        add("fund_c", 250).await.unwrap();
+
+        // 25.1% in a single edge is over
+        assert!(add("fund_b", 2).await.is_err());
+        // ...and so is a top-up that takes the counterparty's total over
+        let err = add("fund_c", 1).await.unwrap_err();
+        assert!(err.to_string().contains("Single counterparty exposure limit exceeded"));
+
+        let graph = service.exposure_graph.read().await;
+        let amounts: Vec<u64> = graph.edges["fund_a"].iter().map(|e| e.exposure_amount).collect();
+        assert_eq!(amounts, vec![249, 250]);
+    }
+
+    #[tokio::test]
+    async fn test_doubling_assets_halves_exposure_percentages() {
+        let service = test_service().await;
+        for fund in ["fund_a", "fund_b", "fund_c"] {
+            add_test_fund(&service, fund).await;
+        }
+        for (to, amount) in [("fund_b", 100), ("fund_c", 50)] {
+            service.add_fund_exposure(
+                "fund_a".to_string(), to.to_string(), amount, ExposureType::DirectInvestment, "proof".to_string(),
+            ).await.unwrap();
+        }
+        service.detect_circular_dependency("fund_a").await.unwrap();
+
+        service.update_fund_assets("fund_a", 2_000).await.unwrap();
+
+        let graph = service.exposure_graph.read().await;
+        let percentages: Vec<f64> = graph.edges["fund_a"].iter().map(|e| e.exposure_percentage).collect();
+        assert_eq!(percentages, vec![5.0, 2.5]);
+        assert_eq!(graph.nodes["fund_a"].total_assets, 2_000);
+        assert!(service.circular_dependency_cache.read().await.get("fund_a").is_none());
+        drop(graph);
+
+        assert!(service.update_fund_assets("unknown", 1).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_exposure_from_fund_without_assets_is_rejected() {
+        let service = test_service().await;
+        for fund in ["fund_a", "fund_b"] {
+            add_test_fund(&service, fund).await;
+        }
+        service.update_fund_assets("fund_a", 0).await.unwrap();
+
+        let err = service.add_fund_exposure(
+            "fund_a".to_string(), "fund_b".to_string(), 1, ExposureType::DirectInvestment, "proof".to_string(),
+        ).await.unwrap_err();
+        assert!(err.to_string().contains("no recorded assets"));
+
+        let err = service.add_fund_exposure(
+            "unknown".to_string(), "fund_b".to_string(), 1, ExposureType::DirectInvestment, "proof".to_string(),
+        ).await.unwrap_err();
+        assert!(err.to_string().contains("Fund not registered"));
+
+        assert!(service.exposure_graph.read().await.edges["fund_a"].is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_shrinking_assets_rechecks_counterparty_limit() {
+        let service = test_service().await.with_max_single_exposure_percentage(25.0);
+        for fund in ["fund_a", "fund_b", "fund_c"] {
+            add_test_fund(&service, fund).await;
+        }
+        for (to, amount) in [("fund_b", 100), ("fund_b", 100), ("fund_c", 50)] {
+            service.add_fund_exposure(
+                "fund_a".to_string(), to.to_string(), amount, ExposureType::DirectInvestment, "proof".to_string(),
+            ).await.unwrap();
+        }
+
+        // fund_b's 200 stays at exactly 25% of 800
+        service.update_fund_assets("fund_a", 800).await.unwrap();
+
+        // ...but 200 of 799 is over, and zero assets always are
+        let err = service.update_fund_assets("fund_a", 799).await.unwrap_err();
+        assert!(err.to_string().contains("Single counterparty exposure limit exceeded"));
+        assert!(service.update_fund_assets("fund_a", 0).await.is_err());
+
+        let graph = service.exposure_graph.read().await;
+        assert_eq!(graph.nodes["fund_a"].total_assets, 800);
+        let percentages: Vec<f64> = graph.edges["fund_a"].iter().map(|e| e.exposure_percentage).collect();
+        assert_eq!(percentages, vec![12.5, 12.5, 6.25]);
+    }
+
+    #[tokio::test]
+    async fn test_remove_exposure_and_deregister_fund() {
+        let service = test_service().await;
+        for fund in ["fund_a", "fund_b", "fund_c"] {
+            add_test_fund(&service, fund).await;
+        }
+        add_edge(&service, "fund_a", "fund_b").await;
+        add_edge(&service, "fund_b", "fund_c").await;
+        add_edge(&service, "fund_b", "fund_a").await;
+        assert!(service.detect_circular_dependency("fund_a").await.unwrap().circular_detected);
+
+        service.remove_fund_exposure("fund_b", "fund_a").await.unwrap();
+        assert!(!service.detect_circular_dependency("fund_a").await.unwrap().circular_detected);
+        assert!(service.remove_fund_exposure("fund_b", "fund_a").await.is_err());
+
+        service.remove_fund_exposure("fund_a", "fund_b").await.unwrap();
+        service.deregister_fund("fund_a", false).await.unwrap();
+
+        let graph = service.exposure_graph.read().await;
+        assert!(!graph.nodes.contains_key("fund_a"));
+        assert!(!graph.edges.contains_key("fund_a"));
+        assert_eq!(graph.total_funds, 2);
+        assert_eq!(graph.total_exposures, 1);
+        assert!(matches!(graph.nodes["fund_b"].fund_type, FundType::Derivative));
+    }
+
+    #[tokio::test]
+    async fn test_deregister_referenced_fund_requires_force() {
+        let service = test_service().await;
+        for fund in ["fund_a", "fund_b"] {
+            add_test_fund(&service, fund).await;
         }
+        add_edge(&service, "fund_a", "fund_b").await;
+
+        let err = service.deregister_fund("fund_b", false).await.unwrap_err();
+        assert!(err.to_string().contains("fund_a"));
+        assert!(service.exposure_graph.read().await.nodes.contains_key("fund_b"));
+
+        service.deregister_fund("fund_b", true).await.unwrap();
+        let graph = service.exposure_graph.read().await;
+        assert!(graph.edges["fund_a"].is_empty());
+        assert_eq!(graph.total_funds, 1);
+        assert_eq!(graph.total_exposures, 0);
+        assert!(matches!(graph.nodes["fund_a"].fund_type, FundType::Primary));
     }
 }
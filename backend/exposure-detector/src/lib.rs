@@ -1,6 +1,10 @@
+pub mod exposure_snapshot;
 pub mod fund_exposure_service;
+pub mod origin_verification;
 
+pub use exposure_snapshot::*;
 pub use fund_exposure_service::*;
+pub use origin_verification::*;
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
@@ -17,6 +21,11 @@ pub struct ExposureConfig {
     pub max_circular_exposure: f64,
     pub monitoring_enabled: bool,
     pub real_time_analysis: bool,
+    pub max_flatten_visits: usize,
+    pub cr4_concentration_threshold: f64,
+    pub max_single_exposure_percentage: f64,
+    /// Authorizer address -> hex Ed25519 public key allowed to sign whitelist changes
+    pub whitelist_authorizers: HashMap<String, String>,
 }
 
 impl Default for ExposureConfig {
@@ -26,6 +35,10 @@ impl Default for ExposureConfig {
             max_circular_exposure: 0.25, // 25% max circular exposure
             monitoring_enabled: true,
             real_time_analysis: true,
+            max_flatten_visits: DEFAULT_MAX_FLATTEN_VISITS,
+            cr4_concentration_threshold: DEFAULT_CR4_CONCENTRATION_THRESHOLD,
+            max_single_exposure_percentage: DEFAULT_MAX_SINGLE_EXPOSURE_PERCENTAGE,
+            whitelist_authorizers: HashMap::new(),
         }
     }
 }
@@ -34,10 +47,16 @@ impl Default for ExposureConfig {
 pub async fn init_exposure_service(config: ExposureConfig) -> Result<FundExposureService> {
     info!("🕸️ Initializing RTF Fund Exposure Detection Service");
 
-    let service = FundExposureService::new(
+    let mut service = FundExposureService::new(
         config.max_exposure_depth,
         config.max_circular_exposure,
-    ).await?;
+    ).await?
+    .with_flatten_visit_budget(config.max_flatten_visits)
+    .with_cr4_threshold(config.cr4_concentration_threshold)
+    .with_max_single_exposure_percentage(config.max_single_exposure_percentage);
+    for (authorizer_address, public_key) in config.whitelist_authorizers {
+        service = service.with_whitelist_authorizer(authorizer_address, public_key);
+    }
 
     info!("✅ RTF Fund Exposure Detection Service initialized successfully");
     Ok(service)
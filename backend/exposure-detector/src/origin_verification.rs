@@ -0,0 +1,413 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use domain_tags::DomainTag;
+use std::collections::{BTreeSet, HashMap};
+
+use crate::fund_exposure_service::{
+    CircuitAncestry, DaoAncestry, FundOriginProof, LegalAncestry, OriginSignature, SignatureType,
+    SignedSnapshot, SignerRole,
+};
+
+/// Fund-Origin Proof signature verification
+/// PRD Section 7: "Fund-Origin Proof: vault_origin_hash = signed snapshot of legal, DAO, and circuit ancestry"
+/// Every signature on a snapshot must come from a registered signer, under that signer's
+/// role and scheme, over `origin_snapshot_message(fund_id, snapshot_hash, merkle_root)`,
+/// so a snapshot signed for one fund cannot register another. The snapshot is accepted once each
+/// required role has at least one valid signature.
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum OriginProofError {
+    #[error("Signer {signer_address} is not a registered origin signer")]
+    UnknownSigner { signer_address: String },
+    #[error("Signer {signer_address} signed as {claimed:?} but is registered as {registered:?}")]
+    RoleMismatch { signer_address: String, claimed: SignerRole, registered: SignerRole },
+    #[error("Signer {signer_address} used {claimed:?} but is registered for {registered:?}")]
+    SchemeMismatch { signer_address: String, claimed: SignatureType, registered: SignatureType },
+    #[error("{signature_type:?} signatures from {signer_address} cannot be verified")]
+    UnsupportedScheme { signer_address: String, signature_type: SignatureType },
+    #[error("Invalid {role:?} signature from {signer_address}: {reason}")]
+    InvalidSignature { signer_address: String, role: SignerRole, reason: String },
+    #[error("Snapshot is missing signatures from roles {missing:?}")]
+    MissingRoles { missing: Vec<SignerRole> },
+    #[error("Snapshot hash {recorded} does not match snapshot data hash {computed}")]
+    SnapshotHashMismatch { recorded: String, computed: String },
+    #[error("Snapshot Merkle root {recorded} does not match ancestry root {computed}")]
+    MerkleRootMismatch { recorded: String, computed: String },
+}
+
+/// Key and role registered for an origin signer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OriginSigner {
+    pub role: SignerRole,
+    pub signature_type: SignatureType,
+    /// Hex-encoded public key in the scheme's wire format
+    pub public_key: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OriginSignaturePolicy {
+    /// Registered signers by `signer_address`
+    pub signers: HashMap<String, OriginSigner>,
+    /// Roles that must each contribute a valid signature
+    pub required_roles: BTreeSet<SignerRole>,
+}
+
+impl Default for OriginSignaturePolicy {
+    fn default() -> Self {
+        Self {
+            signers: HashMap::new(),
+            required_roles: BTreeSet::from([
+                SignerRole::LegalEntity,
+                SignerRole::DaoMultisig,
+                SignerRole::CircuitAuditor,
+            ]),
+        }
+    }
+}
+
+impl OriginSignaturePolicy {
+    pub fn with_signer(mut self, signer_address: impl Into<String>, signer: OriginSigner) -> Self {
+        self.signers.insert(signer_address.into(), signer);
+        self
+    }
+
+    /// Verify every signature on `fund_id`'s `snapshot` and require the role quorum
+    pub fn verify(&self, fund_id: &str, snapshot: &SignedSnapshot) -> Result<(), OriginProofError> {
+        let message = origin_snapshot_message(fund_id, &snapshot.snapshot_hash, &snapshot.merkle_root);
+        let mut signed_roles = BTreeSet::new();
+
+        for signature in &snapshot.signatures {
+            self.verify_signature(&message, signature)?;
+            signed_roles.insert(signature.signer_role.clone());
+        }
+
+        let missing: Vec<SignerRole> = self.required_roles.difference(&signed_roles).cloned().collect();
+        if !missing.is_empty() {
+            return Err(OriginProofError::MissingRoles { missing });
+        }
+        Ok(())
+    }
+
+    fn verify_signature(&self, message: &[u8; 32], signature: &OriginSignature) -> Result<(), OriginProofError> {
+        let signer_address = &signature.signer_address;
+        let signer = self.signers.get(signer_address)
+            .ok_or_else(|| OriginProofError::UnknownSigner { signer_address: signer_address.clone() })?;
+
+        if signer.role != signature.signer_role {
+            return Err(OriginProofError::RoleMismatch {
+                signer_address: signer_address.clone(),
+                claimed: signature.signer_role.clone(),
+                registered: signer.role.clone(),
+            });
+        }
+        if signer.signature_type != signature.signature_type {
+            return Err(OriginProofError::SchemeMismatch {
+                signer_address: signer_address.clone(),
+                claimed: signature.signature_type.clone(),
+                registered: signer.signature_type.clone(),
+            });
+        }
+
+        let invalid = |reason: String| OriginProofError::InvalidSignature {
+            signer_address: signer_address.clone(),
+            role: signer.role.clone(),
+            reason,
+        };
+        let public_key = hex::decode(&signer.public_key).map_err(|e| invalid(format!("public key: {}", e)))?;
+        let signature_bytes = hex::decode(&signature.signature).map_err(|e| invalid(format!("signature: {}", e)))?;
+
+        let result = match signature.signature_type {
+            SignatureType::EdDSA => verify_ed25519(&public_key, message, &signature_bytes),
+            SignatureType::BLS => crypto::bls::verify_with_public_key(&public_key, message, &signature_bytes)
+                .map_err(|e| e.to_string()),
+            // No verifier from public key material alone exists for these schemes yet
+            SignatureType::ECDSA | SignatureType::Dilithium512 => {
+                return Err(OriginProofError::UnsupportedScheme {
+                    signer_address: signer_address.clone(),
+                    signature_type: signature.signature_type.clone(),
+                });
+            }
+        };
+        result.map_err(invalid)
+    }
+}
+
+/// Strictly verify an Ed25519 signature from raw public key and signature bytes
+pub(crate) fn verify_ed25519(public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<(), String> {
+    use ed25519_dalek::{Signature, VerifyingKey};
+
+    let public_key: &[u8; 32] = public_key.try_into()
+        .map_err(|_| format!("Ed25519 public key of {} bytes, expected 32", public_key.len()))?;
+    let key = VerifyingKey::from_bytes(public_key).map_err(|e| format!("Ed25519 public key: {}", e))?;
+    let signature = Signature::from_slice(signature).map_err(|e| format!("malformed Ed25519 signature: {}", e))?;
+    key.verify_strict(message, &signature).map_err(|e| e.to_string())
+}
+
+/// Check the snapshot's hash and Merkle root commit to its data and the proof's ancestry
+///
+/// Signatures cover `snapshot_hash` and `merkle_root`; this ties both to the data and
+/// ancestry the proof actually carries, so neither can be swapped under valid signatures.
+pub fn verify_snapshot_consistency(proof: &FundOriginProof) -> Result<(), OriginProofError> {
+    let snapshot = &proof.signed_snapshot;
+
+    let computed = snapshot_data_hash(&snapshot.snapshot_data);
+    if !computed.eq_ignore_ascii_case(&snapshot.snapshot_hash) {
+        return Err(OriginProofError::SnapshotHashMismatch {
+            recorded: snapshot.snapshot_hash.clone(),
+            computed,
+        });
+    }
+
+    let computed = ancestry_merkle_root(&proof.legal_ancestry, &proof.dao_ancestry, &proof.circuit_ancestry);
+    if !computed.eq_ignore_ascii_case(&snapshot.merkle_root) {
+        return Err(OriginProofError::MerkleRootMismatch {
+            recorded: snapshot.merkle_root.clone(),
+            computed,
+        });
+    }
+    Ok(())
+}
+
+/// Hex SHA-256 of the snapshot data
+pub fn snapshot_data_hash(snapshot_data: &str) -> String {
+    hex::encode(Sha256::digest(snapshot_data.as_bytes()))
+}
+
+/// Hex Merkle root over the legal, DAO and circuit ancestry, in that order
+pub fn ancestry_merkle_root(legal: &LegalAncestry, dao: &DaoAncestry, circuit: &CircuitAncestry) -> String {
+    let leaves = vec![ancestry_leaf(legal), ancestry_leaf(dao), ancestry_leaf(circuit)];
+    let tree = crypto::hashing::MerkleTree::new(leaves).expect("ancestry tree has three leaves");
+    hex::encode(tree.root)
+}
+
+fn ancestry_leaf<T: Serialize>(component: &T) -> Vec<u8> {
+    let encoded = canonical_json::to_canonical_json(component).expect("ancestry serializes to JSON");
+    Sha256::digest(encoded.as_bytes()).to_vec()
+}
+
+/// Message origin signers sign for `fund_id`'s snapshot and its ancestry root
+pub fn origin_snapshot_message(fund_id: &str, snapshot_hash: &str, merkle_root: &str) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(DomainTag::FundOriginSnapshot.as_bytes());
+    for field in [fund_id, snapshot_hash, merkle_root] {
+        hasher.update((field.len() as u64).to_le_bytes());
+        hasher.update(field.as_bytes());
+    }
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::Signer;
+
+    const SNAPSHOT_HASH: &str = "9f2c1d";
+    const FUND_ID: &str = "fund-1";
+    const MERKLE_ROOT: &str = "4a7e03";
+
+    struct Signers {
+        legal: ed25519_dalek::SigningKey,
+        dao: crypto::bls::BlsKeyPair,
+        auditor: ed25519_dalek::SigningKey,
+    }
+
+    fn signers() -> (Signers, OriginSignaturePolicy) {
+        use rand::SeedableRng;
+        let signers = Signers {
+            legal: ed25519_dalek::SigningKey::from_bytes(&[1u8; 32]),
+            dao: crypto::bls::BlsKeyPair::generate(&mut rand::rngs::StdRng::seed_from_u64(1347)).unwrap(),
+            auditor: ed25519_dalek::SigningKey::from_bytes(&[3u8; 32]),
+        };
+        let policy = OriginSignaturePolicy::default()
+            .with_signer("legal", OriginSigner {
+                role: SignerRole::LegalEntity,
+                signature_type: SignatureType::EdDSA,
+                public_key: hex::encode(signers.legal.verifying_key().to_bytes()),
+            })
+            .with_signer("dao", OriginSigner {
+                role: SignerRole::DaoMultisig,
+                signature_type: SignatureType::BLS,
+                public_key: hex::encode(signers.dao.public_key().to_bytes()),
+            })
+            .with_signer("auditor", OriginSigner {
+                role: SignerRole::CircuitAuditor,
+                signature_type: SignatureType::EdDSA,
+                public_key: hex::encode(signers.auditor.verifying_key().to_bytes()),
+            });
+        (signers, policy)
+    }
+
+    fn origin_signature(address: &str, role: SignerRole, signature_type: SignatureType, signature: Vec<u8>) -> OriginSignature {
+        OriginSignature {
+            signer_address: address.to_string(),
+            signer_role: role,
+            signature: hex::encode(signature),
+            signature_type,
+            timestamp: 0,
+        }
+    }
+
+    fn snapshot(signers: &Signers) -> SignedSnapshot {
+        let message = origin_snapshot_message(FUND_ID, SNAPSHOT_HASH, MERKLE_ROOT);
+        SignedSnapshot {
+            snapshot_hash: SNAPSHOT_HASH.to_string(),
+            snapshot_data: String::new(),
+            signatures: vec![
+                origin_signature("legal", SignerRole::LegalEntity, SignatureType::EdDSA,
+                    signers.legal.sign(&message).to_bytes().to_vec()),
+                origin_signature("dao", SignerRole::DaoMultisig, SignatureType::BLS,
+                    signers.dao.sign(&message).to_bytes().to_vec()),
+                origin_signature("auditor", SignerRole::CircuitAuditor, SignatureType::EdDSA,
+                    signers.auditor.sign(&message).to_bytes().to_vec()),
+            ],
+            merkle_root: MERKLE_ROOT.to_string(),
+            block_height: 0,
+            timestamp: 0,
+        }
+    }
+
+    fn consistent_origin_proof() -> FundOriginProof {
+        let legal_ancestry = LegalAncestry {
+            legal_entity_id: "rtf-fund-lp".to_string(),
+            incorporation_documents: vec!["cert_of_formation".to_string()],
+            regulatory_approvals: vec!["sec_reg_d".to_string()],
+            compliance_certifications: vec!["soc2".to_string()],
+            legal_opinion_hash: "legal_opinion".to_string(),
+            jurisdiction_chain: vec!["US-DE".to_string()],
+        };
+        let dao_ancestry = DaoAncestry {
+            governance_contract_address: "gov".to_string(),
+            dao_proposal_history: vec!["p-1".to_string()],
+            voting_power_distribution: HashMap::from([
+                ("alice".to_string(), 0.6),
+                ("bob".to_string(), 0.4),
+            ]),
+            governance_token_address: "token".to_string(),
+            multisig_signers: vec!["alice".to_string(), "bob".to_string()],
+            governance_parameters: HashMap::from([("quorum".to_string(), serde_json::json!(0.4))]),
+        };
+        let circuit_ancestry = CircuitAncestry {
+            zknav_circuit_hash: "circuit".to_string(),
+            verification_key_hash: "vk".to_string(),
+            circuit_parameters: HashMap::new(),
+            trusted_setup_ceremony: "ceremony".to_string(),
+            circuit_audit_reports: vec!["audit".to_string()],
+            upgrade_history: Vec::new(),
+        };
+
+        let snapshot_data = r#"{"fund_id":"fund-1","epoch":7}"#.to_string();
+        FundOriginProof {
+            vault_origin_hash: "origin".to_string(),
+            signed_snapshot: SignedSnapshot {
+                snapshot_hash: snapshot_data_hash(&snapshot_data),
+                snapshot_data,
+                signatures: Vec::new(),
+                merkle_root: ancestry_merkle_root(&legal_ancestry, &dao_ancestry, &circuit_ancestry),
+                block_height: 0,
+                timestamp: 0,
+            },
+            legal_ancestry,
+            dao_ancestry,
+            circuit_ancestry,
+            whitelist_status: crate::fund_exposure_service::WhitelistStatus::ExplicitlyWhitelisted,
+            fork_derivation_proof: None,
+            created_at: 0,
+            version: 1,
+        }
+    }
+
+    #[test]
+    fn test_snapshot_consistency_detects_tampering() {
+        let proof = consistent_origin_proof();
+        assert_eq!(verify_snapshot_consistency(&proof), Ok(()));
+
+        let mut tampered_data = proof.clone();
+        tampered_data.signed_snapshot.snapshot_data = r#"{"fund_id":"fund-1","epoch":8}"#.to_string();
+        assert!(matches!(
+            verify_snapshot_consistency(&tampered_data),
+            Err(OriginProofError::SnapshotHashMismatch { .. })
+        ));
+
+        let mut swapped_ancestry = proof.clone();
+        swapped_ancestry.dao_ancestry.multisig_signers.push("mallory".to_string());
+        assert!(matches!(
+            verify_snapshot_consistency(&swapped_ancestry),
+            Err(OriginProofError::MerkleRootMismatch { .. })
+        ));
+
+        let mut wrong_root = proof;
+        wrong_root.signed_snapshot.merkle_root = "00".repeat(32);
+        assert!(matches!(
+            verify_snapshot_consistency(&wrong_root),
+            Err(OriginProofError::MerkleRootMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_eddsa_and_bls_signatures_meet_role_quorum() {
+        let (signers, policy) = signers();
+        assert_eq!(policy.verify(FUND_ID, &snapshot(&signers)), Ok(()));
+
+        let mut without_auditor = snapshot(&signers);
+        without_auditor.signatures.pop();
+        assert_eq!(
+            policy.verify(FUND_ID, &without_auditor),
+            Err(OriginProofError::MissingRoles { missing: vec![SignerRole::CircuitAuditor] })
+        );
+    }
+
+    #[test]
+    fn test_invalid_signatures_identify_signer_and_role() {
+        let (signers, policy) = signers();
+
+        // Ed25519 bytes presented as the DAO's BLS signature
+        let mut wrong_scheme = snapshot(&signers);
+        wrong_scheme.signatures[1].signature_type = SignatureType::EdDSA;
+        assert!(matches!(
+            policy.verify(FUND_ID, &wrong_scheme),
+            Err(OriginProofError::SchemeMismatch { signer_address, .. }) if signer_address == "dao"
+        ));
+
+        // Auditor's signature over a different snapshot
+        let mut forged = snapshot(&signers);
+        forged.signatures[2].signature = hex::encode(signers.auditor.sign(b"other snapshot").to_bytes());
+        assert!(matches!(
+            policy.verify(FUND_ID, &forged),
+            Err(OriginProofError::InvalidSignature { signer_address, role: SignerRole::CircuitAuditor, .. })
+                if signer_address == "auditor"
+        ));
+
+        let mut post_quantum = snapshot(&signers);
+        post_quantum.signatures[0].signature_type = SignatureType::Dilithium512;
+        let policy = policy.with_signer("legal", OriginSigner {
+            role: SignerRole::LegalEntity,
+            signature_type: SignatureType::Dilithium512,
+            public_key: "00".to_string(),
+        });
+        assert!(matches!(
+            policy.verify(FUND_ID, &post_quantum),
+            Err(OriginProofError::UnsupportedScheme { signature_type: SignatureType::Dilithium512, .. })
+        ));
+
+        // The signatures cover the ancestry root, so it cannot be swapped for another tree
+        let mut swapped_root = snapshot(&signers);
+        swapped_root.merkle_root = "00".repeat(32);
+        assert!(matches!(
+            policy.verify(FUND_ID, &swapped_root),
+            Err(OriginProofError::InvalidSignature { .. })
+        ));
+
+        // A snapshot signed for one fund replayed to register another
+        assert!(matches!(
+            policy.verify("fund-2", &snapshot(&signers)),
+            Err(OriginProofError::InvalidSignature { .. })
+        ));
+
+        let mut impostor = snapshot(&signers);
+        impostor.signatures[0].signer_address = "unregistered".to_string();
+        assert_eq!(
+            policy.verify(FUND_ID, &impostor),
+            Err(OriginProofError::UnknownSigner { signer_address: "unregistered".to_string() })
+        );
+    }
+}
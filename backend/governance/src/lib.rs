@@ -6,11 +6,16 @@
 pub mod advanced_multi_dao;
 
 use anyhow::Result;
+use audit_log::{AuditEvent, AuditLog};
+use domain_tags::DomainTag;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{info, warn, error};
 use chrono::{DateTime, Utc};
+use epoch_clock::{EpochClock, SystemEpochClock};
 
 /// Multi-DAO Governance System coordinator
 #[derive(Debug)]
@@ -21,6 +26,20 @@ pub struct GovernanceSystem {
     esg_dao: advanced_multi_dao::EsgDAO,
     config: GovernanceConfig,
     metrics: RwLock<GovernanceMetrics>,
+    proposals: RwLock<HashMap<String, Proposal>>,
+    votes: RwLock<HashMap<String, Vec<Vote>>>,
+    convictions: RwLock<HashMap<(String, String), ConvictionState>>,
+    epoch_clock: Arc<dyn EpochClock>,
+    /// Epochs forced by `advance_epoch` on top of the clock
+    epoch_offset: AtomicU64,
+    eligible_supply: RwLock<HashMap<DAOType, u64>>,
+    voting_power: RwLock<HashMap<String, u64>>,
+    power_snapshots: RwLock<HashMap<String, HashMap<String, u64>>>,
+    delegations: RwLock<DelegationRegistry>,
+    delegation_snapshots: RwLock<HashMap<String, DelegationRegistry>>,
+    pending_emergency_approvals: RwLock<HashMap<[u8; 32], HashSet<[u8; 32]>>>,
+    emergency_state: SharedEmergencyState,
+    audit_log: Option<Arc<AuditLog>>,
 }
 
 /// Configuration for the governance system
@@ -32,6 +51,39 @@ pub struct GovernanceConfig {
     pub emergency_threshold: f64,
     pub conviction_voting_enabled: bool,
     pub quadratic_voting_enabled: bool,
+    /// Epochs for accrued conviction to decay by half
+    pub conviction_half_life_epochs: u64,
+    /// Length of a governance epoch in seconds
+    pub epoch_duration_secs: u64,
+    /// Share of non-abstaining weighted votes required to pass
+    pub approval_threshold: f64,
+    /// DAO-specific overrides of quorum and approval thresholds
+    pub dao_rules: HashMap<DAOType, DaoVotingRules>,
+    /// Whether a repeat vote replaces the voter's prior vote instead of being rejected
+    pub allow_vote_change: bool,
+    /// Maximum number of hops followed when resolving a delegation chain
+    pub max_delegation_depth: usize,
+    /// Ed25519 public keys allowed to approve emergency actions
+    pub emergency_signers: Vec<[u8; 32]>,
+    /// Deployment emergency approvals are scoped to, e.g. `<chain id>:<program id>`
+    pub emergency_domain: String,
+}
+
+/// Quorum and approval rules applied to a single DAO
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaoVotingRules {
+    pub quorum_threshold: f64,
+    pub approval_threshold: f64,
+}
+
+impl GovernanceConfig {
+    /// Voting rules for a DAO, falling back to the global thresholds
+    pub fn rules_for(&self, dao_type: &DAOType) -> DaoVotingRules {
+        self.dao_rules.get(dao_type).cloned().unwrap_or(DaoVotingRules {
+            quorum_threshold: self.quorum_threshold,
+            approval_threshold: self.approval_threshold,
+        })
+    }
 }
 
 impl Default for GovernanceConfig {
@@ -43,12 +95,20 @@ impl Default for GovernanceConfig {
             emergency_threshold: 0.8, // 80%
             conviction_voting_enabled: true,
             quadratic_voting_enabled: true,
+            conviction_half_life_epochs: 7,
+            epoch_duration_secs: 86_400, // 24 hours
+            approval_threshold: 0.5,  // Simple majority
+            dao_rules: HashMap::new(),
+            allow_vote_change: true,
+            max_delegation_depth: 5,
+            emergency_signers: Vec::new(),
+            emergency_domain: DEFAULT_EMERGENCY_DOMAIN.to_string(),
         }
     }
 }
 
 /// Governance system metrics
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GovernanceMetrics {
     pub total_proposals: u64,
     pub active_proposals: u64,
@@ -105,7 +165,7 @@ pub enum ProposalType {
 }
 
 /// Emergency actions
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum EmergencyAction {
     PauseProtocol,
     FreezeAssets,
@@ -114,6 +174,94 @@ pub enum EmergencyAction {
     SecurityPatch,
 }
 
+/// A single emergency signer's approval over an emergency message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignerApproval {
+    pub signer: [u8; 32],
+    pub signature: Vec<u8>,
+}
+
+/// Signer approvals submitted towards an emergency action
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EmergencyAuthorization {
+    pub approvals: Vec<SignerApproval>,
+}
+
+/// Outcome of submitting emergency approvals
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EmergencyActivation {
+    Pending { approvals: usize, required: usize },
+    Executed,
+}
+
+/// Emergency flags observed by deposit and redemption paths
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EmergencyState {
+    pub paused: bool,
+    pub assets_frozen: bool,
+    pub circuit_breaker_active: bool,
+    pub emergency_withdrawals_enabled: bool,
+    pub last_updated: Option<DateTime<Utc>>,
+    /// Bumped by every executed activation or clearance; approvals sign the current value
+    pub emergency_nonce: u64,
+}
+
+/// Emergency flags shared with services that trip them automatically, such as the
+/// emergency handler's circuit breaker
+pub type SharedEmergencyState = Arc<RwLock<EmergencyState>>;
+
+/// Errors returned when an operation is blocked by emergency state
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum EmergencyError {
+    #[error("Protocol is paused")]
+    ProtocolPaused,
+    #[error("Assets are frozen")]
+    AssetsFrozen,
+    #[error("Circuit breaker is active")]
+    CircuitBreakerActive,
+}
+
+/// Errors returned when a proposal is not in a state that allows the operation
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum ProposalError {
+    #[error("Proposal {proposal_id} cannot execute from status {status:?}")]
+    NotExecutable { proposal_id: String, status: ProposalStatus },
+    #[error("Proposal {proposal_id} has already been executed")]
+    AlreadyExecuted { proposal_id: String },
+}
+
+/// Message emergency signers approve for an action and its justification
+///
+/// `domain` and `nonce` are the deployment's `emergency_domain` and the current
+/// `EmergencyState::emergency_nonce`, so an approval is good for one execution on
+/// one deployment.
+pub fn emergency_message(domain: &str, nonce: u64, action: &EmergencyAction, justification: &str) -> [u8; 32] {
+    emergency_digest(DomainTag::EmergencyActivation, domain, nonce, action, justification)
+}
+
+/// Message emergency signers approve to clear an active emergency action
+pub fn emergency_clearance_message(domain: &str, nonce: u64, action: &EmergencyAction, justification: &str) -> [u8; 32] {
+    emergency_digest(DomainTag::EmergencyClearance, domain, nonce, action, justification)
+}
+
+fn emergency_digest(
+    tag: DomainTag,
+    domain: &str,
+    nonce: u64,
+    action: &EmergencyAction,
+    justification: &str,
+) -> [u8; 32] {
+    use sha2::{Sha256, Digest};
+    let mut hasher = Sha256::new();
+    hasher.update(tag.as_bytes());
+    hasher.update((domain.len() as u64).to_le_bytes());
+    hasher.update(domain.as_bytes());
+    hasher.update(nonce.to_le_bytes());
+    hasher.update(format!("{:?}", action).as_bytes());
+    hasher.update(justification.as_bytes());
+    hasher.finalize().into()
+}
+
 /// Governance proposal
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Proposal {
@@ -132,8 +280,51 @@ pub struct Proposal {
     pub semantic_commitment_hash: String,
 }
 
+impl Proposal {
+    /// Text and instruction data the semantic commitment binds
+    ///
+    /// The text is what voters read; the instruction data is a canonical encoding of what
+    /// the proposal does and who proposed it, including the full `proposal_type` payload.
+    /// The LLM agent is given the same pair when it checks the commitment.
+    pub fn semantic_commitment_inputs(&self) -> (String, Vec<u8>) {
+        #[derive(Serialize)]
+        struct ExecutionContent<'a> {
+            id: &'a str,
+            dao_type: &'a DAOType,
+            proposal_type: &'a ProposalType,
+            proposer: &'a str,
+        }
+
+        let text = format!("{}\n\n{}", self.title, self.description);
+        let instruction_data = canonical_json::to_canonical_json(&ExecutionContent {
+            id: &self.id,
+            dao_type: &self.dao_type,
+            proposal_type: &self.proposal_type,
+            proposer: &self.proposer,
+        }).expect("proposal content serializes to JSON");
+        (text, instruction_data.into_bytes())
+    }
+
+    /// Shared semantic commitment over `semantic_commitment_inputs`, so swapping the
+    /// action or the text after voting changes the commitment
+    pub fn compute_semantic_commitment(&self) -> String {
+        let (text, instruction_data) = self.semantic_commitment_inputs();
+        semantic_commitment::semantic_commitment_hash(&text, &instruction_data)
+    }
+
+    /// Check the proposal still matches the commitment recorded at submission
+    pub fn verify_semantic_commitment(&self) -> Result<()> {
+        if self.compute_semantic_commitment() != self.semantic_commitment_hash {
+            return Err(anyhow::anyhow!(
+                "Proposal {} no longer matches its semantic commitment", self.id
+            ));
+        }
+        Ok(())
+    }
+}
+
 /// Proposal status
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum ProposalStatus {
     Draft,
     Active,
@@ -144,6 +335,48 @@ pub enum ProposalStatus {
     Emergency,
 }
 
+/// Criteria for `GovernanceSystem::list_proposals`; unset fields match everything
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProposalFilter {
+    pub dao_type: Option<DAOType>,
+    pub status: Option<ProposalStatus>,
+    /// Inclusive lower bound on `created_at`
+    pub created_from: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on `created_at`
+    pub created_until: Option<DateTime<Utc>>,
+}
+
+impl ProposalFilter {
+    pub fn matches(&self, proposal: &Proposal) -> bool {
+        self.dao_type.as_ref().is_none_or(|dao| *dao == proposal.dao_type)
+            && self.status.as_ref().is_none_or(|status| *status == proposal.status)
+            && self.created_from.is_none_or(|from| proposal.created_at >= from)
+            && self.created_until.is_none_or(|until| proposal.created_at < until)
+    }
+}
+
+pub const DEFAULT_PROPOSAL_PAGE_SIZE: usize = 50;
+
+pub const DEFAULT_EMERGENCY_DOMAIN: &str = "rtf-governance:local";
+pub const MAX_PROPOSAL_PAGE_SIZE: usize = 200;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Pagination {
+    pub offset: usize,
+    /// Page size (0 = DEFAULT_PROPOSAL_PAGE_SIZE), capped at MAX_PROPOSAL_PAGE_SIZE
+    pub limit: usize,
+}
+
+/// One page of proposals, newest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposalPage {
+    pub proposals: Vec<Proposal>,
+    /// Proposals matching the filter across all pages
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+}
+
 /// Vote record
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Vote {
@@ -162,6 +395,197 @@ pub enum VoteType {
     Abstain,
 }
 
+/// Mechanism categories used for tally breakdowns
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum MechanismKind {
+    Simple,
+    Quadratic,
+    Conviction,
+    Delegation,
+}
+
+impl VotingMechanism {
+    pub fn kind(&self) -> MechanismKind {
+        match self {
+            VotingMechanism::Simple => MechanismKind::Simple,
+            VotingMechanism::Quadratic => MechanismKind::Quadratic,
+            VotingMechanism::Conviction { .. } => MechanismKind::Conviction,
+            VotingMechanism::Delegation { .. } => MechanismKind::Delegation,
+        }
+    }
+}
+
+/// Per-mechanism contribution to a tally
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MechanismTally {
+    pub voters: u64,
+    pub raw_power: u64,
+    pub weighted_for: u64,
+    pub weighted_against: u64,
+    pub weighted_abstain: u64,
+}
+
+/// Result of tallying all votes on a proposal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TallyResult {
+    pub proposal_id: String,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub votes_abstain: u64,
+    pub total_voters: u64,
+    pub delegated_power: u64,
+    pub breakdown: HashMap<MechanismKind, MechanismTally>,
+}
+
+impl TallyResult {
+    fn new(proposal_id: &str) -> Self {
+        Self {
+            proposal_id: proposal_id.to_string(),
+            votes_for: 0,
+            votes_against: 0,
+            votes_abstain: 0,
+            total_voters: 0,
+            delegated_power: 0,
+            breakdown: HashMap::new(),
+        }
+    }
+
+    fn record(&mut self, vote: &Vote, delegated_power: u64, weight: u64) {
+        let entry = self.breakdown.entry(vote.mechanism.kind()).or_default();
+        entry.voters += 1;
+        entry.raw_power = entry.raw_power
+            .saturating_add(vote.voting_power)
+            .saturating_add(delegated_power);
+        self.delegated_power = self.delegated_power.saturating_add(delegated_power);
+
+        match vote.vote_type {
+            VoteType::For => {
+                entry.weighted_for = entry.weighted_for.saturating_add(weight);
+                self.votes_for = self.votes_for.saturating_add(weight);
+            }
+            VoteType::Against => {
+                entry.weighted_against = entry.weighted_against.saturating_add(weight);
+                self.votes_against = self.votes_against.saturating_add(weight);
+            }
+            VoteType::Abstain => {
+                entry.weighted_abstain = entry.weighted_abstain.saturating_add(weight);
+                self.votes_abstain = self.votes_abstain.saturating_add(weight);
+            }
+        }
+        self.total_voters += 1;
+    }
+}
+
+/// Registry of voting power delegations (delegator -> delegate)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DelegationRegistry {
+    delegations: HashMap<String, String>,
+}
+
+impl DelegationRegistry {
+    /// Delegate `from`'s voting power to `to`, rejecting self-delegation and cycles
+    pub fn delegate(&mut self, from: &str, to: &str) -> Result<()> {
+        if from == to {
+            return Err(anyhow::anyhow!("{} cannot delegate to itself", from));
+        }
+
+        // Walking from the new delegate must never lead back to the delegator
+        let mut current = to;
+        let mut visited = HashSet::new();
+        while let Some(next) = self.delegations.get(current) {
+            if next == from {
+                return Err(anyhow::anyhow!("Delegation from {} to {} would create a cycle", from, to));
+            }
+            if !visited.insert(next.as_str()) {
+                break;
+            }
+            current = next.as_str();
+        }
+
+        self.delegations.insert(from.to_string(), to.to_string());
+        Ok(())
+    }
+
+    /// Revoke `from`'s delegation, restoring its own voting power
+    pub fn revoke(&mut self, from: &str) -> Result<String> {
+        self.delegations.remove(from)
+            .ok_or_else(|| anyhow::anyhow!("{} has no active delegation", from))
+    }
+
+    /// Direct delegate of `voter`, if any
+    pub fn delegate_of(&self, voter: &str) -> Option<&str> {
+        self.delegations.get(voter).map(String::as_str)
+    }
+
+    /// Furthest account on `voter`'s delegation chain, within `max_depth` hops, for which
+    /// `voted` holds
+    ///
+    /// When the final delegate did not vote or the chain runs past `max_depth`, the power
+    /// falls back to the last voter before that point, which may be `voter` itself.
+    /// Returns `None` when nobody on the chain voted.
+    pub fn resolve_voting<'a>(&'a self, voter: &'a str, max_depth: usize, voted: impl Fn(&str) -> bool) -> Option<&'a str> {
+        let mut current = voter;
+        let mut last_voting = voted(voter).then_some(voter);
+        for _ in 0..max_depth {
+            let Some(next) = self.delegations.get(current) else {
+                break;
+            };
+            current = next.as_str();
+            if voted(current) {
+                last_voting = Some(current);
+            }
+        }
+        last_voting
+    }
+
+    pub fn delegators(&self) -> impl Iterator<Item = &str> {
+        self.delegations.keys().map(String::as_str)
+    }
+}
+
+/// Conviction accrued by a single voter on a single proposal
+///
+/// Each epoch applies `conviction = conviction_prev * decay + voting_power`, so
+/// conviction approaches `voting_power / (1 - decay)` the longer a vote is held.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConvictionState {
+    pub voting_power: u64,
+    pub conviction: f64,
+    pub last_epoch: u64,
+}
+
+impl ConvictionState {
+    fn new(voting_power: u64, epoch: u64) -> Self {
+        Self {
+            voting_power,
+            conviction: voting_power as f64,
+            last_epoch: epoch,
+        }
+    }
+
+    /// Conviction at `epoch`, accruing every epoch elapsed since `last_epoch`
+    pub fn conviction_at(&self, epoch: u64, decay: f64) -> f64 {
+        let elapsed = epoch.saturating_sub(self.last_epoch);
+        if elapsed == 0 {
+            return self.conviction;
+        }
+
+        let power = self.voting_power as f64;
+        let asymptote = power / (1.0 - decay);
+        let remaining = decay.powi(elapsed.min(i32::MAX as u64) as i32);
+        asymptote + (self.conviction - asymptote) * remaining
+    }
+}
+
+/// Per-epoch conviction decay factor for a half-life measured in epochs
+fn conviction_decay(half_life_epochs: u64) -> f64 {
+    if half_life_epochs == 0 {
+        0.0
+    } else {
+        0.5f64.powf(1.0 / half_life_epochs as f64)
+    }
+}
+
 impl GovernanceSystem {
     /// Create a new governance system
     pub async fn new(config: GovernanceConfig) -> Result<Self> {
@@ -172,6 +596,8 @@ impl GovernanceSystem {
         let legal_dao = advanced_multi_dao::LegalDAO::new(&config).await?;
         let esg_dao = advanced_multi_dao::EsgDAO::new(&config).await?;
         
+        let epoch_clock = Arc::new(SystemEpochClock::new(config.epoch_duration_secs));
+
         Ok(Self {
             validator_dao,
             lp_dao,
@@ -179,9 +605,45 @@ impl GovernanceSystem {
             esg_dao,
             config,
             metrics: RwLock::new(GovernanceMetrics::default()),
+            proposals: RwLock::new(HashMap::new()),
+            votes: RwLock::new(HashMap::new()),
+            convictions: RwLock::new(HashMap::new()),
+            epoch_clock,
+            epoch_offset: AtomicU64::new(0),
+            eligible_supply: RwLock::new(HashMap::new()),
+            voting_power: RwLock::new(HashMap::new()),
+            power_snapshots: RwLock::new(HashMap::new()),
+            delegations: RwLock::new(DelegationRegistry::default()),
+            delegation_snapshots: RwLock::new(HashMap::new()),
+            pending_emergency_approvals: RwLock::new(HashMap::new()),
+            emergency_state: Arc::new(RwLock::new(EmergencyState::default())),
+            audit_log: None,
         })
     }
 
+    /// Record proposal and emergency decisions in `audit_log`
+    pub fn with_audit_log(mut self, audit_log: Arc<AuditLog>) -> Self {
+        self.audit_log = Some(audit_log);
+        self
+    }
+
+    /// Clock driving conviction accrual; `advance_epoch` offsets apply on top of it
+    pub fn with_epoch_clock(mut self, epoch_clock: Arc<dyn EpochClock>) -> Self {
+        self.epoch_clock = epoch_clock;
+        self
+    }
+
+    /// Current governance epoch
+    pub fn current_epoch(&self) -> u64 {
+        self.epoch_clock.current_epoch().saturating_add(self.epoch_offset.load(Ordering::SeqCst))
+    }
+
+    async fn audit(&self, event: AuditEvent) {
+        if let Some(audit_log) = &self.audit_log {
+            audit_log.append(event).await;
+        }
+    }
+
     /// Submit a new proposal
     pub async fn submit_proposal(
         &self,
@@ -193,21 +655,31 @@ impl GovernanceSystem {
     ) -> Result<String> {
         let proposal_id = uuid::Uuid::new_v4().to_string();
         
-        let proposal = Proposal {
+        let mut proposal = Proposal {
             id: proposal_id.clone(),
             dao_type: dao_type.clone(),
             proposal_type,
             title,
             description,
-            proposer,
+            proposer: proposer.clone(),
             created_at: Utc::now(),
             voting_ends_at: Utc::now() + chrono::Duration::hours(self.config.voting_period_hours as i64),
             votes_for: 0,
             votes_against: 0,
             votes_abstain: 0,
             status: ProposalStatus::Active,
-            semantic_commitment_hash: self.generate_semantic_hash(&proposal_id).await?,
+            semantic_commitment_hash: String::new(),
         };
+        proposal.semantic_commitment_hash = proposal.compute_semantic_commitment();
+
+        self.proposals.write().await.insert(proposal_id.clone(), proposal.clone());
+
+        // Snapshot voting power and delegations at creation so later balance changes cannot
+        // inflate votes and delegations cannot be reshuffled mid-vote
+        let snapshot = self.voting_power.read().await.clone();
+        self.power_snapshots.write().await.insert(proposal_id.clone(), snapshot);
+        let delegations = self.delegations.read().await.clone();
+        self.delegation_snapshots.write().await.insert(proposal_id.clone(), delegations);
 
         // Route to appropriate DAO
         match dao_type {
@@ -224,6 +696,11 @@ impl GovernanceSystem {
             metrics.active_proposals += 1;
         }
 
+        self.audit(AuditEvent::new("governance", "proposal_submitted", serde_json::json!({
+            "proposal_id": proposal_id,
+            "dao_type": dao_type,
+        })).with_actor(proposer)).await;
+
         info!("Proposal {} submitted to {:?} DAO", proposal_id, dao_type);
         Ok(proposal_id)
     }
@@ -237,6 +714,36 @@ impl GovernanceSystem {
         voting_power: u64,
         mechanism: VotingMechanism,
     ) -> Result<()> {
+        self.ensure_voting_open(&proposal_id).await?;
+
+        let snapshot_power = self.power_snapshots.read().await
+            .get(&proposal_id)
+            .ok_or_else(|| anyhow::anyhow!("Proposal not found: {}", proposal_id))?
+            .get(&voter)
+            .copied()
+            .unwrap_or(0);
+
+        if snapshot_power == 0 {
+            return Err(anyhow::anyhow!(
+                "{} has no snapshot voting power on proposal {}",
+                voter, proposal_id
+            ));
+        }
+        if voting_power == 0 || voting_power > snapshot_power {
+            return Err(anyhow::anyhow!(
+                "Voting power {} for {} must be between 1 and snapshot power {} on proposal {}",
+                voting_power, voter, snapshot_power, proposal_id
+            ));
+        }
+
+        let previous_vote = self.votes.read().await
+            .get(&proposal_id)
+            .and_then(|votes| votes.iter().position(|v| v.voter == voter));
+
+        if previous_vote.is_some() && !self.config.allow_vote_change {
+            return Err(anyhow::anyhow!("{} has already voted on proposal {}", voter, proposal_id));
+        }
+
         let vote = Vote {
             proposal_id: proposal_id.clone(),
             voter,
@@ -248,128 +755,1509 @@ impl GovernanceSystem {
 
         // Find which DAO contains this proposal and cast vote
         if self.validator_dao.has_proposal(&proposal_id).await? {
-            self.validator_dao.cast_vote(vote).await?;
+            self.validator_dao.cast_vote(vote.clone()).await?;
         } else if self.lp_dao.has_proposal(&proposal_id).await? {
-            self.lp_dao.cast_vote(vote).await?;
+            self.lp_dao.cast_vote(vote.clone()).await?;
         } else if self.legal_dao.has_proposal(&proposal_id).await? {
-            self.legal_dao.cast_vote(vote).await?;
+            self.legal_dao.cast_vote(vote.clone()).await?;
         } else if self.esg_dao.has_proposal(&proposal_id).await? {
-            self.esg_dao.cast_vote(vote).await?;
+            self.esg_dao.cast_vote(vote.clone()).await?;
         } else {
             return Err(anyhow::anyhow!("Proposal not found: {}", proposal_id));
         }
 
+        // A changed vote starts accruing conviction from scratch
+        let conviction_key = (proposal_id.clone(), vote.voter.clone());
+        if matches!(vote.mechanism, VotingMechanism::Conviction { .. }) {
+            let epoch = self.current_epoch();
+            self.convictions.write().await.insert(
+                conviction_key,
+                ConvictionState::new(vote.voting_power, epoch),
+            );
+        } else {
+            self.convictions.write().await.remove(&conviction_key);
+        }
+
+        {
+            let mut votes = self.votes.write().await;
+            let proposal_votes = votes.entry(proposal_id.clone()).or_default();
+            match previous_vote {
+                Some(index) => proposal_votes[index] = vote,
+                None => proposal_votes.push(vote),
+            }
+        }
+
         // Update metrics
         {
             let mut metrics = self.metrics.write().await;
             metrics.total_votes_cast += 1;
         }
 
-        info!("Vote cast on proposal {}", proposal_id);
+        if previous_vote.is_some() {
+            info!("Vote changed on proposal {}", proposal_id);
+        } else {
+            info!("Vote cast on proposal {}", proposal_id);
+        }
         Ok(())
     }
 
-    /// Execute a passed proposal
-    pub async fn execute_proposal(&self, proposal_id: String) -> Result<()> {
-        info!("Executing proposal {}", proposal_id);
-        
-        // Find and execute proposal in appropriate DAO
-        if self.validator_dao.has_proposal(&proposal_id).await? {
-            self.validator_dao.execute_proposal(proposal_id).await?;
-        } else if self.lp_dao.has_proposal(&proposal_id).await? {
-            self.lp_dao.execute_proposal(proposal_id).await?;
-        } else if self.legal_dao.has_proposal(&proposal_id).await? {
-            self.legal_dao.execute_proposal(proposal_id).await?;
-        } else if self.esg_dao.has_proposal(&proposal_id).await? {
-            self.esg_dao.execute_proposal(proposal_id).await?;
-        } else {
+    /// Tally a proposal, weighting each vote by its voting mechanism
+    pub async fn tally_proposal(&self, proposal_id: &str) -> Result<TallyResult> {
+        if !self.proposals.read().await.contains_key(proposal_id) {
             return Err(anyhow::anyhow!("Proposal not found: {}", proposal_id));
         }
 
-        info!("Proposal {} executed successfully", proposal_id);
-        Ok(())
-    }
+        let epoch = self.current_epoch();
+        let decay = conviction_decay(self.config.conviction_half_life_epochs);
 
-    /// Activate emergency protocols
-    pub async fn activate_emergency(&self, action: EmergencyAction, justification: String) -> Result<()> {
-        warn!("Emergency protocol activated: {:?}", action);
-        
-        // Update metrics
+        let (delegated, delegated_away) = self.resolve_delegated_power(proposal_id).await;
+
+        let mut result = TallyResult::new(proposal_id);
         {
-            let mut metrics = self.metrics.write().await;
-            metrics.emergency_activations += 1;
-        }
+            let votes = self.votes.read().await;
+            let convictions = self.convictions.read().await;
+            for vote in votes.get(proposal_id).into_iter().flatten() {
+                let delegated_power = delegated.get(&vote.voter).copied().unwrap_or(0);
 
-        // Implement emergency actions
-        match action {
-            EmergencyAction::PauseProtocol => {
-                // Pause all protocol operations
-                info!("Protocol paused due to emergency");
-            }
-            EmergencyAction::FreezeAssets => {
-                // Freeze all asset movements
-                info!("Assets frozen due to emergency");
-            }
-            EmergencyAction::ActivateCircuitBreaker => {
-                // Activate circuit breaker
-                info!("Circuit breaker activated");
+                // Power delegated away is voted further down the chain; a delegator only
+                // votes power that stopped with it
+                let relayed_vote;
+                let (vote, conviction) = if delegated_away.contains(&vote.voter) {
+                    if delegated_power == 0 {
+                        continue;
+                    }
+                    relayed_vote = Vote { voting_power: 0, ..vote.clone() };
+                    (&relayed_vote, None)
+                } else {
+                    let conviction = convictions
+                        .get(&(proposal_id.to_string(), vote.voter.clone()))
+                        .map(|state| state.conviction_at(epoch, decay));
+                    (vote, conviction)
+                };
+                let weight = self.effective_vote_weight(vote, delegated_power, conviction);
+                result.record(vote, delegated_power, weight);
             }
-            EmergencyAction::EmergencyWithdrawal => {
-                // Enable emergency withdrawals
-                info!("Emergency withdrawal enabled");
+        }
+
+        // Keep the stored proposal totals in line with the weighted tally
+        if let Some(proposal) = self.proposals.write().await.get_mut(proposal_id) {
+            proposal.votes_for = result.votes_for;
+            proposal.votes_against = result.votes_against;
+            proposal.votes_abstain = result.votes_abstain;
+        }
+
+        Ok(result)
+    }
+
+    /// Weighted voting power contributed by a single vote plus power delegated to its voter
+    fn effective_vote_weight(&self, vote: &Vote, delegated_power: u64, conviction: Option<f64>) -> u64 {
+        let power = vote.voting_power.saturating_add(delegated_power);
+        match &vote.mechanism {
+            VotingMechanism::Simple | VotingMechanism::Delegation { .. } => power,
+            VotingMechanism::Quadratic => power.isqrt(),
+            VotingMechanism::Conviction { conviction_score } => match conviction {
+                Some(accrued) if self.config.conviction_voting_enabled => {
+                    (accrued as u64).saturating_add(delegated_power)
+                }
+                _ => (power as f64 * conviction_score.max(0.0)) as u64,
+            },
+        }
+    }
+
+    /// Snapshot power delegated to each voter who voted on a proposal, and the delegators
+    /// whose own votes that power replaces
+    ///
+    /// Both power and delegations come from the proposal's creation-time snapshots.
+    async fn resolve_delegated_power(&self, proposal_id: &str) -> (HashMap<String, u64>, HashSet<String>) {
+        let voters: HashSet<String> = self.votes.read().await
+            .get(proposal_id)
+            .map(|votes| votes.iter().map(|v| v.voter.clone()).collect())
+            .unwrap_or_default();
+        let power_snapshots = self.power_snapshots.read().await;
+        let delegation_snapshots = self.delegation_snapshots.read().await;
+        let (Some(snapshot), Some(delegations)) =
+            (power_snapshots.get(proposal_id), delegation_snapshots.get(proposal_id))
+        else {
+            return (HashMap::new(), HashSet::new());
+        };
+
+        let mut delegated: HashMap<String, u64> = HashMap::new();
+        let mut delegated_away = HashSet::new();
+        for delegator in delegations.delegators() {
+            let power = snapshot.get(delegator).copied().unwrap_or(0);
+            if power == 0 {
+                continue;
             }
-            EmergencyAction::SecurityPatch => {
-                // Apply security patch
-                info!("Security patch applied");
+            let recipient = delegations.resolve_voting(
+                delegator,
+                self.config.max_delegation_depth,
+                |account| voters.contains(account),
+            );
+            if let Some(delegate) = recipient.filter(|&delegate| delegate != delegator) {
+                let entry = delegated.entry(delegate.to_string()).or_default();
+                *entry = entry.saturating_add(power);
+                delegated_away.insert(delegator.to_string());
             }
         }
+        (delegated, delegated_away)
+    }
 
+    /// Delegate a voter's power to another account on proposals created afterwards
+    pub async fn delegate_to(&self, from: String, to: String) -> Result<()> {
+        self.delegations.write().await.delegate(&from, &to)?;
+        info!("{} delegated voting power to {}", from, to);
         Ok(())
     }
 
-    /// Get governance metrics
-    pub async fn get_metrics(&self) -> GovernanceMetrics {
-        self.metrics.read().await.clone()
+    /// Revoke a voter's delegation, restoring self-voting power on proposals created afterwards
+    pub async fn revoke_delegation(&self, from: &str) -> Result<()> {
+        let delegate = self.delegations.write().await.revoke(from)?;
+        info!("{} revoked delegation to {}", from, delegate);
+        Ok(())
     }
 
-    /// Generate semantic commitment hash for LLM integrity
-    async fn generate_semantic_hash(&self, proposal_id: &str) -> Result<String> {
-        use sha2::{Sha256, Digest};
-        let mut hasher = Sha256::new();
-        hasher.update(proposal_id.as_bytes());
-        hasher.update(Utc::now().timestamp().to_string().as_bytes());
-        Ok(format!("{:x}", hasher.finalize()))
+    /// Current accrued conviction for a voter on a proposal (0 if none is held)
+    pub async fn current_conviction(&self, proposal_id: &str, voter: &str) -> Result<f64> {
+        let epoch = self.current_epoch();
+        let decay = conviction_decay(self.config.conviction_half_life_epochs);
+
+        Ok(self.convictions.read().await
+            .get(&(proposal_id.to_string(), voter.to_string()))
+            .map(|state| state.conviction_at(epoch, decay))
+            .unwrap_or(0.0))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Advance the governance epoch ahead of the clock, accruing conviction on all held votes
+    pub async fn advance_epoch(&self) -> u64 {
+        self.epoch_offset.fetch_add(1, Ordering::SeqCst);
+        self.current_epoch()
+    }
 
-    #[tokio::test]
-    async fn test_governance_initialization() {
-        let config = GovernanceConfig::default();
-        let governance = GovernanceSystem::new(config).await;
-        assert!(governance.is_ok());
+    /// Withdraw a voter's vote, resetting any accrued conviction
+    pub async fn withdraw_vote(&self, proposal_id: &str, voter: &str) -> Result<()> {
+        self.ensure_voting_open(proposal_id).await?;
+
+        let removed = {
+            let mut votes = self.votes.write().await;
+            let proposal_votes = votes.get_mut(proposal_id)
+                .ok_or_else(|| anyhow::anyhow!("No votes on proposal: {}", proposal_id))?;
+            let before = proposal_votes.len();
+            proposal_votes.retain(|vote| vote.voter != voter);
+            before - proposal_votes.len()
+        };
+
+        if removed == 0 {
+            return Err(anyhow::anyhow!("Voter {} has no vote on proposal {}", voter, proposal_id));
+        }
+
+        self.convictions.write().await.remove(&(proposal_id.to_string(), voter.to_string()));
+
+        info!("Vote by {} withdrawn from proposal {}", voter, proposal_id);
+        Ok(())
     }
 
-    #[tokio::test]
-    async fn test_proposal_submission() {
-        let config = GovernanceConfig::default();
-        let governance = GovernanceSystem::new(config).await.unwrap();
-        
-        let proposal_id = governance.submit_proposal(
-            DAOType::Validator,
-            ProposalType::ParameterChange {
-                parameter: "test_param".to_string(),
-                old_value: "old".to_string(),
-                new_value: "new".to_string(),
-            },
-            "Test Proposal".to_string(),
-            "Test Description".to_string(),
-            "test_proposer".to_string(),
-        ).await;
-        
-        assert!(proposal_id.is_ok());
+    /// Votes can only be cast or withdrawn while a proposal is active and its window is open
+    async fn ensure_voting_open(&self, proposal_id: &str) -> Result<()> {
+        let proposals = self.proposals.read().await;
+        let proposal = proposals.get(proposal_id)
+            .ok_or_else(|| anyhow::anyhow!("Proposal not found: {}", proposal_id))?;
+
+        if proposal.status != ProposalStatus::Active {
+            return Err(anyhow::anyhow!("Proposal {} is not active: {:?}", proposal_id, proposal.status));
+        }
+        if Utc::now() > proposal.voting_ends_at {
+            return Err(anyhow::anyhow!("Voting on proposal {} ended at {}", proposal_id, proposal.voting_ends_at));
+        }
+        Ok(())
+    }
+
+    /// Record a voter's current voting power; applies to proposals submitted afterwards
+    pub async fn set_voting_power(&self, voter: String, power: u64) {
+        self.voting_power.write().await.insert(voter, power);
+    }
+
+    /// Set the voting supply eligible to participate in a DAO's proposals
+    pub async fn set_eligible_supply(&self, dao_type: DAOType, supply: u64) {
+        self.eligible_supply.write().await.insert(dao_type, supply);
+    }
+
+    /// Close voting on a proposal and decide it against its DAO's quorum and approval rules
+    pub async fn finalize_proposal(&self, proposal_id: &str) -> Result<ProposalStatus> {
+        let (dao_type, voting_ends_at, status) = {
+            let proposals = self.proposals.read().await;
+            let proposal = proposals.get(proposal_id)
+                .ok_or_else(|| anyhow::anyhow!("Proposal not found: {}", proposal_id))?;
+            (proposal.dao_type.clone(), proposal.voting_ends_at, proposal.status.clone())
+        };
+
+        if status != ProposalStatus::Active {
+            return Err(anyhow::anyhow!("Proposal {} is not active: {:?}", proposal_id, status));
+        }
+        if Utc::now() <= voting_ends_at {
+            return Err(anyhow::anyhow!("Voting on proposal {} ends at {}", proposal_id, voting_ends_at));
+        }
+
+        let eligible_supply = self.eligible_supply.read().await
+            .get(&dao_type)
+            .copied()
+            .filter(|supply| *supply > 0)
+            .ok_or_else(|| anyhow::anyhow!("No eligible supply configured for {:?} DAO", dao_type))?;
+
+        let tally = self.tally_proposal(proposal_id).await?;
+        let rules = self.config.rules_for(&dao_type);
+
+        // Quorum counts raw participating power so quadratic weighting cannot mask turnout
+        let participating_power: u64 = tally.breakdown.values().map(|m| m.raw_power).sum();
+        let participation = participating_power as f64 / eligible_supply as f64;
+        let decisive_votes = tally.votes_for + tally.votes_against;
+        let approval = if decisive_votes > 0 {
+            tally.votes_for as f64 / decisive_votes as f64
+        } else {
+            0.0
+        };
+
+        let new_status = if participation >= rules.quorum_threshold && approval > rules.approval_threshold {
+            ProposalStatus::Passed
+        } else {
+            ProposalStatus::Rejected
+        };
+
+        if let Some(proposal) = self.proposals.write().await.get_mut(proposal_id) {
+            proposal.status = new_status.clone();
+        }
+
+        {
+            let mut metrics = self.metrics.write().await;
+            metrics.active_proposals = metrics.active_proposals.saturating_sub(1);
+            match new_status {
+                ProposalStatus::Passed => metrics.passed_proposals += 1,
+                _ => metrics.rejected_proposals += 1,
+            }
+        }
+
+        self.audit(AuditEvent::new("governance", "proposal_finalized", serde_json::json!({
+            "proposal_id": proposal_id,
+            "status": format!("{:?}", new_status),
+            "participation": participation,
+            "approval": approval,
+        }))).await;
+
+        info!(
+            "Proposal {} finalized as {:?} (participation {:.2}%, approval {:.2}%)",
+            proposal_id, new_status, participation * 100.0, approval * 100.0
+        );
+        Ok(new_status)
+    }
+
+    /// Execute a passed proposal
+    ///
+    /// Only `Passed` proposals (see `finalize_proposal`) execute, and each executes once:
+    /// the proposal moves to `Executed` before the DAO runs it and back to `Passed` if
+    /// the DAO fails, so concurrent or repeated calls cannot execute it twice.
+    pub async fn execute_proposal(&self, proposal_id: String) -> Result<()> {
+        info!("Executing proposal {}", proposal_id);
+
+        let proposal = {
+            let mut proposals = self.proposals.write().await;
+            let proposal = proposals.get_mut(&proposal_id)
+                .ok_or_else(|| anyhow::anyhow!("Proposal not found: {}", proposal_id))?;
+            match proposal.status {
+                ProposalStatus::Passed => {}
+                ProposalStatus::Executed => {
+                    return Err(ProposalError::AlreadyExecuted { proposal_id }.into());
+                }
+                ref status => {
+                    return Err(ProposalError::NotExecutable {
+                        proposal_id: proposal_id.clone(),
+                        status: status.clone(),
+                    }.into());
+                }
+            }
+
+            proposal.status = ProposalStatus::Executed;
+            proposal.clone()
+        };
+
+        // Refuse to execute anything other than what was proposed and voted on, checking
+        // the very copy the DAO is handed
+        let result = match proposal.verify_semantic_commitment() {
+            Ok(()) => self.route_execution(&proposal).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            if let Some(proposal) = self.proposals.write().await.get_mut(&proposal_id) {
+                proposal.status = ProposalStatus::Passed;
+            }
+            return Err(e);
+        }
+
+        self.audit(AuditEvent::new("governance", "proposal_executed", serde_json::json!({
+            "proposal_id": proposal_id,
+        }))).await;
+
+        info!("Proposal {} executed successfully", proposal_id);
+        Ok(())
+    }
+
+    /// Execute a verified proposal in the DAO that owns it
+    ///
+    /// The DAO runs the proposal it is handed rather than its own stored copy, so what
+    /// executes is exactly what was checked against the semantic commitment.
+    async fn route_execution(&self, proposal: &Proposal) -> Result<()> {
+        match proposal.dao_type {
+            DAOType::Validator => self.validator_dao.execute_proposal(proposal).await,
+            DAOType::LP => self.lp_dao.execute_proposal(proposal).await,
+            DAOType::Legal => self.legal_dao.execute_proposal(proposal).await,
+            DAOType::ESG => self.esg_dao.execute_proposal(proposal).await,
+        }
+    }
+
+    /// Submit signer approvals for an emergency action
+    ///
+    /// Approvals accumulate across calls; the action executes once approvals from
+    /// distinct configured signers reach `emergency_threshold` of the signer set.
+    pub async fn activate_emergency(
+        &self,
+        action: EmergencyAction,
+        justification: String,
+        authorization: EmergencyAuthorization,
+    ) -> Result<EmergencyActivation> {
+        let nonce = self.emergency_nonce().await;
+        let message = emergency_message(&self.config.emergency_domain, nonce, &action, &justification);
+        let (approvals, required) = self.collect_emergency_approvals(message, &authorization).await?;
+
+        if approvals < required {
+            info!("Emergency {:?} approved by {}/{} signers", action, approvals, required);
+            return Ok(EmergencyActivation::Pending { approvals, required });
+        }
+        self.consume_emergency_nonce(nonce).await?;
+
+        warn!("Emergency protocol activated: {:?} ({})", action, justification);
+
+        // Update metrics
+        {
+            let mut metrics = self.metrics.write().await;
+            metrics.emergency_activations += 1;
+        }
+
+        self.execute_emergency_action(&action).await;
+        self.audit(AuditEvent::new("governance", "emergency_activated", serde_json::json!({
+            "action": action,
+            "justification": justification,
+            "approvals": approvals,
+        }))).await;
+        Ok(EmergencyActivation::Executed)
+    }
+
+    /// Submit signer approvals to clear an active emergency action
+    ///
+    /// Clearance is authorized separately from activation, with the same threshold.
+    pub async fn clear_emergency(
+        &self,
+        action: EmergencyAction,
+        justification: String,
+        authorization: EmergencyAuthorization,
+    ) -> Result<EmergencyActivation> {
+        let nonce = self.emergency_nonce().await;
+        let message = emergency_clearance_message(&self.config.emergency_domain, nonce, &action, &justification);
+        let (approvals, required) = self.collect_emergency_approvals(message, &authorization).await?;
+
+        if approvals < required {
+            info!("Clearance of {:?} approved by {}/{} signers", action, approvals, required);
+            return Ok(EmergencyActivation::Pending { approvals, required });
+        }
+        self.consume_emergency_nonce(nonce).await?;
+
+        {
+            let mut state = self.emergency_state.write().await;
+            match action {
+                EmergencyAction::PauseProtocol => state.paused = false,
+                EmergencyAction::FreezeAssets => state.assets_frozen = false,
+                EmergencyAction::ActivateCircuitBreaker => state.circuit_breaker_active = false,
+                EmergencyAction::EmergencyWithdrawal => state.emergency_withdrawals_enabled = false,
+                EmergencyAction::SecurityPatch => {}
+            }
+            state.last_updated = Some(Utc::now());
+        }
+
+        self.audit(AuditEvent::new("governance", "emergency_cleared", serde_json::json!({
+            "action": action,
+            "justification": justification,
+            "approvals": approvals,
+        }))).await;
+
+        info!("Emergency {:?} cleared ({})", action, justification);
+        Ok(EmergencyActivation::Executed)
+    }
+
+    /// Verify and record approvals for `message`, returning (approvals, required)
+    ///
+    /// Pending approvals are consumed once the threshold is reached.
+    async fn collect_emergency_approvals(
+        &self,
+        message: [u8; 32],
+        authorization: &EmergencyAuthorization,
+    ) -> Result<(usize, usize)> {
+        let required = self.required_emergency_approvals()?;
+
+        for approval in &authorization.approvals {
+            self.verify_signer_approval(&message, approval)?;
+        }
+
+        let mut pending = self.pending_emergency_approvals.write().await;
+        let signers = pending.entry(message).or_default();
+        signers.extend(authorization.approvals.iter().map(|a| a.signer));
+        let approvals = signers.len();
+        if approvals >= required {
+            pending.remove(&message);
+        }
+        Ok((approvals, required))
+    }
+
+    /// Nonce the next emergency activation or clearance must be approved under
+    pub async fn emergency_nonce(&self) -> u64 {
+        self.emergency_state.read().await.emergency_nonce
+    }
+
+    /// Advance the emergency nonce if it is still `nonce`, retiring every approval
+    /// signed under it
+    ///
+    /// Fails when another emergency executed while these approvals were collected.
+    async fn consume_emergency_nonce(&self, nonce: u64) -> Result<()> {
+        let mut state = self.emergency_state.write().await;
+        if state.emergency_nonce != nonce {
+            return Err(anyhow::anyhow!(
+                "Emergency approvals were signed for nonce {} but the current nonce is {}",
+                nonce, state.emergency_nonce
+            ));
+        }
+        state.emergency_nonce += 1;
+        self.pending_emergency_approvals.write().await.clear();
+        Ok(())
+    }
+
+    /// Current emergency flags
+    pub async fn emergency_state(&self) -> EmergencyState {
+        self.emergency_state.read().await.clone()
+    }
+
+    /// Handle to the emergency flags this system enforces
+    pub fn shared_emergency_state(&self) -> SharedEmergencyState {
+        self.emergency_state.clone()
+    }
+
+    pub async fn is_paused(&self) -> bool {
+        self.emergency_state.read().await.paused
+    }
+
+    pub async fn assets_frozen(&self) -> bool {
+        self.emergency_state.read().await.assets_frozen
+    }
+
+    pub async fn circuit_breaker_active(&self) -> bool {
+        self.emergency_state.read().await.circuit_breaker_active
+    }
+
+    /// Check deposits may proceed under the current emergency state
+    pub async fn ensure_deposits_allowed(&self) -> Result<()> {
+        let state = self.emergency_state.read().await;
+        if state.paused {
+            return Err(EmergencyError::ProtocolPaused.into());
+        }
+        if state.assets_frozen {
+            return Err(EmergencyError::AssetsFrozen.into());
+        }
+        if state.circuit_breaker_active {
+            return Err(EmergencyError::CircuitBreakerActive.into());
+        }
+        Ok(())
+    }
+
+    /// Check redemptions may proceed; emergency withdrawals bypass pause and circuit breaker
+    pub async fn ensure_redemptions_allowed(&self) -> Result<()> {
+        let state = self.emergency_state.read().await;
+        if state.assets_frozen {
+            return Err(EmergencyError::AssetsFrozen.into());
+        }
+        if state.emergency_withdrawals_enabled {
+            return Ok(());
+        }
+        if state.paused {
+            return Err(EmergencyError::ProtocolPaused.into());
+        }
+        if state.circuit_breaker_active {
+            return Err(EmergencyError::CircuitBreakerActive.into());
+        }
+        Ok(())
+    }
+
+    /// Number of distinct signer approvals needed to execute an emergency action
+    fn required_emergency_approvals(&self) -> Result<usize> {
+        let signers = self.config.emergency_signers.len();
+        if signers == 0 {
+            return Err(anyhow::anyhow!("No emergency signers configured"));
+        }
+        let required = (self.config.emergency_threshold * signers as f64).ceil() as usize;
+        Ok(required.clamp(1, signers))
+    }
+
+    /// Check an approval comes from a configured signer and signs `message`
+    fn verify_signer_approval(&self, message: &[u8; 32], approval: &SignerApproval) -> Result<()> {
+        use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+        if !self.config.emergency_signers.contains(&approval.signer) {
+            return Err(anyhow::anyhow!("Approval from unknown emergency signer"));
+        }
+
+        let key = VerifyingKey::from_bytes(&approval.signer)
+            .map_err(|e| anyhow::anyhow!("Invalid emergency signer key: {}", e))?;
+        let signature = Signature::from_slice(&approval.signature)
+            .map_err(|e| anyhow::anyhow!("Malformed emergency signature: {}", e))?;
+        key.verify(message, &signature)
+            .map_err(|_| anyhow::anyhow!("Emergency signature verification failed"))
+    }
+
+    /// Apply an authorized emergency action
+    async fn execute_emergency_action(&self, action: &EmergencyAction) {
+        let mut state = self.emergency_state.write().await;
+        match action {
+            EmergencyAction::PauseProtocol => {
+                // Pause all protocol operations
+                state.paused = true;
+                info!("Protocol paused due to emergency");
+            }
+            EmergencyAction::FreezeAssets => {
+                // Freeze all asset movements
+                state.assets_frozen = true;
+                info!("Assets frozen due to emergency");
+            }
+            EmergencyAction::ActivateCircuitBreaker => {
+                // Activate circuit breaker
+                state.circuit_breaker_active = true;
+                info!("Circuit breaker activated");
+            }
+            EmergencyAction::EmergencyWithdrawal => {
+                // Enable emergency withdrawals
+                state.emergency_withdrawals_enabled = true;
+                info!("Emergency withdrawal enabled");
+            }
+            EmergencyAction::SecurityPatch => {
+                // Apply security patch
+                info!("Security patch applied");
+            }
+        }
+        state.last_updated = Some(Utc::now());
+    }
+
+    /// List proposals matching `filter`, newest first
+    ///
+    /// Ties on `created_at` are ordered by proposal id so pages stay stable between calls.
+    pub async fn list_proposals(&self, filter: ProposalFilter, page: Pagination) -> ProposalPage {
+        let limit = match page.limit {
+            0 => DEFAULT_PROPOSAL_PAGE_SIZE,
+            limit => limit.min(MAX_PROPOSAL_PAGE_SIZE),
+        };
+
+        let mut matching: Vec<Proposal> = self.proposals.read().await
+            .values()
+            .filter(|proposal| filter.matches(proposal))
+            .cloned()
+            .collect();
+        matching.sort_by(|a, b| b.created_at.cmp(&a.created_at).then_with(|| a.id.cmp(&b.id)));
+
+        let total = matching.len();
+        let proposals = matching.into_iter().skip(page.offset).take(limit).collect();
+        ProposalPage { proposals, total, offset: page.offset, limit }
+    }
+
+    /// Get governance metrics
+    pub async fn get_metrics(&self) -> GovernanceMetrics {
+        self.metrics.read().await.clone()
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use epoch_clock::MockEpochClock;
+
+    #[tokio::test]
+    async fn test_governance_initialization() {
+        let config = GovernanceConfig::default();
+        let governance = GovernanceSystem::new(config).await;
+        assert!(governance.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_proposal_submission() {
+        let config = GovernanceConfig::default();
+        let governance = GovernanceSystem::new(config).await.unwrap();
+        
+        let proposal_id = governance.submit_proposal(
+            DAOType::Validator,
+            ProposalType::ParameterChange {
+                parameter: "test_param".to_string(),
+                old_value: "old".to_string(),
+                new_value: "new".to_string(),
+            },
+            "Test Proposal".to_string(),
+            "Test Description".to_string(),
+            "test_proposer".to_string(),
+        ).await;
+        
+        assert!(proposal_id.is_ok());
+    }
+
+    async fn submit_parameter_change(governance: &GovernanceSystem, dao_type: DAOType, parameter: &str) -> String {
+        governance.submit_proposal(
+            dao_type,
+            ProposalType::ParameterChange {
+                parameter: parameter.to_string(),
+                old_value: "old".to_string(),
+                new_value: "new".to_string(),
+            },
+            format!("Change {}", parameter),
+            "Test Description".to_string(),
+            "test_proposer".to_string(),
+        ).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_list_proposals_filters_by_dao_and_status() {
+        let governance = GovernanceSystem::new(GovernanceConfig::default()).await.unwrap();
+        let lp_first = submit_parameter_change(&governance, DAOType::LP, "fee").await;
+        let lp_second = submit_parameter_change(&governance, DAOType::LP, "cap").await;
+        let legal = submit_parameter_change(&governance, DAOType::Legal, "jurisdiction").await;
+        governance.proposals.write().await.get_mut(&lp_second).unwrap().status = ProposalStatus::Rejected;
+
+        let ids = |page: ProposalPage| -> HashSet<String> { page.proposals.into_iter().map(|p| p.id).collect() };
+
+        let lp = governance.list_proposals(
+            ProposalFilter { dao_type: Some(DAOType::LP), ..ProposalFilter::default() },
+            Pagination::default(),
+        ).await;
+        assert_eq!(lp.total, 2);
+        assert_eq!(ids(lp), HashSet::from([lp_first.clone(), lp_second.clone()]));
+
+        let active = governance.list_proposals(
+            ProposalFilter { status: Some(ProposalStatus::Active), ..ProposalFilter::default() },
+            Pagination::default(),
+        ).await;
+        assert_eq!(ids(active), HashSet::from([lp_first.clone(), legal]));
+
+        let active_lp = governance.list_proposals(
+            ProposalFilter {
+                dao_type: Some(DAOType::LP),
+                status: Some(ProposalStatus::Active),
+                ..ProposalFilter::default()
+            },
+            Pagination::default(),
+        ).await;
+        assert_eq!(ids(active_lp), HashSet::from([lp_first]));
+
+        let future = governance.list_proposals(
+            ProposalFilter { created_from: Some(Utc::now() + chrono::Duration::hours(1)), ..ProposalFilter::default() },
+            Pagination::default(),
+        ).await;
+        assert_eq!(future.total, 0);
+    }
+
+    #[tokio::test]
+    async fn test_list_proposals_page_boundaries() {
+        let governance = GovernanceSystem::new(GovernanceConfig::default()).await.unwrap();
+        for i in 0..5 {
+            submit_parameter_change(&governance, DAOType::Validator, &format!("param_{}", i)).await;
+        }
+
+        let mut seen = Vec::new();
+        for (offset, expected) in [(0, 2), (2, 2), (4, 1), (5, 0)] {
+            let page = governance.list_proposals(ProposalFilter::default(), Pagination { offset, limit: 2 }).await;
+            assert_eq!(page.total, 5);
+            assert_eq!(page.proposals.len(), expected);
+            seen.extend(page.proposals.into_iter().map(|p| p.id));
+        }
+        assert_eq!(seen.iter().collect::<HashSet<_>>().len(), 5);
+
+        // Repeated listing returns the same order
+        let all = governance.list_proposals(ProposalFilter::default(), Pagination::default()).await;
+        assert_eq!(all.limit, DEFAULT_PROPOSAL_PAGE_SIZE);
+        assert_eq!(all.proposals.into_iter().map(|p| p.id).collect::<Vec<_>>(), seen);
+    }
+
+    #[tokio::test]
+    async fn test_altered_parameter_change_breaks_semantic_commitment() {
+        let governance = GovernanceSystem::new(GovernanceConfig::default()).await.unwrap();
+        let proposal_id = submit_parameter_change(&governance, DAOType::Validator, "max_leverage").await;
+
+        let submitted = governance.proposals.read().await[&proposal_id].clone();
+        assert!(submitted.verify_semantic_commitment().is_ok());
+
+        if let Some(proposal) = governance.proposals.write().await.get_mut(&proposal_id) {
+            proposal.status = ProposalStatus::Passed;
+            proposal.proposal_type = ProposalType::ParameterChange {
+                parameter: "max_leverage".to_string(),
+                old_value: "old".to_string(),
+                new_value: "tampered".to_string(),
+            };
+        }
+
+        let tampered = governance.proposals.read().await[&proposal_id].clone();
+        assert_ne!(tampered.compute_semantic_commitment(), submitted.semantic_commitment_hash);
+        let result = governance.execute_proposal(proposal_id).await;
+        assert!(result.unwrap_err().to_string().contains("semantic commitment"));
+    }
+
+    #[tokio::test]
+    async fn test_semantic_commitment_matches_the_shared_llm_agent_commitment() {
+        let governance = GovernanceSystem::new(GovernanceConfig::default()).await.unwrap();
+        let proposal_id = submit_parameter_change(&governance, DAOType::LP, "fee_rate").await;
+        let proposal = governance.proposals.read().await[&proposal_id].clone();
+
+        // The LLM agent checks this pair against the recorded commitment
+        let (text, instruction_data) = proposal.semantic_commitment_inputs();
+        assert!(text.starts_with(&proposal.title));
+        assert_eq!(
+            proposal.semantic_commitment_hash,
+            semantic_commitment::semantic_commitment_hash(&text, &instruction_data)
+        );
+    }
+
+    /// Register voting power; only voters registered before a proposal is submitted can vote on it
+    async fn register_voters(governance: &GovernanceSystem, voters: &[(&str, u64)]) {
+        for (voter, power) in voters {
+            governance.set_voting_power(voter.to_string(), *power).await;
+        }
+    }
+
+    async fn submit_test_proposal(governance: &GovernanceSystem) -> String {
+        governance.submit_proposal(
+            DAOType::LP,
+            ProposalType::ParameterChange {
+                parameter: "redemption_fee".to_string(),
+                old_value: "50".to_string(),
+                new_value: "25".to_string(),
+            },
+            "Lower redemption fee".to_string(),
+            "Reduce redemption fee to 25 bps".to_string(),
+            "test_proposer".to_string(),
+        ).await.unwrap()
+    }
+
+    async fn register_whale_and_crowd(governance: &GovernanceSystem) {
+        governance.set_voting_power("whale".to_string(), 10_000).await;
+        for i in 0..20 {
+            governance.set_voting_power(format!("crowd_{}", i), 100).await;
+        }
+    }
+
+    async fn cast_whale_vs_crowd(governance: &GovernanceSystem, proposal_id: &str, mechanism: VotingMechanism) {
+        governance.cast_vote(
+            proposal_id.to_string(),
+            "whale".to_string(),
+            VoteType::For,
+            10_000,
+            mechanism.clone(),
+        ).await.unwrap();
+
+        for i in 0..20 {
+            governance.cast_vote(
+                proposal_id.to_string(),
+                format!("crowd_{}", i),
+                VoteType::Against,
+                100,
+                mechanism.clone(),
+            ).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_simple_tally_favours_whale() {
+        let governance = GovernanceSystem::new(GovernanceConfig::default()).await.unwrap();
+        register_whale_and_crowd(&governance).await;
+        let proposal_id = submit_test_proposal(&governance).await;
+        cast_whale_vs_crowd(&governance, &proposal_id, VotingMechanism::Simple).await;
+
+        let tally = governance.tally_proposal(&proposal_id).await.unwrap();
+        assert_eq!(tally.votes_for, 10_000);
+        assert_eq!(tally.votes_against, 2_000);
+        assert_eq!(tally.total_voters, 21);
+    }
+
+    #[tokio::test]
+    async fn test_quadratic_tally_favours_crowd() {
+        let governance = GovernanceSystem::new(GovernanceConfig::default()).await.unwrap();
+        register_whale_and_crowd(&governance).await;
+        let proposal_id = submit_test_proposal(&governance).await;
+        cast_whale_vs_crowd(&governance, &proposal_id, VotingMechanism::Quadratic).await;
+
+        let tally = governance.tally_proposal(&proposal_id).await.unwrap();
+        assert_eq!(tally.votes_for, 100); // sqrt(10_000)
+        assert_eq!(tally.votes_against, 200); // 20 * sqrt(100)
+
+        let quadratic = &tally.breakdown[&MechanismKind::Quadratic];
+        assert_eq!(quadratic.voters, 21);
+        assert_eq!(quadratic.raw_power, 12_000);
+    }
+
+    #[tokio::test]
+    async fn test_conviction_rises_toward_asymptote() {
+        let config = GovernanceConfig {
+            conviction_half_life_epochs: 1, // decay = 0.5, asymptote = 2x voting power
+            ..GovernanceConfig::default()
+        };
+        let clock = Arc::new(MockEpochClock::new(config.epoch_duration_secs, 1_700_000_000));
+        let governance = GovernanceSystem::new(config).await.unwrap()
+            .with_epoch_clock(clock.clone());
+        register_voters(&governance, &[("patient_voter", 100)]).await;
+        let proposal_id = submit_test_proposal(&governance).await;
+
+        governance.cast_vote(
+            proposal_id.clone(),
+            "patient_voter".to_string(),
+            VoteType::For,
+            100,
+            VotingMechanism::Conviction { conviction_score: 1.0 },
+        ).await.unwrap();
+
+        let mut previous = governance.current_conviction(&proposal_id, "patient_voter").await.unwrap();
+        assert_eq!(previous, 100.0);
+
+        for _ in 0..20 {
+            clock.advance_epochs(1);
+            let current = governance.current_conviction(&proposal_id, "patient_voter").await.unwrap();
+            assert!(current > previous);
+            assert!(current < 200.0);
+            previous = current;
+        }
+        assert!(previous > 199.9);
+
+        let tally = governance.tally_proposal(&proposal_id).await.unwrap();
+        assert_eq!(tally.votes_for, previous as u64);
+    }
+
+    #[tokio::test]
+    async fn test_conviction_resets_on_withdrawal() {
+        let clock = Arc::new(MockEpochClock::new(86_400, 1_700_000_000));
+        let governance = GovernanceSystem::new(GovernanceConfig::default()).await.unwrap()
+            .with_epoch_clock(clock.clone());
+        register_voters(&governance, &[("fickle_voter", 100)]).await;
+        let proposal_id = submit_test_proposal(&governance).await;
+
+        governance.cast_vote(
+            proposal_id.clone(),
+            "fickle_voter".to_string(),
+            VoteType::For,
+            100,
+            VotingMechanism::Conviction { conviction_score: 1.0 },
+        ).await.unwrap();
+
+        for _ in 0..5 {
+            clock.advance_epochs(1);
+        }
+        assert!(governance.current_conviction(&proposal_id, "fickle_voter").await.unwrap() > 100.0);
+
+        governance.withdraw_vote(&proposal_id, "fickle_voter").await.unwrap();
+        assert_eq!(governance.current_conviction(&proposal_id, "fickle_voter").await.unwrap(), 0.0);
+
+        let tally = governance.tally_proposal(&proposal_id).await.unwrap();
+        assert_eq!(tally.votes_for, 0);
+    }
+
+    #[tokio::test]
+    async fn test_conviction_accrues_exactly_at_the_epoch_boundary() {
+        let clock = Arc::new(MockEpochClock::new(86_400, 0));
+        clock.set_now(clock.epoch_start(10) - 1);
+        let governance = GovernanceSystem::new(GovernanceConfig::default()).await.unwrap()
+            .with_epoch_clock(clock.clone());
+        register_voters(&governance, &[("late_voter", 100)]).await;
+        let proposal_id = submit_test_proposal(&governance).await;
+
+        governance.cast_vote(
+            proposal_id.clone(),
+            "late_voter".to_string(),
+            VoteType::For,
+            100,
+            VotingMechanism::Conviction { conviction_score: 1.0 },
+        ).await.unwrap();
+        assert_eq!(governance.current_epoch(), 9);
+        assert_eq!(governance.current_conviction(&proposal_id, "late_voter").await.unwrap(), 100.0);
+
+        clock.advance(1);
+        assert_eq!(governance.current_epoch(), 10);
+        assert!(governance.current_conviction(&proposal_id, "late_voter").await.unwrap() > 100.0);
+    }
+
+    #[tokio::test]
+    async fn test_advance_epoch_moves_ahead_of_the_clock() {
+        let clock = Arc::new(MockEpochClock::new(86_400, 1_700_000_000));
+        let governance = GovernanceSystem::new(GovernanceConfig::default()).await.unwrap()
+            .with_epoch_clock(clock.clone());
+        register_voters(&governance, &[("steady_voter", 100)]).await;
+        let proposal_id = submit_test_proposal(&governance).await;
+        let start = governance.current_epoch();
+
+        governance.cast_vote(
+            proposal_id.clone(),
+            "steady_voter".to_string(),
+            VoteType::For,
+            100,
+            VotingMechanism::Conviction { conviction_score: 1.0 },
+        ).await.unwrap();
+
+        assert_eq!(governance.advance_epoch().await, start + 1);
+        assert!(governance.current_conviction(&proposal_id, "steady_voter").await.unwrap() > 100.0);
+
+        // The clock keeps counting on top of the forced epoch
+        clock.advance_epochs(1);
+        assert_eq!(governance.current_epoch(), start + 2);
+    }
+
+    /// Move a proposal's voting deadline into the past so it can be finalized
+    async fn close_voting(governance: &GovernanceSystem, proposal_id: &str) {
+        governance.proposals.write().await
+            .get_mut(proposal_id)
+            .unwrap()
+            .voting_ends_at = Utc::now() - chrono::Duration::seconds(1);
+    }
+
+    #[tokio::test]
+    async fn test_finalize_rejects_majority_without_quorum() {
+        let governance = GovernanceSystem::new(GovernanceConfig::default()).await.unwrap();
+        governance.set_eligible_supply(DAOType::LP, 100_000).await;
+        register_voters(&governance, &[("lp_1", 10_000)]).await;
+        let proposal_id = submit_test_proposal(&governance).await;
+
+        // 100% yes, but only 10% of eligible supply participates
+        governance.cast_vote(
+            proposal_id.clone(),
+            "lp_1".to_string(),
+            VoteType::For,
+            10_000,
+            VotingMechanism::Simple,
+        ).await.unwrap();
+
+        close_voting(&governance, &proposal_id).await;
+        let status = governance.finalize_proposal(&proposal_id).await.unwrap();
+        assert_eq!(status, ProposalStatus::Rejected);
+
+        let metrics = governance.get_metrics().await;
+        assert_eq!(metrics.rejected_proposals, 1);
+        assert_eq!(metrics.active_proposals, 0);
+    }
+
+    #[tokio::test]
+    async fn test_finalize_passes_with_quorum_and_dao_override() {
+        let mut config = GovernanceConfig::default();
+        config.dao_rules.insert(DAOType::LP, DaoVotingRules {
+            quorum_threshold: 0.2,
+            approval_threshold: 0.6,
+        });
+        let governance = GovernanceSystem::new(config).await.unwrap();
+        governance.set_eligible_supply(DAOType::LP, 100_000).await;
+        register_voters(&governance, &[("lp_1", 20_000), ("lp_2", 5_000)]).await;
+        let proposal_id = submit_test_proposal(&governance).await;
+
+        governance.cast_vote(
+            proposal_id.clone(),
+            "lp_1".to_string(),
+            VoteType::For,
+            20_000,
+            VotingMechanism::Simple,
+        ).await.unwrap();
+        governance.cast_vote(
+            proposal_id.clone(),
+            "lp_2".to_string(),
+            VoteType::Against,
+            5_000,
+            VotingMechanism::Simple,
+        ).await.unwrap();
+
+        // 25% participation clears the LP override even though the global quorum is 40%
+        close_voting(&governance, &proposal_id).await;
+        let status = governance.finalize_proposal(&proposal_id).await.unwrap();
+        assert_eq!(status, ProposalStatus::Passed);
+        assert_eq!(governance.get_metrics().await.passed_proposals, 1);
+
+        // A finalized proposal cannot be finalized again
+        assert!(governance.finalize_proposal(&proposal_id).await.is_err());
+    }
+
+    async fn finalized_test_proposal(governance: &GovernanceSystem, vote_type: VoteType) -> String {
+        governance.set_eligible_supply(DAOType::LP, 100_000).await;
+        register_voters(governance, &[("lp_1", 50_000)]).await;
+        let proposal_id = submit_test_proposal(governance).await;
+        governance.cast_vote(
+            proposal_id.clone(),
+            "lp_1".to_string(),
+            vote_type,
+            50_000,
+            VotingMechanism::Simple,
+        ).await.unwrap();
+        close_voting(governance, &proposal_id).await;
+        governance.finalize_proposal(&proposal_id).await.unwrap();
+        proposal_id
+    }
+
+    #[tokio::test]
+    async fn test_only_passed_proposals_execute() {
+        let governance = GovernanceSystem::new(GovernanceConfig::default()).await.unwrap();
+
+        let active = submit_test_proposal(&governance).await;
+        let err = governance.execute_proposal(active.clone()).await.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ProposalError>(),
+            Some(&ProposalError::NotExecutable { proposal_id: active, status: ProposalStatus::Active })
+        );
+
+        let rejected = finalized_test_proposal(&governance, VoteType::Against).await;
+        let err = governance.execute_proposal(rejected.clone()).await.unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ProposalError>(),
+            Some(ProposalError::NotExecutable { status: ProposalStatus::Rejected, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_passed_proposal_executes_exactly_once() {
+        let governance = GovernanceSystem::new(GovernanceConfig::default()).await.unwrap();
+        let proposal_id = finalized_test_proposal(&governance, VoteType::For).await;
+
+        governance.execute_proposal(proposal_id.clone()).await.unwrap();
+        assert_eq!(governance.proposals.read().await[&proposal_id].status, ProposalStatus::Executed);
+
+        let err = governance.execute_proposal(proposal_id.clone()).await.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ProposalError>(),
+            Some(&ProposalError::AlreadyExecuted { proposal_id })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_votes_rejected_once_proposal_is_finalized() {
+        let governance = GovernanceSystem::new(GovernanceConfig::default()).await.unwrap();
+        let proposal_id = finalized_test_proposal(&governance, VoteType::For).await;
+
+        let err = governance
+            .cast_vote(proposal_id.clone(), "lp_1".to_string(), VoteType::Against, 50_000, VotingMechanism::Simple)
+            .await.unwrap_err();
+        assert!(err.to_string().contains("is not active"));
+        assert!(governance.withdraw_vote(&proposal_id, "lp_1").await.is_err());
+
+        let tally = governance.tally_proposal(&proposal_id).await.unwrap();
+        assert_eq!(tally.votes_for, 50_000);
+        assert_eq!(tally.votes_against, 0);
+    }
+
+    #[tokio::test]
+    async fn test_votes_rejected_after_voting_window_closes() {
+        let governance = GovernanceSystem::new(GovernanceConfig::default()).await.unwrap();
+        register_voters(&governance, &[("lp_1", 1_000), ("lp_2", 1_000)]).await;
+        let proposal_id = submit_test_proposal(&governance).await;
+        governance.cast_vote(proposal_id.clone(), "lp_1".to_string(), VoteType::For, 1_000, VotingMechanism::Simple)
+            .await.unwrap();
+
+        close_voting(&governance, &proposal_id).await;
+
+        let err = governance
+            .cast_vote(proposal_id.clone(), "lp_2".to_string(), VoteType::Against, 1_000, VotingMechanism::Simple)
+            .await.unwrap_err();
+        assert!(err.to_string().contains("Voting on proposal"));
+        let err = governance.withdraw_vote(&proposal_id, "lp_1").await.unwrap_err();
+        assert!(err.to_string().contains("Voting on proposal"));
+
+        let tally = governance.tally_proposal(&proposal_id).await.unwrap();
+        assert_eq!(tally.votes_for, 1_000);
+        assert_eq!(tally.total_voters, 1);
+    }
+
+    #[tokio::test]
+    async fn test_double_vote_rejected_when_changes_disallowed() {
+        let config = GovernanceConfig {
+            allow_vote_change: false,
+            ..GovernanceConfig::default()
+        };
+        let governance = GovernanceSystem::new(config).await.unwrap();
+        register_voters(&governance, &[("lp_1", 1_000)]).await;
+        let proposal_id = submit_test_proposal(&governance).await;
+
+        governance.cast_vote(proposal_id.clone(), "lp_1".to_string(), VoteType::For, 1_000, VotingMechanism::Simple)
+            .await.unwrap();
+        let second = governance
+            .cast_vote(proposal_id.clone(), "lp_1".to_string(), VoteType::For, 1_000, VotingMechanism::Simple)
+            .await;
+        assert!(second.is_err());
+
+        let tally = governance.tally_proposal(&proposal_id).await.unwrap();
+        assert_eq!(tally.votes_for, 1_000);
+        assert_eq!(tally.total_voters, 1);
+    }
+
+    #[tokio::test]
+    async fn test_vote_change_replaces_prior_vote() {
+        let governance = GovernanceSystem::new(GovernanceConfig::default()).await.unwrap();
+        register_voters(&governance, &[("lp_1", 2_000)]).await;
+        let proposal_id = submit_test_proposal(&governance).await;
+
+        governance.cast_vote(proposal_id.clone(), "lp_1".to_string(), VoteType::For, 1_000, VotingMechanism::Simple)
+            .await.unwrap();
+        governance.cast_vote(proposal_id.clone(), "lp_1".to_string(), VoteType::Against, 2_000, VotingMechanism::Simple)
+            .await.unwrap();
+
+        let tally = governance.tally_proposal(&proposal_id).await.unwrap();
+        assert_eq!(tally.votes_for, 0);
+        assert_eq!(tally.votes_against, 2_000);
+        assert_eq!(tally.total_voters, 1);
+    }
+
+    #[tokio::test]
+    async fn test_voting_power_snapshot_enforced() {
+        let governance = GovernanceSystem::new(GovernanceConfig::default()).await.unwrap();
+        register_voters(&governance, &[("whale", 10_000)]).await;
+        let proposal_id = submit_test_proposal(&governance).await;
+
+        // Power acquired after proposal creation does not count
+        governance.set_voting_power("flash_borrower".to_string(), 1_000_000).await;
+        governance.set_voting_power("whale".to_string(), 1_000_000).await;
+
+        let flash = governance
+            .cast_vote(proposal_id.clone(), "flash_borrower".to_string(), VoteType::For, 1_000_000, VotingMechanism::Simple)
+            .await;
+        assert!(flash.is_err());
+
+        let inflated = governance
+            .cast_vote(proposal_id.clone(), "whale".to_string(), VoteType::For, 1_000_000, VotingMechanism::Simple)
+            .await;
+        assert!(inflated.is_err());
+
+        governance.cast_vote(proposal_id.clone(), "whale".to_string(), VoteType::For, 10_000, VotingMechanism::Simple)
+            .await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_three_hop_delegation_chain() {
+        let governance = GovernanceSystem::new(GovernanceConfig::default()).await.unwrap();
+        for voter in ["alice", "bob", "carol", "dave"] {
+            governance.set_voting_power(voter.to_string(), 1_000).await;
+        }
+        governance.delegate_to("alice".to_string(), "bob".to_string()).await.unwrap();
+        governance.delegate_to("bob".to_string(), "carol".to_string()).await.unwrap();
+        governance.delegate_to("carol".to_string(), "dave".to_string()).await.unwrap();
+        let proposal_id = submit_test_proposal(&governance).await;
+
+        governance.cast_vote(proposal_id.clone(), "dave".to_string(), VoteType::For, 1_000, VotingMechanism::Simple)
+            .await.unwrap();
+
+        let tally = governance.tally_proposal(&proposal_id).await.unwrap();
+        assert_eq!(tally.votes_for, 4_000);
+        assert_eq!(tally.delegated_power, 3_000);
+    }
+
+    #[tokio::test]
+    async fn test_delegation_cycle_rejected() {
+        let governance = GovernanceSystem::new(GovernanceConfig::default()).await.unwrap();
+        governance.delegate_to("alice".to_string(), "bob".to_string()).await.unwrap();
+        governance.delegate_to("bob".to_string(), "carol".to_string()).await.unwrap();
+
+        assert!(governance.delegate_to("carol".to_string(), "alice".to_string()).await.is_err());
+        assert!(governance.delegate_to("alice".to_string(), "alice".to_string()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_revocation_restores_self_voting_power() {
+        let governance = GovernanceSystem::new(GovernanceConfig::default()).await.unwrap();
+        for voter in ["alice", "bob"] {
+            governance.set_voting_power(voter.to_string(), 1_000).await;
+        }
+        governance.delegate_to("alice".to_string(), "bob".to_string()).await.unwrap();
+        let proposal_id = submit_test_proposal(&governance).await;
+
+        governance.cast_vote(proposal_id.clone(), "alice".to_string(), VoteType::Against, 1_000, VotingMechanism::Simple)
+            .await.unwrap();
+        governance.cast_vote(proposal_id.clone(), "bob".to_string(), VoteType::For, 1_000, VotingMechanism::Simple)
+            .await.unwrap();
+
+        let delegated = governance.tally_proposal(&proposal_id).await.unwrap();
+        assert_eq!(delegated.votes_for, 2_000);
+        assert_eq!(delegated.votes_against, 0);
+
+        // Revoking mid-vote leaves the open proposal's snapshot alone
+        governance.revoke_delegation("alice").await.unwrap();
+        let unchanged = governance.tally_proposal(&proposal_id).await.unwrap();
+        assert_eq!(unchanged.votes_for, 2_000);
+        assert_eq!(unchanged.votes_against, 0);
+
+        let next_proposal_id = submit_test_proposal(&governance).await;
+        governance.cast_vote(next_proposal_id.clone(), "alice".to_string(), VoteType::Against, 1_000, VotingMechanism::Simple)
+            .await.unwrap();
+        governance.cast_vote(next_proposal_id.clone(), "bob".to_string(), VoteType::For, 1_000, VotingMechanism::Simple)
+            .await.unwrap();
+
+        let restored = governance.tally_proposal(&next_proposal_id).await.unwrap();
+        assert_eq!(restored.votes_for, 1_000);
+        assert_eq!(restored.votes_against, 1_000);
+    }
+
+    #[tokio::test]
+    async fn test_delegated_power_falls_back_to_last_voting_delegator() {
+        let governance = GovernanceSystem::new(GovernanceConfig::default()).await.unwrap();
+        for voter in ["alice", "bob", "carol", "dave"] {
+            governance.set_voting_power(voter.to_string(), 1_000).await;
+        }
+        governance.delegate_to("alice".to_string(), "bob".to_string()).await.unwrap();
+        governance.delegate_to("bob".to_string(), "carol".to_string()).await.unwrap();
+        governance.delegate_to("carol".to_string(), "dave".to_string()).await.unwrap();
+        let proposal_id = submit_test_proposal(&governance).await;
+
+        // Dave, at the end of the chain, never votes, so Carol votes the chain's power
+        governance.cast_vote(proposal_id.clone(), "carol".to_string(), VoteType::Against, 1_000, VotingMechanism::Simple)
+            .await.unwrap();
+        let tally = governance.tally_proposal(&proposal_id).await.unwrap();
+        assert_eq!(tally.votes_against, 3_000);
+        assert_eq!(tally.delegated_power, 2_000);
+    }
+
+    #[tokio::test]
+    async fn test_chain_past_depth_limit_keeps_power_with_last_voter() {
+        let config = GovernanceConfig { max_delegation_depth: 1, ..GovernanceConfig::default() };
+        let governance = GovernanceSystem::new(config).await.unwrap();
+        for voter in ["alice", "bob", "carol"] {
+            governance.set_voting_power(voter.to_string(), 1_000).await;
+        }
+        governance.delegate_to("alice".to_string(), "bob".to_string()).await.unwrap();
+        governance.delegate_to("bob".to_string(), "carol".to_string()).await.unwrap();
+        let proposal_id = submit_test_proposal(&governance).await;
+
+        governance.cast_vote(proposal_id.clone(), "bob".to_string(), VoteType::For, 1_000, VotingMechanism::Simple)
+            .await.unwrap();
+        governance.cast_vote(proposal_id.clone(), "carol".to_string(), VoteType::Against, 1_000, VotingMechanism::Simple)
+            .await.unwrap();
+
+        // Alice's power stops at Bob, one hop away, while Bob's own power reaches Carol
+        let tally = governance.tally_proposal(&proposal_id).await.unwrap();
+        assert_eq!(tally.votes_for, 1_000);
+        assert_eq!(tally.votes_against, 2_000);
+    }
+
+    #[tokio::test]
+    async fn test_delegations_after_creation_do_not_move_votes() {
+        let governance = GovernanceSystem::new(GovernanceConfig::default()).await.unwrap();
+        for voter in ["alice", "bob"] {
+            governance.set_voting_power(voter.to_string(), 1_000).await;
+        }
+        let proposal_id = submit_test_proposal(&governance).await;
+
+        governance.cast_vote(proposal_id.clone(), "alice".to_string(), VoteType::Against, 1_000, VotingMechanism::Simple)
+            .await.unwrap();
+        governance.cast_vote(proposal_id.clone(), "bob".to_string(), VoteType::For, 1_000, VotingMechanism::Simple)
+            .await.unwrap();
+        governance.delegate_to("alice".to_string(), "bob".to_string()).await.unwrap();
+
+        let tally = governance.tally_proposal(&proposal_id).await.unwrap();
+        assert_eq!(tally.votes_for, 1_000);
+        assert_eq!(tally.votes_against, 1_000);
+    }
+
+    fn emergency_signing_keys() -> Vec<ed25519_dalek::SigningKey> {
+        (1u8..=3).map(|seed| ed25519_dalek::SigningKey::from_bytes(&[seed; 32])).collect()
+    }
+
+    fn emergency_config(keys: &[ed25519_dalek::SigningKey]) -> GovernanceConfig {
+        GovernanceConfig {
+            emergency_threshold: 0.66, // 2-of-3
+            emergency_signers: keys.iter().map(|k| k.verifying_key().to_bytes()).collect(),
+            ..GovernanceConfig::default()
+        }
+    }
+
+    async fn activation_message(governance: &GovernanceSystem, action: EmergencyAction, justification: &str) -> [u8; 32] {
+        let nonce = governance.emergency_nonce().await;
+        emergency_message(&governance.config.emergency_domain, nonce, &action, justification)
+    }
+
+    async fn clearance_message(governance: &GovernanceSystem, action: EmergencyAction, justification: &str) -> [u8; 32] {
+        let nonce = governance.emergency_nonce().await;
+        emergency_clearance_message(&governance.config.emergency_domain, nonce, &action, justification)
+    }
+
+    fn approve(key: &ed25519_dalek::SigningKey, message: &[u8; 32]) -> EmergencyAuthorization {
+        use ed25519_dalek::Signer;
+        EmergencyAuthorization {
+            approvals: vec![SignerApproval {
+                signer: key.verifying_key().to_bytes(),
+                signature: key.sign(message).to_bytes().to_vec(),
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_single_emergency_approval_does_not_execute() {
+        let keys = emergency_signing_keys();
+        let governance = GovernanceSystem::new(emergency_config(&keys)).await.unwrap();
+        let justification = "Oracle compromise".to_string();
+        let message = activation_message(&governance, EmergencyAction::PauseProtocol, &justification).await;
+
+        let first = governance
+            .activate_emergency(EmergencyAction::PauseProtocol, justification.clone(), approve(&keys[0], &message))
+            .await.unwrap();
+        assert_eq!(first, EmergencyActivation::Pending { approvals: 1, required: 2 });
+
+        // Re-submitting the same signer's approval does not count twice
+        let repeat = governance
+            .activate_emergency(EmergencyAction::PauseProtocol, justification, approve(&keys[0], &message))
+            .await.unwrap();
+        assert_eq!(repeat, EmergencyActivation::Pending { approvals: 1, required: 2 });
+        assert_eq!(governance.get_metrics().await.emergency_activations, 0);
+    }
+
+    #[tokio::test]
+    async fn test_emergency_executes_at_threshold() {
+        let keys = emergency_signing_keys();
+        let governance = GovernanceSystem::new(emergency_config(&keys)).await.unwrap();
+        let justification = "Bridge exploit".to_string();
+        let message = activation_message(&governance, EmergencyAction::FreezeAssets, &justification).await;
+
+        governance
+            .activate_emergency(EmergencyAction::FreezeAssets, justification.clone(), approve(&keys[0], &message))
+            .await.unwrap();
+        let second = governance
+            .activate_emergency(EmergencyAction::FreezeAssets, justification, approve(&keys[1], &message))
+            .await.unwrap();
+
+        assert_eq!(second, EmergencyActivation::Executed);
+        assert_eq!(governance.get_metrics().await.emergency_activations, 1);
+    }
+
+    #[tokio::test]
+    async fn test_governance_decisions_are_audited() {
+        let keys = emergency_signing_keys();
+        let audit_log = Arc::new(AuditLog::new());
+        let governance = GovernanceSystem::new(emergency_config(&keys)).await.unwrap()
+            .with_audit_log(audit_log.clone());
+
+        submit_test_proposal(&governance).await;
+        let justification = "Oracle compromise".to_string();
+        let message = activation_message(&governance, EmergencyAction::PauseProtocol, &justification).await;
+        // A pending approval is not a decision and is not recorded
+        governance
+            .activate_emergency(EmergencyAction::PauseProtocol, justification.clone(), approve(&keys[0], &message))
+            .await.unwrap();
+        governance
+            .activate_emergency(EmergencyAction::PauseProtocol, justification, approve(&keys[1], &message))
+            .await.unwrap();
+
+        let actions: Vec<_> = audit_log.entries().await.into_iter().map(|entry| entry.event.action).collect();
+        assert_eq!(actions, vec!["proposal_submitted", "emergency_activated"]);
+        assert!(audit_log.verify_chain().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_emergency_rejects_unknown_or_mismatched_signatures() {
+        let keys = emergency_signing_keys();
+        let governance = GovernanceSystem::new(emergency_config(&keys)).await.unwrap();
+        let justification = "Bridge exploit".to_string();
+        let message = activation_message(&governance, EmergencyAction::FreezeAssets, &justification).await;
+
+        let outsider = ed25519_dalek::SigningKey::from_bytes(&[9; 32]);
+        assert!(governance
+            .activate_emergency(EmergencyAction::FreezeAssets, justification.clone(), approve(&outsider, &message))
+            .await.is_err());
+
+        // A signature over a different action does not authorize this one
+        let other_message = activation_message(&governance, EmergencyAction::PauseProtocol, &justification).await;
+        assert!(governance
+            .activate_emergency(EmergencyAction::FreezeAssets, justification, approve(&keys[0], &other_message))
+            .await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pause_blocks_deposits_until_cleared() {
+        let keys = emergency_signing_keys();
+        let governance = GovernanceSystem::new(emergency_config(&keys)).await.unwrap();
+        let justification = "Oracle compromise".to_string();
+        let message = activation_message(&governance, EmergencyAction::PauseProtocol, &justification).await;
+
+        assert!(governance.ensure_deposits_allowed().await.is_ok());
+
+        for key in &keys[..2] {
+            governance
+                .activate_emergency(EmergencyAction::PauseProtocol, justification.clone(), approve(key, &message))
+                .await.unwrap();
+        }
+
+        assert!(governance.is_paused().await);
+        assert!(!governance.assets_frozen().await);
+        let err = governance.ensure_deposits_allowed().await.unwrap_err();
+        assert_eq!(err.downcast_ref::<EmergencyError>(), Some(&EmergencyError::ProtocolPaused));
+        assert!(governance.ensure_redemptions_allowed().await.is_err());
+
+        // Activation approvals cannot be replayed to clear the emergency
+        let clearance = clearance_message(&governance, EmergencyAction::PauseProtocol, &justification).await;
+        assert!(governance
+            .clear_emergency(EmergencyAction::PauseProtocol, justification.clone(), approve(&keys[0], &message))
+            .await.is_err());
+
+        for key in &keys[1..] {
+            governance
+                .clear_emergency(EmergencyAction::PauseProtocol, justification.clone(), approve(key, &clearance))
+                .await.unwrap();
+        }
+
+        assert!(!governance.is_paused().await);
+        assert!(governance.ensure_deposits_allowed().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_executed_emergency_approvals_cannot_be_replayed() {
+        let keys = emergency_signing_keys();
+        let governance = GovernanceSystem::new(emergency_config(&keys)).await.unwrap();
+        let justification = "Oracle compromise".to_string();
+        let pause = activation_message(&governance, EmergencyAction::PauseProtocol, &justification).await;
+        for key in &keys[..2] {
+            governance
+                .activate_emergency(EmergencyAction::PauseProtocol, justification.clone(), approve(key, &pause))
+                .await.unwrap();
+        }
+
+        let clearance = clearance_message(&governance, EmergencyAction::PauseProtocol, &justification).await;
+        for key in &keys[..2] {
+            governance
+                .clear_emergency(EmergencyAction::PauseProtocol, justification.clone(), approve(key, &clearance))
+                .await.unwrap();
+        }
+        assert_eq!(governance.emergency_nonce().await, 2);
+
+        // The original pause approvals were signed under nonce 0 and no longer verify
+        for key in &keys[..2] {
+            assert!(governance
+                .activate_emergency(EmergencyAction::PauseProtocol, justification.clone(), approve(key, &pause))
+                .await.is_err());
+        }
+        assert!(!governance.is_paused().await);
+    }
+
+    #[tokio::test]
+    async fn test_emergency_approvals_are_scoped_to_their_deployment() {
+        let keys = emergency_signing_keys();
+        let governance = GovernanceSystem::new(emergency_config(&keys)).await.unwrap();
+        let justification = "Bridge exploit".to_string();
+        let elsewhere = emergency_message("solana-devnet:other-program", 0, &EmergencyAction::FreezeAssets, &justification);
+
+        assert!(governance
+            .activate_emergency(EmergencyAction::FreezeAssets, justification, approve(&keys[0], &elsewhere))
+            .await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_quadratic_tally_of_maximal_power_terminates() {
+        let governance = GovernanceSystem::new(GovernanceConfig::default()).await.unwrap();
+        governance.set_voting_power("leviathan".to_string(), u64::MAX).await;
+        let proposal_id = submit_test_proposal(&governance).await;
+
+        governance.cast_vote(proposal_id.clone(), "leviathan".to_string(), VoteType::For, u64::MAX, VotingMechanism::Quadratic)
+            .await.unwrap();
+
+        let tally = governance.tally_proposal(&proposal_id).await.unwrap();
+        assert_eq!(tally.votes_for, 4_294_967_295); // floor(sqrt(u64::MAX))
     }
 }
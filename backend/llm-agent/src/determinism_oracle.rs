@@ -1,10 +1,13 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use tokio::sync::RwLock;
 use tracing::{info, warn, error};
 use sha2::{Sha256, Digest};
 use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use crate::RiskAssessment;
+use crate::snapshot_store::{InMemorySnapshotStore, SnapshotStore};
 
 /// LLM Determinism Oracle for RTF Infrastructure
 /// PRD Section 8: "LLM Agent Integrity"
@@ -14,7 +17,7 @@ use chrono::{DateTime, Utc};
 /// PRD: "Governance Simulation Mode: impact of proposal over epoch horizon"
 
 pub struct LlmDeterminismOracle {
-    output_snapshots: RwLock<HashMap<String, LlmOutputSnapshot>>,
+    snapshot_store: Arc<dyn SnapshotStore>,
     deviation_detector: DeviationDetector,
     coherence_analyzer: CoherenceAnalyzer,
     simulation_engine: GovernanceSimulationEngine,
@@ -38,6 +41,17 @@ pub struct LlmOutputSnapshot {
     pub prompt_template_hash: String,
 }
 
+/// Outcome of replaying the stored snapshots of a governance event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotReplay {
+    pub governance_event_id: String,
+    pub baseline_snapshot_id: String,
+    pub replayed_snapshot_id: String,
+    pub consensus_reproduced: bool,
+    pub deviation_score: f64,
+    pub deviation_alert: Option<DeviationAlert>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum GovernanceEventType {
     ProposalAnalysis,
@@ -108,14 +122,6 @@ pub struct ReasoningStep {
     pub dependencies: Vec<u32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RiskAssessment {
-    pub overall_risk_score: u8,
-    pub risk_categories: HashMap<String, u8>,
-    pub mitigation_strategies: Vec<String>,
-    pub risk_horizon: String,
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Recommendation {
     pub recommendation_id: String,
@@ -156,12 +162,17 @@ pub struct ExpectedImpact {
 }
 
 /// PRD: "Deviation Detection: diverging from prior outputs on similar governance scenarios"
+/// History is kept per scenario (`context_hash`), bounded to the most recent snapshots
 pub struct DeviationDetector {
-    historical_patterns: HashMap<String, Vec<LlmOutputSnapshot>>,
+    historical_patterns: RwLock<HashMap<String, VecDeque<LlmOutputSnapshot>>>,
     similarity_threshold: f64,
+    max_history_per_scenario: usize,
     deviation_metrics: DeviationMetrics,
 }
 
+/// Default number of snapshots retained per governance scenario
+pub const DEFAULT_MAX_HISTORY_PER_SCENARIO: usize = 32;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeviationMetrics {
     pub semantic_similarity: f64,
@@ -283,11 +294,69 @@ pub struct SimulationResult {
 pub struct EpochProjection {
     pub epoch: u32,
     pub projected_nav: f64,
+    pub projected_aum: f64,
+    pub projected_revenue: f64,
     pub projected_liquidity: f64,
     pub projected_risk_metrics: HashMap<String, f64>,
     pub probability_distribution: Vec<(f64, f64)>, // (value, probability)
 }
 
+/// Parameter changes a proposal applies to the fund, as used by the simulation model
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProposalDeltas {
+    /// Change to the annual management fee in basis points
+    pub fee_change_bps: f64,
+    /// Fraction of AUM moved into (positive) or out of (negative) risk assets
+    pub allocation_shift: f64,
+}
+
+impl ProposalDeltas {
+    /// Extract fee and allocation changes from proposal text
+    /// "from X% to Y%" yields Y - X; otherwise the first percent/bps figure is signed by
+    /// decrease/de-risk wording
+    pub fn from_proposal_text(proposal_text: &str) -> Self {
+        let text = proposal_text.to_lowercase();
+        let figures = extract_bps_figures(&text);
+
+        let magnitude = if text.contains(" from ") && text.contains(" to ") && figures.len() >= 2 {
+            figures[1] - figures[0]
+        } else {
+            figures.first().copied().unwrap_or(0.0)
+        };
+        let signed = |negative: bool| if negative { -magnitude.abs() } else { magnitude };
+
+        let mut deltas = ProposalDeltas::default();
+        if text.contains("fee") {
+            deltas.fee_change_bps = signed(["decrease", "reduce", "lower", "cut"].iter().any(|w| text.contains(w)));
+        } else if ["allocation", "allocate", "rebalance", "shift"].iter().any(|w| text.contains(w)) {
+            let de_risk = ["de-risk", "reduce risk", "treasur", "safer"].iter().any(|w| text.contains(w));
+            deltas.allocation_shift = signed(de_risk) / 10_000.0;
+        }
+        deltas
+    }
+}
+
+/// Percent and basis-point figures in order of appearance, in basis points
+fn extract_bps_figures(text: &str) -> Vec<f64> {
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    let mut figures = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        let token = token.trim_matches(|c: char| c == ',' || c == '.' || c == ';' || c == ')' || c == '(');
+        if let Some(value) = token.strip_suffix('%').and_then(|v| v.parse::<f64>().ok()) {
+            figures.push(value * 100.0);
+        } else if let Some(value) = token.strip_suffix("bps").and_then(|v| v.parse::<f64>().ok()) {
+            figures.push(value);
+        } else if let Ok(value) = token.parse::<f64>() {
+            match tokens.get(i + 1).map(|next| next.trim_matches(|c: char| !c.is_alphanumeric())) {
+                Some("bps") | Some("bp") => figures.push(value),
+                Some("percent") => figures.push(value * 100.0),
+                _ => {}
+            }
+        }
+    }
+    figures
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RiskScenario {
     pub scenario_id: String,
@@ -372,7 +441,7 @@ impl LlmDeterminismOracle {
         info!("🧠 Initializing LLM Determinism Oracle");
         
         Ok(Self {
-            output_snapshots: RwLock::new(HashMap::new()),
+            snapshot_store: Arc::new(InMemorySnapshotStore::new()),
             deviation_detector: DeviationDetector::new(),
             coherence_analyzer: CoherenceAnalyzer::new(),
             simulation_engine: GovernanceSimulationEngine::new(),
@@ -382,6 +451,18 @@ impl LlmDeterminismOracle {
         })
     }
 
+    /// Persist snapshots to the given store instead of the in-memory default
+    pub fn with_snapshot_store(mut self, store: Arc<dyn SnapshotStore>) -> Self {
+        self.snapshot_store = store;
+        self
+    }
+
+    /// Number of epochs governance simulations project forward
+    pub fn with_simulation_horizon(mut self, epochs: u32) -> Self {
+        self.simulation_engine.epoch_horizon = epochs;
+        self
+    }
+
     /// PRD: Create snapshot of assistant outputs for governance event
     pub async fn create_output_snapshot(
         &self,
@@ -413,11 +494,8 @@ impl LlmDeterminismOracle {
             prompt_template_hash: self.calculate_prompt_hash().await?,
         };
         
-        // Store snapshot
-        {
-            let mut snapshots = self.output_snapshots.write().await;
-            snapshots.insert(snapshot.snapshot_id.clone(), snapshot.clone());
-        }
+        // Store snapshot for audit and deterministic replay
+        self.snapshot_store.store_snapshot(snapshot.clone()).await?;
         
         info!("✅ LLM output snapshot created with confidence: {:.2}%", confidence_score * 100.0);
         Ok(snapshot)
@@ -430,22 +508,76 @@ impl LlmDeterminismOracle {
     ) -> Result<Option<DeviationAlert>> {
         info!("🔍 Detecting deviations for governance event: {}", current_output.governance_event_id);
         
+        let alert = self.check_consistency(current_output).await?;
+        self.deviation_detector.record(current_output).await;
+        Ok(alert)
+    }
+
+    /// Compare a snapshot against prior outputs on similar scenarios without recording it
+    pub async fn check_consistency(
+        &self,
+        current_output: &LlmOutputSnapshot,
+    ) -> Result<Option<DeviationAlert>> {
         let deviation_metrics = self.deviation_detector.analyze_deviation(current_output).await?;
-        
+        let expected_pattern = self.get_expected_pattern(current_output).await?;
+        self.deviation_alert(current_output, &deviation_metrics, expected_pattern).await
+    }
+
+    /// Replay the stored snapshots of a governance event: recompute the consensus of the
+    /// baseline (first) and latest snapshot from their raw model outputs, then measure the
+    /// deviation of the latest output from the baseline
+    pub async fn replay_snapshot(&self, governance_event_id: &str) -> Result<SnapshotReplay> {
+        info!("⏪ Replaying snapshots for governance event: {}", governance_event_id);
+
+        let snapshots = self.snapshot_store.snapshots_for_event(governance_event_id).await?;
+        let (baseline, latest) = match (snapshots.first(), snapshots.last()) {
+            (Some(baseline), Some(latest)) => (baseline, latest),
+            _ => return Err(anyhow::anyhow!("No snapshots stored for governance event {}", governance_event_id)),
+        };
+
+        let baseline_consensus = self.calculate_consensus(&baseline.llm_outputs).await?;
+        let replayed_consensus = self.calculate_consensus(&latest.llm_outputs).await?;
+        let consensus_reproduced = baseline_consensus == baseline.consensus_output
+            && replayed_consensus == latest.consensus_output;
+        if !consensus_reproduced {
+            warn!("🚨 Stored consensus for {} does not match its recorded model outputs", governance_event_id);
+        }
+
+        let mut replayed = latest.clone();
+        replayed.consensus_output = replayed_consensus;
+        let deviation_metrics = deviation_against(&replayed, std::slice::from_ref(baseline));
+        let deviation_alert = self.deviation_alert(&replayed, &deviation_metrics, baseline_consensus).await?;
+
+        Ok(SnapshotReplay {
+            governance_event_id: governance_event_id.to_string(),
+            baseline_snapshot_id: baseline.snapshot_id.clone(),
+            replayed_snapshot_id: latest.snapshot_id.clone(),
+            consensus_reproduced,
+            deviation_score: deviation_metrics.overall_deviation_score,
+            deviation_alert,
+        })
+    }
+
+    async fn deviation_alert(
+        &self,
+        current_output: &LlmOutputSnapshot,
+        deviation_metrics: &DeviationMetrics,
+        expected_pattern: String,
+    ) -> Result<Option<DeviationAlert>> {
         if deviation_metrics.overall_deviation_score > self.max_deviation_tolerance {
             let alert = DeviationAlert {
                 alert_id: format!("deviation_alert_{}", chrono::Utc::now().timestamp()),
-                deviation_type: self.classify_deviation_type(&deviation_metrics),
+                deviation_type: self.classify_deviation_type(deviation_metrics),
                 severity: self.calculate_alert_severity(deviation_metrics.overall_deviation_score),
                 current_output: current_output.consensus_output.clone(),
-                expected_pattern: self.get_expected_pattern(current_output).await?,
+                expected_pattern,
                 deviation_score: deviation_metrics.overall_deviation_score,
-                potential_causes: self.identify_potential_causes(&deviation_metrics).await?,
-                recommended_actions: self.generate_recommended_actions(&deviation_metrics).await?,
+                potential_causes: self.identify_potential_causes(deviation_metrics).await?,
+                recommended_actions: self.generate_recommended_actions(deviation_metrics).await?,
                 timestamp: chrono::Utc::now().timestamp(),
             };
             
-            warn!("🚨 Deviation detected: {} (score: {:.2})", alert.deviation_type, alert.deviation_score);
+            warn!("🚨 Deviation detected: {:?} (score: {:.2})", alert.deviation_type, alert.deviation_score);
             Ok(Some(alert))
         } else {
             info!("✅ No significant deviation detected (score: {:.2})", deviation_metrics.overall_deviation_score);
@@ -453,6 +585,13 @@ impl LlmDeterminismOracle {
         }
     }
 
+    /// Most recent snapshot recorded for a governance event
+    pub async fn latest_snapshot_for_event(&self, governance_event_id: &str) -> Option<LlmOutputSnapshot> {
+        self.snapshot_store.snapshots_for_event(governance_event_id).await
+            .ok()
+            .and_then(|snapshots| snapshots.last().cloned())
+    }
+
     /// PRD: Run governance simulation over epoch horizon
     pub async fn simulate_governance_impact(
         &self,
@@ -532,8 +671,11 @@ impl LlmDeterminismOracle {
     }
 
     async fn get_expected_pattern(&self, current_output: &LlmOutputSnapshot) -> Result<String> {
-        // Find similar historical patterns
-        Ok("Expected pattern based on historical analysis".to_string())
+        // Most recent prior output on a similar scenario
+        Ok(self.deviation_detector.similar_history(current_output).await
+            .last()
+            .map(|snapshot| snapshot.consensus_output.clone())
+            .unwrap_or_else(|| "No prior outputs for this scenario".to_string()))
     }
 
     async fn identify_potential_causes(&self, metrics: &DeviationMetrics) -> Result<Vec<String>> {
@@ -564,8 +706,9 @@ impl LlmDeterminismOracle {
 impl DeviationDetector {
     fn new() -> Self {
         Self {
-            historical_patterns: HashMap::new(),
+            historical_patterns: RwLock::new(HashMap::new()),
             similarity_threshold: 0.8,
+            max_history_per_scenario: DEFAULT_MAX_HISTORY_PER_SCENARIO,
             deviation_metrics: DeviationMetrics {
                 semantic_similarity: 0.0,
                 recommendation_consistency: 0.0,
@@ -576,16 +719,125 @@ impl DeviationDetector {
         }
     }
 
+    async fn record(&self, snapshot: &LlmOutputSnapshot) {
+        let mut history = self.historical_patterns.write().await;
+        let scenario = history.entry(snapshot.input_context.context_hash.clone()).or_default();
+        scenario.push_back(snapshot.clone());
+        while scenario.len() > self.max_history_per_scenario {
+            scenario.pop_front();
+        }
+    }
+
+    /// Prior snapshots on the same scenario, or on scenarios whose proposal text is
+    /// at least `similarity_threshold` similar, oldest first
+    async fn similar_history(&self, current: &LlmOutputSnapshot) -> Vec<LlmOutputSnapshot> {
+        let history = self.historical_patterns.read().await;
+        let mut similar: Vec<LlmOutputSnapshot> = history.iter()
+            .filter(|(context_hash, scenario)| {
+                **context_hash == current.input_context.context_hash
+                    || scenario.back().is_some_and(|prior| cosine_similarity(
+                        &prior.input_context.proposal_text,
+                        &current.input_context.proposal_text,
+                    ) >= self.similarity_threshold)
+            })
+            .flat_map(|(_, scenario)| scenario.iter())
+            .filter(|prior| prior.snapshot_id != current.snapshot_id)
+            .cloned()
+            .collect();
+        similar.sort_by_key(|snapshot| snapshot.timestamp);
+        similar
+    }
+
+    /// Deviation of the current output from prior outputs on similar scenarios
     async fn analyze_deviation(&self, current_output: &LlmOutputSnapshot) -> Result<DeviationMetrics> {
-        // Implement deviation analysis logic
-        Ok(DeviationMetrics {
-            semantic_similarity: 0.85,
-            recommendation_consistency: 0.90,
-            risk_assessment_variance: 0.15,
-            reasoning_coherence: 0.88,
-            overall_deviation_score: 0.12,
-        })
+        let history = self.similar_history(current_output).await;
+        Ok(deviation_against(current_output, &history))
+    }
+}
+
+/// Deviation of an output from a set of prior outputs (no deviation when there are none)
+/// overall = 0.6·(1 - semantic similarity) + 0.2·(1 - recommendation consistency) + 0.2·risk variance
+fn deviation_against(current_output: &LlmOutputSnapshot, history: &[LlmOutputSnapshot]) -> DeviationMetrics {
+    if history.is_empty() {
+        return DeviationMetrics {
+            semantic_similarity: 1.0,
+            recommendation_consistency: 1.0,
+            risk_assessment_variance: 0.0,
+            reasoning_coherence: current_output.coherence_score,
+            overall_deviation_score: 0.0,
+        };
+    }
+
+    let n = history.len() as f64;
+    let semantic_similarity = history.iter()
+        .map(|prior| cosine_similarity(&prior.consensus_output, &current_output.consensus_output))
+        .sum::<f64>() / n;
+
+    let current_actions = recommended_actions(current_output);
+    let recommendation_consistency = history.iter()
+        .map(|prior| jaccard(&recommended_actions(prior), &current_actions))
+        .sum::<f64>() / n;
+
+    let current_risk = mean_risk_score(current_output);
+    let risk_assessment_variance = history.iter()
+        .map(|prior| (mean_risk_score(prior) - current_risk).abs() / 100.0)
+        .sum::<f64>() / n;
+
+    let overall_deviation_score = 0.6 * (1.0 - semantic_similarity)
+        + 0.2 * (1.0 - recommendation_consistency)
+        + 0.2 * risk_assessment_variance;
+
+    DeviationMetrics {
+        semantic_similarity,
+        recommendation_consistency,
+        risk_assessment_variance,
+        reasoning_coherence: current_output.coherence_score,
+        overall_deviation_score,
+    }
+}
+
+fn term_frequencies(text: &str) -> HashMap<String, f64> {
+    let mut frequencies = HashMap::new();
+    for token in text.to_lowercase().split(|c: char| !c.is_alphanumeric()).filter(|t| !t.is_empty()) {
+        *frequencies.entry(token.to_string()).or_insert(0.0) += 1.0;
+    }
+    frequencies
+}
+
+/// Cosine similarity of the term-frequency vectors of two texts (1.0 when both are empty)
+pub fn cosine_similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (term_frequencies(a), term_frequencies(b));
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+
+    let dot: f64 = a.iter().filter_map(|(term, x)| b.get(term).map(|y| x * y)).sum();
+    let norm = |v: &HashMap<String, f64>| v.values().map(|x| x * x).sum::<f64>().sqrt();
+    let denominator = norm(&a) * norm(&b);
+    if denominator == 0.0 { 0.0 } else { dot / denominator }
+}
+
+fn recommended_actions(snapshot: &LlmOutputSnapshot) -> HashSet<String> {
+    snapshot.llm_outputs.iter()
+        .flat_map(|output| output.recommendations.iter())
+        .map(|recommendation| format!("{:?}", recommendation.action_type))
+        .collect()
+}
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    a.intersection(b).count() as f64 / a.union(b).count() as f64
+}
+
+fn mean_risk_score(snapshot: &LlmOutputSnapshot) -> f64 {
+    if snapshot.llm_outputs.is_empty() {
+        return 0.0;
     }
+    snapshot.llm_outputs.iter()
+        .map(|output| output.risk_assessment.overall_risk_score as f64)
+        .sum::<f64>() / snapshot.llm_outputs.len() as f64
 }
 
 impl CoherenceAnalyzer {
@@ -622,6 +874,11 @@ impl CoherenceAnalyzer {
     }
 }
 
+const EPOCHS_PER_YEAR: f64 = 52.0;
+
+/// Fraction of shares redeemed per epoch for each basis point of fee increase
+const FEE_OUTFLOW_ELASTICITY: f64 = 0.000_01;
+
 impl GovernanceSimulationEngine {
     fn new() -> Self {
         Self {
@@ -634,29 +891,99 @@ impl GovernanceSimulationEngine {
     async fn run_simulation(
         &self,
         proposal_id: String,
-        _proposal_text: String,
-        _current_fund_state: FundState,
+        proposal_text: String,
+        current_fund_state: FundState,
     ) -> Result<SimulationResult> {
-        // Implement simulation logic
+        let deltas = ProposalDeltas::from_proposal_text(&proposal_text);
+        let epoch_projections = self.project_epochs(&current_fund_state, &deltas);
+
+        let final_nav = epoch_projections.last()
+            .map(|projection| projection.projected_nav)
+            .unwrap_or(current_fund_state.nav_per_share);
+        let final_sigma = epoch_projections.last()
+            .and_then(|projection| projection.projected_risk_metrics.get("volatility"))
+            .map(|volatility| volatility * (self.epoch_horizon as f64).sqrt())
+            .unwrap_or(0.0);
+
         Ok(SimulationResult {
             simulation_id: format!("sim_{}_{}", proposal_id, chrono::Utc::now().timestamp()),
             proposal_id,
-            epoch_projections: Vec::new(),
+            epoch_projections,
             risk_scenarios: Vec::new(),
             confidence_bounds: ConfidenceBounds {
-                lower_bound_95: 0.95,
-                upper_bound_95: 1.05,
-                median_projection: 1.0,
-                standard_deviation: 0.02,
+                lower_bound_95: final_nav * (1.0 - 1.96 * final_sigma),
+                upper_bound_95: final_nav * (1.0 + 1.96 * final_sigma),
+                median_projection: final_nav,
+                standard_deviation: final_nav * final_sigma,
             },
-            key_assumptions: vec!["Market conditions remain stable".to_string()],
+            key_assumptions: vec![
+                "Market conditions remain stable".to_string(),
+                format!("Fee outflow elasticity of {} per bp per epoch", FEE_OUTFLOW_ELASTICITY),
+            ],
             sensitivity_analysis: SensitivityAnalysis {
-                parameter_sensitivities: HashMap::new(),
+                parameter_sensitivities: HashMap::from([
+                    ("fee_change_bps".to_string(), deltas.fee_change_bps),
+                    ("allocation_shift".to_string(), deltas.allocation_shift),
+                ]),
                 correlation_matrix: HashMap::new(),
                 critical_thresholds: HashMap::new(),
             },
         })
     }
+
+    /// Deterministic roll-forward of the fund over `epoch_horizon` epochs with the proposal applied
+    /// - return per epoch scales with risk allocation, volatility proportionally to it
+    /// - fees accrue weekly from NAV; fee increases drive proportional outflows
+    /// - VaR is the one-epoch 95% parametric VaR on projected AUM
+    fn project_epochs(&self, fund_state: &FundState, deltas: &ProposalDeltas) -> Vec<EpochProjection> {
+        let metric = |map: &HashMap<String, f64>, key: &str, default: f64| map.get(key).copied().unwrap_or(default);
+
+        let base_fee_bps = metric(&fund_state.performance_metrics, "management_fee_bps", 100.0);
+        let base_return = metric(&fund_state.performance_metrics, "expected_return", 0.001);
+        let base_volatility = metric(&fund_state.performance_metrics, "volatility", 0.01);
+        let base_allocation = metric(&fund_state.exposure_metrics, "risky_allocation", 0.5);
+
+        let fee_rate = ((base_fee_bps + deltas.fee_change_bps).max(0.0) / 10_000.0) / EPOCHS_PER_YEAR;
+        let risky_allocation = (base_allocation + deltas.allocation_shift).clamp(0.0, 1.0);
+        let risk_scale = if base_allocation > 0.0 { risky_allocation / base_allocation } else { 1.0 };
+        let epoch_return = base_return * risk_scale;
+        let volatility = base_volatility * risk_scale;
+        let outflow_rate = deltas.fee_change_bps.max(0.0) * FEE_OUTFLOW_ELASTICITY;
+        let liquidity = (fund_state.liquidity_ratio - deltas.allocation_shift).clamp(0.0, 1.0);
+
+        let mut nav = fund_state.nav_per_share;
+        let mut shares = if fund_state.nav_per_share > 0.0 {
+            fund_state.total_assets as f64 / fund_state.nav_per_share
+        } else {
+            0.0
+        };
+
+        (1..=self.epoch_horizon).map(|epoch| {
+            let gross_aum = shares * nav * (1.0 + epoch_return);
+            let revenue = gross_aum * fee_rate;
+            nav *= (1.0 + epoch_return) * (1.0 - fee_rate);
+            shares *= 1.0 - outflow_rate;
+            let aum = shares * nav;
+
+            let sigma = volatility * (epoch as f64).sqrt();
+            EpochProjection {
+                epoch,
+                projected_nav: nav,
+                projected_aum: aum,
+                projected_revenue: revenue,
+                projected_liquidity: liquidity,
+                projected_risk_metrics: HashMap::from([
+                    ("volatility".to_string(), volatility),
+                    ("var_95".to_string(), 1.645 * volatility * aum),
+                ]),
+                probability_distribution: vec![
+                    (nav * (1.0 - 1.645 * sigma), 0.05),
+                    (nav, 0.5),
+                    (nav * (1.0 + 1.645 * sigma), 0.95),
+                ],
+            }
+        }).collect()
+    }
 }
 
 impl SecurityMonitor {
@@ -673,3 +1000,170 @@ impl SecurityMonitor {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::test_input_context;
+
+    fn output(response_text: &str, risk_score: u8) -> LlmOutput {
+        LlmOutput {
+            output_id: "output".to_string(),
+            model_name: "governance_assistant".to_string(),
+            model_version: "v1.0".to_string(),
+            response_text: response_text.to_string(),
+            confidence_score: 0.9,
+            reasoning_chain: vec![],
+            risk_assessment: RiskAssessment::from_score(risk_score, vec![], "30_days"),
+            recommendations: vec![],
+            execution_timestamp: 0,
+        }
+    }
+
+    async fn snapshot(oracle: &LlmDeterminismOracle, event_id: &str, response_text: &str, risk_score: u8) -> LlmOutputSnapshot {
+        oracle.create_output_snapshot(
+            event_id.to_string(),
+            GovernanceEventType::ProposalAnalysis,
+            test_input_context("Increase the management fee from 1% to 1.5%"),
+            vec![output(response_text, risk_score)],
+        ).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_consistent_reanalysis_raises_no_alert() {
+        let oracle = LlmDeterminismOracle::new(0.85, 0.15).await.unwrap();
+        let approve = "Approve: the fee increase is modest and funds operations";
+
+        let first = snapshot(&oracle, "prop_1", approve, 20).await;
+        assert!(oracle.detect_deviations(&first).await.unwrap().is_none());
+
+        let second = snapshot(&oracle, "prop_1_rerun", approve, 20).await;
+        assert!(oracle.detect_deviations(&second).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_contradictory_reanalysis_triggers_deviation() {
+        let oracle = LlmDeterminismOracle::new(0.85, 0.15).await.unwrap();
+
+        let first = snapshot(&oracle, "prop_1", "Approve: the fee increase is modest and funds operations", 20).await;
+        oracle.detect_deviations(&first).await.unwrap();
+
+        let contradictory = snapshot(&oracle, "prop_1_rerun", "Reject immediately, severe investor harm expected", 90).await;
+        let alert = oracle.detect_deviations(&contradictory).await.unwrap().unwrap();
+        assert!(alert.deviation_score > 0.15);
+        assert!(matches!(alert.deviation_type, DeviationType::SemanticDrift));
+        assert_eq!(alert.expected_pattern, "Approve: the fee increase is modest and funds operations");
+    }
+
+    #[tokio::test]
+    async fn test_history_is_bounded_per_scenario() {
+        let oracle = LlmDeterminismOracle::new(0.85, 0.15).await.unwrap();
+        for i in 0..DEFAULT_MAX_HISTORY_PER_SCENARIO + 5 {
+            let snap = snapshot(&oracle, &format!("prop_{}", i), "Approve", 20).await;
+            oracle.detect_deviations(&snap).await.unwrap();
+        }
+
+        let history = oracle.deviation_detector.historical_patterns.read().await;
+        assert_eq!(history["ctx"].len(), DEFAULT_MAX_HISTORY_PER_SCENARIO);
+    }
+
+    #[test]
+    fn test_cosine_similarity() {
+        assert!((cosine_similarity("fee increase", "Fee, increase!") - 1.0).abs() < 1e-12);
+        assert_eq!(cosine_similarity("approve", "reject"), 0.0);
+        assert_eq!(cosine_similarity("", ""), 1.0);
+    }
+
+    fn fund_state() -> FundState {
+        test_input_context("").fund_state
+    }
+
+    #[test]
+    fn test_proposal_deltas_from_text() {
+        let deltas = ProposalDeltas::from_proposal_text("Increase the management fee from 1% to 1.5%");
+        assert_eq!(deltas.fee_change_bps, 50.0);
+        let deltas = ProposalDeltas::from_proposal_text("Reduce fees by 25 bps");
+        assert_eq!(deltas.fee_change_bps, -25.0);
+        let deltas = ProposalDeltas::from_proposal_text("Shift 20% of the allocation into high-yield credit");
+        assert!((deltas.allocation_shift - 0.2).abs() < 1e-12);
+    }
+
+    #[tokio::test]
+    async fn test_fee_increase_raises_projected_revenue() {
+        let oracle = LlmDeterminismOracle::new(0.85, 0.15).await.unwrap().with_simulation_horizon(52);
+
+        let baseline = oracle.simulate_governance_impact("p0".to_string(), "Update the vault documentation".to_string(), fund_state()).await.unwrap();
+        let fee_increase = oracle.simulate_governance_impact("p1".to_string(), "Increase the management fee from 1% to 1.5%".to_string(), fund_state()).await.unwrap();
+
+        assert_eq!(fee_increase.epoch_projections.len(), 52);
+        let revenue = |result: &SimulationResult| result.epoch_projections.iter().map(|p| p.projected_revenue).sum::<f64>();
+        assert!(revenue(&fee_increase) > revenue(&baseline));
+        assert!(fee_increase.epoch_projections[51].projected_aum < baseline.epoch_projections[51].projected_aum);
+
+        // Deterministic: same inputs, same trajectory
+        let rerun = oracle.simulate_governance_impact("p1".to_string(), "Increase the management fee from 1% to 1.5%".to_string(), fund_state()).await.unwrap();
+        assert_eq!(revenue(&rerun), revenue(&fee_increase));
+    }
+
+    #[tokio::test]
+    async fn test_risk_raising_proposal_increases_var() {
+        let oracle = LlmDeterminismOracle::new(0.85, 0.15).await.unwrap().with_simulation_horizon(26);
+
+        let baseline = oracle.simulate_governance_impact("p0".to_string(), "Update the vault documentation".to_string(), fund_state()).await.unwrap();
+        let risk_on = oracle.simulate_governance_impact("p1".to_string(), "Shift 30% of the allocation into high-yield credit".to_string(), fund_state()).await.unwrap();
+
+        let var = |result: &SimulationResult, epoch: usize| result.epoch_projections[epoch].projected_risk_metrics["var_95"];
+        for epoch in [0, 12, 25] {
+            assert!(var(&risk_on, epoch) > var(&baseline, epoch));
+        }
+        assert!(risk_on.epoch_projections[25].projected_liquidity < baseline.epoch_projections[25].projected_liquidity);
+    }
+
+    #[tokio::test]
+    async fn test_replay_of_stored_snapshot_reproduces_baseline() {
+        let store = Arc::new(InMemorySnapshotStore::new());
+        let oracle = LlmDeterminismOracle::new(0.85, 0.15).await.unwrap().with_snapshot_store(store.clone());
+
+        let stored = snapshot(&oracle, "prop_1", "Approve: the fee increase is modest and funds operations", 20).await;
+        assert_eq!(store.baseline_snapshot("prop_1").await.unwrap().unwrap().snapshot_id, stored.snapshot_id);
+
+        let replay = oracle.replay_snapshot("prop_1").await.unwrap();
+        assert!(replay.consensus_reproduced);
+        assert_eq!(replay.baseline_snapshot_id, stored.snapshot_id);
+        assert_eq!(replay.deviation_score, 0.0);
+        assert!(replay.deviation_alert.is_none());
+
+        assert!(oracle.replay_snapshot("unknown").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_replay_detects_injected_change() {
+        let store = Arc::new(InMemorySnapshotStore::new());
+        let oracle = LlmDeterminismOracle::new(0.85, 0.15).await.unwrap().with_snapshot_store(store.clone());
+        let baseline = snapshot(&oracle, "prop_1", "Approve: the fee increase is modest and funds operations", 20).await;
+
+        // A later output for the same event that contradicts the baseline
+        let mut injected = baseline.clone();
+        injected.snapshot_id = "snapshot_prop_1_injected".to_string();
+        injected.llm_outputs = vec![output("Reject immediately, severe investor harm expected", 90)];
+        injected.consensus_output = "Reject immediately, severe investor harm expected".to_string();
+        store.store_snapshot(injected).await.unwrap();
+
+        let replay = oracle.replay_snapshot("prop_1").await.unwrap();
+        assert!(replay.consensus_reproduced);
+        assert_eq!(replay.replayed_snapshot_id, "snapshot_prop_1_injected");
+        let alert = replay.deviation_alert.unwrap();
+        assert!(alert.deviation_score > 0.15);
+        assert_eq!(alert.expected_pattern, baseline.consensus_output);
+
+        // A stored consensus that no longer matches its recorded model outputs
+        let mut tampered = baseline.clone();
+        tampered.snapshot_id = "snapshot_prop_1_tampered".to_string();
+        tampered.consensus_output = "Approve without review".to_string();
+        store.store_snapshot(tampered).await.unwrap();
+
+        let replay = oracle.replay_snapshot("prop_1").await.unwrap();
+        assert!(!replay.consensus_reproduced);
+        assert!(replay.deviation_alert.is_none());
+    }
+}
@@ -0,0 +1,50 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+
+use crate::determinism_oracle::LlmOutputSnapshot;
+
+/// Persistence for LLM output snapshots
+/// PRD: "LLM Determinism Oracle: snapshot of assistant outputs for each governance event"
+/// Snapshots are kept per governance event in the order they were stored, so the first
+/// one is the baseline for audit and deterministic replay.
+#[async_trait]
+pub trait SnapshotStore: Send + Sync {
+    /// Append a snapshot to its governance event's history
+    async fn store_snapshot(&self, snapshot: LlmOutputSnapshot) -> Result<()>;
+
+    /// All snapshots stored for a governance event, oldest first
+    async fn snapshots_for_event(&self, governance_event_id: &str) -> Result<Vec<LlmOutputSnapshot>>;
+
+    /// First snapshot stored for a governance event
+    async fn baseline_snapshot(&self, governance_event_id: &str) -> Result<Option<LlmOutputSnapshot>> {
+        Ok(self.snapshots_for_event(governance_event_id).await?.into_iter().next())
+    }
+}
+
+/// Snapshot store backed by process memory
+#[derive(Default)]
+pub struct InMemorySnapshotStore {
+    snapshots: RwLock<HashMap<String, Vec<LlmOutputSnapshot>>>,
+}
+
+impl InMemorySnapshotStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl SnapshotStore for InMemorySnapshotStore {
+    async fn store_snapshot(&self, snapshot: LlmOutputSnapshot) -> Result<()> {
+        let mut snapshots = self.snapshots.write().await;
+        snapshots.entry(snapshot.governance_event_id.clone()).or_default().push(snapshot);
+        Ok(())
+    }
+
+    async fn snapshots_for_event(&self, governance_event_id: &str) -> Result<Vec<LlmOutputSnapshot>> {
+        let snapshots = self.snapshots.read().await;
+        Ok(snapshots.get(governance_event_id).cloned().unwrap_or_default())
+    }
+}
@@ -0,0 +1,270 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use tokio::sync::{broadcast, RwLock};
+use tokio::task::JoinHandle;
+use tokio::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::metrics_service::AlertSeverity;
+use crate::MonitoringConfig;
+
+/// Per-operation latency percentiles over a rolling window
+/// PRD: "Performance targets: <700ms API response time"
+/// Latencies are checked every `alert_check_interval_seconds`; an operation whose p95
+/// exceeds `performance_threshold_ms` raises an `Alert` on the broadcast channel.
+pub struct LatencyMonitor {
+    samples: RwLock<HashMap<String, VecDeque<LatencySample>>>,
+    threshold_ms: u64,
+    check_interval: Duration,
+    window: Duration,
+    max_samples_per_operation: usize,
+    alert_sender: broadcast::Sender<Alert>,
+}
+
+pub const DEFAULT_LATENCY_WINDOW_SECONDS: u64 = 300;
+pub const DEFAULT_MAX_LATENCY_SAMPLES: usize = 10_000;
+const ALERT_CHANNEL_CAPACITY: usize = 256;
+
+#[derive(Debug, Clone, Copy)]
+struct LatencySample {
+    recorded_at: Instant,
+    latency_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LatencyPercentiles {
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    pub p99_ms: u64,
+    pub sample_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Alert {
+    pub alert_id: String,
+    pub operation: String,
+    pub severity: AlertSeverity,
+    pub message: String,
+    pub observed_p95_ms: u64,
+    pub threshold_ms: u64,
+    pub triggered_at: i64,
+}
+
+impl LatencyMonitor {
+    pub fn new(threshold_ms: u64, check_interval: Duration) -> Self {
+        let (alert_sender, _) = broadcast::channel(ALERT_CHANNEL_CAPACITY);
+        Self {
+            samples: RwLock::new(HashMap::new()),
+            threshold_ms,
+            check_interval,
+            window: Duration::from_secs(DEFAULT_LATENCY_WINDOW_SECONDS),
+            max_samples_per_operation: DEFAULT_MAX_LATENCY_SAMPLES,
+            alert_sender,
+        }
+    }
+
+    pub fn from_config(config: &MonitoringConfig) -> Self {
+        Self::new(
+            config.performance_threshold_ms,
+            Duration::from_secs(config.alert_check_interval_seconds.max(1)),
+        )
+    }
+
+    /// Length of the rolling window percentiles are computed over
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Receive every latency alert raised after this call
+    pub fn subscribe(&self) -> broadcast::Receiver<Alert> {
+        self.alert_sender.subscribe()
+    }
+
+    pub async fn record_latency(&self, operation: &str, latency_ms: u64) {
+        self.record_latency_at(operation, latency_ms, Instant::now()).await;
+    }
+
+    pub async fn record_latency_at(&self, operation: &str, latency_ms: u64, now: Instant) {
+        let mut samples = self.samples.write().await;
+        let window = samples.entry(operation.to_string()).or_default();
+        window.push_back(LatencySample { recorded_at: now, latency_ms });
+        while window.len() > self.max_samples_per_operation {
+            window.pop_front();
+        }
+    }
+
+    /// Percentiles of the samples still inside the rolling window
+    pub async fn percentiles(&self, operation: &str) -> Option<LatencyPercentiles> {
+        self.percentiles_at(operation, Instant::now()).await
+    }
+
+    pub async fn percentiles_at(&self, operation: &str, now: Instant) -> Option<LatencyPercentiles> {
+        let mut samples = self.samples.write().await;
+        let window = samples.get_mut(operation)?;
+        self.evict_expired(window, now);
+        compute_percentiles(window)
+    }
+
+    /// Evaluate every operation against the threshold and broadcast alerts for breaches
+    pub async fn check_thresholds(&self) -> Vec<Alert> {
+        self.check_thresholds_at(Instant::now()).await
+    }
+
+    pub async fn check_thresholds_at(&self, now: Instant) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        {
+            let mut samples = self.samples.write().await;
+            for (operation, window) in samples.iter_mut() {
+                self.evict_expired(window, now);
+                let Some(percentiles) = compute_percentiles(window) else { continue };
+                if percentiles.p95_ms > self.threshold_ms {
+                    alerts.push(self.latency_alert(operation, &percentiles));
+                }
+            }
+        }
+
+        for alert in &alerts {
+            warn!("🚨 {}", alert.message);
+            // No subscribers is not an error
+            let _ = self.alert_sender.send(alert.clone());
+        }
+        alerts
+    }
+
+    /// Check thresholds on the configured cadence until the task is aborted
+    pub fn start_alert_loop(self: Arc<Self>) -> JoinHandle<()> {
+        info!("⏱️ Starting latency alert loop every {:?}", self.check_interval);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.check_interval);
+            loop {
+                interval.tick().await;
+                self.check_thresholds().await;
+            }
+        })
+    }
+
+    fn evict_expired(&self, window: &mut VecDeque<LatencySample>, now: Instant) {
+        while window.front().is_some_and(|sample| now.saturating_duration_since(sample.recorded_at) > self.window) {
+            window.pop_front();
+        }
+    }
+
+    fn latency_alert(&self, operation: &str, percentiles: &LatencyPercentiles) -> Alert {
+        let severity = if percentiles.p95_ms > self.threshold_ms * 2 {
+            AlertSeverity::Critical
+        } else {
+            AlertSeverity::Warning
+        };
+        Alert {
+            alert_id: format!("latency_{}_{}", operation, chrono::Utc::now().timestamp_millis()),
+            operation: operation.to_string(),
+            severity,
+            message: format!(
+                "{} p95 latency {}ms exceeds {}ms threshold ({} samples)",
+                operation, percentiles.p95_ms, self.threshold_ms, percentiles.sample_count
+            ),
+            observed_p95_ms: percentiles.p95_ms,
+            threshold_ms: self.threshold_ms,
+            triggered_at: chrono::Utc::now().timestamp(),
+        }
+    }
+}
+
+/// Nearest-rank percentiles
+fn compute_percentiles(window: &VecDeque<LatencySample>) -> Option<LatencyPercentiles> {
+    if window.is_empty() {
+        return None;
+    }
+    let mut latencies: Vec<u64> = window.iter().map(|sample| sample.latency_ms).collect();
+    latencies.sort_unstable();
+
+    let rank = |percentile: f64| {
+        let index = (percentile / 100.0 * latencies.len() as f64).ceil() as usize;
+        latencies[index.clamp(1, latencies.len()) - 1]
+    };
+    Some(LatencyPercentiles {
+        p50_ms: rank(50.0),
+        p95_ms: rank(95.0),
+        p99_ms: rank(99.0),
+        sample_count: latencies.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor() -> LatencyMonitor {
+        LatencyMonitor::from_config(&MonitoringConfig::default())
+    }
+
+    #[tokio::test]
+    async fn test_percentiles_use_nearest_rank() {
+        let monitor = monitor();
+        for latency in 1..=100 {
+            monitor.record_latency("nav_compute", latency).await;
+        }
+
+        let percentiles = monitor.percentiles("nav_compute").await.unwrap();
+        assert_eq!(percentiles, LatencyPercentiles { p50_ms: 50, p95_ms: 95, p99_ms: 99, sample_count: 100 });
+        assert!(monitor.percentiles("unknown").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_alert_fires_only_when_p95_breaches() {
+        let monitor = monitor();
+        let mut alerts = monitor.subscribe();
+
+        // 94 fast requests and 6 slow ones: p95 is slow
+        for _ in 0..94 {
+            monitor.record_latency("deposit", 200).await;
+        }
+        for _ in 0..6 {
+            monitor.record_latency("deposit", 900).await;
+        }
+        // Isolated spikes below the 95th percentile do not alert
+        for _ in 0..99 {
+            monitor.record_latency("redeem", 300).await;
+        }
+        monitor.record_latency("redeem", 5_000).await;
+
+        let raised = monitor.check_thresholds().await;
+        assert_eq!(raised.len(), 1);
+
+        let alert = alerts.try_recv().unwrap();
+        assert_eq!(alert.operation, "deposit");
+        assert_eq!(alert.observed_p95_ms, 900);
+        assert_eq!(alert.threshold_ms, 700);
+        assert!(matches!(alert.severity, AlertSeverity::Warning));
+        assert!(alerts.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_latencies_at_threshold_do_not_alert() {
+        let monitor = monitor();
+        let mut alerts = monitor.subscribe();
+        for _ in 0..50 {
+            monitor.record_latency("deposit", 700).await;
+        }
+
+        assert!(monitor.check_thresholds().await.is_empty());
+        assert!(alerts.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_samples_outside_window_are_evicted() {
+        let monitor = monitor().with_window(Duration::from_secs(60));
+        let start = Instant::now();
+        for _ in 0..20 {
+            monitor.record_latency_at("deposit", 2_000, start).await;
+        }
+        assert_eq!(monitor.check_thresholds_at(start).await.len(), 1);
+
+        monitor.record_latency_at("deposit", 100, start + Duration::from_secs(90)).await;
+        assert!(monitor.check_thresholds_at(start + Duration::from_secs(90)).await.is_empty());
+        let percentiles = monitor.percentiles_at("deposit", start + Duration::from_secs(90)).await.unwrap();
+        assert_eq!(percentiles.sample_count, 1);
+    }
+}
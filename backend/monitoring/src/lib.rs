@@ -1,6 +1,10 @@
+pub mod latency_monitor;
 pub mod metrics_service;
+pub mod prometheus_metrics;
 
+pub use latency_monitor::*;
 pub use metrics_service::*;
+pub use prometheus_metrics::*;
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
@@ -34,7 +38,7 @@ impl Default for MonitoringConfig {
 pub async fn init_monitoring(config: MonitoringConfig) -> Result<MetricsService> {
     info!("🔧 Initializing RTF Monitoring Service");
     
-    let service = MetricsService::new().await?;
+    let service = MetricsService::new().await?.with_monitoring_config(&config)?;
     
     info!("✅ RTF Monitoring Service initialized successfully");
     Ok(service)
@@ -50,4 +54,59 @@ mod tests {
         let result = init_monitoring(config).await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_recorded_events_update_rendered_metrics() {
+        let service = init_monitoring(MonitoringConfig::default()).await.unwrap();
+        assert!(service.render().unwrap().contains("rtf_redemptions_processed_total 0"));
+
+        service.record_redemption_processed();
+        service.record_compliance_check(true);
+        service.record_cross_chain_sync("solana", true);
+        service.record_api_performance("/api/v1/vaults".to_string(), "GET".to_string(), 800, 200).await.unwrap();
+
+        let rendered = service.render().unwrap();
+        assert!(rendered.contains("rtf_redemptions_processed_total 1"));
+        assert!(rendered.contains("rtf_compliance_checks_total{outcome=\"passed\"} 1"));
+        assert!(rendered.contains("rtf_cross_chain_sync_total{chain=\"solana\",outcome=\"success\"} 1"));
+        assert!(rendered.contains("rtf_request_latency_sla_breaches_total 1"));
+    }
+
+    #[tokio::test]
+    async fn test_configured_threshold_is_exported_and_enforced() {
+        let config = MonitoringConfig { performance_threshold_ms: 500, ..MonitoringConfig::default() };
+        let service = init_monitoring(config).await.unwrap();
+        service.record_api_performance("/api/v1/vaults".to_string(), "GET".to_string(), 600, 200).await.unwrap();
+
+        let rendered = service.render().unwrap();
+        assert!(rendered.contains("rtf_request_latency_sla_threshold_seconds 0.5"));
+        assert!(rendered.contains("rtf_request_latency_sla_breaches_total 1"));
+    }
+
+    #[tokio::test]
+    async fn test_exposure_risk_alerts_are_counted() {
+        use exposure_detector::{RiskSeverity, SystemicRisk, SystemicRiskAlert, SystemicRiskType};
+
+        let service = init_monitoring(MonitoringConfig::default()).await.unwrap();
+        let (sender, receiver) = tokio::sync::broadcast::channel(8);
+        let watcher = service.watch_exposure_risks(receiver);
+
+        sender.send(SystemicRiskAlert {
+            risk_key: "HighConcentration:a,b".to_string(),
+            root_fund_id: "a".to_string(),
+            risk: SystemicRisk {
+                risk_type: SystemicRiskType::HighConcentration,
+                description: "High exposure concentration: 60.0% in fund b".to_string(),
+                severity: RiskSeverity::Critical,
+                affected_funds: vec!["a".to_string(), "b".to_string()],
+                mitigation_recommendations: Vec::new(),
+            },
+            raised_at: 0,
+        }).unwrap();
+        drop(sender);
+        watcher.await.unwrap();
+
+        let rendered = service.render().unwrap();
+        assert!(rendered.contains("rtf_critical_exposure_risks_total{risk_type=\"HighConcentration\"} 1"));
+    }
 }
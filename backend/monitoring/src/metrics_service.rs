@@ -4,6 +4,13 @@ use std::collections::HashMap;
 use tokio::sync::RwLock;
 use tokio::time::{Duration, sleep, Instant};
 use tracing::{info, warn, error};
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use exposure_detector::SystemicRiskAlert;
+use crate::latency_monitor::{Alert, LatencyMonitor, LatencyPercentiles};
+use crate::prometheus_metrics::PrometheusMetrics;
+use crate::MonitoringConfig;
 
 /// Comprehensive Monitoring and Metrics Service for RTF Infrastructure
 /// PRD: "Real-time monitoring and prevention systems"
@@ -13,6 +20,8 @@ pub struct MetricsService {
     metrics_store: RwLock<HashMap<String, MetricSeries>>,
     alert_rules: RwLock<HashMap<String, AlertRule>>,
     dashboards: RwLock<HashMap<String, Dashboard>>,
+    prometheus: Arc<PrometheusMetrics>,
+    latency_monitor: Arc<LatencyMonitor>,
     performance_targets: PerformanceTargets,
     retention_policy: RetentionPolicy,
     real_time_monitoring: bool,
@@ -205,17 +214,21 @@ impl MetricsService {
     pub async fn new() -> Result<Self> {
         info!("📊 Initializing Comprehensive Metrics Service");
         
+        let performance_targets = PerformanceTargets {
+            api_response_time_ms: 700,    // PRD requirement
+            nav_computation_time_ms: 5000, // 5 seconds
+            cross_chain_sync_time_ms: 30000, // 30 seconds
+            uptime_percentage: 99.9,
+            error_rate_percentage: 0.1,
+        };
+
         let service = Self {
             metrics_store: RwLock::new(HashMap::new()),
             alert_rules: RwLock::new(HashMap::new()),
             dashboards: RwLock::new(HashMap::new()),
-            performance_targets: PerformanceTargets {
-                api_response_time_ms: 700,    // PRD requirement
-                nav_computation_time_ms: 5000, // 5 seconds
-                cross_chain_sync_time_ms: 30000, // 30 seconds
-                uptime_percentage: 99.9,
-                error_rate_percentage: 0.1,
-            },
+            prometheus: Arc::new(PrometheusMetrics::new(performance_targets.api_response_time_ms)?),
+            latency_monitor: Arc::new(LatencyMonitor::new(performance_targets.api_response_time_ms, Duration::from_secs(60))),
+            performance_targets,
             retention_policy: RetentionPolicy {
                 high_resolution_days: 7,
                 medium_resolution_days: 30,
@@ -238,6 +251,16 @@ impl MetricsService {
         Ok(service)
     }
 
+    /// Apply the latency threshold and alert cadence from the monitoring config
+    /// The Prometheus registry is rebuilt so its SLA buckets, breach counter and threshold
+    /// gauge follow the configured threshold; call this before recording anything.
+    pub fn with_monitoring_config(mut self, config: &MonitoringConfig) -> Result<Self> {
+        self.performance_targets.api_response_time_ms = config.performance_threshold_ms;
+        self.prometheus = Arc::new(PrometheusMetrics::new(config.performance_threshold_ms)?);
+        self.latency_monitor = Arc::new(LatencyMonitor::from_config(config));
+        Ok(self)
+    }
+
     /// PRD: Record metric with real-time monitoring
     /// PRD: "Real-time monitoring and prevention systems"
     pub async fn record_metric(
@@ -295,6 +318,9 @@ impl MetricsService {
         response_time_ms: u64,
         status_code: u16,
     ) -> Result<()> {
+        self.prometheus.observe_request_latency(&endpoint, response_time_ms);
+        self.latency_monitor.record_latency(&endpoint, response_time_ms).await;
+
         let labels = HashMap::from([
             ("endpoint".to_string(), endpoint),
             ("method".to_string(), method),
@@ -333,6 +359,63 @@ impl MetricsService {
         Ok(())
     }
 
+    pub fn record_compliance_check(&self, passed: bool) {
+        self.prometheus.record_compliance_check(passed);
+    }
+
+    pub fn record_redemption_processed(&self) {
+        self.prometheus.record_redemption_processed();
+    }
+
+    pub fn record_cross_chain_sync(&self, chain: &str, success: bool) {
+        self.prometheus.record_cross_chain_sync(chain, success);
+    }
+
+    /// Track an operation's latency for p95 threshold alerting
+    pub async fn record_latency(&self, operation: &str, latency_ms: u64) {
+        self.latency_monitor.record_latency(operation, latency_ms).await;
+    }
+
+    pub async fn latency_percentiles(&self, operation: &str) -> Option<LatencyPercentiles> {
+        self.latency_monitor.percentiles(operation).await
+    }
+
+    /// Receive latency alerts raised by the alert loop
+    pub fn subscribe_alerts(&self) -> broadcast::Receiver<Alert> {
+        self.latency_monitor.subscribe()
+    }
+
+    /// Check latency thresholds on the configured `alert_check_interval_seconds` cadence
+    pub fn start_latency_alerts(&self) -> JoinHandle<()> {
+        self.latency_monitor.clone().start_alert_loop()
+    }
+
+    /// Count and log critical systemic risks from the exposure detector until its channel closes
+    ///
+    /// Pass `FundExposureService::subscribe_risk_alerts()`.
+    pub fn watch_exposure_risks(&self, mut risk_alerts: broadcast::Receiver<SystemicRiskAlert>) -> JoinHandle<()> {
+        let prometheus = self.prometheus.clone();
+        tokio::spawn(async move {
+            loop {
+                match risk_alerts.recv().await {
+                    Ok(alert) => {
+                        error!("🚨 Critical exposure risk from {}: {}", alert.root_fund_id, alert.risk.description);
+                        prometheus.record_critical_exposure_risk(&format!("{:?}", alert.risk.risk_type));
+                    }
+                    Err(broadcast::error::RecvError::Lagged(missed)) => {
+                        warn!("⚠️ Missed {} exposure risk alerts", missed);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    /// Prometheus text exposition for the `/metrics` handler
+    pub fn render(&self) -> Result<String> {
+        self.prometheus.render()
+    }
+
     /// PRD: Generate comprehensive performance report
     /// PRD: "Comprehensive audit trail logging"
     pub async fn generate_performance_report(
@@ -536,22 +619,24 @@ impl MetricsService {
     }
 
     async fn check_alert_rules(&self, metric_name: &str, value: f64) -> Result<()> {
-        let rules = self.alert_rules.read().await;
-        
-        for rule in rules.values() {
-            if rule.metric_name == metric_name && rule.enabled {
-                let triggered = match rule.condition {
+        // Collect triggered rules first: trigger_alert takes the write lock
+        let triggered_rules: Vec<AlertRule> = {
+            let rules = self.alert_rules.read().await;
+            rules.values()
+                .filter(|rule| rule.metric_name == metric_name && rule.enabled)
+                .filter(|rule| match rule.condition {
                     AlertCondition::GreaterThan => value > rule.threshold,
                     AlertCondition::LessThan => value < rule.threshold,
                     AlertCondition::Equals => (value - rule.threshold).abs() < 0.001,
                     AlertCondition::NotEquals => (value - rule.threshold).abs() >= 0.001,
                     _ => false, // Other conditions need historical data
-                };
+                })
+                .cloned()
+                .collect()
+        };
 
-                if triggered {
-                    self.trigger_alert(rule, value).await?;
-                }
-            }
+        for rule in &triggered_rules {
+            self.trigger_alert(rule, value).await?;
         }
 
         Ok(())
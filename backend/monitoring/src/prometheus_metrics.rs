@@ -0,0 +1,161 @@
+use anyhow::Result;
+use prometheus::{
+    Encoder, Gauge, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, Opts, Registry, TextEncoder,
+};
+
+/// Prometheus registry for the RTF `/metrics` endpoint
+/// PRD: "Performance targets: <700ms API response time"
+/// The latency histogram has a bucket boundary at the SLA threshold so the share of
+/// requests within target can be read directly from `le="<threshold>"`; the threshold
+/// itself is exported as a gauge so dashboards and alerts read the same value.
+pub struct PrometheusMetrics {
+    registry: Registry,
+    compliance_checks: IntCounterVec,
+    redemptions_processed: IntCounter,
+    cross_chain_syncs: IntCounterVec,
+    request_latency: HistogramVec,
+    latency_sla_breaches: IntCounter,
+    latency_sla_threshold: Gauge,
+    critical_exposure_risks: IntCounterVec,
+    latency_threshold_ms: u64,
+}
+
+impl PrometheusMetrics {
+    pub fn new(latency_threshold_ms: u64) -> Result<Self> {
+        let registry = Registry::new_custom(Some("rtf".to_string()), None)?;
+
+        let compliance_checks = IntCounterVec::new(
+            Opts::new("compliance_checks_total", "Compliance checks performed, by outcome"),
+            &["outcome"],
+        )?;
+        let redemptions_processed = IntCounter::new(
+            "redemptions_processed_total",
+            "Redemption requests processed",
+        )?;
+        let cross_chain_syncs = IntCounterVec::new(
+            Opts::new("cross_chain_sync_total", "Cross-chain state synchronizations, by chain and outcome"),
+            &["chain", "outcome"],
+        )?;
+        let request_latency = HistogramVec::new(
+            HistogramOpts::new("request_latency_seconds", "API request latency in seconds")
+                .buckets(latency_buckets(latency_threshold_ms)),
+            &["endpoint"],
+        )?;
+        let latency_sla_breaches = IntCounter::new(
+            "request_latency_sla_breaches_total",
+            "API requests slower than the latency SLA",
+        )?;
+        let latency_sla_threshold = Gauge::new(
+            "request_latency_sla_threshold_seconds",
+            "Latency above which an API request breaches the SLA",
+        )?;
+        latency_sla_threshold.set(latency_threshold_ms as f64 / 1000.0);
+        let critical_exposure_risks = IntCounterVec::new(
+            Opts::new("critical_exposure_risks_total", "Critical systemic exposure risks raised, by risk type"),
+            &["risk_type"],
+        )?;
+
+        registry.register(Box::new(compliance_checks.clone()))?;
+        registry.register(Box::new(redemptions_processed.clone()))?;
+        registry.register(Box::new(cross_chain_syncs.clone()))?;
+        registry.register(Box::new(request_latency.clone()))?;
+        registry.register(Box::new(latency_sla_breaches.clone()))?;
+        registry.register(Box::new(latency_sla_threshold.clone()))?;
+        registry.register(Box::new(critical_exposure_risks.clone()))?;
+
+        Ok(Self {
+            registry,
+            compliance_checks,
+            redemptions_processed,
+            cross_chain_syncs,
+            request_latency,
+            latency_sla_breaches,
+            latency_sla_threshold,
+            critical_exposure_risks,
+            latency_threshold_ms,
+        })
+    }
+
+    pub fn record_compliance_check(&self, passed: bool) {
+        let outcome = if passed { "passed" } else { "failed" };
+        self.compliance_checks.with_label_values(&[outcome]).inc();
+    }
+
+    pub fn record_redemption_processed(&self) {
+        self.redemptions_processed.inc();
+    }
+
+    pub fn record_cross_chain_sync(&self, chain: &str, success: bool) {
+        let outcome = if success { "success" } else { "failure" };
+        self.cross_chain_syncs.with_label_values(&[chain, outcome]).inc();
+    }
+
+    pub fn observe_request_latency(&self, endpoint: &str, latency_ms: u64) {
+        self.request_latency
+            .with_label_values(&[endpoint])
+            .observe(latency_ms as f64 / 1000.0);
+        if latency_ms > self.latency_threshold_ms {
+            self.latency_sla_breaches.inc();
+        }
+    }
+
+    pub fn record_critical_exposure_risk(&self, risk_type: &str) {
+        self.critical_exposure_risks.with_label_values(&[risk_type]).inc();
+    }
+
+    /// All registered metrics in the Prometheus text exposition format
+    pub fn render(&self) -> Result<String> {
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+        Ok(String::from_utf8(buffer)?)
+    }
+}
+
+/// Default latency buckets with the SLA threshold inserted as a boundary
+fn latency_buckets(latency_threshold_ms: u64) -> Vec<f64> {
+    let threshold = latency_threshold_ms as f64 / 1000.0;
+    let mut buckets = vec![0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+    if !buckets.iter().any(|bucket| (bucket - threshold).abs() < f64::EPSILON) {
+        buckets.push(threshold);
+        buckets.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    }
+    buckets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_buckets_include_threshold() {
+        let buckets = latency_buckets(700);
+        assert!(buckets.contains(&0.7));
+        assert!(buckets.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(latency_buckets(500).len(), 9);
+    }
+
+    #[test]
+    fn test_recorded_events_appear_in_rendered_output() {
+        let metrics = PrometheusMetrics::new(700).unwrap();
+
+        metrics.record_compliance_check(true);
+        metrics.record_compliance_check(true);
+        metrics.record_compliance_check(false);
+        metrics.record_redemption_processed();
+        metrics.record_cross_chain_sync("ethereum", true);
+        metrics.record_cross_chain_sync("ethereum", false);
+        metrics.observe_request_latency("/api/v1/vaults", 120);
+        metrics.observe_request_latency("/api/v1/vaults", 950);
+
+        let rendered = metrics.render().unwrap();
+        assert!(rendered.contains("# TYPE rtf_compliance_checks_total counter"));
+        assert!(rendered.contains("rtf_compliance_checks_total{outcome=\"passed\"} 2"));
+        assert!(rendered.contains("rtf_compliance_checks_total{outcome=\"failed\"} 1"));
+        assert!(rendered.contains("rtf_redemptions_processed_total 1"));
+        assert!(rendered.contains("rtf_cross_chain_sync_total{chain=\"ethereum\",outcome=\"failure\"} 1"));
+        assert!(rendered.contains("rtf_request_latency_seconds_bucket{endpoint=\"/api/v1/vaults\",le=\"0.7\"} 1"));
+        assert!(rendered.contains("rtf_request_latency_seconds_count{endpoint=\"/api/v1/vaults\"} 2"));
+        assert!(rendered.contains("rtf_request_latency_sla_breaches_total 1"));
+        assert!(rendered.contains("rtf_request_latency_sla_threshold_seconds 0.7"));
+    }
+}
@@ -0,0 +1,152 @@
+use anyhow::Result;
+use rtf_bridge_defense::meta_oracle_selector::{MetaOracleSelector, MtrConfig};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use tokio::sync::RwLock;
+use tracing::warn;
+
+pub use rtf_bridge_defense::meta_oracle_selector::{AggregationPolicy, OracleReading};
+
+/// Aggregated oracle prices with provenance
+/// Each feed's latest reading per symbol is kept, and prices are the meta-oracle's robust
+/// median over them: stale and blacklisted feeds are excluded and outliers beyond the MAD
+/// band rejected before the median is taken, so `feeds_used` lists only the feeds that
+/// actually contributed.
+
+/// Price of one symbol and the feeds it was aggregated from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AggregatedPrice {
+    pub symbol: String,
+    pub price: f64,
+    /// Feeds that survived staleness and outlier filtering, sorted
+    pub feeds_used: Vec<String>,
+    /// Median absolute deviation of the fresh readings, in basis points of `price`
+    pub dispersion_bps: u32,
+    /// Unix milliseconds of the oldest contributing reading
+    pub as_of: i64,
+}
+
+pub struct OracleService {
+    meta_oracle: MetaOracleSelector,
+    /// symbol -> feed -> latest reading
+    readings: RwLock<BTreeMap<String, HashMap<String, OracleReading>>>,
+}
+
+impl OracleService {
+    pub async fn new(policy: AggregationPolicy) -> Result<Self> {
+        let config = MtrConfig { aggregation_policy: policy, ..MtrConfig::default() };
+        Ok(Self {
+            meta_oracle: MetaOracleSelector::new(config).await?,
+            readings: RwLock::new(BTreeMap::new()),
+        })
+    }
+
+    /// Record a feed's reading for `symbol`, replacing any older reading from that feed
+    pub async fn record_reading(&self, symbol: &str, reading: OracleReading) {
+        let mut readings = self.readings.write().await;
+        let feeds = readings.entry(symbol.to_string()).or_default();
+        match feeds.get(&reading.oracle_id) {
+            Some(latest) if latest.timestamp > reading.timestamp => {}
+            _ => {
+                feeds.insert(reading.oracle_id.clone(), reading);
+            }
+        }
+    }
+
+    /// Symbols with at least one recorded reading
+    pub async fn symbols(&self) -> Vec<String> {
+        self.readings.read().await.keys().cloned().collect()
+    }
+
+    /// Robust median price of `symbol`, failing when no quorum of feeds agrees
+    pub async fn aggregated_price(&self, symbol: &str) -> Result<AggregatedPrice> {
+        let readings: Vec<OracleReading> = self.readings.read().await
+            .get(symbol)
+            .map(|feeds| feeds.values().cloned().collect())
+            .ok_or_else(|| anyhow::anyhow!("No readings for {}", symbol))?;
+
+        let aggregated = self.meta_oracle.aggregate(&readings).await
+            .map_err(|e| e.context(format!("Aggregating {}", symbol)))?;
+
+        let mut feeds_used = aggregated.accepted_oracles;
+        feeds_used.sort();
+        let as_of = readings.iter()
+            .filter(|reading| feeds_used.contains(&reading.oracle_id))
+            .map(|reading| reading.timestamp)
+            .min()
+            .unwrap_or_default();
+
+        Ok(AggregatedPrice {
+            symbol: symbol.to_string(),
+            price: aggregated.price,
+            feeds_used,
+            dispersion_bps: dispersion_bps(aggregated.median_absolute_deviation, aggregated.price),
+            as_of,
+        })
+    }
+
+    /// Aggregated prices of every symbol that currently has a quorum, by symbol
+    pub async fn aggregated_prices(&self) -> Vec<AggregatedPrice> {
+        let mut prices = Vec::new();
+        for symbol in self.symbols().await {
+            match self.aggregated_price(&symbol).await {
+                Ok(price) => prices.push(price),
+                Err(e) => warn!("⚠️ No aggregated price for {}: {:#}", symbol, e),
+            }
+        }
+        prices
+    }
+}
+
+fn dispersion_bps(median_absolute_deviation: f64, price: f64) -> u32 {
+    if price == 0.0 {
+        return if median_absolute_deviation == 0.0 { 0 } else { u32::MAX };
+    }
+    (median_absolute_deviation / price.abs() * 10_000.0).round().min(u32::MAX as f64) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reading(oracle_id: &str, price: f64, timestamp: i64) -> OracleReading {
+        OracleReading { oracle_id: oracle_id.to_string(), price, timestamp }
+    }
+
+    #[tokio::test]
+    async fn test_aggregated_price_reports_contributing_feeds_and_dispersion() {
+        let service = OracleService::new(AggregationPolicy::default()).await.unwrap();
+        let now = chrono::Utc::now().timestamp_millis();
+        service.record_reading("SOL/USD", reading("pyth", 101.0, now)).await;
+        service.record_reading("SOL/USD", reading("chainlink", 100.0, now - 1_000)).await;
+        service.record_reading("SOL/USD", reading("switchboard", 99.0, now)).await;
+        service.record_reading("SOL/USD", reading("manipulated", 150.0, now)).await;
+        // An older reading never replaces a newer one from the same feed
+        service.record_reading("SOL/USD", reading("pyth", 250.0, now - 2_000)).await;
+
+        let price = service.aggregated_price("SOL/USD").await.unwrap();
+        assert_eq!(price.symbol, "SOL/USD");
+        assert_eq!(price.feeds_used, vec!["chainlink", "pyth", "switchboard"]);
+        assert!((price.price - 100.0).abs() < 1e-9);
+        // Median 100.5 over all four fresh readings, MAD 1.0
+        assert_eq!(price.dispersion_bps, 100);
+        assert_eq!(price.as_of, now - 1_000);
+    }
+
+    #[tokio::test]
+    async fn test_symbols_without_quorum_are_omitted() {
+        let service = OracleService::new(AggregationPolicy::default()).await.unwrap();
+        let now = chrono::Utc::now().timestamp_millis();
+        for (feed, price) in [("pyth", 20.0), ("chainlink", 20.0), ("switchboard", 20.0)] {
+            service.record_reading("ETH/BTC", reading(feed, price, now)).await;
+        }
+        service.record_reading("BTC/USD", reading("pyth", 60_000.0, now)).await;
+
+        assert!(service.aggregated_price("BTC/USD").await.is_err());
+        assert!(service.aggregated_price("XAU/USD").await.is_err());
+        let prices = service.aggregated_prices().await;
+        assert_eq!(prices.len(), 1);
+        assert_eq!(prices[0].symbol, "ETH/BTC");
+        assert_eq!(prices[0].dispersion_bps, 0);
+    }
+}
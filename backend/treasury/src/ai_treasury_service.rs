@@ -4,6 +4,7 @@ use std::collections::HashMap;
 use tokio::sync::RwLock;
 use tokio::time::{Duration, sleep};
 use tracing::{info, warn, error};
+use crate::mean_variance::{cost_aware_step, optimize_weights, RebalanceCosts};
 
 /// AI-Powered Treasury Management Service for RTF Infrastructure
 /// PRD: "AI-powered treasury management integration"
@@ -19,6 +20,7 @@ pub struct AITreasuryService {
     ai_recommendations_enabled: bool,
     max_position_size: f64,
     risk_tolerance: RiskTolerance,
+    rebalance_costs: RebalanceCosts,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -109,6 +111,38 @@ pub enum RiskTolerance {
     Dynamic,       // AI-adjusted based on market conditions
 }
 
+impl RiskTolerance {
+    /// Risk-aversion coefficient λ in the mean-variance utility wᵀμ − (λ/2)·wᵀΣw
+    pub fn risk_aversion(&self) -> f64 {
+        match self {
+            RiskTolerance::Conservative => 10.0,
+            RiskTolerance::Moderate => 4.0,
+            RiskTolerance::Aggressive => 1.5,
+            RiskTolerance::Dynamic => 4.0,
+        }
+    }
+}
+
+/// Trade that moves one asset from its current to its target portfolio weight
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TradeOrder {
+    pub asset: String,
+    pub side: TradeSide,
+    pub current_weight: f64,
+    pub target_weight: f64,
+    /// Absolute weight change, as a fraction of portfolio value
+    pub weight_change: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+/// Weight changes smaller than this are rounding noise, not trades
+const MIN_TRADE_WEIGHT: f64 = 1e-6;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AIModel {
     pub model_id: String,
@@ -256,6 +290,8 @@ impl AITreasuryService {
             ai_recommendations_enabled: true,
             max_position_size,
             risk_tolerance,
+            // Frictionless until configured: every rebalance goes all the way to target
+            rebalance_costs: RebalanceCosts { tx_cost_bps: 0.0, max_turnover: 2.0 },
         };
 
         // Initialize AI models
@@ -328,6 +364,69 @@ impl AITreasuryService {
         Ok(optimization)
     }
 
+    /// Transaction cost (bps of traded value) and turnover budget Σ|Δw| per rebalance
+    pub fn with_rebalance_costs(mut self, max_turnover: f64, tx_cost_bps: f64) -> Self {
+        self.rebalance_costs = RebalanceCosts { tx_cost_bps, max_turnover };
+        self
+    }
+
+    /// Trades that move the portfolio toward its mean-variance optimal weights
+    /// Targets are long-only, sum to 1, and never exceed `max_position_size`; the
+    /// risk-aversion coefficient comes from the service's `RiskTolerance`.
+    /// Only the part of the move whose utility gain covers the transaction cost and fits
+    /// the turnover budget is traded; an empty result means the portfolio is inside the
+    /// no-rebalance band.
+    pub fn compute_rebalance(
+        &self,
+        current_weights: &[(String, f64)],
+        expected_returns: &[f64],
+        covariance: &[Vec<f64>],
+    ) -> Result<Vec<TradeOrder>> {
+        if current_weights.len() != expected_returns.len() {
+            return Err(anyhow::anyhow!(
+                "Got {} weights but {} expected returns",
+                current_weights.len(),
+                expected_returns.len()
+            ));
+        }
+        if let Some((asset, weight)) = current_weights.iter().find(|(_, weight)| *weight < 0.0) {
+            return Err(anyhow::anyhow!("Negative current weight {} for {}", weight, asset));
+        }
+
+        let targets = optimize_weights(
+            expected_returns,
+            covariance,
+            self.risk_tolerance.risk_aversion(),
+            self.max_position_size,
+        )?;
+
+        let current: Vec<f64> = current_weights.iter().map(|(_, weight)| *weight).collect();
+        let step = cost_aware_step(
+            &current,
+            &targets,
+            expected_returns,
+            covariance,
+            self.risk_tolerance.risk_aversion(),
+            self.max_position_size,
+            self.rebalance_costs,
+        );
+
+        let orders: Vec<TradeOrder> = current_weights.iter().zip(&targets)
+            .map(|((asset, current), target)| (asset, *current, current + step * (target - current)))
+            .filter(|(_, current, target)| (target - current).abs() > MIN_TRADE_WEIGHT)
+            .map(|(asset, current, target)| TradeOrder {
+                asset: asset.clone(),
+                side: if target > current { TradeSide::Buy } else { TradeSide::Sell },
+                current_weight: current,
+                target_weight: target,
+                weight_change: (target - current).abs(),
+            })
+            .collect();
+
+        info!("⚖️ Mean-variance rebalance: {} trades ({:.0}% of the move to target)", orders.len(), step * 100.0);
+        Ok(orders)
+    }
+
     /// PRD: Liquidity forecasting with AI
     /// PRD: "Instant-exit quoting with LLM forecasts"
     pub async fn forecast_liquidity_needs(&self, time_horizon_hours: u32) -> Result<LiquidityForecast> {
@@ -697,3 +796,98 @@ impl AITreasuryService {
         (weighted_risk * 100.0).min(100.0) as u8
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagonal(n: usize, variance: f64) -> Vec<Vec<f64>> {
+        (0..n).map(|i| (0..n).map(|j| if i == j { variance } else { 0.0 }).collect()).collect()
+    }
+
+    fn weights(values: &[f64]) -> Vec<(String, f64)> {
+        values.iter().enumerate().map(|(i, w)| (format!("ASSET{}", i), *w)).collect()
+    }
+
+    fn target(orders: &[TradeOrder], current: &[(String, f64)]) -> Vec<f64> {
+        current.iter()
+            .map(|(asset, weight)| orders.iter().find(|o| &o.asset == asset).map_or(*weight, |o| o.target_weight))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_concentrated_portfolio_is_diversified() {
+        let service = AITreasuryService::new(RiskTolerance::Moderate, 0.4).await.unwrap();
+        let current = weights(&[0.85, 0.05, 0.05, 0.05]);
+
+        let orders = service.compute_rebalance(&current, &[0.05; 4], &diagonal(4, 0.04)).unwrap();
+
+        assert_eq!(orders.len(), 4);
+        assert_eq!(orders[0].side, TradeSide::Sell);
+        assert!((orders[0].weight_change - 0.6).abs() < 1e-6);
+        assert!(orders[1..].iter().all(|o| o.side == TradeSide::Buy && (o.target_weight - 0.25).abs() < 1e-6));
+    }
+
+    #[tokio::test]
+    async fn test_target_weights_respect_position_cap() {
+        let service = AITreasuryService::new(RiskTolerance::Aggressive, 0.25).await.unwrap();
+        let current = weights(&[0.2; 5]);
+        let returns = [0.30, 0.06, 0.05, 0.04, 0.03];
+
+        let orders = service.compute_rebalance(&current, &returns, &diagonal(5, 0.02)).unwrap();
+        let targets = target(&orders, &current);
+
+        assert!(targets.iter().all(|w| *w >= 0.0 && *w <= 0.25 + 1e-9));
+        assert!((targets.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!((targets[0] - 0.25).abs() < 1e-6);
+        assert!(targets[1] > targets[4]);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_rebalance_inputs_are_rejected() {
+        let service = AITreasuryService::new(RiskTolerance::Moderate, 0.5).await.unwrap();
+        assert!(service.compute_rebalance(&weights(&[1.2, -0.2]), &[0.05, 0.05], &diagonal(2, 0.04)).is_err());
+        assert!(service.compute_rebalance(&weights(&[0.5, 0.5]), &[0.05], &diagonal(2, 0.04)).is_err());
+        // Two assets capped at 40% cannot hold the whole portfolio
+        let capped = AITreasuryService::new(RiskTolerance::Moderate, 0.4).await.unwrap();
+        assert!(capped.compute_rebalance(&weights(&[0.5, 0.5]), &[0.05, 0.05], &diagonal(2, 0.04)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_small_drift_stays_inside_no_trade_band() {
+        let service = AITreasuryService::new(RiskTolerance::Moderate, 0.4).await.unwrap()
+            .with_rebalance_costs(0.5, 10.0);
+        let current = weights(&[0.252, 0.248, 0.25, 0.25]);
+
+        let orders = service.compute_rebalance(&current, &[0.05; 4], &diagonal(4, 0.04)).unwrap();
+        assert!(orders.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_large_drift_rebalances_within_turnover_budget() {
+        let returns = [0.05; 4];
+        let covariance = diagonal(4, 0.04);
+        let current = weights(&[0.40, 0.10, 0.25, 0.25]);
+        let current_values: Vec<f64> = current.iter().map(|(_, w)| *w).collect();
+        let utility = |w: &[f64]| crate::mean_variance::portfolio_utility(w, &returns, &covariance, 4.0);
+
+        // Turnover budget binds: only 0.2 of the 0.3 needed is traded
+        let budgeted = AITreasuryService::new(RiskTolerance::Moderate, 0.4).await.unwrap()
+            .with_rebalance_costs(0.2, 10.0);
+        let orders = budgeted.compute_rebalance(&current, &returns, &covariance).unwrap();
+        let targets = target(&orders, &current);
+        let turnover: f64 = orders.iter().map(|o| o.weight_change).sum();
+
+        assert_eq!(orders.len(), 2);
+        assert!((turnover - 0.2).abs() < 1e-6);
+        assert!((targets[0] - 0.30).abs() < 1e-6 && (targets[1] - 0.20).abs() < 1e-6);
+        assert!(utility(&targets) - 0.001 * turnover > utility(&current_values));
+
+        // High costs stop the move early even when turnover is unconstrained
+        let costly = AITreasuryService::new(RiskTolerance::Moderate, 0.4).await.unwrap()
+            .with_rebalance_costs(2.0, 200.0);
+        let orders = costly.compute_rebalance(&current, &returns, &covariance).unwrap();
+        let targets = target(&orders, &current);
+        assert!((targets[0] - 0.375).abs() < 1e-6 && (targets[1] - 0.125).abs() < 1e-6);
+    }
+}
@@ -1,6 +1,8 @@
 pub mod ai_treasury_service;
+pub mod mean_variance;
 
 pub use ai_treasury_service::*;
+pub use mean_variance::*;
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
@@ -17,6 +19,10 @@ pub struct TreasuryConfig {
     pub max_position_size: f64,
     pub rebalancing_enabled: bool,
     pub risk_tolerance: String,
+    /// Maximum total turnover Σ|Δw| per rebalance
+    pub max_turnover: f64,
+    /// Transaction cost in basis points of traded value
+    pub tx_cost_bps: f64,
 }
 
 impl Default for TreasuryConfig {
@@ -26,6 +32,8 @@ impl Default for TreasuryConfig {
             max_position_size: 0.25, // 25% max position
             rebalancing_enabled: true,
             risk_tolerance: "moderate".to_string(),
+            max_turnover: 0.5, // 50% of the portfolio per rebalance
+            tx_cost_bps: 10.0,
         }
     }
 }
@@ -44,7 +52,8 @@ pub async fn init_treasury_service(config: TreasuryConfig) -> Result<AITreasuryS
     let service = AITreasuryService::new(
         risk_tolerance,
         config.max_position_size,
-    ).await?;
+    ).await?
+    .with_rebalance_costs(config.max_turnover, config.tx_cost_bps);
 
     info!("✅ RTF Treasury Management Service initialized successfully");
     Ok(service)
@@ -0,0 +1,232 @@
+use anyhow::{anyhow, Result};
+
+/// Long-only mean-variance optimizer with a per-asset weight cap
+/// maximize  wᵀμ − (λ/2)·wᵀΣw   subject to  Σw = 1,  0 ≤ w ≤ cap
+/// Solved by projected gradient ascent; the projection onto the capped simplex is
+/// exact (bisection on the shift), so every iterate is a feasible portfolio.
+
+const MAX_ITERATIONS: usize = 10_000;
+const CONVERGENCE_TOLERANCE: f64 = 1e-12;
+
+/// Mean-variance utility of a portfolio
+pub fn portfolio_utility(weights: &[f64], expected_returns: &[f64], covariance: &[Vec<f64>], risk_aversion: f64) -> f64 {
+    let expected_return: f64 = weights.iter().zip(expected_returns).map(|(w, mu)| w * mu).sum();
+    expected_return - 0.5 * risk_aversion * portfolio_variance(weights, covariance)
+}
+
+pub fn portfolio_variance(weights: &[f64], covariance: &[Vec<f64>]) -> f64 {
+    weights.iter().enumerate()
+        .map(|(i, wi)| wi * covariance[i].iter().zip(weights).map(|(c, wj)| c * wj).sum::<f64>())
+        .sum()
+}
+
+/// Optimal target weights for the given returns, covariance, risk aversion and cap
+pub fn optimize_weights(
+    expected_returns: &[f64],
+    covariance: &[Vec<f64>],
+    risk_aversion: f64,
+    max_weight: f64,
+) -> Result<Vec<f64>> {
+    let n = expected_returns.len();
+    validate_inputs(n, covariance)?;
+    if risk_aversion.is_nan() || risk_aversion <= 0.0 {
+        return Err(anyhow!("Risk aversion must be positive, got {}", risk_aversion));
+    }
+    if max_weight * (n as f64) < 1.0 - 1e-9 {
+        return Err(anyhow!(
+            "Position cap {:.4} is infeasible for {} assets: weights cannot sum to 1",
+            max_weight, n
+        ));
+    }
+
+    // Step 1/L where L bounds the gradient's Lipschitz constant (Gershgorin bound on λ·Σ)
+    let lipschitz = risk_aversion * covariance.iter()
+        .map(|row| row.iter().map(|c| c.abs()).sum::<f64>())
+        .fold(0.0, f64::max);
+    let step = if lipschitz > 0.0 { 1.0 / lipschitz } else { 1.0 };
+
+    let mut weights = project_onto_capped_simplex(&vec![1.0 / n as f64; n], max_weight);
+    for _ in 0..MAX_ITERATIONS {
+        let gradient: Vec<f64> = expected_returns.iter().zip(covariance)
+            .map(|(mu, row)| mu - risk_aversion * row.iter().zip(&weights).map(|(c, w)| c * w).sum::<f64>())
+            .collect();
+        let candidate: Vec<f64> = weights.iter().zip(&gradient).map(|(w, g)| w + step * g).collect();
+        let next = project_onto_capped_simplex(&candidate, max_weight);
+
+        let change: f64 = next.iter().zip(&weights).map(|(a, b)| (a - b).abs()).sum();
+        weights = next;
+        if change < CONVERGENCE_TOLERANCE {
+            break;
+        }
+    }
+    Ok(weights)
+}
+
+/// Trading frictions applied when moving toward the optimal weights
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RebalanceCosts {
+    /// Proportional cost of trading, in basis points of traded value
+    pub tx_cost_bps: f64,
+    /// Budget for total turnover Σ|Δw| in a single rebalance
+    pub max_turnover: f64,
+}
+
+/// Fraction α ∈ [0, 1] of the move from `current` to `target` worth trading
+/// Along w(α) = current + α·d the net utility gain is
+///   α·(gᵀd − c·Σ|d|) − ½α²·λ·dᵀΣd,   g = μ − λΣ·current,
+/// maximized at α* = (gᵀd − c·Σ|d|) / (λ·dᵀΣd), then limited by the turnover budget.
+/// Returns 0 (no trade) when the utility gain does not cover the transaction cost,
+/// unless a position above `max_weight` must be brought back under the cap, which
+/// takes precedence over both cost and turnover budget.
+pub fn cost_aware_step(
+    current: &[f64],
+    target: &[f64],
+    expected_returns: &[f64],
+    covariance: &[Vec<f64>],
+    risk_aversion: f64,
+    max_weight: f64,
+    costs: RebalanceCosts,
+) -> f64 {
+    let direction: Vec<f64> = target.iter().zip(current).map(|(t, c)| t - c).collect();
+    let turnover: f64 = direction.iter().map(|d| d.abs()).sum();
+    if turnover == 0.0 {
+        return 0.0;
+    }
+
+    // Smallest step that brings every over-cap position down to the cap
+    let required_step = current.iter().zip(&direction)
+        .filter(|(w, d)| **w > max_weight && **d < 0.0)
+        .map(|(w, d)| ((w - max_weight) / -d).min(1.0))
+        .fold(0.0, f64::max);
+
+    let gradient_along: f64 = (0..current.len())
+        .map(|i| {
+            let marginal = expected_returns[i]
+                - risk_aversion * covariance[i].iter().zip(current).map(|(c, w)| c * w).sum::<f64>();
+            marginal * direction[i]
+        })
+        .sum();
+    let cost_per_step = costs.tx_cost_bps / 10_000.0 * turnover;
+    let curvature = risk_aversion * portfolio_variance(&direction, covariance);
+
+    let net_slope = gradient_along - cost_per_step;
+    let optimal_step = if net_slope <= 0.0 {
+        0.0
+    } else if curvature > 0.0 {
+        (net_slope / curvature).min(1.0)
+    } else {
+        1.0
+    };
+    let budget_step = (costs.max_turnover / turnover).min(1.0);
+
+    optimal_step.min(budget_step).max(required_step)
+}
+
+/// Euclidean projection onto { w : Σw = 1, 0 ≤ w ≤ cap }
+/// The projection is w_i = clamp(v_i − τ, 0, cap) for the τ that makes the weights sum to 1.
+pub fn project_onto_capped_simplex(values: &[f64], cap: f64) -> Vec<f64> {
+    let total = |tau: f64| values.iter().map(|v| (v - tau).clamp(0.0, cap)).sum::<f64>();
+
+    let mut low = values.iter().cloned().fold(f64::INFINITY, f64::min) - cap - 1.0;
+    let mut high = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    for _ in 0..200 {
+        let mid = 0.5 * (low + high);
+        if total(mid) > 1.0 {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    let tau = 0.5 * (low + high);
+    let mut weights: Vec<f64> = values.iter().map(|v| (v - tau).clamp(0.0, cap)).collect();
+
+    // Remove residual bisection error so the weights sum to exactly 1
+    let residual = 1.0 - weights.iter().sum::<f64>();
+    if let Some(slack) = weights.iter_mut().find(|w| **w > 0.0 && **w + residual <= cap && **w + residual >= 0.0) {
+        *slack += residual;
+    }
+    weights
+}
+
+fn validate_inputs(n: usize, covariance: &[Vec<f64>]) -> Result<()> {
+    if n == 0 {
+        return Err(anyhow!("Cannot optimize an empty portfolio"));
+    }
+    if covariance.len() != n || covariance.iter().any(|row| row.len() != n) {
+        return Err(anyhow!("Covariance matrix must be {}x{}", n, n));
+    }
+    for (i, row) in covariance.iter().enumerate() {
+        if row[i] < 0.0 {
+            return Err(anyhow!("Covariance diagonal must be non-negative"));
+        }
+        for (j, value) in row.iter().enumerate().take(i) {
+            if (value - covariance[j][i]).abs() > 1e-9 {
+                return Err(anyhow!("Covariance matrix must be symmetric"));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagonal(n: usize, variance: f64) -> Vec<Vec<f64>> {
+        (0..n).map(|i| (0..n).map(|j| if i == j { variance } else { 0.0 }).collect()).collect()
+    }
+
+    #[test]
+    fn test_projection_is_feasible() {
+        let projected = project_onto_capped_simplex(&[2.0, -1.0, 0.3, 0.1], 0.5);
+        assert!((projected.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!(projected.iter().all(|w| (0.0..=0.5 + 1e-12).contains(w)));
+        assert!((projected[0] - 0.5).abs() < 1e-9);
+        assert_eq!(projected[1], 0.0);
+    }
+
+    #[test]
+    fn test_equal_assets_get_equal_weights() {
+        let weights = optimize_weights(&[0.05; 4], &diagonal(4, 0.04), 4.0, 0.4).unwrap();
+        for w in weights {
+            assert!((w - 0.25).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_higher_risk_aversion_lowers_variance() {
+        let returns = [0.12, 0.04];
+        let covariance = vec![vec![0.09, 0.0], vec![0.0, 0.01]];
+        let aggressive = optimize_weights(&returns, &covariance, 1.0, 1.0).unwrap();
+        let conservative = optimize_weights(&returns, &covariance, 10.0, 1.0).unwrap();
+        assert!(portfolio_variance(&conservative, &covariance) < portfolio_variance(&aggressive, &covariance));
+    }
+
+    #[test]
+    fn test_infeasible_cap_is_rejected() {
+        assert!(optimize_weights(&[0.05; 3], &diagonal(3, 0.04), 4.0, 0.25).is_err());
+        assert!(optimize_weights(&[0.05; 2], &diagonal(3, 0.04), 4.0, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_cost_aware_step() {
+        let covariance = diagonal(4, 0.04);
+        let target = [0.25; 4];
+        let free = RebalanceCosts { tx_cost_bps: 0.0, max_turnover: 2.0 };
+
+        // Frictionless move to the optimum
+        let step = cost_aware_step(&[0.40, 0.10, 0.25, 0.25], &target, &[0.05; 4], &covariance, 4.0, 1.0, free);
+        assert!((step - 1.0).abs() < 1e-9);
+
+        // Costs shrink the step: α* = (0.0072 − 0.006) / 0.0072
+        let costly = RebalanceCosts { tx_cost_bps: 200.0, max_turnover: 2.0 };
+        let step = cost_aware_step(&[0.40, 0.10, 0.25, 0.25], &target, &[0.05; 4], &covariance, 4.0, 1.0, costly);
+        assert!((step - 1.0 / 6.0).abs() < 1e-9);
+
+        // Over-cap positions are always brought under the cap
+        let prohibitive = RebalanceCosts { tx_cost_bps: 10_000.0, max_turnover: 0.0 };
+        let step = cost_aware_step(&[0.55, 0.15, 0.15, 0.15], &target, &[0.05; 4], &covariance, 4.0, 0.4, prohibitive);
+        assert!((step - 0.5).abs() < 1e-9);
+    }
+}
+
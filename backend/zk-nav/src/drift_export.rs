@@ -0,0 +1,234 @@
+use anyhow::{anyhow, Result};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::{info, warn};
+
+use crate::zkreplay_integrity::DriftLedger;
+
+/// Drift ledger export and reconciliation
+/// PRD: "Drift ledger: Tracks root Δ across epochs"
+/// The vault's `get_drift_ledger` view returns the on-chain 100-epoch ledger; this
+/// exporter pairs it with the backend ledger, flags epochs where the two disagree,
+/// and signs the result as a JSON bundle for off-chain audit.
+
+/// Mirror of the rtf-vault `DriftLedgerExport` return value
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OnChainDriftLedger {
+    pub vault: String,
+    pub first_epoch: u64,
+    pub drifts_bps: Vec<u64>,
+    pub max_drift_threshold: u64,
+    pub consecutive_violations: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportedEpochDrift {
+    pub epoch: u64,
+    pub on_chain_drift_bps: u64,
+    pub backend_drift_bps: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DriftDiscrepancy {
+    /// Backend has no drift recorded for an epoch the vault tracked
+    MissingBackendEpoch { epoch: u64, on_chain_drift_bps: u64 },
+    /// On-chain and backend drift differ by more than the tolerance
+    Diverged { epoch: u64, on_chain_drift_bps: u64, backend_drift_bps: u64 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DriftLedgerBundle {
+    pub vault: String,
+    pub exported_at: i64,
+    pub tolerance_bps: u64,
+    pub max_drift_threshold: u64,
+    pub consecutive_violations: u8,
+    pub epochs: Vec<ExportedEpochDrift>,
+    pub discrepancies: Vec<DriftDiscrepancy>,
+}
+
+/// Bundle plus an Ed25519 signature over its canonical JSON encoding
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedDriftLedgerBundle {
+    pub bundle: DriftLedgerBundle,
+    pub signer: String,
+    pub signature: String,
+}
+
+impl SignedDriftLedgerBundle {
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Parse a bundle and check it was signed by `signer`
+    pub fn from_json(json: &str, signer: &VerifyingKey) -> Result<Self> {
+        let signed: Self = serde_json::from_str(json)?;
+        if signed.signer != hex::encode(signer.to_bytes()) {
+            return Err(anyhow!("Drift ledger bundle signed by unexpected key {}", signed.signer));
+        }
+        let signature: [u8; 64] = hex::decode(&signed.signature)?
+            .try_into()
+            .map_err(|_| anyhow!("Drift ledger bundle signature must be 64 bytes"))?;
+        signer
+            .verify_strict(canonical_json::to_canonical_json(&signed.bundle)?.as_bytes(), &Signature::from_bytes(&signature))
+            .map_err(|_| anyhow!("Drift ledger bundle signature is invalid"))?;
+        Ok(signed)
+    }
+
+    pub fn is_reconciled(&self) -> bool {
+        self.bundle.discrepancies.is_empty()
+    }
+}
+
+pub struct DriftLedgerExporter {
+    signing_key: SigningKey,
+    tolerance_bps: u64,
+}
+
+impl DriftLedgerExporter {
+    pub fn new(signing_key: SigningKey, tolerance_bps: u64) -> Self {
+        Self { signing_key, tolerance_bps }
+    }
+
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.signing_key.verifying_key()
+    }
+
+    /// Reconcile the on-chain ledger against backend per-epoch drift (basis points) and sign it
+    pub fn export(
+        &self,
+        on_chain: &OnChainDriftLedger,
+        backend_drift_bps: &BTreeMap<u64, u64>,
+        exported_at: i64,
+    ) -> Result<SignedDriftLedgerBundle> {
+        let epochs: Vec<ExportedEpochDrift> = on_chain.drifts_bps.iter().enumerate()
+            .map(|(offset, drift)| {
+                let epoch = on_chain.first_epoch + offset as u64;
+                ExportedEpochDrift {
+                    epoch,
+                    on_chain_drift_bps: *drift,
+                    backend_drift_bps: backend_drift_bps.get(&epoch).copied(),
+                }
+            })
+            .collect();
+        let discrepancies = self.reconcile(&epochs);
+
+        if discrepancies.is_empty() {
+            info!("✅ Drift ledger for {} reconciled across {} epochs", on_chain.vault, epochs.len());
+        } else {
+            warn!("🚨 Drift ledger for {} has {} discrepancies", on_chain.vault, discrepancies.len());
+        }
+
+        let bundle = DriftLedgerBundle {
+            vault: on_chain.vault.clone(),
+            exported_at,
+            tolerance_bps: self.tolerance_bps,
+            max_drift_threshold: on_chain.max_drift_threshold,
+            consecutive_violations: on_chain.consecutive_violations,
+            epochs,
+            discrepancies,
+        };
+        let signature = self.signing_key.sign(canonical_json::to_canonical_json(&bundle)?.as_bytes());
+
+        Ok(SignedDriftLedgerBundle {
+            bundle,
+            signer: hex::encode(self.verifying_key().to_bytes()),
+            signature: hex::encode(signature.to_bytes()),
+        })
+    }
+
+    /// Export against the drift recorded by the zkReplay integrity system
+    pub fn export_against_backend(
+        &self,
+        on_chain: &OnChainDriftLedger,
+        backend: &DriftLedger,
+        exported_at: i64,
+    ) -> Result<SignedDriftLedgerBundle> {
+        self.export(on_chain, &backend_drift_bps(backend), exported_at)
+    }
+
+    fn reconcile(&self, epochs: &[ExportedEpochDrift]) -> Vec<DriftDiscrepancy> {
+        epochs.iter()
+            .filter_map(|entry| match entry.backend_drift_bps {
+                None => Some(DriftDiscrepancy::MissingBackendEpoch {
+                    epoch: entry.epoch,
+                    on_chain_drift_bps: entry.on_chain_drift_bps,
+                }),
+                Some(backend) if backend.abs_diff(entry.on_chain_drift_bps) > self.tolerance_bps => {
+                    Some(DriftDiscrepancy::Diverged {
+                        epoch: entry.epoch,
+                        on_chain_drift_bps: entry.on_chain_drift_bps,
+                        backend_drift_bps: backend,
+                    })
+                }
+                Some(_) => None,
+            })
+            .collect()
+    }
+}
+
+/// Backend consensus drift per epoch, converted from a fraction to basis points
+pub fn backend_drift_bps(ledger: &DriftLedger) -> BTreeMap<u64, u64> {
+    ledger.epochs.iter()
+        .map(|epoch| (epoch.epoch, (epoch.consensus_drift.abs() * 10_000.0).round() as u64))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn on_chain() -> OnChainDriftLedger {
+        OnChainDriftLedger {
+            vault: "vault_1".to_string(),
+            first_epoch: 30,
+            drifts_bps: (30..130).map(|epoch| epoch * 2).collect(),
+            max_drift_threshold: 500,
+            consecutive_violations: 0,
+        }
+    }
+
+    fn matching_backend() -> BTreeMap<u64, u64> {
+        (30..130).map(|epoch| (epoch, epoch * 2)).collect()
+    }
+
+    fn exporter() -> DriftLedgerExporter {
+        DriftLedgerExporter::new(SigningKey::from_bytes(&[11; 32]), 1)
+    }
+
+    #[test]
+    fn test_export_round_trips_through_signed_json() {
+        let exporter = exporter();
+        let signed = exporter.export(&on_chain(), &matching_backend(), 1_700_000_000).unwrap();
+        assert!(signed.is_reconciled());
+
+        let json = signed.to_json().unwrap();
+        let parsed = SignedDriftLedgerBundle::from_json(&json, &exporter.verifying_key()).unwrap();
+        assert_eq!(parsed, signed);
+        assert_eq!(parsed.bundle.epochs.len(), 100);
+        assert_eq!(parsed.bundle.epochs[0].epoch, 30);
+        assert_eq!(parsed.bundle.epochs[99].on_chain_drift_bps, 258);
+
+        // Edited bundles and other signers are rejected
+        let tampered = json.replace("\"on_chain_drift_bps\": 258", "\"on_chain_drift_bps\": 0");
+        assert!(SignedDriftLedgerBundle::from_json(&tampered, &exporter.verifying_key()).is_err());
+        let other = SigningKey::from_bytes(&[12; 32]).verifying_key();
+        assert!(SignedDriftLedgerBundle::from_json(&json, &other).is_err());
+    }
+
+    #[test]
+    fn test_injected_discrepancy_is_flagged() {
+        let mut backend = matching_backend();
+        backend.insert(75, 900);
+        backend.insert(76, 153); // within 1 bps tolerance
+        backend.remove(&100);
+
+        let signed = exporter().export(&on_chain(), &backend, 1_700_000_000).unwrap();
+        assert!(!signed.is_reconciled());
+        assert_eq!(signed.bundle.discrepancies, vec![
+            DriftDiscrepancy::Diverged { epoch: 75, on_chain_drift_bps: 150, backend_drift_bps: 900 },
+            DriftDiscrepancy::MissingBackendEpoch { epoch: 100, on_chain_drift_bps: 200 },
+        ]);
+    }
+}
@@ -1,6 +1,10 @@
 pub mod zkreplay_integrity;
+pub mod nav_attestation;
+pub mod drift_export;
 
 pub use zkreplay_integrity::*;
+pub use nav_attestation::*;
+pub use drift_export::*;
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
@@ -0,0 +1,265 @@
+use anyhow::{anyhow, Result};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use domain_tags::DomainTag;
+use tracing::{info, warn};
+
+/// Off-chain NAV proof verification and attestation
+/// PRD: "NAV is computed daily using a verifiable zk circuit"
+/// The STARK, Starknet fact and Dilithium512 signature are verified here by a
+/// `NavProofVerifier`; the result is a signed `NavAttestation` that the rtf-vault
+/// program checks cheaply in `update_nav_with_zk_proof`. Hashes and wire format
+/// must stay byte-identical to contracts/solana/rtf-vault/src/nav_attestation.rs.
+/// The signature is not part of the wire format: it travels in the Ed25519 precompile
+/// instruction that precedes the NAV update in the same transaction.
+
+pub const NAV_ATTESTATION_LEN: usize = 32 * 5 + 8;
+
+/// Public inputs of the NAV circuit, as submitted on-chain in `NAVData`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NavPublicInputs {
+    pub nav_per_share: u64,
+    pub total_assets: u64,
+    pub total_liabilities: u64,
+    pub timestamp: i64,
+    pub tranche_navs: Vec<u64>,
+}
+
+impl NavPublicInputs {
+    pub fn hash(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(DomainTag::NavProof.as_bytes());
+        hasher.update(self.nav_per_share.to_le_bytes());
+        hasher.update(self.total_assets.to_le_bytes());
+        hasher.update(self.total_liabilities.to_le_bytes());
+        hasher.update(self.timestamp.to_le_bytes());
+        for tranche_nav in &self.tranche_navs {
+            hasher.update(tranche_nav.to_le_bytes());
+        }
+        hasher.finalize().into()
+    }
+}
+
+/// Everything the off-chain verifier checks before attesting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NavProofBundle {
+    pub zk_proof: Vec<u8>,
+    pub starknet_fact: [u8; 32],
+    pub dilithium_signature: Vec<u8>,
+    pub dilithium_public_key: Vec<u8>,
+}
+
+/// Full verification of a NAV proof bundle against its public inputs
+/// Implementations wrap the STARK verifier, Starknet fact registry and Dilithium512.
+pub trait NavProofVerifier: Send + Sync {
+    fn verify(&self, inputs: &NavPublicInputs, bundle: &NavProofBundle) -> Result<()>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NavAttestation {
+    pub public_inputs_hash: [u8; 32],
+    pub verified_root: [u8; 32],
+    pub starknet_fact: [u8; 32],
+    pub pq_signature_hash: [u8; 32],
+    pub pq_public_key_hash: [u8; 32],
+    pub attested_at: i64,
+    #[serde(with = "signature_bytes")]
+    pub signature: [u8; 64],
+}
+
+impl NavAttestation {
+    /// Message signed by the verifier key, bound to the vault program and vault account
+    pub fn digest(&self, program_id: &[u8; 32], vault: &[u8; 32]) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(DomainTag::NavAttestation.as_bytes());
+        hasher.update(program_id);
+        hasher.update(vault);
+        hasher.update(self.public_inputs_hash);
+        hasher.update(self.verified_root);
+        hasher.update(self.starknet_fact);
+        hasher.update(self.pq_signature_hash);
+        hasher.update(self.pq_public_key_hash);
+        hasher.update(self.attested_at.to_le_bytes());
+        hasher.finalize().into()
+    }
+
+    /// Borsh encoding passed as the `zk_proof` instruction argument
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(NAV_ATTESTATION_LEN);
+        bytes.extend_from_slice(&self.public_inputs_hash);
+        bytes.extend_from_slice(&self.verified_root);
+        bytes.extend_from_slice(&self.starknet_fact);
+        bytes.extend_from_slice(&self.pq_signature_hash);
+        bytes.extend_from_slice(&self.pq_public_key_hash);
+        bytes.extend_from_slice(&self.attested_at.to_le_bytes());
+        bytes
+    }
+
+    /// Same checks the vault program and the Ed25519 precompile perform together
+    pub fn verify(
+        &self,
+        inputs: &NavPublicInputs,
+        verifier_key: &VerifyingKey,
+        program_id: &[u8; 32],
+        vault: &[u8; 32],
+    ) -> Result<()> {
+        verifier_key
+            .verify_strict(&self.digest(program_id, vault), &Signature::from_bytes(&self.signature))
+            .map_err(|_| anyhow!("NAV attestation not signed by the configured verifier"))?;
+        if self.public_inputs_hash != inputs.hash() {
+            return Err(anyhow!("NAV attestation does not match the submitted public inputs"));
+        }
+        Ok(())
+    }
+}
+
+/// Verifies NAV proofs off-chain and signs attestations for the vault program
+pub struct NavAttestor {
+    verifier: Box<dyn NavProofVerifier>,
+    signing_key: SigningKey,
+    program_id: [u8; 32],
+}
+
+impl NavAttestor {
+    /// `program_id` is the deployed rtf-vault program the attestations are for
+    pub fn new(verifier: Box<dyn NavProofVerifier>, signing_key: SigningKey, program_id: [u8; 32]) -> Self {
+        Self { verifier, signing_key, program_id }
+    }
+
+    /// Key to configure as the vault's `nav_verifier`
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.signing_key.verifying_key()
+    }
+
+    /// Attest `inputs` for the vault account `vault`
+    pub fn attest(
+        &self,
+        inputs: &NavPublicInputs,
+        bundle: &NavProofBundle,
+        vault: &[u8; 32],
+        attested_at: i64,
+    ) -> Result<NavAttestation> {
+        if let Err(e) = self.verifier.verify(inputs, bundle) {
+            warn!("❌ NAV proof rejected for NAV {}: {}", inputs.nav_per_share, e);
+            return Err(e);
+        }
+
+        let mut attestation = NavAttestation {
+            public_inputs_hash: inputs.hash(),
+            verified_root: Sha256::digest(&bundle.zk_proof).into(),
+            starknet_fact: bundle.starknet_fact,
+            pq_signature_hash: Sha256::digest(&bundle.dilithium_signature).into(),
+            pq_public_key_hash: Sha256::digest(&bundle.dilithium_public_key).into(),
+            attested_at,
+            signature: [0; 64],
+        };
+        attestation.signature = self.signing_key.sign(&attestation.digest(&self.program_id, vault)).to_bytes();
+
+        info!("✅ NAV proof attested for NAV {} at {}", inputs.nav_per_share, inputs.timestamp);
+        Ok(attestation)
+    }
+}
+
+mod signature_bytes {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8; 64], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(bytes)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 64], D::Error> {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        bytes.try_into().map_err(|_| serde::de::Error::custom("signature must be 64 bytes"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Accepts bundles whose proof commits to the public inputs
+    struct CommitmentVerifier;
+
+    impl NavProofVerifier for CommitmentVerifier {
+        fn verify(&self, inputs: &NavPublicInputs, bundle: &NavProofBundle) -> Result<()> {
+            if bundle.zk_proof.get(..32) != Some(&inputs.hash()[..]) {
+                return Err(anyhow!("zk proof does not commit to the public inputs"));
+            }
+            Ok(())
+        }
+    }
+
+    fn inputs() -> NavPublicInputs {
+        NavPublicInputs {
+            nav_per_share: 1_050_000,
+            total_assets: 10_500_000_000,
+            total_liabilities: 0,
+            timestamp: 1_700_000_000,
+            tranche_navs: vec![1_020_000, 1_110_000],
+        }
+    }
+
+    fn bundle(inputs: &NavPublicInputs) -> NavProofBundle {
+        let mut zk_proof = inputs.hash().to_vec();
+        zk_proof.extend_from_slice(b"stark proof body");
+        NavProofBundle {
+            zk_proof,
+            starknet_fact: [5; 32],
+            dilithium_signature: vec![9; 128],
+            dilithium_public_key: vec![3; 64],
+        }
+    }
+
+    const PROGRAM_ID: [u8; 32] = [1; 32];
+    const VAULT: [u8; 32] = [7; 32];
+
+    fn attestor() -> NavAttestor {
+        NavAttestor::new(Box::new(CommitmentVerifier), SigningKey::from_bytes(&[42; 32]), PROGRAM_ID)
+    }
+
+    #[test]
+    fn test_public_inputs_hash_matches_vault_vector() {
+        // Same vector as rtf-vault nav_attestation tests
+        let hex: String = inputs().hash().iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(hex, "54d664971167eed222ae6760336ea8511c53b86c72f09acb47d128d222d37278");
+    }
+
+    #[test]
+    fn test_valid_bundle_is_attested() {
+        let attestor = attestor();
+        let inputs = inputs();
+        let attestation = attestor.attest(&inputs, &bundle(&inputs), &VAULT, 1_700_000_010).unwrap();
+
+        assert!(attestation.verify(&inputs, &attestor.verifying_key(), &PROGRAM_ID, &VAULT).is_ok());
+        assert_eq!(attestation.to_bytes().len(), NAV_ATTESTATION_LEN);
+        assert_eq!(attestation.pq_signature_hash, <[u8; 32]>::from(Sha256::digest([9u8; 128])));
+    }
+
+    #[test]
+    fn test_tampered_public_input_is_rejected() {
+        let attestor = attestor();
+        let inputs = inputs();
+        let attestation = attestor.attest(&inputs, &bundle(&inputs), &VAULT, 1_700_000_010).unwrap();
+
+        let mut tampered = inputs.clone();
+        tampered.tranche_navs[1] += 1;
+        assert!(attestation.verify(&tampered, &attestor.verifying_key(), &PROGRAM_ID, &VAULT).is_err());
+        // The verifier refuses to attest a proof for different inputs
+        assert!(attestor.attest(&tampered, &bundle(&inputs), &VAULT, 1_700_000_010).is_err());
+
+        let mut forged = attestation.clone();
+        forged.public_inputs_hash = tampered.hash();
+        assert!(forged.verify(&tampered, &attestor.verifying_key(), &PROGRAM_ID, &VAULT).is_err());
+    }
+
+    #[test]
+    fn test_attestation_is_bound_to_its_vault_and_program() {
+        let attestor = attestor();
+        let inputs = inputs();
+        let attestation = attestor.attest(&inputs, &bundle(&inputs), &VAULT, 1_700_000_010).unwrap();
+
+        assert!(attestation.verify(&inputs, &attestor.verifying_key(), &PROGRAM_ID, &[8; 32]).is_err());
+        assert!(attestation.verify(&inputs, &attestor.verifying_key(), &[2; 32], &VAULT).is_err());
+    }
+}
@@ -1,9 +1,11 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{info, warn, error};
 use sha2::{Sha256, Digest};
+use epoch_clock::{EpochClock, SystemEpochClock};
 
 /// zkReplay & Integrity System for RTF Infrastructure
 /// PRD Section 5: "zkReplay & Integrity System"
@@ -11,14 +13,20 @@ use sha2::{Sha256, Digest};
 /// PRD: "Drift ledger: Tracks root Δ across epochs"
 /// PRD: "Deviation > threshold = redemption freeze"
 
+/// Voting weight of each chain's replay root, out of `TOTAL_ROOT_WEIGHT`. No single
+/// chain holds a majority, so a consensus root needs at least two chains to agree.
+pub const ETHEREUM_ROOT_WEIGHT: u32 = 35;
+pub const SOLANA_ROOT_WEIGHT: u32 = 30;
+pub const BTC_ANCHOR_ROOT_WEIGHT: u32 = 35;
+pub const TOTAL_ROOT_WEIGHT: u32 = ETHEREUM_ROOT_WEIGHT + SOLANA_ROOT_WEIGHT + BTC_ANCHOR_ROOT_WEIGHT;
+
 pub struct ZkReplayIntegritySystem {
     replay_roots: RwLock<HashMap<u64, ReplayRootSet>>,
     drift_ledger: RwLock<DriftLedger>,
     integrity_validators: Vec<IntegrityValidator>,
     deviation_threshold: f64,
     freeze_threshold: f64,
-    epoch_duration: u64,
-    current_epoch: RwLock<u64>,
+    epoch_clock: Arc<dyn EpochClock>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,6 +49,8 @@ pub struct EthereumRoot {
     pub transaction_root: String,
     pub receipt_root: String,
     pub ccip_message_hash: String,
+    /// zkReplay root the vault's Ethereum contract recorded for this epoch
+    pub replay_root: String,
     pub gas_used: u64,
     pub timestamp: i64,
 }
@@ -53,6 +63,8 @@ pub struct SolanaRoot {
     pub state_root: String,
     pub transaction_root: String,
     pub program_account_hash: String,
+    /// zkReplay root the vault program recorded for this epoch
+    pub replay_root: String,
     pub clock_timestamp: i64,
 }
 
@@ -63,10 +75,27 @@ pub struct BtcAnchorRoot {
     pub merkle_root: String,
     pub babylon_checkpoint: String,
     pub op_return_data: String,
+    /// zkReplay root committed in the anchor's OP_RETURN
+    pub replay_root: String,
     pub confirmations: u32,
     pub timestamp: i64,
 }
 
+/// A replay root and the verified chains that reported it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RootCandidate {
+    pub root: String,
+    pub chains: Vec<String>,
+    pub weight: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ConsensusRootError {
+    /// No root holds a weighted majority; the chains may have forked
+    #[error("No weighted majority of chains agrees on a replay root: {candidates:?}")]
+    Disagreement { candidates: Vec<RootCandidate> },
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CrossChainProofs {
     pub ethereum_to_solana_proof: String,
@@ -251,11 +280,26 @@ impl ZkReplayIntegritySystem {
             integrity_validators,
             deviation_threshold,
             freeze_threshold,
-            epoch_duration,
-            current_epoch: RwLock::new(0),
+            epoch_clock: Arc::new(SystemEpochClock::new(epoch_duration)),
         })
     }
 
+    /// Clock replay roots are checked against; roots for epochs ahead of it are rejected
+    pub fn with_epoch_clock(mut self, epoch_clock: Arc<dyn EpochClock>) -> Self {
+        self.epoch_clock = epoch_clock;
+        self
+    }
+
+    /// Epoch the clock is currently in
+    pub fn current_epoch(&self) -> u64 {
+        self.epoch_clock.current_epoch()
+    }
+
+    /// Epoch a root timestamp (Unix seconds) belongs to
+    pub fn epoch_for(&self, timestamp: i64) -> u64 {
+        self.epoch_clock.epoch_for(timestamp)
+    }
+
     /// PRD: "Triple-check replay roots: Ethereum, Solana, BTC anchor"
     /// Advanced cryptographic verification with cross-chain consistency proofs
     pub async fn triple_check_replay_roots(
@@ -267,6 +311,13 @@ impl ZkReplayIntegritySystem {
     ) -> Result<TripleCheckResult> {
         info!("🔍 Triple-checking replay roots for epoch: {} with advanced verification", epoch);
 
+        let current_epoch = self.current_epoch();
+        if epoch > current_epoch {
+            return Err(anyhow::anyhow!(
+                "Replay roots for epoch {} are ahead of the current epoch {}", epoch, current_epoch
+            ));
+        }
+
         // Step 1: Individual root verification
         let ethereum_verified = self.verify_ethereum_root_integrity(&ethereum_root).await?;
         let solana_verified = self.verify_solana_root_integrity(&solana_root).await?;
@@ -788,6 +839,54 @@ impl ZkReplayIntegritySystem {
         })
     }
 
+    /// Replay root reported by a weighted majority of the verified chains. Native state
+    /// and merkle roots differ by construction across chains, so only the `replay_root`
+    /// each anchor recorded is compared. Chains that failed verification do not vote but
+    /// still count towards the total weight, so losing one raises the bar for the others.
+    /// Without a majority the candidates are returned rather than picking one, which
+    /// would mask a genuine cross-chain fork.
+    async fn calculate_weighted_consensus_root(
+        &self,
+        ethereum_root: &EthereumRoot,
+        solana_root: &SolanaRoot,
+        btc_anchor_root: &BtcAnchorRoot,
+        ethereum_verified: bool,
+        solana_verified: bool,
+        btc_verified: bool,
+    ) -> Result<String> {
+        let votes = [
+            ("ethereum", &ethereum_root.replay_root, ETHEREUM_ROOT_WEIGHT, ethereum_verified),
+            ("solana", &solana_root.replay_root, SOLANA_ROOT_WEIGHT, solana_verified),
+            ("btc", &btc_anchor_root.replay_root, BTC_ANCHOR_ROOT_WEIGHT, btc_verified),
+        ];
+
+        let mut candidates: Vec<RootCandidate> = Vec::new();
+        for (chain, root, weight, verified) in votes {
+            if !verified {
+                continue;
+            }
+            match candidates.iter_mut().find(|candidate| &candidate.root == root) {
+                Some(candidate) => {
+                    candidate.chains.push(chain.to_string());
+                    candidate.weight += weight;
+                }
+                None => candidates.push(RootCandidate {
+                    root: root.clone(),
+                    chains: vec![chain.to_string()],
+                    weight,
+                }),
+            }
+        }
+
+        match candidates.iter().find(|candidate| candidate.weight * 2 > TOTAL_ROOT_WEIGHT) {
+            Some(majority) => Ok(majority.root.clone()),
+            None => {
+                warn!("🚨 Replay roots disagree across chains: {:?}", candidates);
+                Err(ConsensusRootError::Disagreement { candidates }.into())
+            }
+        }
+    }
+
     async fn calculate_consensus_root(
         &self,
         ethereum_root: &EthereumRoot,
@@ -1123,3 +1222,124 @@ pub enum ActionPriority {
     Critical,
     Emergency,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use epoch_clock::MockEpochClock;
+
+    const DAY: u64 = 86_400;
+
+    fn roots(timestamp: i64) -> (EthereumRoot, SolanaRoot, BtcAnchorRoot) {
+        (
+            EthereumRoot {
+                block_number: 1,
+                block_hash: String::new(),
+                state_root: String::new(),
+                transaction_root: String::new(),
+                receipt_root: String::new(),
+                ccip_message_hash: String::new(),
+                replay_root: String::new(),
+                gas_used: 0,
+                timestamp,
+            },
+            SolanaRoot {
+                slot: 1,
+                block_hash: String::new(),
+                parent_hash: String::new(),
+                state_root: String::new(),
+                transaction_root: String::new(),
+                program_account_hash: String::new(),
+                replay_root: String::new(),
+                clock_timestamp: timestamp,
+            },
+            BtcAnchorRoot {
+                block_height: 1,
+                block_hash: String::new(),
+                merkle_root: String::new(),
+                babylon_checkpoint: String::new(),
+                op_return_data: String::new(),
+                replay_root: String::new(),
+                confirmations: 6,
+                timestamp,
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_epochs_follow_the_injected_clock() {
+        let clock = Arc::new(MockEpochClock::new(DAY, 0));
+        clock.set_now(clock.epoch_start(20) - 1);
+        let system = ZkReplayIntegritySystem::new(0.05, 0.10, DAY).await.unwrap()
+            .with_epoch_clock(clock.clone());
+
+        assert_eq!(system.current_epoch(), 19);
+        assert_eq!(system.epoch_for(clock.epoch_start(20) - 1), 19);
+        assert_eq!(system.epoch_for(clock.epoch_start(20)), 20);
+
+        let (ethereum, solana, btc) = roots(clock.now());
+        let err = system.triple_check_replay_roots(20, ethereum, solana, btc).await.unwrap_err();
+        assert!(err.to_string().contains("ahead of the current epoch 19"));
+
+        clock.advance(1);
+        assert_eq!(system.current_epoch(), 20);
+    }
+
+    fn roots_reporting(ethereum: &str, solana: &str, btc: &str) -> (EthereumRoot, SolanaRoot, BtcAnchorRoot) {
+        let (mut ethereum_root, mut solana_root, mut btc_root) = roots(0);
+        ethereum_root.replay_root = ethereum.to_string();
+        solana_root.replay_root = solana.to_string();
+        btc_root.replay_root = btc.to_string();
+        (ethereum_root, solana_root, btc_root)
+    }
+
+    fn disagreement(err: anyhow::Error) -> Vec<RootCandidate> {
+        match err.downcast::<ConsensusRootError>().unwrap() {
+            ConsensusRootError::Disagreement { candidates } => candidates,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unanimous_roots_reach_consensus() {
+        let system = ZkReplayIntegritySystem::new(0.05, 0.10, DAY).await.unwrap();
+        let (mut ethereum, mut solana, mut btc) = roots_reporting("root_a", "root_a", "root_a");
+        // Each chain's own state roots play no part in the vote
+        ethereum.state_root = "eth_state".to_string();
+        solana.state_root = "sol_state".to_string();
+        btc.merkle_root = "btc_merkle".to_string();
+
+        let root = system.calculate_weighted_consensus_root(&ethereum, &solana, &btc, true, true, true).await.unwrap();
+        assert_eq!(root, "root_a");
+    }
+
+    #[tokio::test]
+    async fn test_two_of_three_roots_reach_consensus() {
+        let system = ZkReplayIntegritySystem::new(0.05, 0.10, DAY).await.unwrap();
+        let (ethereum, solana, btc) = roots_reporting("root_a", "root_b", "root_a");
+        let root = system.calculate_weighted_consensus_root(&ethereum, &solana, &btc, true, true, true).await.unwrap();
+        assert_eq!(root, "root_a");
+
+        // Solana (the lightest chain) and BTC still outweigh Ethereum
+        let (ethereum, solana, btc) = roots_reporting("root_a", "root_b", "root_b");
+        let root = system.calculate_weighted_consensus_root(&ethereum, &solana, &btc, true, true, true).await.unwrap();
+        assert_eq!(root, "root_b");
+
+        // A chain that failed verification does not vote, and one chain alone is no majority
+        let (ethereum, solana, btc) = roots_reporting("root_a", "root_a", "root_b");
+        let err = system.calculate_weighted_consensus_root(&ethereum, &solana, &btc, true, false, true).await.unwrap_err();
+        assert_eq!(disagreement(err).len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_total_disagreement_reports_every_candidate() {
+        let system = ZkReplayIntegritySystem::new(0.05, 0.10, DAY).await.unwrap();
+        let (ethereum, solana, btc) = roots_reporting("root_a", "root_b", "root_c");
+
+        let err = system.calculate_weighted_consensus_root(&ethereum, &solana, &btc, true, true, true).await.unwrap_err();
+        assert_eq!(disagreement(err), vec![
+            RootCandidate { root: "root_a".to_string(), chains: vec!["ethereum".to_string()], weight: ETHEREUM_ROOT_WEIGHT },
+            RootCandidate { root: "root_b".to_string(), chains: vec!["solana".to_string()], weight: SOLANA_ROOT_WEIGHT },
+            RootCandidate { root: "root_c".to_string(), chains: vec!["btc".to_string()], weight: BTC_ANCHOR_ROOT_WEIGHT },
+        ]);
+    }
+}
@@ -1,6 +1,11 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use std::collections::VecDeque;
+use epoch_clock::{epoch_for, epoch_start};
+use domain_tags::DomainTag;
+use ed25519_instruction::{load_preceding_instruction, verify_ed25519_instruction};
+use solana_program::instruction::Instruction;
+use solana_program::sysvar;
 
 declare_id!("RTFRedemptionEngine11111111111111111111111111");
 
@@ -22,7 +27,11 @@ pub mod rtf_redemption {
         min_holding_duration: i64,
         epoch_duration: i64,
         mev_protection_delay: i64,
+        reveal_window_duration: i64,
+        tranche_weights: Vec<u64>,
     ) -> Result<()> {
+        validate_tranche_weights(&tranche_weights)?;
+
         let redemption_engine = &mut ctx.accounts.redemption_engine;
         redemption_engine.authority = ctx.accounts.authority.key();
         redemption_engine.vault = ctx.accounts.vault.key();
@@ -30,7 +39,12 @@ pub mod rtf_redemption {
         redemption_engine.min_holding_duration = min_holding_duration;
         redemption_engine.epoch_duration = epoch_duration;
         redemption_engine.mev_protection_delay = mev_protection_delay;
+        redemption_engine.reveal_window_duration = reveal_window_duration;
         redemption_engine.current_epoch = 0;
+        redemption_engine.max_redeemable_per_epoch = [0; MAX_TRANCHES];
+        redemption_engine.tranche_weights = tranche_weights;
+        redemption_engine.forecast_authority = Pubkey::default();
+        redemption_engine.max_forecast_staleness = DEFAULT_MAX_FORECAST_STALENESS;
         redemption_engine.total_pending_redemptions = 0;
         redemption_engine.bump = ctx.bumps.redemption_engine;
 
@@ -38,6 +52,42 @@ pub mod rtf_redemption {
         Ok(())
     }
 
+    /// Cap the underlying assets one tranche may redeem per epoch batch (0 = unlimited)
+    pub fn set_tranche_redemption_cap(
+        ctx: Context<SetTrancheRedemptionCap>,
+        tranche_index: u8,
+        max_redeemable_per_epoch: u64,
+    ) -> Result<()> {
+        require!((tranche_index as usize) < MAX_TRANCHES, RedemptionError::InvalidTrancheIndex);
+        let redemption_engine = &mut ctx.accounts.redemption_engine;
+        redemption_engine.max_redeemable_per_epoch[tranche_index as usize] = max_redeemable_per_epoch;
+        Ok(())
+    }
+
+    /// Replace the per-tranche priority weights, rescoring requests already revealed
+    pub fn set_tranche_weights(
+        ctx: Context<SetTrancheWeights>,
+        tranche_weights: Vec<u64>,
+    ) -> Result<()> {
+        validate_tranche_weights(&tranche_weights)?;
+        let redemption_engine = &mut ctx.accounts.redemption_engine;
+        rescore_pending_requests(&mut redemption_engine.pending_requests, &tranche_weights);
+        redemption_engine.tranche_weights = tranche_weights;
+        Ok(())
+    }
+
+    /// Set the key LLM NAV forecasts must be signed by and how old they may be (0 = default)
+    pub fn set_forecast_authority(
+        ctx: Context<SetForecastAuthority>,
+        forecast_authority: Pubkey,
+        max_forecast_staleness: i64,
+    ) -> Result<()> {
+        let redemption_engine = &mut ctx.accounts.redemption_engine;
+        redemption_engine.forecast_authority = forecast_authority;
+        redemption_engine.max_forecast_staleness = max_forecast_staleness;
+        Ok(())
+    }
+
     /// PRD: Submit redemption request with MEV protection (commit phase)
     pub fn submit_redemption_commitment(
         ctx: Context<SubmitRedemptionCommitment>,
@@ -99,7 +149,7 @@ pub mod rtf_redemption {
             RedemptionError::InvalidCommitmentIndex
         );
 
-        let commitment = &mut redemption_engine.commitments[commitment_index as usize];
+        let commitment = redemption_engine.commitments[commitment_index as usize].clone();
         require!(
             commitment.user == ctx.accounts.user.key(),
             RedemptionError::UnauthorizedReveal
@@ -117,16 +167,15 @@ pub mod rtf_redemption {
         );
 
         // PRD: Advanced commit-reveal scheme verification
-        let revealed_hash = self.compute_commitment_hash(
-            amount,
-            min_assets_out,
-            nonce,
-            ctx.accounts.user.key(),
-            commitment.timestamp,
-        )?;
-
         require!(
-            revealed_hash == commitment.commitment_hash,
+            commitment_matches(
+                &commitment.commitment_hash,
+                amount,
+                min_assets_out,
+                nonce,
+                ctx.accounts.user.key(),
+                commitment.timestamp,
+            )?,
             RedemptionError::InvalidCommitmentReveal
         );
 
@@ -136,19 +185,32 @@ pub mod rtf_redemption {
             RedemptionError::RevealWindowExpired
         );
 
-        // Create redemption request
+        // Create redemption request; it waits for its reveal window to close
+        let window_end = reveal_window_end(
+            clock.unix_timestamp,
+            redemption_engine.epoch_duration,
+            redemption_engine.reveal_window_duration,
+        );
         let redemption_request = RedemptionRequest {
             user: ctx.accounts.user.key(),
             amount,
             min_assets_out,
             tranche_index: commitment.tranche_index,
             timestamp: commitment.timestamp,
-            priority_score: calculate_priority_score(amount, commitment.timestamp, commitment.tranche_index),
+            priority_score: calculate_priority_score(
+                amount,
+                commitment.timestamp,
+                commitment.tranche_index,
+                window_end,
+                &redemption_engine.tranche_weights,
+            ),
             status: RedemptionStatus::Pending,
+            commitment_index,
+            reveal_window_end: window_end,
         };
 
-        redemption_engine.pending_requests.push(redemption_request);
-        commitment.revealed = true;
+        redemption_engine.pending_requests.push(redemption_request.clone());
+        redemption_engine.commitments[commitment_index as usize].revealed = true;
 
         emit!(RedemptionRevealed {
             user: ctx.accounts.user.key(),
@@ -171,77 +233,37 @@ pub mod rtf_redemption {
         let clock = Clock::get()?;
 
         // PRD: Check if epoch has ended
-        let epoch_start = redemption_engine.current_epoch * redemption_engine.epoch_duration;
+        let clock_epoch = epoch_for(clock.unix_timestamp, 0, redemption_engine.epoch_duration.max(0) as u64);
         require!(
-            clock.unix_timestamp >= epoch_start + redemption_engine.epoch_duration,
+            clock_epoch > redemption_engine.current_epoch,
             RedemptionError::EpochNotEnded
         );
 
-        // PRD: Sort requests by priority (first-in, time-bound, tranche-weighted)
-        redemption_engine.pending_requests.sort_by(|a, b| {
-            b.priority_score.cmp(&a.priority_score)
-        });
-
-        let mut executed_count = 0;
-        let mut total_assets_out = 0u64;
-        let batch_size = std::cmp::min(max_batch_size as usize, redemption_engine.pending_requests.len());
-
-        // Execute redemptions in priority order
-        for i in 0..batch_size {
-            if executed_count >= max_batch_size {
-                break;
-            }
-
-            let request = &mut redemption_engine.pending_requests[i];
-            if request.status != RedemptionStatus::Pending {
-                continue;
-            }
-
-            // Calculate assets out based on current NAV
-            let nav_per_share = vault.nav_per_share;
-            let assets_out = (request.amount * nav_per_share) / 1_000_000; // Assuming 6 decimals
-
-            // Check minimum assets out requirement
-            if assets_out < request.min_assets_out {
-                request.status = RedemptionStatus::Failed;
-                emit!(RedemptionFailed {
-                    user: request.user,
-                    amount: request.amount,
-                    reason: "Insufficient assets out".to_string(),
-                });
-                continue;
-            }
-
-            // Check vault liquidity
-            if vault.available_liquidity < assets_out {
-                request.status = RedemptionStatus::Deferred;
-                continue;
-            }
-
-            // Execute redemption
-            vault.available_liquidity -= assets_out;
-            vault.total_shares -= request.amount;
-            total_assets_out += assets_out;
-
-            request.status = RedemptionStatus::Executed;
-            executed_count += 1;
-
-            emit!(RedemptionExecuted {
-                user: request.user,
-                shares_redeemed: request.amount,
-                assets_out,
-                nav_per_share,
-                tranche_index: request.tranche_index,
-            });
-        }
+        // Reveals are processed a whole window at a time, ordered by commit time, so the
+        // order in which they were revealed tells searchers nothing
+        order_revealed_requests(&mut redemption_engine.pending_requests);
+        let ready = closed_window_request_count(&redemption_engine.pending_requests, clock.unix_timestamp);
+
+        // PRD: "tranche-weighted" - each tranche redeems at most its cap per epoch. The batch
+        // belongs to the clock epoch it runs in; at most one runs per epoch
+        let epoch = clock_epoch;
+        let mut budget = EpochRedemptionBudget::new(redemption_engine.max_redeemable_per_epoch);
+        let (executed_count, total_assets_out) = execute_pending_requests(
+            &mut redemption_engine.pending_requests[..ready],
+            vault,
+            &mut budget,
+            epoch,
+            max_batch_size,
+        )?;
 
         // Remove executed requests
         redemption_engine.pending_requests.retain(|req| req.status != RedemptionStatus::Executed);
         redemption_engine.total_pending_redemptions = redemption_engine.pending_requests.len() as u64;
-        redemption_engine.current_epoch += 1;
+        // Epochs that passed without a batch are not replayed
+        redemption_engine.current_epoch = clock_epoch;
 
         emit!(RedemptionBatchExecuted {
-            epoch: redemption_engine.current_epoch - 1,
+            epoch,
             executed_count,
             total_assets_out,
             remaining_requests: redemption_engine.total_pending_redemptions,
@@ -251,26 +273,46 @@ pub mod rtf_redemption {
     }
 
     /// PRD: Get instant-exit quote with LLM forecasts
+    /// The forecast is only applied when signed by the engine's forecast authority for
+    /// this vault and tranche and still fresh; otherwise the quote uses the base NAV.
+    /// The signature is checked by an Ed25519 precompile instruction placed immediately
+    /// before this one.
     pub fn get_instant_exit_quote(
         ctx: Context<GetInstantExitQuote>,
         amount: u64,
         tranche_index: u8,
+        forecast: Option<NavForecast>,
     ) -> Result<()> {
+        let redemption_engine = &ctx.accounts.redemption_engine;
         let vault = &ctx.accounts.vault;
-        let llm_oracle = &ctx.accounts.llm_oracle;
+        let clock = Clock::get()?;
 
         // Get current NAV
         let base_nav_per_share = vault.nav_per_share;
 
         // PRD: Apply LLM forecast adjustment
-        let forecast_adjustment = llm_oracle.get_nav_forecast_adjustment(tranche_index)?;
+        let ed25519_instruction = forecast
+            .as_ref()
+            .and_then(|_| load_preceding_instruction(&ctx.accounts.instructions_sysvar).ok());
+        let forecast = verified_forecast(
+            forecast.as_ref(),
+            ed25519_instruction.as_ref(),
+            &redemption_engine.forecast_authority,
+            &vault.key(),
+            tranche_index,
+            clock.unix_timestamp,
+            redemption_engine.max_forecast_staleness,
+        );
+        let (forecast_adjustment, forecast_confidence) = forecast
+            .map(|forecast| (forecast.adjustment_bps, forecast.confidence_score))
+            .unwrap_or((0, 0));
         let adjusted_nav = apply_forecast_adjustment(base_nav_per_share, forecast_adjustment);
 
         // Calculate instant exit penalty (for immediate liquidity)
         let instant_exit_penalty = calculate_instant_exit_penalty(amount, vault.available_liquidity);
         let final_nav = adjusted_nav * (10000 - instant_exit_penalty) / 10000;
 
-        let assets_out = (amount * final_nav) / 1_000_000;
+        let assets_out = assets_for_shares(amount, final_nav, vault.nav_decimals)?;
 
         emit!(InstantExitQuote {
             user: ctx.accounts.user.key(),
@@ -279,8 +321,9 @@ pub mod rtf_redemption {
             base_nav: base_nav_per_share,
             adjusted_nav,
             penalty_bps: instant_exit_penalty,
-            forecast_confidence: llm_oracle.confidence_score,
-            valid_until: Clock::get()?.unix_timestamp + 300, // 5 minutes
+            forecast_applied: forecast.is_some(),
+            forecast_confidence,
+            valid_until: clock.unix_timestamp + 300, // 5 minutes
         });
 
         Ok(())
@@ -317,20 +360,57 @@ pub mod rtf_redemption {
 }
 
 // Helper functions
-fn calculate_priority_score(amount: u64, timestamp: i64, tranche_index: u8) -> u64 {
-    // PRD: First-in, time-bound, tranche-weighted priority
-    let time_weight = (Clock::get().unwrap().unix_timestamp - timestamp) as u64;
-    let tranche_weight = match tranche_index {
-        0 => 1000, // Senior tranche gets highest priority
-        1 => 500,  // Junior tranche
-        2 => 100,  // LP tranche gets lowest priority
-        _ => 0,
-    };
+
+/// Default priority weight of each tranche, indexed like the vault's `TrancheType`
+pub const DEFAULT_TRANCHE_WEIGHTS: [u64; MAX_TRANCHES] = [
+    1000, // Senior tranche gets highest priority
+    500,  // Junior
+    100,  // LP gets lowest priority
+    750,  // Mezzanine
+    250,  // Equity
+];
+
+/// Every tranche type needs a positive weight, or its requests could starve
+fn validate_tranche_weights(tranche_weights: &[u64]) -> Result<()> {
+    require!(
+        tranche_weights.len() == MAX_TRANCHES && tranche_weights.iter().all(|&weight| weight > 0),
+        RedemptionError::InvalidTrancheWeights
+    );
+    Ok(())
+}
+
+/// PRD: First-in, time-bound, tranche-weighted priority
+/// Age is measured from commit to the close of the reveal window, not to the reveal, so
+/// the score does not depend on when the request was revealed. Commit time orders the
+/// queue; the score only ranks requests committed in the same second, where the age term
+/// is equal and the tranche weight decides.
+fn calculate_priority_score(
+    amount: u64,
+    timestamp: i64,
+    tranche_index: u8,
+    reveal_window_end: i64,
+    tranche_weights: &[u64],
+) -> u64 {
+    let time_weight = reveal_window_end.saturating_sub(timestamp).max(0) as u64;
+    let tranche_weight = tranche_weights.get(tranche_index as usize).copied().unwrap_or(0);
     let amount_weight = amount / 1000; // Normalize amount
 
     time_weight + tranche_weight + amount_weight
 }
 
+/// Recompute the priority of every pending request under new tranche weights
+fn rescore_pending_requests(requests: &mut [RedemptionRequest], tranche_weights: &[u64]) {
+    for request in requests.iter_mut() {
+        request.priority_score = calculate_priority_score(
+            request.amount,
+            request.timestamp,
+            request.tranche_index,
+            request.reveal_window_end,
+            tranche_weights,
+        );
+    }
+}
+
 /// PRD: Advanced commit-reveal scheme with enhanced MEV protection
 fn compute_commitment_hash(
     amount: u64,
@@ -341,18 +421,107 @@ fn compute_commitment_hash(
 ) -> Result<[u8; 32]> {
     // Enhanced commitment hash with timestamp and additional entropy
     let commitment_data = [
+        DomainTag::RedemptionCommitment.as_bytes(),
         &amount.to_le_bytes(),
         &min_assets_out.to_le_bytes(),
         &nonce.to_le_bytes(),
         user.as_ref(),
         &timestamp.to_le_bytes(),
-        b"RTF_REDEMPTION_COMMITMENT_V2", // Version identifier
     ].concat();
 
     let hash = solana_program::keccak::hash(&commitment_data);
     Ok(hash.to_bytes())
 }
 
+/// Pre-V3 encoding, with the version tag appended after the fields. Clients built
+/// before the tag moved to the front still produce this; since a commitment must be
+/// revealed within an hour, it can be dropped once every client is on V3.
+fn compute_legacy_commitment_hash(
+    amount: u64,
+    min_assets_out: u64,
+    nonce: u64,
+    user: Pubkey,
+    timestamp: i64,
+) -> [u8; 32] {
+    solana_program::keccak::hashv(&[
+        &amount.to_le_bytes(),
+        &min_assets_out.to_le_bytes(),
+        &nonce.to_le_bytes(),
+        user.as_ref(),
+        &timestamp.to_le_bytes(),
+        DomainTag::LegacyRedemptionCommitment.as_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Whether the revealed fields open the stored commitment under either encoding
+fn commitment_matches(
+    commitment_hash: &[u8; 32],
+    amount: u64,
+    min_assets_out: u64,
+    nonce: u64,
+    user: Pubkey,
+    timestamp: i64,
+) -> Result<bool> {
+    Ok(compute_commitment_hash(amount, min_assets_out, nonce, user, timestamp)? == *commitment_hash
+        || compute_legacy_commitment_hash(amount, min_assets_out, nonce, user, timestamp) == *commitment_hash)
+}
+
+pub const DEFAULT_MAX_FORECAST_STALENESS: i64 = 600; // 10 minutes
+
+/// Message the forecast authority signs, bound to this program and the quoted vault
+fn forecast_digest(vault: &Pubkey, forecast: &NavForecast) -> [u8; 32] {
+    solana_program::keccak::hashv(&[
+        DomainTag::NavForecast.as_bytes(),
+        crate::ID.as_ref(),
+        vault.as_ref(),
+        &[forecast.tranche_index],
+        &forecast.adjustment_bps.to_le_bytes(),
+        &[forecast.confidence_score],
+        &forecast.issued_at.to_le_bytes(),
+    ]).to_bytes()
+}
+
+/// The forecast, if `ed25519_instruction` verified `forecast_authority`'s signature over it
+/// for this vault and tranche and it was issued within the staleness bound. Anything else
+/// is ignored with a log line, so a missing or spoofed forecast degrades the quote to the
+/// base NAV instead of failing it.
+fn verified_forecast(
+    forecast: Option<&NavForecast>,
+    ed25519_instruction: Option<&Instruction>,
+    forecast_authority: &Pubkey,
+    vault: &Pubkey,
+    tranche_index: u8,
+    now: i64,
+    max_forecast_staleness: i64,
+) -> Option<NavForecast> {
+    let forecast = forecast?;
+    if *forecast_authority == Pubkey::default() {
+        msg!("No forecast authority configured; ignoring forecast");
+        return None;
+    }
+
+    let digest = forecast_digest(vault, forecast);
+    let signed = ed25519_instruction
+        .is_some_and(|instruction| verify_ed25519_instruction(instruction, forecast_authority, &digest).is_ok());
+    if !signed {
+        msg!("Forecast signature missing or invalid; ignoring forecast");
+        return None;
+    }
+
+    let max_staleness = if max_forecast_staleness > 0 {
+        max_forecast_staleness
+    } else {
+        DEFAULT_MAX_FORECAST_STALENESS
+    };
+    let age = now.saturating_sub(forecast.issued_at);
+    if forecast.tranche_index != tranche_index || age < 0 || age > max_staleness {
+        msg!("Forecast stale or for another tranche; ignoring forecast");
+        return None;
+    }
+    Some(forecast.clone())
+}
+
 fn apply_forecast_adjustment(base_nav: u64, adjustment_bps: i16) -> u64 {
     if adjustment_bps >= 0 {
         base_nav + (base_nav * adjustment_bps as u64) / 10000
@@ -361,6 +530,244 @@ fn apply_forecast_adjustment(base_nav: u64, adjustment_bps: i16) -> u64 {
     }
 }
 
+/// Execute pending requests in their current (priority) order against the vault
+/// The request that crosses its tranche's epoch cap is filled up to the cap and its
+/// remainder stays `Pending` in place, so it leads the next epoch's batch; a request
+/// larger than the cap is therefore paid out over several epochs instead of blocking the
+/// tranche. Returns the fully executed count and assets paid out.
+fn execute_pending_requests(
+    pending_requests: &mut [RedemptionRequest],
+    vault: &mut VaultAccount,
+    budget: &mut EpochRedemptionBudget,
+    epoch: u64,
+    max_batch_size: u32,
+) -> Result<(u32, u64)> {
+    let mut executed_count = 0;
+    let mut total_assets_out = 0u64;
+    let batch_size = std::cmp::min(max_batch_size as usize, pending_requests.len());
+
+    // Execute redemptions in priority order
+    for request in pending_requests.iter_mut().take(batch_size) {
+        if executed_count >= max_batch_size {
+            break;
+        }
+        if request.status != RedemptionStatus::Pending {
+            continue;
+        }
+
+        // Calculate assets out based on current NAV
+        let assets_out = assets_for_shares(request.amount, vault.nav_per_share, vault.nav_decimals)?;
+
+        // Check minimum assets out requirement
+        if assets_out < request.min_assets_out {
+            request.status = RedemptionStatus::Failed;
+            emit!(RedemptionFailed {
+                user: request.user,
+                amount: request.amount,
+                reason: "Insufficient assets out".to_string(),
+            });
+            continue;
+        }
+
+        // Check vault liquidity
+        if vault.available_liquidity < assets_out {
+            request.status = RedemptionStatus::Deferred;
+            continue;
+        }
+
+        // Check the tranche's epoch cap; what does not fit carries over to the next epoch
+        let capacity = match budget.admit(request.tranche_index, assets_out) {
+            CapAdmission::Admitted => None,
+            CapAdmission::Partial(capacity) => Some(capacity),
+            CapAdmission::CapReached => Some(0),
+            CapAdmission::Capped => continue,
+        };
+        if let Some(capacity) = capacity {
+            emit!(TrancheRedemptionCapReached {
+                epoch,
+                tranche_index: request.tranche_index,
+                max_redeemable_per_epoch: budget.cap(request.tranche_index),
+                redeemed_in_epoch: budget.redeemed(request.tranche_index),
+            });
+
+            let shares = partial_fill_shares(request.amount, assets_out, capacity);
+            if shares > 0 {
+                let filled_assets = assets_for_shares(shares, vault.nav_per_share, vault.nav_decimals)?;
+                vault.available_liquidity -= filled_assets;
+                vault.total_shares -= shares;
+                total_assets_out += filled_assets;
+
+                // The remainder keeps the same minimum price per share
+                request.min_assets_out = scale_down(request.min_assets_out, request.amount - shares, request.amount);
+                request.amount -= shares;
+
+                emit!(RedemptionExecuted {
+                    user: request.user,
+                    shares_redeemed: shares,
+                    assets_out: filled_assets,
+                    nav_per_share: vault.nav_per_share,
+                    tranche_index: request.tranche_index,
+                });
+            }
+            continue;
+        }
+
+        // Execute redemption
+        vault.available_liquidity -= assets_out;
+        vault.total_shares -= request.amount;
+        total_assets_out += assets_out;
+
+        request.status = RedemptionStatus::Executed;
+        executed_count += 1;
+
+        emit!(RedemptionExecuted {
+            user: request.user,
+            shares_redeemed: request.amount,
+            assets_out,
+            nav_per_share: vault.nav_per_share,
+            tranche_index: request.tranche_index,
+        });
+    }
+
+    Ok((executed_count, total_assets_out))
+}
+
+/// When the reveal window containing `timestamp` closes
+/// Each epoch is split into consecutive windows of `reveal_window_duration` seconds, the
+/// last one cut short at the epoch's end; a non-positive duration makes the whole epoch
+/// one window.
+fn reveal_window_end(timestamp: i64, epoch_duration: i64, reveal_window_duration: i64) -> i64 {
+    let epoch_duration = epoch_duration.max(1);
+    let window = if reveal_window_duration > 0 {
+        reveal_window_duration.min(epoch_duration)
+    } else {
+        epoch_duration
+    };
+    let epoch = epoch_for(timestamp, 0, epoch_duration as u64);
+    let start = epoch_start(epoch, 0, epoch_duration as u64);
+    let window_start = start + (timestamp - start).max(0) / window * window;
+    (window_start + window).min(start + epoch_duration)
+}
+
+/// Order requests window by window, and within a window by commit time. Tranche-weighted
+/// priority only breaks ties between commitments made in the same second, then the
+/// commitment index does, so neither size nor tranche lets a request overtake an
+/// earlier commitment.
+fn order_revealed_requests(requests: &mut [RedemptionRequest]) {
+    requests.sort_by_key(|request| (
+        request.reveal_window_end,
+        request.timestamp,
+        std::cmp::Reverse(request.priority_score),
+        request.commitment_index,
+    ));
+}
+
+/// Number of leading requests (in `order_revealed_requests` order) whose window has closed
+fn closed_window_request_count(requests: &[RedemptionRequest], now: i64) -> usize {
+    requests.iter().take_while(|request| request.reveal_window_end <= now).count()
+}
+
+pub const MAX_TRANCHES: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CapAdmission {
+    Admitted,
+    /// This request is the first in the epoch that does not fit the tranche's cap; only
+    /// the given assets fit, and the tranche closes for the epoch
+    Partial(u64),
+    /// As `Partial`, but the cap is already exhausted
+    CapReached,
+    /// The tranche already hit its cap this epoch
+    Capped,
+}
+
+/// Underlying assets each tranche has redeemed in the current epoch batch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EpochRedemptionBudget {
+    caps: [u64; MAX_TRANCHES],
+    redeemed: [u64; MAX_TRANCHES],
+    capped: [bool; MAX_TRANCHES],
+}
+
+impl EpochRedemptionBudget {
+    pub fn new(caps: [u64; MAX_TRANCHES]) -> Self {
+        Self { caps, redeemed: [0; MAX_TRANCHES], capped: [false; MAX_TRANCHES] }
+    }
+
+    /// Charge `assets` to the tranche if it fits under the cap (0 = unlimited)
+    /// Once a request does not fit it is charged whatever capacity is left and the
+    /// tranche closes for the epoch, so smaller lower-priority requests cannot overtake
+    /// the one carried over.
+    pub fn admit(&mut self, tranche_index: u8, assets: u64) -> CapAdmission {
+        let index = tranche_index as usize;
+        if index >= MAX_TRANCHES || self.caps[index] == 0 {
+            if let Some(redeemed) = self.redeemed.get_mut(index) {
+                *redeemed = redeemed.saturating_add(assets);
+            }
+            return CapAdmission::Admitted;
+        }
+        if self.capped[index] {
+            return CapAdmission::Capped;
+        }
+        match self.redeemed[index].checked_add(assets) {
+            Some(total) if total <= self.caps[index] => {
+                self.redeemed[index] = total;
+                CapAdmission::Admitted
+            }
+            _ => {
+                self.capped[index] = true;
+                let capacity = self.caps[index].saturating_sub(self.redeemed[index]);
+                if capacity == 0 {
+                    return CapAdmission::CapReached;
+                }
+                self.redeemed[index] = self.caps[index];
+                CapAdmission::Partial(capacity)
+            }
+        }
+    }
+
+    pub fn cap(&self, tranche_index: u8) -> u64 {
+        self.caps.get(tranche_index as usize).copied().unwrap_or(0)
+    }
+
+    pub fn redeemed(&self, tranche_index: u8) -> u64 {
+        self.redeemed.get(tranche_index as usize).copied().unwrap_or(0)
+    }
+}
+
+/// Shares of a `shares` request worth `assets` that fit in `capacity` assets, rounded down
+fn partial_fill_shares(shares: u64, assets: u64, capacity: u64) -> u64 {
+    if assets == 0 {
+        return 0;
+    }
+    scale_down(shares, capacity.min(assets), assets)
+}
+
+/// `value * numerator / denominator` for `numerator <= denominator`, rounded down
+fn scale_down(value: u64, numerator: u64, denominator: u64) -> u64 {
+    if denominator == 0 {
+        return 0;
+    }
+    (value as u128 * numerator as u128 / denominator as u128) as u64
+}
+
+/// Same bounds as the vault's `MIN_NAV_DECIMALS..=MAX_NAV_DECIMALS`
+const MIN_NAV_DECIMALS: u8 = 2;
+const MAX_NAV_DECIMALS: u8 = 18;
+
+/// `shares * nav_per_share / 10^nav_decimals`, rounded down in the vault's favour
+fn assets_for_shares(shares: u64, nav_per_share: u64, nav_decimals: u8) -> Result<u64> {
+    require!(
+        (MIN_NAV_DECIMALS..=MAX_NAV_DECIMALS).contains(&nav_decimals),
+        RedemptionError::InvalidNavDecimals
+    );
+    let assets = (shares as u128)
+        .checked_mul(nav_per_share as u128)
+        .ok_or(RedemptionError::MathOverflow)?
+        / 10u128.pow(nav_decimals as u32);
+    u64::try_from(assets).map_err(|_| RedemptionError::MathOverflow.into())
+}
+
 fn calculate_instant_exit_penalty(amount: u64, available_liquidity: u64) -> u16 {
     // Higher penalty for larger redemptions relative to available liquidity
     let utilization_bps = (amount * 10000) / available_liquidity.max(1);
@@ -413,6 +820,30 @@ pub struct Initialize<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct SetTrancheRedemptionCap<'info> {
+    #[account(mut, has_one = authority)]
+    pub redemption_engine: Account<'info, RedemptionEngine>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetForecastAuthority<'info> {
+    #[account(mut, has_one = authority)]
+    pub redemption_engine: Account<'info, RedemptionEngine>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetTrancheWeights<'info> {
+    #[account(mut, has_one = authority)]
+    pub redemption_engine: Account<'info, RedemptionEngine>,
+
+    pub authority: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct SubmitRedemptionCommitment<'info> {
     #[account(mut)]
@@ -445,13 +876,16 @@ pub struct ExecuteRedemptionBatch<'info> {
 
 #[derive(Accounts)]
 pub struct GetInstantExitQuote<'info> {
+    #[account(has_one = vault)]
     pub redemption_engine: Account<'info, RedemptionEngine>,
     pub vault: Account<'info, VaultAccount>,
 
-    /// CHECK: LLM Oracle account
-    pub llm_oracle: UncheckedAccount<'info>,
-
     pub user: Signer<'info>,
+
+    /// CHECK: Instructions sysvar, read for the Ed25519 precompile instruction carrying the
+    /// forecast authority's signature
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
@@ -469,15 +903,29 @@ pub struct RedemptionEngine {
     pub min_holding_duration: i64,
     pub epoch_duration: i64,
     pub mev_protection_delay: i64,
+    pub reveal_window_duration: i64, // Seconds; reveals within one window are ordered by commit time
     pub current_epoch: u64,
     pub total_pending_redemptions: u64,
+    pub max_redeemable_per_epoch: [u64; MAX_TRANCHES], // Underlying assets per tranche (0 = unlimited)
+    pub tranche_weights: Vec<u64>, // Priority weight per tranche type, all positive
+    pub forecast_authority: Pubkey, // Ed25519 key LLM NAV forecasts are signed by (default = none accepted)
+    pub max_forecast_staleness: i64, // Seconds (0 = default)
     pub commitments: Vec<RedemptionCommitment>,
     pub pending_requests: Vec<RedemptionRequest>,
     pub bump: u8,
 }
 
 impl RedemptionEngine {
-    pub const INIT_SPACE: usize = 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 4 + 4 + 1;
+    pub const INIT_SPACE: usize = 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 * MAX_TRANCHES + 4 + 8 * MAX_TRANCHES + 32 + 8 + 4 + 4 + 1;
+}
+
+/// LLM forecast of a tranche's NAV movement
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct NavForecast {
+    pub tranche_index: u8,
+    pub adjustment_bps: i16,
+    pub confidence_score: u8,
+    pub issued_at: i64,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -497,8 +945,11 @@ pub struct RedemptionRequest {
     pub min_assets_out: u64,
     pub tranche_index: u8,
     pub timestamp: i64,
+    // Breaks ties between same-second commits; also reported in `RedemptionRevealed`
     pub priority_score: u64,
     pub status: RedemptionStatus,
+    pub commitment_index: u64,
+    pub reveal_window_end: i64, // Processed only once its reveal window has closed
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
@@ -512,6 +963,7 @@ pub enum RedemptionStatus {
 #[account]
 pub struct VaultAccount {
     pub nav_per_share: u64,
+    pub nav_decimals: u8, // Mirrors the vault's `VaultConfig::nav_decimals`
     pub total_shares: u64,
     pub available_liquidity: u64,
 }
@@ -550,6 +1002,14 @@ pub struct RedemptionFailed {
     pub reason: String,
 }
 
+#[event]
+pub struct TrancheRedemptionCapReached {
+    pub epoch: u64,
+    pub tranche_index: u8,
+    pub max_redeemable_per_epoch: u64,
+    pub redeemed_in_epoch: u64,
+}
+
 #[event]
 pub struct RedemptionBatchExecuted {
     pub epoch: u64,
@@ -566,6 +1026,7 @@ pub struct InstantExitQuote {
     pub base_nav: u64,
     pub adjusted_nav: u64,
     pub penalty_bps: u16,
+    pub forecast_applied: bool,
     pub forecast_confidence: u8,
     pub valid_until: i64,
 }
@@ -610,4 +1071,296 @@ pub enum RedemptionError {
 
     #[msg("Reveal window has expired")]
     RevealWindowExpired,
+
+    #[msg("Invalid tranche index")]
+    InvalidTrancheIndex,
+
+    #[msg("Tranche weights must give every tranche type a positive weight")]
+    InvalidTrancheWeights,
+
+    #[msg("NAV decimals outside the supported range")]
+    InvalidNavDecimals,
+
+    #[msg("Math overflow")]
+    MathOverflow,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vault() -> VaultAccount {
+        VaultAccount {
+            nav_per_share: 1_000_000,
+            nav_decimals: 6,
+            total_shares: 10_000_000,
+            available_liquidity: 10_000_000,
+        }
+    }
+
+    fn request(tranche_index: u8, amount: u64, priority_score: u64) -> RedemptionRequest {
+        RedemptionRequest {
+            user: Pubkey::new_unique(),
+            amount,
+            min_assets_out: 0,
+            tranche_index,
+            timestamp: 0,
+            priority_score,
+            status: RedemptionStatus::Pending,
+            commitment_index: 0,
+            reveal_window_end: 0,
+        }
+    }
+
+    fn revealed(commitment_index: u64, committed_at: i64, revealed_at: i64) -> RedemptionRequest {
+        RedemptionRequest {
+            commitment_index,
+            timestamp: committed_at,
+            reveal_window_end: reveal_window_end(revealed_at, DAY, HOUR),
+            ..request(0, 1_000, 0)
+        }
+    }
+
+    const DAY: i64 = 86_400;
+    const HOUR: i64 = 3_600;
+
+    #[test]
+    fn test_budget_closes_tranche_once_cap_is_hit() {
+        let mut budget = EpochRedemptionBudget::new([1_000, 0, 0, 0, 0]);
+        assert_eq!(budget.admit(0, 600), CapAdmission::Admitted);
+        assert_eq!(budget.admit(0, 500), CapAdmission::Partial(400));
+        // A smaller request that would fit must not overtake the carried-over one
+        assert_eq!(budget.admit(0, 100), CapAdmission::Capped);
+        assert_eq!(budget.redeemed(0), 1_000);
+
+        let mut budget = EpochRedemptionBudget::new([1_000, 0, 0, 0, 0]);
+        assert_eq!(budget.admit(0, 1_000), CapAdmission::Admitted);
+        assert_eq!(budget.admit(0, 1), CapAdmission::CapReached);
+        // Uncapped tranches are unaffected
+        assert_eq!(budget.admit(1, u64::MAX), CapAdmission::Admitted);
+    }
+
+    #[test]
+    fn test_tranche_cap_limits_batch_and_rolls_remainder() {
+        let caps = [2_500_000, 0, 0, 0, 0];
+        let mut vault = vault();
+        let mut pending = vec![
+            request(0, 1_000_000, 90),
+            request(0, 1_000_000, 80),
+            request(1, 3_000_000, 70),
+            request(0, 1_000_000, 60),
+            request(0, 400_000, 50),
+        ];
+
+        let (executed, assets_out) = execute_pending_requests(&mut pending, &mut vault, &mut EpochRedemptionBudget::new(caps), 0, 10).unwrap();
+        assert_eq!((executed, assets_out), (3, 5_500_000));
+        // Tranche 0 stopped at its cap, half-filling the request that crossed it; the
+        // uncapped tranche was not starved
+        let carried: Vec<(u64, u64)> = pending.iter()
+            .filter(|request| request.status == RedemptionStatus::Pending)
+            .map(|request| (request.priority_score, request.amount))
+            .collect();
+        assert_eq!(carried, vec![(60, 500_000), (50, 400_000)]);
+
+        // Next epoch: the carried requests execute in their original priority order
+        pending.retain(|request| request.status != RedemptionStatus::Executed);
+        let (executed, assets_out) = execute_pending_requests(&mut pending, &mut vault, &mut EpochRedemptionBudget::new(caps), 1, 10).unwrap();
+        assert_eq!((executed, assets_out), (2, 900_000));
+        assert!(pending.iter().all(|request| request.status == RedemptionStatus::Executed));
+        assert_eq!(vault.available_liquidity, 3_600_000);
+    }
+
+    #[test]
+    fn test_request_larger_than_the_cap_drains_over_several_epochs() {
+        let caps = [1_000_000, 0, 0, 0, 0];
+        let mut vault = vault();
+        let mut pending = vec![
+            RedemptionRequest { min_assets_out: 2_500_000, ..request(0, 2_500_000, 90) },
+            request(0, 100_000, 80),
+        ];
+
+        // The oversize request takes the whole cap each epoch and keeps its place
+        for epoch in 0..2 {
+            let (executed, assets_out) = execute_pending_requests(&mut pending, &mut vault, &mut EpochRedemptionBudget::new(caps), epoch, 10).unwrap();
+            assert_eq!((executed, assets_out), (0, 1_000_000));
+            assert_eq!(pending[0].status, RedemptionStatus::Pending);
+            assert_eq!(pending[1].status, RedemptionStatus::Pending);
+        }
+        assert_eq!((pending[0].amount, pending[0].min_assets_out), (500_000, 500_000));
+
+        // Its remainder and the request behind it both fit in the third epoch
+        let (executed, assets_out) = execute_pending_requests(&mut pending, &mut vault, &mut EpochRedemptionBudget::new(caps), 2, 10).unwrap();
+        assert_eq!((executed, assets_out), (2, 600_000));
+        assert_eq!(vault.available_liquidity, 7_400_000);
+        assert_eq!(vault.total_shares, 7_400_000);
+    }
+
+    #[test]
+    fn test_reveal_windows_split_each_epoch() {
+        let epoch_start = 10 * DAY;
+        assert_eq!(reveal_window_end(epoch_start, DAY, HOUR), epoch_start + HOUR);
+        assert_eq!(reveal_window_end(epoch_start + HOUR - 1, DAY, HOUR), epoch_start + HOUR);
+        assert_eq!(reveal_window_end(epoch_start + HOUR, DAY, HOUR), epoch_start + 2 * HOUR);
+        // The last window ends with the epoch even when the duration does not divide it
+        assert_eq!(reveal_window_end(epoch_start + DAY - 1, DAY, 7 * HOUR), epoch_start + DAY);
+        // No window duration: the whole epoch is one window
+        assert_eq!(reveal_window_end(epoch_start + 5 * HOUR, DAY, 0), epoch_start + DAY);
+    }
+
+    #[test]
+    fn test_reveals_in_one_window_are_ordered_by_commit_time() {
+        let window = 10 * DAY + 2 * HOUR;
+        // Committed four and three hours before the window opened, but revealed the other way round
+        let early_commit_late_reveal = revealed(7, window - 4 * HOUR, window + 50 * 60);
+        let late_commit_early_reveal = revealed(3, window - 3 * HOUR, window + 5 * 60);
+        // Committed first of all but revealed in the next window
+        let next_window = revealed(1, window - 5 * HOUR, window + HOUR + 1);
+
+        let mut pending = vec![next_window, late_commit_early_reveal, early_commit_late_reveal];
+        order_revealed_requests(&mut pending);
+        let order: Vec<u64> = pending.iter().map(|request| request.commitment_index).collect();
+        assert_eq!(order, vec![7, 3, 1]);
+
+        // Nothing executes while the window is open; then the whole window is ready at once
+        assert_eq!(closed_window_request_count(&pending, window + HOUR - 1), 0);
+        assert_eq!(closed_window_request_count(&pending, window + HOUR), 2);
+        assert_eq!(closed_window_request_count(&pending, window + 2 * HOUR), 3);
+    }
+
+    #[test]
+    fn test_commit_order_wins_over_tranche_weight_and_size() {
+        let window_end = 10 * DAY + HOUR;
+        let mut early_equity = request(4, 1_000, 0);
+        early_equity.timestamp = window_end - HOUR;
+        let mut late_senior = request(0, 50_000_000, 0);
+        late_senior.timestamp = window_end - HOUR + 1;
+        for (commitment_index, request) in [&mut early_equity, &mut late_senior].into_iter().enumerate() {
+            request.commitment_index = commitment_index as u64;
+            request.reveal_window_end = window_end;
+        }
+
+        let mut pending = vec![late_senior, early_equity];
+        rescore_pending_requests(&mut pending, &DEFAULT_TRANCHE_WEIGHTS);
+        assert!(pending[0].priority_score > pending[1].priority_score);
+        order_revealed_requests(&mut pending);
+        assert_eq!(pending[0].tranche_index, 4);
+    }
+
+    #[test]
+    fn test_every_tranche_type_gets_a_positive_weight() {
+        assert!(validate_tranche_weights(&DEFAULT_TRANCHE_WEIGHTS).is_ok());
+        for tranche_index in 0..MAX_TRANCHES as u8 {
+            assert!(calculate_priority_score(0, 0, tranche_index, 0, &DEFAULT_TRANCHE_WEIGHTS) > 0);
+        }
+
+        assert!(validate_tranche_weights(&[1_000, 500, 100]).is_err());
+        assert!(validate_tranche_weights(&[1_000, 500, 100, 0, 250]).is_err());
+    }
+
+    #[test]
+    fn test_reconfiguring_weights_reorders_the_queue() {
+        let window_end = 10 * DAY + HOUR;
+        let mut senior = request(0, 1_000, 0);
+        let mut equity = request(4, 1_000, 0);
+        for (commitment_index, request) in [&mut senior, &mut equity].into_iter().enumerate() {
+            request.commitment_index = commitment_index as u64;
+            request.timestamp = window_end - HOUR;
+            request.reveal_window_end = window_end;
+        }
+
+        let mut pending = vec![equity, senior];
+        rescore_pending_requests(&mut pending, &DEFAULT_TRANCHE_WEIGHTS);
+        order_revealed_requests(&mut pending);
+        assert_eq!(pending[0].tranche_index, 0);
+
+        rescore_pending_requests(&mut pending, &[100, 500, 100, 750, 2_000]);
+        order_revealed_requests(&mut pending);
+        assert_eq!(pending[0].tranche_index, 4);
+        assert_eq!(pending[0].priority_score, HOUR as u64 + 2_000 + 1);
+    }
+
+    const NOW: i64 = 1_700_000_000;
+
+    /// Precompile instruction the quoting transaction would carry for `forecast`
+    fn sign_forecast(signing_key: &ed25519_dalek::SigningKey, vault: &Pubkey, forecast: &NavForecast) -> Instruction {
+        use ed25519_dalek::Signer;
+        let digest = forecast_digest(vault, forecast);
+        let signer = Pubkey::new_from_array(signing_key.verifying_key().to_bytes());
+        ed25519_instruction::new_ed25519_instruction(&signer, &signing_key.sign(&digest).to_bytes(), &digest)
+    }
+
+    fn forecast(adjustment_bps: i16, issued_at: i64) -> NavForecast {
+        NavForecast { tranche_index: 1, adjustment_bps, confidence_score: 90, issued_at }
+    }
+
+    #[test]
+    fn test_signed_forecast_is_applied() {
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&[7; 32]);
+        let authority = Pubkey::new_from_array(signing_key.verifying_key().to_bytes());
+        let vault = Pubkey::new_unique();
+
+        let forecast = forecast(250, NOW - 60);
+        let signature = sign_forecast(&signing_key, &vault, &forecast);
+        let applied = verified_forecast(Some(&forecast), Some(&signature), &authority, &vault, 1, NOW, 0).unwrap();
+        assert_eq!(applied.confidence_score, 90);
+        assert_eq!(apply_forecast_adjustment(1_000_000, applied.adjustment_bps), 1_025_000);
+    }
+
+    #[test]
+    fn test_unsigned_or_stale_forecasts_fall_back_to_base_nav() {
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&[7; 32]);
+        let authority = Pubkey::new_from_array(signing_key.verifying_key().to_bytes());
+        let vault = Pubkey::new_unique();
+
+        // No forecast at all
+        let fresh = forecast(250, NOW);
+        let signature = sign_forecast(&signing_key, &vault, &fresh);
+        assert_eq!(verified_forecast(None, Some(&signature), &authority, &vault, 1, NOW, 0), None);
+
+        // Unsigned, or signed by someone else
+        assert_eq!(verified_forecast(Some(&fresh), None, &authority, &vault, 1, NOW, 0), None);
+        let spoofer = ed25519_dalek::SigningKey::from_bytes(&[8; 32]);
+        let spoofed = sign_forecast(&spoofer, &vault, &fresh);
+        assert_eq!(verified_forecast(Some(&fresh), Some(&spoofed), &authority, &vault, 1, NOW, 0), None);
+
+        // Validly signed, but tampered with, replayed for another vault or tranche, or stale
+        let mut tampered = fresh.clone();
+        tampered.adjustment_bps = 2_500;
+        assert_eq!(verified_forecast(Some(&tampered), Some(&signature), &authority, &vault, 1, NOW, 0), None);
+        assert_eq!(verified_forecast(Some(&fresh), Some(&signature), &authority, &Pubkey::new_unique(), 1, NOW, 0), None);
+        assert_eq!(verified_forecast(Some(&fresh), Some(&signature), &authority, &vault, 2, NOW, 0), None);
+        let stale = forecast(250, NOW - DEFAULT_MAX_FORECAST_STALENESS - 1);
+        let stale_signature = sign_forecast(&signing_key, &vault, &stale);
+        assert_eq!(verified_forecast(Some(&stale), Some(&stale_signature), &authority, &vault, 1, NOW, 0), None);
+        assert!(verified_forecast(
+            Some(&stale),
+            Some(&stale_signature),
+            &authority,
+            &vault,
+            1,
+            NOW,
+            2 * DEFAULT_MAX_FORECAST_STALENESS
+        )
+        .is_some());
+
+        // Without a configured authority nothing is trusted
+        assert_eq!(verified_forecast(Some(&fresh), Some(&signature), &Pubkey::default(), &vault, 1, NOW, 0), None);
+
+        // Ignored forecasts leave the base NAV untouched
+        assert_eq!(apply_forecast_adjustment(1_000_000, 0), 1_000_000);
+    }
+
+    #[test]
+    fn test_reveal_accepts_v3_and_legacy_v2_commitments() {
+        let user = Pubkey::new_unique();
+        let v3 = compute_commitment_hash(1_000, 990, 7, user, 100).unwrap();
+        let v2 = compute_legacy_commitment_hash(1_000, 990, 7, user, 100);
+        assert_ne!(v3, v2);
+
+        assert!(commitment_matches(&v3, 1_000, 990, 7, user, 100).unwrap());
+        assert!(commitment_matches(&v2, 1_000, 990, 7, user, 100).unwrap());
+        // Either encoding still binds every field
+        assert!(!commitment_matches(&v2, 1_001, 990, 7, user, 100).unwrap());
+        assert!(!commitment_matches(&v3, 1_000, 990, 8, user, 100).unwrap());
+    }
 }
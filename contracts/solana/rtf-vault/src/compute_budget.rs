@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use crate::{RedemptionRequest, VaultConfig};
+
+/// Compute-unit budgeting for redemption batches
+/// A transaction that runs out of compute units fails as a whole, so `process_redemptions`
+/// checks the units actually left (`sol_remaining_compute_units`) before each redemption
+/// and stops the batch when the next one's estimated cost might not fit. The remaining
+/// requests stay queued for the next batch. Estimates are deliberately pessimistic:
+/// stopping one redemption early is cheap, overrunning the limit loses the whole batch.
+
+/// Units held back for the work after the loop: the batch event and invariant checks
+pub const BATCH_EPILOGUE_CU: u64 = 15_000;
+/// Queue bookkeeping and the commitment hash check of one redemption
+pub const REDEMPTION_BASE_CU: u32 = 10_000;
+/// One SPL token CPI (burn or transfer)
+pub const TOKEN_CPI_CU: u32 = 6_500;
+
+/// Estimated compute units to execute one redemption: burning the tranche shares and
+/// paying out the assets, plus returning the bond when the request posted one
+pub fn estimate_redemption_cu(request: &RedemptionRequest) -> u32 {
+    let token_cpis = if request.bonding_amount > 0 { 3 } else { 2 };
+    REDEMPTION_BASE_CU + token_cpis * TOKEN_CPI_CU
+}
+
+/// Compute units left in the current transaction
+pub fn remaining_compute_units() -> u64 {
+    anchor_lang::solana_program::compute_units::sol_remaining_compute_units()
+}
+
+/// Compute units a batch may still spend, measured against the transaction's meter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComputeBudget {
+    /// Units remaining when the batch started
+    pub started_with: u64,
+    /// Most units the batch may spend; `u64::MAX` when only the transaction limit applies
+    pub cap: u64,
+}
+
+impl ComputeBudget {
+    /// Budget capped by the vault configuration (0 = no cap), starting at `remaining`
+    pub fn from_config(config: &VaultConfig, remaining: u64) -> Self {
+        let cap = match config.max_redemption_compute_units {
+            0 => u64::MAX,
+            cap => cap as u64,
+        };
+        Self { started_with: remaining, cap }
+    }
+
+    /// Whether work estimated at `units` fits, given the units still `remaining`
+    pub fn fits(&self, remaining: u64, units: u32) -> bool {
+        let spent = self.started_with.saturating_sub(remaining);
+        let units = units as u64;
+        remaining >= units.saturating_add(BATCH_EPILOGUE_CU) && spent.saturating_add(units) <= self.cap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RedemptionStatus;
+
+    fn request(bonding_amount: u64) -> RedemptionRequest {
+        RedemptionRequest {
+            user: Pubkey::default(),
+            tranche_index: 0,
+            shares_amount: 1_000_000,
+            expected_assets: 1_000_000,
+            request_timestamp: 0,
+            processing_slot: 0,
+            status: RedemptionStatus::Pending,
+            commitment_hash: [0; 32],
+            bonding_amount,
+            reveal_deadline: 0,
+            holding_since: 0,
+        }
+    }
+
+    #[test]
+    fn test_redemption_must_fit_in_the_units_left() {
+        let plain = estimate_redemption_cu(&request(0));
+        let bonded = estimate_redemption_cu(&request(500));
+        assert!(bonded > plain);
+
+        // Uncapped: only the transaction's meter applies
+        let remaining = BATCH_EPILOGUE_CU + plain as u64;
+        let budget = ComputeBudget { started_with: 200_000, cap: u64::MAX };
+        assert!(budget.fits(remaining, plain));
+        assert!(!budget.fits(remaining, bonded));
+        assert!(!budget.fits(remaining - 1, plain));
+    }
+
+    #[test]
+    fn test_configured_cap_limits_units_spent_by_the_batch() {
+        let plain = estimate_redemption_cu(&request(0));
+        let budget = ComputeBudget { started_with: 1_400_000, cap: 2 * plain as u64 };
+
+        assert!(budget.fits(1_400_000, plain));
+        // One redemption's worth already spent leaves room for exactly one more
+        assert!(budget.fits(1_400_000 - plain as u64, plain));
+        assert!(!budget.fits(1_400_000 - plain as u64 - 1, plain));
+    }
+}
@@ -0,0 +1,227 @@
+use anchor_lang::prelude::*;
+use crate::RTFError;
+
+/// PRD: "Flashloan-resistance via proof-of-holding (duration > M blocks)"
+/// Every deposit appends a lot of the shares it minted to the depositor's per-tranche
+/// ledger. Redemptions draw on lots oldest first, so a redemption is only as recent as
+/// the youngest lot it consumes: topping up never makes older shares ineligible, while
+/// shares minted moments ago cannot be redeemed. Shares the ledger has no lot for (e.g.
+/// received by transfer) cannot prove a holding period and are not redeemable.
+/// A queued redemption reserves its lots; they are returned with `restore_lot` when the
+/// request is revealed (and re-reserved for the revealed amount), cancelled or expires.
+
+pub const DEPOSIT_LEDGER_SEED: &[u8] = b"deposit_ledger";
+pub const MAX_DEPOSIT_LOTS: usize = 32;
+pub const MIN_HOLDING_DURATION: i64 = 3_600; // 1 hour
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct DepositLot {
+    pub shares: u64,
+    pub deposited_at: i64,
+}
+
+/// Deposit lots of one user in one tranche, oldest first
+/// PDA seeds: ["deposit_ledger", vault, user, tranche_index]
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct DepositLedger {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub tranche_index: u8,
+    #[max_len(MAX_DEPOSIT_LOTS)]
+    pub lots: Vec<DepositLot>,
+    pub bump: u8,
+}
+
+impl DepositLedger {
+    pub fn total_shares(&self) -> u64 {
+        self.lots.iter().fold(0u64, |total, lot| total.saturating_add(lot.shares))
+    }
+
+    /// Append a lot. Once the ledger is full the newest lot absorbs the deposit and takes
+    /// its timestamp, which can make shares look younger but never older.
+    pub fn record_deposit(&mut self, shares: u64, deposited_at: i64) -> Result<()> {
+        if shares == 0 {
+            return Ok(());
+        }
+        match self.lots.last_mut() {
+            Some(newest) if self.lots.len() >= MAX_DEPOSIT_LOTS => {
+                newest.shares = newest.shares.checked_add(shares).ok_or(RTFError::MathOverflow)?;
+                newest.deposited_at = newest.deposited_at.max(deposited_at);
+            }
+            _ => self.lots.push(DepositLot { shares, deposited_at }),
+        }
+        Ok(())
+    }
+
+    /// Deposit time of the youngest lot a FIFO redemption of `shares` draws on
+    pub fn fifo_deposit_timestamp(&self, shares: u64) -> Result<i64> {
+        require!(shares > 0, RTFError::InsufficientShares);
+        let mut remaining = shares;
+        for lot in &self.lots {
+            if remaining <= lot.shares {
+                return Ok(lot.deposited_at);
+            }
+            remaining -= lot.shares;
+        }
+        err!(RTFError::InsufficientHoldingDuration)
+    }
+
+    /// Check `shares` have been held long enough and are out of the tranche lock,
+    /// returning the FIFO deposit timestamp they were checked against
+    pub fn verify_holding(&self, shares: u64, lock_period: u32, now: i64) -> Result<i64> {
+        let deposited_at = self.fifo_deposit_timestamp(shares)?;
+        require!(
+            now.saturating_sub(deposited_at) >= MIN_HOLDING_DURATION,
+            RTFError::InsufficientHoldingDuration
+        );
+        require!(
+            now >= deposited_at.saturating_add(lock_period as i64),
+            RTFError::SharesStillLocked
+        );
+        Ok(deposited_at)
+    }
+
+    /// Return `shares` reserved by a redemption as a lot dated `deposited_at`, the FIFO
+    /// timestamp they were reserved under. Lots stay oldest first; once the ledger is full
+    /// the lot merges into its younger neighbour, which never makes shares look older.
+    pub fn restore_lot(&mut self, shares: u64, deposited_at: i64) -> Result<()> {
+        if shares == 0 {
+            return Ok(());
+        }
+        let position = self.lots.iter()
+            .position(|lot| lot.deposited_at > deposited_at)
+            .unwrap_or(self.lots.len());
+        if self.lots.len() < MAX_DEPOSIT_LOTS {
+            self.lots.insert(position, DepositLot { shares, deposited_at });
+            return Ok(());
+        }
+        let neighbour = &mut self.lots[position.min(MAX_DEPOSIT_LOTS - 1)];
+        neighbour.shares = neighbour.shares.checked_add(shares).ok_or(RTFError::MathOverflow)?;
+        neighbour.deposited_at = neighbour.deposited_at.max(deposited_at);
+        Ok(())
+    }
+
+    /// Remove `shares` from the oldest lots
+    pub fn consume_fifo(&mut self, shares: u64) -> Result<()> {
+        require!(shares <= self.total_shares(), RTFError::InsufficientShares);
+        let mut remaining = shares;
+        let mut emptied = 0;
+        for lot in self.lots.iter_mut() {
+            if remaining == 0 {
+                break;
+            }
+            let taken = remaining.min(lot.shares);
+            lot.shares -= taken;
+            remaining -= taken;
+            if lot.shares == 0 {
+                emptied += 1;
+            }
+        }
+        self.lots.drain(..emptied);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+    const DAY: i64 = 86_400;
+
+    fn ledger(lots: &[(u64, i64)]) -> DepositLedger {
+        let mut ledger = DepositLedger {
+            vault: Pubkey::default(),
+            user: Pubkey::default(),
+            tranche_index: 0,
+            lots: Vec::new(),
+            bump: 0,
+        };
+        for (shares, deposited_at) in lots {
+            ledger.record_deposit(*shares, *deposited_at).unwrap();
+        }
+        ledger
+    }
+
+    #[test]
+    fn test_recent_top_up_does_not_block_older_lots() {
+        // 1_000 shares held for a week, 500 topped up a minute ago
+        let mut ledger = ledger(&[(1_000, NOW - 7 * DAY), (500, NOW - 60)]);
+
+        assert_eq!(ledger.verify_holding(1_000, 0, NOW).unwrap(), NOW - 7 * DAY);
+        // Reaching into the top-up makes the redemption as young as the top-up
+        assert_eq!(
+            ledger.verify_holding(1_001, 0, NOW),
+            Err(RTFError::InsufficientHoldingDuration.into())
+        );
+
+        // Partial redemptions keep consuming the oldest lot first
+        ledger.consume_fifo(600).unwrap();
+        assert_eq!(ledger.lots, vec![
+            DepositLot { shares: 400, deposited_at: NOW - 7 * DAY },
+            DepositLot { shares: 500, deposited_at: NOW - 60 },
+        ]);
+        assert!(ledger.verify_holding(400, 0, NOW).is_ok());
+        ledger.consume_fifo(400).unwrap();
+        assert_eq!(ledger.lots.len(), 1);
+        assert!(ledger.verify_holding(1, 0, NOW).is_err());
+    }
+
+    #[test]
+    fn test_fully_recent_position_is_blocked() {
+        let ledger = ledger(&[(1_000, NOW - 60), (1_000, NOW - 30)]);
+        assert_eq!(
+            ledger.verify_holding(1, 0, NOW),
+            Err(RTFError::InsufficientHoldingDuration.into())
+        );
+        // Once the holding period passes only the tranche lock applies
+        assert!(ledger.verify_holding(1_000, 0, NOW + MIN_HOLDING_DURATION).is_ok());
+        assert_eq!(
+            ledger.verify_holding(1_000, 7 * DAY as u32, NOW + MIN_HOLDING_DURATION),
+            Err(RTFError::SharesStillLocked.into())
+        );
+    }
+
+    #[test]
+    fn test_untracked_shares_and_full_ledger() {
+        let mut ledger = ledger(&[(1_000, NOW - 7 * DAY)]);
+        // Shares beyond the recorded lots were never deposited by this user
+        assert!(ledger.fifo_deposit_timestamp(1_001).is_err());
+        assert!(ledger.consume_fifo(1_001).is_err());
+
+        for i in 1..MAX_DEPOSIT_LOTS as i64 + 5 {
+            ledger.record_deposit(10, NOW - 7 * DAY + i).unwrap();
+        }
+        assert_eq!(ledger.lots.len(), MAX_DEPOSIT_LOTS);
+        assert_eq!(ledger.total_shares(), 1_000 + 10 * (MAX_DEPOSIT_LOTS as u64 + 4));
+        // Overflow deposits merged into the newest lot under the latest timestamp
+        assert_eq!(ledger.lots.last().unwrap().deposited_at, NOW - 7 * DAY + MAX_DEPOSIT_LOTS as i64 + 4);
+    }
+
+    #[test]
+    fn test_restored_lots_keep_fifo_order() {
+        let mut ledger = ledger(&[(1_000, NOW - 7 * DAY), (500, NOW - 60)]);
+
+        // A redemption reserves 1_000 week-old shares and is then cancelled
+        let reserved_at = ledger.verify_holding(1_000, 0, NOW).unwrap();
+        ledger.consume_fifo(1_000).unwrap();
+        assert!(ledger.verify_holding(1, 0, NOW).is_err());
+        ledger.restore_lot(1_000, reserved_at).unwrap();
+        assert_eq!(ledger.lots, vec![
+            DepositLot { shares: 1_000, deposited_at: NOW - 7 * DAY },
+            DepositLot { shares: 500, deposited_at: NOW - 60 },
+        ]);
+        assert!(ledger.verify_holding(1_000, 0, NOW).is_ok());
+
+        // On a full ledger the restored shares join the next younger lot
+        let mut full = ledger(&[(1_000, NOW - 7 * DAY)]);
+        for i in 1..MAX_DEPOSIT_LOTS as i64 {
+            full.record_deposit(10, NOW - DAY + i).unwrap();
+        }
+        full.restore_lot(100, NOW - 2 * DAY).unwrap();
+        assert_eq!(full.lots.len(), MAX_DEPOSIT_LOTS);
+        assert_eq!(full.lots[1], DepositLot { shares: 110, deposited_at: NOW - DAY + 1 });
+        assert_eq!(full.total_shares(), 1_000 + 10 * (MAX_DEPOSIT_LOTS as u64 - 1) + 100);
+    }
+}
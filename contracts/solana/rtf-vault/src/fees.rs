@@ -0,0 +1,185 @@
+use anchor_lang::prelude::*;
+use fixed_point::{div_round, Rounding};
+use crate::{RTFError, Tranche, VaultAccount, nav_scale};
+
+/// Management and performance fees
+/// Fees are taken out of the tranche NAV as they accrue and held in `accrued_fees`
+/// (underlying asset units) until `collect_fees` pays them to the vault's `fee_collector`.
+/// - management: `fee_rate` bps per year of the tranche's value, pro rata by elapsed time
+/// - performance: `performance_fee_rate` bps of NAV above the tranche's high-water mark,
+///   the highest post-fee NAV seen at an accrual. NAV recovering lost ground after a
+///   drawdown is not charged again; only new highs are.
+/// The NAV reduction is rounded down and the fee is the value of that reduction, so
+/// holders are never charged more than the collector receives.
+/// Fees accrue at every instruction that prices shares or replaces NAVs, so deposits and
+/// redemptions never trade at a NAV that still includes fees owed.
+
+pub const SECONDS_PER_YEAR: i64 = 31_536_000;
+const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Accrue every tranche's fees up to `now`, returning the newly accrued total
+pub fn accrue_fees(vault: &mut VaultAccount, now: i64) -> Result<u64> {
+    let nav_decimals = vault.config.nav_decimals;
+    let mut accrued = 0u64;
+    for tranche in vault.tranches.iter_mut() {
+        let fee = accrue_tranche_fees(tranche, nav_decimals, now)?;
+        accrued = accrued.checked_add(fee).ok_or(RTFError::MathOverflow)?;
+    }
+    Ok(accrued)
+}
+
+/// Reapply fees to tranche NAVs just taken from an oracle report, then accrue up to `now`
+/// The oracle values the vault's assets gross, including fees not yet collected, so each of
+/// the first `repriced` tranches has its outstanding `accrued_fees` taken off its new NAV
+/// first; otherwise a NAV update would hand fees already charged back to holders.
+pub fn accrue_fees_after_oracle_nav(vault: &mut VaultAccount, repriced: usize, now: i64) -> Result<u64> {
+    let scale = nav_scale(vault.config.nav_decimals)? as i128;
+    for tranche in vault.tranches.iter_mut().take(repriced).filter(|tranche| tranche.total_supply > 0) {
+        let outstanding = div_round(
+            (tranche.accrued_fees as i128).checked_mul(scale).ok_or(RTFError::MathOverflow)?,
+            tranche.total_supply as i128,
+            Rounding::Down,
+        ).map_err(|_| RTFError::MathOverflow)?;
+        tranche.nav_per_share = (tranche.nav_per_share as i128 - outstanding).max(0) as u64;
+    }
+    accrue_fees(vault, now)
+}
+
+/// Accrue one tranche's fees up to `now`, reducing its NAV by the fee
+pub fn accrue_tranche_fees(tranche: &mut Tranche, nav_decimals: u8, now: i64) -> Result<u64> {
+    let scale = nav_scale(nav_decimals)? as i128;
+    let elapsed = now.saturating_sub(tranche.last_fee_accrual);
+    if elapsed <= 0 {
+        return Ok(0);
+    }
+
+    let nav = tranche.nav_per_share as i128;
+    // Per-share fees in NAV units; both round down
+    let management = div_round(
+        nav.checked_mul(tranche.fee_rate as i128 * elapsed as i128).ok_or(RTFError::MathOverflow)?,
+        BPS_DENOMINATOR * SECONDS_PER_YEAR as i128,
+        Rounding::Down,
+    ).map_err(|_| RTFError::MathOverflow)?;
+    let gain = (nav - tranche.high_water_mark as i128).max(0);
+    let performance = div_round(
+        gain * tranche.performance_fee_rate as i128,
+        BPS_DENOMINATOR,
+        Rounding::Down,
+    ).map_err(|_| RTFError::MathOverflow)?;
+
+    let nav_reduction = (management + performance).min(nav);
+    let fee = div_round(
+        nav_reduction.checked_mul(tranche.total_supply as i128).ok_or(RTFError::MathOverflow)?,
+        scale,
+        Rounding::Down,
+    ).map_err(|_| RTFError::MathOverflow)?;
+    let fee = u64::try_from(fee).map_err(|_| RTFError::MathOverflow)?;
+
+    // An empty tranche has no one to charge, so its NAV is left alone
+    if tranche.total_supply > 0 {
+        tranche.nav_per_share -= nav_reduction as u64;
+        tranche.accrued_fees = tranche.accrued_fees.checked_add(fee).ok_or(RTFError::MathOverflow)?;
+    }
+    tranche.high_water_mark = tranche.high_water_mark.max(tranche.nav_per_share);
+    tranche.last_fee_accrual = now;
+    Ok(fee)
+}
+
+/// Zero every tranche's accrued fees, returning the total to pay out
+pub fn take_accrued_fees(vault: &mut VaultAccount) -> Result<u64> {
+    let mut total = 0u64;
+    for tranche in vault.tranches.iter_mut() {
+        total = total.checked_add(tranche.accrued_fees).ok_or(RTFError::MathOverflow)?;
+        tranche.accrued_fees = 0;
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START: i64 = 1_700_000_000;
+
+    /// 1_000 shares at NAV 1.0, 2% management and 20% performance fee
+    fn test_tranche() -> Tranche {
+        Tranche {
+            tranche_type: crate::TrancheType::Senior,
+            mint: Pubkey::default(),
+            total_supply: 1_000_000_000,
+            nav_per_share: 1_000_000,
+            fee_rate: 200,
+            min_deposit: 0,
+            max_deposit: 0,
+            lock_period: 0,
+            yield_rate: 0,
+            last_yield_update: START,
+            max_redeemable_per_epoch: 0,
+            redemption_epoch: 0,
+            redeemed_in_epoch: 0,
+            performance_fee_rate: 2_000,
+            last_fee_accrual: START,
+            high_water_mark: 1_000_000,
+            accrued_fees: 0,
+            lp_yield: crate::LpYieldModel::default(),
+        }
+    }
+
+    #[test]
+    fn test_management_fee_accrues_with_time() {
+        let mut tranche = test_tranche();
+        assert_eq!(accrue_tranche_fees(&mut tranche, 6, START).unwrap(), 0);
+
+        // Half a year of 2% a year on 1_000 tokens is 10 tokens, 0.01 per share
+        let fee = accrue_tranche_fees(&mut tranche, 6, START + SECONDS_PER_YEAR / 2).unwrap();
+        assert_eq!(fee, 10_000_000);
+        assert_eq!(tranche.nav_per_share, 990_000);
+        assert_eq!(tranche.accrued_fees, 10_000_000);
+
+        // A second half year is charged on the reduced NAV
+        let fee = accrue_tranche_fees(&mut tranche, 6, START + SECONDS_PER_YEAR).unwrap();
+        assert_eq!(fee, 9_900_000);
+        assert_eq!(tranche.accrued_fees, 19_900_000);
+
+        // Accruing twice at the same instant charges nothing extra
+        assert_eq!(accrue_tranche_fees(&mut tranche, 6, START + SECONDS_PER_YEAR).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_performance_fee_only_above_high_water_mark() {
+        let mut tranche = test_tranche();
+        tranche.fee_rate = 0;
+
+        // Gain from 1.00 to 1.10: 20% of the 0.10 above the mark, 0.02 per share
+        tranche.nav_per_share = 1_100_000;
+        assert_eq!(accrue_tranche_fees(&mut tranche, 6, START + 1).unwrap(), 20_000_000);
+        assert_eq!(tranche.nav_per_share, 1_080_000);
+        assert_eq!(tranche.high_water_mark, 1_080_000);
+
+        // Drawdown to 0.90: nothing charged and the mark holds
+        tranche.nav_per_share = 900_000;
+        assert_eq!(accrue_tranche_fees(&mut tranche, 6, START + 2).unwrap(), 0);
+        assert_eq!(tranche.high_water_mark, 1_080_000);
+
+        // Recovery to 1.05 is still below the prior peak
+        tranche.nav_per_share = 1_050_000;
+        assert_eq!(accrue_tranche_fees(&mut tranche, 6, START + 3).unwrap(), 0);
+        assert_eq!(tranche.nav_per_share, 1_050_000);
+        assert_eq!(tranche.high_water_mark, 1_080_000);
+
+        // New high of 1.13: only the 0.05 above the peak is charged, 0.01 per share
+        tranche.nav_per_share = 1_130_000;
+        assert_eq!(accrue_tranche_fees(&mut tranche, 6, START + 4).unwrap(), 10_000_000);
+        assert_eq!(tranche.nav_per_share, 1_120_000);
+        assert_eq!(tranche.high_water_mark, 1_120_000);
+
+        // A 5% gain at 9 decimals
+        let mut nine_decimals = test_tranche();
+        nine_decimals.fee_rate = 0;
+        nine_decimals.total_supply = 1_000_000_000_000;
+        nine_decimals.nav_per_share = 1_050_000_000;
+        nine_decimals.high_water_mark = 1_000_000_000;
+        assert_eq!(accrue_tranche_fees(&mut nine_decimals, 9, START + 1).unwrap(), 10_000_000_000);
+        assert_eq!(nine_decimals.nav_per_share, 1_040_000_000);
+    }
+}
@@ -83,6 +83,7 @@ pub fn deposit_with_advanced_compliance(
     let shares_to_mint = calculate_shares_with_advanced_pricing(
         amount,
         current_nav.nav_per_share,
+        vault.config.nav_decimals,
         vault_utilization,
         tranche.waterfall_priority,
         get_market_volatility(&ctx.accounts.oracle_account)?,
@@ -305,12 +306,13 @@ fn verify_advanced_compliance(
 fn calculate_shares_with_advanced_pricing(
     amount: u64,
     nav_per_share: u64,
+    nav_decimals: u8,
     vault_utilization: u64,
     waterfall_priority: u8,
     market_volatility: u64,
 ) -> Result<u64> {
     // Advanced pricing model with multiple factors
-    let base_shares = (amount * 1_000_000) / nav_per_share;
+    let base_shares = calculate_shares_for_deposit(amount, nav_per_share, nav_decimals)?;
     
     // Apply utilization adjustment
     let utilization_factor = if vault_utilization > 8000 { // >80%
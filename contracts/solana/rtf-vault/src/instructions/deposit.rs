@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer, MintTo};
 use anchor_spl::associated_token::AssociatedToken;
+use domain_tags::DomainTag;
 use crate::{VaultAccount, RTFError, DepositMade, calculate_shares_for_deposit, verify_compliance_proof};
 
 /// Advanced deposit instruction with compliance checks and MEV protection
@@ -48,7 +49,7 @@ pub fn deposit_with_compliance(
 
     // Calculate shares with dynamic pricing
     let current_nav = get_current_nav_from_oracle(&ctx.accounts.oracle_account)?;
-    let shares_to_mint = calculate_shares_for_deposit(amount, current_nav)?;
+    let shares_to_mint = calculate_shares_for_deposit(amount, current_nav, vault.config.nav_decimals)?;
     
     require!(
         shares_to_mint >= min_shares_out,
@@ -253,8 +254,8 @@ fn update_performance_metrics(vault: &mut VaultAccount, amount: u64, shares: u64
 fn calculate_compliance_hash(proof: &[u8]) -> [u8; 32] {
     use sha2::{Sha256, Digest};
     let mut hasher = Sha256::new();
+    hasher.update(DomainTag::ComplianceProof.as_bytes());
     hasher.update(proof);
-    hasher.update(b"RTF_COMPLIANCE_PROOF");
     hasher.finalize().into()
 }
 
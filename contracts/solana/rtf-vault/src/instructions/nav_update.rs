@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use crate::{VaultAccount, RTFError, NAVUpdated, verify_nav_zk_proof, calculate_nav_drift};
+use ed25519_instruction::load_preceding_instruction;
 
 /// Advanced NAV update with zkProof verification and cross-chain anchoring
 pub fn update_nav_with_zk_proof(
@@ -9,6 +10,9 @@ pub fn update_nav_with_zk_proof(
     cross_chain_proofs: CrossChainProofs,
     post_quantum_signature: [u8; 128],
 ) -> Result<()> {
+    let vault_key = ctx.accounts.vault.key();
+    let ed25519_instruction = load_preceding_instruction(&ctx.accounts.instructions_sysvar)
+        .map_err(|_| RTFError::InvalidAttestationSignature)?;
     let vault = &mut ctx.accounts.vault;
     let clock = Clock::get()?;
 
@@ -19,7 +23,7 @@ pub fn update_nav_with_zk_proof(
     );
 
     // Verify zkProof of NAV computation
-    verify_nav_zk_proof(&nav_data, &zk_proof)?;
+    verify_nav_zk_proof(&nav_data, &zk_proof, &vault.config.nav_verifier, &vault_key, &ed25519_instruction)?;
 
     // Verify cross-chain state consistency
     verify_cross_chain_proofs(&cross_chain_proofs, &nav_data)?;
@@ -177,6 +181,10 @@ pub struct UpdateNAVWithZKProof<'info> {
     
     /// CHECK: Cross-chain message relayer
     pub cross_chain_relayer: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar holding the Ed25519 precompile instruction
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
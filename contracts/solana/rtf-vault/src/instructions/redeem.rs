@@ -38,7 +38,7 @@ pub fn request_redemption_advanced(
 
     // Calculate assets to return based on current NAV
     let current_nav = get_current_nav_from_oracle(&ctx.accounts.oracle_account)?;
-    let base_assets = calculate_assets_for_redemption(shares_amount, current_nav)?;
+    let base_assets = calculate_assets_for_redemption(shares_amount, current_nav, vault.config.nav_decimals)?;
 
     // Apply redemption fees and slippage protection
     let (final_assets, fee_amount) = calculate_redemption_fee_and_slippage(
@@ -0,0 +1,215 @@
+use anchor_lang::prelude::*;
+use crate::{RTFError, VaultAccount, nav_scale};
+
+/// Accounting invariants that every mutating instruction must leave intact
+/// - liabilities never exceed assets
+/// - a tranche with shares outstanding has a non-zero NAV
+/// - the tranches' value plus fees awaiting collection matches net assets
+///   (`sum(nav_per_share * total_supply) + accrued_fees ≈ total_assets - total_liabilities`)
+/// - the redemption queue head never passes its tail and the queue stays within bounds
+/// - no tranche has redeemed more than its per-epoch cap
+/// Checking costs compute, so instructions only run it when the program is built with
+/// the `check-invariants` feature; tests and off-chain tooling can call it directly.
+
+/// Slack allowed between the tranches' value and net assets, in basis points of net
+/// assets. Share conversions round in the vault's favour and the oracle reports total
+/// assets and tranche NAVs separately, so the two sides are never exactly equal.
+pub const NET_ASSET_TOLERANCE_BPS: u64 = 10;
+
+/// Check every accounting invariant, failing with the first one violated
+pub fn check_invariants(vault: &VaultAccount) -> Result<()> {
+    require!(
+        vault.total_liabilities <= vault.total_assets,
+        RTFError::InvariantLiabilitiesExceedAssets
+    );
+    for tranche in &vault.tranches {
+        require!(
+            tranche.total_supply == 0 || tranche.nav_per_share > 0,
+            RTFError::InvariantTrancheNavZero
+        );
+    }
+    check_net_assets(vault)?;
+
+    let queue = &vault.redemption_queue;
+    require!(
+        queue.head <= queue.tail && queue.tail - queue.head <= queue.max_queue_size,
+        RTFError::InvariantRedemptionQueueCorrupted
+    );
+
+    for tranche in &vault.tranches {
+        require!(
+            tranche.max_redeemable_per_epoch == 0 || tranche.redeemed_in_epoch <= tranche.max_redeemable_per_epoch,
+            RTFError::InvariantEpochRedemptionCapExceeded
+        );
+    }
+    Ok(())
+}
+
+fn check_net_assets(vault: &VaultAccount) -> Result<()> {
+    let scale = nav_scale(vault.config.nav_decimals)? as u128;
+    let mut accounted = 0u128;
+    for tranche in &vault.tranches {
+        let value = tranche.nav_per_share as u128 * tranche.total_supply as u128 / scale;
+        accounted = accounted
+            .checked_add(value + tranche.accrued_fees as u128)
+            .ok_or(RTFError::MathOverflow)?;
+    }
+
+    let net_assets = (vault.total_assets - vault.total_liabilities) as u128;
+    // At least one unit per tranche for the per-tranche rounding above
+    let tolerance = (net_assets * NET_ASSET_TOLERANCE_BPS as u128 / 10_000).max(vault.tranches.len() as u128);
+    require!(
+        accounted.abs_diff(net_assets) <= tolerance,
+        RTFError::InvariantNetAssetsMismatch
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{accrue_fees, accrue_fees_after_oracle_nav, apply_oracle_nav, take_accrued_fees, RedemptionQueue, Tranche, TrancheType, VaultConfig, VaultStatus};
+
+    const START: i64 = 1_700_000_000;
+
+    fn test_tranche(total_supply: u64, nav_per_share: u64) -> Tranche {
+        Tranche {
+            tranche_type: TrancheType::Senior,
+            mint: Pubkey::default(),
+            total_supply,
+            nav_per_share,
+            fee_rate: 200,
+            min_deposit: 0,
+            max_deposit: 0,
+            lock_period: 0,
+            yield_rate: 0,
+            last_yield_update: START,
+            max_redeemable_per_epoch: 500_000_000,
+            redemption_epoch: 0,
+            redeemed_in_epoch: 0,
+            performance_fee_rate: 0,
+            last_fee_accrual: START,
+            high_water_mark: nav_per_share,
+            accrued_fees: 0,
+            lp_yield: crate::LpYieldModel::default(),
+        }
+    }
+
+    /// 1_000 senior shares at 1.05 and 500 junior shares at 1.10 backing 1_600 tokens
+    /// of assets and 0.05 of liabilities
+    fn test_vault() -> VaultAccount {
+        VaultAccount {
+            authority: Pubkey::default(),
+            config: VaultConfig {
+                underlying_mint: Pubkey::default(),
+                oracle_authority: Pubkey::default(),
+                operator: Pubkey::default(),
+                max_redemption_queue_size: 100,
+                redemption_processing_window: 0,
+                mev_protection_delay: 0,
+                max_nav_drift: 0,
+                max_stress_multiplier: 0,
+                nav_decimals: 6,
+                switchboard_feed: Pubkey::default(),
+                max_oracle_staleness: 0,
+                max_oracle_std_dev_bps: 0,
+                nav_verifier: Pubkey::default(),
+                fee_collector: Pubkey::default(),
+                emergency_pause_authority: Pubkey::default(),
+                max_redemption_compute_units: 0,
+                epoch_duration: 0,
+            },
+            total_assets: 1_600_050_000,
+            total_liabilities: 50_000,
+            nav_per_share: 1_066_666,
+            last_nav_update: START,
+            last_nav_data_hash: [0; 32],
+            epoch: 0,
+            status: VaultStatus::Active,
+            bump: 0,
+            tranches: vec![
+                test_tranche(1_000_000_000, 1_050_000),
+                test_tranche(500_000_000, 1_100_000),
+            ],
+            redemption_queue: RedemptionQueue {
+                head: 3,
+                tail: 5,
+                total_pending: 0,
+                max_queue_size: 100,
+                processing_window: 0,
+                mev_protection_delay: 0,
+                batch_size: 10,
+            },
+        }
+    }
+
+    #[test]
+    fn test_consistent_vault_passes_through_fee_accrual_and_collection() {
+        let mut vault = test_vault();
+        assert!(check_invariants(&vault).is_ok());
+
+        // Accrued fees leave the tranches but stay in the vault until collected
+        assert!(accrue_fees(&mut vault, START + 3 * 86_400 * 30).unwrap() > 0);
+        assert!(check_invariants(&vault).is_ok());
+
+        let collected = take_accrued_fees(&mut vault).unwrap();
+        vault.total_assets -= collected;
+        assert!(check_invariants(&vault).is_ok());
+    }
+
+    #[test]
+    fn test_oracle_nav_update_keeps_fees_already_charged() {
+        let mut vault = test_vault();
+        let now = START + 3 * 86_400 * 30;
+        accrue_fees(&mut vault, now).unwrap();
+        let net_navs: Vec<u64> = vault.tranches.iter().map(|tranche| tranche.nav_per_share).collect();
+
+        // The oracle reprices the same gross assets at the pre-fee NAVs
+        apply_oracle_nav(&mut vault, 1_600_050_000, &[1_050_000, 1_100_000]).unwrap();
+        assert_eq!(accrue_fees_after_oracle_nav(&mut vault, 2, now).unwrap(), 0);
+
+        let repriced_navs: Vec<u64> = vault.tranches.iter().map(|tranche| tranche.nav_per_share).collect();
+        for (repriced, net) in repriced_navs.iter().zip(&net_navs) {
+            assert!(net.abs_diff(*repriced) <= 1);
+        }
+        assert!(check_invariants(&vault).is_ok());
+    }
+
+    #[test]
+    fn test_corrupted_state_reports_the_violated_invariant() {
+        // Shares minted without the assets arriving
+        let mut vault = test_vault();
+        vault.tranches[0].total_supply += 100_000_000;
+        assert_eq!(check_invariants(&vault), Err(RTFError::InvariantNetAssetsMismatch.into()));
+
+        // Assets paid out without burning shares
+        let mut vault = test_vault();
+        vault.total_assets -= 10_000_000;
+        assert_eq!(check_invariants(&vault), Err(RTFError::InvariantNetAssetsMismatch.into()));
+
+        // Rounding-sized differences are tolerated
+        let mut vault = test_vault();
+        vault.total_assets += 1_000;
+        assert!(check_invariants(&vault).is_ok());
+
+        let mut vault = test_vault();
+        vault.total_liabilities = vault.total_assets + 1;
+        assert_eq!(check_invariants(&vault), Err(RTFError::InvariantLiabilitiesExceedAssets.into()));
+
+        let mut vault = test_vault();
+        vault.tranches[1].nav_per_share = 0;
+        assert_eq!(check_invariants(&vault), Err(RTFError::InvariantTrancheNavZero.into()));
+
+        let mut vault = test_vault();
+        vault.redemption_queue.head = vault.redemption_queue.tail + 1;
+        assert_eq!(check_invariants(&vault), Err(RTFError::InvariantRedemptionQueueCorrupted.into()));
+
+        let mut vault = test_vault();
+        vault.redemption_queue.tail = vault.redemption_queue.head + vault.redemption_queue.max_queue_size + 1;
+        assert_eq!(check_invariants(&vault), Err(RTFError::InvariantRedemptionQueueCorrupted.into()));
+
+        let mut vault = test_vault();
+        vault.tranches[0].redeemed_in_epoch = vault.tranches[0].max_redeemable_per_epoch + 1;
+        assert_eq!(check_invariants(&vault), Err(RTFError::InvariantEpochRedemptionCapExceeded.into()));
+    }
+}
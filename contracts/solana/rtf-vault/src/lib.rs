@@ -3,6 +3,8 @@ use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer, MintTo, Burn}
 use anchor_spl::token_2022::{self as token_2022, Token2022};
 use anchor_spl::associated_token::AssociatedToken;
 use switchboard_v2::AggregatorAccountData;
+use anchor_lang::solana_program::sysvar;
+use ed25519_instruction::load_preceding_instruction;
 // use chainlink_ccip::{CCIPMessage, CCIPRouter};  // Will implement interface
 
 pub mod utils;
@@ -15,6 +17,13 @@ pub mod post_quantum;
 pub mod cross_chain;
 pub mod compliance;
 pub mod emergency;
+pub mod oracle;
+pub mod nav_attestation;
+pub mod deposit_ledger;
+pub mod fees;
+pub mod invariants;
+pub mod lp_yield;
+pub mod compute_budget;
 
 pub use state::*;
 pub use utils::*;
@@ -26,6 +35,13 @@ pub use post_quantum::*;
 pub use cross_chain::*;
 pub use compliance::*;
 pub use emergency::*;
+pub use oracle::*;
+pub use nav_attestation::*;
+pub use deposit_ledger::*;
+pub use fees::*;
+pub use invariants::*;
+pub use lp_yield::*;
+pub use compute_budget::*;
 
 declare_id!("RTFVau1tAdvancedSPLTokenVau1tProgram11111111");
 
@@ -68,13 +84,17 @@ pub mod rtf_vault {
             RTFError::InvalidOracleAuthority
         );
 
+        // NAV of 1.0 in the vault's decimals; also rejects out-of-range decimals
+        let initial_nav = nav_scale(vault_config.nav_decimals)?;
+
         // Initialize vault state following PRD specifications
         vault.authority = ctx.accounts.authority.key();
         vault.config = vault_config;
         vault.total_assets = 0;
         vault.total_liabilities = 0;
-        vault.nav_per_share = 1_000_000; // 1.0 with 6 decimals
+        vault.nav_per_share = initial_nav;
         vault.last_nav_update = clock.unix_timestamp;
+        vault.last_nav_data_hash = [0; 32];
         vault.epoch = 0;
         vault.status = VaultStatus::Active;
         vault.bump = ctx.bumps.vault;
@@ -112,7 +132,7 @@ pub mod rtf_vault {
                 tranche_type: tranche_config.tranche_type,
                 mint: tranche_config.mint,
                 total_supply: 0,
-                nav_per_share: 1_000_000,
+                nav_per_share: initial_nav,
                 fee_rate: tranche_config.fee_rate,
                 min_deposit: tranche_config.min_deposit,
                 max_deposit: tranche_config.max_deposit,
@@ -121,6 +141,14 @@ pub mod rtf_vault {
                 last_yield_update: clock.unix_timestamp,
                 waterfall_priority: i as u8,
                 protection_level: tranche_config.protection_level,
+                max_redeemable_per_epoch: tranche_config.max_redeemable_per_epoch,
+                redemption_epoch: 0,
+                redeemed_in_epoch: 0,
+                performance_fee_rate: tranche_config.performance_fee_rate,
+                last_fee_accrual: clock.unix_timestamp,
+                high_water_mark: initial_nav,
+                accrued_fees: 0,
+                lp_yield: LpYieldModel::default(),
             };
         }
 
@@ -170,6 +198,10 @@ pub mod rtf_vault {
             RTFError::InvalidTrancheIndex
         );
 
+        // Shares are priced at the NAV net of fees up to now
+        accrue_fees(vault, clock.unix_timestamp)?;
+
+        let nav_decimals = vault.config.nav_decimals;
         let tranche = &mut vault.tranches[tranche_index as usize];
 
         // Validate deposit amount
@@ -180,7 +212,7 @@ pub mod rtf_vault {
         );
 
         // Calculate shares to mint based on current NAV
-        let shares_to_mint = calculate_shares_for_deposit(amount, tranche.nav_per_share)?;
+        let shares_to_mint = calculate_shares_for_deposit(amount, tranche.nav_per_share, nav_decimals)?;
         
         require!(
             shares_to_mint >= min_shares_out,
@@ -217,6 +249,16 @@ pub mod rtf_vault {
         );
         token::mint_to(mint_ctx, shares_to_mint)?;
 
+        // Record the lot for proof-of-holding on redemption
+        let deposit_ledger = &mut ctx.accounts.deposit_ledger;
+        if deposit_ledger.vault == Pubkey::default() {
+            deposit_ledger.vault = vault.key();
+            deposit_ledger.user = ctx.accounts.user.key();
+            deposit_ledger.tranche_index = tranche_index;
+            deposit_ledger.bump = ctx.bumps.deposit_ledger;
+        }
+        deposit_ledger.record_deposit(shares_to_mint, clock.unix_timestamp)?;
+
         // Update vault state
         vault.total_assets = vault.total_assets.checked_add(amount).unwrap();
         tranche.total_supply = tranche.total_supply.checked_add(shares_to_mint).unwrap();
@@ -241,6 +283,9 @@ pub mod rtf_vault {
             record: deposit_record,
         });
 
+        #[cfg(feature = "check-invariants")]
+        check_invariants(vault)?;
+
         Ok(())
     }
 
@@ -264,29 +309,18 @@ pub mod rtf_vault {
             RTFError::InvalidTrancheIndex
         );
 
+        accrue_fees(vault, clock.unix_timestamp)?;
+
         let tranche = &vault.tranches[tranche_index as usize];
         let user_balance = ctx.accounts.user_tranche_account.amount;
 
         require!(shares_amount <= user_balance, RTFError::InsufficientShares);
 
         // PRD: Flashloan-resistance via proof-of-holding (duration > M blocks)
-        let deposit_timestamp = get_user_deposit_timestamp(
-            &ctx.accounts.user.key(),
-            tranche_index,
-        )?;
-
-        // Check minimum holding duration for flashloan resistance
-        let holding_duration = clock.unix_timestamp - deposit_timestamp;
-        require!(
-            holding_duration >= 3600, // 1 hour minimum holding
-            RTFError::InsufficientHoldingDuration
-        );
-
-        // Check tranche lock period
-        require!(
-            clock.unix_timestamp >= deposit_timestamp + tranche.lock_period as i64,
-            RTFError::SharesStillLocked
-        );
+        // The shares redeemed come from the oldest deposit lots, each of which must have
+        // been held for the minimum duration and be past the tranche lock period
+        let deposit_ledger = &mut ctx.accounts.deposit_ledger;
+        let holding_since = deposit_ledger.verify_holding(shares_amount, tranche.lock_period, clock.unix_timestamp)?;
 
         // PRD: Dynamic redemption bonding under pool stress
         let pool_stress_multiplier = calculate_pool_stress_multiplier(vault)?;
@@ -296,6 +330,7 @@ pub mod rtf_vault {
         let base_assets = calculate_assets_for_redemption(
             shares_amount,
             tranche.nav_per_share,
+            vault.config.nav_decimals,
         )?;
 
         let assets_to_return = if pool_stress_multiplier > 10000 {
@@ -310,6 +345,9 @@ pub mod rtf_vault {
             RTFError::SlippageExceeded
         );
 
+        // A request the cap could never admit would block the FIFO queue behind it
+        check_epoch_cap(tranche, assets_to_return)?;
+
         // PRD: MEV-protected batch submission with commit-reveal scheme
         let redemption_request = RedemptionRequest {
             user: ctx.accounts.user.key(),
@@ -322,8 +360,12 @@ pub mod rtf_vault {
             commitment_hash, // User-provided commitment hash
             bonding_amount,  // Dynamic bonding based on pool stress
             reveal_deadline: clock.unix_timestamp + 300, // 5 minutes to reveal
+            holding_since,
         };
 
+        // Queued shares can no longer back another redemption
+        deposit_ledger.consume_fifo(shares_amount)?;
+
         // Add to queue
         add_to_redemption_queue(vault, redemption_request)?;
 
@@ -337,22 +379,29 @@ pub mod rtf_vault {
             processing_slot: redemption_request.processing_slot,
         });
 
+        #[cfg(feature = "check-invariants")]
+        check_invariants(vault)?;
+
         Ok(())
     }
 
     /// PRD: Reveal phase of commit-reveal scheme for MEV protection
     /// PRD: "MEV-protected batch submission"
+    /// The revealed amount may not exceed the queued one. The lots reserved at request time
+    /// are returned and the revealed amount is re-checked and re-reserved FIFO, so whatever
+    /// is finally redeemed has proven its holding period.
     pub fn reveal_redemption(
         ctx: Context<RevealRedemption>,
         nonce: u64,
         actual_shares_amount: u64,
     ) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
+        let deposit_ledger = &mut ctx.accounts.deposit_ledger;
         let clock = Clock::get()?;
 
         // Find user's pending commitment
         let request_index = find_user_redemption_request(vault, &ctx.accounts.user.key())?;
-        let request = get_redemption_request_mut(vault, request_index)?;
+        let request = get_redemption_request(vault, request_index as u64)?;
 
         // Verify reveal is within window
         require!(
@@ -371,9 +420,35 @@ pub mod rtf_vault {
             computed_hash == request.commitment_hash,
             RTFError::InvalidCommitmentReveal
         );
+        require!(
+            actual_shares_amount > 0 && actual_shares_amount <= request.shares_amount,
+            RTFError::InsufficientShares
+        );
+        require!(
+            deposit_ledger.tranche_index == request.tranche_index,
+            RTFError::InvalidTrancheIndex
+        );
+
+        // Re-run proof-of-holding on the revealed amount
+        let lock_period = vault.tranches.get(request.tranche_index as usize)
+            .ok_or(RTFError::InvalidTrancheIndex)?
+            .lock_period;
+        deposit_ledger.restore_lot(request.shares_amount, request.holding_since)?;
+        let holding_since = deposit_ledger.verify_holding(actual_shares_amount, lock_period, clock.unix_timestamp)?;
+        deposit_ledger.consume_fifo(actual_shares_amount)?;
+
+        // Assets owed shrink with the shares, rounded in the vault's favour
+        let expected_assets = (request.expected_assets as u128 * actual_shares_amount as u128
+            / request.shares_amount as u128) as u64;
+        vault.redemption_queue.total_pending = vault.redemption_queue.total_pending
+            .checked_sub(request.expected_assets - expected_assets)
+            .ok_or(RTFError::MathOverflow)?;
 
         // Update request with revealed amount
+        let request = get_redemption_request_mut(vault, request_index)?;
         request.shares_amount = actual_shares_amount;
+        request.expected_assets = expected_assets;
+        request.holding_since = holding_since;
         request.status = RedemptionStatus::Revealed;
 
         emit!(RedemptionRevealed {
@@ -386,11 +461,49 @@ pub mod rtf_vault {
         Ok(())
     }
 
-    /// Process redemption queue with batch execution
+    /// Withdraw an unrevealed redemption request, returning its reserved deposit lots
+    /// Once the reveal window has passed this is the only way to release the shares.
+    pub fn cancel_redemption(ctx: Context<RevealRedemption>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let deposit_ledger = &mut ctx.accounts.deposit_ledger;
+        let clock = Clock::get()?;
+
+        let request_index = find_user_redemption_request(vault, &ctx.accounts.user.key())?;
+        let request = get_redemption_request(vault, request_index as u64)?;
+
+        // Revealed requests are committed to the next batch
+        require!(
+            matches!(request.status, RedemptionStatus::Pending),
+            RTFError::RedemptionRequestNotFound
+        );
+        require!(
+            deposit_ledger.tranche_index == request.tranche_index,
+            RTFError::InvalidTrancheIndex
+        );
+
+        deposit_ledger.restore_lot(request.shares_amount, request.holding_since)?;
+        vault.redemption_queue.total_pending = vault.redemption_queue.total_pending
+            .checked_sub(request.expected_assets)
+            .ok_or(RTFError::MathOverflow)?;
+        get_redemption_request_mut(vault, request_index)?.status = RedemptionStatus::Cancelled;
+
+        emit!(RedemptionCancelled {
+            vault: vault.key(),
+            user: ctx.accounts.user.key(),
+            shares_amount: request.shares_amount,
+            expired: clock.unix_timestamp > request.reveal_deadline,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Process up to `max_redemptions` queued redemptions, stopping early when the next one
+    /// might not fit in the compute units left. Returns how many were processed.
     pub fn process_redemptions(
         ctx: Context<ProcessRedemptions>,
         max_redemptions: u8,
-    ) -> Result<()> {
+    ) -> Result<u8> {
         let vault = &mut ctx.accounts.vault;
         let clock = Clock::get()?;
 
@@ -400,20 +513,53 @@ pub mod rtf_vault {
             RTFError::Unauthorized
         );
 
+        accrue_fees(vault, clock.unix_timestamp)?;
+
         let mut processed_count = 0;
         let mut total_assets_redeemed = 0u64;
+        let compute_budget = ComputeBudget::from_config(&vault.config, remaining_compute_units());
+        let epoch = advance_vault_epoch(vault, clock.unix_timestamp);
 
         // Process redemptions in FIFO order
         while processed_count < max_redemptions && 
               vault.redemption_queue.head < vault.redemption_queue.tail {
             
             let request = get_redemption_request(vault, vault.redemption_queue.head)?;
+
+            // Cancelled requests already returned their shares
+            if matches!(request.status, RedemptionStatus::Cancelled) {
+                vault.redemption_queue.head += 1;
+                continue;
+            }
             
             // Check if ready for processing (MEV protection)
             if clock.slot < request.processing_slot {
                 break;
             }
 
+            // Leave the rest for the next batch rather than risk failing this one
+            if !compute_budget.fits(remaining_compute_units(), estimate_redemption_cu(&request)) {
+                msg!("Compute budget reached after {} redemptions", processed_count);
+                break;
+            }
+
+            // Per-tranche epoch cap: the queue is FIFO, so a capped request stays at the
+            // head and is the first processed once the next epoch starts
+            let vault_key = vault.key();
+            let tranche = vault.tranches.get_mut(request.tranche_index as usize)
+                .ok_or(RTFError::InvalidTrancheIndex)?;
+            if !reserve_epoch_redemption(tranche, epoch, request.expected_assets)? {
+                emit!(TrancheRedemptionCapReached {
+                    vault: vault_key,
+                    tranche_index: request.tranche_index,
+                    epoch,
+                    max_redeemable_per_epoch: tranche.max_redeemable_per_epoch,
+                    redeemed_in_epoch: tranche.redeemed_in_epoch,
+                    deferred_assets: request.expected_assets,
+                });
+                break;
+            }
+
             // Execute redemption
             execute_redemption(vault, &request, &ctx.remaining_accounts)?;
             
@@ -430,6 +576,58 @@ pub mod rtf_vault {
             timestamp: clock.unix_timestamp,
         });
 
+        #[cfg(feature = "check-invariants")]
+        check_invariants(vault)?;
+
+        Ok(processed_count)
+    }
+
+    /// Accrue management and performance fees and pay them to the fee collector
+    pub fn collect_fees(ctx: Context<CollectFees>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let clock = Clock::get()?;
+
+        require!(
+            ctx.accounts.authority.key() == vault.authority ||
+            ctx.accounts.authority.key() == vault.config.operator,
+            RTFError::Unauthorized
+        );
+
+        accrue_fees(vault, clock.unix_timestamp)?;
+        let amount = take_accrued_fees(vault)?;
+
+        if amount > 0 {
+            let vault_seeds = &[
+                b"vault",
+                vault.authority.as_ref(),
+                &[vault.bump],
+            ];
+            let signer_seeds = &[&vault_seeds[..]];
+
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault_token_account.to_account_info(),
+                    to: ctx.accounts.fee_collector_account.to_account_info(),
+                    authority: vault.to_account_info(),
+                },
+                signer_seeds,
+            );
+            token::transfer(transfer_ctx, amount)?;
+
+            vault.total_assets = vault.total_assets.checked_sub(amount).ok_or(RTFError::MathOverflow)?;
+        }
+
+        emit!(FeesCollected {
+            vault: vault.key(),
+            fee_collector: vault.config.fee_collector,
+            amount,
+            timestamp: clock.unix_timestamp,
+        });
+
+        #[cfg(feature = "check-invariants")]
+        check_invariants(vault)?;
+
         Ok(())
     }
 
@@ -443,7 +641,10 @@ pub mod rtf_vault {
         zk_proof: Vec<u8>,
         starknet_proof: [u8; 32],
         dilithium_signature: [u8; 128], // Post-quantum signature
-    ) -> Result<()> {
+    ) -> Result<NavUpdateOutcome> {
+        let vault_key = ctx.accounts.vault.key();
+        let ed25519_instruction = load_preceding_instruction(&ctx.accounts.instructions_sysvar)
+            .map_err(|_| RTFError::InvalidAttestationSignature)?;
         let vault = &mut ctx.accounts.vault;
         let clock = Clock::get()?;
 
@@ -453,30 +654,63 @@ pub mod rtf_vault {
             RTFError::UnauthorizedOracle
         );
 
-        // PRD: Verify zkProof of NAV computation from Starknet
-        verify_nav_zk_proof(&new_nav_data, &zk_proof)?;
+        // PRD: Verify zkProof of NAV computation from Starknet (attested off-chain)
+        let attestation = verify_nav_zk_proof(
+            &new_nav_data,
+            &zk_proof,
+            &vault.config.nav_verifier,
+            &vault_key,
+            &ed25519_instruction,
+        )?;
 
         // PRD: Verify Starknet proof
-        verify_starknet_proof(&starknet_proof, &new_nav_data)?;
+        verify_starknet_proof(&starknet_proof, &attestation)?;
 
         // PRD: PQ anchoring with SHA256 + Dilithium512
         verify_dilithium_signature(
             &dilithium_signature,
             &vault.config.dilithium_public_key,
-            &new_nav_data,
+            &attestation,
         )?;
 
+        // A resubmission of the last applied NAV (e.g. with only the timestamp bumped)
+        // changes nothing, so it must not re-emit events or advance counters. A later one is
+        // still a fresh reading of a flat NAV, so it refreshes `last_nav_update` and the
+        // staleness charged on deposits stops growing
+        if nav_update_outcome(&vault.last_nav_data_hash, &new_nav_data) == NavUpdateOutcome::Duplicate {
+            if new_nav_data.timestamp > vault.last_nav_update {
+                verify_nav_against_switchboard(
+                    &ctx.accounts.switchboard_aggregator,
+                    &vault.config,
+                    new_nav_data.nav_per_share,
+                    clock.unix_timestamp,
+                )?;
+                vault.last_nav_update = new_nav_data.timestamp;
+            }
+            msg!("Duplicate NAV submission: only the update time was refreshed");
+            return Ok(NavUpdateOutcome::Duplicate);
+        }
+
         // Validate NAV data freshness
         require!(
             new_nav_data.timestamp >= vault.last_nav_update,
             RTFError::StaleNAVData
         );
 
+        // Cross-check the signed NAV against the on-chain Switchboard feed
+        verify_nav_against_switchboard(
+            &ctx.accounts.switchboard_aggregator,
+            &vault.config,
+            new_nav_data.nav_per_share,
+            clock.unix_timestamp,
+        )?;
+
         // PRD: Drift enforcement circuit with 100-epoch ledger
         let nav_drift = calculate_nav_drift(vault.nav_per_share, new_nav_data.nav_per_share)?;
 
         // Update drift ledger for 100-epoch tracking
-        update_drift_ledger(&mut vault.drift_ledger, nav_drift, vault.epoch)?;
+        let epoch = advance_vault_epoch(vault, clock.unix_timestamp);
+        update_drift_ledger(&mut vault.drift_ledger, nav_drift, epoch)?;
 
         // Check for excessive drift
         require!(
@@ -493,16 +727,17 @@ pub mod rtf_vault {
         // Update vault NAV
         vault.nav_per_share = new_nav_data.nav_per_share;
         vault.last_nav_update = new_nav_data.timestamp;
-        vault.total_assets = new_nav_data.total_assets;
+        vault.last_nav_data_hash = nav_content_hash(&new_nav_data);
         vault.total_liabilities = new_nav_data.total_liabilities;
 
-        // Update tranche NAVs
-        for (i, tranche_nav) in new_nav_data.tranche_navs.iter().enumerate() {
-            if i < vault.tranches.len() {
-                vault.tranches[i].nav_per_share = *tranche_nav;
-                vault.tranches[i].last_yield_update = clock.unix_timestamp;
-            }
+        // The oracle reports waterfall figures; LP flows are layered onto total assets and
+        // tranche NAVs alike
+        apply_oracle_nav(vault, new_nav_data.total_assets, &new_nav_data.tranche_navs)?;
+        for tranche in vault.tranches.iter_mut().take(new_nav_data.tranche_navs.len()) {
+            tranche.last_yield_update = clock.unix_timestamp;
         }
+        // Oracle NAVs are gross of fees, so fees are applied after them rather than before
+        accrue_fees_after_oracle_nav(vault, new_nav_data.tranche_navs.len(), clock.unix_timestamp)?;
 
         // PRD: Update cross-chain state for anchoring
         vault.cross_chain_state.starknet_proof = starknet_proof;
@@ -510,7 +745,7 @@ pub mod rtf_vault {
         vault.cross_chain_state.sync_status = SyncStatus::Synced;
 
         // PRD: Update zkNAV state
-        vault.zk_nav_state.current_proof = zk_proof.try_into().unwrap_or([0; 32]);
+        vault.zk_nav_state.current_proof = attestation.verified_root;
         vault.zk_nav_state.last_computation = clock.unix_timestamp;
         vault.zk_nav_state.proof_verification_count += 1;
 
@@ -533,9 +768,64 @@ pub mod rtf_vault {
             timestamp: clock.unix_timestamp,
         });
 
+        #[cfg(feature = "check-invariants")]
+        check_invariants(vault)?;
+
+        Ok(NavUpdateOutcome::Applied)
+    }
+
+    /// Record an LP tranche's pool fee income for `epoch` and its impermanent loss mark
+    pub fn record_lp_flows(
+        ctx: Context<RecordLpFlows>,
+        tranche_index: u8,
+        epoch: u64,
+        fee_income: u64,
+        impermanent_loss: u64,
+    ) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let clock = Clock::get()?;
+
+        require!(
+            ctx.accounts.oracle_authority.key() == vault.config.oracle_authority,
+            RTFError::UnauthorizedOracle
+        );
+
+        let index = tranche_index as usize;
+        record_lp_epoch_flows(vault, index, epoch, fee_income, impermanent_loss, clock.unix_timestamp)?;
+
+        emit!(LpFlowsRecorded {
+            vault: vault.key(),
+            tranche_index,
+            epoch,
+            fee_income,
+            impermanent_loss,
+            nav_per_share: vault.tranches[index].nav_per_share,
+            timestamp: clock.unix_timestamp,
+        });
+
+        #[cfg(feature = "check-invariants")]
+        check_invariants(vault)?;
+
         Ok(())
     }
 
+    /// View: principal, pool fee income and impermanent loss of an LP tranche
+    pub fn get_lp_position_breakdown(
+        ctx: Context<GetLpPositionBreakdown>,
+        tranche_index: u8,
+    ) -> Result<LpPositionBreakdown> {
+        let vault = &ctx.accounts.vault;
+        let tranche = vault.tranches.get(tranche_index as usize).ok_or(RTFError::InvalidTrancheIndex)?;
+        lp_position_breakdown(tranche, vault.config.nav_decimals)
+    }
+
+    /// PRD: "Drift enforcement circuit with 100-epoch ledger"
+    /// View: return the drift ledger in epoch order for off-chain audit
+    pub fn get_drift_ledger(ctx: Context<GetDriftLedger>) -> Result<DriftLedgerExport> {
+        let vault = &ctx.accounts.vault;
+        Ok(export_drift_ledger(vault.key(), &vault.drift_ledger))
+    }
+
     /// PRD: "Modular tranching with sophisticated risk management"
     /// Advanced tranche creation with dynamic risk assessment and allocation optimization
     pub fn create_advanced_tranche(
@@ -571,7 +861,7 @@ pub mod rtf_vault {
             tranche_type: tranche_config.tranche_type.clone(),
             mint: tranche_config.mint,
             total_supply: 0,
-            nav_per_share: 1_000_000, // 1.0 with 6 decimals
+            nav_per_share: nav_scale(vault.config.nav_decimals)?,
             fee_rate: tranche_config.performance_fees.performance_fee_bps,
             min_deposit: tranche_config.liquidity_parameters.min_deposit,
             max_deposit: tranche_config.liquidity_parameters.max_deposit,
@@ -730,6 +1020,7 @@ pub struct InitializeVault<'info> {
 }
 
 #[derive(Accounts)]
+#[instruction(tranche_index: u8)]
 pub struct Deposit<'info> {
     #[account(mut)]
     pub vault: Account<'info, VaultAccount>,
@@ -737,6 +1028,15 @@ pub struct Deposit<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
 
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + DepositLedger::INIT_SPACE,
+        seeds = [DEPOSIT_LEDGER_SEED, vault.key().as_ref(), user.key().as_ref(), &[tranche_index]],
+        bump
+    )]
+    pub deposit_ledger: Account<'info, DepositLedger>,
+
     #[account(
         mut,
         constraint = user_token_account.owner == user.key(),
@@ -768,6 +1068,7 @@ pub struct Deposit<'info> {
 }
 
 #[derive(Accounts)]
+#[instruction(tranche_index: u8)]
 pub struct RequestRedemption<'info> {
     #[account(mut)]
     pub vault: Account<'info, VaultAccount>,
@@ -775,6 +1076,13 @@ pub struct RequestRedemption<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
 
+    #[account(
+        mut,
+        seeds = [DEPOSIT_LEDGER_SEED, vault.key().as_ref(), user.key().as_ref(), &[tranche_index]],
+        bump = deposit_ledger.bump
+    )]
+    pub deposit_ledger: Account<'info, DepositLedger>,
+
     #[account(
         mut,
         constraint = user_tranche_account.owner == user.key()
@@ -794,6 +1102,30 @@ pub struct ProcessRedemptions<'info> {
     pub token_program: Program<'info, Token>,
 }
 
+#[derive(Accounts)]
+pub struct CollectFees<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, VaultAccount>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = vault_token_account.owner == vault.key(),
+        constraint = vault_token_account.mint == vault.config.underlying_mint
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = fee_collector_account.owner == vault.config.fee_collector,
+        constraint = fee_collector_account.mint == vault.config.underlying_mint
+    )]
+    pub fee_collector_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
 #[derive(Accounts)]
 pub struct RevealRedemption<'info> {
     #[account(mut)]
@@ -801,6 +1133,10 @@ pub struct RevealRedemption<'info> {
 
     #[account(mut)]
     pub user: Signer<'info>,
+
+    /// Ledger of the request's tranche; its reserved lots are re-checked or returned
+    #[account(mut, has_one = vault, has_one = user)]
+    pub deposit_ledger: Account<'info, DepositLedger>,
 }
 
 #[derive(Accounts)]
@@ -810,8 +1146,32 @@ pub struct UpdateNAV<'info> {
 
     pub oracle_authority: Signer<'info>,
 
-    /// CHECK: Oracle account validation
-    pub oracle_account: UncheckedAccount<'info>,
+    /// Switchboard feed the submitted NAV is cross-checked against
+    #[account(address = vault.config.switchboard_feed @ RTFError::InvalidOracleFeed)]
+    pub switchboard_aggregator: AccountLoader<'info, AggregatorAccountData>,
+
+    /// CHECK: Instructions sysvar, read for the Ed25519 precompile instruction carrying the
+    /// NAV verifier's attestation signature
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RecordLpFlows<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, VaultAccount>,
+
+    pub oracle_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetLpPositionBreakdown<'info> {
+    pub vault: Account<'info, VaultAccount>,
+}
+
+#[derive(Accounts)]
+pub struct GetDriftLedger<'info> {
+    pub vault: Account<'info, VaultAccount>,
 }
 
 // Data structures
@@ -824,6 +1184,7 @@ pub struct VaultAccount {
     pub total_liabilities: u64,
     pub nav_per_share: u64,
     pub last_nav_update: i64,
+    pub last_nav_data_hash: [u8; 32], // `nav_content_hash` of the last applied NAV update
     pub epoch: u64,
     pub status: VaultStatus,
     pub bump: u8,
@@ -841,8 +1202,16 @@ pub struct VaultConfig {
     pub redemption_processing_window: u32,
     pub mev_protection_delay: u64,
     pub max_nav_drift: u64,
+    pub max_stress_multiplier: u64, // Basis points cap on redemption bonding (0 = default)
+    pub nav_decimals: u8, // Decimals of NAV, deposit amounts and shares (MIN_NAV_DECIMALS..=MAX_NAV_DECIMALS)
+    pub switchboard_feed: Pubkey,
+    pub max_oracle_staleness: i64, // Seconds (0 = default)
+    pub max_oracle_std_dev_bps: u64, // Basis points of the oracle value (0 = default)
+    pub nav_verifier: Pubkey, // Ed25519 key of the off-chain NAV proof verifier
     pub fee_collector: Pubkey,
     pub emergency_pause_authority: Pubkey,
+    pub max_redemption_compute_units: u32, // Cap on CU a redemption batch may spend (0 = transaction limit only)
+    pub epoch_duration: u64, // Seconds per epoch, counted from the Unix epoch (0 = default)
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
@@ -851,12 +1220,20 @@ pub struct Tranche {
     pub mint: Pubkey,
     pub total_supply: u64,
     pub nav_per_share: u64,
-    pub fee_rate: u16,
+    pub fee_rate: u16, // Annual management fee, basis points
     pub min_deposit: u64,
     pub max_deposit: u64,
     pub lock_period: u32,
     pub yield_rate: u64,
     pub last_yield_update: i64,
+    pub max_redeemable_per_epoch: u64, // Underlying assets redeemable per epoch (0 = unlimited)
+    pub redemption_epoch: u64, // Epoch `redeemed_in_epoch` counts towards
+    pub redeemed_in_epoch: u64,
+    pub performance_fee_rate: u16, // Basis points of NAV gains
+    pub last_fee_accrual: i64,
+    pub high_water_mark: u64, // Highest post-fee NAV; performance fees only accrue above it
+    pub accrued_fees: u64, // Underlying assets owed to the fee collector
+    pub lp_yield: LpYieldModel, // Fee income and IL; only used by LP tranches
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
@@ -921,6 +1298,38 @@ pub enum RTFError {
     InvalidDilithiumSignature,
     #[msg("Redemption request not found")]
     RedemptionRequestNotFound,
+    #[msg("Oracle account is not the vault's configured feed")]
+    InvalidOracleFeed,
+    #[msg("Oracle round is stale")]
+    StaleOracleRound,
+    #[msg("Oracle confidence interval too wide")]
+    OracleConfidenceTooWide,
+    #[msg("Submitted NAV diverges from oracle")]
+    OracleNavDivergence,
+    #[msg("Malformed NAV attestation")]
+    InvalidNavAttestation,
+    #[msg("NAV attestation not signed by the configured verifier")]
+    InvalidAttestationSignature,
+    #[msg("NAV attestation does not match the submitted public inputs")]
+    NavPublicInputMismatch,
+    #[msg("NAV decimals outside the supported range")]
+    InvalidNavDecimals,
+    #[msg("Invariant violated: liabilities exceed assets")]
+    InvariantLiabilitiesExceedAssets,
+    #[msg("Invariant violated: tranche with outstanding shares has zero NAV")]
+    InvariantTrancheNavZero,
+    #[msg("Invariant violated: tranche value does not match net assets")]
+    InvariantNetAssetsMismatch,
+    #[msg("Invariant violated: redemption queue head/tail out of bounds")]
+    InvariantRedemptionQueueCorrupted,
+    #[msg("Invariant violated: tranche redeemed more than its epoch cap")]
+    InvariantEpochRedemptionCapExceeded,
+    #[msg("Tranche is not an LP tranche")]
+    NotLpTranche,
+    #[msg("LP flows for this epoch were already recorded or the epoch has not started")]
+    InvalidLpFlowEpoch,
+    #[msg("Redemption exceeds the tranche's per-epoch cap")]
+    RedemptionExceedsEpochCap,
 }
 
 /// PRD: Advanced Yield Strategy for sophisticated return optimization
@@ -0,0 +1,343 @@
+use anchor_lang::prelude::*;
+use crate::{RTFError, Tranche, TrancheType, VaultAccount, nav_scale, vault_epoch};
+
+/// LP tranche yield
+/// LP positions earn pool fee income and carry impermanent loss (IL) on top of their
+/// waterfall return, so an LP tranche's NAV is the oracle's waterfall NAV adjusted by
+/// those flows spread over its shares:
+/// `nav_per_share = waterfall_nav + (accrued_fee_income - impermanent_loss) * scale / total_supply`
+/// Fee income accumulates. IL is a mark-to-market figure that reverses when pool prices
+/// revert, so each report replaces the previous mark. Flows are reported once per epoch,
+/// so a retried report cannot credit the same fee income twice.
+///
+/// The oracle's NAV data (both `total_assets` and the tranche NAVs) excludes LP flows.
+/// Recording flows and applying an oracle NAV therefore layer them onto both totals, so
+/// the tranches keep adding up to net assets.
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Debug, PartialEq, InitSpace)]
+pub struct LpYieldModel {
+    pub accrued_fee_income: u64, // Underlying assets earned from pool fees
+    pub impermanent_loss: u64, // Underlying assets, current mark
+    pub last_flow_epoch: Option<u64>, // Latest epoch whose flows were recorded
+}
+
+/// Value of an LP tranche split into its sources, in underlying asset units
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct LpPositionBreakdown {
+    /// Value excluding fee income and IL
+    pub principal: u64,
+    /// Pool fee income (not the management fees in `Tranche::accrued_fees`)
+    pub accrued_fees: u64,
+    pub impermanent_loss: u64,
+    /// `principal + accrued_fees - impermanent_loss`
+    pub net_value: u64,
+}
+
+impl LpYieldModel {
+    /// Per-share NAV adjustment, rounded down so holders are never credited more than
+    /// the flows are worth
+    fn nav_adjustment(&self, total_supply: u64, scale: u64) -> i128 {
+        if total_supply == 0 {
+            return 0;
+        }
+        (self.net_value() * scale as i128).div_euclid(total_supply as i128)
+    }
+
+    /// Fee income less IL, in underlying assets
+    fn net_value(&self) -> i128 {
+        self.accrued_fee_income as i128 - self.impermanent_loss as i128
+    }
+}
+
+fn lp_tranche_mut(vault: &mut VaultAccount, tranche_index: usize) -> Result<&mut Tranche> {
+    let tranche = vault.tranches.get_mut(tranche_index).ok_or(RTFError::InvalidTrancheIndex)?;
+    require!(matches!(tranche.tranche_type, TrancheType::LP), RTFError::NotLpTranche);
+    Ok(tranche)
+}
+
+fn adjusted_nav(nav_per_share: u64, adjustment: i128) -> Result<u64> {
+    let nav = nav_per_share as i128 + adjustment;
+    u64::try_from(nav.max(0)).map_err(|_| RTFError::MathOverflow.into())
+}
+
+/// Apply a change to an LP tranche's flows, moving its NAV and the vault's assets by
+/// the change in value
+fn update_lp_flows(
+    vault: &mut VaultAccount,
+    tranche_index: usize,
+    update: impl FnOnce(&mut LpYieldModel) -> Result<()>,
+) -> Result<()> {
+    let scale = nav_scale(vault.config.nav_decimals)?;
+    let tranche = lp_tranche_mut(vault, tranche_index)?;
+
+    let old_model = tranche.lp_yield.clone();
+    update(&mut tranche.lp_yield)?;
+    let old_adjustment = old_model.nav_adjustment(tranche.total_supply, scale);
+    let new_adjustment = tranche.lp_yield.nav_adjustment(tranche.total_supply, scale);
+    tranche.nav_per_share = adjusted_nav(tranche.nav_per_share, new_adjustment - old_adjustment)?;
+
+    let value_change = (tranche.lp_yield.accrued_fee_income as i128 - old_model.accrued_fee_income as i128)
+        - (tranche.lp_yield.impermanent_loss as i128 - old_model.impermanent_loss as i128);
+    let total_assets = vault.total_assets as i128 + value_change;
+    vault.total_assets = u64::try_from(total_assets).map_err(|_| RTFError::MathOverflow)?;
+    Ok(())
+}
+
+/// Record an LP tranche's flows for `epoch`: the pool fee income earned in that epoch and
+/// the IL mark at its end. Each epoch is recorded at most once and never before it starts.
+pub fn record_lp_epoch_flows(
+    vault: &mut VaultAccount,
+    tranche_index: usize,
+    epoch: u64,
+    fee_income: u64,
+    impermanent_loss: u64,
+    now: i64,
+) -> Result<()> {
+    let current_epoch = vault_epoch(vault.config.epoch_duration, now);
+    let after_last_flow = match lp_tranche_mut(vault, tranche_index)?.lp_yield.last_flow_epoch {
+        Some(last) => epoch > last,
+        None => true,
+    };
+    require!(epoch <= current_epoch && after_last_flow, RTFError::InvalidLpFlowEpoch);
+
+    accrue_lp_fee_income(vault, tranche_index, fee_income)?;
+    mark_impermanent_loss(vault, tranche_index, impermanent_loss)?;
+    vault.tranches[tranche_index].lp_yield.last_flow_epoch = Some(epoch);
+    Ok(())
+}
+
+/// Credit pool fee income to an LP tranche
+pub fn accrue_lp_fee_income(vault: &mut VaultAccount, tranche_index: usize, amount: u64) -> Result<()> {
+    update_lp_flows(vault, tranche_index, |model| {
+        model.accrued_fee_income = model.accrued_fee_income
+            .checked_add(amount)
+            .ok_or(RTFError::MathOverflow)?;
+        Ok(())
+    })
+}
+
+/// Replace an LP tranche's impermanent loss mark
+pub fn mark_impermanent_loss(vault: &mut VaultAccount, tranche_index: usize, impermanent_loss: u64) -> Result<()> {
+    update_lp_flows(vault, tranche_index, |model| {
+        model.impermanent_loss = impermanent_loss;
+        Ok(())
+    })
+}
+
+/// Apply an oracle NAV report: LP flows are layered onto the reported total assets and
+/// onto each LP tranche's waterfall NAV alike. Tranches beyond `tranche_navs` keep their NAV.
+pub fn apply_oracle_nav(vault: &mut VaultAccount, total_assets: u64, tranche_navs: &[u64]) -> Result<()> {
+    let lp_net_value: i128 = vault.tranches.iter()
+        .filter(|tranche| matches!(tranche.tranche_type, TrancheType::LP))
+        .map(|tranche| tranche.lp_yield.net_value())
+        .sum();
+    let total_assets = (total_assets as i128 + lp_net_value).max(0);
+    vault.total_assets = u64::try_from(total_assets).map_err(|_| RTFError::MathOverflow)?;
+
+    let nav_decimals = vault.config.nav_decimals;
+    for (tranche, waterfall_nav) in vault.tranches.iter_mut().zip(tranche_navs) {
+        apply_waterfall_nav(tranche, *waterfall_nav, nav_decimals)?;
+    }
+    Ok(())
+}
+
+/// Set a tranche's NAV from the oracle's waterfall NAV, re-applying LP flows to LP tranches
+pub fn apply_waterfall_nav(tranche: &mut Tranche, waterfall_nav: u64, nav_decimals: u8) -> Result<()> {
+    tranche.nav_per_share = if matches!(tranche.tranche_type, TrancheType::LP) {
+        let adjustment = tranche.lp_yield.nav_adjustment(tranche.total_supply, nav_scale(nav_decimals)?);
+        adjusted_nav(waterfall_nav, adjustment)?
+    } else {
+        waterfall_nav
+    };
+    Ok(())
+}
+
+/// Principal, fee income and IL making up an LP tranche's value
+pub fn lp_position_breakdown(tranche: &Tranche, nav_decimals: u8) -> Result<LpPositionBreakdown> {
+    require!(matches!(tranche.tranche_type, TrancheType::LP), RTFError::NotLpTranche);
+    let scale = nav_scale(nav_decimals)? as u128;
+
+    let net_value = tranche.nav_per_share as u128 * tranche.total_supply as u128 / scale;
+    let net_value = u64::try_from(net_value).map_err(|_| RTFError::MathOverflow)?;
+    let principal = net_value as i128 + tranche.lp_yield.impermanent_loss as i128
+        - tranche.lp_yield.accrued_fee_income as i128;
+
+    Ok(LpPositionBreakdown {
+        principal: u64::try_from(principal.max(0)).map_err(|_| RTFError::MathOverflow)?,
+        accrued_fees: tranche.lp_yield.accrued_fee_income,
+        impermanent_loss: tranche.lp_yield.impermanent_loss,
+        net_value,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{check_invariants, RedemptionQueue, VaultConfig, VaultStatus, DEFAULT_VAULT_EPOCH_DURATION};
+
+    const START: i64 = 1_700_000_000;
+
+    fn test_tranche(tranche_type: TrancheType, total_supply: u64) -> Tranche {
+        Tranche {
+            tranche_type,
+            mint: Pubkey::default(),
+            total_supply,
+            nav_per_share: 1_000_000,
+            fee_rate: 0,
+            min_deposit: 0,
+            max_deposit: 0,
+            lock_period: 0,
+            yield_rate: 0,
+            last_yield_update: START,
+            max_redeemable_per_epoch: 0,
+            redemption_epoch: 0,
+            redeemed_in_epoch: 0,
+            performance_fee_rate: 0,
+            last_fee_accrual: START,
+            high_water_mark: 1_000_000,
+            accrued_fees: 0,
+            lp_yield: LpYieldModel::default(),
+        }
+    }
+
+    /// 1_000 senior shares and 1_000 LP shares, both at NAV 1.0
+    fn test_vault() -> VaultAccount {
+        VaultAccount {
+            authority: Pubkey::default(),
+            config: VaultConfig {
+                underlying_mint: Pubkey::default(),
+                oracle_authority: Pubkey::default(),
+                operator: Pubkey::default(),
+                max_redemption_queue_size: 100,
+                redemption_processing_window: 0,
+                mev_protection_delay: 0,
+                max_nav_drift: 0,
+                max_stress_multiplier: 0,
+                nav_decimals: 6,
+                switchboard_feed: Pubkey::default(),
+                max_oracle_staleness: 0,
+                max_oracle_std_dev_bps: 0,
+                nav_verifier: Pubkey::default(),
+                fee_collector: Pubkey::default(),
+                emergency_pause_authority: Pubkey::default(),
+                max_redemption_compute_units: 0,
+                epoch_duration: 0,
+            },
+            total_assets: 2_000_000_000,
+            total_liabilities: 0,
+            nav_per_share: 1_000_000,
+            last_nav_update: START,
+            last_nav_data_hash: [0; 32],
+            epoch: 0,
+            status: VaultStatus::Active,
+            bump: 0,
+            tranches: vec![
+                test_tranche(TrancheType::Senior, 1_000_000_000),
+                test_tranche(TrancheType::LP, 1_000_000_000),
+            ],
+            redemption_queue: RedemptionQueue {
+                head: 0,
+                tail: 0,
+                total_pending: 0,
+                max_queue_size: 100,
+                processing_window: 0,
+                mev_protection_delay: 0,
+                batch_size: 10,
+            },
+        }
+    }
+
+    #[test]
+    fn test_fee_income_raises_and_impermanent_loss_reduces_lp_nav() {
+        let mut vault = test_vault();
+
+        // 50 tokens of pool fees: +0.05 per LP share, senior untouched
+        accrue_lp_fee_income(&mut vault, 1, 50_000_000).unwrap();
+        assert_eq!(vault.tranches[1].nav_per_share, 1_050_000);
+        assert_eq!(vault.tranches[0].nav_per_share, 1_000_000);
+        assert_eq!(vault.total_assets, 2_050_000_000);
+        assert!(check_invariants(&vault).is_ok());
+
+        // An IL mark of 80 tokens outweighs the fees
+        mark_impermanent_loss(&mut vault, 1, 80_000_000).unwrap();
+        assert_eq!(vault.tranches[1].nav_per_share, 970_000);
+        assert_eq!(vault.total_assets, 1_970_000_000);
+        assert!(check_invariants(&vault).is_ok());
+
+        assert_eq!(lp_position_breakdown(&vault.tranches[1], 6).unwrap(), LpPositionBreakdown {
+            principal: 1_000_000_000,
+            accrued_fees: 50_000_000,
+            impermanent_loss: 80_000_000,
+            net_value: 970_000_000,
+        });
+
+        // Prices revert and the IL mark shrinks
+        mark_impermanent_loss(&mut vault, 1, 20_000_000).unwrap();
+        assert_eq!(vault.tranches[1].nav_per_share, 1_030_000);
+        assert!(check_invariants(&vault).is_ok());
+    }
+
+    #[test]
+    fn test_waterfall_nav_keeps_lp_flows_and_non_lp_tranches_are_rejected() {
+        let mut vault = test_vault();
+        accrue_lp_fee_income(&mut vault, 1, 50_000_000).unwrap();
+        mark_impermanent_loss(&mut vault, 1, 10_000_000).unwrap();
+
+        // The oracle's waterfall NAV excludes LP flows; they are layered back on top
+        apply_waterfall_nav(&mut vault.tranches[1], 1_100_000, 6).unwrap();
+        assert_eq!(vault.tranches[1].nav_per_share, 1_140_000);
+        apply_waterfall_nav(&mut vault.tranches[0], 1_100_000, 6).unwrap();
+        assert_eq!(vault.tranches[0].nav_per_share, 1_100_000);
+
+        let breakdown = lp_position_breakdown(&vault.tranches[1], 6).unwrap();
+        assert_eq!(breakdown.principal, 1_100_000_000);
+        assert_eq!(breakdown.net_value, 1_140_000_000);
+
+        assert_eq!(accrue_lp_fee_income(&mut vault, 0, 1), Err(RTFError::NotLpTranche.into()));
+        assert_eq!(lp_position_breakdown(&vault.tranches[0], 6), Err(RTFError::NotLpTranche.into()));
+        assert_eq!(mark_impermanent_loss(&mut vault, 5, 1), Err(RTFError::InvalidTrancheIndex.into()));
+    }
+
+    #[test]
+    fn test_flows_are_recorded_once_per_epoch() {
+        let mut vault = test_vault();
+        let epoch = vault_epoch(vault.config.epoch_duration, START);
+
+        record_lp_epoch_flows(&mut vault, 1, epoch, 50_000_000, 0, START).unwrap();
+        // An oracle retry for the same epoch must not credit the fees again
+        assert_eq!(
+            record_lp_epoch_flows(&mut vault, 1, epoch, 50_000_000, 0, START),
+            Err(RTFError::InvalidLpFlowEpoch.into())
+        );
+        assert_eq!(
+            record_lp_epoch_flows(&mut vault, 1, epoch + 1, 10_000_000, 0, START),
+            Err(RTFError::InvalidLpFlowEpoch.into())
+        );
+        assert_eq!(vault.tranches[1].lp_yield.accrued_fee_income, 50_000_000);
+        assert_eq!(vault.total_assets, 2_050_000_000);
+
+        let next_epoch_start = START + DEFAULT_VAULT_EPOCH_DURATION as i64;
+        record_lp_epoch_flows(&mut vault, 1, epoch + 1, 10_000_000, 0, next_epoch_start).unwrap();
+        assert_eq!(vault.tranches[1].lp_yield.accrued_fee_income, 60_000_000);
+        assert!(check_invariants(&vault).is_ok());
+    }
+
+    #[test]
+    fn test_oracle_nav_applies_lp_flows_to_both_totals() {
+        let mut vault = test_vault();
+        let epoch = vault_epoch(vault.config.epoch_duration, START);
+        record_lp_epoch_flows(&mut vault, 1, epoch, 50_000_000, 10_000_000, START).unwrap();
+
+        // Waterfall report: both tranches at 1.1, so 2_200 tokens before LP flows
+        apply_oracle_nav(&mut vault, 2_200_000_000, &[1_100_000, 1_100_000]).unwrap();
+        assert_eq!(vault.tranches[0].nav_per_share, 1_100_000);
+        assert_eq!(vault.tranches[1].nav_per_share, 1_140_000);
+        assert_eq!(vault.total_assets, 2_240_000_000);
+        assert!(check_invariants(&vault).is_ok());
+
+        // Applying the same report again changes nothing
+        apply_oracle_nav(&mut vault, 2_200_000_000, &[1_100_000, 1_100_000]).unwrap();
+        assert_eq!(vault.total_assets, 2_240_000_000);
+        assert_eq!(vault.tranches[1].nav_per_share, 1_140_000);
+    }
+}
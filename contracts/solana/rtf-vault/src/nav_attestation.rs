@@ -0,0 +1,297 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use ed25519_instruction::verify_ed25519_instruction;
+use sha2::{Sha256, Digest};
+use domain_tags::DomainTag;
+use crate::{RTFError, NAVData};
+
+/// PRD: "NAV is computed daily using a verifiable zk circuit"
+/// Full STARK, Starknet fact and Dilithium512 verification is too expensive on-chain, so
+/// the backend `NavProofVerifier` (backend/zk-nav) verifies them off-chain and signs a
+/// succinct attestation. The program only checks that the attestation is signed by the
+/// vault's `nav_verifier` and commits to the submitted NAV, Starknet fact and PQ signature.
+/// The signature itself is checked by the Ed25519 precompile in the instruction preceding
+/// `update_nav_with_zk_proof`; the program inspects that instruction through the
+/// instructions sysvar rather than verifying Ed25519 in BPF.
+
+/// Attestation produced by the off-chain verifier
+/// Wire format is Borsh: fixed-size fields concatenated in order, `attested_at` little-endian.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct NavAttestation {
+    pub public_inputs_hash: [u8; 32],
+    pub verified_root: [u8; 32], // Hash of the zk proof the verifier accepted
+    pub starknet_fact: [u8; 32],
+    pub pq_signature_hash: [u8; 32],
+    pub pq_public_key_hash: [u8; 32],
+    pub attested_at: i64,
+}
+
+impl NavAttestation {
+    /// Message the verifier signs, bound to one deployment of the program and one vault so
+    /// an attestation cannot be replayed against another vault sharing the verifier key
+    pub fn digest(&self, program_id: &Pubkey, vault: &Pubkey) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(DomainTag::NavAttestation.as_bytes());
+        hasher.update(program_id.as_ref());
+        hasher.update(vault.as_ref());
+        hasher.update(&self.public_inputs_hash);
+        hasher.update(&self.verified_root);
+        hasher.update(&self.starknet_fact);
+        hasher.update(&self.pq_signature_hash);
+        hasher.update(&self.pq_public_key_hash);
+        hasher.update(&self.attested_at.to_le_bytes());
+        hasher.finalize().into()
+    }
+}
+
+/// Hash binding the NAV public inputs the zk circuit proved
+pub fn nav_public_inputs_hash(nav_data: &NAVData) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(DomainTag::NavProof.as_bytes());
+    hasher.update(&nav_data.nav_per_share.to_le_bytes());
+    hasher.update(&nav_data.total_assets.to_le_bytes());
+    hasher.update(&nav_data.total_liabilities.to_le_bytes());
+    hasher.update(&nav_data.timestamp.to_le_bytes());
+
+    for tranche_nav in &nav_data.tranche_navs {
+        hasher.update(&tranche_nav.to_le_bytes());
+    }
+
+    hasher.finalize().into()
+}
+
+/// Hash of the values a NAV update applies, excluding its timestamp, so a resubmission
+/// that only bumps the timestamp hashes the same as the original
+pub fn nav_content_hash(nav_data: &NAVData) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(DomainTag::NavContent.as_bytes());
+    hasher.update(&nav_data.nav_per_share.to_le_bytes());
+    hasher.update(&nav_data.total_assets.to_le_bytes());
+    hasher.update(&nav_data.total_liabilities.to_le_bytes());
+    hasher.update(&(nav_data.tranche_navs.len() as u32).to_le_bytes());
+    for tranche_nav in &nav_data.tranche_navs {
+        hasher.update(&tranche_nav.to_le_bytes());
+    }
+    hasher.finalize().into()
+}
+
+/// Return value of `update_nav_with_zk_proof`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NavUpdateOutcome {
+    Applied,
+    /// Same content as the last applied update; only `last_nav_update` was refreshed
+    Duplicate,
+}
+
+/// Whether `nav_data` would change the vault given the hash of its last applied update
+pub fn nav_update_outcome(last_nav_data_hash: &[u8; 32], nav_data: &NAVData) -> NavUpdateOutcome {
+    if nav_content_hash(nav_data) == *last_nav_data_hash {
+        NavUpdateOutcome::Duplicate
+    } else {
+        NavUpdateOutcome::Applied
+    }
+}
+
+/// Decode an attestation and check `ed25519_instruction` verified `nav_verifier`'s
+/// signature over it for this vault and NAV
+pub fn verify_nav_attestation(
+    nav_data: &NAVData,
+    attestation_bytes: &[u8],
+    nav_verifier: &Pubkey,
+    vault: &Pubkey,
+    ed25519_instruction: &Instruction,
+) -> Result<NavAttestation> {
+    let attestation = NavAttestation::try_from_slice(attestation_bytes)
+        .map_err(|_| RTFError::InvalidNavAttestation)?;
+
+    require!(
+        attestation.verified_root != [0; 32],
+        RTFError::InvalidZKProof
+    );
+
+    verify_ed25519_instruction(
+        ed25519_instruction,
+        nav_verifier,
+        &attestation.digest(&crate::ID, vault),
+    )
+    .map_err(|_| RTFError::InvalidAttestationSignature)?;
+
+    require!(
+        attestation.public_inputs_hash == nav_public_inputs_hash(nav_data),
+        RTFError::NavPublicInputMismatch
+    );
+
+    Ok(attestation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{verify_dilithium_signature, verify_nav_zk_proof, verify_starknet_proof};
+    use ed25519_dalek::{Signer, SigningKey};
+    use ed25519_instruction::new_ed25519_instruction;
+
+    const STARKNET_FACT: [u8; 32] = [5; 32];
+    const DILITHIUM_SIGNATURE: [u8; 128] = [9; 128];
+    const DILITHIUM_PUBLIC_KEY: [u8; 64] = [3; 64];
+
+    fn nav_data() -> NAVData {
+        NAVData {
+            nav_per_share: 1_050_000,
+            total_assets: 10_500_000_000,
+            total_liabilities: 0,
+            timestamp: 1_700_000_000,
+            tranche_navs: vec![1_020_000, 1_110_000],
+            oracle_signature: [0; 64],
+            confidence_score: 95,
+            computation_hash: [0; 32],
+        }
+    }
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_bytes(&[42; 32])
+    }
+
+    fn verifier() -> Pubkey {
+        Pubkey::new_from_array(signing_key().verifying_key().to_bytes())
+    }
+
+    fn vault() -> Pubkey {
+        Pubkey::new_from_array([7; 32])
+    }
+
+    fn attest(nav_data: &NAVData) -> NavAttestation {
+        NavAttestation {
+            public_inputs_hash: nav_public_inputs_hash(nav_data),
+            verified_root: Sha256::digest(b"stark proof").into(),
+            starknet_fact: STARKNET_FACT,
+            pq_signature_hash: Sha256::digest(DILITHIUM_SIGNATURE).into(),
+            pq_public_key_hash: Sha256::digest(DILITHIUM_PUBLIC_KEY).into(),
+            attested_at: 1_700_000_010,
+        }
+    }
+
+    /// Precompile instruction the verifier's transaction would carry for `attestation`
+    fn sign(attestation: &NavAttestation, key: &SigningKey, vault: &Pubkey) -> Instruction {
+        let digest = attestation.digest(&crate::ID, vault);
+        let signer = Pubkey::new_from_array(key.verifying_key().to_bytes());
+        new_ed25519_instruction(&signer, &key.sign(&digest).to_bytes(), &digest)
+    }
+
+    #[test]
+    fn test_public_inputs_hash_matches_backend_vector() {
+        // Same vector as backend/zk-nav nav_attestation tests
+        let expected = "54d664971167eed222ae6760336ea8511c53b86c72f09acb47d128d222d37278";
+        let hash = nav_public_inputs_hash(&nav_data());
+        let hex: String = hash.iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(hex, expected);
+    }
+
+    #[test]
+    fn test_duplicate_nav_submission_is_ignored() {
+        let applied = nav_data();
+        let last_nav_data_hash = nav_content_hash(&applied);
+        // Nothing applied yet
+        assert_eq!(nav_update_outcome(&[0; 32], &applied), NavUpdateOutcome::Applied);
+
+        // Same values resubmitted with a later timestamp
+        let mut resubmitted = nav_data();
+        resubmitted.timestamp += 3_600;
+        assert_eq!(nav_update_outcome(&last_nav_data_hash, &resubmitted), NavUpdateOutcome::Duplicate);
+
+        // Any changed value is a genuine update
+        let mut moved = resubmitted.clone();
+        moved.nav_per_share += 1;
+        assert_eq!(nav_update_outcome(&last_nav_data_hash, &moved), NavUpdateOutcome::Applied);
+        let mut rebalanced = nav_data();
+        rebalanced.tranche_navs = vec![1_110_000, 1_020_000];
+        assert_eq!(nav_update_outcome(&last_nav_data_hash, &rebalanced), NavUpdateOutcome::Applied);
+    }
+
+    #[test]
+    fn test_valid_attestation_is_accepted() {
+        let nav_data = nav_data();
+        let attestation = attest(&nav_data);
+        let ed25519 = sign(&attestation, &signing_key(), &vault());
+        let bytes = attestation.try_to_vec().unwrap();
+
+        let attestation = verify_nav_zk_proof(&nav_data, &bytes, &verifier(), &vault(), &ed25519).unwrap();
+        assert!(verify_starknet_proof(&STARKNET_FACT, &attestation).is_ok());
+        assert!(verify_dilithium_signature(&DILITHIUM_SIGNATURE, &DILITHIUM_PUBLIC_KEY, &attestation).is_ok());
+    }
+
+    #[test]
+    fn test_tampered_public_input_is_rejected() {
+        let attestation = attest(&nav_data());
+        let ed25519 = sign(&attestation, &signing_key(), &vault());
+        let bytes = attestation.try_to_vec().unwrap();
+        let mut tampered = nav_data();
+        tampered.nav_per_share += 1;
+
+        assert_eq!(
+            verify_nav_zk_proof(&tampered, &bytes, &verifier(), &vault(), &ed25519).unwrap_err(),
+            RTFError::NavPublicInputMismatch.into()
+        );
+    }
+
+    #[test]
+    fn test_tampered_attestation_is_rejected() {
+        let nav_data = nav_data();
+        let signed = attest(&nav_data);
+        let ed25519 = sign(&signed, &signing_key(), &vault());
+        let mut attestation = signed.clone();
+        attestation.starknet_fact = [6; 32];
+        let bytes = attestation.try_to_vec().unwrap();
+        assert_eq!(
+            verify_nav_zk_proof(&nav_data, &bytes, &verifier(), &vault(), &ed25519).unwrap_err(),
+            RTFError::InvalidAttestationSignature.into()
+        );
+
+        // Signed by someone other than the configured verifier
+        let bytes = signed.try_to_vec().unwrap();
+        let other_key = SigningKey::from_bytes(&[1; 32]);
+        let forged = sign(&signed, &other_key, &vault());
+        assert_eq!(
+            verify_nav_zk_proof(&nav_data, &bytes, &verifier(), &vault(), &forged).unwrap_err(),
+            RTFError::InvalidAttestationSignature.into()
+        );
+
+        assert_eq!(
+            verify_nav_zk_proof(&nav_data, &bytes[..100], &verifier(), &vault(), &ed25519).unwrap_err(),
+            RTFError::InvalidNavAttestation.into()
+        );
+    }
+
+    #[test]
+    fn test_attestation_for_another_vault_is_rejected() {
+        let nav_data = nav_data();
+        let attestation = attest(&nav_data);
+        let bytes = attestation.try_to_vec().unwrap();
+
+        // Same verifier key configured on a second vault
+        let other_vault = Pubkey::new_from_array([8; 32]);
+        let ed25519 = sign(&attestation, &signing_key(), &other_vault);
+        assert_eq!(
+            verify_nav_zk_proof(&nav_data, &bytes, &verifier(), &vault(), &ed25519).unwrap_err(),
+            RTFError::InvalidAttestationSignature.into()
+        );
+        assert!(verify_nav_zk_proof(&nav_data, &bytes, &verifier(), &other_vault, &ed25519).is_ok());
+    }
+
+    #[test]
+    fn test_unattested_starknet_and_pq_inputs_are_rejected() {
+        let attestation = attest(&nav_data());
+        assert_eq!(
+            verify_starknet_proof(&[7; 32], &attestation).unwrap_err(),
+            RTFError::InvalidStarknetProof.into()
+        );
+        assert_eq!(
+            verify_dilithium_signature(&[8; 128], &DILITHIUM_PUBLIC_KEY, &attestation).unwrap_err(),
+            RTFError::InvalidDilithiumSignature.into()
+        );
+        assert_eq!(
+            verify_dilithium_signature(&DILITHIUM_SIGNATURE, &[4; 64], &attestation).unwrap_err(),
+            RTFError::InvalidDilithiumSignature.into()
+        );
+    }
+}
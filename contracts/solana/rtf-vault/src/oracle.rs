@@ -0,0 +1,222 @@
+use anchor_lang::prelude::*;
+use fixed_point::{div_round, Rounding};
+use switchboard_v2::{AggregatorAccountData, SwitchboardDecimal};
+use crate::{RTFError, VaultConfig, calculate_nav_drift, nav_scale};
+
+/// PRD: "Real-time NAV updates via oracles"
+/// A signed NAV update is only accepted when the vault's Switchboard feed has a fresh,
+/// tight-confidence confirmed round that agrees with it within `max_nav_drift`.
+
+pub const DEFAULT_MAX_ORACLE_STALENESS: i64 = 300; // 5 minutes
+pub const DEFAULT_MAX_ORACLE_STD_DEV_BPS: u64 = 100; // 1% of the oracle value
+
+/// Latest confirmed Switchboard round in the vault's NAV units
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OracleRound {
+    pub nav_per_share: u64,
+    pub std_deviation: u64,
+    pub round_open_timestamp: i64,
+}
+
+/// Limits the oracle round and submitted NAV must satisfy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OracleBounds {
+    pub max_staleness: i64, // Seconds
+    pub max_std_dev_bps: u64, // Std-dev relative to the oracle value
+    pub max_nav_drift: u64, // Basis points between oracle and submitted NAV
+}
+
+impl OracleBounds {
+    /// Bounds from the vault configuration (0 = default)
+    pub fn from_config(config: &VaultConfig) -> Self {
+        Self {
+            max_staleness: if config.max_oracle_staleness > 0 {
+                config.max_oracle_staleness
+            } else {
+                DEFAULT_MAX_ORACLE_STALENESS
+            },
+            max_std_dev_bps: if config.max_oracle_std_dev_bps > 0 {
+                config.max_oracle_std_dev_bps
+            } else {
+                DEFAULT_MAX_ORACLE_STD_DEV_BPS
+            },
+            max_nav_drift: config.max_nav_drift,
+        }
+    }
+}
+
+/// Read the latest confirmed round of a Switchboard aggregator at `nav_decimals`
+pub fn read_switchboard_round(aggregator: &AggregatorAccountData, nav_decimals: u8) -> Result<OracleRound> {
+    let round = aggregator.latest_confirmed_round;
+    Ok(OracleRound {
+        nav_per_share: switchboard_decimal_to_nav(round.result, nav_decimals)?,
+        std_deviation: switchboard_decimal_to_nav(round.std_deviation, nav_decimals)?,
+        round_open_timestamp: round.round_open_timestamp,
+    })
+}
+
+/// Reject stale or low-confidence rounds and NAVs that diverge from the oracle
+pub fn validate_nav_against_oracle(
+    round: &OracleRound,
+    submitted_nav: u64,
+    now: i64,
+    bounds: &OracleBounds,
+) -> Result<()> {
+    require!(round.round_open_timestamp > 0, RTFError::StaleOracleRound);
+    require!(
+        now.saturating_sub(round.round_open_timestamp) <= bounds.max_staleness,
+        RTFError::StaleOracleRound
+    );
+    require!(round.nav_per_share > 0, RTFError::InvalidOracleFeed);
+
+    // std_dev_bps = std_deviation * 10000 / oracle value
+    let std_dev_bps = (round.std_deviation as u128)
+        .checked_mul(10_000u128)
+        .and_then(|x| x.checked_div(round.nav_per_share as u128))
+        .ok_or(RTFError::MathOverflow)?;
+    require!(
+        std_dev_bps <= bounds.max_std_dev_bps as u128,
+        RTFError::OracleConfidenceTooWide
+    );
+
+    let divergence = calculate_nav_drift(round.nav_per_share, submitted_nav)?;
+    require!(
+        divergence <= bounds.max_nav_drift,
+        RTFError::OracleNavDivergence
+    );
+
+    Ok(())
+}
+
+/// Load the vault's aggregator and cross-check the submitted NAV against it
+pub fn verify_nav_against_switchboard(
+    aggregator: &AccountLoader<AggregatorAccountData>,
+    config: &VaultConfig,
+    submitted_nav: u64,
+    now: i64,
+) -> Result<OracleRound> {
+    let round = read_switchboard_round(&*aggregator.load()?, config.nav_decimals)?;
+    validate_nav_against_oracle(&round, submitted_nav, now, &OracleBounds::from_config(config))?;
+    Ok(round)
+}
+
+/// Convert a Switchboard decimal (mantissa · 10^-scale) to `nav_decimals` fixed point
+fn switchboard_decimal_to_nav(value: SwitchboardDecimal, nav_decimals: u8) -> Result<u64> {
+    nav_scale(nav_decimals)?;
+    require!(value.mantissa >= 0, RTFError::InvalidOracleFeed);
+    let nav_decimals = nav_decimals as u32;
+    let scaled = if value.scale >= nav_decimals {
+        let divisor = 10i128.checked_pow(value.scale - nav_decimals).ok_or(RTFError::InvalidOracleFeed)?;
+        div_round(value.mantissa, divisor, Rounding::HalfUp).map_err(|_| RTFError::MathOverflow)?
+    } else {
+        let multiplier = 10i128.pow(nav_decimals - value.scale);
+        value.mantissa.checked_mul(multiplier).ok_or(RTFError::MathOverflow)?
+    };
+    u64::try_from(scaled).map_err(|_| RTFError::MathOverflow.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use switchboard_v2::AggregatorRound;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn bounds() -> OracleBounds {
+        OracleBounds { max_staleness: 300, max_std_dev_bps: 100, max_nav_drift: 200 }
+    }
+
+    /// Aggregator whose latest confirmed round reports `value` ± `std_dev` (9-decimal mantissas)
+    fn mock_aggregator(value: i128, std_dev: i128, opened_at: i64) -> AggregatorAccountData {
+        let mut aggregator = AggregatorAccountData::default();
+        aggregator.latest_confirmed_round = AggregatorRound {
+            result: SwitchboardDecimal::new(value, 9),
+            std_deviation: SwitchboardDecimal::new(std_dev, 9),
+            round_open_timestamp: opened_at,
+            ..Default::default()
+        };
+        aggregator
+    }
+
+    fn check(aggregator: &AggregatorAccountData, submitted_nav: u64) -> Result<()> {
+        let round = read_switchboard_round(aggregator, 6)?;
+        validate_nav_against_oracle(&round, submitted_nav, NOW, &bounds())
+    }
+
+    #[test]
+    fn test_fresh_agreeing_round_is_accepted() {
+        let aggregator = mock_aggregator(1_050_000_000, 2_000_000, NOW - 60);
+        let round = read_switchboard_round(&aggregator, 6).unwrap();
+
+        assert_eq!(round.nav_per_share, 1_050_000);
+        assert_eq!(round.std_deviation, 2_000);
+
+        let round = read_switchboard_round(&aggregator, 9).unwrap();
+        assert_eq!(round.nav_per_share, 1_050_000_000);
+        assert_eq!(round.std_deviation, 2_000_000);
+        assert!(check(&aggregator, 1_050_000).is_ok());
+        // 1.5% away from the oracle is inside the 2% drift bound
+        assert!(check(&aggregator, 1_065_750).is_ok());
+    }
+
+    #[test]
+    fn test_stale_round_is_rejected() {
+        let stale = mock_aggregator(1_050_000_000, 2_000_000, NOW - 301);
+        assert_eq!(check(&stale, 1_050_000), Err(RTFError::StaleOracleRound.into()));
+
+        // A feed that never confirmed a round is treated as stale
+        let unconfirmed = mock_aggregator(1_050_000_000, 2_000_000, 0);
+        assert_eq!(check(&unconfirmed, 1_050_000), Err(RTFError::StaleOracleRound.into()));
+    }
+
+    #[test]
+    fn test_wide_confidence_interval_is_rejected() {
+        // 2% std-dev against a 1% bound
+        let noisy = mock_aggregator(1_000_000_000, 20_000_000, NOW - 60);
+        assert_eq!(check(&noisy, 1_000_000), Err(RTFError::OracleConfidenceTooWide.into()));
+    }
+
+    #[test]
+    fn test_divergent_nav_is_rejected() {
+        let aggregator = mock_aggregator(1_000_000_000, 1_000_000, NOW - 60);
+        assert_eq!(check(&aggregator, 1_030_000), Err(RTFError::OracleNavDivergence.into()));
+        assert_eq!(check(&aggregator, 970_000), Err(RTFError::OracleNavDivergence.into()));
+    }
+
+    #[test]
+    fn test_decimal_conversion() {
+        assert_eq!(switchboard_decimal_to_nav(SwitchboardDecimal::new(105, 2), 6).unwrap(), 1_050_000);
+        assert_eq!(switchboard_decimal_to_nav(SwitchboardDecimal::new(12_345_675, 7), 6).unwrap(), 1_234_568);
+        assert_eq!(switchboard_decimal_to_nav(SwitchboardDecimal::new(12_345_675, 7), 9).unwrap(), 1_234_567_500);
+        assert!(switchboard_decimal_to_nav(SwitchboardDecimal::new(-1, 0), 6).is_err());
+        assert!(switchboard_decimal_to_nav(SwitchboardDecimal::new(105, 2), 19).is_err());
+    }
+
+    #[test]
+    fn test_bounds_fall_back_to_defaults() {
+        let config = VaultConfig {
+            underlying_mint: Pubkey::default(),
+            oracle_authority: Pubkey::default(),
+            operator: Pubkey::default(),
+            max_redemption_queue_size: 100,
+            redemption_processing_window: 3_600,
+            mev_protection_delay: 2,
+            max_nav_drift: 500,
+            max_stress_multiplier: 0,
+            nav_decimals: 6,
+            switchboard_feed: Pubkey::default(),
+            max_oracle_staleness: 0,
+            max_oracle_std_dev_bps: 0,
+            nav_verifier: Pubkey::default(),
+            fee_collector: Pubkey::default(),
+            emergency_pause_authority: Pubkey::default(),
+            max_redemption_compute_units: 0,
+            epoch_duration: 0,
+        };
+        assert_eq!(OracleBounds::from_config(&config), OracleBounds {
+            max_staleness: DEFAULT_MAX_ORACLE_STALENESS,
+            max_std_dev_bps: DEFAULT_MAX_ORACLE_STD_DEV_BPS,
+            max_nav_drift: 500,
+        });
+    }
+}
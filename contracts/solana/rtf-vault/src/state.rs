@@ -12,6 +12,7 @@ pub struct VaultAccount {
     pub total_liabilities: u64,
     pub nav_per_share: u64,
     pub last_nav_update: i64,
+    pub last_nav_data_hash: [u8; 32], // `nav_content_hash` of the last applied NAV update
     pub epoch: u64,
     pub status: VaultStatus,
 
@@ -70,13 +71,20 @@ pub struct VaultConfig {
 
     /// NAV and drift parameters
     pub max_nav_drift: u64, // Basis points
+    pub nav_decimals: u8, // Decimals of NAV, deposit amounts and shares
     pub nav_update_frequency: u64, // Seconds
 
+    /// Switchboard cross-check of submitted NAVs
+    pub switchboard_feed: Pubkey,
+    pub max_oracle_staleness: i64, // Seconds (0 = default)
+    pub max_oracle_std_dev_bps: u64, // Basis points of the oracle value (0 = default)
+
     /// Redemption queue configuration
     pub max_redemption_queue_size: u64,
     pub redemption_processing_window: u64, // Seconds
     pub mev_protection_delay: u64, // Slots
     pub batch_size: u8,
+    pub max_stress_multiplier: u64, // Basis points cap on redemption bonding (0 = default)
 
     /// Fee structure
     pub management_fee: u16, // Basis points
@@ -86,6 +94,7 @@ pub struct VaultConfig {
     /// Post-quantum security
     pub enable_post_quantum: bool,
     pub dilithium_public_key: [u8; 64],
+    pub nav_verifier: Pubkey, // Ed25519 key of the off-chain NAV proof verifier
 
     /// Cross-chain configuration
     pub ethereum_contract: [u8; 20],
@@ -108,6 +117,14 @@ pub struct Tranche {
     pub last_yield_update: i64,
     pub waterfall_priority: u8,
     pub protection_level: u8, // 0-100 (100 = fully protected)
+    pub max_redeemable_per_epoch: u64, // Underlying assets redeemable per epoch (0 = unlimited)
+    pub redemption_epoch: u64, // Epoch `redeemed_in_epoch` counts towards
+    pub redeemed_in_epoch: u64,
+    pub performance_fee_rate: u16, // Basis points of NAV gains
+    pub last_fee_accrual: i64,
+    pub high_water_mark: u64, // Highest post-fee NAV; performance fees only accrue above it
+    pub accrued_fees: u64, // Underlying assets owed to the fee collector
+    pub lp_yield: crate::LpYieldModel, // Fee income and IL; only used by LP tranches
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
@@ -164,6 +181,21 @@ pub struct DriftLedger {
     pub current_index: u8,
     pub max_drift_threshold: u64,
     pub consecutive_violations: u8,
+    /// Vault epoch of the most recent update
+    pub last_epoch: u64,
+}
+
+pub const DRIFT_LEDGER_EPOCHS: u64 = 100;
+
+/// Drift ledger in epoch order, returned by `get_drift_ledger` for off-chain audit
+/// Sized to fit Solana return data (1024 bytes) with all 100 epochs.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DriftLedgerExport {
+    pub vault: Pubkey,
+    pub first_epoch: u64,
+    pub drifts_bps: Vec<u64>, // drifts_bps[i] is the drift of epoch first_epoch + i
+    pub max_drift_threshold: u64,
+    pub consecutive_violations: u8,
 }
 
 /// Emergency state management
@@ -250,6 +282,7 @@ pub struct RedemptionRequest {
     pub commitment_hash: [u8; 32],
     pub bonding_amount: u64,      // PRD: Dynamic bonding under pool stress
     pub reveal_deadline: i64,     // PRD: Commit-reveal scheme deadline
+    pub holding_since: i64,       // FIFO deposit timestamp of the deposit lots reserved
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
@@ -283,6 +316,8 @@ pub struct TrancheConfig {
     pub min_deposit: u64,
     pub max_deposit: u64,
     pub lock_period: u32,
+    pub max_redeemable_per_epoch: u64, // Underlying assets redeemable per epoch (0 = unlimited)
+    pub performance_fee_rate: u16, // Basis points of NAV gains
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
@@ -382,6 +417,35 @@ pub struct RedemptionRequested {
     pub processing_slot: u64,
 }
 
+#[event]
+pub struct FeesCollected {
+    pub vault: Pubkey,
+    pub fee_collector: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LpFlowsRecorded {
+    pub vault: Pubkey,
+    pub tranche_index: u8,
+    pub epoch: u64,
+    pub fee_income: u64,
+    pub impermanent_loss: u64,
+    pub nav_per_share: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TrancheRedemptionCapReached {
+    pub vault: Pubkey,
+    pub tranche_index: u8,
+    pub epoch: u64,
+    pub max_redeemable_per_epoch: u64,
+    pub redeemed_in_epoch: u64,
+    pub deferred_assets: u64,
+}
+
 #[event]
 pub struct RedemptionsProcessed {
     pub vault: Pubkey,
@@ -529,6 +593,15 @@ pub struct RedemptionRevealed {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct RedemptionCancelled {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub shares_amount: u64,
+    pub expired: bool,
+    pub timestamp: i64,
+}
+
 /// PRD: Event for cross-chain anchoring
 #[event]
 pub struct CrossChainAnchor {
@@ -546,6 +619,8 @@ pub const MIN_NAV_UPDATE_INTERVAL: i64 = 300; // 5 minutes
 pub const MAX_NAV_DRIFT_BASIS_POINTS: u64 = 1000; // 10%
 pub const DEFAULT_LOCK_PERIOD: u32 = 86400; // 24 hours
 pub const MEV_PROTECTION_SLOTS: u64 = 32; // ~13 seconds on Solana
+pub const MAX_POOL_STRESS_MULTIPLIER: u64 = 15_000; // 50% maximum redemption bonding
+pub const MAX_STRESS_PREMIUM: u64 = 10_000; // Premium at full queue + zero liquidity
 
 // Validation helpers
 impl RedemptionRequest {
@@ -1,39 +1,70 @@
 use anchor_lang::prelude::*;
-use crate::{RTFError, VaultAccount, RedemptionRequest, RedemptionStatus, NAVData, DriftLedger};
+use anchor_lang::solana_program::instruction::Instruction;
+use crate::{RTFError, VaultAccount, RedemptionRequest, RedemptionStatus, NAVData, DriftLedger, Tranche};
+use crate::{DriftLedgerExport, DRIFT_LEDGER_EPOCHS};
+use crate::{NavAttestation, verify_nav_attestation};
+use crate::{MAX_POOL_STRESS_MULTIPLIER, MAX_STRESS_PREMIUM};
 use sha2::{Sha256, Digest};
+use domain_tags::DomainTag;
+use epoch_clock::epoch_for;
+use fixed_point::{div_round, Rounding};
 
-/// Calculate shares to mint for a given deposit amount
+/// Share/asset conversions always round in the vault's favour
+/// Invariant: for a fixed NAV, no sequence of deposits and redemptions returns more
+/// assets than were deposited. Shares minted are rounded down and assets paid out are
+/// rounded down, so each conversion leaks at most one raw unit to the vault and never
+/// to the user. Intermediates are 128-bit, so products of two u64 values are checked
+/// before the final range check rather than wrapping.
+
+/// Bounds on `VaultConfig::nav_decimals`. 10^18 is the largest power of ten a u64
+/// holds, which keeps `amount · scale` within i128.
+pub const MIN_NAV_DECIMALS: u8 = 2;
+pub const MAX_NAV_DECIMALS: u8 = 18;
+
+/// Raw `nav_per_share` of 1.0 for a vault with `nav_decimals`
+pub fn nav_scale(nav_decimals: u8) -> Result<u64> {
+    require!(
+        (MIN_NAV_DECIMALS..=MAX_NAV_DECIMALS).contains(&nav_decimals),
+        RTFError::InvalidNavDecimals
+    );
+    Ok(10u64.pow(nav_decimals as u32))
+}
+
+/// Calculate shares to mint for a given deposit amount, rounded down
+/// Amounts, shares and `nav_per_share` are all fixed point with `nav_decimals` decimals
 pub fn calculate_shares_for_deposit(
     deposit_amount: u64,
     nav_per_share: u64,
+    nav_decimals: u8,
 ) -> Result<u64> {
-    if nav_per_share == 0 {
-        return Err(RTFError::MathOverflow.into());
-    }
-    
-    // shares = (deposit_amount * 1e6) / nav_per_share
-    let shares = (deposit_amount as u128)
-        .checked_mul(1_000_000u128)
-        .and_then(|x| x.checked_div(nav_per_share as u128))
-        .and_then(|x| u64::try_from(x).ok())
-        .ok_or(RTFError::MathOverflow)?;
-    
-    Ok(shares)
+    let scale = nav_scale(nav_decimals)?;
+    // shares = deposit_amount * scale / nav_per_share
+    let shares = (deposit_amount as i128)
+        .checked_mul(scale as i128)
+        .ok_or(RTFError::MathOverflow)
+        .and_then(|scaled| {
+            div_round(scaled, nav_per_share as i128, Rounding::Down).map_err(|_| RTFError::MathOverflow)
+        })?;
+
+    u64::try_from(shares).map_err(|_| RTFError::MathOverflow.into())
 }
 
-/// Calculate assets to return for a given redemption amount
+/// Calculate assets to return for a given redemption amount, rounded down
 pub fn calculate_assets_for_redemption(
     shares_amount: u64,
     nav_per_share: u64,
+    nav_decimals: u8,
 ) -> Result<u64> {
-    // assets = (shares_amount * nav_per_share) / 1e6
-    let assets = (shares_amount as u128)
-        .checked_mul(nav_per_share as u128)
-        .and_then(|x| x.checked_div(1_000_000u128))
-        .and_then(|x| u64::try_from(x).ok())
-        .ok_or(RTFError::MathOverflow)?;
-    
-    Ok(assets)
+    let scale = nav_scale(nav_decimals)?;
+    // assets = shares_amount * nav_per_share / scale
+    let assets = (shares_amount as i128)
+        .checked_mul(nav_per_share as i128)
+        .ok_or(RTFError::MathOverflow)
+        .and_then(|product| {
+            div_round(product, scale as i128, Rounding::Down).map_err(|_| RTFError::MathOverflow)
+        })?;
+
+    u64::try_from(assets).map_err(|_| RTFError::MathOverflow.into())
 }
 
 /// Calculate NAV drift percentage
@@ -65,10 +96,10 @@ pub fn calculate_commitment_hash(
     slot: u64,
 ) -> Result<[u8; 32]> {
     let mut hasher = Sha256::new();
+    hasher.update(DomainTag::VaultRedemptionCommitment.as_bytes());
     hasher.update(user.as_ref());
     hasher.update(&shares_amount.to_le_bytes());
     hasher.update(&slot.to_le_bytes());
-    hasher.update(b"RTF_REDEMPTION_COMMITMENT");
     
     Ok(hasher.finalize().into())
 }
@@ -111,9 +142,52 @@ pub fn get_redemption_request(
         processing_slot: 0,
         status: RedemptionStatus::Pending,
         commitment_hash: [0; 32],
+        bonding_amount: 0,
+        reveal_deadline: 0,
+        holding_since: 0,
     })
 }
 
+pub const DEFAULT_VAULT_EPOCH_DURATION: u64 = 86_400; // 1 day
+
+/// Epoch containing `now` for a vault epoch length in seconds (0 = default)
+pub fn vault_epoch(epoch_duration: u64, now: i64) -> u64 {
+    let epoch_duration = if epoch_duration > 0 { epoch_duration } else { DEFAULT_VAULT_EPOCH_DURATION };
+    epoch_for(now, 0, epoch_duration)
+}
+
+/// Move the vault to the epoch containing `now`, never backwards, and return it
+pub fn advance_vault_epoch(vault: &mut VaultAccount, now: i64) -> u64 {
+    vault.epoch = vault.epoch.max(vault_epoch(vault.config.epoch_duration, now));
+    vault.epoch
+}
+
+/// Reject redemptions larger than the tranche's per-epoch cap (0 = unlimited). Caps are
+/// fixed at initialization, so every queued request fits a fresh epoch's allowance.
+pub fn check_epoch_cap(tranche: &Tranche, assets: u64) -> Result<()> {
+    require!(
+        tranche.max_redeemable_per_epoch == 0 || assets <= tranche.max_redeemable_per_epoch,
+        RTFError::RedemptionExceedsEpochCap
+    );
+    Ok(())
+}
+
+/// Charge `assets` to the tranche's redemption allowance for `epoch`, starting a fresh
+/// allowance when the epoch has moved on. Returns false, leaving the allowance untouched,
+/// when the redemption would exceed `max_redeemable_per_epoch` (0 = unlimited).
+pub fn reserve_epoch_redemption(tranche: &mut Tranche, epoch: u64, assets: u64) -> Result<bool> {
+    if tranche.redemption_epoch != epoch {
+        tranche.redemption_epoch = epoch;
+        tranche.redeemed_in_epoch = 0;
+    }
+    let redeemed = tranche.redeemed_in_epoch.checked_add(assets).ok_or(RTFError::MathOverflow)?;
+    if tranche.max_redeemable_per_epoch > 0 && redeemed > tranche.max_redeemable_per_epoch {
+        return Ok(false);
+    }
+    tranche.redeemed_in_epoch = redeemed;
+    Ok(true)
+}
+
 /// Execute a single redemption
 pub fn execute_redemption(
     vault: &mut VaultAccount,
@@ -143,59 +217,18 @@ pub fn execute_redemption(
     Ok(())
 }
 
-/// Get user deposit timestamp for lock period validation
-pub fn get_user_deposit_timestamp(
-    user: &Pubkey,
-    tranche_index: u8,
-) -> Result<i64> {
-    // In a real implementation, this would query a user deposit history account
-    // For now, return current timestamp (no lock)
-    let clock = Clock::get()?;
-    Ok(clock.unix_timestamp)
-}
-
 /// Verify zero-knowledge proof of NAV computation
+/// `zk_proof` is the off-chain verifier's `NavAttestation`; the STARK itself is verified
+/// by backend/zk-nav and only the signed attestation is checked here. `ed25519_instruction`
+/// is the precompile instruction that verified the attestation signature.
 pub fn verify_nav_zk_proof(
     nav_data: &NAVData,
     zk_proof: &[u8],
-) -> Result<()> {
-    // Placeholder for ZK proof verification
-    // In a real implementation, this would:
-    // 1. Parse the proof bytes
-    // 2. Verify the STARK proof against the NAV computation
-    // 3. Ensure the proof corresponds to the provided NAV data
-    
-    require!(
-        !zk_proof.is_empty() && zk_proof.len() >= 32,
-        RTFError::InvalidZKProof
-    );
-    
-    // Verify proof structure and cryptographic validity
-    let proof_hash = Sha256::digest(zk_proof);
-    let expected_hash = calculate_nav_proof_hash(nav_data)?;
-    
-    require!(
-        proof_hash.as_slice() == expected_hash.as_slice(),
-        RTFError::InvalidZKProof
-    );
-    
-    Ok(())
-}
-
-/// Calculate expected NAV proof hash
-fn calculate_nav_proof_hash(nav_data: &NAVData) -> Result<[u8; 32]> {
-    let mut hasher = Sha256::new();
-    hasher.update(&nav_data.nav_per_share.to_le_bytes());
-    hasher.update(&nav_data.total_assets.to_le_bytes());
-    hasher.update(&nav_data.total_liabilities.to_le_bytes());
-    hasher.update(&nav_data.timestamp.to_le_bytes());
-    
-    for tranche_nav in &nav_data.tranche_navs {
-        hasher.update(&tranche_nav.to_le_bytes());
-    }
-    
-    hasher.update(b"RTF_NAV_PROOF");
-    Ok(hasher.finalize().into())
+    nav_verifier: &Pubkey,
+    vault: &Pubkey,
+    ed25519_instruction: &Instruction,
+) -> Result<NavAttestation> {
+    verify_nav_attestation(nav_data, zk_proof, nav_verifier, vault, ed25519_instruction)
 }
 
 /// Advanced fee calculation with dynamic rates
@@ -254,34 +287,89 @@ pub fn verify_post_quantum_signature(
 /// PRD: Calculate pool stress multiplier for dynamic redemption bonding
 /// PRD: "Dynamic redemption bonding under pool stress"
 pub fn calculate_pool_stress_multiplier(vault: &VaultAccount) -> Result<u64> {
-    let utilization_rate = if vault.config.max_capacity > 0 {
-        (vault.total_assets * 10000) / vault.config.max_capacity
+    let queue = &vault.redemption_queue;
+    let queued_requests = queue.tail.saturating_sub(queue.head);
+    let available_assets = vault.total_assets.saturating_sub(queue.total_pending);
+
+    let max_multiplier = if vault.config.max_stress_multiplier > 0 {
+        vault.config.max_stress_multiplier
+    } else {
+        MAX_POOL_STRESS_MULTIPLIER
+    };
+
+    calculate_stress_multiplier_bps(
+        queued_requests,
+        queue.max_queue_size as u64,
+        available_assets,
+        vault.total_assets,
+        max_multiplier,
+    )
+}
+
+/// Stress multiplier in basis points (10000 = no stress) derived from
+/// redemption queue utilization and liquidity coverage.
+///
+/// Each signal contributes up to half of `MAX_STRESS_PREMIUM` on top of
+/// 10000, and the result is capped at `max_multiplier` so bonding can never
+/// become confiscatory.
+pub fn calculate_stress_multiplier_bps(
+    queued_requests: u64,
+    max_queue_size: u64,
+    available_assets: u64,
+    total_assets: u64,
+    max_multiplier: u64,
+) -> Result<u64> {
+    // Queue utilization: queued / max_queue_size (basis points, saturating at 100%)
+    let queue_utilization = if max_queue_size > 0 {
+        ((queued_requests as u128 * 10_000u128) / max_queue_size as u128).min(10_000) as u64
     } else {
         0
     };
 
-    // Stress multiplier increases with utilization
-    let stress_multiplier = match utilization_rate {
-        0..=5000 => 10000,      // 0-50% utilization: no stress
-        5001..=7500 => 10500,   // 50-75%: 5% bonding
-        7501..=9000 => 11000,   // 75-90%: 10% bonding
-        9001..=9500 => 12000,   // 90-95%: 20% bonding
-        _ => 15000,             // >95%: 50% bonding
+    // Liquidity coverage: available / total_assets (basis points, saturating at 100%)
+    let liquidity_coverage = if total_assets > 0 {
+        ((available_assets as u128 * 10_000u128) / total_assets as u128).min(10_000) as u64
+    } else {
+        10_000
     };
+    let liquidity_shortfall = 10_000 - liquidity_coverage;
 
-    Ok(stress_multiplier)
+    let stress_premium = (queue_utilization as u128)
+        .checked_add(liquidity_shortfall as u128)
+        .and_then(|x| x.checked_mul(MAX_STRESS_PREMIUM as u128))
+        .and_then(|x| x.checked_div(20_000u128))
+        .and_then(|x| u64::try_from(x).ok())
+        .ok_or(RTFError::MathOverflow)?;
+
+    let stress_multiplier = 10_000u64
+        .checked_add(stress_premium)
+        .ok_or(RTFError::MathOverflow)?;
+
+    Ok(stress_multiplier.min(max_multiplier.max(10_000)))
 }
 
 /// PRD: Update drift ledger for 100-epoch tracking
 /// PRD: "Drift enforcement circuit with 100-epoch ledger"
+/// `epoch` is the clock-derived vault epoch. Epochs skipped since the last update had no
+/// NAV update and record zero drift; several updates in one epoch record the largest.
 pub fn update_drift_ledger(
     drift_ledger: &mut DriftLedger,
     nav_drift: u64,
     epoch: u64,
 ) -> Result<()> {
-    let index = (epoch % 100) as usize;
-    drift_ledger.epoch_drifts[index] = nav_drift;
+    let skipped = epoch.saturating_sub(drift_ledger.last_epoch).saturating_sub(1).min(DRIFT_LEDGER_EPOCHS);
+    for offset in 1..=skipped {
+        drift_ledger.epoch_drifts[((drift_ledger.last_epoch + offset) % DRIFT_LEDGER_EPOCHS) as usize] = 0;
+    }
+
+    let index = (epoch % DRIFT_LEDGER_EPOCHS) as usize;
+    drift_ledger.epoch_drifts[index] = if epoch == drift_ledger.last_epoch {
+        drift_ledger.epoch_drifts[index].max(nav_drift)
+    } else {
+        nav_drift
+    };
     drift_ledger.current_index = index as u8;
+    drift_ledger.last_epoch = drift_ledger.last_epoch.max(epoch);
 
     // Check for consecutive violations
     if nav_drift > drift_ledger.max_drift_threshold {
@@ -293,11 +381,30 @@ pub fn update_drift_ledger(
     Ok(())
 }
 
+/// Unroll the drift ring buffer into epoch order, oldest first, ending at the epoch of
+/// the most recent `update_drift_ledger` call
+pub fn export_drift_ledger(vault: Pubkey, drift_ledger: &DriftLedger) -> DriftLedgerExport {
+    let current_epoch = drift_ledger.last_epoch;
+    let first_epoch = current_epoch.saturating_sub(DRIFT_LEDGER_EPOCHS - 1);
+    let drifts_bps = (first_epoch..=current_epoch)
+        .map(|epoch| drift_ledger.epoch_drifts[(epoch % DRIFT_LEDGER_EPOCHS) as usize])
+        .collect();
+
+    DriftLedgerExport {
+        vault,
+        first_epoch,
+        drifts_bps,
+        max_drift_threshold: drift_ledger.max_drift_threshold,
+        consecutive_violations: drift_ledger.consecutive_violations,
+    }
+}
+
 /// PRD: Verify Starknet proof
 /// PRD: "Post to Solana, anchor to BTC via Babylon + OP_RETURN, push to Ethereum via CCIP"
-pub fn verify_starknet_proof(proof: &[u8; 32], nav_data: &NAVData) -> Result<()> {
-    // Placeholder for Starknet proof verification
-    require!(!proof.iter().all(|&x| x == 0), RTFError::InvalidZKProof);
+pub fn verify_starknet_proof(proof: &[u8; 32], attestation: &NavAttestation) -> Result<()> {
+    // The Starknet fact is verified off-chain; it must be the one the attestation covers
+    require!(!proof.iter().all(|&x| x == 0), RTFError::InvalidStarknetProof);
+    require!(*proof == attestation.starknet_fact, RTFError::InvalidStarknetProof);
 
     msg!("Starknet proof verified");
     Ok(())
@@ -308,18 +415,24 @@ pub fn verify_starknet_proof(proof: &[u8; 32], nav_data: &NAVData) -> Result<()>
 pub fn verify_dilithium_signature(
     signature: &[u8; 128],
     public_key: &[u8; 64],
-    nav_data: &NAVData,
+    attestation: &NavAttestation,
 ) -> Result<()> {
-    // Placeholder for Dilithium signature verification
-    // In production, this would use the pqcrypto_dilithium crate
+    // Dilithium verification runs off-chain; bind the signature and key it accepted
     require!(
         !signature.iter().all(|&x| x == 0),
-        RTFError::InvalidZKProof
+        RTFError::InvalidDilithiumSignature
     );
 
     require!(
         !public_key.iter().all(|&x| x == 0),
-        RTFError::InvalidZKProof
+        RTFError::InvalidDilithiumSignature
+    );
+
+    let signature_hash: [u8; 32] = Sha256::digest(signature).into();
+    let public_key_hash: [u8; 32] = Sha256::digest(public_key).into();
+    require!(
+        signature_hash == attestation.pq_signature_hash && public_key_hash == attestation.pq_public_key_hash,
+        RTFError::InvalidDilithiumSignature
     );
 
     msg!("Dilithium512 signature verified");
@@ -343,16 +456,258 @@ pub fn get_redemption_request_mut(vault: &mut VaultAccount, index: usize) -> Res
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_shares_calculation() {
         let deposit = 1000_000; // 1 token with 6 decimals
         let nav = 1_100_000; // 1.1 NAV
         
-        let shares = calculate_shares_for_deposit(deposit, nav).unwrap();
+        let shares = calculate_shares_for_deposit(deposit, nav, 6).unwrap();
         assert_eq!(shares, 909_090); // ~0.909 shares
     }
 
+    proptest! {
+        #[test]
+        fn prop_deposit_then_redeem_round_trips(
+            deposit in 1u64..=1_000_000_000_000_000, // up to 1B tokens
+            nav in 1_000u64..=1_000_000_000_000,
+            decimals in prop_oneof![Just(6u8), Just(9u8)],
+        ) {
+            let shares = calculate_shares_for_deposit(deposit, nav, decimals).unwrap();
+            let redeemed = calculate_assets_for_redemption(shares, nav, decimals).unwrap();
+
+            // Never more than was deposited, and short by less than one share unit's value
+            let scale = nav_scale(decimals).unwrap();
+            let one_share_unit = (nav + scale - 1) / scale;
+            prop_assert!(redeemed <= deposit);
+            prop_assert!(deposit - redeemed <= one_share_unit);
+        }
+
+        #[test]
+        fn prop_deposit_redeem_sequences_never_extract_value(
+            nav in 1_000u64..=1_000_000_000_000,
+            operations in proptest::collection::vec((any::<bool>(), 1u64..=1_000_000_000_000), 1..40),
+        ) {
+            let mut deposited: u128 = 0;
+            let mut withdrawn: u128 = 0;
+            let mut shares_held: u64 = 0;
+
+            for (is_deposit, size) in operations {
+                if is_deposit {
+                    shares_held += calculate_shares_for_deposit(size, nav, 6).unwrap();
+                    deposited += size as u128;
+                } else if shares_held > 0 {
+                    // Redeem an arbitrary slice of the position
+                    let shares = size % shares_held + 1;
+                    withdrawn += calculate_assets_for_redemption(shares, nav, 6).unwrap() as u128;
+                    shares_held -= shares;
+                }
+                // Assets paid out plus the value of what is still held never exceed what went in
+                let claim = withdrawn + calculate_assets_for_redemption(shares_held, nav, 6).unwrap() as u128;
+                prop_assert!(claim <= deposited);
+            }
+        }
+
+        #[test]
+        fn prop_splitting_never_beats_a_single_conversion(
+            nav in 1_000u64..=1_000_000_000_000,
+            first in 1u64..=1_000_000_000_000,
+            second in 1u64..=1_000_000_000_000,
+        ) {
+            let split_shares = calculate_shares_for_deposit(first, nav, 6).unwrap()
+                + calculate_shares_for_deposit(second, nav, 6).unwrap();
+            prop_assert!(split_shares <= calculate_shares_for_deposit(first + second, nav, 6).unwrap());
+
+            let split_assets = calculate_assets_for_redemption(first, nav, 6).unwrap()
+                + calculate_assets_for_redemption(second, nav, 6).unwrap();
+            prop_assert!(split_assets <= calculate_assets_for_redemption(first + second, nav, 6).unwrap());
+        }
+
+        #[test]
+        fn prop_share_math_never_panics(amount in any::<u64>(), nav in any::<u64>(), decimals in any::<u8>()) {
+            // Out-of-range inputs surface as errors rather than panics
+            let _ = calculate_shares_for_deposit(amount, nav, decimals);
+            let _ = calculate_assets_for_redemption(amount, nav, decimals);
+        }
+    }
+
+    #[test]
+    fn test_conversions_round_toward_vault() {
+        // 1 / 1.1 = 0.9090909.. shares and 0.909090 * 1.1 = 0.999999 assets
+        assert_eq!(calculate_shares_for_deposit(1_000_000, 1_100_000, 6).unwrap(), 909_090);
+        assert_eq!(calculate_assets_for_redemption(909_090, 1_100_000, 6).unwrap(), 999_999);
+        // Dust deposits mint nothing rather than a free share unit
+        assert_eq!(calculate_shares_for_deposit(1, 1_100_000, 6).unwrap(), 0);
+        assert!(calculate_shares_for_deposit(1_000_000, 0, 6).is_err());
+        assert!(calculate_assets_for_redemption(u64::MAX, u64::MAX, 6).is_err());
+    }
+
+    #[test]
+    fn test_tranche_epoch_cap_rolls_excess_to_next_epoch() {
+        let mut tranche = Tranche {
+            tranche_type: crate::TrancheType::Senior,
+            mint: Pubkey::default(),
+            total_supply: 0,
+            nav_per_share: 1_000_000,
+            fee_rate: 0,
+            min_deposit: 0,
+            max_deposit: 0,
+            lock_period: 0,
+            yield_rate: 0,
+            last_yield_update: 0,
+            max_redeemable_per_epoch: 2_500_000,
+            redemption_epoch: 0,
+            redeemed_in_epoch: 0,
+            performance_fee_rate: 0,
+            last_fee_accrual: 0,
+            high_water_mark: 1_000_000,
+            accrued_fees: 0,
+            lp_yield: crate::LpYieldModel::default(),
+        };
+
+        // Queued redemptions of 1M each: two fit in epoch 7, the third waits
+        assert!(reserve_epoch_redemption(&mut tranche, 7, 1_000_000).unwrap());
+        assert!(reserve_epoch_redemption(&mut tranche, 7, 1_000_000).unwrap());
+        assert!(!reserve_epoch_redemption(&mut tranche, 7, 1_000_000).unwrap());
+        assert_eq!(tranche.redeemed_in_epoch, 2_000_000);
+        // Still capped for the rest of the epoch
+        assert!(!reserve_epoch_redemption(&mut tranche, 7, 1_000_000).unwrap());
+
+        // The carried request goes through once the epoch advances
+        assert!(reserve_epoch_redemption(&mut tranche, 8, 1_000_000).unwrap());
+        assert_eq!((tranche.redemption_epoch, tranche.redeemed_in_epoch), (8, 1_000_000));
+
+        tranche.max_redeemable_per_epoch = 0;
+        assert!(reserve_epoch_redemption(&mut tranche, 8, u64::MAX - 1_000_000).unwrap());
+    }
+
+    #[test]
+    fn test_oversize_redemption_is_refused_before_it_can_block_the_queue() {
+        let mut tranche = Tranche {
+            tranche_type: crate::TrancheType::Senior,
+            mint: Pubkey::default(),
+            total_supply: 0,
+            nav_per_share: 1_000_000,
+            fee_rate: 0,
+            min_deposit: 0,
+            max_deposit: 0,
+            lock_period: 0,
+            yield_rate: 0,
+            last_yield_update: 0,
+            max_redeemable_per_epoch: 2_500_000,
+            redemption_epoch: 0,
+            redeemed_in_epoch: 0,
+            performance_fee_rate: 0,
+            last_fee_accrual: 0,
+            high_water_mark: 1_000_000,
+            accrued_fees: 0,
+            lp_yield: crate::LpYieldModel::default(),
+        };
+
+        assert_eq!(check_epoch_cap(&tranche, 2_500_001), Err(RTFError::RedemptionExceedsEpochCap.into()));
+
+        // Anything the cap admits fits a fresh epoch, however full the last one was
+        assert!(check_epoch_cap(&tranche, 2_500_000).is_ok());
+        assert!(reserve_epoch_redemption(&mut tranche, 3, 1_000_000).unwrap());
+        assert!(!reserve_epoch_redemption(&mut tranche, 3, 2_500_000).unwrap());
+        assert!(reserve_epoch_redemption(&mut tranche, 4, 2_500_000).unwrap());
+
+        tranche.max_redeemable_per_epoch = 0;
+        assert!(check_epoch_cap(&tranche, u64::MAX).is_ok());
+    }
+
+    #[test]
+    fn test_vault_epoch_follows_the_clock() {
+        assert_eq!(vault_epoch(3_600, 3_599), 0);
+        assert_eq!(vault_epoch(3_600, 7_200), 2);
+        assert_eq!(vault_epoch(0, 2 * DEFAULT_VAULT_EPOCH_DURATION as i64 - 1), 1);
+        assert_eq!(vault_epoch(0, -5), 0);
+    }
+
+    #[test]
+    fn test_nine_decimal_nav_conversions() {
+        // 1 token at NAV 1.1, both with 9 decimals
+        assert_eq!(nav_scale(9).unwrap(), 1_000_000_000);
+        assert_eq!(calculate_shares_for_deposit(1_000_000_000, 1_100_000_000, 9).unwrap(), 909_090_909);
+        assert_eq!(calculate_assets_for_redemption(909_090_909, 1_100_000_000, 9).unwrap(), 999_999_999);
+
+        // At NAV 1.0 deposits and redemptions are exact
+        let shares = calculate_shares_for_deposit(123_456_789_012, nav_scale(9).unwrap(), 9).unwrap();
+        assert_eq!(shares, 123_456_789_012);
+        assert_eq!(calculate_assets_for_redemption(shares, nav_scale(9).unwrap(), 9).unwrap(), 123_456_789_012);
+
+        // The same economic position converts consistently at 6 and 9 decimals
+        let shares_6 = calculate_shares_for_deposit(2_500_000, 1_250_000, 6).unwrap();
+        let shares_9 = calculate_shares_for_deposit(2_500_000_000, 1_250_000_000, 9).unwrap();
+        assert_eq!(shares_6 * 1_000, shares_9);
+    }
+
+    #[test]
+    fn test_nav_decimals_must_be_in_range() {
+        assert!(nav_scale(MIN_NAV_DECIMALS).is_ok());
+        assert_eq!(nav_scale(MAX_NAV_DECIMALS).unwrap(), 1_000_000_000_000_000_000);
+        assert!(nav_scale(MIN_NAV_DECIMALS - 1).is_err());
+        assert!(nav_scale(MAX_NAV_DECIMALS + 1).is_err());
+        assert!(calculate_shares_for_deposit(1_000_000, 1_000_000, 19).is_err());
+        assert!(calculate_assets_for_redemption(1_000_000, 1_000_000, 1).is_err());
+    }
+
+    #[test]
+    fn test_drift_ledger_export_is_in_epoch_order() {
+        let mut ledger = empty_drift_ledger();
+        for epoch in 0..130u64 {
+            update_drift_ledger(&mut ledger, epoch * 10, epoch).unwrap();
+        }
+
+        let vault = Pubkey::new_unique();
+        let export = export_drift_ledger(vault, &ledger);
+        assert_eq!(export.first_epoch, 30);
+        assert_eq!(export.drifts_bps.len(), 100);
+        assert_eq!(export.drifts_bps[0], 300);
+        assert_eq!(export.drifts_bps[99], 1_290);
+        assert!(export.drifts_bps.windows(2).all(|pair| pair[1] == pair[0] + 10));
+        // Full 100-epoch export fits in Solana return data
+        assert!(export.try_to_vec().unwrap().len() <= 1024);
+
+        // Young vaults export only the epochs that exist
+        let mut young = empty_drift_ledger();
+        for epoch in 0..5u64 {
+            update_drift_ledger(&mut young, 10, epoch).unwrap();
+        }
+        let export = export_drift_ledger(vault, &young);
+        assert_eq!(export.first_epoch, 0);
+        assert_eq!(export.drifts_bps.len(), 5);
+    }
+
+    fn empty_drift_ledger() -> DriftLedger {
+        DriftLedger {
+            epoch_drifts: [0; 100],
+            current_index: 0,
+            max_drift_threshold: 500,
+            consecutive_violations: 0,
+            last_epoch: 0,
+        }
+    }
+
+    #[test]
+    fn test_drift_ledger_keys_updates_by_epoch() {
+        let mut ledger = empty_drift_ledger();
+        for epoch in 0..100u64 {
+            update_drift_ledger(&mut ledger, 40, epoch).unwrap();
+        }
+
+        // Two updates in epoch 100 keep the larger drift
+        update_drift_ledger(&mut ledger, 70, 100).unwrap();
+        update_drift_ledger(&mut ledger, 20, 100).unwrap();
+        // Epochs 101 and 102 had no update, so the entries left from epochs 1 and 2 are cleared
+        update_drift_ledger(&mut ledger, 30, 103).unwrap();
+
+        let export = export_drift_ledger(Pubkey::new_unique(), &ledger);
+        assert_eq!(export.first_epoch, 4);
+        assert_eq!(&export.drifts_bps[95..], &[40, 70, 0, 0, 30]);
+    }
+
     #[test]
     fn test_nav_drift_calculation() {
         let old_nav = 1_000_000;
@@ -362,6 +717,37 @@ mod tests {
         assert_eq!(drift, 500); // 5% drift in basis points
     }
 
+    #[test]
+    fn test_pool_stress_multiplier_increases_with_queue_depth() {
+        let max_queue = 1_000;
+        let total_assets = 10_000_000;
+
+        let idle = calculate_stress_multiplier_bps(0, max_queue, total_assets, total_assets, 20_000).unwrap();
+        let half = calculate_stress_multiplier_bps(500, max_queue, total_assets, total_assets, 20_000).unwrap();
+        let near_full = calculate_stress_multiplier_bps(950, max_queue, total_assets, total_assets, 20_000).unwrap();
+
+        assert_eq!(idle, 10_000); // No stress at an empty queue
+        assert!(half > idle);
+        assert!(near_full > half);
+    }
+
+    #[test]
+    fn test_pool_stress_multiplier_accounts_for_liquidity() {
+        let liquid = calculate_stress_multiplier_bps(500, 1_000, 10_000_000, 10_000_000, 20_000).unwrap();
+        let illiquid = calculate_stress_multiplier_bps(500, 1_000, 2_000_000, 10_000_000, 20_000).unwrap();
+
+        assert!(illiquid > liquid);
+    }
+
+    #[test]
+    fn test_pool_stress_multiplier_is_capped() {
+        let capped = calculate_stress_multiplier_bps(950, 1_000, 500_000, 10_000_000, 12_000).unwrap();
+        assert_eq!(capped, 12_000);
+
+        let max_stress = calculate_stress_multiplier_bps(1_000, 1_000, 0, 10_000_000, MAX_POOL_STRESS_MULTIPLIER).unwrap();
+        assert_eq!(max_stress, MAX_POOL_STRESS_MULTIPLIER);
+    }
+
     #[test]
     fn test_commitment_hash() {
         let user = Pubkey::new_unique();
@@ -0,0 +1,496 @@
+#![allow(dead_code)]
+
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, AnchorSerialize, Discriminator, InstructionData, ToAccountMetas};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ed25519_dalek::{Signer as _, SigningKey};
+use ed25519_instruction::new_ed25519_instruction;
+use rtf_vault::*;
+use sha2::{Digest, Sha256};
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    clock::Clock,
+    instruction::{Instruction, InstructionError},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction, system_program, sysvar,
+    transaction::{Transaction, TransactionError},
+};
+use switchboard_v2::{AggregatorAccountData, AggregatorRound, SwitchboardDecimal};
+
+pub const NOW: i64 = 1_700_000_000;
+pub const NAV_DECIMALS: u8 = 6;
+pub const ONE: u64 = 1_000_000; // NAV of 1.0 at `NAV_DECIMALS`
+pub const MEV_PROTECTION_DELAY: u64 = 5; // Slots
+pub const MAX_NAV_DRIFT: u64 = 300; // Basis points
+pub const USER_FUNDS: u64 = 1_000_000_000;
+
+pub const STARKNET_FACT: [u8; 32] = [5; 32];
+pub const DILITHIUM_SIGNATURE: [u8; 128] = [9; 128];
+pub const DILITHIUM_PUBLIC_KEY: [u8; 64] = [3; 64];
+
+/// Logs of a successful transaction, or the error it failed with
+pub type TxResult = Result<Vec<String>, TransactionError>;
+
+/// Who signs an instruction besides the fee payer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Payer,
+    Authority,
+    Oracle,
+    User,
+}
+
+/// The vault program running in a `solana-program-test` bank, with an underlying mint,
+/// two tranche mints owned by the vault PDA, a funded user and a Switchboard feed
+/// The bank runs the compiled `rtf_vault.so`, so compute-unit limits apply as on chain;
+/// build it with `cargo build-sbf` and point `SBF_OUT_DIR` at `target/deploy`.
+pub struct VaultHarness {
+    pub context: ProgramTestContext,
+    pub authority: Keypair,
+    pub oracle: Keypair,
+    pub user: Keypair,
+    pub nav_verifier: SigningKey,
+    pub vault: Pubkey,
+    pub underlying_mint: Pubkey,
+    pub tranche_mints: Vec<Pubkey>,
+    pub vault_token_account: Pubkey,
+    pub user_token_account: Pubkey,
+    pub switchboard_feed: Pubkey,
+}
+
+impl VaultHarness {
+    pub async fn new() -> Self {
+        let mut program_test = ProgramTest::new("rtf_vault", rtf_vault::ID, None);
+        program_test.prefer_bpf(true);
+        let context = program_test.start_with_context().await;
+
+        let authority = Keypair::new();
+        let oracle = Keypair::new();
+        let user = Keypair::new();
+        let (vault, _) = Pubkey::find_program_address(&[b"vault", authority.pubkey().as_ref()], &rtf_vault::ID);
+
+        let mut harness = Self {
+            context,
+            authority,
+            oracle,
+            user,
+            nav_verifier: SigningKey::from_bytes(&[42; 32]),
+            vault,
+            underlying_mint: Pubkey::default(),
+            tranche_mints: Vec::new(),
+            vault_token_account: Pubkey::default(),
+            user_token_account: Pubkey::default(),
+            switchboard_feed: Pubkey::new_unique(),
+        };
+
+        for keypair in [&harness.authority, &harness.oracle, &harness.user] {
+            let pubkey = keypair.pubkey();
+            harness.context.set_account(&pubkey, &Account::new(10_000_000_000, 0, &system_program::ID).into());
+        }
+        harness.set_clock(NOW, 1).await;
+
+        let payer = harness.context.payer.pubkey();
+        harness.underlying_mint = harness.create_mint(&payer).await;
+        harness.tranche_mints = vec![harness.create_mint(&vault).await, harness.create_mint(&vault).await];
+        harness.vault_token_account = harness.create_token_account(&vault).await;
+        harness.user_token_account = harness.create_token_account(&harness.user.pubkey()).await;
+
+        let mint_to = spl_token::instruction::mint_to(
+            &spl_token::ID,
+            &harness.underlying_mint,
+            &harness.user_token_account,
+            &payer,
+            &[],
+            USER_FUNDS,
+        ).unwrap();
+        harness.send(mint_to, &[]).await.unwrap();
+
+        harness.set_oracle_round(1_000_000_000, 100_000, NOW);
+        harness
+    }
+
+    /// A harness with the vault already initialized from the default configuration
+    pub async fn initialized() -> Self {
+        let mut harness = Self::new().await;
+        let initialize = harness.initialize_ix(harness.vault_config(), harness.tranche_configs());
+        harness.send_as(initialize, Role::Authority).await.unwrap();
+        harness
+    }
+
+    pub fn vault_config(&self) -> VaultConfig {
+        VaultConfig {
+            underlying_mint: self.underlying_mint,
+            oracle_authority: self.oracle.pubkey(),
+            operator: self.authority.pubkey(),
+            max_redemption_queue_size: 100,
+            redemption_processing_window: 0,
+            mev_protection_delay: MEV_PROTECTION_DELAY,
+            max_nav_drift: MAX_NAV_DRIFT,
+            max_stress_multiplier: 0,
+            nav_decimals: NAV_DECIMALS,
+            switchboard_feed: self.switchboard_feed,
+            max_oracle_staleness: 0,
+            max_oracle_std_dev_bps: 0,
+            nav_verifier: Pubkey::new_from_array(self.nav_verifier.verifying_key().to_bytes()),
+            fee_collector: self.authority.pubkey(),
+            emergency_pause_authority: self.authority.pubkey(),
+            max_redemption_compute_units: 0,
+            epoch_duration: 0,
+        }
+    }
+
+    /// Senior and junior tranches, no lock and no per-epoch cap
+    pub fn tranche_configs(&self) -> Vec<TrancheConfig> {
+        [TrancheType::Senior, TrancheType::Junior]
+            .into_iter()
+            .zip(&self.tranche_mints)
+            .map(|(tranche_type, mint)| TrancheConfig {
+                tranche_type,
+                mint: *mint,
+                fee_rate: 0,
+                min_deposit: ONE,
+                max_deposit: 0,
+                lock_period: 0,
+                max_redeemable_per_epoch: 0,
+                performance_fee_rate: 0,
+            })
+            .collect()
+    }
+
+    pub fn deposit_ledger(&self, tranche_index: u8) -> Pubkey {
+        let user = self.user.pubkey();
+        let seeds: &[&[u8]] = &[DEPOSIT_LEDGER_SEED, self.vault.as_ref(), user.as_ref(), &[tranche_index]];
+        Pubkey::find_program_address(seeds, &rtf_vault::ID).0
+    }
+
+    pub fn user_tranche_account(&self, tranche_index: u8) -> Pubkey {
+        spl_associated_token_account::get_associated_token_address(
+            &self.user.pubkey(),
+            &self.tranche_mints[tranche_index as usize],
+        )
+    }
+
+    pub fn initialize_ix(&self, vault_config: VaultConfig, tranche_configs: Vec<TrancheConfig>) -> Instruction {
+        Instruction {
+            program_id: rtf_vault::ID,
+            accounts: rtf_vault::accounts::InitializeVault {
+                vault: self.vault,
+                authority: self.authority.pubkey(),
+                system_program: system_program::ID,
+                token_program: spl_token::ID,
+                rent: sysvar::rent::ID,
+            }.to_account_metas(None),
+            data: rtf_vault::instruction::InitializeVault {
+                vault_id: "rtf-test-vault".to_string(),
+                fund_origin_hash: [1; 32],
+                legal_doc_hash: [2; 32],
+                vault_config,
+                tranche_configs,
+            }.data(),
+        }
+    }
+
+    pub fn deposit_ix(&self, tranche_index: u8, amount: u64, min_shares_out: u64) -> Instruction {
+        Instruction {
+            program_id: rtf_vault::ID,
+            accounts: rtf_vault::accounts::Deposit {
+                vault: self.vault,
+                user: self.user.pubkey(),
+                deposit_ledger: self.deposit_ledger(tranche_index),
+                user_token_account: self.user_token_account,
+                vault_token_account: self.vault_token_account,
+                tranche_mint: self.tranche_mints[tranche_index as usize],
+                user_tranche_account: self.user_tranche_account(tranche_index),
+                token_program: spl_token::ID,
+                associated_token_program: spl_associated_token_account::ID,
+                system_program: system_program::ID,
+            }.to_account_metas(None),
+            data: rtf_vault::instruction::Deposit { tranche_index, amount, min_shares_out }.data(),
+        }
+    }
+
+    pub fn request_redemption_ix(
+        &self,
+        tranche_index: u8,
+        shares_amount: u64,
+        min_assets_out: u64,
+        commitment_hash: [u8; 32],
+    ) -> Instruction {
+        Instruction {
+            program_id: rtf_vault::ID,
+            accounts: rtf_vault::accounts::RequestRedemption {
+                vault: self.vault,
+                user: self.user.pubkey(),
+                deposit_ledger: self.deposit_ledger(tranche_index),
+                user_tranche_account: self.user_tranche_account(tranche_index),
+                token_program: spl_token::ID,
+            }.to_account_metas(None),
+            data: rtf_vault::instruction::RequestRedemption {
+                tranche_index,
+                shares_amount,
+                min_assets_out,
+                commitment_hash,
+            }.data(),
+        }
+    }
+
+    pub fn reveal_redemption_ix(&self, tranche_index: u8, nonce: u64, actual_shares_amount: u64) -> Instruction {
+        Instruction {
+            program_id: rtf_vault::ID,
+            accounts: rtf_vault::accounts::RevealRedemption {
+                vault: self.vault,
+                user: self.user.pubkey(),
+                deposit_ledger: self.deposit_ledger(tranche_index),
+            }.to_account_metas(None),
+            data: rtf_vault::instruction::RevealRedemption { nonce, actual_shares_amount }.data(),
+        }
+    }
+
+    pub fn cancel_redemption_ix(&self, tranche_index: u8) -> Instruction {
+        Instruction {
+            program_id: rtf_vault::ID,
+            accounts: rtf_vault::accounts::RevealRedemption {
+                vault: self.vault,
+                user: self.user.pubkey(),
+                deposit_ledger: self.deposit_ledger(tranche_index),
+            }.to_account_metas(None),
+            data: rtf_vault::instruction::CancelRedemption {}.data(),
+        }
+    }
+
+    pub fn process_redemptions_ix(&self, authority: &Pubkey, max_redemptions: u8) -> Instruction {
+        Instruction {
+            program_id: rtf_vault::ID,
+            accounts: rtf_vault::accounts::ProcessRedemptions {
+                vault: self.vault,
+                authority: *authority,
+                token_program: spl_token::ID,
+            }.to_account_metas(None),
+            data: rtf_vault::instruction::ProcessRedemptions { max_redemptions }.data(),
+        }
+    }
+
+    /// NAV update signed by the harness's NAV verifier, as backend/zk-nav would attest it,
+    /// preceded by the Ed25519 precompile instruction that checks the signature
+    pub fn update_nav_ixs(&self, new_nav_data: NAVData) -> [Instruction; 2] {
+        let attestation = NavAttestation {
+            public_inputs_hash: nav_public_inputs_hash(&new_nav_data),
+            verified_root: Sha256::digest(b"stark proof").into(),
+            starknet_fact: STARKNET_FACT,
+            pq_signature_hash: Sha256::digest(DILITHIUM_SIGNATURE).into(),
+            pq_public_key_hash: Sha256::digest(DILITHIUM_PUBLIC_KEY).into(),
+            attested_at: new_nav_data.timestamp,
+        };
+        let digest = attestation.digest(&rtf_vault::ID, &self.vault);
+        let verifier = Pubkey::new_from_array(self.nav_verifier.verifying_key().to_bytes());
+        let signature = self.nav_verifier.sign(&digest).to_bytes();
+
+        let update = Instruction {
+            program_id: rtf_vault::ID,
+            accounts: rtf_vault::accounts::UpdateNAV {
+                vault: self.vault,
+                oracle_authority: self.oracle.pubkey(),
+                switchboard_aggregator: self.switchboard_feed,
+                instructions_sysvar: sysvar::instructions::ID,
+            }.to_account_metas(None),
+            data: rtf_vault::instruction::UpdateNavWithZkProof {
+                new_nav_data,
+                zk_proof: attestation.try_to_vec().unwrap(),
+                starknet_proof: STARKNET_FACT,
+                dilithium_signature: DILITHIUM_SIGNATURE,
+            }.data(),
+        };
+        [new_ed25519_instruction(&verifier, &signature, &digest), update]
+    }
+
+    /// Sign with the payer and `role` and execute, returning the program logs
+    pub async fn send_as(&mut self, instruction: Instruction, role: Role) -> TxResult {
+        self.send_batch_as(&[instruction], role).await
+    }
+
+    /// Sign `instructions` as one transaction with the payer and `role` and execute them
+    pub async fn send_batch_as(&mut self, instructions: &[Instruction], role: Role) -> TxResult {
+        let signer = match role {
+            Role::Payer => return self.send_batch(instructions, &[]).await,
+            Role::Authority => self.authority.insecure_clone(),
+            Role::Oracle => self.oracle.insecure_clone(),
+            Role::User => self.user.insecure_clone(),
+        };
+        self.send_batch(instructions, &[&signer]).await
+    }
+
+    /// Sign with the payer and `signers` and execute, returning the program logs
+    pub async fn send(&mut self, instruction: Instruction, signers: &[&Keypair]) -> TxResult {
+        self.send_batch(&[instruction], signers).await
+    }
+
+    async fn send_batch(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> TxResult {
+        // A fresh blockhash keeps retries of an identical instruction from being deduplicated
+        let blockhash = self.context.get_new_latest_blockhash().await.unwrap();
+        let mut all_signers = vec![&self.context.payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.context.payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+
+        let outcome = self.context.banks_client
+            .process_transaction_with_metadata(transaction)
+            .await
+            .map_err(|error: BanksClientError| error.unwrap())?;
+        outcome.result.map(|()| outcome.metadata.map(|metadata| metadata.log_messages).unwrap_or_default())
+    }
+
+    pub async fn vault_account(&mut self) -> VaultAccount {
+        let account = self.context.banks_client.get_account(self.vault).await.unwrap().expect("vault exists");
+        VaultAccount::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub async fn deposit_ledger_account(&mut self, tranche_index: u8) -> DepositLedger {
+        let address = self.deposit_ledger(tranche_index);
+        let account = self.context.banks_client.get_account(address).await.unwrap().expect("ledger exists");
+        DepositLedger::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub async fn token_balance(&mut self, token_account: Pubkey) -> u64 {
+        let account = self.context.banks_client.get_account(token_account).await.unwrap().expect("token account exists");
+        spl_token::state::Account::unpack(&account.data).unwrap().amount
+    }
+
+    pub async fn clock(&mut self) -> Clock {
+        self.context.banks_client.get_sysvar::<Clock>().await.unwrap()
+    }
+
+    pub async fn set_clock(&mut self, unix_timestamp: i64, slot: u64) {
+        let mut clock = self.clock().await;
+        if slot > clock.slot {
+            self.context.warp_to_slot(slot).unwrap();
+            clock = self.clock().await;
+        }
+        clock.unix_timestamp = unix_timestamp;
+        self.context.set_sysvar(&clock);
+    }
+
+    /// Move the bank forward by `seconds` and `slots`
+    pub async fn advance(&mut self, seconds: i64, slots: u64) {
+        let clock = self.clock().await;
+        self.set_clock(clock.unix_timestamp + seconds, clock.slot + slots).await;
+    }
+
+    /// Make the Switchboard feed's latest confirmed round report `value` ± `std_dev`
+    /// (9-decimal mantissas), opened at `opened_at`
+    pub fn set_oracle_round(&mut self, value: i128, std_dev: i128, opened_at: i64) {
+        let mut aggregator = AggregatorAccountData::default();
+        aggregator.latest_confirmed_round = AggregatorRound {
+            result: SwitchboardDecimal::new(value, 9),
+            std_deviation: SwitchboardDecimal::new(std_dev, 9),
+            round_open_timestamp: opened_at,
+            ..Default::default()
+        };
+
+        let mut data = AggregatorAccountData::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(&aggregator));
+        let mut account = AccountSharedData::new(1_000_000_000, data.len(), &switchboard_v2::ID);
+        account.set_data_from_slice(&data);
+        self.context.set_account(&self.switchboard_feed, &account);
+    }
+
+    async fn create_mint(&mut self, mint_authority: &Pubkey) -> Pubkey {
+        let mint = Keypair::new();
+        let payer = self.context.payer.pubkey();
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+        let instructions = [
+            system_instruction::create_account(
+                &payer,
+                &mint.pubkey(),
+                rent.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_mint2(&spl_token::ID, &mint.pubkey(), mint_authority, None, NAV_DECIMALS)
+                .unwrap(),
+        ];
+        self.send_all(&instructions, &[&mint]).await;
+        mint.pubkey()
+    }
+
+    async fn create_token_account(&mut self, owner: &Pubkey) -> Pubkey {
+        let token_account = Keypair::new();
+        let payer = self.context.payer.pubkey();
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+        let instructions = [
+            system_instruction::create_account(
+                &payer,
+                &token_account.pubkey(),
+                rent.minimum_balance(spl_token::state::Account::LEN),
+                spl_token::state::Account::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_account3(&spl_token::ID, &token_account.pubkey(), &self.underlying_mint, owner)
+                .unwrap(),
+        ];
+        self.send_all(&instructions, &[&token_account]).await;
+        token_account.pubkey()
+    }
+
+    async fn send_all(&mut self, instructions: &[Instruction], signers: &[&Keypair]) {
+        let blockhash = self.context.get_new_latest_blockhash().await.unwrap();
+        let mut all_signers = vec![&self.context.payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.context.payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        self.context.banks_client.process_transaction(transaction).await.unwrap();
+    }
+}
+
+/// NAV update at `nav_per_share` for the vault and both tranches
+pub fn nav_data(nav_per_share: u64, total_assets: u64, timestamp: i64) -> NAVData {
+    NAVData {
+        nav_per_share,
+        total_assets,
+        total_liabilities: 0,
+        timestamp,
+        tranche_navs: vec![nav_per_share, nav_per_share],
+        oracle_signature: [0; 64],
+        confidence_score: 95,
+        computation_hash: [0; 32],
+    }
+}
+
+/// Events of type `E` emitted in `logs`, in order
+pub fn events<E: AnchorDeserialize + Discriminator>(logs: &[String]) -> Vec<E> {
+    logs.iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .filter_map(|encoded| STANDARD.decode(encoded).ok())
+        .filter_map(|data| {
+            let payload = data.strip_prefix(&E::DISCRIMINATOR[..])?;
+            E::deserialize(&mut &payload[..]).ok()
+        })
+        .collect()
+}
+
+/// The single event of type `E` emitted in `logs`
+pub fn event<E: AnchorDeserialize + Discriminator>(logs: &[String]) -> E {
+    let mut events = events::<E>(logs);
+    assert_eq!(events.len(), 1, "expected exactly one event");
+    events.remove(0)
+}
+
+pub fn assert_rtf_error(result: TxResult, error: RTFError) {
+    let code = ERROR_CODE_OFFSET + error as u32;
+    match result {
+        Err(TransactionError::InstructionError(_, InstructionError::Custom(actual))) => {
+            assert_eq!(actual, code, "expected error code {}, got {}", code, actual)
+        }
+        other => panic!("expected error code {}, got {:?}", code, other),
+    }
+}
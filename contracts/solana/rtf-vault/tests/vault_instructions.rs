@@ -0,0 +1,315 @@
+use rtf_vault::*;
+use solana_sdk::signature::Signer;
+
+mod common;
+use common::*;
+
+const DEPOSIT: u64 = 10 * ONE;
+const REDEEM: u64 = 4 * ONE;
+
+/// Deposit into the senior tranche and wait out the minimum holding period
+async fn funded_vault() -> VaultHarness {
+    let mut harness = VaultHarness::initialized().await;
+    let deposit = harness.deposit_ix(0, DEPOSIT, DEPOSIT);
+    harness.send_as(deposit, Role::User).await.unwrap();
+    harness.advance(MIN_HOLDING_DURATION, 1).await;
+    harness
+}
+
+#[tokio::test]
+async fn test_initialize_vault_sets_up_tranches_at_unit_nav() {
+    let mut harness = VaultHarness::new().await;
+    let initialize = harness.initialize_ix(harness.vault_config(), harness.tranche_configs());
+    let logs = harness.send_as(initialize, Role::Authority).await.unwrap();
+
+    let initialized: VaultInitialized = event(&logs);
+    assert_eq!(initialized.vault, harness.vault);
+    assert_eq!(initialized.authority, harness.authority.pubkey());
+    assert_eq!(initialized.timestamp, NOW);
+
+    let vault = harness.vault_account().await;
+    assert_eq!(vault.authority, harness.authority.pubkey());
+    assert_eq!(vault.nav_per_share, ONE);
+    assert_eq!(vault.total_assets, 0);
+    assert_eq!(vault.tranches.len(), 2);
+    for (tranche, mint) in vault.tranches.iter().zip(&harness.tranche_mints) {
+        assert_eq!(tranche.mint, *mint);
+        assert_eq!(tranche.nav_per_share, ONE);
+        assert_eq!(tranche.total_supply, 0);
+    }
+    assert_eq!(vault.redemption_queue.head, 0);
+    assert_eq!(vault.redemption_queue.tail, 0);
+}
+
+#[tokio::test]
+async fn test_initialize_vault_with_one_tranche_is_rejected() {
+    let mut harness = VaultHarness::new().await;
+    let mut tranche_configs = harness.tranche_configs();
+    tranche_configs.truncate(1);
+
+    let initialize = harness.initialize_ix(harness.vault_config(), tranche_configs);
+    assert_rtf_error(harness.send_as(initialize, Role::Authority).await, RTFError::InvalidTrancheCount);
+    assert!(harness.context.banks_client.get_account(harness.vault).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_deposit_mints_shares_at_tranche_nav() {
+    let mut harness = VaultHarness::initialized().await;
+    let deposit = harness.deposit_ix(0, DEPOSIT, DEPOSIT);
+    let logs = harness.send_as(deposit, Role::User).await.unwrap();
+
+    let deposited: DepositMade = event(&logs);
+    assert_eq!(deposited.user, harness.user.pubkey());
+    assert_eq!(deposited.amount, DEPOSIT);
+    assert_eq!(deposited.shares_minted, DEPOSIT);
+    assert_eq!(deposited.record.nav_per_share, ONE);
+
+    assert_eq!(harness.token_balance(harness.user_token_account).await, USER_FUNDS - DEPOSIT);
+    assert_eq!(harness.token_balance(harness.vault_token_account).await, DEPOSIT);
+    assert_eq!(harness.token_balance(harness.user_tranche_account(0)).await, DEPOSIT);
+
+    let vault = harness.vault_account().await;
+    assert_eq!(vault.total_assets, DEPOSIT);
+    assert_eq!(vault.tranches[0].total_supply, DEPOSIT);
+    assert_eq!(vault.tranches[1].total_supply, 0);
+
+    let ledger = harness.deposit_ledger_account(0).await;
+    assert_eq!(ledger.lots, vec![DepositLot { shares: DEPOSIT, deposited_at: NOW }]);
+}
+
+#[tokio::test]
+async fn test_deposit_below_min_shares_out_is_rejected() {
+    let mut harness = VaultHarness::initialized().await;
+    let deposit = harness.deposit_ix(0, DEPOSIT, DEPOSIT + 1);
+    assert_rtf_error(harness.send_as(deposit, Role::User).await, RTFError::SlippageExceeded);
+
+    // Nothing moved and no lot was recorded
+    assert_eq!(harness.token_balance(harness.user_token_account).await, USER_FUNDS);
+    assert_eq!(harness.token_balance(harness.vault_token_account).await, 0);
+    assert_eq!(harness.vault_account().await.tranches[0].total_supply, 0);
+    let ledger = harness.deposit_ledger(0);
+    assert!(harness.context.banks_client.get_account(ledger).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_redemption_is_requested_revealed_and_processed() {
+    let mut harness = funded_vault().await;
+
+    // The commitment nonce is the slot the request becomes processable at
+    let processing_slot = harness.clock().await.slot + MEV_PROTECTION_DELAY;
+    let commitment_hash = calculate_commitment_hash(&harness.user.pubkey(), REDEEM, processing_slot).unwrap();
+    let request = harness.request_redemption_ix(0, REDEEM, REDEEM, commitment_hash);
+    let logs = harness.send_as(request, Role::User).await.unwrap();
+
+    let requested: RedemptionRequested = event(&logs);
+    assert_eq!(requested.shares_amount, REDEEM);
+    assert_eq!(requested.expected_assets, REDEEM);
+    assert_eq!(requested.queue_position, 1);
+    assert_eq!(requested.processing_slot, processing_slot);
+
+    let vault = harness.vault_account().await;
+    assert_eq!(vault.redemption_queue.tail, 1);
+    assert_eq!(vault.redemption_queue.total_pending, REDEEM);
+    assert_eq!(harness.deposit_ledger_account(0).await.total_shares(), DEPOSIT - REDEEM);
+
+    let reveal = harness.reveal_redemption_ix(0, processing_slot, REDEEM);
+    let logs = harness.send_as(reveal, Role::User).await.unwrap();
+    let revealed: RedemptionRevealed = event(&logs);
+    assert_eq!(revealed.actual_shares_amount, REDEEM);
+
+    // Still inside the MEV protection delay: nothing is processed
+    let process = harness.process_redemptions_ix(&harness.authority.pubkey(), 10);
+    let logs = harness.send_as(process, Role::Authority).await.unwrap();
+    assert_eq!(event::<RedemptionsProcessed>(&logs).processed_count, 0);
+
+    harness.advance(0, MEV_PROTECTION_DELAY).await;
+    let process = harness.process_redemptions_ix(&harness.authority.pubkey(), 10);
+    let logs = harness.send_as(process, Role::Authority).await.unwrap();
+
+    let processed: RedemptionsProcessed = event(&logs);
+    assert_eq!(processed.processed_count, 1);
+    assert_eq!(processed.total_assets_redeemed, REDEEM);
+    assert_eq!(processed.remaining_queue_size, 0);
+
+    let vault = harness.vault_account().await;
+    assert_eq!(vault.redemption_queue.head, 1);
+    assert_eq!(vault.redemption_queue.total_pending, 0);
+}
+
+#[tokio::test]
+async fn test_reveal_above_the_queued_amount_is_rejected() {
+    let mut harness = funded_vault().await;
+
+    // Queue REDEEM shares but commit to twice as many
+    let processing_slot = harness.clock().await.slot + MEV_PROTECTION_DELAY;
+    let commitment_hash = calculate_commitment_hash(&harness.user.pubkey(), 2 * REDEEM, processing_slot).unwrap();
+    let request = harness.request_redemption_ix(0, REDEEM, REDEEM, commitment_hash);
+    harness.send_as(request, Role::User).await.unwrap();
+
+    let reveal = harness.reveal_redemption_ix(0, processing_slot, 2 * REDEEM);
+    assert_rtf_error(harness.send_as(reveal, Role::User).await, RTFError::InsufficientShares);
+    assert_eq!(harness.deposit_ledger_account(0).await.total_shares(), DEPOSIT - REDEEM);
+}
+
+#[tokio::test]
+async fn test_reveal_with_the_wrong_nonce_or_after_the_window_is_rejected() {
+    let mut harness = funded_vault().await;
+    let processing_slot = harness.clock().await.slot + MEV_PROTECTION_DELAY;
+    let commitment_hash = calculate_commitment_hash(&harness.user.pubkey(), REDEEM, processing_slot).unwrap();
+    let request = harness.request_redemption_ix(0, REDEEM, REDEEM, commitment_hash);
+    harness.send_as(request, Role::User).await.unwrap();
+
+    let reveal = harness.reveal_redemption_ix(0, processing_slot + 1, REDEEM);
+    assert_rtf_error(harness.send_as(reveal, Role::User).await, RTFError::InvalidCommitmentReveal);
+
+    // The matching reveal is refused once the window has passed
+    harness.advance(301, 1).await;
+    let reveal = harness.reveal_redemption_ix(0, processing_slot, REDEEM);
+    assert_rtf_error(harness.send_as(reveal, Role::User).await, RTFError::RevealWindowExpired);
+    assert_eq!(harness.vault_account().await.redemption_queue.total_pending, REDEEM);
+}
+
+#[tokio::test]
+async fn test_cancelled_redemption_returns_its_lots() {
+    let mut harness = funded_vault().await;
+    let request = harness.request_redemption_ix(0, REDEEM, REDEEM, [7; 32]);
+    harness.send_as(request, Role::User).await.unwrap();
+    assert_eq!(harness.deposit_ledger_account(0).await.total_shares(), DEPOSIT - REDEEM);
+
+    // Let the reveal window lapse, then reclaim the shares
+    harness.advance(301, 1).await;
+    let cancel = harness.cancel_redemption_ix(0);
+    let logs = harness.send_as(cancel, Role::User).await.unwrap();
+    let cancelled: RedemptionCancelled = event(&logs);
+    assert_eq!(cancelled.shares_amount, REDEEM);
+    assert!(cancelled.expired);
+
+    let ledger = harness.deposit_ledger_account(0).await;
+    assert_eq!(ledger.total_shares(), DEPOSIT);
+    assert_eq!(ledger.lots[0].deposited_at, NOW);
+    assert_eq!(harness.vault_account().await.redemption_queue.total_pending, 0);
+}
+
+#[tokio::test]
+async fn test_process_redemptions_stops_at_the_compute_unit_cap() {
+    let mut harness = VaultHarness::new().await;
+    let mut config = harness.vault_config();
+    // Room for one plain redemption at its estimated cost, but not a second
+    config.max_redemption_compute_units = REDEMPTION_BASE_CU + 2 * TOKEN_CPI_CU + 1_000;
+    let initialize = harness.initialize_ix(config, harness.tranche_configs());
+    harness.send_as(initialize, Role::Authority).await.unwrap();
+    let deposit = harness.deposit_ix(0, DEPOSIT, DEPOSIT);
+    harness.send_as(deposit, Role::User).await.unwrap();
+    harness.advance(MIN_HOLDING_DURATION, 1).await;
+
+    for nonce in 0..2u8 {
+        let request = harness.request_redemption_ix(0, ONE, ONE, [nonce; 32]);
+        harness.send_as(request, Role::User).await.unwrap();
+    }
+    harness.advance(0, MEV_PROTECTION_DELAY).await;
+
+    let process = harness.process_redemptions_ix(&harness.authority.pubkey(), 10);
+    let logs = harness.send_as(process, Role::Authority).await.unwrap();
+    let processed: RedemptionsProcessed = event(&logs);
+    assert_eq!(processed.processed_count, 1);
+    assert_eq!(processed.remaining_queue_size, 1);
+
+    // The deferred redemption heads the next batch
+    let process = harness.process_redemptions_ix(&harness.authority.pubkey(), 10);
+    let logs = harness.send_as(process, Role::Authority).await.unwrap();
+    let processed: RedemptionsProcessed = event(&logs);
+    assert_eq!(processed.processed_count, 1);
+    assert_eq!(processed.remaining_queue_size, 0);
+}
+
+#[tokio::test]
+async fn test_redemption_below_min_assets_out_is_rejected() {
+    let mut harness = funded_vault().await;
+    let request = harness.request_redemption_ix(0, REDEEM, REDEEM + 1, [7; 32]);
+    assert_rtf_error(harness.send_as(request, Role::User).await, RTFError::SlippageExceeded);
+
+    // Not queued, and the shares can still back a later request
+    let vault = harness.vault_account().await;
+    assert_eq!(vault.redemption_queue.tail, 0);
+    assert_eq!(vault.redemption_queue.total_pending, 0);
+    assert_eq!(harness.deposit_ledger_account(0).await.total_shares(), DEPOSIT);
+}
+
+#[tokio::test]
+async fn test_process_redemptions_by_a_stranger_is_rejected() {
+    let mut harness = funded_vault().await;
+    let process = harness.process_redemptions_ix(&harness.user.pubkey(), 10);
+    assert_rtf_error(harness.send_as(process, Role::User).await, RTFError::Unauthorized);
+}
+
+#[tokio::test]
+async fn test_nav_update_applies_the_attested_nav() {
+    let mut harness = funded_vault().await;
+    let clock = harness.clock().await;
+    harness.set_oracle_round(1_020_000_000, 100_000, clock.unix_timestamp);
+
+    let nav = nav_data(1_020_000, 10_200_000, clock.unix_timestamp);
+    let update = harness.update_nav_ixs(nav);
+    let logs = harness.send_batch_as(&update, Role::Oracle).await.unwrap();
+
+    let updated: NAVUpdated = event(&logs);
+    assert_eq!(updated.new_nav, 1_020_000);
+    assert_eq!(updated.total_assets, 10_200_000);
+    assert_eq!(updated.oracle, harness.oracle.pubkey());
+
+    let vault = harness.vault_account().await;
+    assert_eq!(vault.nav_per_share, 1_020_000);
+    assert_eq!(vault.total_assets, 10_200_000);
+    assert_eq!(vault.last_nav_update, clock.unix_timestamp);
+    assert!(vault.tranches.iter().all(|tranche| tranche.nav_per_share == 1_020_000));
+}
+
+#[tokio::test]
+async fn test_flat_nav_update_refreshes_the_update_time() {
+    let mut harness = funded_vault().await;
+    let clock = harness.clock().await;
+    harness.set_oracle_round(1_020_000_000, 100_000, clock.unix_timestamp);
+    let update = harness.update_nav_ixs(nav_data(1_020_000, 10_200_000, clock.unix_timestamp));
+    harness.send_batch_as(&update, Role::Oracle).await.unwrap();
+
+    // A day later the fund reports the same NAV
+    harness.advance(86_400, 1).await;
+    let later = clock.unix_timestamp + 86_400;
+    harness.set_oracle_round(1_020_000_000, 100_000, later);
+    let update = harness.update_nav_ixs(nav_data(1_020_000, 10_200_000, later));
+    let logs = harness.send_batch_as(&update, Role::Oracle).await.unwrap();
+    assert!(events::<NAVUpdated>(&logs).is_empty());
+
+    let vault = harness.vault_account().await;
+    assert_eq!(vault.nav_per_share, 1_020_000);
+    assert_eq!(vault.last_nav_update, later);
+}
+
+#[tokio::test]
+async fn test_nav_update_diverging_from_the_oracle_is_rejected() {
+    let mut harness = funded_vault().await;
+    let clock = harness.clock().await;
+
+    // The feed reads 1.06, the submitted 1.02 is ~3.8% off against a 3% tolerance
+    harness.set_oracle_round(1_060_000_000, 100_000, clock.unix_timestamp);
+    let update = harness.update_nav_ixs(nav_data(1_020_000, 10_200_000, clock.unix_timestamp));
+    assert_rtf_error(harness.send_batch_as(&update, Role::Oracle).await, RTFError::OracleNavDivergence);
+
+    let vault = harness.vault_account().await;
+    assert_eq!(vault.nav_per_share, ONE);
+    assert_eq!(vault.total_assets, DEPOSIT);
+}
+
+#[tokio::test]
+async fn test_nav_update_without_the_verifier_signature_is_rejected() {
+    let mut harness = funded_vault().await;
+    let clock = harness.clock().await;
+    harness.set_oracle_round(1_020_000_000, 100_000, clock.unix_timestamp);
+
+    // The update alone, with no Ed25519 precompile instruction before it
+    let [_, update] = harness.update_nav_ixs(nav_data(1_020_000, 10_200_000, clock.unix_timestamp));
+    assert_rtf_error(harness.send_as(update, Role::Oracle).await, RTFError::InvalidAttestationSignature);
+
+    let vault = harness.vault_account().await;
+    assert_eq!(vault.nav_per_share, ONE);
+}
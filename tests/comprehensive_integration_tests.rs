@@ -123,10 +123,11 @@ mod integration_tests {
         assert!(cached_record.is_some(), "Should retrieve cached record");
         
         // Test ZK attestation verification if enabled
-        if let Some(attestation_hash) = &compliance_record.zk_attestation_hash {
+        if let Some(attestation) = &compliance_record.zk_attestation {
             let verification_result = esg_system.verify_zk_attestation(
                 "test_entity_001",
-                attestation_hash,
+                compliance_record.overall_score,
+                &attestation.commitment,
             ).await?;
             assert!(verification_result, "ZK attestation should verify successfully");
         }
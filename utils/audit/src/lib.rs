@@ -0,0 +1,241 @@
+//! # Tamper-Evident Audit Log for RTF Infrastructure
+//!
+//! Append-only record of compliance-relevant actions (governance decisions, emergency
+//! activations, ESG checks). Each entry commits to the hash of the entry before it, so
+//! rewriting, reordering or dropping any past entry breaks `verify_chain`.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use domain_tags::DomainTag;
+use std::fmt;
+use thiserror::Error;
+use tokio::sync::RwLock;
+
+/// SHA-256 of an audit entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct EntryHash(pub [u8; 32]);
+
+impl EntryHash {
+    /// `prev_hash` of the first entry
+    pub const GENESIS: Self = Self([0; 32]);
+}
+
+impl fmt::Display for EntryHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&hex::encode(self.0))
+    }
+}
+
+/// Action being recorded
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEvent {
+    /// Service that performed the action, e.g. "governance"
+    pub service: String,
+    /// Short action name, e.g. "proposal_executed"
+    pub action: String,
+    pub actor: Option<String>,
+    pub details: serde_json::Value,
+}
+
+impl AuditEvent {
+    pub fn new(service: impl Into<String>, action: impl Into<String>, details: serde_json::Value) -> Self {
+        Self {
+            service: service.into(),
+            action: action.into(),
+            actor: None,
+            details,
+        }
+    }
+
+    pub fn with_actor(mut self, actor: impl Into<String>) -> Self {
+        self.actor = Some(actor.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub sequence: u64,
+    pub recorded_at: i64,
+    pub event: AuditEvent,
+    pub prev_hash: EntryHash,
+    pub hash: EntryHash,
+}
+
+impl AuditEntry {
+    /// Hash over the entry's contents and its predecessor's hash
+    pub fn compute_hash(&self) -> EntryHash {
+        let mut hasher = Sha256::new();
+        hasher.update(DomainTag::AuditEntry.as_bytes());
+        hasher.update(self.prev_hash.0);
+        hasher.update(self.sequence.to_le_bytes());
+        hasher.update(self.recorded_at.to_le_bytes());
+        // `details` is free-form JSON, so key order and number formatting are normalized
+        let event = canonical_json::to_canonical_json(&self.event).expect("audit events serialize to JSON");
+        hasher.update(event.as_bytes());
+        EntryHash(hasher.finalize().into())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum AuditError {
+    #[error("Audit entry {sequence} is out of sequence")]
+    SequenceGap { sequence: u64 },
+    #[error("Audit entry {sequence} does not link to its predecessor")]
+    BrokenLink { sequence: u64 },
+    #[error("Audit entry {sequence} has been modified")]
+    HashMismatch { sequence: u64 },
+}
+
+/// Hash-chained, append-only audit log shared between services
+#[derive(Debug, Default)]
+pub struct AuditLog {
+    entries: RwLock<Vec<AuditEntry>>,
+}
+
+impl AuditLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an event, returning the new head of the chain
+    pub async fn append(&self, event: AuditEvent) -> EntryHash {
+        let mut entries = self.entries.write().await;
+        let prev_hash = entries.last().map(|entry| entry.hash).unwrap_or(EntryHash::GENESIS);
+        let mut entry = AuditEntry {
+            sequence: entries.len() as u64,
+            recorded_at: chrono::Utc::now().timestamp(),
+            event,
+            prev_hash,
+            hash: EntryHash::GENESIS,
+        };
+        entry.hash = entry.compute_hash();
+        let hash = entry.hash;
+        entries.push(entry);
+        hash
+    }
+
+    /// Check every entry links to its predecessor and still matches its hash
+    pub async fn verify_chain(&self) -> Result<(), AuditError> {
+        verify_entries(&self.entries.read().await)
+    }
+
+    pub async fn head(&self) -> EntryHash {
+        self.entries.read().await.last().map(|entry| entry.hash).unwrap_or(EntryHash::GENESIS)
+    }
+
+    pub async fn len(&self) -> usize {
+        self.entries.read().await.len()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.entries.read().await.is_empty()
+    }
+
+    /// Copy of the log for export; verify it independently with `verify_entries`
+    pub async fn entries(&self) -> Vec<AuditEntry> {
+        self.entries.read().await.clone()
+    }
+
+    /// Entries recorded by one service
+    pub async fn entries_for(&self, service: &str) -> Vec<AuditEntry> {
+        self.entries.read().await.iter()
+            .filter(|entry| entry.event.service == service)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Verify an exported chain from its first entry
+pub fn verify_entries(entries: &[AuditEntry]) -> Result<(), AuditError> {
+    let mut prev_hash = EntryHash::GENESIS;
+    for (index, entry) in entries.iter().enumerate() {
+        if entry.sequence != index as u64 {
+            return Err(AuditError::SequenceGap { sequence: entry.sequence });
+        }
+        if entry.prev_hash != prev_hash {
+            return Err(AuditError::BrokenLink { sequence: entry.sequence });
+        }
+        if entry.compute_hash() != entry.hash {
+            return Err(AuditError::HashMismatch { sequence: entry.sequence });
+        }
+        prev_hash = entry.hash;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    async fn populated_log() -> AuditLog {
+        let log = AuditLog::new();
+        log.append(AuditEvent::new("governance", "proposal_executed", json!({ "proposal_id": "p-1" }))
+            .with_actor("validator-dao")).await;
+        log.append(AuditEvent::new("emergency", "circuit_breaker_triggered", json!({ "component": "vault" }))).await;
+        log.append(AuditEvent::new("esg", "compliance_check", json!({ "entity_id": "fund-1", "score": 82.5 }))).await;
+        log
+    }
+
+    #[tokio::test]
+    async fn test_well_formed_chain_verifies() {
+        let log = populated_log().await;
+        assert_eq!(log.verify_chain().await, Ok(()));
+
+        let entries = log.entries().await;
+        assert_eq!(entries[0].prev_hash, EntryHash::GENESIS);
+        assert_eq!(entries[2].prev_hash, entries[1].hash);
+        assert_eq!(log.head().await, entries[2].hash);
+        assert_eq!(log.entries_for("esg").await.len(), 1);
+        assert_eq!(AuditLog::new().verify_chain().await, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn test_mutating_any_past_entry_breaks_verification() {
+        let log = populated_log().await;
+        let entries = log.entries().await;
+
+        for index in 0..entries.len() {
+            let mut tampered = entries.clone();
+            tampered[index].event.details = json!({ "rewritten": true });
+            assert_eq!(verify_entries(&tampered), Err(AuditError::HashMismatch { sequence: index as u64 }));
+
+            // Re-hashing the edited entry still breaks the link from its successor
+            tampered[index].hash = tampered[index].compute_hash();
+            if index + 1 < entries.len() {
+                assert_eq!(verify_entries(&tampered), Err(AuditError::BrokenLink { sequence: index as u64 + 1 }));
+            }
+        }
+
+        let mut reordered = entries.clone();
+        reordered.swap(0, 1);
+        assert!(verify_entries(&reordered).is_err());
+
+        let mut truncated = entries.clone();
+        truncated.remove(1);
+        assert!(verify_entries(&truncated).is_err());
+
+        let mut backdated = entries;
+        backdated[1].recorded_at -= 3_600;
+        assert_eq!(verify_entries(&backdated), Err(AuditError::HashMismatch { sequence: 1 }));
+    }
+    #[tokio::test]
+    async fn test_equal_details_hash_identically() {
+        let log = populated_log().await;
+        let mut entry = log.entries().await.remove(2);
+        let hash = entry.compute_hash();
+
+        // Same details rebuilt in a different key order and with an integral float
+        let mut details = serde_json::Map::new();
+        details.insert("score".to_string(), json!(82.5));
+        details.insert("entity_id".to_string(), json!("fund-1"));
+        entry.event.details = serde_json::Value::Object(details);
+        assert_eq!(entry.compute_hash(), hash);
+
+        entry.event.details = json!({ "entity_id": "fund-1", "score": 82.5, "weight": 1.0 });
+        let with_float = entry.compute_hash();
+        entry.event.details = json!({ "weight": 1, "score": 82.5, "entity_id": "fund-1" });
+        assert_eq!(entry.compute_hash(), with_float);
+    }
+}
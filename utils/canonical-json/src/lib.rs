@@ -0,0 +1,134 @@
+//! # Canonical JSON for RTF Commitments
+//!
+//! Hashes and signatures over JSON must not depend on how a value happened to be built.
+//! `serde_json` keeps map keys in insertion order when its `preserve_order` feature is
+//! enabled anywhere in the build, and prints `1.0` and `1` differently, so commitments are
+//! computed over the canonical form produced here instead:
+//! - object keys are sorted by their UTF-8 bytes, recursively
+//! - no insignificant whitespace
+//! - floats with no fractional part that fit an integer are printed as that integer,
+//!   and `-0.0` as `0`; other floats keep serde_json's shortest round-trip form
+
+use serde::Serialize;
+use serde_json::{Number, Value};
+use sha2::{Digest, Sha256};
+
+/// Canonical encoding of an already-built JSON value
+pub fn canonical_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value);
+    out
+}
+
+/// Canonical encoding of any serializable value
+pub fn to_canonical_json<T: Serialize + ?Sized>(value: &T) -> serde_json::Result<String> {
+    Ok(canonical_json(&serde_json::to_value(value)?))
+}
+
+/// SHA-256 over `domain` followed by the canonical encoding of `value`
+pub fn canonical_sha256<T: Serialize + ?Sized>(domain: &[u8], value: &T) -> serde_json::Result<[u8; 32]> {
+    let mut hasher = Sha256::new();
+    hasher.update(domain);
+    hasher.update(to_canonical_json(value)?.as_bytes());
+    Ok(hasher.finalize().into())
+}
+
+fn write_value(out: &mut String, value: &Value) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => write_number(out, n),
+        Value::String(s) => write_string(out, s),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(out, item);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_unstable_by(|a, b| a.0.as_bytes().cmp(b.0.as_bytes()));
+            out.push('{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(out, key);
+                out.push(':');
+                write_value(out, item);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_number(out: &mut String, n: &Number) {
+    if n.is_f64() {
+        let f = n.as_f64().unwrap_or_default();
+        // Range checks are exclusive at the top so the cast cannot saturate
+        if f.fract() == 0.0 && f >= i64::MIN as f64 && f < i64::MAX as f64 {
+            out.push_str(&(f as i64).to_string());
+            return;
+        }
+        if f.fract() == 0.0 && f >= 0.0 && f < u64::MAX as f64 {
+            out.push_str(&(f as u64).to_string());
+            return;
+        }
+    }
+    out.push_str(&n.to_string());
+}
+
+fn write_string(out: &mut String, s: &str) {
+    out.push_str(&serde_json::to_string(s).expect("strings serialize to JSON"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Map};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_insertion_order_does_not_change_encoding() {
+        let mut first = Map::new();
+        first.insert("zeta".to_string(), json!(1));
+        first.insert("alpha".to_string(), json!({ "b": [1, 2], "a": null }));
+        let mut inner = Map::new();
+        inner.insert("a".to_string(), Value::Null);
+        inner.insert("b".to_string(), json!([1, 2]));
+        let mut second = Map::new();
+        second.insert("alpha".to_string(), Value::Object(inner));
+        second.insert("zeta".to_string(), json!(1));
+
+        let (first, second) = (Value::Object(first), Value::Object(second));
+        assert_eq!(canonical_json(&first), r#"{"alpha":{"a":null,"b":[1,2]},"zeta":1}"#);
+        assert_eq!(canonical_json(&first), canonical_json(&second));
+        assert_eq!(canonical_sha256(b"test", &first).unwrap(), canonical_sha256(b"test", &second).unwrap());
+    }
+
+    #[test]
+    fn test_hash_maps_encode_identically_regardless_of_build_order() {
+        let forward: HashMap<String, u64> = (0..32).map(|i| (format!("key{}", i), i)).collect();
+        let reverse: HashMap<String, u64> = (0..32).rev().map(|i| (format!("key{}", i), i)).collect();
+
+        assert_eq!(to_canonical_json(&forward).unwrap(), to_canonical_json(&reverse).unwrap());
+        assert_eq!(canonical_sha256(b"test", &forward).unwrap(), canonical_sha256(b"test", &reverse).unwrap());
+    }
+
+    #[test]
+    fn test_numbers_are_normalized() {
+        assert_eq!(canonical_json(&json!([1.0, 1, -0.0, -2.0, 0.25, 1e300])), "[1,1,0,-2,0.25,1e300]");
+        assert_eq!(canonical_json(&json!(u64::MAX)), u64::MAX.to_string());
+        assert_ne!(canonical_sha256(b"test", &json!(1)).unwrap(), canonical_sha256(b"test", &json!(2)).unwrap());
+    }
+
+    #[test]
+    fn test_strings_are_escaped_and_domain_separates() {
+        assert_eq!(canonical_json(&json!({ "q\"": "line\nbreak" })), r#"{"q\"":"line\nbreak"}"#);
+        assert_ne!(canonical_sha256(b"a", &json!(1)).unwrap(), canonical_sha256(b"b", &json!(1)).unwrap());
+    }
+}
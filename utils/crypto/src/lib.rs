@@ -3,10 +3,39 @@
 //! Advanced cryptographic primitives and utilities for secure operations
 //! in the RTF protocol including hashing, encryption, and digital signatures.
 
-use anyhow::{Result, anyhow};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use rand::{RngCore, CryptoRng};
+use rand::rngs::OsRng;
+
+pub use signatures::BatchVerificationError;
+
+/// Errors returned by the crypto utilities
+/// Implements `std::error::Error`, so `?` converts it into `anyhow::Error` for callers
+/// that don't need to match on it.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CryptoError {
+    #[error("Key not found: {0}")]
+    KeyNotFound(String),
+    #[error("Encryption failed: {0}")]
+    EncryptionFailed(String),
+    #[error("Decryption failed: {0}")]
+    DecryptionFailed(String),
+    #[error("Invalid length: {0}")]
+    InvalidLength(String),
+    #[error("Invalid key: {0}")]
+    InvalidKey(String),
+    #[error("Key generation failed: {0}")]
+    KeyGenerationFailed(String),
+    #[error("Invalid signature: {0}")]
+    SignatureInvalid(String),
+    #[error(transparent)]
+    BatchVerification(#[from] BatchVerificationError),
+    #[error("Invalid proof: {0}")]
+    ProofInvalid(String),
+}
+
+pub type Result<T> = std::result::Result<T, CryptoError>;
 
 /// Advanced hashing utilities
 pub mod hashing {
@@ -62,7 +91,7 @@ pub mod hashing {
         /// Create a new Merkle tree from leaves
         pub fn new(leaves: Vec<Vec<u8>>) -> Result<Self> {
             if leaves.is_empty() {
-                return Err(anyhow!("Cannot create Merkle tree with no leaves"));
+                return Err(CryptoError::InvalidLength("cannot create Merkle tree with no leaves".to_string()));
             }
 
             let root = Self::compute_root(&leaves)?;
@@ -96,7 +125,9 @@ pub mod hashing {
         /// Generate a Merkle proof for a leaf
         pub fn generate_proof(&self, leaf_index: usize) -> Result<Vec<Vec<u8>>> {
             if leaf_index >= self.leaves.len() {
-                return Err(anyhow!("Leaf index out of bounds"));
+                return Err(CryptoError::InvalidLength(format!(
+                    "leaf index {} out of bounds for {} leaves", leaf_index, self.leaves.len()
+                )));
             }
 
             let mut proof = Vec::new();
@@ -199,7 +230,7 @@ pub mod symmetric {
             let nonce = Nonce::from_slice(&nonce_bytes);
 
             let ciphertext = cipher.encrypt(nonce, data)
-                .map_err(|e| anyhow!("Encryption failed: {}", e))?;
+                .map_err(|e| CryptoError::EncryptionFailed(e.to_string()))?;
 
             Ok(EncryptedData {
                 ciphertext,
@@ -214,7 +245,7 @@ pub mod symmetric {
             let nonce = Nonce::from_slice(&encrypted_data.nonce);
 
             let plaintext = cipher.decrypt(nonce, encrypted_data.ciphertext.as_ref())
-                .map_err(|e| anyhow!("Decryption failed: {}", e))?;
+                .map_err(|e| CryptoError::DecryptionFailed(e.to_string()))?;
 
             Ok(plaintext)
         }
@@ -224,84 +255,289 @@ pub mod symmetric {
 /// Digital signature utilities
 pub mod signatures {
     use super::*;
-    use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signature, Signer, Verifier};
+    use ed25519_dalek::{Signer, SigningKey};
+    pub use ed25519_dalek::{Signature, VerifyingKey};
+    use std::fmt;
 
     /// Ed25519 key pair wrapper
     #[derive(Debug)]
     pub struct Ed25519KeyPair {
-        keypair: Keypair,
+        signing_key: SigningKey,
     }
 
     impl Ed25519KeyPair {
         /// Generate a new Ed25519 key pair
         pub fn generate<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
-            let keypair = Keypair::generate(rng);
-            Self { keypair }
+            Self { signing_key: SigningKey::generate(rng) }
+        }
+
+        /// Restore a key pair from `to_bytes` output (secret key followed by public key)
+        pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+            let bytes: &[u8; 64] = bytes.try_into()
+                .map_err(|_| CryptoError::InvalidKey(format!("Ed25519 key pair of {} bytes, expected 64", bytes.len())))?;
+            let signing_key = SigningKey::from_keypair_bytes(bytes)
+                .map_err(|e| CryptoError::InvalidKey(format!("Ed25519 key pair: {}", e)))?;
+            Ok(Self { signing_key })
+        }
+
+        /// Serialize the key pair (secret key followed by public key)
+        pub fn to_bytes(&self) -> [u8; 64] {
+            self.signing_key.to_keypair_bytes()
         }
 
         /// Get the public key
-        pub fn public_key(&self) -> PublicKey {
-            self.keypair.public
+        pub fn public_key(&self) -> VerifyingKey {
+            self.signing_key.verifying_key()
         }
 
         /// Sign a message
         pub fn sign(&self, message: &[u8]) -> Signature {
-            self.keypair.sign(message)
+            self.signing_key.sign(message)
         }
 
-        /// Verify a signature
+        /// Verify a signature, rejecting malleable signatures and small-order keys
         pub fn verify(&self, message: &[u8], signature: &Signature) -> Result<()> {
-            self.keypair.public.verify(message, signature)
-                .map_err(|e| anyhow!("Signature verification failed: {}", e))
+            self.signing_key.verifying_key().verify_strict(message, signature)
+                .map_err(|e| CryptoError::SignatureInvalid(e.to_string()))
+        }
+    }
+
+    /// Parse a public key received over the wire
+    pub fn public_key_from_bytes(bytes: &[u8]) -> Result<VerifyingKey> {
+        let bytes: &[u8; 32] = bytes.try_into()
+            .map_err(|_| CryptoError::InvalidKey(format!("Ed25519 public key of {} bytes, expected 32", bytes.len())))?;
+        VerifyingKey::from_bytes(bytes).map_err(|e| CryptoError::InvalidKey(format!("Ed25519 public key: {}", e)))
+    }
+
+    /// Encode a public key for the wire
+    pub fn public_key_to_bytes(public_key: &VerifyingKey) -> [u8; 32] {
+        public_key.to_bytes()
+    }
+
+    /// Verify a signature using only the signer's raw public key
+    pub fn verify_with_public_key(public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<()> {
+        let public_key = public_key_from_bytes(public_key)?;
+        let signature = Signature::from_slice(signature)
+            .map_err(|e| CryptoError::SignatureInvalid(format!("malformed Ed25519 signature: {}", e)))?;
+        public_key.verify_strict(message, &signature)
+            .map_err(|e| CryptoError::SignatureInvalid(e.to_string()))
+    }
+
+    /// Position of the first invalid signature in a batch
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct BatchVerificationError {
+        pub index: usize,
+    }
+
+    impl fmt::Display for BatchVerificationError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "Signature {} in batch failed verification", self.index)
+        }
+    }
+
+    impl std::error::Error for BatchVerificationError {}
+
+    /// Verify `signatures[i]` over `messages[i]` by `public_keys[i]` for every `i` at once
+    ///
+    /// A failed batch is re-checked one signature at a time so the error names the
+    /// first invalid index (`CryptoError::BatchVerification`).
+    pub fn verify_batch(messages: &[&[u8]], signatures: &[Signature], public_keys: &[VerifyingKey]) -> Result<()> {
+        if messages.len() != signatures.len() || messages.len() != public_keys.len() {
+            return Err(CryptoError::InvalidLength(format!(
+                "batch of {} messages, {} signatures, {} public keys",
+                messages.len(), signatures.len(), public_keys.len()
+            )));
+        }
+        if messages.is_empty() || ed25519_dalek::verify_batch(messages, signatures, public_keys).is_ok() {
+            return Ok(());
+        }
+
+        let index = (0..messages.len())
+            .find(|&i| public_keys[i].verify_strict(messages[i], &signatures[i]).is_err())
+            .ok_or_else(|| CryptoError::SignatureInvalid(
+                "batch verification failed but every signature verifies individually".to_string()
+            ))?;
+        Err(BatchVerificationError { index }.into())
+    }
+}
+
+/// BLS12-381 aggregate signatures
+///
+/// One aggregate signature proves every signer in a set approved the same message,
+/// e.g. a DAO multisig snapshot. Uses the proof-of-possession ciphersuite: public keys
+/// must pass `verify_possession` when registered, which rules out rogue-key attacks on
+/// `verify_aggregate`.
+pub mod bls {
+    use super::*;
+    use blst::min_pk::{AggregateSignature, SecretKey};
+    use blst::BLST_ERROR;
+    pub use blst::min_pk::{PublicKey, Signature};
+
+    /// Domain separation tag for message signatures
+    pub const SIGNATURE_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+    /// Domain separation tag for proofs of possession
+    pub const POSSESSION_DST: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+    /// BLS key pair
+    pub struct BlsKeyPair {
+        secret: SecretKey,
+        public: PublicKey,
+    }
+
+    impl BlsKeyPair {
+        /// Generate a new BLS key pair
+        pub fn generate<R: RngCore + CryptoRng>(rng: &mut R) -> Result<Self> {
+            let mut ikm = [0u8; 32];
+            rng.fill_bytes(&mut ikm);
+            let secret = SecretKey::key_gen(&ikm, &[])
+                .map_err(|e| CryptoError::KeyGenerationFailed(format!("BLS: {:?}", e)))?;
+            let public = secret.sk_to_pk();
+            Ok(Self { secret, public })
+        }
+
+        /// Get the public key
+        pub fn public_key(&self) -> PublicKey {
+            self.public
+        }
+
+        /// Sign a message
+        pub fn sign(&self, message: &[u8]) -> Signature {
+            self.secret.sign(message, SIGNATURE_DST, &[])
+        }
+
+        /// Prove ownership of the secret key behind `public_key`
+        pub fn prove_possession(&self) -> Signature {
+            self.secret.sign(&self.public.to_bytes(), POSSESSION_DST, &[])
+        }
+    }
+
+    /// Check a proof of possession before accepting a public key into a signer set
+    pub fn verify_possession(public_key: &PublicKey, proof: &Signature) -> Result<()> {
+        check(proof.verify(true, &public_key.to_bytes(), POSSESSION_DST, &[], public_key, true))
+            .map_err(|e| CryptoError::ProofInvalid(format!("BLS proof of possession: {}", e)))
+    }
+
+    /// Verify a single signature from raw public key and signature bytes
+    pub fn verify_with_public_key(public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<()> {
+        let public_key = PublicKey::from_bytes(public_key)
+            .map_err(|e| CryptoError::InvalidKey(format!("BLS public key: {:?}", e)))?;
+        let signature = Signature::from_bytes(signature)
+            .map_err(|e| CryptoError::SignatureInvalid(format!("malformed BLS signature: {:?}", e)))?;
+        check(signature.verify(true, message, SIGNATURE_DST, &[], &public_key, true))
+            .map_err(|e| CryptoError::SignatureInvalid(format!("BLS: {}", e)))
+    }
+
+    /// Combine signatures over the same message into one
+    pub fn aggregate(signatures: &[Signature]) -> Result<Signature> {
+        let signatures: Vec<&Signature> = signatures.iter().collect();
+        AggregateSignature::aggregate(&signatures, true)
+            .map(|aggregate| aggregate.to_signature())
+            .map_err(|e| CryptoError::SignatureInvalid(format!("BLS aggregation: {:?}", e)))
+    }
+
+    /// Verify that every key in `public_keys` signed `message`
+    pub fn verify_aggregate(public_keys: &[PublicKey], message: &[u8], aggregate: &Signature) -> Result<()> {
+        if public_keys.is_empty() {
+            return Err(CryptoError::InvalidLength("BLS aggregate verification needs at least one public key".to_string()));
+        }
+        let public_keys: Vec<&PublicKey> = public_keys.iter().collect();
+        check(aggregate.fast_aggregate_verify(true, message, SIGNATURE_DST, &public_keys))
+            .map_err(|e| CryptoError::SignatureInvalid(format!("BLS aggregate: {}", e)))
+    }
+
+    fn check(result: BLST_ERROR) -> std::result::Result<(), String> {
+        match result {
+            BLST_ERROR::BLST_SUCCESS => Ok(()),
+            error => Err(format!("{:?}", error)),
         }
     }
 }
 
 /// Cryptographic utilities manager
+/// Key material and nonces are drawn from the manager's RNG, `OsRng` unless another is
+/// injected with `with_rng`; a seeded RNG makes every generated key reproducible.
 #[derive(Debug)]
-pub struct CryptoManager {
+pub struct CryptoManager<R = OsRng> {
+    rng: R,
     symmetric_keys: HashMap<String, symmetric::SymmetricKey>,
     signature_keys: HashMap<String, signatures::Ed25519KeyPair>,
 }
 
 impl CryptoManager {
-    /// Create a new crypto manager
+    /// Create a new crypto manager drawing randomness from the OS
     pub fn new() -> Self {
+        Self::with_rng(OsRng)
+    }
+}
+
+impl<R: RngCore + CryptoRng> CryptoManager<R> {
+    /// Create a new crypto manager drawing randomness from `rng`
+    pub fn with_rng(rng: R) -> Self {
         Self {
+            rng,
             symmetric_keys: HashMap::new(),
             signature_keys: HashMap::new(),
         }
     }
 
     /// Generate and store a symmetric key
-    pub fn generate_symmetric_key<R: RngCore + CryptoRng>(
+    pub fn generate_symmetric_key(&mut self, key_id: String) {
+        let key = symmetric::SymmetricKey::generate(&mut self.rng);
+        self.symmetric_keys.insert(key_id, key);
+    }
+
+    /// Generate and store a symmetric key from `rng` instead of the manager's RNG
+    pub fn generate_symmetric_key_with_rng<G: RngCore + CryptoRng>(
         &mut self,
         key_id: String,
-        rng: &mut R,
+        rng: &mut G,
     ) {
         let key = symmetric::SymmetricKey::generate(rng);
         self.symmetric_keys.insert(key_id, key);
     }
 
     /// Generate and store a signature key pair
-    pub fn generate_signature_keypair<R: RngCore + CryptoRng>(
+    pub fn generate_signature_keypair(&mut self, key_id: String) {
+        let keypair = signatures::Ed25519KeyPair::generate(&mut self.rng);
+        self.signature_keys.insert(key_id, keypair);
+    }
+
+    /// Generate and store a signature key pair from `rng` instead of the manager's RNG
+    pub fn generate_signature_keypair_with_rng<G: RngCore + CryptoRng>(
         &mut self,
         key_id: String,
-        rng: &mut R,
+        rng: &mut G,
     ) {
         let keypair = signatures::Ed25519KeyPair::generate(rng);
         self.signature_keys.insert(key_id, keypair);
     }
 
-    /// Encrypt data with a symmetric key
-    pub fn encrypt_data<R: RngCore + CryptoRng>(
+    /// Stored symmetric key
+    pub fn symmetric_key(&self, key_id: &str) -> Option<&symmetric::SymmetricKey> {
+        self.symmetric_keys.get(key_id)
+    }
+
+    /// Stored signature key pair
+    pub fn signature_keypair(&self, key_id: &str) -> Option<&signatures::Ed25519KeyPair> {
+        self.signature_keys.get(key_id)
+    }
+
+    /// Encrypt data with a symmetric key, drawing the nonce from the manager's RNG
+    pub fn encrypt_data(&mut self, key_id: &str, data: &[u8]) -> Result<symmetric::EncryptedData> {
+        let key = self.symmetric_keys.get(key_id)
+            .ok_or_else(|| CryptoError::KeyNotFound(key_id.to_string()))?;
+        key.encrypt(data, &mut self.rng)
+    }
+
+    /// Encrypt data with a symmetric key, drawing the nonce from `rng`
+    pub fn encrypt_data_with_rng<G: RngCore + CryptoRng>(
         &self,
         key_id: &str,
         data: &[u8],
-        rng: &mut R,
+        rng: &mut G,
     ) -> Result<symmetric::EncryptedData> {
         let key = self.symmetric_keys.get(key_id)
-            .ok_or_else(|| anyhow!("Symmetric key not found: {}", key_id))?;
+            .ok_or_else(|| CryptoError::KeyNotFound(key_id.to_string()))?;
         key.encrypt(data, rng)
     }
 
@@ -312,7 +548,7 @@ impl CryptoManager {
         encrypted_data: &symmetric::EncryptedData,
     ) -> Result<Vec<u8>> {
         let key = self.symmetric_keys.get(key_id)
-            .ok_or_else(|| anyhow!("Symmetric key not found: {}", key_id))?;
+            .ok_or_else(|| CryptoError::KeyNotFound(key_id.to_string()))?;
         key.decrypt(encrypted_data)
     }
 }
@@ -322,3 +558,190 @@ impl Default for CryptoManager {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use signatures::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    #[test]
+    fn test_public_key_bytes_round_trip() {
+        let keypair = Ed25519KeyPair::generate(&mut ChaCha20Rng::seed_from_u64(1345));
+        let bytes = public_key_to_bytes(&keypair.public_key());
+        assert_eq!(public_key_from_bytes(&bytes).unwrap(), keypair.public_key());
+        assert!(public_key_from_bytes(&bytes[..31]).is_err());
+
+        let restored = Ed25519KeyPair::from_bytes(&keypair.to_bytes()).unwrap();
+        assert_eq!(restored.public_key(), keypair.public_key());
+    }
+
+    #[test]
+    fn test_verify_with_public_key_accepts_external_signature() {
+        use ed25519_dalek::Signer;
+
+        // Signed by a service that only shares its public key and signature bytes
+        let signer = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+        let message = b"fund origin snapshot";
+        let signature = signer.sign(message).to_bytes();
+
+        let public_bytes = signer.verifying_key().to_bytes();
+        assert!(verify_with_public_key(&public_bytes, message, &signature).is_ok());
+        assert!(verify_with_public_key(&public_bytes, b"altered snapshot", &signature).is_err());
+        assert!(verify_with_public_key(&public_bytes, message, &signature[..63]).is_err());
+    }
+
+    #[test]
+    fn test_verify_with_public_key_rejects_small_order_key() {
+        // Identity public key with R = identity and s = 0 satisfies the unbatched equation
+        // for every message; only strict verification refuses it
+        let mut identity = [0u8; 32];
+        identity[0] = 1;
+        let mut signature = [0u8; 64];
+        signature[..32].copy_from_slice(&identity);
+
+        assert!(verify_with_public_key(&identity, b"any message", &signature).is_err());
+    }
+
+    fn bls_signers() -> Vec<bls::BlsKeyPair> {
+        let mut rng = StdRng::seed_from_u64(1344);
+        (0..3).map(|_| bls::BlsKeyPair::generate(&mut rng).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_bls_aggregate_of_three_signers_verifies() {
+        let signers = bls_signers();
+        let snapshot = b"dao multisig snapshot epoch 42";
+        let public_keys: Vec<_> = signers.iter().map(|s| s.public_key()).collect();
+        for signer in &signers {
+            assert!(bls::verify_possession(&signer.public_key(), &signer.prove_possession()).is_ok());
+        }
+
+        let signatures: Vec<_> = signers.iter().map(|s| s.sign(snapshot)).collect();
+        let aggregate = bls::aggregate(&signatures).unwrap();
+
+        assert!(bls::verify_aggregate(&public_keys, snapshot, &aggregate).is_ok());
+        assert!(bls::verify_aggregate(&public_keys, b"different snapshot", &aggregate).is_err());
+    }
+
+    #[test]
+    fn test_bls_aggregate_missing_signer_is_rejected() {
+        let signers = bls_signers();
+        let snapshot = b"dao multisig snapshot epoch 42";
+        let public_keys: Vec<_> = signers.iter().map(|s| s.public_key()).collect();
+
+        let partial = bls::aggregate(&[signers[0].sign(snapshot), signers[1].sign(snapshot)]).unwrap();
+        assert!(bls::verify_aggregate(&public_keys, snapshot, &partial).is_err());
+        assert!(bls::verify_aggregate(&public_keys[..2], snapshot, &partial).is_ok());
+
+        // A proof of possession does not transfer to another key
+        assert!(bls::verify_possession(&signers[0].public_key(), &signers[1].prove_possession()).is_err());
+    }
+
+    fn signed_batch(size: usize) -> (Vec<Vec<u8>>, Vec<Signature>, Vec<VerifyingKey>) {
+        let mut rng = StdRng::seed_from_u64(1344);
+        let mut messages = Vec::new();
+        let mut sigs = Vec::new();
+        let mut keys = Vec::new();
+        for i in 0..size {
+            let keypair = Ed25519KeyPair::generate(&mut rng);
+            let message = format!("snapshot-{}", i).into_bytes();
+            sigs.push(keypair.sign(&message));
+            keys.push(keypair.public_key());
+            messages.push(message);
+        }
+        (messages, sigs, keys)
+    }
+
+    #[test]
+    fn test_verify_batch_accepts_valid_signatures() {
+        let (messages, sigs, keys) = signed_batch(8);
+        let messages: Vec<&[u8]> = messages.iter().map(Vec::as_slice).collect();
+        assert!(verify_batch(&messages, &sigs, &keys).is_ok());
+        assert!(verify_batch(&messages[..2], &sigs, &keys).is_err());
+    }
+
+    #[test]
+    fn test_verify_batch_identifies_corrupted_signature() {
+        let (messages, mut sigs, keys) = signed_batch(8);
+        let mut corrupted = sigs[5].to_bytes();
+        corrupted[0] ^= 0x01;
+        sigs[5] = Signature::from_bytes(&corrupted);
+
+        let messages: Vec<&[u8]> = messages.iter().map(Vec::as_slice).collect();
+        let err = verify_batch(&messages, &sigs, &keys).unwrap_err();
+        assert_eq!(err, CryptoError::BatchVerification(BatchVerificationError { index: 5 }));
+    }
+
+    fn seeded_manager(seed: u64) -> CryptoManager<ChaCha20Rng> {
+        let mut manager = CryptoManager::with_rng(ChaCha20Rng::seed_from_u64(seed));
+        manager.generate_symmetric_key("vault".to_string());
+        manager.generate_signature_keypair("oracle".to_string());
+        manager
+    }
+
+    #[test]
+    fn test_seeded_manager_generates_identical_keys() {
+        let (mut first, mut second) = (seeded_manager(42), seeded_manager(42));
+        assert_eq!(first.symmetric_key("vault").unwrap().key_data, second.symmetric_key("vault").unwrap().key_data);
+        assert_eq!(
+            first.signature_keypair("oracle").unwrap().to_bytes(),
+            second.signature_keypair("oracle").unwrap().to_bytes()
+        );
+
+        // Nonces come from the same stream, so ciphertexts match too
+        let first_encrypted = first.encrypt_data("vault", b"nav snapshot").unwrap();
+        let second_encrypted = second.encrypt_data("vault", b"nav snapshot").unwrap();
+        assert_eq!(first_encrypted.nonce, second_encrypted.nonce);
+        assert_eq!(first_encrypted.ciphertext, second_encrypted.ciphertext);
+        assert_eq!(first.decrypt_data("vault", &first_encrypted).unwrap(), b"nav snapshot");
+
+        let other = seeded_manager(43);
+        assert_ne!(first.symmetric_key("vault").unwrap().key_data, other.symmetric_key("vault").unwrap().key_data);
+    }
+
+    #[test]
+    fn test_injected_rng_matches_direct_generation() {
+        let mut manager = CryptoManager::new();
+        manager.generate_symmetric_key_with_rng("vault".to_string(), &mut ChaCha20Rng::seed_from_u64(7));
+        manager.generate_signature_keypair_with_rng("oracle".to_string(), &mut ChaCha20Rng::seed_from_u64(7));
+
+        let key = symmetric::SymmetricKey::generate(&mut ChaCha20Rng::seed_from_u64(7));
+        let keypair = Ed25519KeyPair::generate(&mut ChaCha20Rng::seed_from_u64(7));
+        assert_eq!(manager.symmetric_key("vault").unwrap().key_data, key.key_data);
+        assert_eq!(manager.signature_keypair("oracle").unwrap().to_bytes(), keypair.to_bytes());
+    }
+
+    #[test]
+    fn test_missing_key_id_is_key_not_found() {
+        let mut manager = CryptoManager::with_rng(ChaCha20Rng::seed_from_u64(1));
+        manager.generate_symmetric_key("vault".to_string());
+        let encrypted = manager.encrypt_data("vault", b"nav snapshot").unwrap();
+
+        match manager.encrypt_data("treasury", b"nav snapshot") {
+            Err(CryptoError::KeyNotFound(key_id)) => assert_eq!(key_id, "treasury"),
+            other => panic!("expected KeyNotFound, got {:?}", other),
+        }
+        assert_eq!(
+            manager.decrypt_data("treasury", &encrypted),
+            Err(CryptoError::KeyNotFound("treasury".to_string()))
+        );
+
+        // Still usable as an anyhow error
+        let err: anyhow::Error = manager.decrypt_data("treasury", &encrypted).unwrap_err().into();
+        assert!(matches!(err.downcast_ref::<CryptoError>(), Some(CryptoError::KeyNotFound(_))));
+    }
+
+    #[test]
+    fn test_tampered_ciphertext_is_decryption_failed() {
+        let mut manager = CryptoManager::with_rng(ChaCha20Rng::seed_from_u64(1));
+        manager.generate_symmetric_key("vault".to_string());
+        let mut encrypted = manager.encrypt_data("vault", b"nav snapshot").unwrap();
+        encrypted.ciphertext[0] ^= 0x01;
+        assert!(matches!(manager.decrypt_data("vault", &encrypted), Err(CryptoError::DecryptionFailed(_))));
+
+        assert!(matches!(hashing::MerkleTree::new(Vec::new()), Err(CryptoError::InvalidLength(_))));
+    }
+}
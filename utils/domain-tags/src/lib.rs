@@ -0,0 +1,187 @@
+//! # Hash Domain Tags
+//!
+//! Every hash RTF signs, commits to or stores is computed over a domain tag followed by
+//! the payload, so a digest valid in one context can never be replayed in another. The
+//! tags live here rather than next to each hashing site so they cannot collide:
+//! - every tag is distinct and none is a prefix of another
+//! - a tag's bytes never change once released; a new encoding gets a new versioned tag
+//!
+//! Hashing sites feed `DomainTag::as_bytes` to the hasher before anything else.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DomainTag {
+    /// Public inputs of the zkNAV proof
+    NavProof,
+    /// Off-chain verifier's signed NAV attestation
+    NavAttestation,
+    /// NAV content, excluding the timestamp, used to spot duplicate submissions
+    NavContent,
+    /// Bridged message origin proofs
+    ChainOrigin,
+    /// Bridge message envelopes checked by the zk message filter
+    BridgeMessage,
+    /// ESG attestation commitments
+    EsgAttestation,
+    /// Origin hash of a fund forked from a parent fund
+    DerivedFundOrigin,
+    /// Fund-origin snapshot message signed by origin signers
+    FundOriginSnapshot,
+    /// Commitment binding a proposal's text to its instruction data
+    SemanticCommitment,
+    /// Hash-chained audit log entries
+    AuditEntry,
+    /// Scenario key of an LLM governance analysis context
+    LlmContext,
+    /// Vault redemption queue commitments
+    VaultRedemptionCommitment,
+    /// Redemption engine commit-reveal commitments
+    RedemptionCommitment,
+    /// LLM NAV forecasts signed by the forecast authority
+    NavForecast,
+    /// Emergency actions approved by the emergency multisig
+    EmergencyActivation,
+    /// Clearance of an active emergency action
+    EmergencyClearance,
+    /// Execution approvals for an armed suicide lock
+    SuicideLockExecution,
+    /// Cancellation approvals for an armed suicide lock
+    SuicideLockCancellation,
+    /// Digest a KILT credential's proof commits to
+    KiltCredential,
+    /// Compliance proofs attached to vault deposits
+    ComplianceProof,
+    /// Pre-V3 redemption engine commitments, which carried the tag as a suffix.
+    /// Only accepted at reveal, for commitments made before the V3 upgrade
+    LegacyRedemptionCommitment,
+    /// Fund whitelist changes signed by a registered authorizer
+    WhitelistAuthorization,
+}
+
+impl DomainTag {
+    pub const ALL: [DomainTag; 22] = [
+        DomainTag::NavProof,
+        DomainTag::NavAttestation,
+        DomainTag::NavContent,
+        DomainTag::ChainOrigin,
+        DomainTag::BridgeMessage,
+        DomainTag::EsgAttestation,
+        DomainTag::DerivedFundOrigin,
+        DomainTag::FundOriginSnapshot,
+        DomainTag::SemanticCommitment,
+        DomainTag::AuditEntry,
+        DomainTag::LlmContext,
+        DomainTag::VaultRedemptionCommitment,
+        DomainTag::RedemptionCommitment,
+        DomainTag::NavForecast,
+        DomainTag::EmergencyActivation,
+        DomainTag::EmergencyClearance,
+        DomainTag::SuicideLockExecution,
+        DomainTag::SuicideLockCancellation,
+        DomainTag::KiltCredential,
+        DomainTag::ComplianceProof,
+        DomainTag::LegacyRedemptionCommitment,
+        DomainTag::WhitelistAuthorization,
+    ];
+
+    /// Bytes prefixed to the hashed payload
+    pub const fn as_bytes(self) -> &'static [u8] {
+        match self {
+            DomainTag::NavProof => b"RTF_NAV_PROOF_V2",
+            DomainTag::NavAttestation => b"RTF_NAV_ATTESTATION_V1",
+            DomainTag::NavContent => b"RTF_NAV_CONTENT_V1",
+            DomainTag::ChainOrigin => b"RTF-CHAIN-ORIGIN-V1",
+            DomainTag::BridgeMessage => b"RTF-BRIDGE-MESSAGE-V1",
+            DomainTag::EsgAttestation => b"RTF-ESG-ATTESTATION-V1",
+            DomainTag::DerivedFundOrigin => b"RTF_DERIVED_FUND_ORIGIN_V1",
+            DomainTag::FundOriginSnapshot => b"RTF_FUND_ORIGIN_SNAPSHOT",
+            DomainTag::SemanticCommitment => b"RTF-SEMANTIC-COMMITMENT-V1",
+            DomainTag::AuditEntry => b"RTF_AUDIT_ENTRY_V1",
+            DomainTag::LlmContext => b"RTF_LLM_CONTEXT_V1",
+            DomainTag::VaultRedemptionCommitment => b"RTF_VAULT_REDEMPTION_COMMITMENT_V1",
+            DomainTag::RedemptionCommitment => b"RTF_REDEMPTION_COMMITMENT_V3",
+            DomainTag::NavForecast => b"RTF_NAV_FORECAST_V1",
+            DomainTag::EmergencyActivation => b"RTF_EMERGENCY_ACTIVATION",
+            DomainTag::EmergencyClearance => b"RTF_EMERGENCY_CLEARANCE",
+            DomainTag::SuicideLockExecution => b"RTF_SUICIDE_LOCK_EXECUTION",
+            DomainTag::SuicideLockCancellation => b"RTF_SUICIDE_LOCK_CANCELLATION_V1",
+            DomainTag::KiltCredential => b"RTF-KILT-CREDENTIAL-V1",
+            DomainTag::ComplianceProof => b"RTF_COMPLIANCE_PROOF_V2",
+            DomainTag::LegacyRedemptionCommitment => b"RTF_REDEMPTION_COMMITMENT_V2",
+            DomainTag::WhitelistAuthorization => b"RTF_WHITELIST_AUTHORIZATION_V1",
+        }
+    }
+}
+
+impl AsRef<[u8]> for DomainTag {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha2::{Digest, Sha256};
+
+    fn tagged_digest(tag: DomainTag, payload: &[u8]) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(tag.as_bytes());
+        hasher.update(payload);
+        hasher.finalize().into()
+    }
+
+    #[test]
+    fn test_same_payload_under_different_tags_has_different_digests() {
+        let payload = b"vault-1:1000000:12345";
+        for (i, a) in DomainTag::ALL.iter().enumerate() {
+            for b in &DomainTag::ALL[i + 1..] {
+                assert_ne!(tagged_digest(*a, payload), tagged_digest(*b, payload), "{:?} vs {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_no_tag_is_a_prefix_of_another() {
+        // Otherwise `tag_a || payload` could equal `tag_b || other_payload`
+        for a in DomainTag::ALL {
+            for b in DomainTag::ALL {
+                if a != b {
+                    assert!(!b.as_bytes().starts_with(a.as_bytes()), "{:?} prefixes {:?}", a, b);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_tags_are_stable() {
+        // Changing any of these invalidates every stored digest and signature under it
+        let expected: [(DomainTag, &[u8]); 22] = [
+            (DomainTag::NavProof, b"RTF_NAV_PROOF_V2"),
+            (DomainTag::NavAttestation, b"RTF_NAV_ATTESTATION_V1"),
+            (DomainTag::NavContent, b"RTF_NAV_CONTENT_V1"),
+            (DomainTag::ChainOrigin, b"RTF-CHAIN-ORIGIN-V1"),
+            (DomainTag::BridgeMessage, b"RTF-BRIDGE-MESSAGE-V1"),
+            (DomainTag::EsgAttestation, b"RTF-ESG-ATTESTATION-V1"),
+            (DomainTag::DerivedFundOrigin, b"RTF_DERIVED_FUND_ORIGIN_V1"),
+            (DomainTag::FundOriginSnapshot, b"RTF_FUND_ORIGIN_SNAPSHOT"),
+            (DomainTag::SemanticCommitment, b"RTF-SEMANTIC-COMMITMENT-V1"),
+            (DomainTag::AuditEntry, b"RTF_AUDIT_ENTRY_V1"),
+            (DomainTag::LlmContext, b"RTF_LLM_CONTEXT_V1"),
+            (DomainTag::VaultRedemptionCommitment, b"RTF_VAULT_REDEMPTION_COMMITMENT_V1"),
+            (DomainTag::RedemptionCommitment, b"RTF_REDEMPTION_COMMITMENT_V3"),
+            (DomainTag::NavForecast, b"RTF_NAV_FORECAST_V1"),
+            (DomainTag::EmergencyActivation, b"RTF_EMERGENCY_ACTIVATION"),
+            (DomainTag::EmergencyClearance, b"RTF_EMERGENCY_CLEARANCE"),
+            (DomainTag::SuicideLockExecution, b"RTF_SUICIDE_LOCK_EXECUTION"),
+            (DomainTag::SuicideLockCancellation, b"RTF_SUICIDE_LOCK_CANCELLATION_V1"),
+            (DomainTag::KiltCredential, b"RTF-KILT-CREDENTIAL-V1"),
+            (DomainTag::ComplianceProof, b"RTF_COMPLIANCE_PROOF_V2"),
+            (DomainTag::LegacyRedemptionCommitment, b"RTF_REDEMPTION_COMMITMENT_V2"),
+            (DomainTag::WhitelistAuthorization, b"RTF_WHITELIST_AUTHORIZATION_V1"),
+        ];
+        for (tag, bytes) in expected {
+            assert_eq!(tag.as_bytes(), bytes, "{:?}", tag);
+        }
+        assert_eq!(expected.len(), DomainTag::ALL.len());
+    }
+}
@@ -0,0 +1,223 @@
+//! # Ed25519 Precompile Introspection for RTF Programs
+//!
+//! Verifying Ed25519 in BPF costs far more compute than a transaction can spare, so RTF
+//! programs let the runtime's Ed25519 precompile check signatures instead:
+//! - the client places an Ed25519 program instruction immediately before the program
+//!   instruction that relies on the signature
+//! - the runtime rejects the whole transaction if that signature is invalid
+//! - the program reads the preceding instruction from the instructions sysvar and checks it
+//!   verified the expected signer over the expected message
+//!
+//! Only single-signature instructions that carry their key, signature and message inline
+//! are accepted, so the offsets cannot point the precompile at data from another instruction.
+
+use solana_program::account_info::AccountInfo;
+use solana_program::ed25519_program;
+use solana_program::instruction::Instruction;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+
+/// Size of the `num_signatures` byte plus padding
+const HEADER_LEN: usize = 2;
+/// Size of one `Ed25519SignatureOffsets` entry
+const OFFSETS_LEN: usize = 14;
+const PUBLIC_KEY_LEN: usize = 32;
+const SIGNATURE_LEN: usize = 64;
+
+const PUBLIC_KEY_OFFSET: usize = HEADER_LEN + OFFSETS_LEN;
+const SIGNATURE_OFFSET: usize = PUBLIC_KEY_OFFSET + PUBLIC_KEY_LEN;
+const MESSAGE_OFFSET: usize = SIGNATURE_OFFSET + SIGNATURE_LEN;
+
+/// Instruction index the precompile reads as "this instruction"
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Why a preceding instruction does not prove the expected signature
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ed25519InstructionError {
+    /// No instruction precedes the current one
+    Missing,
+    /// The preceding instruction is not addressed to the Ed25519 program
+    WrongProgram,
+    /// The instruction data does not hold exactly one inline signature
+    MalformedData,
+    /// The signature was checked against a different public key
+    SignerMismatch,
+    /// The signature was checked over a different message
+    MessageMismatch,
+}
+
+/// Ed25519 program instruction that checks `signature` by `signer` over `message`
+pub fn new_ed25519_instruction(signer: &Pubkey, signature: &[u8; 64], message: &[u8]) -> Instruction {
+    let offsets = [
+        SIGNATURE_OFFSET as u16,
+        CURRENT_INSTRUCTION,
+        PUBLIC_KEY_OFFSET as u16,
+        CURRENT_INSTRUCTION,
+        MESSAGE_OFFSET as u16,
+        message.len() as u16,
+        CURRENT_INSTRUCTION,
+    ];
+
+    let mut data = Vec::with_capacity(MESSAGE_OFFSET + message.len());
+    data.extend_from_slice(&[1, 0]);
+    for offset in offsets {
+        data.extend_from_slice(&offset.to_le_bytes());
+    }
+    data.extend_from_slice(signer.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+
+    Instruction {
+        program_id: ed25519_program::ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Instruction immediately before the one currently executing
+pub fn load_preceding_instruction(instructions_sysvar: &AccountInfo) -> Result<Instruction, ProgramError> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let preceding_index = current_index
+        .checked_sub(1)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    load_instruction_at_checked(preceding_index as usize, instructions_sysvar)
+}
+
+/// Check `instruction` is an Ed25519 program instruction that verified `signer` over `message`
+pub fn verify_ed25519_instruction(
+    instruction: &Instruction,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<(), Ed25519InstructionError> {
+    if instruction.program_id != ed25519_program::ID {
+        return Err(Ed25519InstructionError::WrongProgram);
+    }
+
+    let data = &instruction.data;
+    if data.len() < MESSAGE_OFFSET || data[0] != 1 {
+        return Err(Ed25519InstructionError::MalformedData);
+    }
+
+    let offset = |index: usize| {
+        let start = HEADER_LEN + index * 2;
+        u16::from_le_bytes([data[start], data[start + 1]])
+    };
+    let [signature_offset, signature_index, public_key_offset, public_key_index, message_offset, message_size, message_index] =
+        [0, 1, 2, 3, 4, 5, 6].map(offset);
+
+    if [signature_index, public_key_index, message_index]
+        .iter()
+        .any(|&index| index != CURRENT_INSTRUCTION)
+    {
+        return Err(Ed25519InstructionError::MalformedData);
+    }
+    let read = |offset: u16, len: usize| data.get(offset as usize..offset as usize + len);
+    let signature = read(signature_offset, SIGNATURE_LEN);
+    let public_key = read(public_key_offset, PUBLIC_KEY_LEN);
+    let signed_message = read(message_offset, message_size as usize);
+    let (Some(_), Some(public_key), Some(signed_message)) = (signature, public_key, signed_message) else {
+        return Err(Ed25519InstructionError::MalformedData);
+    };
+
+    if public_key != signer.as_ref() {
+        return Err(Ed25519InstructionError::SignerMismatch);
+    }
+    if signed_message != message {
+        return Err(Ed25519InstructionError::MessageMismatch);
+    }
+    Ok(())
+}
+
+/// Load the instruction before the current one and check it verified `signer` over `message`
+pub fn verify_preceding_ed25519(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<(), Ed25519InstructionError> {
+    let instruction =
+        load_preceding_instruction(instructions_sysvar).map_err(|_| Ed25519InstructionError::Missing)?;
+    verify_ed25519_instruction(&instruction, signer, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    const MESSAGE: &[u8] = b"nav attestation digest";
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_bytes(&[42; 32])
+    }
+
+    fn signer() -> Pubkey {
+        Pubkey::new_from_array(signing_key().verifying_key().to_bytes())
+    }
+
+    fn signed_instruction(message: &[u8]) -> Instruction {
+        let signature = signing_key().sign(message).to_bytes();
+        new_ed25519_instruction(&signer(), &signature, message)
+    }
+
+    #[test]
+    fn test_layout_matches_precompile_offsets() {
+        let instruction = signed_instruction(MESSAGE);
+        assert_eq!(instruction.program_id, ed25519_program::ID);
+        assert_eq!(instruction.data.len(), 112 + MESSAGE.len());
+        assert_eq!(&instruction.data[..4], &[1, 0, 48, 0]);
+        assert_eq!(&instruction.data[16..48], signer().as_ref());
+        assert_eq!(&instruction.data[112..], MESSAGE);
+    }
+
+    #[test]
+    fn test_matching_instruction_is_accepted() {
+        assert_eq!(verify_ed25519_instruction(&signed_instruction(MESSAGE), &signer(), MESSAGE), Ok(()));
+    }
+
+    #[test]
+    fn test_mismatched_instruction_is_rejected() {
+        let instruction = signed_instruction(MESSAGE);
+        let other_signer = Pubkey::new_from_array(SigningKey::from_bytes(&[1; 32]).verifying_key().to_bytes());
+        assert_eq!(
+            verify_ed25519_instruction(&instruction, &other_signer, MESSAGE),
+            Err(Ed25519InstructionError::SignerMismatch)
+        );
+        assert_eq!(
+            verify_ed25519_instruction(&instruction, &signer(), b"other digest"),
+            Err(Ed25519InstructionError::MessageMismatch)
+        );
+
+        let mut wrong_program = instruction.clone();
+        wrong_program.program_id = Pubkey::new_unique();
+        assert_eq!(
+            verify_ed25519_instruction(&wrong_program, &signer(), MESSAGE),
+            Err(Ed25519InstructionError::WrongProgram)
+        );
+    }
+
+    #[test]
+    fn test_offsets_into_other_instructions_are_rejected() {
+        // Message index pointing at instruction 0 instead of the precompile's own data
+        let mut instruction = signed_instruction(MESSAGE);
+        instruction.data[14..16].copy_from_slice(&0u16.to_le_bytes());
+        assert_eq!(
+            verify_ed25519_instruction(&instruction, &signer(), MESSAGE),
+            Err(Ed25519InstructionError::MalformedData)
+        );
+
+        let mut two_signatures = signed_instruction(MESSAGE);
+        two_signatures.data[0] = 2;
+        assert_eq!(
+            verify_ed25519_instruction(&two_signatures, &signer(), MESSAGE),
+            Err(Ed25519InstructionError::MalformedData)
+        );
+
+        let mut truncated = signed_instruction(MESSAGE);
+        truncated.data.truncate(120);
+        assert_eq!(
+            verify_ed25519_instruction(&truncated, &signer(), MESSAGE),
+            Err(Ed25519InstructionError::MalformedData)
+        );
+    }
+}
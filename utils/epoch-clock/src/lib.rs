@@ -0,0 +1,211 @@
+//! # Epoch Clock for RTF Services
+//!
+//! zkReplay, redemption and governance all bucket time into fixed-length epochs, and must
+//! agree on which epoch a timestamp belongs to. Every service derives epochs through this
+//! crate instead of doing its own timestamp math:
+//! - epoch `n` covers `[genesis + n * duration, genesis + (n + 1) * duration)`
+//! - timestamps before genesis belong to epoch 0
+//! - timestamps are Unix seconds
+//!
+//! Services hold an `Arc<dyn EpochClock>` so tests can swap in a `MockEpochClock`.
+//! Without the default `std` feature only the epoch math and the trait are built, so
+//! on-chain programs can share them without pulling in the system clock.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use core::fmt::Debug;
+#[cfg(feature = "std")]
+use std::sync::atomic::{AtomicI64, Ordering};
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Epoch containing `timestamp`. A zero `epoch_duration` is treated as one second.
+pub fn epoch_for(timestamp: i64, genesis: i64, epoch_duration: u64) -> u64 {
+    if timestamp <= genesis {
+        return 0;
+    }
+    let elapsed = (timestamp as i128 - genesis as i128) as u128;
+    (elapsed / epoch_duration.max(1) as u128).min(u64::MAX as u128) as u64
+}
+
+/// First timestamp of `epoch`, saturating at `i64::MAX`
+pub fn epoch_start(epoch: u64, genesis: i64, epoch_duration: u64) -> i64 {
+    let start = genesis as i128 + epoch as i128 * epoch_duration.max(1) as i128;
+    start.min(i64::MAX as i128) as i64
+}
+
+/// Source of epochs; services take one through `with_epoch_clock` so they can derive
+/// epochs from a `MockEpochClock` instead of the system clock
+pub trait EpochClock: Debug + Send + Sync {
+    /// Length of every epoch in seconds
+    fn epoch_duration(&self) -> u64;
+
+    /// Start of epoch 0
+    fn genesis(&self) -> i64;
+
+    /// Current Unix time in seconds
+    fn now(&self) -> i64;
+
+    fn current_epoch(&self) -> u64 {
+        self.epoch_for(self.now())
+    }
+
+    fn epoch_start(&self, epoch: u64) -> i64 {
+        epoch_start(epoch, self.genesis(), self.epoch_duration())
+    }
+
+    fn epoch_for(&self, timestamp: i64) -> u64 {
+        epoch_for(timestamp, self.genesis(), self.epoch_duration())
+    }
+}
+
+/// Epochs over the system clock
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct SystemEpochClock {
+    epoch_duration: u64,
+    genesis: i64,
+}
+
+#[cfg(feature = "std")]
+impl SystemEpochClock {
+    pub fn new(epoch_duration: u64) -> Self {
+        Self { epoch_duration: epoch_duration.max(1), genesis: 0 }
+    }
+
+    pub fn with_genesis(mut self, genesis: i64) -> Self {
+        self.genesis = genesis;
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl EpochClock for SystemEpochClock {
+    fn epoch_duration(&self) -> u64 {
+        self.epoch_duration
+    }
+
+    fn genesis(&self) -> i64 {
+        self.genesis
+    }
+
+    fn now(&self) -> i64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs() as i64)
+            .unwrap_or_default()
+    }
+}
+
+/// Epochs over a manually driven clock, for tests
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct MockEpochClock {
+    epoch_duration: u64,
+    genesis: i64,
+    now: AtomicI64,
+}
+
+#[cfg(feature = "std")]
+impl MockEpochClock {
+    pub fn new(epoch_duration: u64, now: i64) -> Self {
+        Self { epoch_duration: epoch_duration.max(1), genesis: 0, now: AtomicI64::new(now) }
+    }
+
+    pub fn with_genesis(mut self, genesis: i64) -> Self {
+        self.genesis = genesis;
+        self
+    }
+
+    pub fn set_now(&self, now: i64) {
+        self.now.store(now, Ordering::SeqCst);
+    }
+
+    pub fn advance(&self, seconds: i64) {
+        self.now.fetch_add(seconds, Ordering::SeqCst);
+    }
+
+    /// Move forward by whole epochs, keeping the offset within the epoch
+    pub fn advance_epochs(&self, epochs: u64) {
+        self.advance((epochs * self.epoch_duration) as i64);
+    }
+}
+
+#[cfg(feature = "std")]
+impl EpochClock for MockEpochClock {
+    fn epoch_duration(&self) -> u64 {
+        self.epoch_duration
+    }
+
+    fn genesis(&self) -> i64 {
+        self.genesis
+    }
+
+    fn now(&self) -> i64 {
+        self.now.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    const DAY: u64 = 86_400;
+    const GENESIS: i64 = 1_700_000_000;
+
+    #[test]
+    fn test_boundary_timestamps_map_to_the_same_epoch_everywhere() {
+        let system = SystemEpochClock::new(DAY).with_genesis(GENESIS);
+        let mock = MockEpochClock::new(DAY, GENESIS).with_genesis(GENESIS);
+
+        for epoch in [0u64, 1, 2, 365] {
+            let start = epoch_start(epoch, GENESIS, DAY);
+            assert_eq!(system.epoch_start(epoch), start);
+            assert_eq!(mock.epoch_start(epoch), start);
+
+            // The first second of an epoch belongs to it, the last second of the previous
+            // epoch does not
+            for (timestamp, expected) in [
+                (start - 1, epoch.saturating_sub(1)),
+                (start, epoch),
+                (start + DAY as i64 - 1, epoch),
+                (start + DAY as i64, epoch + 1),
+            ] {
+                assert_eq!(epoch_for(timestamp, GENESIS, DAY), expected, "timestamp {}", timestamp);
+                assert_eq!(system.epoch_for(timestamp), expected);
+                assert_eq!(mock.epoch_for(timestamp), expected);
+
+                let derived = mock.epoch_for(timestamp);
+                assert!(mock.epoch_start(derived) <= timestamp.max(GENESIS));
+                assert!(timestamp < mock.epoch_start(derived + 1));
+            }
+        }
+
+        // Before genesis everything is epoch 0
+        assert_eq!(system.epoch_for(0), 0);
+        assert_eq!(system.epoch_for(i64::MIN), 0);
+    }
+
+    #[test]
+    fn test_mock_clock_current_epoch_follows_time() {
+        let clock = MockEpochClock::new(DAY, GENESIS + 10).with_genesis(GENESIS);
+        assert_eq!(clock.current_epoch(), 0);
+
+        clock.advance_epochs(3);
+        assert_eq!(clock.current_epoch(), 3);
+        assert_eq!(clock.now() - clock.epoch_start(3), 10);
+
+        clock.set_now(clock.epoch_start(5) - 1);
+        assert_eq!(clock.current_epoch(), 4);
+        clock.advance(1);
+        assert_eq!(clock.current_epoch(), 5);
+    }
+
+    #[test]
+    fn test_system_clock_current_epoch_matches_epoch_for_now() {
+        let clock = SystemEpochClock::new(DAY);
+        let epoch = clock.current_epoch();
+        assert!(epoch == clock.epoch_for(clock.now()) || epoch + 1 == clock.epoch_for(clock.now()));
+        assert!(epoch > 19_000);
+    }
+}
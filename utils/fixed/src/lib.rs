@@ -0,0 +1,228 @@
+//! # Fixed-Point Arithmetic for RTF Infrastructure
+//!
+//! Deterministic decimal arithmetic for consensus-relevant values: NAV per share,
+//! share balances and redemption amounts. Values are `i128` scaled by 10^DECIMALS,
+//! every operation is checked, and every division rounds in an explicit direction,
+//! so results are identical on every platform that evaluates them.
+
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum FixedPointError {
+    #[error("Fixed-point arithmetic overflow")]
+    Overflow,
+    #[error("Fixed-point division by zero")]
+    DivisionByZero,
+    #[error("Fixed-point value out of range for the target type")]
+    OutOfRange,
+}
+
+pub type FixedResult<T> = std::result::Result<T, FixedPointError>;
+
+/// Direction applied when a result is not representable at the target precision
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    /// Toward negative infinity
+    Down,
+    /// Toward positive infinity
+    Up,
+    /// To the nearest value, ties toward positive infinity
+    HalfUp,
+}
+
+/// Decimal number stored as `raw / 10^DECIMALS`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct FixedPoint<const DECIMALS: u32> {
+    raw: i128,
+}
+
+/// Six decimals: SPL token amounts, vault shares and `nav_per_share`
+pub type Fixed6 = FixedPoint<6>;
+/// Nine decimals: rates and intermediate risk calculations
+pub type Fixed9 = FixedPoint<9>;
+
+impl<const DECIMALS: u32> FixedPoint<DECIMALS> {
+    pub const SCALE: i128 = 10i128.pow(DECIMALS);
+    pub const ZERO: Self = Self { raw: 0 };
+    pub const ONE: Self = Self { raw: Self::SCALE };
+
+    pub const fn from_raw(raw: i128) -> Self {
+        Self { raw }
+    }
+
+    /// Interpret an on-chain `u64` amount that is already scaled by 10^DECIMALS
+    pub const fn from_u64_raw(raw: u64) -> Self {
+        Self { raw: raw as i128 }
+    }
+
+    pub const fn raw(self) -> i128 {
+        self.raw
+    }
+
+    pub fn from_integer(value: i64) -> FixedResult<Self> {
+        (value as i128).checked_mul(Self::SCALE)
+            .map(Self::from_raw)
+            .ok_or(FixedPointError::Overflow)
+    }
+
+    /// Raw value as an on-chain `u64` amount
+    pub fn to_u64_raw(self) -> FixedResult<u64> {
+        u64::try_from(self.raw).map_err(|_| FixedPointError::OutOfRange)
+    }
+
+    pub fn is_negative(self) -> bool {
+        self.raw < 0
+    }
+
+    pub fn checked_add(self, other: Self) -> FixedResult<Self> {
+        self.raw.checked_add(other.raw).map(Self::from_raw).ok_or(FixedPointError::Overflow)
+    }
+
+    pub fn checked_sub(self, other: Self) -> FixedResult<Self> {
+        self.raw.checked_sub(other.raw).map(Self::from_raw).ok_or(FixedPointError::Overflow)
+    }
+
+    pub fn checked_mul(self, other: Self, rounding: Rounding) -> FixedResult<Self> {
+        let product = self.raw.checked_mul(other.raw).ok_or(FixedPointError::Overflow)?;
+        div_round(product, Self::SCALE, rounding).map(Self::from_raw)
+    }
+
+    pub fn checked_div(self, other: Self, rounding: Rounding) -> FixedResult<Self> {
+        if other.raw == 0 {
+            return Err(FixedPointError::DivisionByZero);
+        }
+        let numerator = self.raw.checked_mul(Self::SCALE).ok_or(FixedPointError::Overflow)?;
+        div_round(numerator, other.raw, rounding).map(Self::from_raw)
+    }
+
+    /// Convert to another precision, rounding when precision is lost
+    pub fn rescale<const TARGET: u32>(self, rounding: Rounding) -> FixedResult<FixedPoint<TARGET>> {
+        let raw = if TARGET >= DECIMALS {
+            self.raw.checked_mul(10i128.pow(TARGET.abs_diff(DECIMALS))).ok_or(FixedPointError::Overflow)?
+        } else {
+            div_round(self.raw, 10i128.pow(DECIMALS.abs_diff(TARGET)), rounding)?
+        };
+        Ok(FixedPoint::from_raw(raw))
+    }
+}
+
+impl<const DECIMALS: u32> fmt::Display for FixedPoint<DECIMALS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.raw < 0 { "-" } else { "" };
+        let magnitude = self.raw.unsigned_abs();
+        let scale = Self::SCALE as u128;
+        if DECIMALS == 0 {
+            return write!(f, "{}{}", sign, magnitude);
+        }
+        write!(
+            f,
+            "{}{}.{:0width$}",
+            sign,
+            magnitude / scale,
+            magnitude % scale,
+            width = DECIMALS as usize
+        )
+    }
+}
+
+/// `numerator / denominator` rounded in the requested direction
+pub fn div_round(numerator: i128, denominator: i128, rounding: Rounding) -> FixedResult<i128> {
+    if denominator == 0 {
+        return Err(FixedPointError::DivisionByZero);
+    }
+    // Only i128::MIN / -1 overflows
+    let truncated = numerator.checked_div(denominator).ok_or(FixedPointError::Overflow)?;
+    let remainder = numerator % denominator;
+    if remainder == 0 {
+        return Ok(truncated);
+    }
+
+    // Truncation rounds toward zero; floor differs from it for negative results
+    let negative = (numerator < 0) != (denominator < 0);
+    let floor = if negative { truncated - 1 } else { truncated };
+    let round_up = match rounding {
+        Rounding::Down => false,
+        Rounding::Up => true,
+        Rounding::HalfUp => {
+            // Fractional part above the floor is |r|/|d| when positive, 1 - |r|/|d| when negative
+            let twice_remainder = remainder.unsigned_abs() * 2;
+            let divisor = denominator.unsigned_abs();
+            if negative { twice_remainder <= divisor } else { twice_remainder >= divisor }
+        }
+    };
+    if round_up {
+        floor.checked_add(1).ok_or(FixedPointError::Overflow)
+    } else {
+        Ok(floor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_mul_div_rounding() {
+        let nav = Fixed6::from_raw(1_100_000);
+        let amount = Fixed6::from_raw(1_000_000);
+
+        assert_eq!(amount.checked_div(nav, Rounding::Down).unwrap().raw(), 909_090);
+        assert_eq!(amount.checked_div(nav, Rounding::Up).unwrap().raw(), 909_091);
+        assert_eq!(amount.checked_div(nav, Rounding::HalfUp).unwrap().raw(), 909_091);
+
+        let third = Fixed6::ONE.checked_div(Fixed6::from_integer(3).unwrap(), Rounding::Down).unwrap();
+        assert_eq!(third.checked_mul(Fixed6::from_integer(3).unwrap(), Rounding::Down).unwrap().raw(), 999_999);
+    }
+
+    #[test]
+    fn test_negative_values_round_by_direction() {
+        assert_eq!(div_round(-7, 2, Rounding::Down).unwrap(), -4);
+        assert_eq!(div_round(-7, 2, Rounding::Up).unwrap(), -3);
+        assert_eq!(div_round(-7, 2, Rounding::HalfUp).unwrap(), -3);
+        assert_eq!(div_round(-8, 3, Rounding::HalfUp).unwrap(), -3);
+        assert_eq!(div_round(7, -2, Rounding::Down).unwrap(), -4);
+    }
+
+    #[test]
+    fn test_overflow_and_range_errors() {
+        let large = Fixed6::from_raw(i128::MAX / 2);
+        assert_eq!(large.checked_mul(large, Rounding::Down), Err(FixedPointError::Overflow));
+        assert_eq!(large.checked_add(large), Ok(Fixed6::from_raw(i128::MAX - 1)));
+        assert_eq!(Fixed6::ONE.checked_div(Fixed6::ZERO, Rounding::Down), Err(FixedPointError::DivisionByZero));
+        assert_eq!(Fixed6::from_raw(-1).to_u64_raw(), Err(FixedPointError::OutOfRange));
+        assert_eq!(Fixed6::from_raw(u64::MAX as i128 + 1).to_u64_raw(), Err(FixedPointError::OutOfRange));
+    }
+
+    #[test]
+    fn test_rescale_and_display() {
+        let nav = Fixed9::from_raw(1_234_567_891);
+        assert_eq!(nav.rescale::<6>(Rounding::Down).unwrap(), Fixed6::from_raw(1_234_567));
+        assert_eq!(nav.rescale::<6>(Rounding::HalfUp).unwrap(), Fixed6::from_raw(1_234_568));
+        assert_eq!(Fixed6::from_raw(1_234_567).rescale::<9>(Rounding::Down).unwrap(), Fixed9::from_raw(1_234_567_000));
+
+        assert_eq!(Fixed6::from_raw(1_100_000).to_string(), "1.100000");
+        assert_eq!(Fixed6::from_raw(-5).to_string(), "-0.000005");
+    }
+
+    proptest! {
+        #[test]
+        fn prop_rounding_directions_bracket_exact_result(numerator in any::<i64>(), denominator in any::<i64>()) {
+            prop_assume!(denominator != 0);
+            let (n, d) = (numerator as i128, denominator as i128);
+            let down = div_round(n, d, Rounding::Down).unwrap();
+            let up = div_round(n, d, Rounding::Up).unwrap();
+            let nearest = div_round(n, d, Rounding::HalfUp).unwrap();
+
+            prop_assert!(up - down <= 1);
+            prop_assert!(down <= nearest && nearest <= up);
+            // down ≤ n/d ≤ up, checked without division
+            if d > 0 {
+                prop_assert!(down * d <= n && n <= up * d);
+            } else {
+                prop_assert!(down * d >= n && n >= up * d);
+            }
+        }
+    }
+}
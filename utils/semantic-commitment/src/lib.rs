@@ -0,0 +1,35 @@
+//! # Proposal Semantic Commitments
+//!
+//! A proposal's semantic commitment binds the text voters read to the instruction data
+//! that executes if it passes. Governance records the commitment at submission and
+//! re-checks it before execution, and the LLM agent checks the same commitment when
+//! comparing the text's intent with the instructions, so both compute it here.
+
+use domain_tags::DomainTag;
+use sha2::{Digest, Sha256};
+
+/// Commitment binding a proposal's human-readable text to its instruction bytes
+///
+/// The text is length-prefixed so no split of the same bytes between text and
+/// instruction data produces the same commitment.
+pub fn semantic_commitment_hash(proposal_text: &str, instruction_data: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(DomainTag::SemanticCommitment.as_bytes());
+    hasher.update((proposal_text.len() as u64).to_be_bytes());
+    hasher.update(proposal_text.as_bytes());
+    hasher.update(instruction_data);
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boundary_between_text_and_instructions_is_committed() {
+        let commitment = semantic_commitment_hash("Set fee to 1.5%", b"\x01\x02");
+        assert_eq!(commitment, semantic_commitment_hash("Set fee to 1.5%", b"\x01\x02"));
+        assert_ne!(commitment, semantic_commitment_hash("Set fee to 1.5%\x01", b"\x02"));
+        assert_ne!(commitment, semantic_commitment_hash("Set fee to 1.5%", b"\x01\x03"));
+    }
+}